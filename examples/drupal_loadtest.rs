@@ -28,7 +28,7 @@ use rand::Rng;
 use regex::Regex;
 
 fn main() -> Result<(), SwanlingError> {
-    SwanlingAttack::initialize()?
+    let swanling_metrics = SwanlingAttack::initialize()?
         .register_taskset(
             taskset!("AnonBrowsingUser")
                 .set_weight(4)?
@@ -77,8 +77,15 @@ fn main() -> Result<(), SwanlingError> {
                         .set_name("(Auth) comment form"),
                 ),
         )
-        .execute()?
-        .print();
+        .execute();
+
+    match swanling_metrics {
+        Ok(swanling_metrics) => swanling_metrics.print(),
+        // Display the information requested with --help or --version, instead of returning
+        // it as an error.
+        Err(SwanlingError::Help { message }) => println!("{}", message),
+        Err(e) => return Err(e),
+    }
 
     Ok(())
 }
@@ -20,7 +20,7 @@
 use swanling::prelude::*;
 
 fn main() -> Result<(), SwanlingError> {
-    SwanlingAttack::initialize()?
+    let swanling_metrics = SwanlingAttack::initialize()?
         // In this example, we only create a single taskset, named "WebsiteUser".
         .register_taskset(
             taskset!("WebsiteUser")
@@ -32,8 +32,15 @@ fn main() -> Result<(), SwanlingError> {
                 .register_task(task!(website_index))
                 .register_task(task!(website_about)),
         )
-        .execute()?
-        .print();
+        .execute();
+
+    match swanling_metrics {
+        Ok(swanling_metrics) => swanling_metrics.print(),
+        // Display the information requested with --help or --version, instead of returning
+        // it as an error.
+        Err(SwanlingError::Help { message }) => println!("{}", message),
+        Err(e) => return Err(e),
+    }
 
     Ok(())
 }
@@ -46,11 +46,18 @@ fn main() -> Result<(), SwanlingError> {
         taskset = new_taskset;
     }
 
-    SwanlingAttack::initialize()?
+    let swanling_metrics = SwanlingAttack::initialize()?
         // In this example, we only create a single taskset, named "WebsiteUser".
         .register_taskset(taskset)
-        .execute()?
-        .print();
+        .execute();
+
+    match swanling_metrics {
+        Ok(swanling_metrics) => swanling_metrics.print(),
+        // Display the information requested with --help or --version, instead of returning
+        // it as an error.
+        Err(SwanlingError::Help { message }) => println!("{}", message),
+        Err(e) => return Err(e),
+    }
 
     Ok(())
 }
@@ -19,7 +19,7 @@
 use swanling::prelude::*;
 
 fn main() -> Result<(), SwanlingError> {
-    SwanlingAttack::initialize()?
+    let swanling_metrics = SwanlingAttack::initialize()?
         // We only create the "TheInternetUser" taskset.
         .register_taskset(
             taskset!("TheInternetUser")
@@ -29,8 +29,15 @@ fn main() -> Result<(), SwanlingError> {
                 .register_task(task!(website_slow))
                 .register_task(task!(website_redirect)),
         )
-        .execute()?
-        .print();
+        .execute();
+
+    match swanling_metrics {
+        Ok(swanling_metrics) => swanling_metrics.print(),
+        // Display the information requested with --help or --version, instead of returning
+        // it as an error.
+        Err(SwanlingError::Help { message }) => println!("{}", message),
+        Err(e) => return Err(e),
+    }
 
     Ok(())
 }
@@ -13,7 +13,7 @@ use crate::spanish::*;
 ///  - Anonymous English user: loads the English version of all pages
 ///  - Anonymous Spanish user: loads the Spanish version of all pages
 fn main() -> Result<(), SwanlingError> {
-    let _swanling_metrics = SwanlingAttack::initialize()?
+    let swanling_metrics = SwanlingAttack::initialize()?
         .register_taskset(
             taskset!("Anonymous English user")
                 .set_weight(40)?
@@ -81,8 +81,15 @@ fn main() -> Result<(), SwanlingError> {
                 ),
         )
         .set_default(SwanlingDefault::Host, "https://drupal-9.ddev.site/")?
-        .execute()?
-        .print();
+        .execute();
+
+    match swanling_metrics {
+        Ok(swanling_metrics) => swanling_metrics.print(),
+        // Display the information requested with --help or --version, instead of returning
+        // it as an error.
+        Err(SwanlingError::Help { message }) => println!("{}", message),
+        Err(e) => return Err(e),
+    }
 
     Ok(())
 }
@@ -0,0 +1,43 @@
+//! Retire running users and replace them with fresh users.
+//!
+//! With `--user-churn 5/min`, Swanling retires five randomly selected running users every
+//! minute while the load test runs. Retired users run their `on_stop` tasks and session
+//! functions, then restart as a fresh user with a new `uuid` and client, running their
+//! `on_start` session functions and tasks again. This models real session churn, so login
+//! and cold cache paths are exercised throughout the load test rather than only while users
+//! are starting.
+
+use std::time::Instant;
+
+/// Decides how many running users to retire, at a configured rate.
+#[derive(Debug)]
+pub(crate) struct SwanlingUserChurn {
+    /// How many users to retire per second.
+    rate: f64,
+    /// Users due to be retired, including fractions of a user carried over between checks.
+    due: f64,
+    /// Timer tracking when users were last checked.
+    timer: Instant,
+}
+impl SwanlingUserChurn {
+    pub(crate) fn new(rate: f64) -> Self {
+        SwanlingUserChurn {
+            rate,
+            due: 0.0,
+            timer: Instant::now(),
+        }
+    }
+
+    /// Returns how many users to retire since the last check. While the load test isn't
+    /// running, for example because it's paused, no users are due to be retired.
+    pub(crate) fn check(&mut self, running: bool) -> usize {
+        if running {
+            self.due += self.timer.elapsed().as_secs_f64() * self.rate;
+        }
+        self.timer = Instant::now();
+
+        let retiring = self.due.floor();
+        self.due -= retiring;
+        retiring as usize
+    }
+}
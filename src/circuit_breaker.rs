@@ -0,0 +1,109 @@
+//! Stop the load test when too many requests fail.
+//!
+//! With `--abort-on-error-rate 25%@30s`, Swanling samples how many requests were made and
+//! how many failed every second while the load test runs. When more than 25% of the
+//! requests made in the last 30 seconds failed, the load test is stopped and its metrics
+//! and html report are flagged as aborted, so an unattended load test doesn't keep
+//! overwhelming a target that's already failing.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use crate::metrics::SwanlingMetrics;
+use crate::util;
+
+/// How often to sample the request metrics, in seconds.
+const SAMPLE_INTERVAL: usize = 1;
+
+/// Stops the load test when the error rate exceeds a threshold.
+#[derive(Debug)]
+pub(crate) struct SwanlingCircuitBreaker {
+    /// The threshold as configured, for example `25%@30s`.
+    value: String,
+    /// The percentage of requests that can fail.
+    threshold: f64,
+    /// How many seconds the error rate is measured over.
+    window: usize,
+    /// When each sample was taken, and how many requests had been made and had failed.
+    samples: VecDeque<(Instant, usize, usize)>,
+    /// Timer tracking when to take the next sample.
+    timer: Instant,
+}
+impl SwanlingCircuitBreaker {
+    pub(crate) fn new(value: &str, threshold: f64, window: usize) -> Self {
+        SwanlingCircuitBreaker {
+            value: value.to_string(),
+            threshold,
+            window,
+            samples: VecDeque::new(),
+            timer: Instant::now(),
+        }
+    }
+
+    /// Forget earlier samples when starting a new load test.
+    pub(crate) fn reset(&mut self) {
+        self.samples.clear();
+        self.timer = Instant::now();
+    }
+
+    /// The threshold, as the option it was configured with.
+    pub(crate) fn threshold(&self) -> String {
+        format!("--abort-on-error-rate {}", self.value)
+    }
+
+    /// Regularly sample the request metrics, returning why the load test should be
+    /// aborted if the error rate over the whole window exceeded the threshold.
+    pub(crate) fn check(&mut self, metrics: &SwanlingMetrics) -> Option<String> {
+        if !self.samples.is_empty() && !util::timer_expired(self.timer, SAMPLE_INTERVAL) {
+            return None;
+        }
+        self.timer = Instant::now();
+
+        let (requests, fails) =
+            metrics
+                .requests
+                .values()
+                .fold((0, 0), |(requests, fails), request| {
+                    (
+                        requests + request.success_count + request.fail_count,
+                        fails + request.fail_count,
+                    )
+                });
+
+        // Metrics are reset once all users have started, so start sampling again.
+        if let Some((_, last_requests, last_fails)) = self.samples.back() {
+            if requests < *last_requests || fails < *last_fails {
+                self.samples.clear();
+            }
+        }
+        self.samples.push_back((self.timer, requests, fails));
+
+        // Only keep the newest sample taken at least a window ago, which the window is
+        // measured from.
+        while self.samples.len() > 1 && self.samples[1].0.elapsed().as_secs() >= self.window as u64
+        {
+            self.samples.pop_front();
+        }
+        let (started, first_requests, first_fails) = self.samples[0];
+        if started.elapsed().as_secs() < self.window as u64 {
+            return None;
+        }
+
+        let requests = requests - first_requests;
+        if requests == 0 {
+            return None;
+        }
+        let error_rate = (fails - first_fails) as f64 / requests as f64 * 100.0;
+        if error_rate > self.threshold {
+            Some(format!(
+                "{:.1}% of {} requests failed in the last {} seconds, exceeding {}",
+                error_rate,
+                requests,
+                self.window,
+                self.threshold()
+            ))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! Build a [`SwanlingConfiguration`](../struct.SwanlingConfiguration.html) in code.
+//!
+//! Applications embedding Swanling, and tests, often don't have command line arguments to
+//! parse. Instead of building a list of strings for
+//! [`parse_args_default`](https://docs.rs/gumdrop/*/gumdrop/trait.Options.html#method.parse_args_default),
+//! start with [`SwanlingConfiguration::builder`](../struct.SwanlingConfiguration.html#method.builder)
+//! and set each option with a typed setter named after it. Options that aren't set keep their
+//! default value, exactly as if they hadn't been passed on the command line.
+//!
+//! Setters don't validate their values: as with command line options, the configuration is
+//! validated when the load test starts.
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//! use swanling::SwanlingConfiguration;
+//!
+//! fn main() -> Result<(), SwanlingError> {
+//!     let configuration = SwanlingConfiguration::builder()
+//!         .host("http://local.dev/")
+//!         .users(10)
+//!         .hatch_rate("2")
+//!         .run_time("5m")
+//!         .label("git_sha=1a2b3c4")
+//!         .build();
+//!
+//!     let _swanling_attack = SwanlingAttack::initialize_with_config(configuration)?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use crate::logger::SwanlingLogFormat;
+use crate::metrics::{SwanlingCoordinatedOmissionMitigation, SwanlingMetricsFormat};
+use crate::SwanlingConfiguration;
+
+// Define a setter for each option, converting the value to the type of the option.
+macro_rules! setters {
+    ($($(#[$attr:meta])* $kind:ident $name:ident: $type:ty,)*) => {
+        $(setter!($(#[$attr])* $kind $name: $type);)*
+    };
+}
+
+macro_rules! setter {
+    // Options set to a value of the same type.
+    ($(#[$attr:meta])* value $name:ident: $type:ty) => {
+        $(#[$attr])*
+        pub fn $name(mut self, value: $type) -> Self {
+            self.configuration.$name = value;
+            self
+        }
+    };
+    // Options that are unset unless given a value.
+    ($(#[$attr:meta])* optional $name:ident: $type:ty) => {
+        $(#[$attr])*
+        pub fn $name(mut self, value: $type) -> Self {
+            self.configuration.$name = Some(value);
+            self
+        }
+    };
+    // Options set to a string.
+    ($(#[$attr:meta])* string $name:ident: $type:ty) => {
+        $(#[$attr])*
+        pub fn $name(mut self, value: $type) -> Self {
+            self.configuration.$name = value.to_string();
+            self
+        }
+    };
+    // Options that are unset unless given a string.
+    ($(#[$attr:meta])* optional_string $name:ident: $type:ty) => {
+        $(#[$attr])*
+        pub fn $name(mut self, value: $type) -> Self {
+            self.configuration.$name = Some(value.to_string());
+            self
+        }
+    };
+    // Options that can be set more than once, each call adding a value.
+    ($(#[$attr:meta])* repeatable $name:ident: $type:ty) => {
+        $(#[$attr])*
+        pub fn $name(mut self, value: $type) -> Self {
+            self.configuration.$name.push(value.to_string());
+            self
+        }
+    };
+}
+
+/// Builds a [`SwanlingConfiguration`](../struct.SwanlingConfiguration.html) with typed
+/// setters, returned by
+/// [`SwanlingConfiguration::builder`](../struct.SwanlingConfiguration.html#method.builder).
+#[derive(Clone, Debug, Default)]
+pub struct SwanlingConfigurationBuilder {
+    /// The configuration being built.
+    configuration: SwanlingConfiguration,
+}
+impl SwanlingConfigurationBuilder {
+    /// Returns the configuration, to pass to
+    /// [`SwanlingAttack::initialize_with_config`](../struct.SwanlingAttack.html#method.initialize_with_config).
+    pub fn build(self) -> SwanlingConfiguration {
+        self.configuration
+    }
+
+    setters! {
+        /// Lists all tasks and exits
+        value list: bool,
+        /// Sets --list format (text, json)
+        string format: &str,
+        /// Validates the load test, prints its plan and exits
+        value dry_run: bool,
+        /// Defines host to load test (ie http://10.21.32.33), or comma-separated hosts to spread users over (ie http://a,http://b=2)
+        string host: &str,
+        /// Sets concurrent users (default: number of CPUs)
+        optional users: usize,
+        /// Sets per-second user hatch rate, or a ramp like 1..20/5m (default: 1)
+        optional_string hatch_rate: &str,
+        /// Launches users in batches of this size (default: 1)
+        optional hatch_batch: usize,
+        /// Randomly shifts when each batch launches (ie 200ms)
+        string hatch_jitter: &str,
+        /// Stops after (30s, 20m, 3h, 1h30m, etc)
+        string run_time: &str,
+        /// Aborts users still running this long after stopping (ie 60s)
+        string shutdown_timeout: &str,
+        /// Enables Swanling log file and sets name
+        string swanling_log: &str,
+        /// Sets Swanling log level (-g, -gg, etc)
+        value log_level: u8,
+        /// Sets Swanling verbosity (-v, -vv, etc)
+        value verbose: u8,
+        /// How often to optionally print running metrics
+        optional running_metrics: usize,
+        /// Sets running metrics format (table, compact, json)
+        optional running_metrics_format: SwanlingMetricsFormat,
+        /// Only displays the final metrics summary
+        value summary_only: bool,
+        /// Sets final metrics summary format (table, compact, json)
+        optional summary_format: SwanlingMetricsFormat,
+        /// Sets comma-separated response time percentiles to report
+        string percentiles: &str,
+        /// Displays a live dashboard instead of running metrics
+        value tui: bool,
+        /// Doesn't reset metrics after all users have started
+        value no_reset_metrics: bool,
+        /// Doesn't track metrics
+        value no_metrics: bool,
+        /// Doesn't track task metrics
+        value no_task_metrics: bool,
+        /// Doesn't display an error summary
+        value no_error_summary: bool,
+        /// Summarizes errors per value of a request tag (repeatable)
+        repeatable error_tag: &str,
+        /// Sets maximum unique request names tracked
+        value max_request_names: usize,
+        /// How often users send metrics to the parent (default: 100ms)
+        string metrics_flush: &str,
+        /// Create an html-formatted report
+        string report_file: &str,
+        /// How often to write report snapshots while running (ie 5m)
+        string report_interval: &str,
+        /// Sets comma-separated latency histogram buckets in the report (in ms)
+        string histogram_buckets: &str,
+        /// Adds metadata to reports, ie git_sha=1a2b3c4
+        repeatable label: &str,
+        /// Identifies this run in logs, metrics and reports (default: random UUID)
+        string run_id: &str,
+        /// Sends the run ID in an X-Load-Test-Run header with each request
+        value run_header: bool,
+        /// Sends X-Swanling-Task and X-Swanling-User headers with each request
+        value task_headers: bool,
+        /// Uploads metrics and report to object storage, ie s3://bucket/prefix
+        string upload_url: &str,
+        /// How often to upload metrics snapshots (default: 60)
+        optional upload_interval: usize,
+        /// Posts load test events and results to a webhook
+        string notify_url: &str,
+        /// Merges JSON metrics files into one report and exits
+        value merge_reports: bool,
+        /// JSON metrics files to merge with --merge-reports
+        repeatable reports: &str,
+        /// Sets request log file name
+        string request_log: &str,
+        /// Sets request log format (csv, json, ndjson, pretty-json, raw)
+        optional request_format: SwanlingLogFormat,
+        /// Records raw latency of each request to a csv or parquet file
+        string record_raw_latencies: &str,
+        /// Sets task log file name
+        string task_log: &str,
+        /// Sets task log format (csv, json, ndjson, pretty-json, raw)
+        optional task_format: SwanlingLogFormat,
+        /// Sets error log file name
+        string error_log: &str,
+        /// Sets error log format (csv, json, ndjson, pretty-json, raw)
+        optional error_format: SwanlingLogFormat,
+        /// Aggregates identical errors in the error log for this long (ie 1s)
+        string error_log_window: &str,
+        /// Sets debug log file name
+        string debug_log: &str,
+        /// Sets debug log format (csv, json, ndjson, pretty-json, raw)
+        optional debug_format: SwanlingLogFormat,
+        /// Do not include the response body in the debug log
+        value no_debug_body: bool,
+        /// Truncates response bodies in the debug log to this many bytes
+        value debug_body_max_bytes: usize,
+        /// Redacts a regex from headers and bodies in the debug log (repeatable)
+        repeatable debug_redact: &str,
+        /// Runs one user through one iteration of each task set, printing each response
+        value debug_single_user: bool,
+        /// Tracks additional status code metrics
+        value status_codes: bool,
+        /// Doesn't enable telnet Controller
+        value no_telnet: bool,
+        /// Sets telnet Controller host (default: 0.0.0.0)
+        string telnet_host: &str,
+        /// Sets telnet Controller TCP port (default: 5116)
+        value telnet_port: u16,
+        /// Doesn't enable WebSocket Controller
+        value no_websocket: bool,
+        /// Sets WebSocket Controller host (default: 0.0.0.0)
+        string websocket_host: &str,
+        /// Sets WebSocket Controller TCP port (default: 5117)
+        value websocket_port: u16,
+        /// Doesn't automatically start load test
+        value no_autostart: bool,
+        /// Waits to start load test until (ie 2024-06-01T02:00:00Z)
+        string start_at: &str,
+        /// Sets coordinated omission mitigation strategy
+        optional co_mitigation: SwanlingCoordinatedOmissionMitigation,
+        /// Sets maximum requests per second
+        value throttle_requests: usize,
+        /// Changes maximum requests per second over time (ie 100:5m,500:10m,1000)
+        string throttle_plan: &str,
+        /// Sets number of tokio worker threads (default: number of CPUs)
+        optional runtime_threads: usize,
+        /// Seeds each user's random number generator
+        optional seed: u64,
+        /// Requests uncompressed responses
+        value no_gzip: bool,
+        /// Sets Accept-Encoding header sent with requests
+        string accept_encoding: &str,
+        /// Picks each user's user agent from a file
+        string user_agent_file: &str,
+        /// Delays a percentage of requests (ie 50ms@1%)
+        string inject_latency: &str,
+        /// Aborts a percentage of requests (ie 1%)
+        string inject_abort: &str,
+        /// Replays requests from a request log
+        string replay_log: &str,
+        /// Sets replay speed factor (default: 1.0)
+        optional_string replay_speed: &str,
+        /// Resolves hostnames with a DNS server (ie 10.0.0.53:53)
+        string dns_server: &str,
+        /// Resolves hostnames for every request
+        value no_dns_cache: bool,
+        /// Resolves hostnames again every VALUE requests per user
+        value dns_refresh: usize,
+        /// Requests a path from each host before starting, ie /healthz
+        string healthcheck: &str,
+        /// How often to check hosts while running (default: 10)
+        optional healthcheck_interval: usize,
+        /// Pauses the load test while all hosts fail the health check
+        value healthcheck_pause: bool,
+        /// Stops when too many requests fail in a window (ie 25%@30s)
+        string abort_on_error_rate: &str,
+        /// Warns when p99 response time stays too high (ie 500ms@30s)
+        string latency_alert: &str,
+        /// Retires and replaces running users (ie 5/min)
+        string user_churn: &str,
+        /// Sets local IP address to send requests from (repeatable)
+        repeatable bind_address: &str,
+        /// Sets a value tasks can read with user.config_value (ie tenant=42)
+        repeatable set: &str,
+        /// Saves users' cookies when stopping and restores them when starting
+        string save_sessions: &str,
+        /// Follows base_url redirect with subsequent requests
+        value sticky_follow: bool,
+        /// Pins each user to a backend with a cookie
+        string sticky_cookie: &str,
+        /// Pins each user to a backend with a header
+        string sticky_header: &str,
+        /// Sets comma-separated backends to pin users to
+        string sticky_backends: &str,
+        /// Counts backends serving requests from a response header
+        string backend_header: &str,
+        /// Enables distributed load test Manager mode
+        value manager: bool,
+        /// Sets number of Workers to expect
+        optional expect_workers: u16,
+        /// Tells Manager to ignore load test checksum
+        value no_hash_check: bool,
+        /// Assigns users to Workers connecting after the load test started
+        value hot_add_workers: bool,
+        /// Runs a Worker in the Manager process
+        value manager_also_worker: bool,
+        /// Sets host Manager listens on (default: 0.0.0.0)
+        string manager_bind_host: &str,
+        /// Sets port Manager listens on (default: 5115)
+        value manager_bind_port: u16,
+        /// Enables distributed load test Worker mode
+        value worker: bool,
+        /// Sets host Worker connects to (default: 127.0.0.1)
+        string manager_host: &str,
+        /// Sets port Worker connects to (default: 5115)
+        value manager_port: u16,
+        /// Sets index of this process in a fleet without a Manager
+        optional fleet_index: usize,
+        /// Sets number of processes in a fleet without a Manager
+        optional fleet_size: usize,
+    }
+}
@@ -3,16 +3,19 @@
 //! By default, Swanling launches both a telnet Controller and a WebSocket Controller, allowing
 //! real-time control of the running load test.
 
-use crate::metrics::SwanlingMetrics;
+use crate::metrics::{SwanlingAnnotation, SwanlingMetrics};
 use crate::util;
 use crate::{
     AttackPhase, SwanlingAttack, SwanlingAttackRunState, SwanlingConfiguration, SwanlingError,
+    SwanlingProgress,
 };
 
 use async_trait::async_trait;
+use chrono::Utc;
 use futures::{SinkExt, StreamExt};
 use regex::{Regex, RegexSet};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::io;
 use std::str;
 use std::str::FromStr;
@@ -96,6 +99,62 @@ pub enum SwanlingControllerCommand {
     ///
     /// This can be configured when Swanling is idle as well as when a Swanling load test is running.
     RunTime,
+    /// Configure any [`SwanlingConfiguration`](../struct.SwanlingConfiguration.html) option,
+    /// named like its field or its command line flag. Flags are set to `true` or `false`.
+    /// Options that only take effect when Swanling launches, such as the Controller ports,
+    /// can't be configured.
+    ///
+    /// # Example
+    /// Tells Swanling to throttle the next load test to 5 requests per second.
+    /// ```notest
+    /// set throttle_requests 5
+    /// ```
+    ///
+    /// Swanling must be idle to process this command.
+    Set,
+    /// Add a timestamped note to the running load test, which is included in the html
+    /// report and in json-formatted metrics.
+    ///
+    /// # Example
+    /// Marks when a new version of the load tested application was deployed.
+    /// ```notest
+    /// annotate deployed version 1.2.0
+    /// ```
+    ///
+    /// Swanling must be running to process this command.
+    Annotate,
+    /// Generate the html report from the current
+    /// [`SwanlingMetric`](../metrics/struct.SwanlingMetrics.html)s, without waiting for the load
+    /// test to finish.
+    ///
+    /// # Example
+    /// Returns the html report.
+    /// ```notest
+    /// report
+    /// ```
+    ///
+    /// # Example
+    /// Writes the html report to a file, relative to where Swanling is running.
+    /// ```notest
+    /// report reports/partial.html
+    /// ```
+    ///
+    /// Swanling must have started a load test with metrics enabled to process this command.
+    Report,
+    /// Drain a Worker from a running Regatta, for example to do maintenance on its server.
+    /// The Worker's [`SwanlingUser`](../swanling/struct.SwanlingUser.html)s stop gracefully
+    /// and it sends its final metrics to the Manager before exiting, then its users are
+    /// distributed among the remaining Workers, which launch them at the hatch rate.
+    ///
+    /// # Example
+    /// Drains the second Worker that connected to the Manager.
+    /// ```notest
+    /// drain 2
+    /// ```
+    ///
+    /// Swanling must be running as a Manager with at least one other Worker to process this
+    /// command.
+    Drain,
     /// Display the current [`SwanlingConfiguration`](../struct.SwanlingConfiguration.html)s.
     ///
     /// # Example
@@ -134,6 +193,83 @@ pub enum SwanlingControllerCommand {
     ///
     /// This command can be run at any time.
     MetricsJson,
+    /// Display where the load test is in its lifecycle: its
+    /// [`AttackPhase`](../enum.AttackPhase.html), how long it has been running and will
+    /// run, and how many [`SwanlingUser`](../swanling/struct.SwanlingUser.html)s have launched.
+    ///
+    /// # Example
+    /// Returns the current Swanling progress.
+    /// ```notest
+    /// status
+    /// ```
+    ///
+    /// This command can be run at any time.
+    Status,
+    /// Display where the load test is in its lifecycle in json format.
+    ///
+    /// # Example
+    /// Returns the current Swanling progress in json format.
+    /// ```notest
+    /// statusjson
+    /// ```
+    ///
+    /// This command can be run at any time.
+    StatusJson,
+    /// Display the Workers connected to a Regatta Manager: how many
+    /// [`SwanlingUser`](../swanling/struct.SwanlingUser.html)s each has launched, when each
+    /// last sent its metrics to the Manager, and how many seconds ago that was.
+    ///
+    /// # Example
+    /// Returns the status of each connected Worker.
+    /// ```notest
+    /// workers
+    /// ```
+    ///
+    /// Swanling must be running as a Manager to process this command.
+    Workers,
+    /// Display the Workers connected to a Regatta Manager in json format.
+    ///
+    /// # Example
+    /// Returns the status of each connected Worker in json format.
+    /// ```notest
+    /// workersjson
+    /// ```
+    ///
+    /// Swanling must be running as a Manager to process this command.
+    WorkersJson,
+    /// Zero all metrics collected so far, starting a new measurement window.
+    ///
+    /// # Example
+    /// Resets the metrics of the running load test.
+    /// ```notest
+    /// reset
+    /// ```
+    ///
+    /// Swanling must be running to process this command.
+    Reset,
+    /// Pause a running load test. [`SwanlingUser`](../swanling/struct.SwanlingUser.html)s
+    /// finish the task iteration they are running, then wait without starting another,
+    /// keeping their clients and connections. Time spent paused isn't included in the
+    /// duration of the load test, and so doesn't lower the per-second metrics.
+    ///
+    /// # Example
+    /// Pauses the running load test.
+    /// ```notest
+    /// pause
+    /// ```
+    ///
+    /// Swanling must be running to process this command.
+    Pause,
+    /// Resume a paused load test.
+    ///
+    /// # Example
+    /// Resumes the paused load test.
+    /// ```notest
+    /// resume
+    /// ```
+    ///
+    /// Swanling must be paused to process this command.
+    Resume,
     /// Displays a list of all commands supported by the Controller.
     ///
     /// # Example
@@ -154,7 +290,7 @@ pub enum SwanlingControllerCommand {
     ///
     /// This command can be run at any time.
     Exit,
-    /// Start an idle test.
+    /// Start an idle test, optionally configuring options first.
     ///
     /// # Example
     /// Starts an idle load test.
@@ -162,6 +298,25 @@ pub enum SwanlingControllerCommand {
     /// start
     /// ```
     ///
+    /// Options can be configured as with the [`Set`](#variant.Set) command, and are only
+    /// configured if they are all valid and the load test starts.
+    ///
+    /// # Example
+    /// Starts an idle load test with 200 users, launched 5 per second, for 10 minutes.
+    /// ```notest
+    /// start users=200 hatch_rate=5 run_time=10m
+    /// ```
+    ///
+    /// If `start_at` is in the future, Swanling remains idle until that time, so multiple
+    /// independent load generators can start at the same time. A load test waiting to
+    /// start can be canceled with [`Stop`](#variant.Stop).
+    ///
+    /// # Example
+    /// Starts an idle load test at 2am UTC on June 1st, 2024.
+    /// ```notest
+    /// start start_at=2024-06-01T02:00:00Z
+    /// ```
+    ///
     /// Swanling must be idle to process this command.
     Start,
     /// Stop a running test, putting it into an idle state.
@@ -204,6 +359,53 @@ pub(crate) enum SwanlingControllerResponseMessage {
     Config(Box<SwanlingConfiguration>),
     /// A response containing current load test metrics.
     Metrics(Box<SwanlingMetrics>),
+    /// A response containing an html report.
+    Report(String),
+    /// A response containing the progress of the load test.
+    Status(SwanlingProgress),
+    /// A response containing the status of each Worker connected to the Manager.
+    #[cfg_attr(not(feature = "gaggle"), allow(dead_code))]
+    Workers(Vec<SwanlingWorkerStatus>),
+}
+
+/// The status of a Worker connected to a Regatta Manager, returned by the `workers`
+/// Controller command.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SwanlingWorkerStatus {
+    /// The Worker's id, assigned in the order Workers connected, starting at 1.
+    pub worker_id: usize,
+    /// How many [`SwanlingUser`](../swanling/struct.SwanlingUser.html)s the Manager assigned
+    /// to the Worker.
+    pub assigned_users: usize,
+    /// How many of its [`SwanlingUser`](../swanling/struct.SwanlingUser.html)s the Worker has
+    /// launched.
+    pub users: usize,
+    /// When the Worker last sent its metrics to the Manager, as a unix timestamp, if it has.
+    pub last_flush: Option<i64>,
+    /// How many seconds ago the Worker last sent its metrics to the Manager. A Worker
+    /// sends its metrics every time its users finish a request, so a growing lag means the
+    /// Worker is stalled or can't reach the Manager.
+    pub lag: Option<u64>,
+    /// Whether the Worker is being drained with the `drain` Controller command.
+    pub draining: bool,
+}
+/// Display the status of a Worker on a single line.
+impl fmt::Display for SwanlingWorkerStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "worker {}: {}/{} users launched, ",
+            self.worker_id, self.users, self.assigned_users
+        )?;
+        match self.lag {
+            Some(lag) => write!(f, "last metrics flush {}s ago", lag)?,
+            None => write!(f, "no metrics flushed yet")?,
+        }
+        if self.draining {
+            write!(f, ", draining")?;
+        }
+        Ok(())
+    }
 }
 
 /// The request that's passed from the controller to the parent thread.
@@ -226,6 +428,88 @@ pub(crate) struct SwanlingControllerResponse {
     pub response: SwanlingControllerResponseMessage,
 }
 
+/// Requests from a [`SwanlingAttackHandle`](./struct.SwanlingAttackHandle.html) identify
+/// themselves to the parent process as this Controller client.
+const HANDLE_CLIENT_ID: u32 = 0;
+
+/// A handle controlling a [`SwanlingAttack`](../struct.SwanlingAttack.html) from the
+/// application embedding it, returned by
+/// [`SwanlingAttack::execute_with_handle`](../struct.SwanlingAttack.html#method.execute_with_handle).
+///
+/// Requests are processed by the parent process exactly like the commands of the telnet and
+/// WebSocket Controllers. Once the load test has finished, requests are ignored. The handle
+/// can be cloned to control the load test from multiple tasks.
+#[derive(Clone, Debug)]
+pub struct SwanlingAttackHandle {
+    /// Sends requests to the parent process over the Controller channel.
+    controller_request_tx: flume::Sender<SwanlingControllerRequest>,
+}
+impl SwanlingAttackHandle {
+    pub(crate) fn new(controller_request_tx: flume::Sender<SwanlingControllerRequest>) -> Self {
+        SwanlingAttackHandle {
+            controller_request_tx,
+        }
+    }
+
+    /// Stop the starting or running load test, like the `stop` Controller command. Swanling
+    /// then remains idle until it's shut down. Returns `false` if no load test was starting
+    /// or running.
+    pub async fn stop(&self) -> bool {
+        matches!(
+            self.request(SwanlingControllerCommand::Stop).await,
+            Some(SwanlingControllerResponseMessage::Bool(true))
+        )
+    }
+
+    /// Stop any running load test and shut down, like the `shutdown` Controller command,
+    /// ending the load test. Returns `false` if the load test had already finished.
+    pub async fn shutdown(&self) -> bool {
+        matches!(
+            self.request(SwanlingControllerCommand::Shutdown).await,
+            Some(SwanlingControllerResponseMessage::Bool(true))
+        )
+    }
+
+    /// Get a copy of the current [`SwanlingMetrics`](../metrics/struct.SwanlingMetrics.html),
+    /// like the `metrics` Controller command. Returns `None` if the load test has finished.
+    pub async fn metrics_snapshot(&self) -> Option<SwanlingMetrics> {
+        match self.request(SwanlingControllerCommand::Metrics).await {
+            Some(SwanlingControllerResponseMessage::Metrics(metrics)) => Some(*metrics),
+            _ => None,
+        }
+    }
+
+    /// Get where the load test is in its lifecycle, like the `status` Controller command.
+    /// Returns `None` if the load test has finished.
+    pub async fn status(&self) -> Option<SwanlingProgress> {
+        match self.request(SwanlingControllerCommand::Status).await {
+            Some(SwanlingControllerResponseMessage::Status(progress)) => Some(progress),
+            _ => None,
+        }
+    }
+
+    // Send a request to the parent process, returning its response, or None if the load
+    // test has finished.
+    async fn request(
+        &self,
+        command: SwanlingControllerCommand,
+    ) -> Option<SwanlingControllerResponseMessage> {
+        let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+        self.controller_request_tx
+            .send_async(SwanlingControllerRequest {
+                response_channel: Some(response_tx),
+                client_id: HANDLE_CLIENT_ID,
+                request: SwanlingControllerRequestMessage {
+                    command,
+                    value: None,
+                },
+            })
+            .await
+            .ok()?;
+        response_rx.await.ok().map(|response| response.response)
+    }
+}
+
 /// This structure defines the required json format of any request sent to the WebSocket
 /// Controller.
 ///
@@ -233,12 +517,19 @@ pub(crate) struct SwanlingControllerResponse {
 /// ```json
 /// {
 ///     "request": String,
+///     "id": Value,
 /// }
 ///
 /// ```
 ///
 /// The request "String" value must be a valid
-/// [`SwanlingControllerCommand`](./enum.SwanlingControllerCommand.html).
+/// [`SwanlingControllerCommand`](./enum.SwanlingControllerCommand.html). The "id" is
+/// optional, and can be any json value: when set, it is returned in the response so
+/// clients can tell which request a response belongs to.
+///
+/// Multiple requests can be batched in a single message by sending them as a json array.
+/// They are processed in order, and their responses are returned together as a json array
+/// in a single message.
 ///
 /// # Example
 /// The following request will shut down the load test:
@@ -248,12 +539,34 @@ pub(crate) struct SwanlingControllerResponse {
 /// }
 /// ```
 ///
+/// The following requests will configure the number of users and then start the load
+/// test:
+/// ```json
+/// [
+///     { "request": "users 10", "id": 1 },
+///     { "request": "start", "id": 2 }
+/// ]
+/// ```
+///
 /// Responses will be formatted as defined in
 /// [SwanlingControllerWebSocketResponse](./struct.SwanlingControllerWebSocketResponse.html).
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SwanlingControllerWebSocketRequest {
     /// A valid command string.
     pub request: String,
+    /// An optional identifier returned in the response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<serde_json::Value>,
+}
+
+/// A WebSocket Controller message contains a single request, or a batch of requests.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SwanlingControllerWebSocketRequests {
+    /// A single request, answered with a single response.
+    Single(SwanlingControllerWebSocketRequest),
+    /// Multiple requests, answered with an array of responses.
+    Batch(Vec<SwanlingControllerWebSocketRequest>),
 }
 
 /// This structure defines the json format of any response returned from the WebSocket
@@ -264,9 +577,12 @@ pub struct SwanlingControllerWebSocketRequest {
 /// {
 ///     "response": String,
 ///     "success": bool,
+///     "id": Value,
 /// }
 /// ```
 ///
+/// The "id" is only included if it was set in the request.
+///
 /// # Example
 /// The following response will be returned when a request is made to shut down the
 /// load test:
@@ -285,6 +601,21 @@ pub struct SwanlingControllerWebSocketResponse {
     pub response: String,
     /// Whether the request was successful or not.
     pub success: bool,
+    /// The identifier of the request, if set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<serde_json::Value>,
+}
+
+/// Responses to a WebSocket Controller message, sent together once all of its requests are
+/// processed.
+#[derive(Debug, Default)]
+struct SwanlingControllerWebSocketReply {
+    /// The identifier of the request being processed.
+    id: Option<serde_json::Value>,
+    /// A response for each request processed.
+    responses: Vec<SwanlingControllerWebSocketResponse>,
+    /// Why the connection is closing, if it is.
+    close: Option<&'static str>,
 }
 
 /// Return type to indicate whether or not to exit the Controller thread.
@@ -297,12 +628,6 @@ type SwanlingControllerTelnetMessage = [u8; 1024];
 type SwanlingControllerWebSocketMessage =
     std::result::Result<tungstenite::Message, tungstenite::Error>;
 
-/// Simplify the SwanlingControllerExecuteCommand trait definition for WebSockets.
-type SwanlingControllerWebSocketSender = futures::stream::SplitSink<
-    tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>,
-    tungstenite::Message,
->;
-
 /// This state object is created in the main Controller thread and then passed to the specific
 /// per-client thread.
 pub(crate) struct SwanlingControllerState {
@@ -409,31 +734,83 @@ impl SwanlingControllerState {
                         }
                     };
 
-                    // Extract the command string in a protocol-specific way.
-                    if let Ok(command_string) = self.get_command_string(data).await {
+                    // Extract the requests in a protocol-specific way.
+                    let mut reply = SwanlingControllerWebSocketReply::default();
+                    let (requests, batch) =
+                        match self.get_command_string(data).await.map(|r| {
+                            serde_json::from_str::<SwanlingControllerWebSocketRequests>(&r)
+                        }) {
+                            Ok(Ok(SwanlingControllerWebSocketRequests::Single(request))) => {
+                                (vec![request], false)
+                            }
+                            Ok(Ok(SwanlingControllerWebSocketRequests::Batch(requests))) => {
+                                (requests, true)
+                            }
+                            _ => {
+                                self.write_to_socket(
+                                    &mut reply,
+                                    Err("unable to parse json, see Swanling README.md".to_string()),
+                                )
+                                .await;
+                                (Vec::new(), false)
+                            }
+                        };
+
+                    // Process the requests in order, until one exits the Controller.
+                    let mut exit_controller = false;
+                    for request in requests {
+                        reply.id = request.id;
                         // Extract the command and value in a generic way.
-                        if let Ok(request_message) = self.get_match(&command_string.trim()).await {
-                            if self.execute_command(&mut ws_sender, request_message).await {
+                        if let Ok(request_message) = self.get_match(request.request.trim()).await {
+                            if self.execute_command(&mut reply, request_message).await {
                                 // If execute_command() returns true, it's time to exit.
-                                info!(
-                                    "Telnet client [{}] disconnected from {}",
-                                    self.thread_id, self.peer_address
-                                );
+                                exit_controller = true;
                                 break;
                             }
                         } else {
                             self.write_to_socket(
-                                &mut ws_sender,
+                                &mut reply,
                                 Err("unrecognized command, see Swanling README.md".to_string()),
                             )
                             .await;
                         }
+                    }
+
+                    // Send all responses in a single message.
+                    let json = if batch {
+                        serde_json::to_string(&reply.responses)
                     } else {
-                        self.write_to_socket(
-                            &mut ws_sender,
-                            Err("unable to parse json, see Swanling README.md".to_string()),
-                        )
-                        .await;
+                        serde_json::to_string(&reply.responses.first())
+                    };
+                    match json {
+                        Ok(json) => {
+                            if let Err(e) = ws_sender.send(Message::Text(json)).await {
+                                info!("failed to write data to websocket: {}", e);
+                            }
+                        }
+                        Err(e) => warn!("failed to json encode response: {}", e),
+                    }
+
+                    // If exiting, notify the WebSocket client that this connection is closing.
+                    if let Some(reason) = reply.close {
+                        if ws_sender
+                            .send(Message::Close(Some(tungstenite::protocol::CloseFrame {
+                                code: tungstenite::protocol::frame::coding::CloseCode::Normal,
+                                reason: std::borrow::Cow::Borrowed(reason),
+                            })))
+                            .await
+                            .is_err()
+                        {
+                            warn!("failed to write data to stream");
+                        }
+                    }
+
+                    if exit_controller {
+                        info!(
+                            "Telnet client [{}] disconnected from {}",
+                            self.thread_id, self.peer_address
+                        );
+                        break;
                     }
                 }
             }
@@ -457,9 +834,22 @@ impl SwanlingControllerState {
                 value: None,
             })
         } else if matches.matched(SwanlingControllerCommand::Start as usize) {
+            // Any options are separated by spaces, and validated by the parent process.
+            let options = command_string
+                .split_whitespace()
+                .skip(1)
+                .map(|option| match option.split_once('=') {
+                    Some((option, value)) => format!("{}={}", option.to_lowercase(), value),
+                    None => option.to_string(),
+                })
+                .collect::<Vec<String>>();
             Ok(SwanlingControllerRequestMessage {
                 command: SwanlingControllerCommand::Start,
-                value: None,
+                value: if options.is_empty() {
+                    None
+                } else {
+                    Some(options.join(" "))
+                },
             })
         } else if matches.matched(SwanlingControllerCommand::Stop as usize) {
             Ok(SwanlingControllerRequestMessage {
@@ -491,6 +881,41 @@ impl SwanlingControllerState {
                 command: SwanlingControllerCommand::MetricsJson,
                 value: None,
             })
+        } else if matches.matched(SwanlingControllerCommand::Status as usize) {
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::Status,
+                value: None,
+            })
+        } else if matches.matched(SwanlingControllerCommand::StatusJson as usize) {
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::StatusJson,
+                value: None,
+            })
+        } else if matches.matched(SwanlingControllerCommand::Workers as usize) {
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::Workers,
+                value: None,
+            })
+        } else if matches.matched(SwanlingControllerCommand::WorkersJson as usize) {
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::WorkersJson,
+                value: None,
+            })
+        } else if matches.matched(SwanlingControllerCommand::Reset as usize) {
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::Reset,
+                value: None,
+            })
+        } else if matches.matched(SwanlingControllerCommand::Pause as usize) {
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::Pause,
+                value: None,
+            })
+        } else if matches.matched(SwanlingControllerCommand::Resume as usize) {
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::Resume,
+                value: None,
+            })
         } else if matches.matched(SwanlingControllerCommand::Host as usize) {
             // Perform a second regex to capture the host value.
             let caps = self.captures[SwanlingControllerCommand::Host as usize]
@@ -539,6 +964,47 @@ impl SwanlingControllerState {
                 command: SwanlingControllerCommand::RunTime,
                 value: Some(run_time.to_string()),
             })
+        } else if matches.matched(SwanlingControllerCommand::Set as usize) {
+            // Perform a second regex to capture the option and value, which are validated
+            // by the parent process.
+            let caps = self.captures[SwanlingControllerCommand::Set as usize]
+                .captures(command_string)
+                .unwrap();
+            let option = caps.get(2).map_or("", |m| m.as_str());
+            let value = caps.get(3).map_or("", |m| m.as_str());
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::Set,
+                value: Some(format!("{} {}", option.to_lowercase(), value)),
+            })
+        } else if matches.matched(SwanlingControllerCommand::Annotate as usize) {
+            // Perform a second regex to capture the note.
+            let caps = self.captures[SwanlingControllerCommand::Annotate as usize]
+                .captures(command_string)
+                .unwrap();
+            let text = caps.get(2).map_or("", |m| m.as_str());
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::Annotate,
+                value: Some(text.to_string()),
+            })
+        } else if matches.matched(SwanlingControllerCommand::Report as usize) {
+            // Perform a second regex to capture the optional path to write the report to.
+            let caps = self.captures[SwanlingControllerCommand::Report as usize]
+                .captures(command_string)
+                .unwrap();
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::Report,
+                value: caps.get(3).map(|m| m.as_str().to_string()),
+            })
+        } else if matches.matched(SwanlingControllerCommand::Drain as usize) {
+            // Perform a second regex to capture the id of the Worker to drain.
+            let caps = self.captures[SwanlingControllerCommand::Drain as usize]
+                .captures(command_string)
+                .unwrap();
+            let worker_id = caps.get(2).map_or("", |m| m.as_str());
+            Ok(SwanlingControllerRequestMessage {
+                command: SwanlingControllerCommand::Drain,
+                value: Some(worker_id.to_string()),
+            })
         } else {
             Err(())
         }
@@ -628,6 +1094,32 @@ impl SwanlingControllerState {
                     Err("failed to configure run_time".to_string())
                 }
             }
+            SwanlingControllerCommand::Set => {
+                if let SwanlingControllerResponseMessage::Bool(true) = response {
+                    Ok("option configured".to_string())
+                } else {
+                    Err("failed to configure option, be sure option and value are valid and load test is idle".to_string())
+                }
+            }
+            SwanlingControllerCommand::Annotate => {
+                if let SwanlingControllerResponseMessage::Bool(true) = response {
+                    Ok("annotation added".to_string())
+                } else {
+                    Err("load test not running, failed to add annotation".to_string())
+                }
+            }
+            SwanlingControllerCommand::Report => match response {
+                SwanlingControllerResponseMessage::Report(report) => Ok(report),
+                SwanlingControllerResponseMessage::Bool(true) => Ok("report written".to_string()),
+                _ => Err("load test with metrics not started, or path not writable".to_string()),
+            },
+            SwanlingControllerCommand::Drain => {
+                if let SwanlingControllerResponseMessage::Bool(true) = response {
+                    Ok("worker draining".to_string())
+                } else {
+                    Err("failed to drain worker, be sure it's running and isn't the last worker of a running manager".to_string())
+                }
+            }
             SwanlingControllerCommand::Config => {
                 if let SwanlingControllerResponseMessage::Config(config) = response {
                     Ok(format!("{:#?}", config))
@@ -656,12 +1148,69 @@ impl SwanlingControllerState {
                     Err("error loading metrics".to_string())
                 }
             }
+            SwanlingControllerCommand::Status => {
+                if let SwanlingControllerResponseMessage::Status(progress) = response {
+                    Ok(progress.to_string())
+                } else {
+                    Err("error loading status".to_string())
+                }
+            }
+            SwanlingControllerCommand::StatusJson => {
+                if let SwanlingControllerResponseMessage::Status(progress) = response {
+                    Ok(serde_json::to_string(&progress).expect("unexpected serde failure"))
+                } else {
+                    Err("error loading status".to_string())
+                }
+            }
+            SwanlingControllerCommand::Workers => {
+                if let SwanlingControllerResponseMessage::Workers(workers) = response {
+                    if workers.is_empty() {
+                        Ok("no workers connected".to_string())
+                    } else {
+                        Ok(workers
+                            .iter()
+                            .map(|worker| worker.to_string())
+                            .collect::<Vec<String>>()
+                            .join("\n"))
+                    }
+                } else {
+                    Err("not running as a manager, no workers to display".to_string())
+                }
+            }
+            SwanlingControllerCommand::WorkersJson => {
+                if let SwanlingControllerResponseMessage::Workers(workers) = response {
+                    Ok(serde_json::to_string(&workers).expect("unexpected serde failure"))
+                } else {
+                    Err("not running as a manager, no workers to display".to_string())
+                }
+            }
+            SwanlingControllerCommand::Reset => {
+                if let SwanlingControllerResponseMessage::Bool(true) = response {
+                    Ok("metrics reset".to_string())
+                } else {
+                    Err("load test not running, failed to reset metrics".to_string())
+                }
+            }
+            SwanlingControllerCommand::Pause => {
+                if let SwanlingControllerResponseMessage::Bool(true) = response {
+                    Ok("load test paused".to_string())
+                } else {
+                    Err("load test not running or already paused, failed to pause".to_string())
+                }
+            }
+            SwanlingControllerCommand::Resume => {
+                if let SwanlingControllerResponseMessage::Bool(true) = response {
+                    Ok("load test resumed".to_string())
+                } else {
+                    Err("load test not paused, failed to resume".to_string())
+                }
+            }
             SwanlingControllerCommand::Start => {
                 if let SwanlingControllerResponseMessage::Bool(true) = response {
                     Ok("load test started".to_string())
                 } else {
                     Err(
-                        "unable to start load test, be sure it is idle and host is configured"
+                        "unable to start load test, be sure it is idle, host is configured and options are valid"
                             .to_string(),
                     )
                 }
@@ -725,7 +1274,7 @@ impl SwanlingController<SwanlingControllerTelnetMessage> for SwanlingControllerS
 }
 #[async_trait]
 impl SwanlingController<SwanlingControllerWebSocketMessage> for SwanlingControllerState {
-    // Extract the command string from a WebSocket Controller client request.
+    // Extract the json requests from a WebSocket Controller client request.
     async fn get_command_string(
         &self,
         raw_value: SwanlingControllerWebSocketMessage,
@@ -734,17 +1283,7 @@ impl SwanlingController<SwanlingControllerWebSocketMessage> for SwanlingControll
             if request.is_text() {
                 if let Ok(request) = request.into_text() {
                     debug!("websocket request: {:?}", request.trim());
-                    let command_string: SwanlingControllerWebSocketRequest =
-                        match serde_json::from_str(&request) {
-                            Ok(c) => c,
-                            Err(_) => {
-                                return Err(
-                                    "unrecognized json request, refer to Swanling README.md"
-                                        .to_string(),
-                                )
-                            }
-                        };
-                    return Ok(command_string.request);
+                    return Ok(request);
                 } else {
                     // Failed to consume the WebSocket message and convert it to a String.
                     return Err("unsupported string format".to_string());
@@ -834,32 +1373,24 @@ impl SwanlingControllerExecuteCommand<tokio::net::TcpStream> for SwanlingControl
     }
 }
 #[async_trait]
-impl SwanlingControllerExecuteCommand<SwanlingControllerWebSocketSender>
+impl SwanlingControllerExecuteCommand<SwanlingControllerWebSocketReply>
     for SwanlingControllerState
 {
     // Run the command received from a WebSocket Controller request.
     async fn execute_command(
         &self,
-        socket: &mut SwanlingControllerWebSocketSender,
+        reply: &mut SwanlingControllerWebSocketReply,
         request_message: SwanlingControllerRequestMessage,
     ) -> SwanlingControllerExit {
         // First handle commands that don't require interaction with the parent process.
         if let Some(message) = self.process_local_command(&request_message) {
-            self.write_to_socket(socket, Ok(message)).await;
+            self.write_to_socket(reply, Ok(message)).await;
 
             // If Exit was received return true to exit, otherwise return false.
             let exit_controller = request_message.command == SwanlingControllerCommand::Exit;
             // If exiting, notify the WebSocket client that this connection is closing.
-            if exit_controller
-                && socket
-                    .send(Message::Close(Some(tungstenite::protocol::CloseFrame {
-                        code: tungstenite::protocol::frame::coding::CloseCode::Normal,
-                        reason: std::borrow::Cow::Borrowed("exit"),
-                    })))
-                    .await
-                    .is_err()
-            {
-                warn!("failed to write data to stream");
+            if exit_controller {
+                reply.close = Some("exit");
             }
 
             return exit_controller;
@@ -869,6 +1400,8 @@ impl SwanlingControllerExecuteCommand<SwanlingControllerWebSocketSender>
         let command = match request_message.command {
             SwanlingControllerCommand::Config => SwanlingControllerCommand::ConfigJson,
             SwanlingControllerCommand::Metrics => SwanlingControllerCommand::MetricsJson,
+            SwanlingControllerCommand::Status => SwanlingControllerCommand::StatusJson,
+            SwanlingControllerCommand::Workers => SwanlingControllerCommand::WorkersJson,
             _ => request_message.command.clone(),
         };
 
@@ -879,7 +1412,7 @@ impl SwanlingControllerExecuteCommand<SwanlingControllerWebSocketSender>
                 // Receiving an error here means the parent closed the communication
                 // channel. Write the error to the Controller client and then return
                 // true to exit.
-                self.write_to_socket(socket, Err(e)).await;
+                self.write_to_socket(reply, Err(e)).await;
                 return true;
             }
         };
@@ -887,62 +1420,35 @@ impl SwanlingControllerExecuteCommand<SwanlingControllerWebSocketSender>
         // If Shutdown command was received return true to exit, otherwise return false.
         let exit_controller = command == SwanlingControllerCommand::Shutdown;
 
-        // Write the response to the Controller client socket.
-        self.write_to_socket(socket, self.process_response(command, response))
+        // Add the response to the reply sent to the Controller client.
+        self.write_to_socket(reply, self.process_response(command, response))
             .await;
 
         // If exiting, notify the WebSocket client that this connection is closing.
-        if exit_controller
-            && socket
-                .send(Message::Close(Some(tungstenite::protocol::CloseFrame {
-                    code: tungstenite::protocol::frame::coding::CloseCode::Normal,
-                    reason: std::borrow::Cow::Borrowed("shutdown"),
-                })))
-                .await
-                .is_err()
-        {
-            warn!("failed to write data to stream");
+        if exit_controller {
+            reply.close = Some("shutdown");
         }
 
         // Return true if it's time to exit the Controller.
         exit_controller
     }
 
-    // Send a json-formatted response to the WebSocket.
+    // Add a response to the reply sent to the WebSocket, identified like the request.
     async fn write_to_socket(
         &self,
-        socket: &mut SwanlingControllerWebSocketSender,
+        reply: &mut SwanlingControllerWebSocketReply,
         response_result: Result<String, String>,
     ) {
-        let success;
-        let response = match response_result {
-            Ok(m) => {
-                success = true;
-                m
-            }
-            Err(e) => {
-                success = false;
-                e
-            }
+        let (response, success) = match response_result {
+            Ok(m) => (m, true),
+            Err(e) => (e, false),
         };
-        if let Err(e) = socket
-            .send(Message::Text(
-                match serde_json::to_string(&SwanlingControllerWebSocketResponse {
-                    response,
-                    // Success is true if there is no error, false if there is an error.
-                    success,
-                }) {
-                    Ok(json) => json,
-                    Err(e) => {
-                        warn!("failed to json encode response: {}", e);
-                        return;
-                    }
-                },
-            ))
-            .await
-        {
-            info!("failed to write data to websocket: {}", e);
-        }
+        reply.responses.push(SwanlingControllerWebSocketResponse {
+            response,
+            // Success is true if there is no error, false if there is an error.
+            success,
+            id: reply.id.clone(),
+        });
     }
 }
 
@@ -989,6 +1495,10 @@ pub(crate) async fn controller_main(
     let hatchrate_regex = r"(?i)^(hatchrate|hatch_rate|hatch-rate) ([0-9]*(\.[0-9]*)?){1}$";
     let runtime_regex =
         r"(?i)^(run|runtime|run_time|run-time|) (\d+|((\d+?)h)?((\d+?)m)?((\d+?)s)?)$";
+    let set_regex = r"(?i)^(set) (-{0,2}[a-z_-]+) (.+)$";
+    let annotate_regex = r"(?i)^(annotate) (.+)$";
+    let report_regex = r"(?i)^(report)( (.+))?$";
+    let drain_regex = r"(?i)^(drain) (\d+)$";
 
     // The following RegexSet is matched against all commands received through the controller.
     // Developer note: The order commands are defined here must match the order in which
@@ -1003,6 +1513,14 @@ pub(crate) async fn controller_main(
         hatchrate_regex,
         // Modify how long the load test will run.
         runtime_regex,
+        // Modify any other option.
+        set_regex,
+        // Add a note to the running load test.
+        annotate_regex,
+        // Generate the html report, optionally writing it to a file.
+        report_regex,
+        // Drain a Worker from the running Regatta.
+        drain_regex,
         // Display the current load test configuration.
         r"(?i)^config$",
         // Display the current load test configuration in json.
@@ -1011,12 +1529,26 @@ pub(crate) async fn controller_main(
         r"(?i)^(metrics|stats)$",
         // Display running metrics for the currently active load test in json.
         r"(?i)^(metricsjson|metrics-json|statsjson|stats-json)$",
+        // Display where the currently active load test is in its lifecycle.
+        r"(?i)^status$",
+        // Display where the currently active load test is in its lifecycle in json.
+        r"(?i)^(statusjson|status-json)$",
+        // Display the Workers connected to the Manager.
+        r"(?i)^workers$",
+        // Display the Workers connected to the Manager in json.
+        r"(?i)^(workersjson|workers-json)$",
+        // Reset the metrics of the currently active load test.
+        r"(?i)^(reset|reset-metrics)$",
+        // Pause the currently active load test.
+        r"(?i)^pause$",
+        // Resume the currently paused load test.
+        r"(?i)^(resume|unpause)$",
         // Provide a list of possible commands.
         r"(?i)^(help|\?)$",
         // Exit/quit the controller connection, does not affect load test.
         r"(?i)^(exit|quit)$",
-        // Start an idle load test.
-        r"(?i)^start$",
+        // Start an idle load test, optionally configuring options.
+        r"(?i)^start( -{0,2}[a-z_-]+=\S+)*$",
         // Stop an idle load test.
         r"(?i)^stop$",
         // Shutdown the load test (which will cause the controller connection to quit).
@@ -1031,6 +1563,10 @@ pub(crate) async fn controller_main(
         Regex::new(users_regex).unwrap(),
         Regex::new(hatchrate_regex).unwrap(),
         Regex::new(runtime_regex).unwrap(),
+        Regex::new(set_regex).unwrap(),
+        Regex::new(annotate_regex).unwrap(),
+        Regex::new(report_regex).unwrap(),
+        Regex::new(drain_regex).unwrap(),
     ];
 
     // Counter increments each time a controller client connects with this protocol.
@@ -1082,16 +1618,28 @@ fn display_help() -> String {
  help (?)           this help
  exit (quit)        exit controller
  start              start an idle load test
- stop               stop a running load test and return to idle state
+ start OPTION=VALUE start with options, ie start users=10 run_time=1m
+ stop               stop a running or scheduled load test and return to idle state
  shutdown           shutdown running load test (and exit controller)
  host HOST          set host to load test, ie http://localhost/
  users INT          set number of simulated users
  hatchrate FLOAT    set per-second rate users hatch
  runtime TIME       set how long to run test, ie 1h30m5s
+ set OPTION VALUE   set any other option, ie set throttle_requests 5
+ annotate TEXT      add a note to the running load test, ie annotate cache flushed
+ report [PATH]      display html report, or write it to a file, ie report partial.html
+ drain WORKER       stop a worker and move its users to the others (manager only)
  config             display load test configuration
  config-json        display load test configuration in json format
  metrics            display metrics for current load test
- metrics-json       display metrics for current load test in json format",
+ metrics-json       display metrics for current load test in json format
+ status             display phase, elapsed and remaining time, and users launched
+ status-json        display status of current load test in json format
+ workers            display users and last metrics flush of each worker (manager only)
+ workers-json       display status of each worker in json format (manager only)
+ reset              reset metrics for current load test
+ pause              pause users after their current task iteration
+ resume             resume a paused load test",
         env!("CARGO_PKG_NAME"),
         env!("CARGO_PKG_VERSION")
     )
@@ -1153,11 +1701,102 @@ impl SwanlingAttack {
                                 )),
                             );
                         }
+                        // Send back where the load test is in its lifecycle.
+                        SwanlingControllerCommand::Status
+                        | SwanlingControllerCommand::StatusJson => {
+                            self.reply_to_controller(
+                                message,
+                                SwanlingControllerResponseMessage::Status(self.progress()),
+                            );
+                        }
+                        // Reset the running metrics, and acknowledge command.
+                        SwanlingControllerCommand::Reset => {
+                            // We can only reset the metrics of a running load test.
+                            if self.attack_phase == AttackPhase::Running {
+                                self.reset_metrics_from_controller(swanling_attack_run_state)
+                                    .await?;
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(true),
+                                );
+                            } else {
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(false),
+                                );
+                            }
+                        }
+                        // Pause the running load test, and acknowledge command.
+                        SwanlingControllerCommand::Pause => {
+                            // We can only pause a running load test that isn't paused.
+                            if self.attack_phase == AttackPhase::Running && self.paused.is_none() {
+                                self.pause_attack(swanling_attack_run_state);
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(true),
+                                );
+                            } else {
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(false),
+                                );
+                            }
+                        }
+                        // Resume the paused load test, and acknowledge command.
+                        SwanlingControllerCommand::Resume => {
+                            if self.attack_phase == AttackPhase::Running && self.paused.is_some() {
+                                self.resume_attack(swanling_attack_run_state);
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(true),
+                                );
+                            } else {
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(false),
+                                );
+                            }
+                        }
                         // Start the load test, and acknowledge command.
                         SwanlingControllerCommand::Start => {
                             // We can only start an idle load test.
                             if self.attack_phase == AttackPhase::Idle {
-                                if self.prepare_load_test().is_ok() {
+                                // Configure any options, restoring the previous configuration
+                                // if they are invalid or the load test can't start.
+                                let previous = self.configuration.clone();
+                                let options = message
+                                    .request
+                                    .value
+                                    .as_deref()
+                                    .unwrap_or_default()
+                                    .split_whitespace()
+                                    .filter_map(|option| option.split_once('='))
+                                    .collect::<Vec<(&str, &str)>>();
+                                let mut prepared = self
+                                    .set_options(&options)
+                                    .and_then(|_| self.prepare_load_test());
+                                if prepared.is_ok() {
+                                    // Don't start the load test if any host is unhealthy.
+                                    prepared = self.check_health(swanling_attack_run_state).await;
+                                }
+                                if let Err(e) = &prepared {
+                                    warn!("failed to start load test: {:?}", e);
+                                    self.restore_configuration(previous)?;
+                                }
+                                let scheduled = self
+                                    .configuration
+                                    .get_start_at()
+                                    .filter(|start_at| *start_at > Utc::now());
+                                if let (Ok(_), Some(start_at)) = (&prepared, scheduled) {
+                                    // Remain idle until the scheduled time.
+                                    info!("load test scheduled to start at {}", start_at);
+                                    swanling_attack_run_state.start_scheduled = true;
+                                    swanling_attack_run_state.idle_status_displayed = false;
+                                    self.reply_to_controller(
+                                        message,
+                                        SwanlingControllerResponseMessage::Bool(true),
+                                    );
+                                } else if prepared.is_ok() {
                                     self.set_attack_phase(
                                         swanling_attack_run_state,
                                         AttackPhase::Starting,
@@ -1200,6 +1839,24 @@ impl SwanlingAttack {
                                     message,
                                     SwanlingControllerResponseMessage::Bool(true),
                                 );
+                            // A load test waiting to start at a scheduled time can also be
+                            // stopped, remaining idle instead.
+                            } else if self.attack_phase == AttackPhase::Idle
+                                && (swanling_attack_run_state.start_scheduled
+                                    || (!self.configuration.no_autostart
+                                        && self
+                                            .configuration
+                                            .get_start_at()
+                                            .filter(|start_at| *start_at > Utc::now())
+                                            .is_some()))
+                            {
+                                info!("scheduled load test canceled");
+                                swanling_attack_run_state.start_scheduled = false;
+                                self.configuration.no_autostart = true;
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(true),
+                                );
                             } else {
                                 self.reply_to_controller(
                                     message,
@@ -1326,6 +1983,103 @@ impl SwanlingAttack {
                                 );
                             }
                         }
+                        SwanlingControllerCommand::Set => {
+                            if self.attack_phase == AttackPhase::Idle {
+                                // The controller combines the option and value, separated by
+                                // the first space.
+                                if let Some((option, value)) = message
+                                    .request
+                                    .value
+                                    .as_ref()
+                                    .and_then(|v| v.split_once(' '))
+                                {
+                                    info!("changing {} to {}", option, value);
+                                    let success = match self.set_options(&[(option, value)]) {
+                                        Ok(_) => true,
+                                        Err(e) => {
+                                            warn!("failed to change {}: {:?}", option, e);
+                                            false
+                                        }
+                                    };
+                                    self.reply_to_controller(
+                                        message,
+                                        SwanlingControllerResponseMessage::Bool(success),
+                                    );
+                                } else {
+                                    warn!(
+                                        "Controller didn't provide option: {:#?}",
+                                        &message.request
+                                    );
+                                }
+                            } else {
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(false),
+                                );
+                            }
+                        }
+                        SwanlingControllerCommand::Annotate => {
+                            // We can only annotate a starting or running load test.
+                            if [AttackPhase::Starting, AttackPhase::Running]
+                                .contains(&self.attack_phase)
+                            {
+                                if let Some(text) = &message.request.value {
+                                    info!("adding annotation: {}", text);
+                                    self.metrics
+                                        .annotations
+                                        .push(SwanlingAnnotation::new(self.metrics.started, text));
+                                    self.reply_to_controller(
+                                        message,
+                                        SwanlingControllerResponseMessage::Bool(true),
+                                    );
+                                } else {
+                                    warn!(
+                                        "Controller didn't provide annotation: {:#?}",
+                                        &message.request
+                                    );
+                                }
+                            } else {
+                                self.reply_to_controller(
+                                    message,
+                                    SwanlingControllerResponseMessage::Bool(false),
+                                );
+                            }
+                        }
+                        // Only a Manager has Workers to display or drain.
+                        SwanlingControllerCommand::Workers
+                        | SwanlingControllerCommand::WorkersJson
+                        | SwanlingControllerCommand::Drain => {
+                            self.reply_to_controller(
+                                message,
+                                SwanlingControllerResponseMessage::Bool(false),
+                            );
+                        }
+                        // Generate the html report from the current metrics, and return it or
+                        // write it to the requested path.
+                        SwanlingControllerCommand::Report => {
+                            let response = if self.metrics.started.is_none()
+                                || self.configuration.no_metrics
+                            {
+                                SwanlingControllerResponseMessage::Bool(false)
+                            } else {
+                                self.update_duration();
+                                let report = self.build_html_report();
+                                match &message.request.value {
+                                    Some(path) => match tokio::fs::write(path, report).await {
+                                        Ok(_) => {
+                                            info!("wrote html report file to: {}", path);
+                                            SwanlingControllerResponseMessage::Bool(true)
+                                        }
+                                        Err(e) => {
+                                            warn!("failed to write html report to {}: {}", path, e);
+                                            SwanlingControllerResponseMessage::Bool(false)
+                                        }
+                                    },
+                                    None => SwanlingControllerResponseMessage::Report(report),
+                                }
+                            };
+                            self.reply_to_controller(message, response);
+                        }
                         // These messages shouldn't be received here.
                         SwanlingControllerCommand::Help | SwanlingControllerCommand::Exit => {
                             warn!("Unexpected command: {:?}", &message.request);
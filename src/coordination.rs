@@ -0,0 +1,339 @@
+//! Coordinate users with named counters, barriers and flags.
+//!
+//! Some scenarios need users to act together, for example all users submitting an order in
+//! the same second, or only one user running an admin task. Instead of unsafe statics,
+//! [`SwanlingUser::coordination`](../swanling/struct.SwanlingUser.html#method.coordination)
+//! returns primitives owned by the [`SwanlingAttack`](../struct.SwanlingAttack.html) and
+//! shared by all of its users, looked up by name:
+//!
+//!  - [`counter`](./struct.SwanlingCoordination.html#method.counter): an atomic counter.
+//!  - [`once`](./struct.SwanlingCoordination.html#method.once): true only the first time
+//!    it's called with a name.
+//!  - [`barrier`](./struct.SwanlingCoordination.html#method.barrier): waits until a number of
+//!    users are waiting, then releases them all at once.
+//!  - [`flag`](./struct.SwanlingCoordination.html#method.flag): a flag one user sets, that
+//!    other users check or wait for.
+//!
+//! When running in a distributed Regatta, each Worker has its own primitives.
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//!
+//! let mut task = task!(submit_order);
+//!
+//! /// Wait until 10 users are ready, then all submit an order at the same time.
+//! async fn submit_order(user: &SwanlingUser) -> SwanlingTaskResult {
+//!     user.coordination().barrier("submit", 10).wait().await;
+//!     let _swanling = user.post("/order", "item=1").await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::AtomicUsize;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{watch, Barrier};
+
+/// A flag shared by all users, that's either set or cleared. Users can check whether the
+/// flag is set, or wait until it's set.
+#[derive(Clone, Debug)]
+pub struct SwanlingFlag {
+    sender: Arc<watch::Sender<bool>>,
+}
+impl SwanlingFlag {
+    pub(crate) fn new() -> Self {
+        SwanlingFlag {
+            sender: Arc::new(watch::channel(false).0),
+        }
+    }
+
+    /// Set the flag, waking all users waiting for it.
+    pub fn set(&self) {
+        self.sender.send_replace(true);
+    }
+
+    /// Clear the flag.
+    pub fn clear(&self) {
+        self.sender.send_replace(false);
+    }
+
+    /// Returns true if the flag is set.
+    pub fn is_set(&self) -> bool {
+        *self.sender.borrow()
+    }
+
+    /// Wait until the flag is set, returning immediately if it's already set.
+    pub async fn wait(&self) {
+        let mut receiver = self.sender.subscribe();
+        // The sender is held by self, so can't be dropped while waiting.
+        let _ = receiver.wait_for(|set| *set).await;
+    }
+}
+
+/// The primitives created so far, by name.
+#[derive(Default)]
+struct SwanlingCoordinationPrimitives {
+    counters: HashMap<String, Arc<AtomicUsize>>,
+    barriers: HashMap<String, Arc<Barrier>>,
+    flags: HashMap<String, SwanlingFlag>,
+    /// Barriers of tasks with a rendezvous, keyed by task set and task index.
+    rendezvous: HashMap<(usize, usize), Arc<Barrier>>,
+}
+
+/// Named counters, barriers and flags shared by all users, see the
+/// [module documentation](./index.html).
+///
+/// Each primitive is created the first time it's requested by name, and the same primitive
+/// is returned to all users requesting it afterwards. Cloning returns another handle to the
+/// same primitives.
+#[derive(Clone)]
+pub struct SwanlingCoordination {
+    primitives: Arc<Mutex<SwanlingCoordinationPrimitives>>,
+    /// Set while the load test is stopping, so users no longer wait at a rendezvous.
+    released: SwanlingFlag,
+}
+impl SwanlingCoordination {
+    /// Returns the counter with this name, starting at 0.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::sync::atomic::Ordering;
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(create_account);
+    ///
+    /// /// Give each account a unique number, shared by all users.
+    /// async fn create_account(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let number = user.coordination().counter("accounts").fetch_add(1, Ordering::SeqCst);
+    ///     let _swanling = user.post("/account", &format!("name=user{}", number)).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn counter(&self, name: &str) -> Arc<AtomicUsize> {
+        self.primitives
+            .lock()
+            .unwrap()
+            .counters
+            .entry(name.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Returns true the first time it's called with this name, and false afterwards, so
+    /// only one user runs a task.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(admin_task);
+    ///
+    /// /// Only one user rebuilds the search index.
+    /// async fn admin_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     if user.coordination().once("reindex") {
+    ///         let _swanling = user.post("/admin/reindex", "").await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn once(&self, name: &str) -> bool {
+        self.counter(name)
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+            == 0
+    }
+
+    /// Returns the barrier with this name, created for `users` users the first time it's
+    /// requested. Later requests return the same barrier, whatever `users` they pass.
+    ///
+    /// Users awaiting [`wait`](https://docs.rs/tokio/*/tokio/sync/struct.Barrier.html#method.wait)
+    /// are blocked until `users` users are waiting, then all are released at once and the
+    /// barrier can be used again. A user blocked at a barrier can't stop until it's
+    /// released, so make sure enough users reach the barrier, for example by using the
+    /// number of users running the task set.
+    pub fn barrier(&self, name: &str, users: usize) -> Arc<Barrier> {
+        self.primitives
+            .lock()
+            .unwrap()
+            .barriers
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(Barrier::new(users)))
+            .clone()
+    }
+
+    /// Returns the flag with this name, initially cleared.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(buy_ticket);
+    ///
+    /// /// Wait until the sale opens, then buy a ticket.
+    /// async fn buy_ticket(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let sale = user.coordination().flag("sale");
+    ///     if user.coordination().once("open sale") {
+    ///         let _swanling = user.post("/admin/sale", "open=1").await?;
+    ///         sale.set();
+    ///     }
+    ///     sale.wait().await;
+    ///     let _swanling = user.post("/ticket", "").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn flag(&self, name: &str) -> SwanlingFlag {
+        self.primitives
+            .lock()
+            .unwrap()
+            .flags
+            .entry(name.to_string())
+            .or_insert_with(SwanlingFlag::new)
+            .clone()
+    }
+
+    /// Wait until `users` users reach the rendezvous of a task, see
+    /// [`SwanlingTask::set_rendezvous`](../swanling/struct.SwanlingTask.html#method.set_rendezvous).
+    /// Returns immediately once rendezvous are released.
+    pub(crate) async fn rendezvous(
+        &self,
+        task_sets_index: usize,
+        tasks_index: usize,
+        users: usize,
+    ) {
+        if self.released.is_set() {
+            return;
+        }
+        let barrier = self
+            .primitives
+            .lock()
+            .unwrap()
+            .rendezvous
+            .entry((task_sets_index, tasks_index))
+            .or_insert_with(|| Arc::new(Barrier::new(users)))
+            .clone();
+        tokio::select! {
+            _ = barrier.wait() => (),
+            _ = self.released.wait() => (),
+        }
+    }
+
+    /// Release all users waiting at a rendezvous, and stop waiting at rendezvous, so users
+    /// can exit when the load test stops.
+    pub(crate) fn release_rendezvous(&self) {
+        self.released.set();
+        // Released users no longer count as waiting, so start over with new barriers.
+        self.primitives.lock().unwrap().rendezvous.clear();
+    }
+
+    /// Wait at rendezvous again, when the load test starts.
+    pub(crate) fn reset_rendezvous(&self) {
+        self.released.clear();
+    }
+}
+impl Default for SwanlingCoordination {
+    fn default() -> Self {
+        SwanlingCoordination {
+            primitives: Arc::new(Mutex::new(SwanlingCoordinationPrimitives::default())),
+            released: SwanlingFlag::new(),
+        }
+    }
+}
+/// Only display how many primitives have been created.
+impl fmt::Debug for SwanlingCoordination {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let primitives = self.primitives.lock().unwrap();
+        f.debug_struct("SwanlingCoordination")
+            .field("counters", &primitives.counters.len())
+            .field("barriers", &primitives.barriers.len())
+            .field("flags", &primitives.flags.len())
+            .field("released", &self.released.is_set())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn shared_by_name() {
+        let coordination = SwanlingCoordination::default();
+        let other = coordination.clone();
+        coordination.counter("a").fetch_add(2, Ordering::SeqCst);
+        assert_eq!(other.counter("a").load(Ordering::SeqCst), 2);
+        assert_eq!(other.counter("b").load(Ordering::SeqCst), 0);
+
+        assert!(coordination.once("admin"));
+        assert!(!other.once("admin"));
+        assert!(!coordination.once("admin"));
+    }
+
+    #[tokio::test]
+    async fn flag_wakes_waiters() {
+        let coordination = SwanlingCoordination::default();
+        let flag = coordination.flag("ready");
+        assert!(!flag.is_set());
+
+        let waiter = tokio::spawn({
+            let coordination = coordination.clone();
+            async move { coordination.flag("ready").wait().await }
+        });
+        flag.set();
+        waiter.await.unwrap();
+        assert!(coordination.flag("ready").is_set());
+        // Waiting for a flag that's already set returns immediately.
+        flag.wait().await;
+        flag.clear();
+        assert!(!coordination.flag("ready").is_set());
+    }
+
+    #[tokio::test]
+    async fn barrier_releases_together() {
+        let coordination = SwanlingCoordination::default();
+        let arrived = coordination.counter("arrived");
+        let mut waiters = Vec::new();
+        for _ in 0..3 {
+            let coordination = coordination.clone();
+            let arrived = arrived.clone();
+            waiters.push(tokio::spawn(async move {
+                arrived.fetch_add(1, Ordering::SeqCst);
+                coordination.barrier("start", 3).wait().await;
+                // All users arrived before any was released.
+                arrived.load(Ordering::SeqCst)
+            }));
+        }
+        for waiter in waiters {
+            assert_eq!(waiter.await.unwrap(), 3);
+        }
+    }
+
+    #[tokio::test]
+    async fn release_rendezvous() {
+        let coordination = SwanlingCoordination::default();
+        let waiter = tokio::spawn({
+            let coordination = coordination.clone();
+            async move { coordination.rendezvous(0, 0, 2).await }
+        });
+        tokio::task::yield_now().await;
+        coordination.release_rendezvous();
+        waiter.await.unwrap();
+        // Released rendezvous don't wait.
+        coordination.rendezvous(0, 0, 2).await;
+
+        coordination.reset_rendezvous();
+        let waiters: Vec<_> = (0..2)
+            .map(|_| {
+                let coordination = coordination.clone();
+                tokio::spawn(async move { coordination.rendezvous(0, 0, 2).await })
+            })
+            .collect();
+        for waiter in waiters {
+            waiter.await.unwrap();
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! Optionally control how hostnames are resolved.
+//!
+//! When the `--dns-server`, `--no-dns-cache` or `--dns-refresh` run-time options are set
+//! (or the matching [`SwanlingDefault`](../enum.SwanlingDefault.html) default configuration
+//! options), each [`SwanlingUser`](../swanling/struct.SwanlingUser.html) resolves hostnames
+//! with its own resolver instead of relying on the system resolver alone. This makes it
+//! possible to load test DNS-based traffic management, such as weighted records or
+//! failover, and observe its effects in the metrics.
+//!
+//! With `--dns-server`, hostnames are resolved by querying the specified DNS server for
+//! their IPv4 and IPv6 addresses over UDP. With `--no-dns-cache`, connections aren't
+//! reused so the hostname is resolved again for every request. With `--dns-refresh`,
+//! connections aren't reused either, but resolved addresses are cached until the user has
+//! made the specified number of requests.
+
+use hyper::client::connect::dns::Name;
+use rand::Rng;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::net::UdpSocket;
+use tokio::time::{timeout, Duration};
+
+use crate::SwanlingConfiguration;
+
+/// How long to wait for the DNS server to answer a query.
+const DNS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The maximum size of a DNS message sent over UDP.
+const DNS_MESSAGE_SIZE: usize = 512;
+
+/// The DNS record type of an IPv4 address.
+const RECORD_A: u16 = 1;
+
+/// The DNS record type of an IPv6 address.
+const RECORD_AAAA: u16 = 28;
+
+/// Errors resolving hostnames are returned to the client.
+type DnsError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Resolves hostnames for the client of a single
+/// [`SwanlingUser`](../swanling/struct.SwanlingUser.html).
+#[derive(Debug)]
+pub(crate) struct SwanlingResolver {
+    /// An optional DNS server to query instead of the system resolver.
+    server: Option<SocketAddr>,
+    /// Whether connections can be reused, otherwise every request resolves the hostname.
+    pub(crate) keep_alive: bool,
+    /// An optional number of requests after which cached addresses are discarded. If not
+    /// set, addresses are not cached.
+    refresh: Option<usize>,
+    /// Addresses resolved since the cache was last refreshed, by hostname.
+    cache: Arc<Mutex<HashMap<String, Vec<IpAddr>>>>,
+    /// Requests made since the cache was last refreshed.
+    requests: AtomicUsize,
+}
+impl SwanlingResolver {
+    /// Build a resolver if any DNS option is configured, otherwise the client uses the
+    /// system resolver.
+    pub(crate) fn from_configuration(configuration: &SwanlingConfiguration) -> Option<Arc<Self>> {
+        let server = configuration.get_dns_server();
+        if server.is_none() && !configuration.no_dns_cache && configuration.dns_refresh == 0 {
+            return None;
+        }
+
+        Some(Arc::new(SwanlingResolver {
+            server,
+            keep_alive: !configuration.no_dns_cache && configuration.dns_refresh == 0,
+            refresh: Some(configuration.dns_refresh).filter(|r| *r > 0),
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            requests: AtomicUsize::new(0),
+        }))
+    }
+
+    /// Count a request, discarding cached addresses every `--dns-refresh` requests.
+    pub(crate) fn request_sent(&self) {
+        if let Some(refresh) = self.refresh {
+            if self.requests.fetch_add(1, Ordering::SeqCst) + 1 >= refresh {
+                self.requests.store(0, Ordering::SeqCst);
+                if let Ok(mut cache) = self.cache.lock() {
+                    cache.clear();
+                }
+            }
+        }
+    }
+}
+impl Resolve for SwanlingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+
+        // Use cached addresses if enabled and the hostname was already resolved.
+        let cache = self.refresh.map(|_| self.cache.clone());
+        if let Some(cache) = cache.as_ref() {
+            if let Some(addresses) = cache.lock().ok().and_then(|c| c.get(&host).cloned()) {
+                return Box::pin(futures::future::ready(Ok(to_addrs(addresses))));
+            }
+        }
+
+        let server = self.server;
+        Box::pin(async move {
+            let addresses = match server {
+                Some(server) => query_server(server, &host).await?,
+                None => tokio::net::lookup_host((host.as_str(), 0))
+                    .await?
+                    .map(|address| address.ip())
+                    .collect(),
+            };
+            debug!("resolved {} to {:?}", host, addresses);
+
+            if let Some(cache) = cache {
+                if let Ok(mut cache) = cache.lock() {
+                    cache.insert(host, addresses.clone());
+                }
+            }
+
+            Ok(to_addrs(addresses))
+        })
+    }
+}
+
+/// The client sets the port of the resolved addresses.
+fn to_addrs(addresses: Vec<IpAddr>) -> Addrs {
+    Box::new(
+        addresses
+            .into_iter()
+            .map(|address| SocketAddr::new(address, 0)),
+    )
+}
+
+/// Resolve a hostname by querying a DNS server for its IPv4 and IPv6 addresses.
+async fn query_server(server: SocketAddr, host: &str) -> Result<Vec<IpAddr>, DnsError> {
+    let local_address: SocketAddr = if server.is_ipv4() {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = UdpSocket::bind(local_address).await?;
+    socket.connect(server).await?;
+
+    let mut addresses = Vec::new();
+    for record_type in &[RECORD_A, RECORD_AAAA] {
+        let id: u16 = rand::thread_rng().gen();
+        socket.send(&build_query(id, host, *record_type)?).await?;
+
+        let mut response = [0; DNS_MESSAGE_SIZE];
+        let length = timeout(DNS_TIMEOUT, socket.recv(&mut response))
+            .await
+            .map_err(|_| format!("DNS server {} didn't respond", server))??;
+        addresses.extend(parse_response(&response[..length], id)?);
+    }
+
+    if addresses.is_empty() {
+        return Err(format!("DNS server {} has no addresses for {}", server, host).into());
+    }
+
+    Ok(addresses)
+}
+
+/// Build a recursive DNS query for the records of one type of a hostname.
+fn build_query(id: u16, host: &str, record_type: u16) -> Result<Vec<u8>, DnsError> {
+    let mut query = Vec::with_capacity(DNS_MESSAGE_SIZE);
+    query.extend_from_slice(&id.to_be_bytes());
+    // Request recursion, and ask a single question.
+    query.extend_from_slice(&[0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0]);
+    for label in host.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(format!("invalid hostname: {}", host).into());
+        }
+        query.push(label.len() as u8);
+        query.extend_from_slice(label.as_bytes());
+    }
+    query.push(0);
+    query.extend_from_slice(&record_type.to_be_bytes());
+    // Internet class.
+    query.extend_from_slice(&1u16.to_be_bytes());
+
+    Ok(query)
+}
+
+/// Parse the IPv4 and IPv6 addresses answering a DNS query.
+fn parse_response(response: &[u8], id: u16) -> Result<Vec<IpAddr>, DnsError> {
+    if response.len() < 12 || u16::from_be_bytes([response[0], response[1]]) != id {
+        return Err("invalid DNS response".into());
+    }
+    // A non-zero response code is an error, for example if the hostname doesn't exist.
+    let response_code = response[3] & 0x0f;
+    if response_code != 0 {
+        return Err(format!("DNS server returned error code {}", response_code).into());
+    }
+    let questions = u16::from_be_bytes([response[4], response[5]]);
+    let answers = u16::from_be_bytes([response[6], response[7]]);
+
+    // Skip the questions, which are followed by the answers.
+    let mut position = 12;
+    for _ in 0..questions {
+        position = skip_name(response, position)? + 4;
+    }
+
+    let mut addresses = Vec::new();
+    for _ in 0..answers {
+        position = skip_name(response, position)?;
+        let record = response
+            .get(position..position + 10)
+            .ok_or("truncated DNS response")?;
+        let record_type = u16::from_be_bytes([record[0], record[1]]);
+        let length = u16::from_be_bytes([record[8], record[9]]) as usize;
+        position += 10;
+        let data = response
+            .get(position..position + length)
+            .ok_or("truncated DNS response")?;
+        match (record_type, length) {
+            (RECORD_A, 4) => addresses.push(IpAddr::from([data[0], data[1], data[2], data[3]])),
+            (RECORD_AAAA, 16) => {
+                let mut octets = [0; 16];
+                octets.copy_from_slice(data);
+                addresses.push(IpAddr::from(octets));
+            }
+            // Ignore other records, such as a CNAME followed by the addresses it points to.
+            _ => (),
+        }
+        position += length;
+    }
+
+    Ok(addresses)
+}
+
+/// Return the position following a name in a DNS message.
+fn skip_name(message: &[u8], mut position: usize) -> Result<usize, DnsError> {
+    loop {
+        match *message.get(position).ok_or("truncated DNS response")? {
+            0 => return Ok(position + 1),
+            // A compressed name ends with a pointer to the rest of the name.
+            length if length & 0xc0 == 0xc0 => return Ok(position + 2),
+            length => position += length as usize + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query() {
+        let query = build_query(0x1234, "www.example.com", RECORD_A).unwrap();
+        assert_eq!(&query[..2], &[0x12, 0x34]);
+        assert_eq!(&query[12..29], b"\x03www\x07example\x03com\x00");
+        assert_eq!(&query[29..], &[0, 1, 0, 1]);
+        assert_eq!(skip_name(&query, 12).unwrap(), 29);
+
+        // Hostnames must be made of valid labels.
+        assert!(build_query(0x1234, "www..com", RECORD_A).is_err());
+        assert!(build_query(0x1234, &"a".repeat(64), RECORD_A).is_err());
+    }
+
+    #[test]
+    fn response() {
+        // Answer the query with a CNAME and an address, using compressed names.
+        let mut response = build_query(0x1234, "www.example.com", RECORD_A).unwrap();
+        response[2] = 0x81;
+        response[7] = 2;
+        response.extend_from_slice(&[0xc0, 12, 0, 5, 0, 1, 0, 0, 0, 60, 0, 2, 0xc0, 16]);
+        response.extend_from_slice(&[0xc0, 16, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 10, 0, 0, 1]);
+        assert_eq!(
+            parse_response(&response, 0x1234).unwrap(),
+            vec![IpAddr::from([10, 0, 0, 1])]
+        );
+
+        // Responses to other queries are invalid.
+        assert!(parse_response(&response, 0x4321).is_err());
+
+        // Truncated responses are invalid.
+        assert!(parse_response(&response[..response.len() - 1], 0x1234).is_err());
+
+        // The hostname doesn't exist.
+        response[3] = 0x83;
+        assert!(parse_response(&response, 0x1234).is_err());
+    }
+}
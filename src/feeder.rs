@@ -0,0 +1,377 @@
+//! Hand out test data, such as credentials, so no two users use the same row at once.
+//!
+//! Many load tests need a unique account per user, for example because the target only
+//! allows one session per account. A feeder registered with
+//! [`SwanlingAttack::register_feeder`](../struct.SwanlingAttack.html#method.register_feeder)
+//! holds rows of test data, and users check out a row with
+//! [`SwanlingUser::feeder`](../swanling/struct.SwanlingUser.html#method.feeder). A row that's
+//! checked out isn't handed to another user until it's released, either with
+//! [`SwanlingFeederLease::release`](./struct.SwanlingFeederLease.html#method.release) or when
+//! the lease is dropped.
+//!
+//! When running a fleet with `--fleet-index` and `--fleet-size`, each process only hands out
+//! its share of the rows, so no two processes use the same row. When running in a
+//! distributed Regatta, or to share rows between processes dynamically, register a backend
+//! with [`SwanlingAttack::set_feeder_backend`](../struct.SwanlingAttack.html#method.set_feeder_backend)
+//! to coordinate which rows are in use, for example in Redis. The backend then hands out all
+//! rows to all processes. A Regatta with feeders but no backend is rejected, as each Worker
+//! would hand out every row.
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//!
+//! /// Log in with an account no other user is using, and release it when done.
+//! async fn log_in(user: &SwanlingUser) -> SwanlingTaskResult {
+//!     let account = user.feeder("accounts")?.checkout().await?;
+//!     let _swanling = user.post("/login", account.row()).await?;
+//!     let _swanling = user.get("/orders").await?;
+//!     account.release().await;
+//!
+//!     Ok(())
+//! }
+//!
+//! fn main() -> Result<(), SwanlingError> {
+//!     SwanlingAttack::initialize()?
+//!         .register_taskset(taskset!("LoadTest").register_task(task!(log_in)))
+//!         .register_feeder(
+//!             "accounts",
+//!             vec!["name=alice&pass=1".to_string(), "name=bob&pass=2".to_string()],
+//!         )?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+use crate::swanling::SwanlingTaskError;
+
+/// How long to wait before trying to claim a row again, when all rows are claimed in a
+/// backend that can't notify when a row is released.
+const BACKEND_RETRY: tokio::time::Duration = tokio::time::Duration::from_millis(100);
+
+/// An error returned by a feeder backend.
+pub type SwanlingFeederError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Coordinates which rows of each feeder are in use between processes, implemented with the
+/// store of your choice.
+///
+/// For example, with Redis a row is claimed with `SET swanling:{feeder}:{row} 1 NX PX
+/// {timeout}`, trying rows from a random start until one is set, and released with `DEL`.
+/// The timeout frees the rows of a process that exits without releasing them.
+#[async_trait]
+pub trait SwanlingFeederBackend: Send + Sync {
+    /// Claim one of the `rows` rows of the feeder `feeder` that isn't claimed by any user of
+    /// any process, returning its index, or `None` if all rows are claimed.
+    async fn claim(&self, feeder: &str, rows: usize) -> Result<Option<usize>, SwanlingFeederError>;
+
+    /// Release a row claimed earlier, so it can be claimed again.
+    async fn release(&self, feeder: &str, row: usize) -> Result<(), SwanlingFeederError>;
+}
+
+/// Which rows of a feeder are in use by this process.
+#[derive(Default)]
+struct SwanlingFeederClaims {
+    /// The indexes of the rows checked out.
+    claimed: HashSet<usize>,
+    /// The index of the next row to try, so rows are used in turn.
+    next: usize,
+}
+
+/// The rows of a feeder, and which of them are in use.
+struct SwanlingFeederRows {
+    rows: Vec<String>,
+    claims: Mutex<SwanlingFeederClaims>,
+    /// Wakes users waiting for a row when one is released.
+    released: Notify,
+}
+
+/// The feeders registered with the load test, by name.
+#[derive(Clone, Default)]
+pub(crate) struct SwanlingFeeders {
+    feeders: HashMap<String, Arc<SwanlingFeederRows>>,
+    backend: Option<Arc<dyn SwanlingFeederBackend>>,
+}
+impl SwanlingFeeders {
+    /// Add a feeder, returning false if a feeder with the same name already exists.
+    pub(crate) fn insert(&mut self, name: &str, rows: Vec<String>) -> bool {
+        if self.feeders.contains_key(name) {
+            return false;
+        }
+        self.feeders.insert(
+            name.to_string(),
+            Arc::new(SwanlingFeederRows {
+                rows,
+                claims: Mutex::new(SwanlingFeederClaims::default()),
+                released: Notify::new(),
+            }),
+        );
+        true
+    }
+
+    /// Coordinate which rows are in use with `backend` instead of in this process.
+    pub(crate) fn set_backend(&mut self, backend: Arc<dyn SwanlingFeederBackend>) {
+        self.backend = Some(backend);
+    }
+
+    /// Returns true if feeders are registered without a backend, so rows are only handed
+    /// out once within this process.
+    pub(crate) fn without_backend(&self) -> bool {
+        !self.feeders.is_empty() && self.backend.is_none()
+    }
+
+    /// Returns the feeder with this name, handing out this process's share of the rows of a
+    /// fleet of `fleet` processes.
+    pub(crate) fn get(&self, name: &str, fleet: Option<(usize, usize)>) -> Option<SwanlingFeeder> {
+        self.feeders.get(name).map(|rows| SwanlingFeeder {
+            name: name.to_string(),
+            rows: rows.clone(),
+            backend: self.backend.clone(),
+            fleet,
+        })
+    }
+}
+/// Only display the names of the feeders.
+impl fmt::Debug for SwanlingFeeders {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SwanlingFeeders")
+            .field("feeders", &self.feeders.keys().collect::<Vec<_>>())
+            .field("backend", &self.backend.is_some())
+            .finish()
+    }
+}
+
+/// Rows of test data handed out to one user at a time, see the
+/// [module documentation](./index.html).
+#[derive(Clone)]
+pub struct SwanlingFeeder {
+    name: String,
+    rows: Arc<SwanlingFeederRows>,
+    backend: Option<Arc<dyn SwanlingFeederBackend>>,
+    /// This process's index in a fleet, and the number of processes in the fleet.
+    fleet: Option<(usize, usize)>,
+}
+impl SwanlingFeeder {
+    /// Check out a row no other user is using, waiting until one is released if all rows are
+    /// in use. Returns an error if this process has no rows to hand out.
+    pub async fn checkout(&self) -> Result<SwanlingFeederLease, SwanlingTaskError> {
+        if !(0..self.len()).any(|index| self.in_share(index)) {
+            return Err(self.error("no rows to hand out in this process".to_string()));
+        }
+        loop {
+            // Register interest before trying, so a row released in between isn't missed.
+            let released = self.rows.released.notified();
+            if let Some(lease) = self.try_checkout().await? {
+                return Ok(lease);
+            }
+            match self.backend {
+                // Rows released by other processes don't wake this one.
+                Some(_) => {
+                    let _ = tokio::time::timeout(BACKEND_RETRY, released).await;
+                }
+                None => released.await,
+            }
+        }
+    }
+
+    /// Check out a row no other user is using, or return `None` if all rows are in use.
+    pub async fn try_checkout(&self) -> Result<Option<SwanlingFeederLease>, SwanlingTaskError> {
+        let index = match self.backend.as_ref() {
+            Some(backend) => backend
+                .claim(&self.name, self.rows.rows.len())
+                .await
+                .map_err(|e| self.error(e.to_string()))?
+                .filter(|index| *index < self.rows.rows.len()),
+            None => self.claim(),
+        };
+        Ok(index.map(|index| SwanlingFeederLease {
+            feeder: self.clone(),
+            index,
+            released: false,
+        }))
+    }
+
+    /// Returns how many rows the feeder holds, including rows of other processes in a fleet.
+    pub fn len(&self) -> usize {
+        self.rows.rows.len()
+    }
+
+    /// Returns true if the feeder holds no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.rows.is_empty()
+    }
+
+    /// Claim the next row of this process's share that isn't in use.
+    fn claim(&self) -> Option<usize> {
+        let rows = self.rows.rows.len();
+        let mut claims = self.rows.claims.lock().unwrap();
+        for offset in 0..rows {
+            let index = (claims.next + offset) % rows;
+            if self.in_share(index) && claims.claimed.insert(index) {
+                claims.next = index + 1;
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    /// Returns true if the row is handed out by this process. A backend hands out all rows to
+    /// all processes.
+    fn in_share(&self, index: usize) -> bool {
+        match (self.backend.as_ref(), self.fleet) {
+            (None, Some((fleet_index, fleet_size))) => index % fleet_size == fleet_index,
+            _ => true,
+        }
+    }
+
+    /// Release a row claimed in this process, waking a user waiting for a row.
+    fn unclaim(&self, index: usize) {
+        self.rows.claims.lock().unwrap().claimed.remove(&index);
+        self.rows.released.notify_one();
+    }
+
+    fn error(&self, detail: String) -> SwanlingTaskError {
+        SwanlingTaskError::FeederFailed {
+            name: self.name.clone(),
+            detail,
+        }
+    }
+}
+impl fmt::Debug for SwanlingFeeder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SwanlingFeeder")
+            .field("name", &self.name)
+            .field("rows", &self.rows.rows.len())
+            .finish()
+    }
+}
+
+/// A row checked out of a [`SwanlingFeeder`](./struct.SwanlingFeeder.html), released when
+/// the lease is dropped.
+#[derive(Debug)]
+pub struct SwanlingFeederLease {
+    feeder: SwanlingFeeder,
+    index: usize,
+    released: bool,
+}
+impl SwanlingFeederLease {
+    /// Returns the row checked out.
+    pub fn row(&self) -> &str {
+        &self.feeder.rows.rows[self.index]
+    }
+
+    /// Returns the index of the row checked out.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Release the row so another user can check it out. Errors releasing the row from a
+    /// backend are logged.
+    pub async fn release(mut self) {
+        self.released = true;
+        match self.feeder.backend.as_ref() {
+            Some(backend) => {
+                if let Err(e) = backend.release(&self.feeder.name, self.index).await {
+                    warn!(
+                        "failed to release feeder {} row {}: {}",
+                        self.feeder.name, self.index, e
+                    );
+                }
+                // Wake users of this process waiting for a row.
+                self.feeder.rows.released.notify_one();
+            }
+            None => self.feeder.unclaim(self.index),
+        }
+    }
+}
+impl Drop for SwanlingFeederLease {
+    fn drop(&mut self) {
+        if self.released {
+            return;
+        }
+        match self.feeder.backend.clone() {
+            // Release the row from the backend in the background, as drop can't wait.
+            Some(backend) => {
+                if let Ok(handle) = tokio::runtime::Handle::try_current() {
+                    let feeder = self.feeder.clone();
+                    let index = self.index;
+                    handle.spawn(async move {
+                        if let Err(e) = backend.release(&feeder.name, index).await {
+                            warn!(
+                                "failed to release feeder {} row {}: {}",
+                                feeder.name, index, e
+                            );
+                        }
+                        feeder.rows.released.notify_one();
+                    });
+                }
+            }
+            None => self.feeder.unclaim(self.index),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feeders(rows: usize) -> SwanlingFeeders {
+        let mut feeders = SwanlingFeeders::default();
+        assert!(feeders.insert("accounts", (0..rows).map(|r| r.to_string()).collect()));
+        feeders
+    }
+
+    #[tokio::test]
+    async fn unique_rows() {
+        let feeder = feeders(2).get("accounts", None).unwrap();
+        let first = feeder.try_checkout().await.unwrap().unwrap();
+        let second = feeder.try_checkout().await.unwrap().unwrap();
+        assert_ne!(first.index(), second.index());
+        // All rows are in use.
+        assert!(feeder.try_checkout().await.unwrap().is_none());
+
+        // A released row can be checked out again, also when the lease is dropped.
+        let index = first.index();
+        first.release().await;
+        assert_eq!(feeder.try_checkout().await.unwrap().unwrap().index(), index);
+        drop(second);
+        assert!(feeder.try_checkout().await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn fleet_share() {
+        let feeders = feeders(5);
+        let feeder = feeders.get("accounts", Some((1, 2))).unwrap();
+        let mut leases = Vec::new();
+        while let Some(lease) = feeder.try_checkout().await.unwrap() {
+            leases.push(lease);
+        }
+        let mut rows: Vec<&str> = leases.iter().map(|lease| lease.row()).collect();
+        rows.sort_unstable();
+        assert_eq!(rows, vec!["1", "3"]);
+    }
+
+    #[tokio::test]
+    async fn checkout_waits() {
+        let feeder = feeders(1).get("accounts", None).unwrap();
+        let lease = feeder.try_checkout().await.unwrap().unwrap();
+        let waiting = tokio::spawn({
+            let feeder = feeder.clone();
+            async move { feeder.checkout().await.unwrap().index() }
+        });
+        tokio::task::yield_now().await;
+        lease.release().await;
+        assert_eq!(waiting.await.unwrap(), 0);
+    }
+
+    #[test]
+    fn duplicate_name() {
+        let mut feeders = feeders(1);
+        assert!(!feeders.insert("accounts", vec![]));
+        assert!(feeders.get("missing", None).is_none());
+    }
+}
@@ -0,0 +1,179 @@
+//! Check the hosts being load tested are healthy before and during the load test.
+//!
+//! When `--healthcheck` is set to a path, Swanling requests it from every host being load
+//! tested before starting, and refuses to start if any host doesn't respond with a success
+//! status code. While the load test runs, the hosts are checked again every
+//! `--healthcheck-interval` seconds, without delaying the parent loop. When a host starts or
+//! stops failing the health check a warning is logged and the metrics are annotated, so the
+//! annotation is included in the html report.
+//!
+//! With `--healthcheck-pause`, the load test is paused while every host fails the health
+//! check, instead of recording connection errors that only show the target is down, and
+//! resumed when a host passes the health check again.
+
+use futures::future::join_all;
+use std::collections::BTreeSet;
+use std::time::Duration;
+use url::Url;
+
+use crate::metrics::{SwanlingAnnotation, SwanlingMetrics};
+use crate::util;
+use crate::SwanlingError;
+
+/// How long to wait for a host to respond to the health check, in seconds.
+const HEALTHCHECK_TIMEOUT: u64 = 5;
+
+/// The health check URL of each host, and an explanation if it failed the health check.
+type HealthCheckResults = Vec<(Url, Result<(), String>)>;
+
+/// Checks the hosts being load tested respond to the health check path.
+#[derive(Debug)]
+pub(crate) struct SwanlingHealthCheck {
+    client: reqwest::Client,
+    /// The path requested from each host.
+    path: String,
+    /// The health check URL of each host being load tested.
+    urls: Vec<Url>,
+    /// How often to check the hosts while the load test runs, in seconds, or 0 to only
+    /// check them before starting.
+    interval: usize,
+    /// Whether to pause the load test while every host fails the health check.
+    pub(crate) pause: bool,
+    /// Whether the load test was paused because every host failed the health check.
+    pub(crate) paused: bool,
+    /// Timer tracking when to check the hosts next.
+    timer: std::time::Instant,
+    /// Receives the results of the health check in progress, if any.
+    pending: Option<flume::Receiver<HealthCheckResults>>,
+    /// The hosts that failed the previous health check, so each change is only reported
+    /// once.
+    failing: BTreeSet<Url>,
+}
+impl SwanlingHealthCheck {
+    pub(crate) fn new(path: &str, interval: usize, pause: bool) -> Result<Self, SwanlingError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(HEALTHCHECK_TIMEOUT))
+            .build()?;
+        Ok(SwanlingHealthCheck {
+            client,
+            path: path.to_string(),
+            urls: Vec::new(),
+            interval,
+            pause,
+            paused: false,
+            timer: std::time::Instant::now(),
+            pending: None,
+            failing: BTreeSet::new(),
+        })
+    }
+
+    /// Check every host before starting a load test, failing if any host is unhealthy.
+    pub(crate) async fn preflight(&mut self, hosts: Vec<Url>) -> Result<(), SwanlingError> {
+        let mut urls = Vec::with_capacity(hosts.len());
+        for host in hosts {
+            let url = host
+                .join(&self.path)
+                .map_err(|parse_error| SwanlingError::InvalidHost {
+                    host: host.to_string(),
+                    detail: format!("Failed to add the --healthcheck path {}.", self.path),
+                    parse_error,
+                })?;
+            urls.push(url);
+        }
+        urls.sort();
+        urls.dedup();
+        self.urls = urls;
+        self.paused = false;
+        self.pending = None;
+        self.failing.clear();
+        self.timer = std::time::Instant::now();
+
+        for (url, result) in check(&self.client, &self.urls).await {
+            match result {
+                Ok(()) => info!("{} passed health check", url),
+                Err(detail) => {
+                    return Err(SwanlingError::HealthCheckFailed {
+                        url: url.to_string(),
+                        detail,
+                    })
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Regularly check the hosts in the background while the load test runs. Returns
+    /// whether every host failed the health check each time a check completes.
+    pub(crate) fn poll(&mut self, metrics: &mut SwanlingMetrics) -> Option<bool> {
+        if let Some(pending) = self.pending.as_ref() {
+            return match pending.try_recv() {
+                Ok(results) => {
+                    self.pending = None;
+                    Some(self.record(results, metrics))
+                }
+                Err(flume::TryRecvError::Empty) => None,
+                Err(flume::TryRecvError::Disconnected) => {
+                    self.pending = None;
+                    None
+                }
+            };
+        }
+
+        if !self.urls.is_empty() && util::timer_expired(self.timer, self.interval) {
+            self.timer = std::time::Instant::now();
+            let (results_tx, results_rx) = flume::bounded(1);
+            let client = self.client.clone();
+            let urls = self.urls.clone();
+            tokio::spawn(async move {
+                let _ = results_tx.send_async(check(&client, &urls).await).await;
+            });
+            self.pending = Some(results_rx);
+        }
+
+        None
+    }
+
+    // Report each host that started or stopped failing the health check, and return
+    // whether every host failed it.
+    fn record(&mut self, results: HealthCheckResults, metrics: &mut SwanlingMetrics) -> bool {
+        for (url, result) in &results {
+            match result {
+                Err(detail) if self.failing.insert(url.clone()) => {
+                    warn!("{} failed health check: {}", url, detail);
+                    metrics.annotations.push(SwanlingAnnotation::new(
+                        metrics.started,
+                        &format!("{} failed health check", url),
+                    ));
+                }
+                Ok(()) if self.failing.remove(url) => {
+                    info!("{} passed health check again", url);
+                    metrics.annotations.push(SwanlingAnnotation::new(
+                        metrics.started,
+                        &format!("{} passed health check", url),
+                    ));
+                }
+                _ => (),
+            }
+        }
+
+        !results.is_empty() && results.iter().all(|(_, result)| result.is_err())
+    }
+}
+
+// Request the health check URL of each host at the same time.
+async fn check(client: &reqwest::Client, urls: &[Url]) -> HealthCheckResults {
+    let responses = join_all(urls.iter().map(|url| client.get(url.clone()).send())).await;
+    urls.iter()
+        .cloned()
+        .zip(responses)
+        .map(|(url, response)| {
+            let result = match response {
+                Ok(response) if response.status().is_success() => Ok(()),
+                Ok(response) => Err(format!("responded with {}", response.status())),
+                Err(e) => Err(e.to_string()),
+            };
+            (url, result)
+        })
+        .collect()
+}
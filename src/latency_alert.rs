@@ -0,0 +1,138 @@
+//! Warn when the latency of a request stays too high.
+//!
+//! With `--latency-alert 500ms@30s`, Swanling calculates the 99th percentile response time
+//! of the requests made with each name every second while the load test runs. When it stays
+//! above 500 milliseconds for 30 seconds, a warning is logged, the metrics are annotated so
+//! the alert is included in the html report, and subscribers to
+//! [`SwanlingAttack::subscribe_events`](../struct.SwanlingAttack.html#method.subscribe_events)
+//! receive a [`SwanlingEvent::LatencyAlert`](../enum.SwanlingEvent.html#variant.LatencyAlert).
+//! This way unattended load tests surface problems while they're still running. Each request
+//! is only reported again after its latency recovered.
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+use crate::metrics::{self, SwanlingAnnotation, SwanlingMetrics};
+use crate::util;
+
+/// How often to sample the request metrics, in seconds.
+const SAMPLE_INTERVAL: usize = 1;
+
+/// The response times of a request at the previous sample, and how long its latency has
+/// been too high.
+#[derive(Debug, Default)]
+struct SwanlingLatencySample {
+    /// How often each response time had been seen.
+    times: BTreeMap<usize, usize>,
+    /// How many responses had been seen.
+    counter: usize,
+    /// When the latency first exceeded the bound, if it still does.
+    exceeded: Option<Instant>,
+    /// Whether the request has been reported since its latency exceeded the bound.
+    alerted: bool,
+}
+
+/// Warns when the 99th percentile response time of a request exceeds a bound for too long.
+#[derive(Debug)]
+pub(crate) struct SwanlingLatencyAlert {
+    /// The bound as configured, for example `500ms@30s`.
+    value: String,
+    /// The 99th percentile response time requests can take, in milliseconds.
+    bound: usize,
+    /// How many seconds the bound can be exceeded before warning.
+    window: usize,
+    /// The previous sample of each request, by name.
+    samples: HashMap<String, SwanlingLatencySample>,
+    /// Timer tracking when to take the next sample.
+    timer: Instant,
+}
+impl SwanlingLatencyAlert {
+    pub(crate) fn new(value: &str, bound: usize, window: usize) -> Self {
+        SwanlingLatencyAlert {
+            value: value.to_string(),
+            bound,
+            window,
+            samples: HashMap::new(),
+            timer: Instant::now(),
+        }
+    }
+
+    /// Forget earlier samples when starting a new load test.
+    pub(crate) fn reset(&mut self) {
+        self.samples.clear();
+        self.timer = Instant::now();
+    }
+
+    /// The bound, as the option it was configured with.
+    pub(crate) fn threshold(&self) -> String {
+        format!("--latency-alert {}", self.value)
+    }
+
+    /// Regularly sample the request metrics, returning the name of each request whose 99th
+    /// percentile response time just exceeded the bound for the whole window, and why.
+    pub(crate) fn check(&mut self, metrics: &mut SwanlingMetrics) -> Vec<(String, String)> {
+        if !util::timer_expired(self.timer, SAMPLE_INTERVAL) {
+            return Vec::new();
+        }
+        self.timer = Instant::now();
+
+        let mut alerts = Vec::new();
+        for (name, request) in &metrics.requests {
+            let sample = self.samples.entry(name.to_string()).or_default();
+            // Metrics are reset once all users have started, so start sampling again, still
+            // remembering whether the latency was too high.
+            if request.raw_data.counter < sample.counter {
+                sample.times.clear();
+                sample.counter = 0;
+            }
+            let made = request.raw_data.counter - sample.counter;
+            // Without new responses the latency is unknown, so wait for the next sample.
+            if made == 0 {
+                continue;
+            }
+
+            // Only the responses seen since the previous sample count towards its latency.
+            let times: BTreeMap<usize, usize> = request
+                .raw_data
+                .times
+                .iter()
+                .map(|(time, count)| (*time, count - sample.times.get(time).unwrap_or(&0)))
+                .filter(|(_, count)| *count > 0)
+                .collect();
+            let p99 = metrics::response_time_percentile(&times, made, 0, usize::MAX, 0.99);
+            sample.times = request.raw_data.times.clone();
+            sample.counter = request.raw_data.counter;
+
+            if p99 <= self.bound {
+                if sample.alerted {
+                    info!("p99 latency of {} recovered to {}ms", name, p99);
+                }
+                sample.exceeded = None;
+                sample.alerted = false;
+                continue;
+            }
+            let exceeded = *sample.exceeded.get_or_insert(self.timer);
+            if !sample.alerted && exceeded.elapsed().as_secs() >= self.window as u64 {
+                sample.alerted = true;
+                alerts.push((
+                    name.to_string(),
+                    format!(
+                        "p99 latency of {} is {}ms, exceeding {} for {} seconds",
+                        name,
+                        p99,
+                        self.threshold(),
+                        self.window
+                    ),
+                ));
+            }
+        }
+
+        for (_, detail) in &alerts {
+            warn!("{}", detail);
+            metrics
+                .annotations
+                .push(SwanlingAnnotation::new(metrics.started, detail));
+        }
+        alerts
+    }
+}
@@ -45,7 +45,7 @@
 //!
 //! ```rust
 //! use swanling::swanling::{
-//!     SwanlingTask, SwanlingTaskError, SwanlingTaskFunction, SwanlingTaskResult, SwanlingTaskSet, SwanlingUser,
+//!     SwanlingClientMode, SwanlingTask, SwanlingTaskError, SwanlingTaskFunction, SwanlingTaskResult, SwanlingTaskSet, SwanlingUser,
 //! };
 //! use swanling::metrics::SwanlingMetrics;
 //! use swanling::{
@@ -417,15 +417,39 @@
 #[macro_use]
 extern crate log;
 
+mod churn;
+mod circuit_breaker;
+pub mod configuration_builder;
 pub mod controller;
+pub mod coordination;
+mod dns;
+pub mod feeder;
+mod healthcheck;
+mod latency_alert;
+mod limits;
 pub mod logger;
 #[cfg(feature = "gaggle")]
 mod manager;
 pub mod metrics;
+mod monitor;
+mod notify;
 pub mod prelude;
-mod report;
+mod progress_bar;
+#[cfg(feature = "queue")]
+pub mod queue;
+mod replay;
+pub mod report;
+mod sessions;
+pub mod shared_cache;
+#[cfg(feature = "sql")]
+pub mod sql;
 pub mod swanling;
+pub mod template;
 mod throttle;
+#[cfg(feature = "tui")]
+mod tui;
+#[cfg(feature = "object-storage")]
+mod upload;
 mod user;
 pub mod util;
 #[cfg(feature = "gaggle")]
@@ -437,29 +461,35 @@ use lazy_static::lazy_static;
 #[cfg(feature = "gaggle")]
 use nng::Socket;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{thread_rng, Rng};
 use serde::{Deserialize, Serialize};
 use simplelog::*;
 use std::collections::hash_map::DefaultHasher;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 use std::sync::{
     atomic::{AtomicBool, AtomicUsize, Ordering},
     Arc,
 };
-use std::{fmt, io, time};
+use std::{fmt, future::Future, io, time};
 use tokio::fs::File;
-use tokio::runtime::Runtime;
+use tokio::runtime::{Handle, Runtime};
 
-use crate::controller::{SwanlingControllerProtocol, SwanlingControllerRequest};
+use crate::controller::{
+    SwanlingAttackHandle, SwanlingControllerProtocol, SwanlingControllerRequest,
+};
 use crate::logger::{SwanlingLogFormat, SwanlingLoggerJoinHandle, SwanlingLoggerTx};
-use crate::metrics::{SwanlingCoordinatedOmissionMitigation, SwanlingMetric, SwanlingMetrics};
+use crate::metrics::{
+    SwanlingAnnotation, SwanlingCoordinatedOmissionMitigation, SwanlingMetric, SwanlingMetrics,
+    SwanlingMetricsFormat, SwanlingRequestMetric,
+};
 use crate::swanling::{
-    GaggleUser, SwanlingTask, SwanlingTaskSet, SwanlingUser, SwanlingUserCommand,
+    new_uuid, GaggleUser, SwanlingClientMode, SwanlingHooks, SwanlingRunningTasks,
+    SwanlingSharedData, SwanlingTask, SwanlingTaskSet, SwanlingUser, SwanlingUserCommand,
 };
 #[cfg(feature = "gaggle")]
-use crate::worker::{register_shutdown_pipe_handler, GaggleMetrics};
+use crate::worker::register_shutdown_pipe_handler;
 
 /// Constant defining Swanling's default port when running a Regatta.
 const DEFAULT_PORT: &str = "5115";
@@ -470,6 +500,55 @@ const DEFAULT_TELNET_PORT: &str = "5116";
 /// Constant defining Swanling's default WebSocket Controller port.
 const DEFAULT_WEBSOCKET_PORT: &str = "5117";
 
+/// Options that only take effect when Swanling launches, so can't be changed by a
+/// Controller.
+const STARTUP_OPTIONS: &[&str] = &[
+    "help",
+    "version",
+    "list",
+    "format",
+    "dry_run",
+    "debug_single_user",
+    "swanling_log",
+    "log_level",
+    "verbose",
+    "tui",
+    "run_id",
+    "run_header",
+    "task_headers",
+    "upload_url",
+    "notify_url",
+    "upload_interval",
+    "merge_reports",
+    "reports",
+    "runtime_threads",
+    "healthcheck",
+    "healthcheck_interval",
+    "healthcheck_pause",
+    "abort_on_error_rate",
+    "latency_alert",
+    "user_churn",
+    "no_telnet",
+    "telnet_host",
+    "telnet_port",
+    "no_websocket",
+    "websocket_host",
+    "websocket_port",
+    "no_autostart",
+    "manager",
+    "expect_workers",
+    "no_hash_check",
+    "hot_add_workers",
+    "manager_also_worker",
+    "manager_bind_host",
+    "manager_bind_port",
+    "worker",
+    "manager_host",
+    "manager_port",
+    "fleet_index",
+    "fleet_size",
+];
+
 // WORKER_ID is only used when running a gaggle (a distributed load test).
 lazy_static! {
     static ref WORKER_ID: AtomicUsize = AtomicUsize::new(0);
@@ -498,7 +577,6 @@ pub fn get_worker_id() -> usize {
 pub(crate) struct Socket {}
 
 /// An enumeration of all errors a [`SwanlingAttack`](./struct.SwanlingAttack.html) can return.
-#[derive(Debug)]
 pub enum SwanlingError {
     /// Wraps a [`std::io::Error`](https://doc.rust-lang.org/std/io/struct.Error.html).
     Io(io::Error),
@@ -514,6 +592,13 @@ pub enum SwanlingError {
         /// An optional explanation of the error.
         detail: String,
     },
+    /// A host being load tested failed the `--healthcheck`.
+    HealthCheckFailed {
+        /// The health check URL that failed.
+        url: String,
+        /// An optional explanation of the error.
+        detail: String,
+    },
     /// Failed to parse a hostname.
     InvalidHost {
         /// The invalid hostname that caused this error.
@@ -548,11 +633,23 @@ pub enum SwanlingError {
         /// An optional explanation of the error.
         detail: String,
     },
+    /// More than one problem was found validating the load test and its configuration,
+    /// all reported together.
+    InvalidConfiguration {
+        /// Each problem found.
+        errors: Vec<SwanlingError>,
+    },
     /// [`SwanlingAttack`](./struct.SwanlingAttack.html) has no [`SwanlingTaskSet`](./swanling/struct.SwanlingTaskSet.html) defined.
     NoTaskSets {
         /// An optional explanation of the error.
         detail: String,
     },
+    /// The `--help` or `--version` flag was set, so information was requested instead of
+    /// running the load test. Swanling doesn't display it, leaving that to the application.
+    Help {
+        /// The requested information, ready to be displayed.
+        message: String,
+    },
 }
 /// Implement a helper to provide a text description of all possible types of errors.
 impl SwanlingError {
@@ -562,11 +659,31 @@ impl SwanlingError {
             SwanlingError::Reqwest(_) => "reqwest::Error",
             SwanlingError::TokioJoin(_) => "tokio::task::JoinError",
             SwanlingError::FeatureNotEnabled { .. } => "required compile-time feature not enabled",
+            SwanlingError::HealthCheckFailed { .. } => "health check failed",
             SwanlingError::InvalidHost { .. } => "failed to parse hostname",
             SwanlingError::InvalidOption { .. } => "invalid option or value specified",
+            SwanlingError::InvalidConfiguration { .. } => "invalid configuration",
             SwanlingError::InvalidWaitTime { .. } => "invalid wait_time specified",
             SwanlingError::InvalidWeight { .. } => "invalid weight specified",
             SwanlingError::NoTaskSets { .. } => "no task sets defined",
+            SwanlingError::Help { .. } => "help requested",
+        }
+    }
+
+    // Add the problems this error reports to a list of problems.
+    fn collect_into(self, errors: &mut Vec<SwanlingError>) {
+        match self {
+            SwanlingError::InvalidConfiguration { errors: collected } => errors.extend(collected),
+            error => errors.push(error),
+        }
+    }
+
+    // Report the problems found validating a load test together, if any.
+    fn from_errors(mut errors: Vec<SwanlingError>) -> Result<(), SwanlingError> {
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(SwanlingError::InvalidConfiguration { errors }),
         }
     }
 }
@@ -588,11 +705,95 @@ impl fmt::Display for SwanlingError {
             SwanlingError::InvalidHost {
                 ref parse_error, ..
             } => write!(f, "SwanlingError: {} ({})", self.describe(), parse_error),
+            SwanlingError::HealthCheckFailed {
+                ref url,
+                ref detail,
+            } => write!(
+                f,
+                "SwanlingError: {} ({}: {})",
+                self.describe(),
+                url,
+                detail
+            ),
+            SwanlingError::Help { ref message } => write!(f, "{}", message),
+            SwanlingError::InvalidConfiguration { ref errors } => {
+                write!(f, "SwanlingError: {}:", self.describe())?;
+                for error in errors {
+                    write!(f, "\n - {:?}", error)?;
+                }
+                Ok(())
+            }
             _ => write!(f, "SwanlingError: {}", self.describe()),
         }
     }
 }
 
+/// Implement debug formatting by hand, so the information requested with `--help` is
+/// displayed as is when returned from `main()`.
+impl fmt::Debug for SwanlingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SwanlingError::Io(source) => f.debug_tuple("Io").field(source).finish(),
+            SwanlingError::Reqwest(source) => f.debug_tuple("Reqwest").field(source).finish(),
+            SwanlingError::TokioJoin(source) => f.debug_tuple("TokioJoin").field(source).finish(),
+            SwanlingError::FeatureNotEnabled { feature, detail } => f
+                .debug_struct("FeatureNotEnabled")
+                .field("feature", feature)
+                .field("detail", detail)
+                .finish(),
+            SwanlingError::HealthCheckFailed { url, detail } => f
+                .debug_struct("HealthCheckFailed")
+                .field("url", url)
+                .field("detail", detail)
+                .finish(),
+            SwanlingError::InvalidHost {
+                host,
+                detail,
+                parse_error,
+            } => f
+                .debug_struct("InvalidHost")
+                .field("host", host)
+                .field("detail", detail)
+                .field("parse_error", parse_error)
+                .finish(),
+            SwanlingError::InvalidOption {
+                option,
+                value,
+                detail,
+            } => f
+                .debug_struct("InvalidOption")
+                .field("option", option)
+                .field("value", value)
+                .field("detail", detail)
+                .finish(),
+            SwanlingError::InvalidWaitTime {
+                min_wait,
+                max_wait,
+                detail,
+            } => f
+                .debug_struct("InvalidWaitTime")
+                .field("min_wait", min_wait)
+                .field("max_wait", max_wait)
+                .field("detail", detail)
+                .finish(),
+            SwanlingError::InvalidWeight { weight, detail } => f
+                .debug_struct("InvalidWeight")
+                .field("weight", weight)
+                .field("detail", detail)
+                .finish(),
+            SwanlingError::InvalidConfiguration { errors } => f
+                .debug_struct("InvalidConfiguration")
+                .field("errors", errors)
+                .finish(),
+            SwanlingError::NoTaskSets { detail } => f
+                .debug_struct("NoTaskSets")
+                .field("detail", detail)
+                .finish(),
+            SwanlingError::Help { message } => write!(f, "{}", message),
+        }
+    }
+}
+
 // Define the lower level source of this error, if any.
 impl std::error::Error for SwanlingError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
@@ -643,7 +844,7 @@ pub enum AttackMode {
     Worker,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 /// A [`SwanlingAttack`](./struct.SwanlingAttack.html) load test moves through each of the following
 /// phases during a complete load test.
 pub enum AttackPhase {
@@ -660,6 +861,127 @@ pub enum AttackPhase {
     Shutdown,
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// A task set registered in a [`SwanlingAttack`](./struct.SwanlingAttack.html), returned by
+/// [`SwanlingAttack::list`](./struct.SwanlingAttack.html#method.list).
+pub struct SwanlingTaskSetListing {
+    /// The name of the task set.
+    pub name: String,
+    /// How many users run the task set relative to other task sets.
+    pub weight: usize,
+    /// How many users run the task set, if fixed instead of weighted.
+    pub fixed_users: Option<usize>,
+    /// The host the task set runs against, if set with `set_host()` or `set_base_url()`.
+    pub host: Option<String>,
+    /// The tasks in the task set, in the order they were registered.
+    pub tasks: Vec<SwanlingTaskListing>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// A task registered in a task set, part of a
+/// [`SwanlingTaskSetListing`](./struct.SwanlingTaskSetListing.html).
+pub struct SwanlingTaskListing {
+    /// The name of the task, empty if it's not named.
+    pub name: String,
+    /// How often the task runs relative to other tasks in the task set.
+    pub weight: usize,
+    /// When the task runs compared to other tasks in the task set, 0 if not sequenced.
+    pub sequence: usize,
+    /// Whether the task runs when the user starts.
+    pub on_start: bool,
+    /// Whether the task runs when the user stops.
+    pub on_stop: bool,
+    /// How often the task runs, if it's scheduled instead of weighted.
+    pub schedule: Option<std::time::Duration>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// Where a [`SwanlingAttack`](./struct.SwanlingAttack.html) is in its lifecycle, returned by
+/// [`SwanlingAttack::progress`](./struct.SwanlingAttack.html#method.progress),
+/// [`SwanlingAttackHandle::status`](./controller/struct.SwanlingAttackHandle.html#method.status)
+/// and the `status` Controller command.
+pub struct SwanlingProgress {
+    /// The phase the load test is in.
+    pub phase: AttackPhase,
+    /// Whether the load test is paused.
+    pub paused: bool,
+    /// How many seconds the load test has been running, not counting time spent paused.
+    pub elapsed: usize,
+    /// How many seconds the load test will run before stopping, if `--run-time` is set.
+    pub remaining: Option<usize>,
+    /// How many [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s have launched.
+    pub users: usize,
+    /// How many [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s will launch.
+    pub target_users: usize,
+}
+/// Display the progress of the load test, one value per line.
+impl fmt::Display for SwanlingProgress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "phase: {:?}", self.phase)?;
+        writeln!(f, "paused: {}", self.paused)?;
+        writeln!(f, "elapsed: {}s", self.elapsed)?;
+        match self.remaining {
+            Some(remaining) => writeln!(f, "remaining: {}s", remaining)?,
+            None => writeln!(f, "remaining: unlimited")?,
+        }
+        write!(f, "users: {}/{}", self.users, self.target_users)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Lifecycle events of a [`SwanlingAttack`](./struct.SwanlingAttack.html), received by
+/// subscribing with
+/// [`SwanlingAttack::subscribe_events`](./struct.SwanlingAttack.html#method.subscribe_events).
+pub enum SwanlingEvent {
+    /// A [`SwanlingUser`](./swanling/struct.SwanlingUser.html) was launched.
+    UserStarted {
+        /// The number of the user, starting at 1 as in the logs.
+        user: usize,
+        /// The name of the [`SwanlingTaskSet`](./swanling/struct.SwanlingTaskSet.html) the user
+        /// is running, or `replay` for users replaying a request log.
+        task_set: String,
+    },
+    /// A [`SwanlingUser`](./swanling/struct.SwanlingUser.html) exited, either because the load
+    /// test is stopping or because the user was removed from the load test.
+    UserStopped {
+        /// The number of the user, starting at 1 as in the logs.
+        user: usize,
+        /// The name of the [`SwanlingTaskSet`](./swanling/struct.SwanlingTaskSet.html) the user
+        /// was running, or `replay` for users replaying a request log.
+        task_set: String,
+    },
+    /// The load test entered a new [`AttackPhase`](./enum.AttackPhase.html).
+    PhaseChanged(AttackPhase),
+    /// A Controller or `--healthcheck-pause` paused the running load test.
+    Paused,
+    /// A Controller or `--healthcheck-pause` resumed the paused load test.
+    Resumed,
+    /// The load test exceeded a threshold, and is stopping.
+    ThresholdBreached {
+        /// The threshold that was exceeded, such as `--abort-on-error-rate 25%@30s`.
+        threshold: String,
+        /// How the threshold was exceeded.
+        detail: String,
+    },
+    /// The 99th percentile response time of a request exceeded `--latency-alert` for too
+    /// long. The load test keeps running.
+    LatencyAlert {
+        /// The name of the request, such as `GET /`.
+        request: String,
+        /// The bound that was exceeded, such as `--latency-alert 500ms@30s`.
+        threshold: String,
+        /// How the bound was exceeded.
+        detail: String,
+    },
+}
+
+// Send an event to all subscribers, ignoring subscribers that stopped listening.
+fn send_event(events: &[flume::Sender<SwanlingEvent>], event: SwanlingEvent) {
+    for subscriber in events {
+        let _ = subscriber.send(event.clone());
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 /// Used to define the order [`SwanlingTaskSet`](./swanling/struct.SwanlingTaskSet.html)s and
 /// [`SwanlingTask`](./swanling/struct.SwanlingTask.html)s are allocated.
@@ -686,6 +1008,10 @@ pub struct SwanlingDefaults {
     users: Option<usize>,
     /// An optional default number of clients to start per second.
     hatch_rate: Option<String>,
+    /// An optional default number of clients to start at a time.
+    hatch_batch: Option<usize>,
+    /// An optional default for how much to randomly shift when each batch starts.
+    hatch_jitter: Option<String>,
     /// An optional default number of seconds for the test to run.
     run_time: Option<usize>,
     /// An optional default log level.
@@ -696,6 +1022,16 @@ pub struct SwanlingDefaults {
     verbose: Option<u8>,
     /// An optional default for printing running metrics.
     running_metrics: Option<usize>,
+    /// An optional default for the format running metrics are displayed in.
+    running_metrics_format: Option<SwanlingMetricsFormat>,
+    /// An optional default for only displaying the final metrics summary.
+    summary_only: Option<bool>,
+    /// An optional default for the format the final metrics summary is displayed in.
+    summary_format: Option<SwanlingMetricsFormat>,
+    /// An optional default for the response time percentiles reported.
+    percentiles: Option<String>,
+    /// An optional default for displaying a live dashboard.
+    tui: Option<bool>,
     /// An optional default for not resetting metrics after all users started.
     no_reset_metrics: Option<bool>,
     /// An optional default for not tracking metrics.
@@ -704,12 +1040,28 @@ pub struct SwanlingDefaults {
     no_task_metrics: Option<bool>,
     /// An optional default for not displaying an error summary.
     no_error_summary: Option<bool>,
+    /// An optional default for the maximum number of unique request names tracked.
+    max_request_names: Option<usize>,
+    /// An optional default for how often users send aggregated metrics to the parent.
+    metrics_flush: Option<String>,
     /// An optional default for the html-formatted report file name.
     report_file: Option<String>,
+    /// An optional default for how often to write report snapshots.
+    report_interval: Option<String>,
+    /// An optional default for the latency histogram bucket boundaries in the report.
+    histogram_buckets: Option<String>,
+    /// An optional default for the object storage URL metrics are uploaded to.
+    upload_url: Option<String>,
+    /// An optional default for how often metrics snapshots are uploaded.
+    upload_interval: Option<usize>,
+    /// An optional default for the webhook notified of load test events.
+    notify_url: Option<String>,
     /// An optional default for the requests log file name.
     request_log: Option<String>,
     /// An optional default for the requests log file format.
     request_format: Option<SwanlingLogFormat>,
+    /// An optional default for the raw request latencies file name.
+    record_raw_latencies: Option<String>,
     /// An optional default for the tasks log file name.
     task_log: Option<String>,
     /// An optional default for the tasks log file format.
@@ -718,32 +1070,99 @@ pub struct SwanlingDefaults {
     error_log: Option<String>,
     /// An optional default for the error log format.
     error_format: Option<SwanlingLogFormat>,
+    /// An optional default for how long to aggregate identical errors in the error log.
+    error_log_window: Option<String>,
     /// An optional default for the debug log file name.
     debug_log: Option<String>,
     /// An optional default for the debug log format.
     debug_format: Option<SwanlingLogFormat>,
     /// An optional default for not logging response body in debug log.
     no_debug_body: Option<bool>,
+    /// An optional default for how many bytes of response bodies to include in the debug log.
+    debug_body_max_bytes: Option<usize>,
+    /// Optional default regular expressions redacted from the debug log.
+    debug_redact: Vec<String>,
     /// An optional default for not enabling telnet Controller thread.
     no_telnet: Option<bool>,
     /// An optional default for not enabling WebSocket Controller thread.
     no_websocket: Option<bool>,
     /// An optional default for not auto-starting the load test.
     no_autostart: Option<bool>,
+    /// An optional default for when to start the load test.
+    start_at: Option<String>,
     /// An optional default for coordinated omission mitigation.
     co_mitigation: Option<SwanlingCoordinatedOmissionMitigation>,
     /// An optional default to track additional status code metrics.
     status_codes: Option<bool>,
+    /// An optional default for sending the run ID in a header with each request.
+    run_header: Option<bool>,
+    /// An optional default for sending headers identifying the task and user with each
+    /// request.
+    task_headers: Option<bool>,
     /// An optional default maximum requests per second.
     throttle_requests: Option<usize>,
+    /// An optional default for how the maximum requests per second changes over time.
+    throttle_plan: Option<String>,
+    /// An optional default number of tokio worker threads.
+    runtime_threads: Option<usize>,
+    /// An optional default seed for the random number generator of each user.
+    seed: Option<u64>,
+    /// An optional default for requesting uncompressed responses.
+    no_gzip: Option<bool>,
+    /// An optional default for the Accept-Encoding header sent with requests.
+    accept_encoding: Option<String>,
+    /// An optional default for the file of user agents users pick from.
+    user_agent_file: Option<String>,
+    /// An optional default for delaying a percentage of requests.
+    inject_latency: Option<String>,
+    /// An optional default for aborting a percentage of requests.
+    inject_abort: Option<String>,
+    /// An optional default for the DNS server used to resolve hostnames.
+    dns_server: Option<String>,
+    /// An optional default for resolving hostnames for every request.
+    no_dns_cache: Option<bool>,
+    /// An optional default for how many requests each user makes before resolving hostnames again.
+    dns_refresh: Option<usize>,
+    /// An optional default for the path requested to check hosts are healthy.
+    healthcheck: Option<String>,
+    /// An optional default for how often hosts are checked while the load test runs.
+    healthcheck_interval: Option<usize>,
+    /// An optional default for pausing the load test while all hosts are unhealthy.
+    healthcheck_pause: Option<bool>,
+    /// An optional default for stopping the load test when too many requests fail.
+    abort_on_error_rate: Option<String>,
+    /// An optional default for warning when the latency of a request stays too high.
+    latency_alert: Option<String>,
+    /// An optional default for how long to wait for users to exit when stopping.
+    shutdown_timeout: Option<String>,
+    /// An optional default for how often running users are retired and replaced.
+    user_churn: Option<String>,
+    /// An optional default for the file users' cookies are saved to and restored from.
+    save_sessions: Option<String>,
     /// An optional default to follows base_url redirect with subsequent request.
     sticky_follow: Option<bool>,
+    /// An optional default for the cookie used to pin users to a backend.
+    sticky_cookie: Option<String>,
+    /// An optional default for the header used to pin users to a backend.
+    sticky_header: Option<String>,
+    /// An optional default for the backends users are pinned to.
+    sticky_backends: Option<String>,
+    /// An optional default for the response header identifying which backend served a request.
+    backend_header: Option<String>,
+    /// An optional default for the request log file to replay.
+    replay_log: Option<String>,
+    /// An optional default for the speed factor used to replay a request log.
+    replay_speed: Option<String>,
     /// An optional default to enable Manager mode.
     manager: Option<bool>,
     /// An optional default for number of Workers to expect.
     expect_workers: Option<u16>,
     /// An optional default for Manager to ignore load test checksum.
     no_hash_check: Option<bool>,
+    /// An optional default for Manager to assign users to Workers connecting late.
+    hot_add_workers: Option<bool>,
+    /// An optional default for Manager to also run a Worker in the same process.
+    manager_also_worker: Option<bool>,
     /// An optional default for host telnet Controller listens on.
     telnet_host: Option<String>,
     /// An optional default for port telnet Controller listens on.
@@ -762,6 +1181,10 @@ pub struct SwanlingDefaults {
     manager_host: Option<String>,
     /// An optional default for port Worker connects to.
     manager_port: Option<u16>,
+    /// An optional default for this process's index in a fleet.
+    fleet_index: Option<usize>,
+    /// An optional default for the number of processes in a fleet.
+    fleet_size: Option<usize>,
 }
 
 /// Allows the optional configuration of Swanling's defaults.
@@ -773,6 +1196,10 @@ pub enum SwanlingDefault {
     Users,
     /// An optional default number of clients to start per second.
     HatchRate,
+    /// An optional default number of clients to start at a time.
+    HatchBatch,
+    /// An optional default for how much to randomly shift when each batch starts.
+    HatchJitter,
     /// An optional default number of seconds for the test to run.
     RunTime,
     /// An optional default log level.
@@ -783,6 +1210,16 @@ pub enum SwanlingDefault {
     Verbose,
     /// An optional default for printing running metrics.
     RunningMetrics,
+    /// An optional default for the format running metrics are displayed in.
+    RunningMetricsFormat,
+    /// An optional default for only displaying the final metrics summary.
+    SummaryOnly,
+    /// An optional default for the format the final metrics summary is displayed in.
+    SummaryFormat,
+    /// An optional default for the comma-separated response time percentiles reported.
+    Percentiles,
+    /// An optional default for displaying a live dashboard, requires the `tui` feature.
+    Tui,
     /// An optional default for not resetting metrics after all users started.
     NoResetMetrics,
     /// An optional default for not tracking metrics.
@@ -791,12 +1228,31 @@ pub enum SwanlingDefault {
     NoTaskMetrics,
     /// An optional default for not displaying an error summary.
     NoErrorSummary,
+    /// An optional default for the maximum number of unique request names tracked.
+    MaxRequestNames,
+    /// An optional default for how often users send aggregated metrics to the parent.
+    MetricsFlush,
     /// An optional default for the report file name.
     ReportFile,
+    /// An optional default for how often to write timestamped snapshots of the report while
+    /// the load test runs, for example `5m`.
+    ReportInterval,
+    /// An optional default for the comma-separated latency histogram bucket boundaries
+    /// in the report, in milliseconds.
+    HistogramBuckets,
+    /// An optional default for the object storage URL metrics and the report are uploaded
+    /// to, requires the `object-storage` feature.
+    UploadUrl,
+    /// An optional default for how often metrics snapshots are uploaded, in seconds.
+    UploadInterval,
+    /// An optional default for the webhook URL load test events are posted to.
+    NotifyUrl,
     /// An optional default for the request log file name.
     RequestLog,
     /// An optional default for the request log file format.
     RequestFormat,
+    /// An optional default for the raw request latencies file name.
+    RecordRawLatencies,
     /// An optional default for the task log file name.
     TaskLog,
     /// An optional default for the task log file format.
@@ -805,12 +1261,21 @@ pub enum SwanlingDefault {
     ErrorLog,
     /// An optional default for the error log format.
     ErrorFormat,
+    /// An optional default for how long to aggregate identical errors in the error log, for
+    /// example `1s`.
+    ErrorLogWindow,
     /// An optional default for the debug log file name.
     DebugLog,
     /// An optional default for the debug log format.
     DebugFormat,
     /// An optional default for not logging the response body in the debug log.
     NoDebugBody,
+    /// An optional default for how many bytes of response bodies to include in the debug
+    /// log.
+    DebugBodyMaxBytes,
+    /// An optional default regular expression redacted from headers and bodies in the debug
+    /// log. Can be set more than once to redact several patterns.
+    DebugRedact,
     /// An optional default for not enabling telnet Controller thread.
     NoTelnet,
     /// An optional default for not enabling WebSocket Controller thread.
@@ -819,18 +1284,82 @@ pub enum SwanlingDefault {
     CoordinatedOmissionMitigation,
     /// An optional default for not automatically starting load test.
     NoAutoStart,
+    /// An optional default for when to start the load test, an RFC 3339 timestamp such as
+    /// `2024-06-01T02:00:00Z`.
+    StartAt,
     /// An optional default to track additional status code metrics.
     StatusCodes,
     /// An optional default maximum requests per second.
     ThrottleRequests,
+    /// An optional default number of tokio worker threads.
+    RuntimeThreads,
+    /// An optional default seed for the random number generator of each user.
+    Seed,
+    /// An optional default for requesting uncompressed responses.
+    NoGzip,
+    /// An optional default for the Accept-Encoding header sent with requests.
+    AcceptEncoding,
+    /// An optional default for the file of user agents users pick from.
+    UserAgentFile,
+    /// An optional default for delaying a percentage of requests.
+    InjectLatency,
+    /// An optional default for aborting a percentage of requests.
+    InjectAbort,
+    /// An optional default for the DNS server used to resolve hostnames.
+    DnsServer,
+    /// An optional default for resolving hostnames for every request.
+    NoDnsCache,
+    /// An optional default for how many requests each user makes before resolving hostnames again.
+    DnsRefresh,
+    /// An optional default for the path requested to check hosts are healthy.
+    Healthcheck,
+    /// An optional default for how often hosts are checked while the load test runs, in
+    /// seconds.
+    HealthcheckInterval,
+    /// An optional default for pausing the load test while all hosts are unhealthy.
+    HealthcheckPause,
+    /// An optional default for stopping the load test when too many requests fail, such as
+    /// `25%@30s`.
+    AbortOnErrorRate,
+    /// An optional default for warning when the 99th percentile response time of a request
+    /// stays too high, such as `500ms@30s`.
+    LatencyAlert,
+    /// An optional default for how long to wait for users to exit when stopping, such as
+    /// `60s`.
+    ShutdownTimeout,
+    /// An optional default for how often running users are retired and replaced, such as
+    /// `5/min`.
+    UserChurn,
+    /// An optional default for how the maximum requests per second changes over time, such
+    /// as `100:5m,500:10m,1000`.
+    ThrottlePlan,
+    /// An optional default for the file users' cookies are saved to when the load test
+    /// stops, and restored from when it starts.
+    SaveSessions,
     /// An optional default to follows base_url redirect with subsequent request.
     StickyFollow,
+    /// An optional default for the cookie used to pin users to a backend.
+    StickyCookie,
+    /// An optional default for the header used to pin users to a backend.
+    StickyHeader,
+    /// An optional default for the backends users are pinned to.
+    StickyBackends,
+    /// An optional default for the response header identifying which backend served a request.
+    BackendHeader,
+    /// An optional default for the request log file to replay.
+    ReplayLog,
+    /// An optional default for the speed factor used to replay a request log.
+    ReplaySpeed,
     /// An optional default to enable Manager mode.
     Manager,
     /// An optional default for number of Workers to expect.
     ExpectWorkers,
     /// An optional default for Manager to ignore load test checksum.
     NoHashCheck,
+    /// An optional default for Manager to assign users to Workers connecting late.
+    HotAddWorkers,
+    /// An optional default for Manager to also run a Worker in the same process.
+    ManagerAlsoWorker,
     /// An optional default for host telnet Controller listens on.
     TelnetHost,
     /// An optional default for port telnet Controller listens on.
@@ -849,6 +1378,16 @@ pub enum SwanlingDefault {
     ManagerHost,
     /// An optional default for port Worker connects to.
     ManagerPort,
+    /// An optional default for this process's index in a fleet.
+    FleetIndex,
+    /// An optional default for the number of processes in a fleet.
+    FleetSize,
+    /// An optional default for sending the run ID in an `X-Load-Test-Run` header with each
+    /// request.
+    RunHeader,
+    /// An optional default for sending `X-Swanling-Task` and `X-Swanling-User` headers
+    /// identifying the task and user with each request.
+    TaskHeaders,
 }
 
 #[derive(Debug)]
@@ -863,6 +1402,12 @@ struct SwanlingAttackRunState {
     /// A counter tracking which [`SwanlingUser`](./swanling/struct.SwanlingUser.html) is being
     /// spawned.
     spawn_user_counter: usize,
+    /// How many more [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s to spawn in the
+    /// current batch.
+    spawn_batch_remaining: usize,
+    /// How many milliseconds in total spawning [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s
+    /// has fallen behind the hatch rate.
+    spawn_drift: u64,
     /// This variable accounts for time spent doing things which is then subtracted from
     /// the time sleeping to avoid an unintentional drift in events that are supposed to
     /// happen regularly.
@@ -881,6 +1426,8 @@ struct SwanlingAttackRunState {
     /// Optional receiver for all [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s from
     /// throttle thread, if enabled.
     throttle_threads_tx: Option<flume::Sender<bool>>,
+    /// Optional unbuffered sender for high priority requests to throttle thread, if enabled.
+    throttle_priority_tx: Option<flume::Sender<bool>>,
     /// Optional sender for throttle thread, if enabled.
     parent_to_throttle_tx: Option<flume::Sender<bool>>,
     /// Optional channel allowing controller thread to make requests, if not disabled.
@@ -892,14 +1439,27 @@ struct SwanlingAttackRunState {
     metrics_header_displayed: bool,
     /// When entering the idle phase use this flag to only display a message one time.
     idle_status_displayed: bool,
+    /// A Controller started the load test, which waits in the idle phase until `--start-at`.
+    start_scheduled: bool,
     /// Collection of all [`SwanlingUser`](./swanling/struct.SwanlingUser.html) threads so they
     /// can be stopped later.
     users: Vec<tokio::task::JoinHandle<()>>,
+    /// The number and task set of each launched user, and the tasks it's running, to report
+    /// users that don't exit when the load test stops. The metrics each user hasn't sent yet
+    /// are kept to collect them if it's aborted.
+    user_tasks: Vec<(
+        usize,
+        String,
+        SwanlingRunningTasks,
+        Arc<std::sync::Mutex<metrics::SwanlingMetricsBatch>>,
+    )>,
     /// All unbounded senders to allow communication with
     /// [`SwanlingUser`](./swanling/struct.SwanlingUser.html) threads.
     user_channels: Vec<flume::Sender<SwanlingUserCommand>>,
     /// Timer tracking when to display running metrics, if enabled.
     running_metrics_timer: std::time::Instant,
+    /// Timer tracking when to write a report snapshot, if enabled.
+    report_snapshot_timer: std::time::Instant,
     /// Boolean flag indicating if running metrics should be displayed.
     display_running_metrics: bool,
     /// Boolean flag indicating if all [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s
@@ -910,8 +1470,49 @@ struct SwanlingAttackRunState {
     /// Thread-safe boolean flag indicating if the [`SwanlingAttack`](./struct.SwanlingAttack.html)
     /// has been canceled.
     canceled: Arc<AtomicBool>,
+    /// Flag set when ctrl-c is caught again while stopping, to stop waiting for users to exit.
+    forced: coordination::SwanlingFlag,
     /// Optional socket used to coordinate a distributed Regatta.
     socket: Option<Socket>,
+    /// Optional live dashboard, restoring the terminal when dropped.
+    #[cfg(feature = "tui")]
+    tui: Option<tui::SwanlingTui>,
+    /// Optional uploader writing metrics and the report to object storage.
+    #[cfg(feature = "object-storage")]
+    uploader: Option<upload::SwanlingUploader>,
+    /// Optional notifier posting load test events to a webhook.
+    notifier: Option<notify::SwanlingNotifier>,
+    /// Optional monitor sampling the resources used by the load generator.
+    monitor: Option<monitor::SwanlingMonitor>,
+    /// Optional health check of the hosts being load tested.
+    healthcheck: Option<healthcheck::SwanlingHealthCheck>,
+    /// Optional circuit breaker stopping the load test when too many requests fail.
+    circuit_breaker: Option<circuit_breaker::SwanlingCircuitBreaker>,
+    /// Optional watchdog warning when the latency of a request stays too high.
+    latency_alert: Option<latency_alert::SwanlingLatencyAlert>,
+    /// Optional churn retiring running users to replace them with fresh users.
+    user_churn: Option<churn::SwanlingUserChurn>,
+    /// Optional progress bar displayed between running metrics.
+    progress_bar: Option<progress_bar::SwanlingProgressBar>,
+    /// Tasks merging the batches of request metrics sent by
+    /// [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s.
+    metrics_shards: metrics::SwanlingMetricsShards,
+}
+
+/// The tokio runtime a load test runs on.
+enum SwanlingRuntime {
+    /// A runtime built by Swanling.
+    Built(Runtime),
+    /// A runtime supplied with `set_runtime()`.
+    Supplied(Handle),
+}
+impl SwanlingRuntime {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        match self {
+            SwanlingRuntime::Built(runtime) => runtime.block_on(future),
+            SwanlingRuntime::Supplied(handle) => handle.block_on(future),
+        }
+    }
 }
 
 /// Global internal state for the load test.
@@ -942,12 +1543,50 @@ pub struct SwanlingAttack {
     scheduler: SwanlingScheduler,
     /// When the load test started.
     started: Option<time::Instant>,
+    /// When a Controller paused the load test, if it's paused.
+    paused: Option<time::Instant>,
     /// All metrics merged together.
     metrics: SwanlingMetrics,
+    /// Requests loaded from a request log, replayed instead of running task sets.
+    replay_requests: Vec<replay::SwanlingReplayRequest>,
+    /// How many times faster than originally made requests are replayed.
+    replay_speed: f32,
+    /// User agents loaded from `--user-agent-file`, for task sets without their own.
+    user_agents: Vec<String>,
+    /// Hooks invoked with every request and response.
+    hooks: SwanlingHooks,
+    /// Data set by the `test_start` task, shared with all users.
+    shared_data: SwanlingSharedData,
+    /// A size-bounded cache shared with all users.
+    shared_cache: shared_cache::SwanlingSharedCache,
+    /// Counters, barriers and flags shared with all users.
+    coordination: coordination::SwanlingCoordination,
+    /// Rows of test data handed out to one user at a time.
+    feeders: feeder::SwanlingFeeders,
+    /// An optional pool of database connections shared with all users.
+    #[cfg(feature = "sql")]
+    sql: Option<sql::SwanlingSqlPool>,
+    /// An optional message queue producer shared with all users.
+    #[cfg(feature = "queue")]
+    producer: Option<queue::SwanlingQueue>,
+    /// Channels to subscribers of lifecycle events.
+    events: Vec<flume::Sender<SwanlingEvent>>,
+    /// An optional runtime supplied with `set_runtime()`, used instead of building one.
+    runtime: Option<Handle>,
+    /// Whether ctrl-c is caught to stop the load test, not when it runs on the caller's
+    /// runtime.
+    catch_ctrlc: bool,
+    /// The Controller channel a handle returned by `execute_with_handle()` sends requests to.
+    handle_channel: Option<(
+        flume::Sender<SwanlingControllerRequest>,
+        flume::Receiver<SwanlingControllerRequest>,
+    )>,
 }
 /// Swanling's internal global state.
 impl SwanlingAttack {
-    /// Load configuration and initialize a [`SwanlingAttack`](./struct.SwanlingAttack.html).
+    /// Load configuration from the command line and initialize a
+    /// [`SwanlingAttack`](./struct.SwanlingAttack.html). Invalid command line arguments are
+    /// returned as an error instead of exiting the process.
     ///
     /// # Example
     /// ```rust
@@ -956,34 +1595,21 @@ impl SwanlingAttack {
     /// let mut swanling_attack = SwanlingAttack::initialize();
     /// ```
     pub fn initialize() -> Result<SwanlingAttack, SwanlingError> {
-        Ok(SwanlingAttack {
-            test_start_task: None,
-            test_stop_task: None,
-            task_sets: Vec::new(),
-            weighted_users: Vec::new(),
-            weighted_gaggle_users: Vec::new(),
-            defaults: SwanlingDefaults::default(),
-            configuration: SwanlingConfiguration::parse_args_default_or_exit(),
-            run_time: 0,
-            attack_mode: AttackMode::Undefined,
-            attack_phase: AttackPhase::Idle,
-            scheduler: SwanlingScheduler::RoundRobin,
-            started: None,
-            metrics: SwanlingMetrics::default(),
-        })
+        SwanlingAttack::initialize_with_config(SwanlingConfiguration::from_args()?)
     }
 
     /// Initialize a [`SwanlingAttack`](./struct.SwanlingAttack.html) with an already loaded
     /// configuration.
     ///
-    /// This is generally used by Worker instances and tests.
+    /// This is generally used by Worker instances, tests, and applications embedding Swanling,
+    /// building the configuration with
+    /// [`SwanlingConfiguration::builder`](./struct.SwanlingConfiguration.html#method.builder).
     ///
     /// # Example
     /// ```rust
     /// use swanling::{SwanlingAttack, SwanlingConfiguration};
-    /// use gumdrop::Options;
     ///
-    /// let configuration = SwanlingConfiguration::parse_args_default_or_exit();
+    /// let configuration = SwanlingConfiguration::builder().users(10).build();
     /// let mut swanling_attack = SwanlingAttack::initialize_with_config(configuration);
     /// ```
     pub fn initialize_with_config(
@@ -1002,7 +1628,24 @@ impl SwanlingAttack {
             attack_phase: AttackPhase::Idle,
             scheduler: SwanlingScheduler::RoundRobin,
             started: None,
+            paused: None,
             metrics: SwanlingMetrics::default(),
+            replay_requests: Vec::new(),
+            replay_speed: 1.0,
+            user_agents: Vec::new(),
+            hooks: SwanlingHooks::default(),
+            shared_data: SwanlingSharedData::default(),
+            shared_cache: shared_cache::SwanlingSharedCache::default(),
+            coordination: coordination::SwanlingCoordination::default(),
+            feeders: feeder::SwanlingFeeders::default(),
+            #[cfg(feature = "sql")]
+            sql: None,
+            #[cfg(feature = "queue")]
+            producer: None,
+            events: Vec::new(),
+            runtime: None,
+            catch_ctrlc: true,
+            handle_channel: None,
         })
     }
 
@@ -1015,6 +1658,15 @@ impl SwanlingAttack {
         // Allow optionally controlling debug output level
         let debug_level;
         match self.configuration.verbose {
+            // Only display errors, which are written to stderr, if only displaying the
+            // final metrics summary.
+            0 if self.configuration.summary_only
+                || self.defaults.summary_only == Some(true)
+                || self.configuration.tui
+                || self.defaults.tui == Some(true) =>
+            {
+                debug_level = LevelFilter::Error
+            }
             0 => debug_level = LevelFilter::Warn,
             1 => debug_level = LevelFilter::Info,
             2 => debug_level = LevelFilter::Debug,
@@ -1187,7 +1839,10 @@ impl SwanlingAttack {
     /// [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s that run the rest of the load
     /// test. For example, if the [`SwanlingUser`](./swanling/struct.SwanlingUser.html)
     /// logs in during `test_start`, subsequent [`SwanlingUser`](./swanling/struct.SwanlingUser.html)
-    /// do not retain this session and are therefor not already logged in.
+    /// do not retain this session and are therefor not already logged in. Data needed
+    /// by subsequent [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s, such as an
+    /// authentication token, can instead be shared with
+    /// [`set_shared_data`](./swanling/struct.SwanlingUser.html#method.set_shared_data).
     ///
     /// When running in a distributed Regatta, this task is only run one time by the
     /// Manager.
@@ -1243,955 +1898,4161 @@ impl SwanlingAttack {
         self
     }
 
-    /// Use configured SwanlingScheduler to build out a properly weighted list of
-    /// [`SwanlingTaskSet`](./swanling/struct.SwanlingTaskSet.html)s to be assigned to
-    /// [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s
-    fn allocate_task_sets(&mut self) -> Vec<usize> {
-        trace!("allocate_task_sets");
-
-        let mut u: usize = 0;
-        let mut v: usize;
-        for task_set in &self.task_sets {
-            if u == 0 {
-                u = task_set.weight;
-            } else {
-                v = task_set.weight;
-                trace!("calculating greatest common denominator of {} and {}", u, v);
-                u = util::gcd(u, v);
-                trace!("inner gcd: {}", u);
-            }
-        }
-        // 'u' will always be the greatest common divisor
-        debug!("gcd: {}", u);
-
-        // Build a vector of vectors to be used to schedule users.
-        let mut available_task_sets = Vec::with_capacity(self.task_sets.len());
-        let mut total_task_sets = 0;
-        for (index, task_set) in self.task_sets.iter().enumerate() {
-            // divide by greatest common divisor so vector is as short as possible
-            let weight = task_set.weight / u;
-            trace!(
-                "{}: {} has weight of {} (reduced with gcd to {})",
-                index,
-                task_set.name,
-                task_set.weight,
-                weight
-            );
-            let weighted_sets = vec![index; weight];
-            total_task_sets += weight;
-            available_task_sets.push(weighted_sets);
-        }
-
-        info!(
-            "allocating tasks and task sets with {:?} scheduler",
-            self.scheduler
-        );
-
-        // Now build the weighted list with the appropriate scheduler.
-        let mut weighted_task_sets = Vec::new();
-        match self.scheduler {
-            SwanlingScheduler::RoundRobin => {
-                // Allocate task sets round robin.
-                let task_sets_len = available_task_sets.len();
-                loop {
-                    for (task_set_index, task_sets) in available_task_sets
-                        .iter_mut()
-                        .enumerate()
-                        .take(task_sets_len)
-                    {
-                        if let Some(task_set) = task_sets.pop() {
-                            debug!("allocating 1 user from TaskSet {}", task_set_index);
-                            weighted_task_sets.push(task_set);
-                        }
-                    }
-                    if weighted_task_sets.len() >= total_task_sets {
-                        break;
-                    }
-                }
-            }
-            SwanlingScheduler::Serial => {
-                // Allocate task sets serially in the weighted order defined.
-                for (task_set_index, task_sets) in available_task_sets.iter().enumerate() {
-                    debug!(
-                        "allocating all {} users from TaskSet {}",
-                        task_sets.len(),
-                        task_set_index
-                    );
-                    weighted_task_sets.append(&mut task_sets.clone());
-                }
-            }
-            SwanlingScheduler::Random => {
-                // Allocate task sets randomly.
-                loop {
-                    let task_set = available_task_sets.choose_mut(&mut rand::thread_rng());
-                    match task_set {
-                        Some(set) => {
-                            if let Some(s) = set.pop() {
-                                weighted_task_sets.push(s);
-                            }
-                        }
-                        None => warn!("randomly allocating a SwanlingTaskSet failed, trying again"),
-                    }
-                    if weighted_task_sets.len() >= total_task_sets {
-                        break;
-                    }
-                }
-            }
-        }
-        weighted_task_sets
+    /// Register a hook that is invoked with every request before it is sent, allowing
+    /// cross-cutting changes such as signing requests or adding headers to all requests.
+    ///
+    /// The hook receives the built
+    /// [`reqwest::Request`](https://docs.rs/reqwest/*/reqwest/struct.Request.html), so the
+    /// final URL, headers and body are all available, along with the
+    /// [`SwanlingUser`](./swanling/struct.SwanlingUser.html) making the request. Hooks are
+    /// invoked in the order they are registered.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     SwanlingAttack::initialize()?
+    ///         .register_request_hook(|request, user| {
+    ///             // Identify which user made each request.
+    ///             if let Ok(value) = user.weighted_users_index.to_string().parse() {
+    ///                 request.headers_mut().insert("x-swanling-user", value);
+    ///             }
+    ///         });
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn register_request_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&mut reqwest::Request, &SwanlingUser) + Send + Sync + 'static,
+    {
+        self.hooks.request.push(Arc::new(hook));
+        self
     }
 
-    /// Allocate a vector of weighted [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s.
-    fn weight_task_set_users(&mut self) -> Result<Vec<SwanlingUser>, SwanlingError> {
-        trace!("weight_task_set_users");
-
-        let weighted_task_sets = self.allocate_task_sets();
-
-        // Allocate a state for each user that will be hatched.
-        info!("initializing user states...");
-        let mut weighted_users = Vec::new();
-        let mut user_count = 0;
-        loop {
-            for task_sets_index in &weighted_task_sets {
-                debug!(
-                    "creating user state: {} ({})",
-                    weighted_users.len(),
-                    task_sets_index
-                );
-                let base_url = swanling::get_base_url(
-                    self.get_configuration_host(),
-                    self.task_sets[*task_sets_index].host.clone(),
-                    self.defaults.host.clone(),
-                )?;
-                weighted_users.push(SwanlingUser::new(
-                    self.task_sets[*task_sets_index].task_sets_index,
-                    base_url,
-                    self.task_sets[*task_sets_index].min_wait,
-                    self.task_sets[*task_sets_index].max_wait,
-                    &self.configuration,
-                    self.metrics.hash,
-                )?);
-                user_count += 1;
-                // Users are required here so unwrap() is safe.
-                if user_count >= self.configuration.users.unwrap() {
-                    debug!("created {} weighted_users", user_count);
-                    return Ok(weighted_users);
-                }
-            }
-        }
+    /// Register a hook that is invoked with every response before its metrics are recorded,
+    /// allowing cross-cutting changes such as custom failure classification.
+    ///
+    /// The hook receives the
+    /// [`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html) and a
+    /// mutable reference to the [`SwanlingRequestMetric`](./metrics/struct.SwanlingRequestMetric.html)
+    /// that will be recorded. Hooks are not invoked when no response is received. Hooks are
+    /// invoked in the order they are registered.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     SwanlingAttack::initialize()?
+    ///         .register_response_hook(|response, request| {
+    ///             // Maintenance pages are failures, even though they return a 200.
+    ///             if response.headers().contains_key("x-maintenance") {
+    ///                 request.success = false;
+    ///                 request.error = "maintenance mode".to_string();
+    ///             }
+    ///         });
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn register_response_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&reqwest::Response, &mut SwanlingRequestMetric) + Send + Sync + 'static,
+    {
+        self.hooks.response.push(Arc::new(hook));
+        self
     }
 
-    /// Allocate a vector of weighted [`GaggleUser`](./swanling/struct.GaggleUser.html).
-    fn prepare_worker_task_set_users(&mut self) -> Result<Vec<GaggleUser>, SwanlingError> {
-        trace!("prepare_worker_task_set_users");
-
-        let weighted_task_sets = self.allocate_task_sets();
+    /// Customize the [`reqwest::ClientBuilder`](https://docs.rs/reqwest/*/reqwest/struct.ClientBuilder.html)
+    /// used to build the client of each [`SwanlingUser`](./swanling/struct.SwanlingUser.html),
+    /// allowing anything reqwest supports to be configured, such as the TLS backend, the local
+    /// address to bind to, or timeouts.
+    ///
+    /// The function receives a builder with Swanling's defaults already applied, and returns the
+    /// builder to use. It is invoked each time a user's client is built, including when it is
+    /// rebuilt by [`SwanlingClientMode::PerIteration`](./swanling/enum.SwanlingClientMode.html).
+    /// Overriding the redirect policy or cookie store disables Swanling's own handling of
+    /// redirects and `--sticky-cookie`. Clients built by
+    /// [`SwanlingUser::set_client_builder`](./swanling/struct.SwanlingUser.html#method.set_client_builder)
+    /// are not affected.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     SwanlingAttack::initialize()?
+    ///         .set_client_builder(|builder| builder.timeout(Duration::from_secs(5)));
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_client_builder<F>(mut self, client_builder: F) -> Self
+    where
+        F: Fn(reqwest::ClientBuilder) -> reqwest::ClientBuilder + Send + Sync + 'static,
+    {
+        self.hooks.client_builder = Some(Arc::new(client_builder));
+        self
+    }
 
-        // Determine the users sent to each Worker.
-        info!("preparing users for Workers...");
-        let mut weighted_users = Vec::new();
-        let mut user_count = 0;
-        loop {
-            for task_sets_index in &weighted_task_sets {
-                let base_url = swanling::get_base_url(
-                    self.get_configuration_host(),
-                    self.task_sets[*task_sets_index].host.clone(),
-                    self.defaults.host.clone(),
-                )?;
-                weighted_users.push(GaggleUser::new(
-                    self.task_sets[*task_sets_index].task_sets_index,
-                    base_url,
-                    self.task_sets[*task_sets_index].min_wait,
-                    self.task_sets[*task_sets_index].max_wait,
-                    &self.configuration,
-                    self.metrics.hash,
-                ));
-                user_count += 1;
-                // Users are required here so unwrap() is safe.
-                if user_count >= self.configuration.users.unwrap() {
-                    debug!("prepared {} weighted_gaggle_users", user_count);
-                    return Ok(weighted_users);
-                }
-            }
-        }
+    /// Returns the phase the load test is in. A load test that hasn't been executed is
+    /// [`AttackPhase::Idle`](./enum.AttackPhase.html#variant.Idle). While it runs, track
+    /// its phase with [`subscribe_events`](./struct.SwanlingAttack.html#method.subscribe_events)
+    /// or [`SwanlingAttackHandle::status`](./controller/struct.SwanlingAttackHandle.html#method.status).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    /// use swanling::AttackPhase;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let swanling_attack = SwanlingAttack::initialize()?;
+    ///     assert_eq!(swanling_attack.phase(), AttackPhase::Idle);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn phase(&self) -> AttackPhase {
+        self.attack_phase.clone()
     }
 
-    // Configure which mode this [`SwanlingAttack`](./struct.SwanlingAttack.html)
-    // will run in.
-    fn set_attack_mode(&mut self) -> Result<(), SwanlingError> {
-        // Determine if Manager is enabled by default.
-        let manager_is_default = if let Some(value) = self.defaults.manager {
-            value
+    /// Returns where the load test is in its lifecycle: its phase, how long it has been
+    /// running and will run, and how many users have launched.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let swanling_attack = SwanlingAttack::initialize()?;
+    ///     let progress = swanling_attack.progress();
+    ///     assert_eq!(progress.elapsed, 0);
+    ///     assert_eq!(progress.users, 0);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn progress(&self) -> SwanlingProgress {
+        // While paused, the elapsed time doesn't increase.
+        let elapsed = self.started.map_or(0, |started| {
+            (self.paused.unwrap_or_else(time::Instant::now) - started).as_secs() as usize
+        });
+        let remaining = if self.run_time > 0 {
+            Some(self.run_time.saturating_sub(elapsed))
         } else {
-            false
+            None
         };
-
-        // Determine if Worker is enabled by default.
-        let worker_is_default = if let Some(value) = self.defaults.worker {
-            value
+        // Users are only allocated when the load test starts.
+        let target_users = if self.weighted_users.is_empty() {
+            self.configuration.users.unwrap_or_default()
         } else {
-            false
+            self.weighted_users.len()
         };
+        SwanlingProgress {
+            phase: self.attack_phase.clone(),
+            paused: self.paused.is_some(),
+            elapsed,
+            remaining,
+            users: self.metrics.users,
+            target_users,
+        }
+    }
+
+    /// Returns the name of each registered task set, in the order they were registered, with
+    /// the names of its tasks. Unnamed tasks have an empty name. This allows tests to confirm
+    /// a load test runs the expected tasks, for example when tasks and task sets are named
+    /// with constants shared with the code asserting against their metrics. Names are
+    /// checked to be unique when the load test starts.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// const WEBSITE: &str = "Website";
+    /// const INDEX: &str = "index";
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let swanling_attack = SwanlingAttack::initialize()?
+    ///         .register_taskset(taskset!(WEBSITE)
+    ///             .register_task(task!(index).set_name(INDEX))
+    ///         );
+    ///     assert_eq!(
+    ///         swanling_attack.task_names(),
+    ///         vec![(WEBSITE.to_string(), vec![INDEX.to_string()])]
+    ///     );
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn index(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn task_names(&self) -> Vec<(String, Vec<String>)> {
+        self.task_sets
+            .iter()
+            .map(|task_set| {
+                (
+                    task_set.name.to_string(),
+                    task_set
+                        .tasks
+                        .iter()
+                        .map(|task| task.name.to_string())
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the registered task sets, in the order they were registered, each with its
+    /// tasks. This is the information displayed with `--list`, or with `--list --format json`
+    /// as JSON, allowing applications to inspect what a load test runs without running it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let swanling_attack = SwanlingAttack::initialize()?
+    ///         .register_taskset(taskset!("Website")
+    ///             .set_weight(2)?
+    ///             .register_task(task!(index).set_name("index"))
+    ///         );
+    ///
+    ///     let task_sets = swanling_attack.list();
+    ///     assert_eq!(task_sets[0].name, "Website");
+    ///     assert_eq!(task_sets[0].weight, 2);
+    ///     assert_eq!(task_sets[0].tasks[0].name, "index");
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn index(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn list(&self) -> Vec<SwanlingTaskSetListing> {
+        self.task_sets
+            .iter()
+            .map(|task_set| SwanlingTaskSetListing {
+                name: task_set.name.to_string(),
+                weight: task_set.weight,
+                fixed_users: task_set.fixed_users,
+                host: task_set
+                    .host
+                    .clone()
+                    .or_else(|| task_set.base_url.as_ref().map(|url| url.to_string())),
+                tasks: task_set
+                    .tasks
+                    .iter()
+                    .map(|task| SwanlingTaskListing {
+                        name: task.name.to_string(),
+                        weight: task.weight,
+                        sequence: task.sequence,
+                        on_start: task.on_start,
+                        on_stop: task.on_stop,
+                        schedule: task.schedule,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Subscribe to the lifecycle events of the load test, so an application embedding
+    /// Swanling can react when users start and stop, and when the load test changes
+    /// phase, without polling. Events are buffered until they are received, and the
+    /// stream ends once the load test has finished.
+    ///
+    /// Subscribe before calling [`execute`](./struct.SwanlingAttack.html#method.execute),
+    /// which blocks until the load test finishes, and receive events in another thread.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    /// use swanling::SwanlingEvent;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let mut swanling_attack = SwanlingAttack::initialize()?;
+    ///     let events = swanling_attack.subscribe_events();
+    ///
+    ///     std::thread::spawn(move || {
+    ///         for event in events.iter() {
+    ///             if let SwanlingEvent::PhaseChanged(phase) = event {
+    ///                 println!("load test is now {:?}", phase);
+    ///             }
+    ///         }
+    ///     });
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn subscribe_events(&mut self) -> flume::Receiver<SwanlingEvent> {
+        let (sender, receiver) = flume::unbounded();
+        self.events.push(sender);
+        receiver
+    }
+
+    /// Run the load test on an existing [`tokio::runtime::Runtime`](https://docs.rs/tokio/*/tokio/runtime/struct.Runtime.html),
+    /// instead of the multi-threaded runtime Swanling builds by default. This allows the
+    /// runtime to be tuned for the host, for example pinning worker threads to cores with
+    /// `Builder::on_thread_start`, and Swanling to be embedded in an existing async
+    /// application. The runtime must be multi-threaded, with I/O and time enabled.
+    ///
+    /// [`execute`](./struct.SwanlingAttack.html#method.execute) blocks until the load test
+    /// finishes, so it must not be called from a task running on the runtime: call it from
+    /// another thread, or with `tokio::task::spawn_blocking`. The Controllers keep listening
+    /// until the runtime shuts down, so disable them with `--no-telnet` and `--no-websocket`
+    /// to run more than one load test on the same runtime. This can't be used together with
+    /// `--runtime-threads`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let runtime = tokio::runtime::Builder::new_multi_thread()
+    ///         .worker_threads(2)
+    ///         .enable_all()
+    ///         .build()?;
+    ///
+    ///     SwanlingAttack::initialize()?
+    ///         .set_runtime(runtime.handle().clone());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_runtime(mut self, runtime: Handle) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    /// Change how many values the cache shared by all users holds, see
+    /// [`SwanlingUser::shared_cache`](./swanling/struct.SwanlingUser.html#method.shared_cache).
+    /// Once the cache is full, the least recently used value is evicted to make room for a
+    /// new one. Defaults to 1,000 values.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     SwanlingAttack::initialize()?
+    ///         .set_shared_cache_capacity(100)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_shared_cache_capacity(mut self, capacity: usize) -> Result<Self, SwanlingError> {
+        if capacity == 0 {
+            return Err(SwanlingError::InvalidOption {
+                option: "SwanlingAttack.set_shared_cache_capacity".to_string(),
+                value: capacity.to_string(),
+                detail: "The shared cache capacity must be set to at least 1.".to_string(),
+            });
+        }
+        self.shared_cache = shared_cache::SwanlingSharedCache::new(capacity);
+
+        Ok(self)
+    }
+
+    /// Register rows of test data, such as credentials, handed out to one user at a time,
+    /// see [`SwanlingUser::feeder`](./swanling/struct.SwanlingUser.html#method.feeder) and
+    /// the [`feeder`](./feeder/index.html) module. When running a fleet, each process only
+    /// hands out its share of the rows.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     SwanlingAttack::initialize()?
+    ///         .register_feeder("accounts", vec!["alice".to_string(), "bob".to_string()])?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn register_feeder(mut self, name: &str, rows: Vec<String>) -> Result<Self, SwanlingError> {
+        if rows.is_empty() {
+            return Err(SwanlingError::InvalidOption {
+                option: "SwanlingAttack.register_feeder".to_string(),
+                value: name.to_string(),
+                detail: "A feeder must have at least one row.".to_string(),
+            });
+        }
+        if !self.feeders.insert(name, rows) {
+            return Err(SwanlingError::InvalidOption {
+                option: "SwanlingAttack.register_feeder".to_string(),
+                value: name.to_string(),
+                detail: "A feeder with this name is already registered.".to_string(),
+            });
+        }
+
+        Ok(self)
+    }
+
+    /// Coordinate which rows of each feeder are in use with a backend shared by all
+    /// processes, for example Redis, instead of in this process. This is needed to share
+    /// rows between the Workers of a distributed Regatta, which won't start with feeders but no
+    /// backend. See the
+    /// [`feeder`](./feeder/index.html) module.
+    pub fn set_feeder_backend<B: feeder::SwanlingFeederBackend + 'static>(
+        mut self,
+        backend: B,
+    ) -> Self {
+        self.feeders.set_backend(Arc::new(backend));
+        self
+    }
+
+    /// Share a pool of database connections with the `test_start` and `test_stop` tasks and
+    /// all users, see [`SwanlingUser::sql`](./swanling/struct.SwanlingUser.html#method.sql)
+    /// and the [`sql`](./sql/index.html) module. Only available with the `sql` feature.
+    ///
+    /// How many times each query succeeded and failed and how long it took is included in
+    /// [`SwanlingMetrics::queries`](./metrics/struct.SwanlingMetrics.html#structfield.queries).
+    #[cfg(feature = "sql")]
+    pub fn set_sql_pool(mut self, pool: sql::SwanlingSqlPool) -> Self {
+        self.sql = Some(pool);
+        self
+    }
+
+    /// Share a message queue producer with all users, to publish messages to Kafka, AMQP or
+    /// other message queues with
+    /// [`SwanlingUser::publish`](./swanling/struct.SwanlingUser.html#method.publish). See the
+    /// [`queue`](./queue/index.html) module. Only available with the `queue` feature.
+    #[cfg(feature = "queue")]
+    pub fn set_producer<P: queue::SwanlingProducer + 'static>(mut self, producer: P) -> Self {
+        self.producer = Some(queue::SwanlingQueue(Arc::new(producer)));
+        self
+    }
+
+    /// Use configured SwanlingScheduler to build out a properly weighted list of
+    /// [`SwanlingTaskSet`](./swanling/struct.SwanlingTaskSet.html)s to be assigned to
+    /// [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s
+    fn allocate_task_sets(&mut self) -> Vec<usize> {
+        trace!("allocate_task_sets");
+
+        let mut u: usize = 0;
+        let mut v: usize;
+        // Task sets with fixed users are not allocated by weight.
+        for task_set in self.task_sets.iter().filter(|t| t.fixed_users.is_none()) {
+            if u == 0 {
+                u = task_set.weight;
+            } else {
+                v = task_set.weight;
+                trace!("calculating greatest common denominator of {} and {}", u, v);
+                u = util::gcd(u, v);
+                trace!("inner gcd: {}", u);
+            }
+        }
+        // 'u' will always be the greatest common divisor
+        debug!("gcd: {}", u);
+
+        // Build a vector of vectors to be used to schedule users.
+        let mut available_task_sets = Vec::with_capacity(self.task_sets.len());
+        let mut total_task_sets = 0;
+        for (index, task_set) in self.task_sets.iter().enumerate() {
+            if task_set.fixed_users.is_some() {
+                available_task_sets.push(Vec::new());
+                continue;
+            }
+            // divide by greatest common divisor so vector is as short as possible
+            let weight = task_set.weight / u;
+            trace!(
+                "{}: {} has weight of {} (reduced with gcd to {})",
+                index,
+                task_set.name,
+                task_set.weight,
+                weight
+            );
+            let weighted_sets = vec![index; weight];
+            total_task_sets += weight;
+            available_task_sets.push(weighted_sets);
+        }
+
+        info!(
+            "allocating tasks and task sets with {:?} scheduler",
+            self.scheduler
+        );
+
+        // Now build the weighted list with the appropriate scheduler.
+        let mut weighted_task_sets = Vec::new();
+        match self.scheduler {
+            SwanlingScheduler::RoundRobin => {
+                // Allocate task sets round robin.
+                let task_sets_len = available_task_sets.len();
+                loop {
+                    for (task_set_index, task_sets) in available_task_sets
+                        .iter_mut()
+                        .enumerate()
+                        .take(task_sets_len)
+                    {
+                        if let Some(task_set) = task_sets.pop() {
+                            debug!("allocating 1 user from TaskSet {}", task_set_index);
+                            weighted_task_sets.push(task_set);
+                        }
+                    }
+                    if weighted_task_sets.len() >= total_task_sets {
+                        break;
+                    }
+                }
+            }
+            SwanlingScheduler::Serial => {
+                // Allocate task sets serially in the weighted order defined.
+                for (task_set_index, task_sets) in available_task_sets.iter().enumerate() {
+                    debug!(
+                        "allocating all {} users from TaskSet {}",
+                        task_sets.len(),
+                        task_set_index
+                    );
+                    weighted_task_sets.append(&mut task_sets.clone());
+                }
+            }
+            SwanlingScheduler::Random => {
+                // Allocate task sets randomly.
+                loop {
+                    let task_set = available_task_sets.choose_mut(&mut rand::thread_rng());
+                    match task_set {
+                        Some(set) => {
+                            if let Some(s) = set.pop() {
+                                weighted_task_sets.push(s);
+                            }
+                        }
+                        None => warn!("randomly allocating a SwanlingTaskSet failed, trying again"),
+                    }
+                    if weighted_task_sets.len() >= total_task_sets {
+                        break;
+                    }
+                }
+            }
+        }
+        weighted_task_sets
+    }
+
+    /// Build the list of [`SwanlingTaskSet`](./swanling/struct.SwanlingTaskSet.html)s
+    /// assigned to each user that will be launched: first all users with a
+    /// [fixed](./swanling/struct.SwanlingTaskSet.html#method.set_fixed_users) task set, then
+    /// the remaining users allocated by weight.
+    fn allocate_users(&mut self) -> Result<Vec<usize>, SwanlingError> {
+        trace!("allocate_users");
+
+        // Users are required here so unwrap() is safe.
+        let users = self.configuration.users.unwrap();
+        let fixed_users: usize = self.task_sets.iter().filter_map(|t| t.fixed_users).sum();
+        if fixed_users > users {
+            return Err(SwanlingError::InvalidOption {
+                option: "--users".to_string(),
+                value: users.to_string(),
+                detail: format!(
+                    "--users must be at least {} to launch all fixed users.",
+                    fixed_users
+                ),
+            });
+        }
+
+        let mut task_sets_indexes = Vec::with_capacity(users);
+        for (index, task_set) in self.task_sets.iter().enumerate() {
+            if let Some(task_set_users) = task_set.fixed_users {
+                debug!(
+                    "allocating {} fixed users from TaskSet {}",
+                    task_set_users, index
+                );
+                task_sets_indexes.append(&mut vec![index; task_set_users]);
+            }
+        }
+
+        if users > fixed_users {
+            let weighted_task_sets = self.allocate_task_sets();
+            if weighted_task_sets.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--users".to_string(),
+                    value: users.to_string(),
+                    detail: format!(
+                        "--users can not be more than {} when all task sets have fixed users.",
+                        fixed_users
+                    ),
+                });
+            }
+            task_sets_indexes.extend(
+                weighted_task_sets
+                    .iter()
+                    .cycle()
+                    .take(users - fixed_users)
+                    .copied(),
+            );
+        }
+
+        Ok(task_sets_indexes)
+    }
+
+    /// Allocate a vector of weighted [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s.
+    fn weight_task_set_users(&mut self) -> Result<Vec<SwanlingUser>, SwanlingError> {
+        trace!("weight_task_set_users");
+
+        // In a fleet, only allocate this process's share of the users.
+        let allocated_task_sets = self.allocate_users()?;
+        let configuration = &self.configuration;
+        let allocated_task_sets: Vec<(usize, usize)> = allocated_task_sets
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| configuration.is_fleet_user(*index))
+            .collect();
+
+        // Allocate a state for each user that will be hatched.
+        info!("initializing user states...");
+        let mut weighted_users = Vec::with_capacity(allocated_task_sets.len());
+        for (user_index, task_sets_index) in allocated_task_sets {
+            debug!(
+                "creating user state: {} ({})",
+                weighted_users.len(),
+                task_sets_index
+            );
+            let base_url = self.get_task_set_host(task_sets_index, user_index)?;
+            let mut user = SwanlingUser::new(
+                self.task_sets[task_sets_index].task_sets_index,
+                base_url,
+                self.task_sets[task_sets_index].min_wait,
+                self.task_sets[task_sets_index].max_wait,
+                &self.configuration,
+                self.metrics.hash,
+            )?;
+            // Simulate the task set's network conditions, if any.
+            user.bandwidth = self.task_sets[task_sets_index].bandwidth;
+            user.added_latency = self.task_sets[task_sets_index].added_latency;
+            user.task_set_base_url = self.task_sets[task_sets_index].base_url.clone();
+            user.redirect_policy = self.task_sets[task_sets_index].redirect_policy.clone();
+            weighted_users.push(user);
+        }
+        debug!("created {} weighted_users", weighted_users.len());
+
+        Ok(weighted_users)
+    }
+
+    /// Allocate a vector of weighted [`GaggleUser`](./swanling/struct.GaggleUser.html).
+    fn prepare_worker_task_set_users(&mut self) -> Result<Vec<GaggleUser>, SwanlingError> {
+        trace!("prepare_worker_task_set_users");
+
+        let allocated_task_sets = self.allocate_users()?;
+
+        // Determine the users sent to each Worker.
+        info!("preparing users for Workers...");
+        let mut weighted_users = Vec::with_capacity(allocated_task_sets.len());
+        for (user_index, task_sets_index) in allocated_task_sets.into_iter().enumerate() {
+            let base_url = self.get_task_set_host(task_sets_index, user_index)?;
+            weighted_users.push(GaggleUser::new(
+                self.task_sets[task_sets_index].task_sets_index,
+                base_url,
+                self.task_sets[task_sets_index].min_wait,
+                self.task_sets[task_sets_index].max_wait,
+                &self.configuration,
+                self.metrics.hash,
+            ));
+        }
+        debug!("prepared {} weighted_gaggle_users", weighted_users.len());
+
+        Ok(weighted_users)
+    }
+
+    // Configure which mode this [`SwanlingAttack`](./struct.SwanlingAttack.html)
+    // will run in.
+    fn set_attack_mode(&mut self) -> Result<(), SwanlingError> {
+        // Determine if Manager is enabled by default.
+        let manager_is_default = if let Some(value) = self.defaults.manager {
+            value
+        } else {
+            false
+        };
+
+        // Determine if Worker is enabled by default.
+        let worker_is_default = if let Some(value) = self.defaults.worker {
+            value
+        } else {
+            false
+        };
+
+        // Don't allow Manager and Worker to both be the default.
+        if manager_is_default && worker_is_default {
+            return Err(SwanlingError::InvalidOption {
+                option: "SwanlingDefault::Worker".to_string(),
+                value: "true".to_string(),
+                detail: "The SwanlingDefault::Worker default can not be set together with the SwanlingDefault::Manager default"
+                    .to_string(),
+            });
+        }
+
+        // Manager mode if --manager is set, or --worker is not set and Manager is default.
+        if self.configuration.manager || (!self.configuration.worker && manager_is_default) {
+            self.attack_mode = AttackMode::Manager;
+            if self.configuration.worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--worker".to_string(),
+                    value: "true".to_string(),
+                    detail: "The --worker flag can not be set together with the --manager flag"
+                        .to_string(),
+                });
+            }
+
+            if !self.configuration.debug_log.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--debug-file".to_string(),
+                    value: self.configuration.debug_log.clone(),
+                    detail:
+                        "The --debug-file option can not be set together with the --manager flag."
+                            .to_string(),
+                });
+            }
+        }
+
+        // Worker mode if --worker is set, or --manager is not set and Worker is default.
+        if self.configuration.worker || (!self.configuration.manager && worker_is_default) {
+            self.attack_mode = AttackMode::Worker;
+            if self.configuration.manager {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--manager".to_string(),
+                    value: "true".to_string(),
+                    detail: "The --manager flag can not be set together with the --worker flag."
+                        .to_string(),
+                });
+            }
+
+            if !self.configuration.host.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--host".to_string(),
+                    value: self.configuration.host.clone(),
+                    detail: "The --host option can not be set together with the --worker flag."
+                        .to_string(),
+                });
+            }
+        }
+
+        // Otherwise run in standalone attack mode.
+        if self.attack_mode == AttackMode::Undefined {
+            self.attack_mode = AttackMode::StandAlone;
+
+            if self.configuration.no_hash_check {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--no-hash-check".to_string(),
+                    value: self.configuration.no_hash_check.to_string(),
+                    detail: "The --no-hash-check flag can not be set without also setting the --manager flag.".to_string(),
+                });
+            }
+
+            if self.configuration.hot_add_workers {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--hot-add-workers".to_string(),
+                    value: self.configuration.hot_add_workers.to_string(),
+                    detail: "The --hot-add-workers flag can not be set without also setting the --manager flag.".to_string(),
+                });
+            }
+
+            if self.configuration.manager_also_worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--manager-also-worker".to_string(),
+                    value: self.configuration.manager_also_worker.to_string(),
+                    detail: "The --manager-also-worker flag can not be set without also setting the --manager flag.".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Change from one attack_phase to another.
+    fn set_attack_phase(
+        &mut self,
+        swanling_attack_run_state: &mut SwanlingAttackRunState,
+        phase: AttackPhase,
+    ) {
+        // There's nothing to do if already in the specified phase.
+        if self.attack_phase == phase {
+            return;
+        }
+
+        // The drift timer starts at 0 any time the phase is changed.
+        swanling_attack_run_state.drift_timer = tokio::time::Instant::now();
+
+        // Optional debug output.
+        info!("entering SwanlingAttack phase: {:?}", &phase);
+        send_event(&self.events, SwanlingEvent::PhaseChanged(phase.clone()));
+
+        // Users wait at task rendezvous while the load test runs, and are released when it
+        // stops so they can exit.
+        match phase {
+            AttackPhase::Starting => self.coordination.reset_rendezvous(),
+            AttackPhase::Stopping => self.coordination.release_rendezvous(),
+            _ => (),
+        }
+
+        // Update the current phase.
+        self.attack_phase = phase;
+    }
+
+    // Determine how many Workers to expect.
+    fn set_expect_workers(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.expect_workers";
+
+        // Check if --expect-workers was set.
+        if self.configuration.expect_workers.is_some() {
+            key = "--expect-workers";
+        // Otherwise check if a custom default is set.
+        } else if let Some(default_expect_workers) = self.defaults.expect_workers {
+            if self.attack_mode == AttackMode::Manager {
+                key = "set_default(SwanlingDefault::ExpectWorkers)";
+
+                self.configuration.expect_workers = Some(default_expect_workers);
+            }
+        }
+
+        if let Some(expect_workers) = self.configuration.expect_workers {
+            // Disallow --expect-workers without --manager.
+            if self.attack_mode != AttackMode::Manager {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: expect_workers.to_string(),
+                    detail: format!(
+                        "{} can not be set without also setting the --manager flag.",
+                        key
+                    ),
+                });
+            } else {
+                // Must expect at least 1 Worker when running as Manager.
+                if expect_workers < 1 {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: expect_workers.to_string(),
+                        detail: format!("{} must be set to at least 1.", key),
+                    });
+                }
+
+                // Must not expect more Workers than Users. Users are required at this point so
+                // using unwrap() is safe.
+                if expect_workers as usize > self.configuration.users.unwrap() {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: expect_workers.to_string(),
+                        detail: format!(
+                            "{} can not be set to a value larger than --users option.",
+                            key
+                        ),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Configure the host and port the Manager listens on.
+    fn set_gaggle_host_and_port(&mut self) -> Result<(), SwanlingError> {
+        // Configure manager_bind_host and manager_bind_port.
+        if self.attack_mode == AttackMode::Manager {
+            // Use default if run-time option not set.
+            if self.configuration.manager_bind_host.is_empty() {
+                self.configuration.manager_bind_host =
+                    if let Some(host) = self.defaults.manager_bind_host.clone() {
+                        host
+                    } else {
+                        "0.0.0.0".to_string()
+                    }
+            }
+
+            // Use default if run-time option not set.
+            if self.configuration.manager_bind_port == 0 {
+                self.configuration.manager_bind_port =
+                    if let Some(port) = self.defaults.manager_bind_port {
+                        port
+                    } else {
+                        DEFAULT_PORT.to_string().parse().unwrap()
+                    };
+            }
+        } else {
+            if !self.configuration.manager_bind_host.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--manager-bind-host".to_string(),
+                    value: self.configuration.manager_bind_host.clone(),
+                    detail: "The --manager-bind-host option can not be set together with the --worker flag.".to_string(),
+                });
+            }
+
+            if self.configuration.manager_bind_port != 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--manager-bind-port".to_string(),
+                    value: self.configuration.manager_bind_port.to_string(),
+                    detail: "The --manager-bind-port option can not be set together with the --worker flag.".to_string(),
+                });
+            }
+        }
+
+        // Configure manager_host and manager_port.
+        if self.attack_mode == AttackMode::Worker {
+            // Use default if run-time option not set.
+            if self.configuration.manager_host.is_empty() {
+                self.configuration.manager_host =
+                    if let Some(host) = self.defaults.manager_host.clone() {
+                        host
+                    } else {
+                        "127.0.0.1".to_string()
+                    }
+            }
+
+            // Use default if run-time option not set.
+            if self.configuration.manager_port == 0 {
+                self.configuration.manager_port = if let Some(port) = self.defaults.manager_port {
+                    port
+                } else {
+                    DEFAULT_PORT.to_string().parse().unwrap()
+                };
+            }
+        } else {
+            if !self.configuration.manager_host.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--manager-host".to_string(),
+                    value: self.configuration.manager_host.clone(),
+                    detail:
+                        "The --manager-host option must be set together with the --worker flag."
+                            .to_string(),
+                });
+            }
+
+            if self.configuration.manager_port != 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--manager-port".to_string(),
+                    value: self.configuration.manager_port.to_string(),
+                    detail:
+                        "The --manager-port option must be set together with the --worker flag."
+                            .to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Configure how many [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s to hatch.
+    fn set_users(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.users";
+        let mut value = 0;
+
+        // Check if --users is set.
+        if let Some(users) = self.configuration.users {
+            key = "--users";
+            value = users;
+        // If not, check if a default for users is set.
+        } else if let Some(default_users) = self.defaults.users {
+            // On Worker users comes from the Manager.
+            if self.attack_mode == AttackMode::Worker {
+                self.configuration.users = None;
+            // Otherwise use default.
+            } else {
+                key = "set_default(SwanlingDefault::Users)";
+                value = default_users;
+
+                self.configuration.users = Some(default_users);
+            }
+        // If not and if not running on Worker, default to 1.
+        } else if self.attack_mode != AttackMode::Worker {
+            // This should not be able to fail, but setting up debug in case the number
+            // of cpus library returns an invalid number.
+            key = "num_cpus::get()";
+            value = num_cpus::get();
+
+            info!("concurrent users defaulted to {} (number of CPUs)", value);
+
+            self.configuration.users = Some(value);
+        }
+
+        // Perform bounds checking.
+        if let Some(users) = self.configuration.users {
+            // Setting --users with --worker is not allowed.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Setting users to 0 is not allowed.
+            if users == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: "0".to_string(),
+                    detail: "The --users option must be set to at least 1.".to_string(),
+                });
+            }
+
+            // Debug output.
+            info!("users = {}", users);
+        }
+
+        Ok(())
+    }
+
+    // Configure maximum run time if specified, otherwise run until canceled.
+    fn set_run_time(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.run_time";
+        let mut value = 0;
+
+        // Use --run-time if set, don't allow on Worker.
+        self.run_time = if !self.configuration.run_time.is_empty() {
+            key = "--run-time";
+            value = util::parse_timespan(&self.configuration.run_time);
+            value
+        // Otherwise, use default if set, but not on Worker.
+        } else if let Some(default_run_time) = self.defaults.run_time {
+            if self.attack_mode == AttackMode::Worker {
+                0
+            } else {
+                key = "set_default(SwanlingDefault::RunTime)";
+                value = default_run_time;
+                default_run_time
+            }
+        }
+        // Otherwise the test runs until canceled.
+        else {
+            0
+        };
+
+        if self.run_time > 0 {
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Debug output.
+            info!("run_time = {}", self.run_time);
+        }
+
+        Ok(())
+    }
+
+    // Configure how long to wait for users to exit when stopping, if limited.
+    fn set_shutdown_timeout(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.shutdown_timeout";
+
+        if !self.configuration.shutdown_timeout.is_empty() {
+            key = "--shutdown-timeout";
+        // In Gaggles, shutdown_timeout is only set on Worker.
+        } else if self.attack_mode != AttackMode::Manager {
+            if let Some(default_shutdown_timeout) = self.defaults.shutdown_timeout.clone() {
+                key = "set_default(SwanlingDefault::ShutdownTimeout)";
+                self.configuration.shutdown_timeout = default_shutdown_timeout;
+            }
+        }
+
+        if self.configuration.shutdown_timeout.is_empty() {
+            return Ok(());
+        }
+
+        // Users are stopped by the process that launches them.
+        if self.attack_mode == AttackMode::Manager {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.shutdown_timeout.clone(),
+                detail: format!("{} can not be set together with the --manager flag.", key),
+            });
+        }
+
+        if util::parse_timespan(&self.configuration.shutdown_timeout) == 0 {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.shutdown_timeout.clone(),
+                detail: format!("{} must be a time, for example 60s.", key),
+            });
+        }
+
+        info!("shutdown_timeout = {}", self.configuration.shutdown_timeout);
+
+        Ok(())
+    }
+
+    // Configure how quickly to hatch [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s.
+    fn set_hatch_rate(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.hatch_rate";
+        let mut value = "".to_string();
+
+        // Check if --hash-rate is set.
+        if let Some(hatch_rate) = &self.configuration.hatch_rate {
+            key = "--hatch_rate";
+            value = hatch_rate.to_string();
+        // If not, check if a default hatch_rate is set.
+        } else if let Some(default_hatch_rate) = &self.defaults.hatch_rate {
+            // On Worker hatch_rate comes from the Manager.
+            if self.attack_mode == AttackMode::Worker {
+                self.configuration.hatch_rate = None;
+            // Otherwise use default.
+            } else {
+                key = "set_default(SwanlingDefault::HatchRate)";
+                value = default_hatch_rate.to_string();
+                self.configuration.hatch_rate = Some(default_hatch_rate.to_string());
+            }
+        // If not and if not running on Worker, default to 1.
+        } else if self.attack_mode != AttackMode::Worker {
+            // This should not be able to fail, but setting up debug in case a later
+            // change introduces the potential for failure.
+            key = "Swanling default";
+            value = "1".to_string();
+            self.configuration.hatch_rate = Some(value.to_string());
+        }
+
+        // Verbose output.
+        if let Some(hatch_rate) = &self.configuration.hatch_rate {
+            // Setting --hatch-rate with --worker is not allowed.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value,
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Setting --hatch-rate of 0 is not allowed.
+            if hatch_rate.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value,
+                    detail: format!("{} must be set to at least 1.", key),
+                });
+            }
+
+            // Hatch rate must be a positive number, or a ramp between positive numbers.
+            if hatch_rate.contains("..") && !util::is_valid_hatch_rate(hatch_rate) {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value,
+                    detail: format!(
+                        "{} ramps must be formatted FROM..TO/TIME[:exp], such as 1..20/5m, with rates greater than 0.",
+                        key
+                    ),
+                });
+            }
+
+            // Debug output.
+            info!("hatch_rate = {}", hatch_rate);
+        }
+
+        Ok(())
+    }
+
+    // Configure how many users to hatch at a time.
+    fn set_hatch_batch(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.hatch_batch";
+
+        if self.configuration.hatch_batch.is_some() {
+            key = "--hatch-batch";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_hatch_batch) = self.defaults.hatch_batch {
+                key = "set_default(SwanlingDefault::HatchBatch)";
+                self.configuration.hatch_batch = Some(default_hatch_batch);
+            }
+        }
+
+        if let Some(hatch_batch) = self.configuration.hatch_batch {
+            // Setting --hatch-batch with --worker is not allowed.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: hatch_batch.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            if hatch_batch == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: hatch_batch.to_string(),
+                    detail: format!("{} must be set to at least 1.", key),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Configure how many worker threads the tokio runtime has.
+    fn set_runtime_threads(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.runtime_threads";
+
+        if self.configuration.runtime_threads.is_some() {
+            key = "--runtime-threads";
+        // If not otherwise set, check if there's a default. Workers build their own runtime,
+        // so the default is also used by Workers.
+        } else if let Some(default_runtime_threads) = self.defaults.runtime_threads {
+            key = "set_default(SwanlingDefault::RuntimeThreads)";
+            self.configuration.runtime_threads = Some(default_runtime_threads);
+        }
+
+        if let Some(runtime_threads) = self.configuration.runtime_threads {
+            // A supplied runtime already has its worker threads.
+            if self.runtime.is_some() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: runtime_threads.to_string(),
+                    detail: format!("{} can not be set together with set_runtime().", key),
+                });
+            }
+
+            if runtime_threads == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: runtime_threads.to_string(),
+                    detail: format!("{} must be set to at least 1.", key),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Configure the seed of each user's random number generator.
+    fn set_seed(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.seed";
+
+        if self.configuration.seed.is_some() {
+            key = "--seed";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_seed) = self.defaults.seed {
+                key = "set_default(SwanlingDefault::Seed)";
+                self.configuration.seed = Some(default_seed);
+            }
+        }
+
+        if let Some(seed) = self.configuration.seed {
+            // Workers use the seed configured on the Manager.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: seed.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Configure randomly shifting when each batch of users hatches.
+    fn set_hatch_jitter(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.hatch_jitter";
+
+        if !self.configuration.hatch_jitter.is_empty() {
+            key = "--hatch-jitter";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_hatch_jitter) = self.defaults.hatch_jitter.clone() {
+                key = "set_default(SwanlingDefault::HatchJitter)";
+                self.configuration.hatch_jitter = default_hatch_jitter;
+            }
+        }
+
+        if self.configuration.hatch_jitter.is_empty() {
+            return Ok(());
+        }
+
+        // Setting --hatch-jitter with --worker is not allowed.
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.hatch_jitter.clone(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        if util::parse_milliseconds(&self.configuration.hatch_jitter).is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.hatch_jitter.clone(),
+                detail: format!("{} must be a delay, for example 200ms.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure the coordinated omission mitigation strategy.
+    fn set_coordinated_omission(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.coordinated_omission";
+        let mut value = Some(SwanlingCoordinatedOmissionMitigation::Disabled);
+
+        if self.configuration.co_mitigation.is_some() {
+            key = "--co-mitigation";
+            value = self.configuration.co_mitigation.clone();
+        }
+
+        // Use default for co_mitigation if set and not on Worker.
+        if self.configuration.co_mitigation.is_none() {
+            if let Some(default_co_mitigation) = self.defaults.co_mitigation.as_ref() {
+                // In Gaggles, co_mitigation is only set on Manager.
+                if self.attack_mode != AttackMode::Worker {
+                    key = "set_default(SwanlingDefault::CoordinatedOmissionMitigation)";
+                    value = Some(default_co_mitigation.clone());
+
+                    self.configuration.co_mitigation = Some(default_co_mitigation.clone());
+                }
+            }
+        }
+
+        // Otherwise default to SwanlingCoordinaatedOmissionMitigation::Average.
+        if self.configuration.co_mitigation.is_none() && self.attack_mode != AttackMode::Worker {
+            self.configuration.co_mitigation = value.clone();
+        }
+
+        if let Some(co_mitigation) = self.configuration.co_mitigation.as_ref() {
+            // Setting --co-mitigation with --worker is not allowed.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: format!("{:?}", value),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Setting --co-mitigation with --no-metrics is not allowed.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: format!("{:?}", value),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            if co_mitigation != &SwanlingCoordinatedOmissionMitigation::Disabled
+                && self.scheduler == SwanlingScheduler::Random
+            {
+                // Coordinated Omission Mitigation is not possible together with the random scheduler,
+                // as it's impossible to calculate an accurate request cadence.
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: format!("{:?}", value),
+                    detail: format!(
+                        "{} can not be set together with SwanlingScheduler::Random.",
+                        key
+                    ),
+                });
+            }
+
+            info!(
+                "co_mitigation = {:?}",
+                self.configuration.co_mitigation.as_ref().unwrap()
+            );
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--no-gzip` flag is enabled.
+    fn set_no_gzip(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.no_gzip";
+        let mut value = false;
+
+        if self.configuration.no_gzip {
+            key = "--no-gzip";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_no_gzip) = self.defaults.no_gzip {
+                key = "set_default(SwanlingDefault::NoGzip)";
+                value = default_no_gzip;
+
+                self.configuration.no_gzip = default_no_gzip;
+            }
+        }
+
+        if self.configuration.no_gzip && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine the Accept-Encoding header sent with requests, if any.
+    fn set_accept_encoding(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.accept_encoding";
+
+        if !self.configuration.accept_encoding.is_empty() {
+            key = "--accept-encoding";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_accept_encoding) = self.defaults.accept_encoding.clone() {
+                key = "set_default(SwanlingDefault::AcceptEncoding)";
+                self.configuration.accept_encoding = default_accept_encoding;
+            }
+        }
+
+        if self.configuration.accept_encoding.is_empty() {
+            return Ok(());
+        }
+
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.accept_encoding.clone(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        if self.configuration.no_gzip {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.accept_encoding.clone(),
+                detail: format!("{} can not be set together with the --no-gzip flag.", key),
+            });
+        }
+
+        if reqwest::header::HeaderValue::from_str(&self.configuration.accept_encoding).is_err() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.accept_encoding.clone(),
+                detail: format!("{} must be a valid header value.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Load the user agents users pick from, if enabled.
+    fn set_user_agent_file(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.user_agent_file";
+
+        if !self.configuration.user_agent_file.is_empty() {
+            key = "--user-agent-file";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_user_agent_file) = self.defaults.user_agent_file.clone() {
+                key = "set_default(SwanlingDefault::UserAgentFile)";
+                self.configuration.user_agent_file = default_user_agent_file;
+            }
+        }
+
+        if self.configuration.user_agent_file.is_empty() {
+            return Ok(());
+        }
+
+        // Workers run their own task sets, so the file can't be read on the Manager.
+        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.user_agent_file.clone(),
+                detail: format!(
+                    "{} can not be set together with the --manager or --worker flags.",
+                    key
+                ),
+            });
+        }
+
+        let contents = match std::fs::read_to_string(&self.configuration.user_agent_file) {
+            Ok(contents) => contents,
+            Err(e) => {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.user_agent_file.clone(),
+                    detail: format!("Failed to read user agents: {}", e),
+                });
+            }
+        };
+
+        // Each non-empty line is a user agent.
+        self.user_agents = Vec::new();
+        for line in contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+        {
+            if reqwest::header::HeaderValue::from_str(line).is_err() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.user_agent_file.clone(),
+                    detail: format!("{} contains an invalid user agent: {}", key, line),
+                });
+            }
+            self.user_agents.push(line.to_string());
+        }
+
+        if self.user_agents.is_empty() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.user_agent_file.clone(),
+                detail: format!("{} does not contain any user agents.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure delaying a percentage of requests, if enabled.
+    fn set_inject_latency(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.inject_latency";
+
+        if !self.configuration.inject_latency.is_empty() {
+            key = "--inject-latency";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_inject_latency) = self.defaults.inject_latency.clone() {
+                key = "set_default(SwanlingDefault::InjectLatency)";
+                self.configuration.inject_latency = default_inject_latency;
+            }
+        }
+
+        if self.configuration.inject_latency.is_empty() {
+            return Ok(());
+        }
+
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.inject_latency.clone(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        if self.configuration.get_inject_latency().is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.inject_latency.clone(),
+                detail: format!(
+                    "{} must be a delay and a percentage of requests, for example 50ms@1%.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure aborting a percentage of requests, if enabled.
+    fn set_inject_abort(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.inject_abort";
+
+        if !self.configuration.inject_abort.is_empty() {
+            key = "--inject-abort";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_inject_abort) = self.defaults.inject_abort.clone() {
+                key = "set_default(SwanlingDefault::InjectAbort)";
+                self.configuration.inject_abort = default_inject_abort;
+            }
+        }
+
+        if self.configuration.inject_abort.is_empty() {
+            return Ok(());
+        }
+
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.inject_abort.clone(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        if self.configuration.get_inject_abort().is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.inject_abort.clone(),
+                detail: format!("{} must be a percentage of requests, for example 1%.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine the DNS server used to resolve hostnames, if any.
+    fn set_dns_server(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.dns_server";
+
+        if !self.configuration.dns_server.is_empty() {
+            key = "--dns-server";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_dns_server) = self.defaults.dns_server.clone() {
+                key = "set_default(SwanlingDefault::DnsServer)";
+                self.configuration.dns_server = default_dns_server;
+            }
+        }
+
+        if self.configuration.dns_server.is_empty() {
+            return Ok(());
+        }
+
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.dns_server.clone(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        if self.configuration.get_dns_server().is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.dns_server.clone(),
+                detail: format!(
+                    "{} must be an IP address and optional port, for example 10.0.0.53:53.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine whether hostnames are resolved for every request.
+    fn set_no_dns_cache(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.no_dns_cache";
+        let mut value = false;
+
+        if self.configuration.no_dns_cache {
+            key = "--no-dns-cache";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_no_dns_cache) = self.defaults.no_dns_cache {
+                key = "set_default(SwanlingDefault::NoDnsCache)";
+                value = default_no_dns_cache;
+
+                self.configuration.no_dns_cache = default_no_dns_cache;
+            }
+        }
+
+        if self.configuration.no_dns_cache && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine how many requests each user makes before resolving hostnames again, if any.
+    fn set_dns_refresh(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.dns_refresh";
+
+        if self.configuration.dns_refresh > 0 {
+            key = "--dns-refresh";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_dns_refresh) = self.defaults.dns_refresh {
+                key = "set_default(SwanlingDefault::DnsRefresh)";
+                self.configuration.dns_refresh = default_dns_refresh;
+            }
+        }
+
+        if self.configuration.dns_refresh == 0 {
+            return Ok(());
+        }
+
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.dns_refresh.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        if self.configuration.no_dns_cache {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.dns_refresh.to_string(),
+                detail: format!("{} can not be set together with --no-dns-cache.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure checking the hosts are healthy before and during the load test.
+    fn set_healthcheck(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.healthcheck";
+
+        if !self.configuration.healthcheck.is_empty() {
+            key = "--healthcheck";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_healthcheck) = self.defaults.healthcheck.clone() {
+                key = "set_default(SwanlingDefault::Healthcheck)";
+                self.configuration.healthcheck = default_healthcheck;
+            }
+        }
+
+        let mut interval_key = "configuration.healthcheck_interval";
+        if self.configuration.healthcheck_interval.is_some() {
+            interval_key = "--healthcheck-interval";
+        } else if let Some(default_healthcheck_interval) = self.defaults.healthcheck_interval {
+            interval_key = "set_default(SwanlingDefault::HealthcheckInterval)";
+            self.configuration.healthcheck_interval = Some(default_healthcheck_interval);
+        }
+
+        let mut pause_key = "configuration.healthcheck_pause";
+        if self.configuration.healthcheck_pause {
+            pause_key = "--healthcheck-pause";
+        } else if let Some(default_healthcheck_pause) = self.defaults.healthcheck_pause {
+            pause_key = "set_default(SwanlingDefault::HealthcheckPause)";
+            self.configuration.healthcheck_pause = default_healthcheck_pause;
+        }
+
+        if self.configuration.healthcheck.is_empty() {
+            if let Some(healthcheck_interval) = self.configuration.healthcheck_interval {
+                return Err(SwanlingError::InvalidOption {
+                    option: interval_key.to_string(),
+                    value: healthcheck_interval.to_string(),
+                    detail: format!("{} requires --healthcheck.", interval_key),
+                });
+            }
+            if self.configuration.healthcheck_pause {
+                return Err(SwanlingError::InvalidOption {
+                    option: pause_key.to_string(),
+                    value: true.to_string(),
+                    detail: format!("{} requires --healthcheck.", pause_key),
+                });
+            }
+            return Ok(());
+        }
+
+        // Hosts are checked by the process that launches users.
+        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.healthcheck.clone(),
+                detail: format!(
+                    "{} can not be set together with the --manager or --worker flags.",
+                    key
+                ),
+            });
+        }
+
+        if !self.configuration.healthcheck.starts_with('/') {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.healthcheck.clone(),
+                detail: format!("{} must be a path, for example /healthz.", key),
+            });
+        }
+
+        if self.configuration.healthcheck_pause
+            && self.configuration.healthcheck_interval == Some(0)
+        {
+            return Err(SwanlingError::InvalidOption {
+                option: pause_key.to_string(),
+                value: true.to_string(),
+                detail: format!(
+                    "{} requires hosts to be checked while the load test runs, but {} is 0.",
+                    pause_key, interval_key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure saving users' cookies when the load test stops, and restoring them when it
+    // starts.
+    fn set_save_sessions(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.save_sessions";
+
+        if !self.configuration.save_sessions.is_empty() {
+            key = "--save-sessions";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_save_sessions) = self.defaults.save_sessions.clone() {
+                key = "set_default(SwanlingDefault::SaveSessions)";
+                self.configuration.save_sessions = default_save_sessions;
+            }
+        }
+
+        if self.configuration.save_sessions.is_empty() {
+            return Ok(());
+        }
+
+        // Sessions are saved by the process that launches users.
+        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.save_sessions.clone(),
+                detail: format!(
+                    "{} can not be set together with the --manager or --worker flags.",
+                    key
+                ),
+            });
+        }
+
+        // Confirm sessions saved by an earlier load test can be restored.
+        sessions::read(&self.configuration.save_sessions)?;
+
+        Ok(())
+    }
+
+    // Configure stopping the load test when too many requests fail.
+    fn set_abort_on_error_rate(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.abort_on_error_rate";
+
+        if !self.configuration.abort_on_error_rate.is_empty() {
+            key = "--abort-on-error-rate";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_abort_on_error_rate) = self.defaults.abort_on_error_rate.clone() {
+                key = "set_default(SwanlingDefault::AbortOnErrorRate)";
+                self.configuration.abort_on_error_rate = default_abort_on_error_rate;
+            }
+        }
+
+        if self.configuration.abort_on_error_rate.is_empty() {
+            return Ok(());
+        }
+
+        // The error rate is measured by the process that launches users.
+        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.abort_on_error_rate.clone(),
+                detail: format!(
+                    "{} can not be set together with the --manager or --worker flags.",
+                    key
+                ),
+            });
+        }
+
+        if self.configuration.no_metrics {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.abort_on_error_rate.clone(),
+                detail: format!(
+                    "{} can not be set together with the --no-metrics flag.",
+                    key
+                ),
+            });
+        }
+
+        if self.configuration.get_abort_on_error_rate().is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.abort_on_error_rate.clone(),
+                detail: format!(
+                    "{} must be a percentage and a time, for example 25%@30s.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure warning when the latency of a request stays too high.
+    fn set_latency_alert(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.latency_alert";
+
+        if !self.configuration.latency_alert.is_empty() {
+            key = "--latency-alert";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_latency_alert) = self.defaults.latency_alert.clone() {
+                key = "set_default(SwanlingDefault::LatencyAlert)";
+                self.configuration.latency_alert = default_latency_alert;
+            }
+        }
+
+        if self.configuration.latency_alert.is_empty() {
+            return Ok(());
+        }
+
+        // Latency is measured by the process that launches users.
+        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.latency_alert.clone(),
+                detail: format!(
+                    "{} can not be set together with the --manager or --worker flags.",
+                    key
+                ),
+            });
+        }
+
+        if self.configuration.no_metrics {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.latency_alert.clone(),
+                detail: format!(
+                    "{} can not be set together with the --no-metrics flag.",
+                    key
+                ),
+            });
+        }
+
+        if self.configuration.get_latency_alert().is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.latency_alert.clone(),
+                detail: format!(
+                    "{} must be a response time in milliseconds and a time, for example 500ms@30s.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure retiring and replacing running users, if enabled.
+    fn set_user_churn(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.user_churn";
+
+        if !self.configuration.user_churn.is_empty() {
+            key = "--user-churn";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_user_churn) = self.defaults.user_churn.clone() {
+                key = "set_default(SwanlingDefault::UserChurn)";
+                self.configuration.user_churn = default_user_churn;
+            }
+        }
+
+        if self.configuration.user_churn.is_empty() {
+            return Ok(());
+        }
+
+        // Users are retired by the process that launches them.
+        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.user_churn.clone(),
+                detail: format!(
+                    "{} can not be set together with the --manager or --worker flags.",
+                    key
+                ),
+            });
+        }
+
+        if self.configuration.get_user_churn().is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.user_churn.clone(),
+                detail: format!(
+                    "{} must be a number of users and a time, for example 5/min.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure how the maximum requests per second changes over time, if enabled.
+    fn set_throttle_plan(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.throttle_plan";
+
+        if !self.configuration.throttle_plan.is_empty() {
+            key = "--throttle-plan";
+        // In Gaggles, throttle_plan is only set on Worker.
+        } else if self.attack_mode != AttackMode::Manager {
+            if let Some(default_throttle_plan) = self.defaults.throttle_plan.clone() {
+                key = "set_default(SwanlingDefault::ThrottlePlan)";
+                self.configuration.throttle_plan = default_throttle_plan;
+            }
+        }
+
+        if self.configuration.throttle_plan.is_empty() {
+            return Ok(());
+        }
+
+        // Setting --throttle-plan with --manager is not allowed.
+        if self.attack_mode == AttackMode::Manager {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.throttle_plan.clone(),
+                detail: format!("{} can not be set together with the --manager flag.", key),
+            });
+        }
+
+        if self.configuration.throttle_requests > 0 {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.throttle_plan.clone(),
+                detail: format!("{} can not be set together with --throttle-requests.", key),
+            });
+        }
+
+        if self.configuration.get_throttle_plan().is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.throttle_plan.clone(),
+                detail: format!(
+                    "{} must be a comma-separated list of requests per second from 1 to 1,000,000 and how long they last, for example 100:5m,500:10m,1000.",
+                    key
+                ),
+            });
+        }
+
+        info!("throttle_plan = {}", self.configuration.throttle_plan);
+
+        Ok(())
+    }
+
+    // Configure maximum requests per second if throttle enabled.
+    fn set_throttle_requests(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.throttle_requests";
+        let mut value = 0;
+
+        if self.configuration.throttle_requests > 0 {
+            key = "--throttle-requests";
+            value = self.configuration.throttle_requests;
+        }
+
+        // Use default for throttle_requests if set and not on Worker.
+        if self.configuration.throttle_requests == 0 {
+            if let Some(default_throttle_requests) = self.defaults.throttle_requests {
+                // In Gaggles, throttle_requests is only set on Worker.
+                if self.attack_mode != AttackMode::Manager {
+                    key = "set_default(SwanlingDefault::ThrottleRequests)";
+                    value = default_throttle_requests;
+
+                    self.configuration.throttle_requests = default_throttle_requests;
+                }
+            }
+        }
+
+        if self.configuration.throttle_requests > 0 {
+            // Setting --throttle-requests with --worker is not allowed.
+            if self.attack_mode == AttackMode::Manager {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --manager flag.", key),
+                });
+            }
+
+            // Be sure throttle_requests is in allowed range.
+            if self.configuration.throttle_requests == 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} must be set to at least 1 request per second.", key),
+                });
+            } else if self.configuration.throttle_requests > 1_000_000 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set to more than 1,000,000 requests per second.",
+                        key
+                    ),
+                });
+            }
+
+            info!(
+                "throttle_requests = {}",
+                self.configuration.throttle_requests
+            );
+        }
+
+        Ok(())
+    }
+
+    // Determine if `no_reset_statics` is enabled.
+    fn set_no_reset_metrics(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.no_reset_metrics";
+        let mut value = false;
+
+        if self.configuration.no_reset_metrics {
+            key = "--no-reset-metrics";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_no_reset_metrics) = self.defaults.no_reset_metrics {
+                key = "set_default(SwanlingDefault::NoResetMetrics)";
+                value = default_no_reset_metrics;
+
+                // Optionally set default.
+                self.configuration.no_reset_metrics = default_no_reset_metrics;
+            }
+        }
+
+        // Setting --no-reset-metrics with --worker is not allowed.
+        if self.configuration.no_reset_metrics && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--status-codes` flag is enabled.
+    fn set_status_codes(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.status_codes";
+        let mut value = false;
+
+        if self.configuration.status_codes {
+            key = "--status-codes";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_status_codes) = self.defaults.status_codes {
+                key = "set_default(SwanlingDefault::StatusCodes)";
+                value = default_status_codes;
+
+                // Optionally set default.
+                self.configuration.status_codes = default_status_codes;
+            }
+        }
+
+        // Setting --status-codes with --worker is not allowed.
+        if self.configuration.status_codes && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--running-metrics` flag is enabled.
+    fn set_running_metrics(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.running_metrics";
+        let mut value = 0;
+
+        if let Some(running_metrics) = self.configuration.running_metrics {
+            key = "--running-metrics";
+            value = running_metrics;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_running_metrics) = self.defaults.running_metrics {
+                key = "set_default(SwanlingDefault::RunningMetrics)";
+                value = default_running_metrics;
+
+                self.configuration.running_metrics = Some(default_running_metrics);
+            }
+        }
+
+        // Setting --running-metrics with --worker is not allowed.
+        if let Some(running_metrics) = self.configuration.running_metrics {
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            if running_metrics > 0 {
+                info!("running_metrics = {}", running_metrics);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Determine the format running metrics are displayed in.
+    fn set_running_metrics_format(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.running_metrics_format";
+
+        if self.configuration.running_metrics_format.is_some() {
+            key = "--running-metrics-format";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_running_metrics_format) =
+                self.defaults.running_metrics_format.as_ref()
+            {
+                key = "set_default(SwanlingDefault::RunningMetricsFormat)";
+                self.configuration.running_metrics_format =
+                    Some(default_running_metrics_format.clone());
+            }
+        }
+
+        // Setting --running-metrics-format with --worker is not allowed.
+        if let Some(running_metrics_format) = self.configuration.running_metrics_format.as_ref() {
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: format!("{:?}", running_metrics_format),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--tui` flag is enabled.
+    fn set_tui(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.tui";
+        let mut value = false;
+
+        if self.configuration.tui {
+            key = "--tui";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_tui) = self.defaults.tui {
+                key = "set_default(SwanlingDefault::Tui)";
+                value = default_tui;
+
+                self.configuration.tui = default_tui;
+            }
+        }
+
+        if self.configuration.tui {
+            // The dashboard controls the load test like a Controller, which isn't supported
+            // in Regatta mode.
+            if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --manager or --worker flags.",
+                        key
+                    ),
+                });
+            }
+
+            // The dashboard replaces running metrics.
+            if self.configuration.running_metrics.is_some() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --running-metrics option.",
+                        key
+                    ),
+                });
+            }
+
+            // The dashboard displays metrics.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --no-metrics flag.",
+                        key
+                    ),
+                });
+            }
+
+            #[cfg(not(feature = "tui"))]
+            {
+                return Err(SwanlingError::FeatureNotEnabled {
+                    feature: "tui".to_string(),
+                    detail: "Load test must be recompiled with `--features tui` to display a live dashboard.".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--summary-only` flag is enabled.
+    fn set_summary_only(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.summary_only";
+        let mut value = false;
+
+        if self.configuration.summary_only {
+            key = "--summary-only";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_summary_only) = self.defaults.summary_only {
+                key = "set_default(SwanlingDefault::SummaryOnly)";
+                value = default_summary_only;
+
+                self.configuration.summary_only = default_summary_only;
+            }
+        }
+
+        if self.configuration.summary_only {
+            // Setting --summary-only with --worker is not allowed.
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+
+            // Setting --summary-only with --running-metrics is not allowed.
+            if self.configuration.running_metrics.is_some() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --running-metrics option.",
+                        key
+                    ),
+                });
+            }
+
+            // Setting --summary-only with --verbose is not allowed.
+            if self.configuration.verbose > 0 {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with the --verbose flag.", key),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Determine the format the final metrics summary is displayed in.
+    fn set_summary_format(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.summary_format";
+
+        if self.configuration.summary_format.is_some() {
+            key = "--summary-format";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_summary_format) = self.defaults.summary_format.as_ref() {
+                key = "set_default(SwanlingDefault::SummaryFormat)";
+                self.configuration.summary_format = Some(default_summary_format.clone());
+            }
+        }
+
+        // Setting --summary-format with --worker is not allowed.
+        if let Some(summary_format) = self.configuration.summary_format.as_ref() {
+            if self.attack_mode == AttackMode::Worker {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: format!("{:?}", summary_format),
+                    detail: format!("{} can not be set together with the --worker flag.", key),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Determine the response time percentiles reported.
+    fn set_percentiles(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.percentiles";
+
+        if !self.configuration.percentiles.is_empty() {
+            key = "--percentiles";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_percentiles) = self.defaults.percentiles.clone() {
+                key = "set_default(SwanlingDefault::Percentiles)";
+                self.configuration.percentiles = default_percentiles;
+            }
+        }
+
+        if self.configuration.percentiles.is_empty() {
+            return Ok(());
+        }
+
+        // Setting --percentiles with --worker is not allowed.
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.percentiles.clone(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        if self.configuration.get_percentiles().is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.percentiles.clone(),
+                detail: format!(
+                    "{} must be a comma-separated list of percentiles greater than 0 and up to 100, ie 50,90,99.9.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine the latency histogram bucket boundaries in the html report.
+    fn set_histogram_buckets(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.histogram_buckets";
+
+        if !self.configuration.histogram_buckets.is_empty() {
+            key = "--histogram-buckets";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_histogram_buckets) = self.defaults.histogram_buckets.clone() {
+                key = "set_default(SwanlingDefault::HistogramBuckets)";
+                self.configuration.histogram_buckets = default_histogram_buckets;
+            }
+        }
+
+        if self.configuration.histogram_buckets.is_empty() {
+            return Ok(());
+        }
+
+        // Setting --histogram-buckets with --worker is not allowed.
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.histogram_buckets.clone(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        if self.configuration.get_histogram_buckets().is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.histogram_buckets.clone(),
+                detail: format!(
+                    "{} must be a comma-separated list of increasing milliseconds, ie 10,50,100,500.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine how often to write report snapshots while the load test runs.
+    fn set_report_interval(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.report_interval";
+
+        if !self.configuration.report_interval.is_empty() {
+            key = "--report-interval";
+        // If not otherwise set and not Manager, check if there's a default.
+        } else if self.attack_mode != AttackMode::Manager {
+            if let Some(default_report_interval) = self.defaults.report_interval.clone() {
+                key = "set_default(SwanlingDefault::ReportInterval)";
+                self.configuration.report_interval = default_report_interval;
+            }
+        }
+
+        if self.configuration.report_interval.is_empty() {
+            return Ok(());
+        }
+
+        if util::parse_timespan(&self.configuration.report_interval) == 0 {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.report_interval.clone(),
+                detail: format!("{} must be a time, for example 5m.", key),
+            });
+        }
+
+        // Snapshots are written next to the report file.
+        if self.get_report_file_path().is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.report_interval.clone(),
+                detail: format!(
+                    "The --report-file option must be set together with {}.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--no-task-metrics` flag is enabled.
+    fn set_no_task_metrics(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.no_task_metrics";
+        let mut value = false;
+
+        if self.configuration.no_task_metrics {
+            key = "--no-task-metrics";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_no_task_metrics) = self.defaults.no_task_metrics {
+                key = "set_default(SwanlingDefault::NoTaskMetrics)";
+                value = default_no_task_metrics;
+
+                self.configuration.no_task_metrics = default_no_task_metrics;
+            }
+        }
+
+        // Setting --no-task-metrics with --worker is not allowed.
+        if self.configuration.no_task_metrics && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--no-error-summary` flag is enabled.
+    fn set_no_error_summary(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.no_error_summary";
+        let mut value = false;
+
+        if self.configuration.no_error_summary {
+            key = "--no-error-summary";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_no_error_summary) = self.defaults.no_error_summary {
+                key = "set_default(SwanlingDefault::NoErrorSummary)";
+                value = default_no_error_summary;
+
+                self.configuration.no_error_summary = default_no_error_summary;
+            }
+        }
+
+        // Setting --no-error-summary with --worker is not allowed.
+        if self.configuration.no_error_summary && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Validate the request tags errors are summarized by, if any.
+    fn set_error_tag(&mut self) -> Result<(), SwanlingError> {
+        if self.configuration.error_tag.is_empty() {
+            return Ok(());
+        }
+
+        let value = self.configuration.error_tag.join(" ");
+        // Workers summarize errors by the tags configured on the Manager.
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: "--error-tag".to_string(),
+                value,
+                detail: "--error-tag can not be set together with the --worker flag.".to_string(),
+            });
+        }
+        if self.configuration.no_error_summary {
+            return Err(SwanlingError::InvalidOption {
+                option: "--error-tag".to_string(),
+                value,
+                detail: "--error-tag can not be set together with the --no-error-summary flag."
+                    .to_string(),
+            });
+        }
+        if self
+            .configuration
+            .error_tag
+            .iter()
+            .any(|key| key.is_empty())
+        {
+            return Err(SwanlingError::InvalidOption {
+                option: "--error-tag".to_string(),
+                value,
+                detail: "--error-tag must be the key of a request tag, for example tenant."
+                    .to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine the maximum number of unique request names tracked, if limited.
+    fn set_max_request_names(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.max_request_names";
+
+        if self.configuration.max_request_names > 0 {
+            key = "--max-request-names";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_max_request_names) = self.defaults.max_request_names {
+                key = "set_default(SwanlingDefault::MaxRequestNames)";
+                self.configuration.max_request_names = default_max_request_names;
+            }
+        }
+
+        // Setting --max-request-names with --worker is not allowed.
+        if self.configuration.max_request_names > 0 && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.max_request_names.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure how often users send aggregated metrics to the parent.
+    fn set_metrics_flush(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.metrics_flush";
+
+        if !self.configuration.metrics_flush.is_empty() {
+            key = "--metrics-flush";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_metrics_flush) = self.defaults.metrics_flush.clone() {
+                key = "set_default(SwanlingDefault::MetricsFlush)";
+                self.configuration.metrics_flush = default_metrics_flush;
+            }
+        }
+
+        if self.configuration.metrics_flush.is_empty() {
+            return Ok(());
+        }
+
+        // Setting --metrics-flush with --worker is not allowed.
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.metrics_flush.clone(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        if util::parse_milliseconds(&self.configuration.metrics_flush).is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.metrics_flush.clone(),
+                detail: format!("{} must be a delay, for example 100ms.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--no-metrics` flag is enabled.
+    fn set_no_metrics(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.no_metrics";
+        let mut value = false;
+
+        if self.configuration.no_metrics {
+            key = "--no-metrics";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_no_metrics) = self.defaults.no_metrics {
+                key = "set_default(SwanlingDefault::NoMetrics)";
+                value = default_no_metrics;
+
+                self.configuration.no_metrics = default_no_metrics;
+            }
+        }
+
+        // Setting --no-metrics with --worker is not allowed.
+        if self.configuration.no_metrics && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        // Don't allow overhead of collecting metrics unless we're printing them.
+        if self.configuration.no_metrics {
+            if self.configuration.status_codes {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --status-codes flag.",
+                        key
+                    ),
+                });
+            }
+
+            // Don't allow overhead of collecting metrics unless we're printing them.
+            if self.configuration.running_metrics.is_some() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --running_metrics option.",
+                        key
+                    ),
+                });
+            }
+
+            // There is nothing to log if metrics are disabled.
+            if !self.configuration.request_log.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --requests-file option.",
+                        key
+                    ),
+                });
+            }
+
+            // There are no latencies to record if metrics are disabled.
+            if !self.configuration.record_raw_latencies.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "{} can not be set together with the --record-raw-latencies option.",
+                        key
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Determine the cookie used to pin users to a backend, if any.
+    fn set_sticky_cookie(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.sticky_cookie";
+
+        if !self.configuration.sticky_cookie.is_empty() {
+            key = "--sticky-cookie";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_sticky_cookie) = self.defaults.sticky_cookie.clone() {
+                key = "set_default(SwanlingDefault::StickyCookie)";
+                self.configuration.sticky_cookie = default_sticky_cookie;
+            }
+        }
+
+        if !self.configuration.sticky_cookie.is_empty() && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.sticky_cookie.clone(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        // Cookie names can't contain separators.
+        if self
+            .configuration
+            .sticky_cookie
+            .contains(|c: char| c == '=' || c == ';' || c.is_whitespace())
+        {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.sticky_cookie.clone(),
+                detail: format!("{} must be a valid cookie name.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine the header used to pin users to a backend, if any.
+    fn set_sticky_header(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.sticky_header";
+
+        if !self.configuration.sticky_header.is_empty() {
+            key = "--sticky-header";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_sticky_header) = self.defaults.sticky_header.clone() {
+                key = "set_default(SwanlingDefault::StickyHeader)";
+                self.configuration.sticky_header = default_sticky_header;
+            }
+        }
+
+        if self.configuration.sticky_header.is_empty() {
+            return Ok(());
+        }
+
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.sticky_header.clone(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        if reqwest::header::HeaderName::from_bytes(self.configuration.sticky_header.as_bytes())
+            .is_err()
+        {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.sticky_header.clone(),
+                detail: format!("{} must be a valid header name.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine the backends users are pinned to, if any.
+    fn set_sticky_backends(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.sticky_backends";
+
+        if !self.configuration.sticky_backends.is_empty() {
+            key = "--sticky-backends";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_sticky_backends) = self.defaults.sticky_backends.clone() {
+                key = "set_default(SwanlingDefault::StickyBackends)";
+                self.configuration.sticky_backends = default_sticky_backends;
+            }
+        }
+
+        if self.configuration.sticky_backends.is_empty() {
+            // A cookie or header can't pin users without backends to pin them to.
+            if !self.configuration.sticky_cookie.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--sticky-cookie".to_string(),
+                    value: self.configuration.sticky_cookie.clone(),
+                    detail: "--sticky-cookie requires --sticky-backends.".to_string(),
+                });
+            }
+            if !self.configuration.sticky_header.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--sticky-header".to_string(),
+                    value: self.configuration.sticky_header.clone(),
+                    detail: "--sticky-header requires --sticky-backends.".to_string(),
+                });
+            }
+            return Ok(());
+        }
+
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.sticky_backends.clone(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        if self.configuration.sticky_cookie.is_empty()
+            && self.configuration.sticky_header.is_empty()
+        {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.sticky_backends.clone(),
+                detail: format!("{} requires --sticky-cookie or --sticky-header.", key),
+            });
+        }
+
+        let backends = self.configuration.get_sticky_backends();
+        if backends.is_empty() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.sticky_backends.clone(),
+                detail: format!("{} must list at least one backend.", key),
+            });
+        }
+        if let Some(backend) = backends
+            .iter()
+            .find(|b| reqwest::header::HeaderValue::from_str(b).is_err() || b.contains(';'))
+        {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.sticky_backends.clone(),
+                detail: format!("{} contains an invalid backend: {}", key, backend),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine the response header identifying which backend served a request, if any.
+    fn set_backend_header(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.backend_header";
+
+        if !self.configuration.backend_header.is_empty() {
+            key = "--backend-header";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_backend_header) = self.defaults.backend_header.clone() {
+                key = "set_default(SwanlingDefault::BackendHeader)";
+                self.configuration.backend_header = default_backend_header;
+            }
+        }
+
+        if self.configuration.backend_header.is_empty() {
+            return Ok(());
+        }
+
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.backend_header.clone(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        if self.configuration.no_metrics {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.backend_header.clone(),
+                detail: format!(
+                    "{} can not be set together with the --no-metrics flag.",
+                    key
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Validate the local addresses requests are sent from, if any.
+    fn set_bind_address(&mut self) -> Result<(), SwanlingError> {
+        // Bind addresses are local to each host, so unlike most options they are also
+        // configured on Workers.
+        for address in &self.configuration.bind_address {
+            if address.trim().parse::<std::net::IpAddr>().is_err() {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--bind-address".to_string(),
+                    value: address.to_string(),
+                    detail: "--bind-address must be an IP address, for example 10.0.0.2."
+                        .to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Validate the `--label` key/value pairs added to reports.
+    fn set_labels(&mut self) -> Result<(), SwanlingError> {
+        if self.configuration.label.is_empty() {
+            return Ok(());
+        }
+
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: "--label".to_string(),
+                value: self.configuration.label.join(" "),
+                detail: "--label can not be set together with the --worker flag.".to_string(),
+            });
+        }
+
+        for value in &self.configuration.label {
+            match value.split_once('=') {
+                Some((key, _)) if !key.trim().is_empty() => (),
+                _ => {
+                    return Err(SwanlingError::InvalidOption {
+                        option: "--label".to_string(),
+                        value: value.to_string(),
+                        detail: "--label must be a key and a value, for example version=1.2.0."
+                            .to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Configure the ID identifying this run, generating one if not set.
+    fn set_run_id(&mut self) -> Result<(), SwanlingError> {
+        // Workers use the run ID of the Manager.
+        if self.attack_mode == AttackMode::Worker {
+            if !self.configuration.run_id.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--run-id".to_string(),
+                    value: self.configuration.run_id.clone(),
+                    detail: "--run-id can not be set together with the --worker flag.".to_string(),
+                });
+            }
+            return Ok(());
+        }
+
+        if self.configuration.run_id.is_empty() {
+            self.configuration.run_id = new_uuid();
+        // The run ID is sent in a header, so must be printable.
+        } else if !self
+            .configuration
+            .run_id
+            .chars()
+            .all(|c| c.is_ascii_graphic())
+        {
+            return Err(SwanlingError::InvalidOption {
+                option: "--run-id".to_string(),
+                value: self.configuration.run_id.clone(),
+                detail: "--run-id can only contain printable ascii characters, without spaces."
+                    .to_string(),
+            });
+        }
+
+        info!("run_id = {}", self.configuration.run_id);
+
+        Ok(())
+    }
+
+    // Determine if the `--run-header` flag is enabled.
+    fn set_run_header(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.run_header";
+        let mut value = false;
+
+        if self.configuration.run_header {
+            key = "--run-header";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_run_header) = self.defaults.run_header {
+                key = "set_default(SwanlingDefault::RunHeader)";
+                value = default_run_header;
+
+                // Optionally set default.
+                self.configuration.run_header = default_run_header;
+            }
+        }
+
+        // Setting --run-header with --worker is not allowed.
+        if self.configuration.run_header && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--task-headers` flag is enabled.
+    fn set_task_headers(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.task_headers";
+        let mut value = false;
+
+        if self.configuration.task_headers {
+            key = "--task-headers";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_task_headers) = self.defaults.task_headers {
+                key = "set_default(SwanlingDefault::TaskHeaders)";
+                value = default_task_headers;
+
+                // Optionally set default.
+                self.configuration.task_headers = default_task_headers;
+            }
+        }
+
+        // Setting --task-headers with --worker is not allowed.
+        if self.configuration.task_headers && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure uploading metrics and the report to object storage.
+    fn set_upload_url(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.upload_url";
+
+        if !self.configuration.upload_url.is_empty() {
+            key = "--upload-url";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_upload_url) = self.defaults.upload_url.clone() {
+                key = "set_default(SwanlingDefault::UploadUrl)";
+                self.configuration.upload_url = default_upload_url;
+            }
+        }
+
+        let mut interval_key = "configuration.upload_interval";
+        if self.configuration.upload_interval.is_some() {
+            interval_key = "--upload-interval";
+        } else if let Some(default_upload_interval) = self.defaults.upload_interval {
+            interval_key = "set_default(SwanlingDefault::UploadInterval)";
+            self.configuration.upload_interval = Some(default_upload_interval);
+        }
+
+        if self.configuration.upload_url.is_empty() {
+            if let Some(upload_interval) = self.configuration.upload_interval {
+                return Err(SwanlingError::InvalidOption {
+                    option: interval_key.to_string(),
+                    value: upload_interval.to_string(),
+                    detail: format!("{} requires --upload-url.", interval_key),
+                });
+            }
+            return Ok(());
+        }
+
+        // Metrics are uploaded by the process that collects them.
+        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.upload_url.clone(),
+                detail: format!(
+                    "{} can not be set together with the --manager or --worker flags.",
+                    key
+                ),
+            });
+        }
+
+        if self.configuration.no_metrics {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.upload_url.clone(),
+                detail: format!(
+                    "{} can not be set together with the --no-metrics flag.",
+                    key
+                ),
+            });
+        }
+
+        #[cfg(feature = "object-storage")]
+        {
+            // Confirm the URL and credentials are valid.
+            if let Err(e) = upload::SwanlingUploader::new(&self.configuration.upload_url, 0) {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: self.configuration.upload_url.clone(),
+                    detail: format!("{} {}.", key, e),
+                });
+            }
+            Ok(())
+        }
+
+        #[cfg(not(feature = "object-storage"))]
+        Err(SwanlingError::FeatureNotEnabled {
+            feature: "object-storage".to_string(),
+            detail:
+                "Load test must be recompiled with `--features object-storage` to upload metrics."
+                    .to_string(),
+        })
+    }
+
+    // Configure notifying a webhook of load test events.
+    fn set_notify_url(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.notify_url";
+
+        if !self.configuration.notify_url.is_empty() {
+            key = "--notify-url";
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            if let Some(default_notify_url) = self.defaults.notify_url.clone() {
+                key = "set_default(SwanlingDefault::NotifyUrl)";
+                self.configuration.notify_url = default_notify_url;
+            }
+        }
+
+        if self.configuration.notify_url.is_empty() {
+            return Ok(());
+        }
+
+        // Events are sent by the process that launches users.
+        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.notify_url.clone(),
+                detail: format!(
+                    "{} can not be set together with the --manager or --worker flags.",
+                    key
+                ),
+            });
+        }
+
+        if let Err(e) = notify::SwanlingNotifier::validate(&self.configuration.notify_url) {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.notify_url.clone(),
+                detail: format!("{} {}.", key, e),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Validate the `--set` key/value pairs made available to tasks.
+    fn set_config_values(&mut self) -> Result<(), SwanlingError> {
+        if self.configuration.set.is_empty() {
+            return Ok(());
+        }
+
+        if self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: "--set".to_string(),
+                value: self.configuration.set.join(" "),
+                detail: "--set can not be set together with the --worker flag.".to_string(),
+            });
+        }
+
+        for value in &self.configuration.set {
+            match value.split_once('=') {
+                Some((key, _)) if !key.trim().is_empty() => (),
+                _ => {
+                    return Err(SwanlingError::InvalidOption {
+                        option: "--set".to_string(),
+                        value: value.to_string(),
+                        detail: "--set must be a key and a value, for example tenant=42."
+                            .to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Determine if the `--sticky-follow` flag is enabled.
+    fn set_sticky_follow(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.sticky_follow";
+        let mut value = false;
+
+        if self.configuration.sticky_follow {
+            key = "--sticky-follow";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_sticky_follow) = self.defaults.sticky_follow {
+                key = "set_default(SwanlingDefault::StickyFollow)";
+                value = default_sticky_follow;
+
+                self.configuration.sticky_follow = default_sticky_follow;
+            }
+        }
+
+        if self.configuration.sticky_follow && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "gaggle")]
+    // Determine if `--no-hash-check` flag is enabled.
+    fn set_no_hash_check(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.no_hash_check";
+        let mut value = false;
+
+        if self.configuration.no_hash_check {
+            key = "--no-hash-check";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_no_hash_check) = self.defaults.no_hash_check {
+                key = "set_default(SwanlingDefault::NoHashCheck)";
+                value = default_no_hash_check;
 
-        // Don't allow Manager and Worker to both be the default.
-        if manager_is_default && worker_is_default {
+                self.configuration.no_hash_check = default_no_hash_check;
+            }
+        }
+
+        if self.configuration.no_hash_check && self.attack_mode == AttackMode::Worker {
             return Err(SwanlingError::InvalidOption {
-                option: "SwanlingDefault::Worker".to_string(),
-                value: "true".to_string(),
-                detail: "The SwanlingDefault::Worker default can not be set together with the SwanlingDefault::Manager default"
-                    .to_string(),
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
             });
         }
 
-        // Manager mode if --manager is set, or --worker is not set and Manager is default.
-        if self.configuration.manager || (!self.configuration.worker && manager_is_default) {
-            self.attack_mode = AttackMode::Manager;
-            if self.configuration.worker {
-                return Err(SwanlingError::InvalidOption {
-                    option: "--worker".to_string(),
-                    value: "true".to_string(),
-                    detail: "The --worker flag can not be set together with the --manager flag"
-                        .to_string(),
-                });
-            }
+        Ok(())
+    }
 
-            if !self.configuration.debug_log.is_empty() {
-                return Err(SwanlingError::InvalidOption {
-                    option: "--debug-file".to_string(),
-                    value: self.configuration.debug_log.clone(),
-                    detail:
-                        "The --debug-file option can not be set together with the --manager flag."
-                            .to_string(),
-                });
-            }
-        }
+    #[cfg(feature = "gaggle")]
+    // Determine if `--hot-add-workers` flag is enabled.
+    fn set_hot_add_workers(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.hot_add_workers";
+        let mut value = false;
 
-        // Worker mode if --worker is set, or --manager is not set and Worker is default.
-        if self.configuration.worker || (!self.configuration.manager && worker_is_default) {
-            self.attack_mode = AttackMode::Worker;
-            if self.configuration.manager {
-                return Err(SwanlingError::InvalidOption {
-                    option: "--manager".to_string(),
-                    value: "true".to_string(),
-                    detail: "The --manager flag can not be set together with the --worker flag."
-                        .to_string(),
-                });
-            }
+        if self.configuration.hot_add_workers {
+            key = "--hot-add-workers";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_hot_add_workers) = self.defaults.hot_add_workers {
+                key = "set_default(SwanlingDefault::HotAddWorkers)";
+                value = default_hot_add_workers;
 
-            if !self.configuration.host.is_empty() {
-                return Err(SwanlingError::InvalidOption {
-                    option: "--host".to_string(),
-                    value: self.configuration.host.clone(),
-                    detail: "The --host option can not be set together with the --worker flag."
-                        .to_string(),
-                });
+                self.configuration.hot_add_workers = default_hot_add_workers;
             }
         }
 
-        // Otherwise run in standalone attack mode.
-        if self.attack_mode == AttackMode::Undefined {
-            self.attack_mode = AttackMode::StandAlone;
-
-            if self.configuration.no_hash_check {
-                return Err(SwanlingError::InvalidOption {
-                    option: "--no-hash-check".to_string(),
-                    value: self.configuration.no_hash_check.to_string(),
-                    detail: "The --no-hash-check flag can not be set without also setting the --manager flag.".to_string(),
-                });
-            }
+        if self.configuration.hot_add_workers && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
         }
 
         Ok(())
     }
 
-    // Change from one attack_phase to another.
-    fn set_attack_phase(
-        &mut self,
-        swanling_attack_run_state: &mut SwanlingAttackRunState,
-        phase: AttackPhase,
-    ) {
-        // There's nothing to do if already in the specified phase.
-        if self.attack_phase == phase {
-            return;
-        }
+    #[cfg(feature = "gaggle")]
+    // Determine if `--manager-also-worker` flag is enabled.
+    fn set_manager_also_worker(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.manager_also_worker";
+        let mut value = false;
 
-        // The drift timer starts at 0 any time the phase is changed.
-        swanling_attack_run_state.drift_timer = tokio::time::Instant::now();
+        if self.configuration.manager_also_worker {
+            key = "--manager-also-worker";
+            value = true;
+        // If not otherwise set and not Worker, check if there's a default.
+        } else if self.attack_mode != AttackMode::Worker {
+            // Optionally set default.
+            if let Some(default_manager_also_worker) = self.defaults.manager_also_worker {
+                key = "set_default(SwanlingDefault::ManagerAlsoWorker)";
+                value = default_manager_also_worker;
 
-        // Optional debug output.
-        info!("entering SwanlingAttack phase: {:?}", &phase);
+                self.configuration.manager_also_worker = default_manager_also_worker;
+            }
+        }
 
-        // Update the current phase.
-        self.attack_phase = phase;
-    }
+        if self.configuration.manager_also_worker && self.attack_mode == AttackMode::Worker {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: value.to_string(),
+                detail: format!("{} can not be set together with the --worker flag.", key),
+            });
+        }
 
-    // Determine how many Workers to expect.
-    fn set_expect_workers(&mut self) -> Result<(), SwanlingError> {
-        // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.expect_workers";
+        Ok(())
+    }
 
-        // Check if --expect-workers was set.
-        if self.configuration.expect_workers.is_some() {
-            key = "--expect-workers";
-        // Otherwise check if a custom default is set.
-        } else if let Some(default_expect_workers) = self.defaults.expect_workers {
-            if self.attack_mode == AttackMode::Manager {
-                key = "set_default(SwanlingDefault::ExpectWorkers)";
+    // Configure which share of the load test this process runs in a fleet without a Manager.
+    fn set_fleet(&mut self) -> Result<(), SwanlingError> {
+        // Track how values get set so we can return a meaningful error if necessary.
+        let mut index_key = "configuration.fleet_index";
+        let mut size_key = "configuration.fleet_size";
+
+        if self.configuration.fleet_index.is_some() {
+            index_key = "--fleet-index";
+        } else if let Some(default_fleet_index) = self.defaults.fleet_index {
+            index_key = "set_default(SwanlingDefault::FleetIndex)";
+            self.configuration.fleet_index = Some(default_fleet_index);
+        }
 
-                self.configuration.expect_workers = Some(default_expect_workers);
-            }
+        if self.configuration.fleet_size.is_some() {
+            size_key = "--fleet-size";
+        } else if let Some(default_fleet_size) = self.defaults.fleet_size {
+            size_key = "set_default(SwanlingDefault::FleetSize)";
+            self.configuration.fleet_size = Some(default_fleet_size);
         }
 
-        if let Some(expect_workers) = self.configuration.expect_workers {
-            // Disallow --expect-workers without --manager.
-            if self.attack_mode != AttackMode::Manager {
+        let (fleet_index, fleet_size) = match (
+            self.configuration.fleet_index,
+            self.configuration.fleet_size,
+        ) {
+            (Some(fleet_index), Some(fleet_size)) => (fleet_index, fleet_size),
+            (None, None) => return Ok(()),
+            (Some(fleet_index), None) => {
                 return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: expect_workers.to_string(),
+                    option: index_key.to_string(),
+                    value: fleet_index.to_string(),
                     detail: format!(
-                        "{} can not be set without also setting the --manager flag.",
-                        key
+                        "{} can not be set without also setting --fleet-size.",
+                        index_key
+                    ),
+                });
+            }
+            (None, Some(fleet_size)) => {
+                return Err(SwanlingError::InvalidOption {
+                    option: size_key.to_string(),
+                    value: fleet_size.to_string(),
+                    detail: format!(
+                        "{} can not be set without also setting --fleet-index.",
+                        size_key
                     ),
                 });
-            } else {
-                // Must expect at least 1 Worker when running as Manager.
-                if expect_workers < 1 {
-                    return Err(SwanlingError::InvalidOption {
-                        option: key.to_string(),
-                        value: expect_workers.to_string(),
-                        detail: format!("{} must be set to at least 1.", key),
-                    });
-                }
-
-                // Must not expect more Workers than Users. Users are required at this point so
-                // using unwrap() is safe.
-                if expect_workers as usize > self.configuration.users.unwrap() {
-                    return Err(SwanlingError::InvalidOption {
-                        option: key.to_string(),
-                        value: expect_workers.to_string(),
-                        detail: format!(
-                            "{} can not be set to a value larger than --users option.",
-                            key
-                        ),
-                    });
-                }
             }
+        };
+
+        // A fleet runs without a Manager.
+        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+            return Err(SwanlingError::InvalidOption {
+                option: size_key.to_string(),
+                value: fleet_size.to_string(),
+                detail: format!(
+                    "{} can not be set together with the --manager or --worker flags.",
+                    size_key
+                ),
+            });
+        }
+
+        if fleet_size < 1 {
+            return Err(SwanlingError::InvalidOption {
+                option: size_key.to_string(),
+                value: fleet_size.to_string(),
+                detail: format!("{} must be set to at least 1.", size_key),
+            });
+        }
+
+        if fleet_index >= fleet_size {
+            return Err(SwanlingError::InvalidOption {
+                option: index_key.to_string(),
+                value: fleet_index.to_string(),
+                detail: format!("{} must be smaller than --fleet-size.", index_key),
+            });
+        }
+
+        // Each process must launch at least one user. Users are required at this point so
+        // using unwrap() is safe.
+        if fleet_size > self.configuration.users.unwrap() {
+            return Err(SwanlingError::InvalidOption {
+                option: size_key.to_string(),
+                value: fleet_size.to_string(),
+                detail: format!(
+                    "{} can not be set to a value larger than --users option.",
+                    size_key
+                ),
+            });
         }
 
+        // Unless otherwise configured, each process displays metrics that can be merged.
+        if self.configuration.summary_format.is_none() {
+            self.configuration.summary_format = Some(SwanlingMetricsFormat::Json);
+        }
+
+        info!("fleet_index = {}, fleet_size = {}", fleet_index, fleet_size);
+
         Ok(())
     }
 
-    // Configure the host and port the Manager listens on.
-    fn set_gaggle_host_and_port(&mut self) -> Result<(), SwanlingError> {
-        // Configure manager_bind_host and manager_bind_port.
-        if self.attack_mode == AttackMode::Manager {
-            // Use default if run-time option not set.
-            if self.configuration.manager_bind_host.is_empty() {
-                self.configuration.manager_bind_host =
-                    if let Some(host) = self.defaults.manager_bind_host.clone() {
-                        host
-                    } else {
-                        "0.0.0.0".to_string()
-                    }
-            }
+    // Configure the request log to replay, if enabled.
+    fn set_replay_log(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.replay_log";
 
-            // Use default if run-time option not set.
-            if self.configuration.manager_bind_port == 0 {
-                self.configuration.manager_bind_port =
-                    if let Some(port) = self.defaults.manager_bind_port {
-                        port
-                    } else {
-                        DEFAULT_PORT.to_string().parse().unwrap()
-                    };
-            }
-        } else {
-            if !self.configuration.manager_bind_host.is_empty() {
-                return Err(SwanlingError::InvalidOption {
-                    option: "--manager-bind-host".to_string(),
-                    value: self.configuration.manager_bind_host.clone(),
-                    detail: "The --manager-bind-host option can not be set together with the --worker flag.".to_string(),
-                });
-            }
+        if !self.configuration.replay_log.is_empty() {
+            key = "--replay-log";
+        } else if let Some(default_replay_log) = self.defaults.replay_log.clone() {
+            key = "set_default(SwanlingDefault::ReplayLog)";
+            self.configuration.replay_log = default_replay_log;
+        }
 
-            if self.configuration.manager_bind_port != 0 {
-                return Err(SwanlingError::InvalidOption {
-                    option: "--manager-bind-port".to_string(),
-                    value: self.configuration.manager_bind_port.to_string(),
-                    detail: "The --manager-bind-port option can not be set together with the --worker flag.".to_string(),
-                });
-            }
+        // Nothing more to do if replay isn't enabled.
+        if self.configuration.replay_log.is_empty() {
+            return Ok(());
         }
 
-        // Configure manager_host and manager_port.
-        if self.attack_mode == AttackMode::Worker {
-            // Use default if run-time option not set.
-            if self.configuration.manager_host.is_empty() {
-                self.configuration.manager_host =
-                    if let Some(host) = self.defaults.manager_host.clone() {
-                        host
-                    } else {
-                        "127.0.0.1".to_string()
-                    }
-            }
+        // Requests can only be replayed by a single process.
+        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.replay_log.clone(),
+                detail: format!(
+                    "{} can not be set together with the --manager or --worker flags.",
+                    key
+                ),
+            });
+        }
 
-            // Use default if run-time option not set.
-            if self.configuration.manager_port == 0 {
-                self.configuration.manager_port = if let Some(port) = self.defaults.manager_port {
-                    port
-                } else {
-                    DEFAULT_PORT.to_string().parse().unwrap()
-                };
-            }
-        } else {
-            if !self.configuration.manager_host.is_empty() {
+        self.replay_requests = match replay::load_replay_log(&self.configuration.replay_log) {
+            Ok(requests) => requests,
+            Err(SwanlingError::InvalidOption { detail, .. }) => {
                 return Err(SwanlingError::InvalidOption {
-                    option: "--manager-host".to_string(),
-                    value: self.configuration.manager_host.clone(),
-                    detail:
-                        "The --manager-host option must be set together with the --worker flag."
-                            .to_string(),
+                    option: key.to_string(),
+                    value: self.configuration.replay_log.clone(),
+                    detail,
                 });
             }
-
-            if self.configuration.manager_port != 0 {
+            Err(e) => {
                 return Err(SwanlingError::InvalidOption {
-                    option: "--manager-port".to_string(),
-                    value: self.configuration.manager_port.to_string(),
-                    detail:
-                        "The --manager-port option must be set together with the --worker flag."
-                            .to_string(),
+                    option: key.to_string(),
+                    value: self.configuration.replay_log.clone(),
+                    detail: format!("Failed to read request log: {}", e),
                 });
             }
+        };
+
+        // In a fleet, each process replays the requests of its share of the users.
+        let configuration = &self.configuration;
+        self.replay_requests
+            .retain(|request| configuration.is_fleet_user(request.user));
+
+        if self.replay_requests.is_empty() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.replay_log.clone(),
+                detail: "The request log does not contain any requests to replay.".to_string(),
+            });
         }
 
+        info!(
+            "replaying {} requests from {}",
+            self.replay_requests.len(),
+            self.configuration.replay_log
+        );
+
         Ok(())
     }
 
-    // Configure how many [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s to hatch.
-    fn set_users(&mut self) -> Result<(), SwanlingError> {
+    // Configure how quickly requests are replayed.
+    fn set_replay_speed(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.users";
-        let mut value = 0;
+        let mut key = "configuration.replay_speed";
+
+        if self.configuration.replay_speed.is_some() {
+            key = "--replay-speed";
+        // The default is only relevant when replaying a request log.
+        } else if !self.configuration.replay_log.is_empty() {
+            if let Some(default_replay_speed) = self.defaults.replay_speed.clone() {
+                key = "set_default(SwanlingDefault::ReplaySpeed)";
+                self.configuration.replay_speed = Some(default_replay_speed);
+            }
+        }
 
-        // Check if --users is set.
-        if let Some(users) = self.configuration.users {
-            key = "--users";
-            value = users;
-        // If not, check if a default for users is set.
-        } else if let Some(default_users) = self.defaults.users {
-            // On Worker users comes from the Manager.
-            if self.attack_mode == AttackMode::Worker {
-                self.configuration.users = None;
-            // Otherwise use default.
-            } else {
-                key = "set_default(SwanlingDefault::Users)";
-                value = default_users;
+        if let Some(replay_speed) = self.configuration.replay_speed.as_ref() {
+            // Setting --replay-speed without --replay-log is not allowed.
+            if self.configuration.replay_log.is_empty() {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: replay_speed.to_string(),
+                    detail: format!("{} can not be set without --replay-log.", key),
+                });
+            }
 
-                self.configuration.users = Some(default_users);
+            match replay_speed.parse::<f32>() {
+                Ok(speed) if speed > 0.0 => {
+                    info!("replay_speed = {}", speed);
+                    self.replay_speed = speed;
+                }
+                _ => {
+                    return Err(SwanlingError::InvalidOption {
+                        option: key.to_string(),
+                        value: replay_speed.to_string(),
+                        detail: format!("{} must be a number greater than 0.", key),
+                    });
+                }
             }
-        // If not and if not running on Worker, default to 1.
-        } else if self.attack_mode != AttackMode::Worker {
-            // This should not be able to fail, but setting up debug in case the number
-            // of cpus library returns an invalid number.
-            key = "num_cpus::get()";
-            value = num_cpus::get();
+        }
 
-            info!("concurrent users defaulted to {} (number of CPUs)", value);
+        Ok(())
+    }
 
-            self.configuration.users = Some(value);
+    // If enabled, returns the path of the report_file, otherwise returns None.
+    fn get_report_file_path(&mut self) -> Option<String> {
+        // If metrics are disabled, or running in Manager mode, there is no
+        // report file, exit immediately.
+        if self.configuration.no_metrics || self.attack_mode == AttackMode::Manager {
+            return None;
         }
 
-        // Perform bounds checking.
-        if let Some(users) = self.configuration.users {
-            // Setting --users with --worker is not allowed.
-            if self.attack_mode == AttackMode::Worker {
+        // If --report-file is set, return it.
+        if !self.configuration.report_file.is_empty() {
+            return Some(self.configuration.report_file.to_string());
+        }
+
+        // If SwanlingDefault::ReportFile is set, return it.
+        if let Some(default_report_file) = &self.defaults.report_file {
+            return Some(default_report_file.to_string());
+        }
+
+        // Otherwise there is no report file.
+        None
+    }
+
+    // Configure requests log format.
+    fn set_request_format(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.request_format";
+        let mut value = Some(SwanlingLogFormat::Json);
+
+        if self.configuration.request_format.is_some() {
+            key = "--requests-format";
+            value = self.configuration.request_format.clone();
+        } else if let Some(default_request_format) = self.defaults.request_format.as_ref() {
+            // In Gaggles, request_format is only set on Worker.
+            if self.attack_mode != AttackMode::Manager {
+                key = "set_default(SwanlingDefault::RequestFormat)";
+                value = Some(default_request_format.clone());
+                self.configuration.request_format = Some(default_request_format.clone());
+            }
+        }
+
+        // Otherwise default to SwanlingLogFormat::Json.
+        if !self.configuration.request_log.is_empty()
+            && self.configuration.request_format.is_none()
+            && self.attack_mode != AttackMode::Manager
+        {
+            self.configuration.request_format = value.clone();
+        }
+
+        if self.configuration.request_format.is_some() {
+            // Log format isn't relevant if metrics aren't enabled.
+            if self.configuration.no_metrics {
                 return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: value.to_string(),
-                    detail: format!("{} can not be set together with the --worker flag.", key),
+                    option: "--no-metrics".to_string(),
+                    value: "true".to_string(),
+                    detail: "The --no-metrics flag can not be set together with the --requests-format option.".to_string(),
                 });
             }
-
-            // Setting users to 0 is not allowed.
-            if users == 0 {
+            // Log format isn't relevant if log not enabled.
+            else if self.configuration.request_log.is_empty() {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: "0".to_string(),
-                    detail: "The --users option must be set to at least 1.".to_string(),
+                    value: format!("{:?}", value),
+                    detail: "The --requests-file option must be set together with the --requests-format option.".to_string(),
                 });
             }
+        }
 
-            // Debug output.
-            info!("users = {}", users);
+        Ok(())
+    }
+
+    // Confirm raw request latencies can be recorded, if enabled.
+    fn set_record_raw_latencies(&mut self) -> Result<(), SwanlingError> {
+        let value = self.configuration.record_raw_latencies.clone();
+        if value.is_empty() {
+            return Ok(());
+        }
+
+        // Track how value gets set so we can return a meaningful error if necessary. The
+        // default was already loaded by `configure_loggers()`.
+        let key = if self.defaults.record_raw_latencies.as_ref() == Some(&value) {
+            "set_default(SwanlingDefault::RecordRawLatencies)"
+        } else {
+            "--record-raw-latencies"
+        };
+
+        // In Gaggles, latencies are recorded by the Workers.
+        if self.attack_mode == AttackMode::Manager {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value,
+                detail: format!("{} can not be set together with the --manager flag.", key),
+            });
+        }
+
+        // Parquet files are only written when compiled with the parquet feature.
+        #[cfg(not(feature = "parquet"))]
+        if logger::is_parquet(&value) {
+            return Err(SwanlingError::FeatureNotEnabled {
+                feature: "parquet".to_string(),
+                detail: format!(
+                    "Load test must be recompiled with `--features parquet` to record {} in the parquet format.",
+                    key
+                ),
+            });
         }
 
         Ok(())
     }
 
-    // Configure maximum run time if specified, otherwise run until canceled.
-    fn set_run_time(&mut self) -> Result<(), SwanlingError> {
+    // Configure tasks log format.
+    fn set_task_format(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.run_time";
-        let mut value = 0;
+        let mut key = "configuration.task_format";
+        let mut value = Some(SwanlingLogFormat::Json);
 
-        // Use --run-time if set, don't allow on Worker.
-        self.run_time = if !self.configuration.run_time.is_empty() {
-            key = "--run-time";
-            value = util::parse_timespan(&self.configuration.run_time);
-            value
-        // Otherwise, use default if set, but not on Worker.
-        } else if let Some(default_run_time) = self.defaults.run_time {
-            if self.attack_mode == AttackMode::Worker {
-                0
-            } else {
-                key = "set_default(SwanlingDefault::RunTime)";
-                value = default_run_time;
-                default_run_time
+        if self.configuration.task_format.is_some() {
+            key = "--tasks-format";
+            value = self.configuration.task_format.clone();
+        } else if let Some(default_task_format) = self.defaults.task_format.as_ref() {
+            // In Gaggles, task_format is only set on Worker.
+            if self.attack_mode != AttackMode::Manager {
+                key = "set_default(SwanlingDefault::TaskFormat)";
+                value = Some(default_task_format.clone());
+                self.configuration.task_format = Some(default_task_format.clone());
             }
         }
-        // Otherwise the test runs until canceled.
-        else {
-            0
-        };
 
-        if self.run_time > 0 {
-            if self.attack_mode == AttackMode::Worker {
+        // Otherwise default to SwanlingLogFormat::Json.
+        if !self.configuration.task_log.is_empty()
+            && self.configuration.task_format.is_none()
+            && self.attack_mode != AttackMode::Manager
+        {
+            self.configuration.task_format = value.clone();
+        }
+
+        if self.configuration.task_format.is_some() {
+            // Log format isn't relevant if metrics aren't enabled.
+            if self.configuration.no_metrics {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--no-metrics".to_string(),
+                    value: "true".to_string(),
+                    detail: "The --no-metrics flag can not be set together with the --tasks-format option.".to_string(),
+                });
+            }
+            // Log format isn't relevant if log not enabled.
+            else if self.configuration.task_log.is_empty() {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: value.to_string(),
-                    detail: format!("{} can not be set together with the --worker flag.", key),
+                    value: format!("{:?}", value),
+                    detail: "The --tasks-file option must be set together with the --tasks-format option.".to_string(),
                 });
             }
-
-            // Debug output.
-            info!("run_time = {}", self.run_time);
         }
 
         Ok(())
     }
 
-    // Configure how quickly to hatch [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s.
-    fn set_hatch_rate(&mut self) -> Result<(), SwanlingError> {
+    // Configure tasks log format.
+    fn set_error_format(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.hatch_rate";
-        let mut value = "".to_string();
+        let mut key = "configuration.error_format";
+        let mut value = Some(SwanlingLogFormat::Json);
 
-        // Check if --hash-rate is set.
-        if let Some(hatch_rate) = &self.configuration.hatch_rate {
-            key = "--hatch_rate";
-            value = hatch_rate.to_string();
-        // If not, check if a default hatch_rate is set.
-        } else if let Some(default_hatch_rate) = &self.defaults.hatch_rate {
-            // On Worker hatch_rate comes from the Manager.
-            if self.attack_mode == AttackMode::Worker {
-                self.configuration.hatch_rate = None;
-            // Otherwise use default.
-            } else {
-                key = "set_default(SwanlingDefault::HatchRate)";
-                value = default_hatch_rate.to_string();
-                self.configuration.hatch_rate = Some(default_hatch_rate.to_string());
+        if self.configuration.error_format.is_some() {
+            key = "--error-format";
+            value = self.configuration.error_format.clone();
+        } else if let Some(default_error_format) = self.defaults.error_format.as_ref() {
+            // In Gaggles, error_format is only set on Worker.
+            if self.attack_mode != AttackMode::Manager {
+                key = "set_default(SwanlingDefault::ErrorFormat)";
+                value = Some(default_error_format.clone());
+                self.configuration.error_format = Some(default_error_format.clone());
             }
-        // If not and if not running on Worker, default to 1.
-        } else if self.attack_mode != AttackMode::Worker {
-            // This should not be able to fail, but setting up debug in case a later
-            // change introduces the potential for failure.
-            key = "Swanling default";
-            value = "1".to_string();
-            self.configuration.hatch_rate = Some(value.to_string());
         }
 
-        // Verbose output.
-        if let Some(hatch_rate) = &self.configuration.hatch_rate {
-            // Setting --hatch-rate with --worker is not allowed.
-            if self.attack_mode == AttackMode::Worker {
+        // Otherwise default to SwanlingLogFormat::Json.
+        if !self.configuration.error_log.is_empty()
+            && self.configuration.error_format.is_none()
+            && self.attack_mode != AttackMode::Manager
+        {
+            self.configuration.error_format = value.clone();
+        }
+
+        if self.configuration.error_format.is_some() {
+            // Log format isn't relevant if metrics aren't enabled.
+            if self.configuration.no_metrics {
                 return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value,
-                    detail: format!("{} can not be set together with the --worker flag.", key),
+                    option: "--no-metrics".to_string(),
+                    value: "true".to_string(),
+                    detail: "The --no-metrics flag can not be set together with the --error-format option.".to_string(),
                 });
             }
-
-            // Setting --hatch-rate of 0 is not allowed.
-            if hatch_rate.is_empty() {
+            // Log format isn't relevant if log not enabled.
+            else if self.configuration.error_log.is_empty() {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value,
-                    detail: format!("{} must be set to at least 1.", key),
+                    value: format!("{:?}", value),
+                    detail: "The --error-file option must be set together with the --error-format option.".to_string(),
                 });
             }
-
-            // Debug output.
-            info!("hatch_rate = {}", hatch_rate);
         }
 
         Ok(())
     }
 
-    // Configure the coordinated omission mitigation strategy.
-    fn set_coordinated_omission(&mut self) -> Result<(), SwanlingError> {
+    // Configure how long to aggregate identical errors in the error log.
+    fn set_error_log_window(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.coordinated_omission";
-        let mut value = Some(SwanlingCoordinatedOmissionMitigation::Disabled);
+        let mut key = "configuration.error_log_window";
 
-        if self.configuration.co_mitigation.is_some() {
-            key = "--co-mitigation";
-            value = self.configuration.co_mitigation.clone();
+        if !self.configuration.error_log_window.is_empty() {
+            key = "--error-log-window";
+        // If not otherwise set and not Manager, check if there's a default.
+        } else if self.attack_mode != AttackMode::Manager {
+            if let Some(default_error_log_window) = self.defaults.error_log_window.clone() {
+                key = "set_default(SwanlingDefault::ErrorLogWindow)";
+                self.configuration.error_log_window = default_error_log_window;
+            }
         }
 
-        // Use default for co_mitigation if set and not on Worker.
-        if self.configuration.co_mitigation.is_none() {
-            if let Some(default_co_mitigation) = self.defaults.co_mitigation.as_ref() {
-                // In Gaggles, co_mitigation is only set on Manager.
-                if self.attack_mode != AttackMode::Worker {
-                    key = "set_default(SwanlingDefault::CoordinatedOmissionMitigation)";
-                    value = Some(default_co_mitigation.clone());
+        if self.configuration.error_log_window.is_empty() {
+            return Ok(());
+        }
 
-                    self.configuration.co_mitigation = Some(default_co_mitigation.clone());
-                }
+        // In Gaggles, the error log is written by Workers.
+        if self.attack_mode == AttackMode::Manager {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.error_log_window.clone(),
+                detail: format!("{} can not be set together with the --manager flag.", key),
+            });
+        }
+
+        if util::parse_milliseconds(&self.configuration.error_log_window).is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.error_log_window.clone(),
+                detail: format!("{} must be a time window, for example 1s.", key),
+            });
+        }
+
+        // Errors can only be aggregated if they are logged.
+        if self.configuration.error_log.is_empty() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.error_log_window.clone(),
+                detail: format!("The --error-log option must be set together with {}.", key),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Configure debug log format.
+    fn set_debug_format(&mut self) -> Result<(), SwanlingError> {
+        // Track how value gets set so we can return a meaningful error if necessary.
+        let mut key = "configuration.debug_format";
+        let mut value = Some(SwanlingLogFormat::Json);
+
+        if self.configuration.debug_format.is_some() {
+            key = "--debug-format";
+            value = self.configuration.debug_format.clone();
+        } else if let Some(default_debug_format) = self.defaults.debug_format.as_ref() {
+            // In Gaggles, debug_format is only set on Worker.
+            if self.attack_mode != AttackMode::Manager {
+                key = "set_default(SwanlingDefault::DebugFormat)";
+                value = Some(default_debug_format.clone());
+                self.configuration.debug_format = Some(default_debug_format.clone());
             }
         }
 
-        // Otherwise default to SwanlingCoordinaatedOmissionMitigation::Average.
-        if self.configuration.co_mitigation.is_none() && self.attack_mode != AttackMode::Worker {
-            self.configuration.co_mitigation = value.clone();
+        // Otherwise default to SwanlingLogFormat::Json.
+        if !self.configuration.debug_log.is_empty()
+            && self.configuration.debug_format.is_none()
+            && self.attack_mode != AttackMode::Manager
+        {
+            self.configuration.debug_format = value.clone();
         }
 
-        if let Some(co_mitigation) = self.configuration.co_mitigation.as_ref() {
-            // Setting --co-mitigation with --worker is not allowed.
-            if self.attack_mode == AttackMode::Worker {
+        if self.configuration.debug_format.is_some() {
+            // Log format isn't relevant if log not enabled.
+            if self.configuration.debug_log.is_empty() {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
                     value: format!("{:?}", value),
-                    detail: format!("{} can not be set together with the --worker flag.", key),
+                    detail: "The --debug-file option must be set together with the --debug-format option.".to_string(),
                 });
             }
+        }
 
-            // Setting --co-mitigation with --no-metrics is not allowed.
-            if self.configuration.no_metrics {
+        Ok(())
+    }
+
+    // Configure whether or not to only print the plan of the load test.
+    fn set_dry_run(&mut self) -> Result<(), SwanlingError> {
+        if self.configuration.dry_run {
+            // Workers receive their plan from the Manager.
+            if self.attack_mode == AttackMode::Worker {
                 return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: format!("{:?}", value),
-                    detail: format!(
-                        "{} can not be set together with the --no-metrics flag.",
-                        key
-                    ),
+                    option: "--dry-run".to_string(),
+                    value: "true".to_string(),
+                    detail: "--dry-run can not be set together with the --worker flag.".to_string(),
                 });
             }
 
-            if co_mitigation != &SwanlingCoordinatedOmissionMitigation::Disabled
-                && self.scheduler == SwanlingScheduler::Random
-            {
-                // Coordinated Omission Mitigation is not possible together with the random scheduler,
-                // as it's impossible to calculate an accurate request cadence.
+            // There's no load test to plan when merging earlier reports.
+            if self.merging_reports() {
                 return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: format!("{:?}", value),
-                    detail: format!(
-                        "{} can not be set together with SwanlingScheduler::Random.",
-                        key
-                    ),
+                    option: "--dry-run".to_string(),
+                    value: "true".to_string(),
+                    detail: "--dry-run can not be set together with --merge-reports.".to_string(),
                 });
             }
-
-            info!(
-                "co_mitigation = {:?}",
-                self.configuration.co_mitigation.as_ref().unwrap()
-            );
         }
 
         Ok(())
     }
 
-    // Configure maximum requests per second if throttle enabled.
-    fn set_throttle_requests(&mut self) -> Result<(), SwanlingError> {
-        // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.throttle_requests";
-        let mut value = 0;
-
-        if self.configuration.throttle_requests > 0 {
-            key = "--throttle-requests";
-            value = self.configuration.throttle_requests;
-        }
-
-        // Use default for throttle_requests if set and not on Worker.
-        if self.configuration.throttle_requests == 0 {
-            if let Some(default_throttle_requests) = self.defaults.throttle_requests {
-                // In Gaggles, throttle_requests is only set on Worker.
-                if self.attack_mode != AttackMode::Manager {
-                    key = "set_default(SwanlingDefault::ThrottleRequests)";
-                    value = default_throttle_requests;
-
-                    self.configuration.throttle_requests = default_throttle_requests;
-                }
-            }
-        }
-
-        if self.configuration.throttle_requests > 0 {
-            // Setting --throttle-requests with --worker is not allowed.
-            if self.attack_mode == AttackMode::Manager {
+    // Configure whether or not to only run a single user, printing each response.
+    fn set_debug_single_user(&mut self) -> Result<(), SwanlingError> {
+        if self.configuration.debug_single_user {
+            // Can't debug a single user in Regatta mode.
+            if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
                 return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: value.to_string(),
-                    detail: format!("{} can not be set together with the --manager flag.", key),
+                    option: "--debug-single-user".to_string(),
+                    value: "true".to_string(),
+                    detail: "--debug-single-user can not be set together with the --manager or --worker flags."
+                        .to_string(),
                 });
             }
 
-            // Be sure throttle_requests is in allowed range.
-            if self.configuration.throttle_requests == 0 {
+            if self.configuration.dry_run {
                 return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: value.to_string(),
-                    detail: format!("{} must be set to at least 1 request per second.", key),
+                    option: "--debug-single-user".to_string(),
+                    value: "true".to_string(),
+                    detail: "--debug-single-user can not be set together with --dry-run."
+                        .to_string(),
                 });
-            } else if self.configuration.throttle_requests > 1_000_000 {
+            }
+
+            if self.merging_reports() {
                 return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: value.to_string(),
-                    detail: format!(
-                        "{} can not be set to more than 1,000,000 requests per second.",
-                        key
-                    ),
+                    option: "--debug-single-user".to_string(),
+                    value: "true".to_string(),
+                    detail: "--debug-single-user can not be set together with --merge-reports."
+                        .to_string(),
                 });
             }
-
-            info!(
-                "throttle_requests = {}",
-                self.configuration.throttle_requests
-            );
         }
 
         Ok(())
     }
 
-    // Determine if `no_reset_statics` is enabled.
-    fn set_no_reset_metrics(&mut self) -> Result<(), SwanlingError> {
-        // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.no_reset_metrics";
-        let mut value = false;
-
-        if self.configuration.no_reset_metrics {
-            key = "--no-reset-metrics";
-            value = true;
-        // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
-            if let Some(default_no_reset_metrics) = self.defaults.no_reset_metrics {
-                key = "set_default(SwanlingDefault::NoResetMetrics)";
-                value = default_no_reset_metrics;
-
-                // Optionally set default.
-                self.configuration.no_reset_metrics = default_no_reset_metrics;
+    // Configure whether or not to enable the telnet Controller. Always disable when running as a Worker.
+    fn set_no_telnet(&mut self) {
+        // Workers are controlled by the Manager, force disable.
+        if self.attack_mode == AttackMode::Worker {
+            self.configuration.no_telnet = true;
+        // Otherwise, if --no-telnet flag not set, respect default if configured.
+        } else if !self.configuration.no_telnet {
+            if let Some(default_no_telnet) = self.defaults.no_telnet {
+                self.configuration.no_telnet = default_no_telnet;
             }
         }
+    }
 
-        // Setting --no-reset-metrics with --worker is not allowed.
-        if self.configuration.no_reset_metrics && self.attack_mode == AttackMode::Worker {
-            return Err(SwanlingError::InvalidOption {
-                option: key.to_string(),
-                value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
-            });
+    // Configure whether or not to enable the WebSocket Controller. Always disable when running as a Worker.
+    fn set_no_websocket(&mut self) {
+        // Workers are controlled by the Manager, force disable.
+        if self.attack_mode == AttackMode::Worker {
+            self.configuration.no_websocket = true;
+        // Otherwise, if --no-websocket flag not set, respect default if configured.
+        } else if !self.configuration.no_websocket {
+            if let Some(default_no_telnet) = self.defaults.no_telnet {
+                self.configuration.no_websocket = default_no_telnet;
+            }
         }
-
-        Ok(())
     }
 
-    // Determine if the `--status-codes` flag is enabled.
-    fn set_status_codes(&mut self) -> Result<(), SwanlingError> {
+    // Configure when to start the load test.
+    fn set_start_at(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.status_codes";
-        let mut value = false;
+        let mut key = "configuration.start_at";
 
-        if self.configuration.status_codes {
-            key = "--status-codes";
-            value = true;
-        // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
-            if let Some(default_status_codes) = self.defaults.status_codes {
-                key = "set_default(SwanlingDefault::StatusCodes)";
-                value = default_status_codes;
+        if !self.configuration.start_at.is_empty() {
+            key = "--start-at";
+        // Otherwise set default if configured.
+        } else if let Some(default_start_at) = self.defaults.start_at.clone() {
+            key = "set_default(SwanlingDefault::StartAt)";
+            self.configuration.start_at = default_start_at;
+        }
 
-                // Optionally set default.
-                self.configuration.status_codes = default_status_codes;
-            }
+        if self.configuration.start_at.is_empty() {
+            return Ok(());
         }
 
-        // Setting --status-codes with --worker is not allowed.
-        if self.configuration.status_codes && self.attack_mode == AttackMode::Worker {
+        // Regatta Workers are started by the Manager.
+        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
             return Err(SwanlingError::InvalidOption {
                 option: key.to_string(),
-                value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
+                value: self.configuration.start_at.clone(),
+                detail: format!(
+                    "{} can not be set together with the --manager or --worker flags.",
+                    key
+                ),
+            });
+        }
+
+        if self.configuration.get_start_at().is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: key.to_string(),
+                value: self.configuration.start_at.clone(),
+                detail: format!(
+                    "{} must be an RFC 3339 timestamp, for example 2024-06-01T02:00:00Z.",
+                    key
+                ),
             });
         }
 
         Ok(())
     }
 
-    // Determine if the `--running-metrics` flag is enabled.
-    fn set_running_metrics(&mut self) -> Result<(), SwanlingError> {
+    // Configure whether or not to autostart the load test.
+    fn set_no_autostart(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.running_metrics";
-        let mut value = 0;
+        let mut key = "configuration.no_autostart";
+        let mut value = false;
 
-        if let Some(running_metrics) = self.configuration.running_metrics {
-            key = "--running-metrics";
-            value = running_metrics;
-        // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
-            // Optionally set default.
-            if let Some(default_running_metrics) = self.defaults.running_metrics {
-                key = "set_default(SwanlingDefault::RunningMetrics)";
-                value = default_running_metrics;
+        // Currently Gaggles are not Controller-aware.
+        if self.configuration.no_autostart {
+            key = "--no-autostart";
+            value = true;
+        // Otherwise set default if configured.
+        } else if let Some(default_no_autostart) = self.defaults.no_autostart {
+            key = "set_default(SwanlingDefault::NoAutoStart)";
+            value = default_no_autostart;
 
-                self.configuration.running_metrics = Some(default_running_metrics);
-            }
+            self.configuration.no_autostart = default_no_autostart;
         }
 
-        // Setting --running-metrics with --worker is not allowed.
-        if let Some(running_metrics) = self.configuration.running_metrics {
-            if self.attack_mode == AttackMode::Worker {
+        if self.configuration.no_autostart {
+            // Can't disable autostart in Regatta mode.
+            if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
                     value: value.to_string(),
-                    detail: format!("{} can not be set together with the --worker flag.", key),
+                    detail: format!(
+                        "{} can not be set together with the --manager or --worker flags.",
+                        key
+                    ),
                 });
             }
 
-            if running_metrics > 0 {
-                info!("running_metrics = {}", running_metrics);
+            // Can't disable autostart if there's no Controller or dashboard enabled.
+            if self.configuration.no_telnet
+                && self.configuration.no_websocket
+                && !self.configuration.tui
+                && self.defaults.tui != Some(true)
+            {
+                return Err(SwanlingError::InvalidOption {
+                    option: key.to_string(),
+                    value: value.to_string(),
+                    detail: format!("{} can not be set together with both the --no-telnet and --no-websocket flags.", key),
+                });
             }
         }
 
         Ok(())
     }
 
-    // Determine if the `--no-task-metrics` flag is enabled.
-    fn set_no_task_metrics(&mut self) -> Result<(), SwanlingError> {
+    // Configure whether to log response body.
+    fn set_no_debug_body(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.no_task_metrics";
+        let mut key = "configuration.no_debug_body";
         let mut value = false;
 
-        if self.configuration.no_task_metrics {
-            key = "--no-task-metrics";
+        if self.configuration.no_debug_body {
+            key = "--no-debug-body";
             value = true;
-        // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
+        // If not otherwise set and not Manager, check if there's a default.
+        } else if self.attack_mode != AttackMode::Manager {
             // Optionally set default.
-            if let Some(default_no_task_metrics) = self.defaults.no_task_metrics {
-                key = "set_default(SwanlingDefault::NoTaskMetrics)";
-                value = default_no_task_metrics;
+            if let Some(default_no_debug_body) = self.defaults.no_debug_body {
+                key = "set_default(SwanlingDefault::NoDebugBody)";
+                value = default_no_debug_body;
 
-                self.configuration.no_task_metrics = default_no_task_metrics;
+                self.configuration.no_debug_body = default_no_debug_body;
             }
         }
 
-        // Setting --no-task-metrics with --worker is not allowed.
-        if self.configuration.no_task_metrics && self.attack_mode == AttackMode::Worker {
+        if self.configuration.no_debug_body && self.attack_mode == AttackMode::Manager {
             return Err(SwanlingError::InvalidOption {
                 option: key.to_string(),
                 value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
+                detail: format!("{} can not be set together with the --manager flag.", key),
             });
         }
 
         Ok(())
     }
 
-    // Determine if the `--no-error-summary` flag is enabled.
-    fn set_no_error_summary(&mut self) -> Result<(), SwanlingError> {
+    // Configure how many bytes of response bodies to include in the debug log.
+    fn set_debug_body_max_bytes(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.no_error_summary";
-        let mut value = false;
-
-        if self.configuration.no_error_summary {
-            key = "--no-error-summary";
-            value = true;
-        // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
-            // Optionally set default.
-            if let Some(default_no_error_summary) = self.defaults.no_error_summary {
-                key = "set_default(SwanlingDefault::NoErrorSummary)";
-                value = default_no_error_summary;
+        let mut key = "configuration.debug_body_max_bytes";
 
-                self.configuration.no_error_summary = default_no_error_summary;
+        if self.configuration.debug_body_max_bytes > 0 {
+            key = "--debug-body-max-bytes";
+        // If not otherwise set and not Manager, check if there's a default.
+        } else if self.attack_mode != AttackMode::Manager {
+            if let Some(default_debug_body_max_bytes) = self.defaults.debug_body_max_bytes {
+                key = "set_default(SwanlingDefault::DebugBodyMaxBytes)";
+                self.configuration.debug_body_max_bytes = default_debug_body_max_bytes;
             }
         }
 
-        // Setting --no-error-summary with --worker is not allowed.
-        if self.configuration.no_error_summary && self.attack_mode == AttackMode::Worker {
+        // The debug log is written by Workers.
+        if self.configuration.debug_body_max_bytes > 0 && self.attack_mode == AttackMode::Manager {
             return Err(SwanlingError::InvalidOption {
                 option: key.to_string(),
-                value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
+                value: self.configuration.debug_body_max_bytes.to_string(),
+                detail: format!("{} can not be set together with the --manager flag.", key),
             });
         }
 
         Ok(())
     }
 
-    // Determine if the `--no-metrics` flag is enabled.
-    fn set_no_metrics(&mut self) -> Result<(), SwanlingError> {
+    // Configure regular expressions redacted from the debug log.
+    fn set_debug_redact(&mut self) -> Result<(), SwanlingError> {
         // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.no_metrics";
-        let mut value = false;
+        let mut key = "configuration.debug_redact";
 
-        if self.configuration.no_metrics {
-            key = "--no-metrics";
-            value = true;
-        // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
-            // Optionally set default.
-            if let Some(default_no_metrics) = self.defaults.no_metrics {
-                key = "set_default(SwanlingDefault::NoMetrics)";
-                value = default_no_metrics;
+        if !self.configuration.debug_redact.is_empty() {
+            key = "--debug-redact";
+        // If not otherwise set and not Manager, check if there's a default.
+        } else if self.attack_mode != AttackMode::Manager && !self.defaults.debug_redact.is_empty()
+        {
+            key = "set_default(SwanlingDefault::DebugRedact)";
+            self.configuration.debug_redact = self.defaults.debug_redact.clone();
+        }
 
-                self.configuration.no_metrics = default_no_metrics;
-            }
+        if self.configuration.debug_redact.is_empty() {
+            return Ok(());
         }
 
-        // Setting --no-metrics with --worker is not allowed.
-        if self.configuration.no_metrics && self.attack_mode == AttackMode::Worker {
+        // The debug log is written by Workers.
+        if self.attack_mode == AttackMode::Manager {
             return Err(SwanlingError::InvalidOption {
                 option: key.to_string(),
-                value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
+                value: self.configuration.debug_redact.join(" "),
+                detail: format!("{} can not be set together with the --manager flag.", key),
             });
         }
 
-        // Don't allow overhead of collecting metrics unless we're printing them.
-        if self.configuration.no_metrics {
-            if self.configuration.status_codes {
-                return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: value.to_string(),
-                    detail: format!(
-                        "{} can not be set together with the --status-codes flag.",
-                        key
-                    ),
-                });
-            }
-
-            // Don't allow overhead of collecting metrics unless we're printing them.
-            if self.configuration.running_metrics.is_some() {
-                return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: value.to_string(),
-                    detail: format!(
-                        "{} can not be set together with the --running_metrics option.",
-                        key
-                    ),
-                });
-            }
-
-            // There is nothing to log if metrics are disabled.
-            if !self.configuration.request_log.is_empty() {
+        for pattern in &self.configuration.debug_redact {
+            if let Err(e) = regex::Regex::new(pattern) {
                 return Err(SwanlingError::InvalidOption {
                     option: key.to_string(),
-                    value: value.to_string(),
-                    detail: format!(
-                        "{} can not be set together with the --requests-file option.",
-                        key
-                    ),
+                    value: pattern.to_string(),
+                    detail: format!("{} must be a valid regular expression: {}", key, e),
                 });
             }
         }
@@ -2199,577 +6060,851 @@ impl SwanlingAttack {
         Ok(())
     }
 
-    // Determine if the `--sticky-follow` flag is enabled.
-    fn set_sticky_follow(&mut self) -> Result<(), SwanlingError> {
-        // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.sticky_follow";
-        let mut value = false;
-
-        if self.configuration.sticky_follow {
-            key = "--sticky-follow";
-            value = true;
-        // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
-            // Optionally set default.
-            if let Some(default_sticky_follow) = self.defaults.sticky_follow {
-                key = "set_default(SwanlingDefault::StickyFollow)";
-                value = default_sticky_follow;
-
-                self.configuration.sticky_follow = default_sticky_follow;
-            }
-        }
-
-        if self.configuration.sticky_follow && self.attack_mode == AttackMode::Worker {
-            return Err(SwanlingError::InvalidOption {
-                option: key.to_string(),
-                value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
-            });
-        }
+    /// Execute the [`SwanlingAttack`](./struct.SwanlingAttack.html) load test.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let _swanling_metrics = SwanlingAttack::initialize()?
+    ///         .register_taskset(taskset!("ExampleTasks")
+    ///             .register_task(task!(example_task).set_weight(2)?)
+    ///             .register_task(task!(another_example_task).set_weight(3)?)
+    ///             // Swanling must run against a host, point to localhost so test starts.
+    ///             .set_host("http://localhost")
+    ///         )
+    ///         // Exit after one second so test doesn't run forever.
+    ///         .set_default(SwanlingDefault::RunTime, 1)?
+    ///         .execute()?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn example_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/foo").await?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn another_example_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/bar").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn execute(mut self) -> Result<SwanlingMetrics, SwanlingError> {
+        self.prepare_execute()?;
 
-        Ok(())
+        let rt = self.runtime()?;
+        rt.block_on(self.run_execute())
     }
 
-    #[cfg(feature = "gaggle")]
-    // Determine if `--no-hash-check` flag is enabled.
-    fn set_no_hash_check(&mut self) -> Result<(), SwanlingError> {
-        // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.no_hash_check";
-        let mut value = false;
-
-        if self.configuration.no_hash_check {
-            key = "--no-hash-check";
-            value = true;
-        // If not otherwise set and not Worker, check if there's a default.
-        } else if self.attack_mode != AttackMode::Worker {
-            // Optionally set default.
-            if let Some(default_no_hash_check) = self.defaults.no_hash_check {
-                key = "set_default(SwanlingDefault::NoHashCheck)";
-                value = default_no_hash_check;
-
-                self.configuration.no_hash_check = default_no_hash_check;
-            }
-        }
+    /// Execute the [`SwanlingAttack`](./struct.SwanlingAttack.html) load test on the tokio
+    /// runtime that's already running, instead of building a runtime, returning the metrics
+    /// once the load test finishes. This allows Swanling to be embedded in applications that
+    /// already run tokio, for example a service controlling load tests.
+    ///
+    /// This can't be used together with `--runtime-threads`, and any runtime supplied with
+    /// [`set_runtime`](./struct.SwanlingAttack.html#method.set_runtime) is ignored. The
+    /// Controllers keep listening until the runtime shuts down, so disable them with
+    /// `--no-telnet` and `--no-websocket` to run more than one load test.
+    ///
+    /// Swanling doesn't catch ctrl-c, which is left to the application: stop the load test
+    /// with the handle returned by
+    /// [`execute_with_handle`](./struct.SwanlingAttack.html#method.execute_with_handle).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), SwanlingError> {
+    ///     let _swanling_metrics = SwanlingAttack::initialize()?
+    ///         .register_taskset(taskset!("ExampleTasks")
+    ///             .register_task(task!(example_task))
+    ///             // Swanling must run against a host, point to localhost so test starts.
+    ///             .set_host("http://localhost")
+    ///         )
+    ///         // Exit after one second so test doesn't run forever.
+    ///         .set_default(SwanlingDefault::RunTime, 1)?
+    ///         .execute_async()
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn example_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/foo").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn execute_async(mut self) -> Result<SwanlingMetrics, SwanlingError> {
+        self.prepare_execute()?;
 
-        if self.configuration.no_hash_check && self.attack_mode == AttackMode::Worker {
+        // The load test runs on the runtime that's already running.
+        if let Some(runtime_threads) = self.configuration.runtime_threads {
             return Err(SwanlingError::InvalidOption {
-                option: key.to_string(),
-                value: value.to_string(),
-                detail: format!("{} can not be set together with the --worker flag.", key),
+                option: "--runtime-threads".to_string(),
+                value: runtime_threads.to_string(),
+                detail: "--runtime-threads can not be set together with execute_async()."
+                    .to_string(),
             });
         }
 
-        Ok(())
+        // The application embedding the load test handles ctrl-c itself.
+        self.catch_ctrlc = false;
+
+        self.run_execute().await
     }
 
-    // If enabled, returns the path of the report_file, otherwise returns None.
-    fn get_report_file_path(&mut self) -> Option<String> {
-        // If metrics are disabled, or running in Manager mode, there is no
-        // report file, exit immediately.
-        if self.configuration.no_metrics || self.attack_mode == AttackMode::Manager {
-            return None;
-        }
+    /// Execute the [`SwanlingAttack`](./struct.SwanlingAttack.html) load test like
+    /// [`execute_async`](./struct.SwanlingAttack.html#method.execute_async), also returning a
+    /// [`SwanlingAttackHandle`](./controller/struct.SwanlingAttackHandle.html) that stops or
+    /// shuts down the load test and gets snapshots of its metrics, the same way the Controllers
+    /// do. The load test runs once the returned future is awaited or spawned.
+    ///
+    /// The handle controls load tests running in standalone mode, and works with the
+    /// telnet and WebSocket Controllers disabled.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), SwanlingError> {
+    ///     let (handle, attack) = SwanlingAttack::initialize()?
+    ///         .register_taskset(taskset!("ExampleTasks")
+    ///             .register_task(task!(example_task))
+    ///             // Swanling must run against a host, point to localhost so test starts.
+    ///             .set_host("http://localhost")
+    ///         )
+    ///         .set_default(SwanlingDefault::NoTelnet, true)?
+    ///         .set_default(SwanlingDefault::NoWebSocket, true)?
+    ///         .execute_with_handle();
+    ///     let attack = tokio::spawn(attack);
+    ///
+    ///     // Shut down the load test after one second.
+    ///     tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    ///     if let Some(metrics) = handle.metrics_snapshot().await {
+    ///         println!("{} users running", metrics.users);
+    ///     }
+    ///     handle.shutdown().await;
+    ///
+    ///     let _swanling_metrics = attack.await??;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn example_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/foo").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn execute_with_handle(
+        mut self,
+    ) -> (
+        SwanlingAttackHandle,
+        impl Future<Output = Result<SwanlingMetrics, SwanlingError>>,
+    ) {
+        let (controller_request_tx, controller_request_rx) = flume::unbounded();
+        let handle = SwanlingAttackHandle::new(controller_request_tx.clone());
+        self.handle_channel = Some((controller_request_tx, controller_request_rx));
 
-        // If --report-file is set, return it.
-        if !self.configuration.report_file.is_empty() {
-            return Some(self.configuration.report_file.to_string());
+        (handle, self.execute_async())
+    }
+
+    // Validate the load test and its configuration before executing it.
+    fn prepare_execute(&mut self) -> Result<(), SwanlingError> {
+        // If help flag is set, return usage instead of running the load test.
+        if self.configuration.help {
+            return Err(SwanlingError::Help {
+                message: format!(
+                    "Usage: {} [OPTIONS]\n\n{}",
+                    std::env::args().next().unwrap_or_default(),
+                    SwanlingConfiguration::usage()
+                ),
+            });
         }
 
-        // If SwanlingDefault::ReportFile is set, return it.
-        if let Some(default_report_file) = &self.defaults.report_file {
-            return Some(default_report_file.to_string());
+        // If version flag is set, return package name and version instead of running the
+        // load test.
+        if self.configuration.version {
+            return Err(SwanlingError::Help {
+                message: format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            });
         }
 
-        // Otherwise there is no report file.
-        None
-    }
+        // Collect all problems with the load test and its configuration, to report them
+        // together.
+        let mut errors = Vec::new();
 
-    // Configure requests log format.
-    fn set_request_format(&mut self) -> Result<(), SwanlingError> {
-        // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.request_format";
-        let mut value = Some(SwanlingLogFormat::Json);
+        // At least one task set is required.
+        if self.task_sets.is_empty() {
+            errors.push(SwanlingError::NoTaskSets {
+                detail: "No task sets are defined.".to_string(),
+            });
+        }
 
-        if self.configuration.request_format.is_some() {
-            key = "--requests-format";
-            value = self.configuration.request_format.clone();
-        } else if let Some(default_request_format) = self.defaults.request_format.as_ref() {
-            // In Gaggles, request_format is only set on Worker.
-            if self.attack_mode != AttackMode::Manager {
-                key = "set_default(SwanlingDefault::RequestFormat)";
-                value = Some(default_request_format.clone());
-                self.configuration.request_format = Some(default_request_format.clone());
-            }
+        // Confirm names don't collide, merging unrelated metrics.
+        if let Err(e) = self.validate_names() {
+            e.collect_into(&mut errors);
         }
 
-        // Otherwise default to SwanlingLogFormat::Json.
-        if !self.configuration.request_log.is_empty()
-            && self.configuration.request_format.is_none()
-            && self.attack_mode != AttackMode::Manager
-        {
-            self.configuration.request_format = value.clone();
+        // Confirm all task dependencies can be resolved.
+        if let Err(e) = self.validate_task_dependencies() {
+            e.collect_into(&mut errors);
         }
 
-        if self.configuration.request_format.is_some() {
-            // Log format isn't relevant if metrics aren't enabled.
-            if self.configuration.no_metrics {
-                return Err(SwanlingError::InvalidOption {
-                    option: "--no-metrics".to_string(),
-                    value: "true".to_string(),
-                    detail: "The --no-metrics flag can not be set together with the --requests-format option.".to_string(),
+        // Validate the format task sets and tasks are listed in.
+        if !self.configuration.format.is_empty() {
+            if !self.configuration.list {
+                errors.push(SwanlingError::InvalidOption {
+                    option: "--format".to_string(),
+                    value: self.configuration.format.clone(),
+                    detail: "--format can only be set together with --list.".to_string(),
                 });
-            }
-            // Log format isn't relevant if log not enabled.
-            else if self.configuration.request_log.is_empty() {
-                return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: format!("{:?}", value),
-                    detail: "The --requests-file option must be set together with the --requests-format option.".to_string(),
+            } else if !["text", "json"].contains(&self.configuration.format.as_str()) {
+                errors.push(SwanlingError::InvalidOption {
+                    option: "--format".to_string(),
+                    value: self.configuration.format.clone(),
+                    detail: "--format must be text or json.".to_string(),
                 });
             }
         }
 
-        Ok(())
-    }
+        // Display task sets and tasks instead of running the load test.
+        if self.configuration.list && errors.is_empty() {
+            if self.configuration.format == "json" {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&self.list()).expect("unexpected serde failure")
+                );
+            } else {
+                println!("Available tasks:");
+                for task_set in self.list() {
+                    if let Some(fixed_users) = task_set.fixed_users {
+                        println!(" - {} (fixed users: {})", task_set.name, fixed_users);
+                    } else {
+                        println!(" - {} (weight: {})", task_set.name, task_set.weight);
+                    }
+                    for task in task_set.tasks {
+                        if let Some(schedule) = task.schedule {
+                            println!("    o {} (every: {:?})", task.name, schedule);
+                        } else {
+                            println!("    o {} (weight: {})", task.name, task.weight);
+                        }
+                    }
+                }
+            }
+            return Ok(());
+        }
 
-    // Configure tasks log format.
-    fn set_task_format(&mut self) -> Result<(), SwanlingError> {
-        // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.task_format";
-        let mut value = Some(SwanlingLogFormat::Json);
+        // Configure loggers.
+        self.configuration.configure_loggers(&self.defaults);
 
-        if self.configuration.task_format.is_some() {
-            key = "--tasks-format";
-            value = self.configuration.task_format.clone();
-        } else if let Some(default_task_format) = self.defaults.task_format.as_ref() {
-            // In Gaggles, task_format is only set on Worker.
-            if self.attack_mode != AttackMode::Manager {
-                key = "set_default(SwanlingDefault::TaskFormat)";
-                value = Some(default_task_format.clone());
-                self.configuration.task_format = Some(default_task_format.clone());
-            }
+        // Initialize logger.
+        self.initialize_logger();
+
+        // Configure run mode (StandAlone, Worker, Manager).
+        if let Err(e) = self.set_attack_mode() {
+            e.collect_into(&mut errors);
         }
 
-        // Otherwise default to SwanlingLogFormat::Json.
-        if !self.configuration.task_log.is_empty()
-            && self.configuration.task_format.is_none()
-            && self.attack_mode != AttackMode::Manager
-        {
-            self.configuration.task_format = value.clone();
+        // Confirm feeders don't hand out the same rows on every Worker.
+        if let Err(e) = self.validate_feeders() {
+            e.collect_into(&mut errors);
         }
 
-        if self.configuration.task_format.is_some() {
-            // Log format isn't relevant if metrics aren't enabled.
-            if self.configuration.no_metrics {
-                return Err(SwanlingError::InvalidOption {
-                    option: "--no-metrics".to_string(),
-                    value: "true".to_string(),
-                    detail: "The --no-metrics flag can not be set together with the --tasks-format option.".to_string(),
-                });
-            }
-            // Log format isn't relevant if log not enabled.
-            else if self.configuration.task_log.is_empty() {
-                return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: format!("{:?}", value),
-                    detail: "The --tasks-file option must be set together with the --tasks-format option.".to_string(),
-                });
-            }
+        // Determine whether or not to only print the plan of the load test.
+        if let Err(e) = self.set_dry_run() {
+            e.collect_into(&mut errors);
         }
 
-        Ok(())
-    }
+        // Determine whether or not to only run a single user, printing each response.
+        if let Err(e) = self.set_debug_single_user() {
+            e.collect_into(&mut errors);
+        }
 
-    // Configure tasks log format.
-    fn set_error_format(&mut self) -> Result<(), SwanlingError> {
-        // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.error_format";
-        let mut value = Some(SwanlingLogFormat::Json);
+        // Determine whether or not to enable the telnet Controller.
+        self.set_no_telnet();
 
-        if self.configuration.error_format.is_some() {
-            key = "--error-format";
-            value = self.configuration.error_format.clone();
-        } else if let Some(default_error_format) = self.defaults.error_format.as_ref() {
-            // In Gaggles, error_format is only set on Worker.
-            if self.attack_mode != AttackMode::Manager {
-                key = "set_default(SwanlingDefault::ErrorFormat)";
-                value = Some(default_error_format.clone());
-                self.configuration.error_format = Some(default_error_format.clone());
-            }
+        // Determine whether or not to enable the WebSocket Controller.
+        self.set_no_websocket();
+
+        // Validate all other options.
+        if let Err(e) = self.configure() {
+            e.collect_into(&mut errors);
         }
 
-        // Otherwise default to SwanlingLogFormat::Json.
-        if !self.configuration.error_log.is_empty()
-            && self.configuration.error_format.is_none()
-            && self.attack_mode != AttackMode::Manager
-        {
-            self.configuration.error_format = value.clone();
+        // Earlier load tests are merged instead of running a load test.
+        if self.merging_reports() {
+            return SwanlingError::from_errors(errors);
         }
 
-        if self.configuration.error_format.is_some() {
-            // Log format isn't relevant if metrics aren't enabled.
-            if self.configuration.no_metrics {
-                return Err(SwanlingError::InvalidOption {
-                    option: "--no-metrics".to_string(),
-                    value: "true".to_string(),
-                    detail: "The --no-metrics flag can not be set together with the --error-format option.".to_string(),
-                });
-            }
-            // Log format isn't relevant if log not enabled.
-            else if self.configuration.error_log.is_empty() {
-                return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: format!("{:?}", value),
-                    detail: "The --error-file option must be set together with the --error-format option.".to_string(),
-                });
+        // Confirm there's either a global host, or each task set has a host defined.
+        let host_configured = match self.validate_host() {
+            Ok(()) => true,
+            Err(e) => {
+                if self.configuration.no_autostart
+                    && !self.configuration.dry_run
+                    && !self.configuration.debug_single_user
+                {
+                    info!("host must be configured via Controller before starting load test");
+                } else {
+                    // If auto-starting, host must be valid.
+                    e.collect_into(&mut errors);
+                }
+                false
             }
+        };
+
+        // Report all problems found together.
+        SwanlingError::from_errors(errors)?;
+
+        if host_configured {
+            info!("global host configured: {}", self.configuration.host);
+            self.prepare_load_test()?;
+        }
+
+        // Calculate a unique hash for the current load test.
+        let mut s = DefaultHasher::new();
+        self.task_sets.hash(&mut s);
+        self.metrics.hash = s.finish();
+        debug!("hash: {}", self.metrics.hash);
+
+        // Display the plan instead of running the load test.
+        if self.configuration.dry_run {
+            self.print_plan();
         }
 
         Ok(())
     }
 
-    // Configure debug log format.
-    fn set_debug_format(&mut self) -> Result<(), SwanlingError> {
-        // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.debug_format";
-        let mut value = Some(SwanlingLogFormat::Json);
-
-        if self.configuration.debug_format.is_some() {
-            key = "--debug-format";
-            value = self.configuration.debug_format.clone();
-        } else if let Some(default_debug_format) = self.defaults.debug_format.as_ref() {
-            // In Gaggles, debug_format is only set on Worker.
-            if self.attack_mode != AttackMode::Manager {
-                key = "set_default(SwanlingDefault::DebugFormat)";
-                value = Some(default_debug_format.clone());
-                self.configuration.debug_format = Some(default_debug_format.clone());
+    // Display how the validated load test would run: the hosts it load tests, how many
+    // users run each task set, the order each user runs tasks in, and all options that
+    // aren't set to their default value.
+    fn print_plan(&self) {
+        println!("Dry run, no requests will be made.");
+
+        println!("\nHosts:");
+        match self.get_hosts() {
+            Ok(hosts) => {
+                let mut printed: Vec<&url::Url> = Vec::new();
+                for host in &hosts {
+                    if printed.contains(&host) {
+                        continue;
+                    }
+                    printed.push(host);
+                    // Resolve each host, the same way users would when connecting.
+                    match host.socket_addrs(|| None) {
+                        Ok(addresses) => println!(
+                            " - {} ({})",
+                            host,
+                            addresses
+                                .iter()
+                                .map(|address| address.ip().to_string())
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        ),
+                        Err(e) => println!(" - {} (failed to resolve: {})", host, e),
+                    }
+                }
             }
+            Err(e) => println!(" - {}", e),
         }
 
-        // Otherwise default to SwanlingLogFormat::Json.
-        if !self.configuration.debug_log.is_empty()
-            && self.configuration.debug_format.is_none()
-            && self.attack_mode != AttackMode::Manager
-        {
-            self.configuration.debug_format = value.clone();
+        // Count how many users are allocated to each task set.
+        let mut users = vec![0; self.task_sets.len()];
+        if self.attack_mode == AttackMode::Manager {
+            for user in &self.weighted_gaggle_users {
+                users[user.task_sets_index] += 1;
+            }
+        } else {
+            for user in &self.weighted_users {
+                users[user.task_sets_index] += 1;
+            }
+        }
+        let task_names = |weighted_tasks: &WeightedSwanlingTasks| {
+            weighted_tasks
+                .iter()
+                .map(|(tasks_index, name)| {
+                    if name.is_empty() {
+                        format!("task {}", tasks_index)
+                    } else {
+                        name.to_string()
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        println!("\nUsers: {}", users.iter().sum::<usize>());
+        for task_set in &self.task_sets {
+            println!(
+                " - {}: {} users",
+                task_set.name, users[task_set.task_sets_index]
+            );
+            if !task_set.weighted_on_start_tasks.is_empty() {
+                println!(
+                    "    on_start: {}",
+                    task_names(&task_set.weighted_on_start_tasks)
+                );
+            }
+            if !task_set.weighted_tasks.is_empty() {
+                println!("    tasks: {}", task_names(&task_set.weighted_tasks));
+            }
+            for task in &task_set.tasks {
+                if let Some(schedule) = task.schedule {
+                    println!("    scheduled: {} (every: {:?})", task.name, schedule);
+                }
+            }
+            if !task_set.weighted_on_stop_tasks.is_empty() {
+                println!(
+                    "    on_stop: {}",
+                    task_names(&task_set.weighted_on_stop_tasks)
+                );
+            }
         }
 
-        if self.configuration.debug_format.is_some() {
-            // Log format isn't relevant if log not enabled.
-            if self.configuration.debug_log.is_empty() {
-                return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: format!("{:?}", value),
-                    detail: "The --debug-file option must be set together with the --debug-format option.".to_string(),
-                });
+        // Compare the effective configuration with the default configuration.
+        println!("\nOptions:");
+        let default_configuration: SwanlingConfiguration =
+            SwanlingConfiguration::parse_args_default::<&str>(&[])
+                .expect("default configuration is valid");
+        if let (Ok(serde_json::Value::Object(options)), Ok(serde_json::Value::Object(defaults))) = (
+            serde_json::to_value(&self.configuration),
+            serde_json::to_value(&default_configuration),
+        ) {
+            for (option, value) in options {
+                if option != "dry_run" && defaults.get(&option) != Some(&value) {
+                    println!(" - {}: {}", option.replace('_', "-"), value);
+                }
             }
         }
-
-        Ok(())
     }
 
-    // Configure whether or not to enable the telnet Controller. Always disable when in Regatta mode.
-    fn set_no_telnet(&mut self) {
-        // Currently Gaggles are not Controller-aware, force disable.
-        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
-            self.configuration.no_telnet = true;
-        // Otherwise, if --no-telnet flag not set, respect default if configured.
-        } else if !self.configuration.no_telnet {
-            if let Some(default_no_telnet) = self.defaults.no_telnet {
-                self.configuration.no_telnet = default_no_telnet;
-            }
-        }
+    // Whether to merge the metrics of earlier load tests instead of running a load test.
+    fn merging_reports(&self) -> bool {
+        self.configuration.merge_reports || !self.configuration.reports.is_empty()
     }
 
-    // Configure whether or not to enable the WebSocket Controller. Always disable when in Regatta mode.
-    fn set_no_websocket(&mut self) {
-        // Currently Gaggles are not Controller-aware, force disable.
-        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
-            self.configuration.no_websocket = true;
-        // Otherwise, if --no-websocket flag not set, respect default if configured.
-        } else if !self.configuration.no_websocket {
-            if let Some(default_no_telnet) = self.defaults.no_telnet {
-                self.configuration.no_websocket = default_no_telnet;
-            }
+    // Run the validated load test on the current runtime.
+    async fn run_execute(mut self) -> Result<SwanlingMetrics, SwanlingError> {
+        // Task sets and tasks have already been listed, don't run the load test.
+        if self.configuration.list {
+            return Ok(self.metrics);
         }
-    }
 
-    // Configure whether or not to autostart the load test.
-    fn set_no_autostart(&mut self) -> Result<(), SwanlingError> {
-        // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.no_autostart";
-        let mut value = false;
+        // Merge the metrics of earlier load tests instead of running a load test.
+        if self.merging_reports() {
+            return self.merge_reports().await;
+        }
 
-        // Currently Gaggles are not Controller-aware.
-        if self.configuration.no_autostart {
-            key = "--no-autostart";
-            value = true;
-        // Otherwise set default if configured.
-        } else if let Some(default_no_autostart) = self.defaults.no_autostart {
-            key = "set_default(SwanlingDefault::NoAutoStart)";
-            value = default_no_autostart;
+        // The plan has already been printed, don't run the load test.
+        if self.configuration.dry_run {
+            return Ok(self.metrics);
+        }
 
-            self.configuration.no_autostart = default_no_autostart;
+        // Run a single user instead of the load test.
+        if self.configuration.debug_single_user {
+            self.debug_single_user().await?;
+            return Ok(self.metrics);
         }
 
-        if self.configuration.no_autostart {
-            // Can't disable autostart in Regatta mode.
-            if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode) {
-                return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: value.to_string(),
-                    detail: format!(
-                        "{} can not be set together with the --manager or --worker flags.",
-                        key
-                    ),
+        // Start swanling in manager mode.
+        if self.attack_mode == AttackMode::Manager {
+            #[cfg(feature = "gaggle")]
+            {
+                self = manager::manager_main(self).await;
+            }
+
+            #[cfg(not(feature = "gaggle"))]
+            {
+                return Err(SwanlingError::FeatureNotEnabled {
+                    feature: "gaggle".to_string(), detail: "Load test must be recompiled with `--features gaggle` to start in manager mode.".to_string()
                 });
             }
+        }
+        // Start swanling in worker mode.
+        else if self.attack_mode == AttackMode::Worker {
+            #[cfg(feature = "gaggle")]
+            {
+                self = worker::worker_main(&self).await;
+            }
 
-            // Can't disable autostart if there's no Controller enabled.
-            if self.configuration.no_telnet && self.configuration.no_websocket {
-                return Err(SwanlingError::InvalidOption {
-                    option: key.to_string(),
-                    value: value.to_string(),
-                    detail: format!("{} can not be set together with both the --no-telnet and --no-websocket flags.", key),
+            #[cfg(not(feature = "gaggle"))]
+            {
+                return Err(SwanlingError::FeatureNotEnabled {
+                    feature: "gaggle".to_string(),
+                    detail: "Load test must be recompiled with `--features gaggle` to start in worker mode.".to_string(),
                 });
             }
         }
+        // Start swanling in single-process mode.
+        else {
+            self = self.start_attack(None).await?;
+        }
 
-        Ok(())
+        Ok(self.metrics)
     }
 
-    // Configure whether to log response body.
-    fn set_no_debug_body(&mut self) -> Result<(), SwanlingError> {
-        // Track how value gets set so we can return a meaningful error if necessary.
-        let mut key = "configuration.no_debug_body";
-        let mut value = false;
-
-        if self.configuration.no_debug_body {
-            key = "--no-debug-body";
-            value = true;
-        // If not otherwise set and not Manager, check if there's a default.
-        } else if self.attack_mode != AttackMode::Manager {
-            // Optionally set default.
-            if let Some(default_no_debug_body) = self.defaults.no_debug_body {
-                key = "set_default(SwanlingDefault::NoDebugBody)";
-                value = default_no_debug_body;
+    // The runtime the load test runs on: either supplied with set_runtime(), or built with
+    // --runtime-threads worker threads.
+    fn runtime(&self) -> Result<SwanlingRuntime, SwanlingError> {
+        if let Some(handle) = self.runtime.clone() {
+            return Ok(SwanlingRuntime::Supplied(handle));
+        }
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        builder.enable_all();
+        if let Some(runtime_threads) = self.configuration.runtime_threads {
+            builder.worker_threads(runtime_threads);
+        }
+        Ok(SwanlingRuntime::Built(builder.build()?))
+    }
 
-                self.configuration.no_debug_body = default_no_debug_body;
-            }
+    // Merge the JSON metrics written by earlier load tests into a single report, instead of
+    // running a load test.
+    async fn merge_reports(mut self) -> Result<SwanlingMetrics, SwanlingError> {
+        if !self.configuration.merge_reports {
+            return Err(SwanlingError::InvalidOption {
+                option: "--merge-reports".to_string(),
+                value: self.configuration.reports.join(" "),
+                detail: "Metrics files can only be listed together with the --merge-reports flag."
+                    .to_string(),
+            });
         }
 
-        if self.configuration.no_debug_body && self.attack_mode == AttackMode::Manager {
+        if self.configuration.reports.is_empty() {
             return Err(SwanlingError::InvalidOption {
-                option: key.to_string(),
-                value: value.to_string(),
-                detail: format!("{} can not be set together with the --manager flag.", key),
+                option: "--merge-reports".to_string(),
+                value: "".to_string(),
+                detail: "--merge-reports requires at least one JSON metrics file.".to_string(),
             });
         }
 
-        Ok(())
-    }
+        if self.attack_mode != AttackMode::StandAlone {
+            return Err(SwanlingError::InvalidOption {
+                option: "--merge-reports".to_string(),
+                value: self.configuration.reports.join(" "),
+                detail:
+                    "--merge-reports can not be set together with the --manager or --worker flags."
+                        .to_string(),
+            });
+        }
 
-    /// Execute the [`SwanlingAttack`](./struct.SwanlingAttack.html) load test.
-    ///
-    /// # Example
-    /// ```rust
-    /// use swanling::prelude::*;
-    ///
-    /// fn main() -> Result<(), SwanlingError> {
-    ///     let _swanling_metrics = SwanlingAttack::initialize()?
-    ///         .register_taskset(taskset!("ExampleTasks")
-    ///             .register_task(task!(example_task).set_weight(2)?)
-    ///             .register_task(task!(another_example_task).set_weight(3)?)
-    ///             // Swanling must run against a host, point to localhost so test starts.
-    ///             .set_host("http://localhost")
-    ///         )
-    ///         // Exit after one second so test doesn't run forever.
-    ///         .set_default(SwanlingDefault::RunTime, 1)?
-    ///         .execute()?;
-    ///
-    ///     Ok(())
-    /// }
-    ///
-    /// async fn example_task(user: &SwanlingUser) -> SwanlingTaskResult {
-    ///     let _swanling = user.get("/foo").await?;
-    ///
-    ///     Ok(())
-    /// }
-    ///
-    /// async fn another_example_task(user: &SwanlingUser) -> SwanlingTaskResult {
-    ///     let _swanling = user.get("/bar").await?;
-    ///
-    ///     Ok(())
-    /// }
-    /// ```
-    pub fn execute(mut self) -> Result<SwanlingMetrics, SwanlingError> {
-        // If version flag is set, display package name and version and exit.
-        if self.configuration.version {
-            println!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
-            std::process::exit(0);
+        let mut metrics = Vec::new();
+        for path in &self.configuration.reports {
+            metrics.push(report::read_metrics(path)?);
         }
 
-        // At least one task set is required.
-        if self.task_sets.is_empty() {
-            return Err(SwanlingError::NoTaskSets {
-                detail: "No task sets are defined.".to_string(),
+        // Confirm all metrics are from the same load test, for example all processes of a
+        // fleet, unless disabled.
+        if !self.configuration.no_hash_check && metrics.iter().any(|m| m.hash != metrics[0].hash) {
+            return Err(SwanlingError::InvalidOption {
+                option: "--merge-reports".to_string(),
+                value: self.configuration.reports.join(" "),
+                detail: "The metrics files are from different load tests, set --no-hash-check to merge them anyway."
+                    .to_string(),
             });
         }
+        self.metrics = report::merge(metrics);
+        info!(
+            "merged metrics from {} load tests",
+            self.configuration.reports.len()
+        );
 
-        // Display task sets and tasks, then exit.
-        if self.configuration.list {
-            println!("Available tasks:");
-            for task_set in self.task_sets {
-                println!(" - {} (weight: {})", task_set.name, task_set.weight);
-                for task in task_set.tasks {
-                    println!("    o {} (weight: {})", task.name, task.weight);
-                }
-            }
-            std::process::exit(0);
+        if self.metrics.started.is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: "--merge-reports".to_string(),
+                value: self.configuration.reports.join(" "),
+                detail: "The metrics files don't contain a load test that started.".to_string(),
+            });
         }
 
-        // Configure loggers.
-        self.configuration.configure_loggers(&self.defaults);
-
-        // Initialize logger.
-        self.initialize_logger();
+        if !self.configuration.no_metrics {
+            self.metrics.display_metrics = true;
+            self.metrics.display_status_codes = self.configuration.status_codes;
+            self.metrics.display_compression =
+                self.configuration.no_gzip || !self.configuration.accept_encoding.is_empty();
+            self.metrics.summary_format = self
+                .configuration
+                .summary_format
+                .clone()
+                .unwrap_or_default();
+            self.metrics.percentiles = self.configuration.get_percentiles().unwrap_or_default();
+            // Configured labels take precedence over labels of the merged load tests.
+            self.metrics.labels.extend(self.configuration.get_labels());
+        }
 
-        // Configure run mode (StandAlone, Worker, Manager).
-        self.set_attack_mode()?;
+        // Write an html report, if enabled.
+        let mut report_file = match self.prepare_report_file().await {
+            Ok(f) => f,
+            Err(e) => {
+                return Err(SwanlingError::InvalidOption {
+                    option: "--report-file".to_string(),
+                    value: self.get_report_file_path().unwrap(),
+                    detail: format!("Failed to create report file: {}", e),
+                })
+            }
+        };
+        self.write_html_report(report_file.as_mut()).await?;
 
-        // Determine whether or not to enable the telnet Controller.
-        self.set_no_telnet();
+        Ok(self.metrics)
+    }
 
-        // Determine whether or not to enable the WebSocket Controller.
-        self.set_no_websocket();
+    // Validate all options that can be changed by a Controller while the load test is idle,
+    // applying custom defaults where options aren't set.
+    fn configure(&mut self) -> Result<(), SwanlingError> {
+        // Validate every option, even after finding a problem, to report all of them together.
+        let mut errors = Vec::new();
+        let mut check = |result: Result<(), SwanlingError>| {
+            if let Err(e) = result {
+                e.collect_into(&mut errors);
+            }
+        };
 
         // Determine whether or not to autostart load test.
-        self.set_no_autostart()?;
+        check(self.set_no_autostart());
+
+        // Configure when to start the load test.
+        check(self.set_start_at());
 
         // Configure number of users to simulate.
-        self.set_users()?;
+        check(self.set_users());
 
         // Configure expect_workers if running in Manager attack mode.
-        self.set_expect_workers()?;
+        check(self.set_expect_workers());
 
         // Configure host and ports if running in a Regatta distributed load test.
-        self.set_gaggle_host_and_port()?;
+        check(self.set_gaggle_host_and_port());
 
         // Configure how long to run.
-        self.set_run_time()?;
+        check(self.set_run_time());
+
+        // Configure how long to wait for users to exit when stopping.
+        check(self.set_shutdown_timeout());
 
         // Configure how many users to hatch per second.
-        self.set_hatch_rate()?;
+        check(self.set_hatch_rate());
+
+        // Configure how many users to hatch at a time.
+        check(self.set_hatch_batch());
+
+        // Configure randomly shifting when each batch of users hatches.
+        check(self.set_hatch_jitter());
 
         // Configure the requests log format.
-        self.set_request_format()?;
+        check(self.set_request_format());
+
+        // Confirm raw request latencies can be recorded, if enabled.
+        check(self.set_record_raw_latencies());
 
         // Configure the tasks log format.
-        self.set_task_format()?;
+        check(self.set_task_format());
 
         // Configure the tasks log format.
-        self.set_error_format()?;
+        check(self.set_error_format());
+
+        // Configure how long to aggregate identical errors in the error log.
+        check(self.set_error_log_window());
 
         // Configure the debug log format.
-        self.set_debug_format()?;
+        check(self.set_debug_format());
 
         // Determine whether or not to log response body.
-        self.set_no_debug_body()?;
+        check(self.set_no_debug_body());
+
+        // Configure how many bytes of response bodies to include in the debug log.
+        check(self.set_debug_body_max_bytes());
+
+        // Configure what to redact from the debug log.
+        check(self.set_debug_redact());
 
         // Configure coordinated ommission mitigation strategy.
-        self.set_coordinated_omission()?;
+        check(self.set_coordinated_omission());
 
         // Configure throttle if enabled.
-        self.set_throttle_requests()?;
+        check(self.set_throttle_requests());
+        check(self.set_throttle_plan());
+
+        // Configure the number of tokio worker threads.
+        check(self.set_runtime_threads());
+
+        // Configure the seed of each user's random number generator.
+        check(self.set_seed());
+
+        // Configure which response encodings are requested.
+        check(self.set_no_gzip());
+        check(self.set_accept_encoding());
+        check(self.set_user_agent_file());
+        check(self.set_inject_latency());
+        check(self.set_inject_abort());
+
+        // Configure how hostnames are resolved.
+        check(self.set_dns_server());
+        check(self.set_no_dns_cache());
+        check(self.set_dns_refresh());
+
+        // Configure checking the hosts are healthy.
+        check(self.set_healthcheck());
 
         // Configure status_codes flag.
-        self.set_status_codes()?;
+        check(self.set_status_codes());
 
         // Configure running_metrics flag.
-        self.set_running_metrics()?;
+        check(self.set_running_metrics());
+
+        // Configure running_metrics_format.
+        check(self.set_running_metrics_format());
+
+        // Configure summary_only flag.
+        check(self.set_summary_only());
+
+        // Configure summary_format.
+        check(self.set_summary_format());
+
+        // Configure the response time percentiles reported.
+        check(self.set_percentiles());
+
+        // Configure the latency histogram buckets in the html report.
+        check(self.set_histogram_buckets());
+
+        // Configure how often to write report snapshots.
+        check(self.set_report_interval());
+
+        // Validate the metadata added to reports.
+        check(self.set_labels());
+
+        // Configure the ID identifying this run, and whether it's sent with requests.
+        check(self.set_run_id());
+        check(self.set_run_header());
+
+        // Configure task_headers flag.
+        check(self.set_task_headers());
+
+        // Configure tui flag.
+        check(self.set_tui());
 
         // Configure no_reset_metrics flag.
-        self.set_no_reset_metrics()?;
+        check(self.set_no_reset_metrics());
 
         // Configure no_task_metrics flag.
-        self.set_no_task_metrics()?;
+        check(self.set_no_task_metrics());
 
         // Configure no_error_summary flag.
-        self.set_no_error_summary()?;
+        check(self.set_no_error_summary());
+
+        // Configure which request tags errors are summarized by.
+        check(self.set_error_tag());
+
+        // Configure the maximum number of unique request names tracked.
+        check(self.set_max_request_names());
+
+        // Configure how often users send aggregated metrics to the parent.
+        check(self.set_metrics_flush());
 
         // Configure no_metrics flag.
-        self.set_no_metrics()?;
+        check(self.set_no_metrics());
+
+        // Configure stopping the load test when too many requests fail.
+        check(self.set_abort_on_error_rate());
+
+        // Configure warning when the latency of a request stays too high.
+        check(self.set_latency_alert());
+
+        // Configure retiring and replacing running users.
+        check(self.set_user_churn());
+
+        // Configure saving and restoring users' cookies.
+        check(self.set_save_sessions());
+
+        // Configure uploading metrics to object storage.
+        check(self.set_upload_url());
+
+        // Configure notifying a webhook of load test events.
+        check(self.set_notify_url());
+
+        // Validate the local addresses requests are sent from.
+        check(self.set_bind_address());
+
+        // Validate values made available to tasks.
+        check(self.set_config_values());
 
         // Configure sticky_follow flag.
-        self.set_sticky_follow()?;
+        check(self.set_sticky_follow());
+
+        // Configure how users are pinned to backends.
+        check(self.set_sticky_cookie());
+        check(self.set_sticky_header());
+        check(self.set_sticky_backends());
+
+        // Configure backend_header.
+        check(self.set_backend_header());
 
         // Configure no_hash_check flag.
         #[cfg(feature = "gaggle")]
-        self.set_no_hash_check()?;
+        check(self.set_no_hash_check());
 
-        // Confirm there's either a global host, or each task set has a host defined.
-        if let Err(e) = self.validate_host() {
-            if self.configuration.no_autostart {
-                info!("host must be configured via Controller before starting load test");
-            } else {
-                // If auto-starting, host must be valid.
-                return Err(e);
-            }
-        } else {
-            info!("global host configured: {}", self.configuration.host);
-            self.prepare_load_test()?;
-        }
+        // Configure hot_add_workers flag.
+        #[cfg(feature = "gaggle")]
+        check(self.set_hot_add_workers());
 
-        // Calculate a unique hash for the current load test.
-        let mut s = DefaultHasher::new();
-        self.task_sets.hash(&mut s);
-        self.metrics.hash = s.finish();
-        debug!("hash: {}", self.metrics.hash);
+        // Configure manager_also_worker flag.
+        #[cfg(feature = "gaggle")]
+        check(self.set_manager_also_worker());
 
-        // Start swanling in manager mode.
-        if self.attack_mode == AttackMode::Manager {
-            #[cfg(feature = "gaggle")]
-            {
-                let rt = Runtime::new().unwrap();
-                self = rt.block_on(manager::manager_main(self));
-            }
+        // Configure this process's share of a fleet.
+        check(self.set_fleet());
+
+        // Configure replay_log and load the requests to replay.
+        check(self.set_replay_log());
 
-            #[cfg(not(feature = "gaggle"))]
-            {
-                return Err(SwanlingError::FeatureNotEnabled {
-                    feature: "gaggle".to_string(), detail: "Load test must be recompiled with `--features gaggle` to start in manager mode.".to_string()
-                });
-            }
-        }
-        // Start swanling in worker mode.
-        else if self.attack_mode == AttackMode::Worker {
-            #[cfg(feature = "gaggle")]
-            {
-                let rt = Runtime::new().unwrap();
-                self = rt.block_on(worker::worker_main(&self));
-            }
+        // Configure how quickly to replay requests.
+        check(self.set_replay_speed());
 
-            #[cfg(not(feature = "gaggle"))]
-            {
-                return Err(SwanlingError::FeatureNotEnabled {
-                    feature: "gaggle".to_string(),
-                    detail: "Load test must be recompiled with `--features gaggle` to start in worker mode.".to_string(),
-                });
-            }
-        }
-        // Start swanling in single-process mode.
-        else {
-            let rt = Runtime::new().unwrap();
-            self = rt.block_on(self.start_attack(None))?;
+        SwanlingError::from_errors(errors)
+    }
+
+    // Change options while the load test is idle. If any option or the new configuration
+    // is invalid, the previous configuration is restored.
+    pub(crate) fn set_options(&mut self, options: &[(&str, &str)]) -> Result<(), SwanlingError> {
+        let previous = self.configuration.clone();
+        let result = options
+            .iter()
+            .try_for_each(|(option, value)| self.configuration.set_option(option, value))
+            .and_then(|_| self.configure());
+        if result.is_err() {
+            self.restore_configuration(previous)?;
         }
+        result
+    }
 
-        Ok(self.metrics)
+    // Restore a previous configuration, after failing to change options.
+    pub(crate) fn restore_configuration(
+        &mut self,
+        configuration: SwanlingConfiguration,
+    ) -> Result<(), SwanlingError> {
+        self.configuration = configuration;
+        self.configure()
     }
 
     // Returns OK(()) if there's a valid host, SwanlingError with details if not.
     fn validate_host(&mut self) -> Result<(), SwanlingError> {
+        if !self.configuration.host.is_empty() && self.configuration.get_hosts().is_none() {
+            return Err(SwanlingError::InvalidOption {
+                option: "--host".to_string(),
+                value: self.configuration.host.clone(),
+                detail: "The --host option must be a host, or a comma-separated list of hosts each optionally followed by =weight (ie http://a,http://b=2).".to_string(),
+            });
+        }
         if self.configuration.host.is_empty() {
             for task_set in &self.task_sets {
+                if let Some(base_url) = &task_set.base_url {
+                    info!("base url for {} configured: {}", task_set.name, base_url);
+                    continue;
+                }
                 match &task_set.host {
                     Some(h) => {
                         if util::is_valid_host(h).is_ok() {
@@ -2813,6 +6948,10 @@ impl SwanlingAttack {
             task_set.weighted_on_start_tasks = weighted_on_start_tasks;
             task_set.weighted_tasks = weighted_tasks;
             task_set.weighted_on_stop_tasks = weighted_on_stop_tasks;
+            // Task sets without their own user agents use --user-agent-file, if set.
+            if task_set.user_agents.is_empty() {
+                task_set.user_agents = self.user_agents.clone();
+            }
             debug!(
                 "weighted {} on_start: {:?} tasks: {:?} on_stop: {:?}",
                 task_set.name,
@@ -2826,11 +6965,35 @@ impl SwanlingAttack {
             // Stand-alone and Manager processes can display metrics.
             if !self.configuration.no_metrics {
                 self.metrics.display_metrics = true;
+                self.metrics.running_metrics_format = self
+                    .configuration
+                    .running_metrics_format
+                    .clone()
+                    .unwrap_or_default();
+                // The dashboard replaces all other output until the load test finishes.
+                self.metrics.summary_only =
+                    self.configuration.summary_only || self.configuration.tui;
+                self.metrics.summary_format = self
+                    .configuration
+                    .summary_format
+                    .clone()
+                    .unwrap_or_default();
+                self.metrics.percentiles = self.configuration.get_percentiles().unwrap_or_default();
+                self.metrics.labels = self.configuration.get_labels();
             }
+            self.metrics.run_id = self.configuration.run_id.clone();
 
             if self.attack_mode == AttackMode::StandAlone {
                 // Allocate a state for each of the users we are about to start.
                 self.weighted_users = self.weight_task_set_users()?;
+
+                // Confirm the operating system allows enough connections for these users.
+                limits::check_limits(self.weighted_users.len())?;
+
+                // Confirm enough users run each task with a rendezvous.
+                if !self.configuration.debug_single_user {
+                    self.validate_rendezvous()?;
+                }
             } else if self.attack_mode == AttackMode::Manager {
                 // Build a list of users to be allocated on Workers.
                 self.weighted_gaggle_users = self.prepare_worker_task_set_users()?;
@@ -2840,13 +7003,169 @@ impl SwanlingAttack {
         Ok(())
     }
 
-    /// Helper to wrap configured host in `Option<>` if set.
-    fn get_configuration_host(&self) -> Option<String> {
-        if self.configuration.host.is_empty() {
-            None
-        } else {
-            Some(self.configuration.host.to_string())
+    // Confirm each task set has a unique name, and each task a unique name across all task
+    // sets. Requests made by a named task are named after it, so tasks with the same name
+    // would silently merge their metrics.
+    fn validate_names(&self) -> Result<(), SwanlingError> {
+        let mut errors = Vec::new();
+        let mut task_set_names = HashSet::new();
+        let mut task_names: HashMap<&String, &String> = HashMap::new();
+        for task_set in &self.task_sets {
+            if !task_set_names.insert(&task_set.name) {
+                errors.push(SwanlingError::InvalidOption {
+                    option: "SwanlingAttack.register_taskset".to_string(),
+                    value: task_set.name.to_string(),
+                    detail: format!(
+                        "More than one task set is named {}, task sets must have unique names.",
+                        task_set.name
+                    ),
+                });
+            }
+
+            for task in &task_set.tasks {
+                if task.name.is_empty() {
+                    continue;
+                }
+                if let Some(other_task_set) = task_names.insert(&task.name, &task_set.name) {
+                    let task_sets = if other_task_set == &task_set.name {
+                        format!("the {} task set", task_set.name)
+                    } else {
+                        format!("the {} and {} task sets", other_task_set, task_set.name)
+                    };
+                    errors.push(SwanlingError::InvalidOption {
+                        option: "SwanlingTask.set_name".to_string(),
+                        value: task.name.to_string(),
+                        detail: format!(
+                            "More than one task in {} is named {}, so their requests would be counted together.",
+                            task_sets, task.name
+                        ),
+                    });
+                }
+            }
+        }
+
+        SwanlingError::from_errors(errors)
+    }
+
+    // Confirm each task only depends on another task registered in the same task set.
+    fn validate_task_dependencies(&self) -> Result<(), SwanlingError> {
+        let mut errors = Vec::new();
+        for task_set in &self.task_sets {
+            for task in &task_set.tasks {
+                if let Some(depends_on) = &task.depends_on {
+                    if !task_set
+                        .tasks
+                        .iter()
+                        .any(|t| &t.name == depends_on && t.tasks_index != task.tasks_index)
+                    {
+                        errors.push(SwanlingError::InvalidOption {
+                            option: "SwanlingTask.set_depends_on".to_string(),
+                            value: depends_on.to_string(),
+                            detail: format!(
+                                "No other task named {} is registered in the {} task set.",
+                                depends_on, task_set.name
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+
+        SwanlingError::from_errors(errors)
+    }
+
+    // Confirm a Regatta coordinates which feeder rows are in use with a backend, as each
+    // Worker only knows which rows its own users checked out.
+    fn validate_feeders(&self) -> Result<(), SwanlingError> {
+        if [AttackMode::Manager, AttackMode::Worker].contains(&self.attack_mode)
+            && self.feeders.without_backend()
+        {
+            return Err(SwanlingError::InvalidOption {
+                option: "SwanlingAttack.register_feeder".to_string(),
+                value: "".to_string(),
+                detail: "SwanlingAttack.set_feeder_backend must be set to use feeders in Regatta mode, otherwise each Worker would hand out every row.".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // Confirm each task set runs at least as many users as its tasks wait for at a rendezvous,
+    // as otherwise they would wait until the load test stops.
+    fn validate_rendezvous(&self) -> Result<(), SwanlingError> {
+        for task_set in &self.task_sets {
+            let users = self
+                .weighted_users
+                .iter()
+                .filter(|user| user.task_sets_index == task_set.task_sets_index)
+                .count();
+            for task in &task_set.tasks {
+                if let Some(rendezvous) = task.rendezvous {
+                    if rendezvous > users {
+                        return Err(SwanlingError::InvalidOption {
+                            option: "SwanlingTask.set_rendezvous".to_string(),
+                            value: rendezvous.to_string(),
+                            detail: format!(
+                                "The {} task set only runs {} users, so fewer than {} users can reach the rendezvous.",
+                                task_set.name, users, rendezvous
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Helper to wrap the configured host the user with the given index load tests in
+    /// `Option<>` if set.
+    fn get_configuration_host(&self, user_index: usize) -> Option<String> {
+        self.configuration.get_host(user_index)
+    }
+
+    // Determine the host users running a task set are configured with. If no other host
+    // is defined, this is the base URL set with `SwanlingTaskSet::set_base_url()`.
+    fn get_task_set_host(
+        &self,
+        task_sets_index: usize,
+        user_index: usize,
+    ) -> Result<url::Url, SwanlingError> {
+        let task_set = &self.task_sets[task_sets_index];
+        let task_set_host = task_set
+            .host
+            .clone()
+            .or_else(|| task_set.base_url.as_ref().map(|url| url.to_string()));
+        swanling::get_base_url(
+            self.get_configuration_host(user_index),
+            task_set_host,
+            self.defaults.host.clone(),
+        )
+    }
+
+    // Determine every host being load tested.
+    fn get_hosts(&self) -> Result<Vec<url::Url>, SwanlingError> {
+        let mut hosts = Vec::new();
+        match self.configuration.get_hosts() {
+            Some(configured) if !configured.is_empty() => {
+                for (host, _) in configured {
+                    hosts.push(swanling::get_base_url(Some(host.to_string()), None, None)?);
+                }
+            }
+            _ => {
+                for task_sets_index in 0..self.task_sets.len() {
+                    hosts.push(self.get_task_set_host(task_sets_index, 0)?);
+                }
+            }
+        }
+        // Task sets and tasks can also be load tested on their own base URL.
+        for task_set in &self.task_sets {
+            hosts.extend(task_set.base_url.clone());
+            for task in &task_set.tasks {
+                hosts.extend(task.base_url.clone());
+            }
         }
+        Ok(hosts)
     }
 
     // Helper to spawn a throttle thread if configured. The throttle thread opens
@@ -2857,20 +7176,42 @@ impl SwanlingAttack {
     ) -> (
         // A channel used by [`SwanlingUser`](./swanling/struct.SwanlingUser.html)s to throttle requests.
         Option<flume::Sender<bool>>,
+        // A channel used by high priority requests to jump the throttle queue.
+        Option<flume::Sender<bool>>,
         // A channel used by parent to tell throttle the load test is complete.
         Option<flume::Sender<bool>>,
     ) {
+        // The steps of the throttle plan, or a single step lasting the whole load test.
+        let throttle_plan = if self.configuration.throttle_requests > 0 {
+            vec![(self.configuration.throttle_requests, 0)]
+        } else if !self.configuration.throttle_plan.is_empty() {
+            // The throttle plan is validated at startup.
+            self.configuration.get_throttle_plan().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
         // If the throttle isn't enabled, return immediately.
-        if self.configuration.throttle_requests == 0 {
-            return (None, None);
+        if throttle_plan.is_empty() {
+            return (None, None, None);
         }
+        // The bucket holds the most requests allowed per second by any step of the plan.
+        let throttle_requests = throttle_plan
+            .iter()
+            .map(|(requests, _)| *requests)
+            .max()
+            .unwrap_or(1);
 
         // Create a bounded channel allowing single-sender multi-receiver to throttle
         // [`SwanlingUser`](./swanling/struct.SwanlingUser.html) threads.
         let (all_threads_throttle, throttle_receiver): (
             flume::Sender<bool>,
             flume::Receiver<bool>,
-        ) = flume::bounded(self.configuration.throttle_requests);
+        ) = flume::bounded(throttle_requests);
+
+        // Create an unbuffered channel, so high priority requests wait until the throttle
+        // thread receives them.
+        let (priority_throttle, priority_receiver) = flume::bounded(0);
 
         // Create a channel allowing the parent to inform the throttle thread when the
         // load test is finished. Even though we only send one message, we can't use a
@@ -2879,8 +7220,9 @@ impl SwanlingAttack {
 
         // Launch a new thread for throttling, no need to rejoin it.
         let _ = Some(tokio::spawn(throttle::throttle_main(
-            self.configuration.throttle_requests,
+            throttle_plan,
             throttle_receiver,
+            priority_receiver,
             throttle_rx,
         )));
 
@@ -2891,20 +7233,37 @@ impl SwanlingAttack {
         // add a token to the bucket before making a request, and are blocked until this
         // throttle thread "leaks out" a token thereby creating space. More information
         // can be found at: https://en.wikipedia.org/wiki/Leaky_bucket
-        for _ in 1..self.configuration.throttle_requests {
+        for _ in 1..throttle_requests {
             let _ = sender.send_async(true).await;
         }
 
-        (Some(all_threads_throttle), Some(parent_to_throttle_tx))
+        (
+            Some(all_threads_throttle),
+            Some(priority_throttle),
+            Some(parent_to_throttle_tx),
+        )
     }
 
     // Helper to optionally spawn a telnet and/or WebSocket Controller thread. The Controller
     // threads share a control channel, allowing it to send requests to the parent process. When
     // a response is required, the Controller will also send a one-shot channel allowing a direct
-    // reply.
-    async fn setup_controllers(&mut self) -> Option<flume::Receiver<SwanlingControllerRequest>> {
-        // If the telnet controller is disabled, return immediately.
-        if self.configuration.no_telnet && self.configuration.no_websocket {
+    // reply. The channel is also created if the dashboard is enabled, which sends requests the
+    // same way.
+    async fn setup_controllers(
+        &mut self,
+    ) -> Option<(
+        flume::Sender<SwanlingControllerRequest>,
+        flume::Receiver<SwanlingControllerRequest>,
+    )> {
+        // A handle returned by execute_with_handle() already sends requests to this channel.
+        let handle_channel = self.handle_channel.take();
+
+        // If all controllers are disabled, return immediately.
+        if self.configuration.no_telnet
+            && self.configuration.no_websocket
+            && !self.configuration.tui
+            && handle_channel.is_none()
+        {
             return None;
         }
 
@@ -2913,7 +7272,7 @@ impl SwanlingAttack {
         let (all_threads_controller_request_tx, controller_request_rx): (
             flume::Sender<SwanlingControllerRequest>,
             flume::Receiver<SwanlingControllerRequest>,
-        ) = flume::unbounded();
+        ) = handle_channel.unwrap_or_else(flume::unbounded);
 
         // Configured telnet Controller if not disabled.
         if !self.configuration.no_telnet {
@@ -2971,13 +7330,13 @@ impl SwanlingAttack {
             // There is no need to rejoin this thread when the load test ends.
             let _ = Some(tokio::spawn(controller::controller_main(
                 self.configuration.clone(),
-                all_threads_controller_request_tx,
+                all_threads_controller_request_tx.clone(),
                 SwanlingControllerProtocol::WebSocket,
             )));
         }
 
-        // Return the parent end of the Controller channel.
-        Some(controller_request_rx)
+        // Return both ends of the Controller channel.
+        Some((all_threads_controller_request_tx, controller_request_rx))
     }
 
     // Prepare an asynchronous file writer for `report_file` (if enabled).
@@ -2989,6 +7348,60 @@ impl SwanlingAttack {
         }
     }
 
+    // Run a single user through one iteration of each task set, printing each response,
+    // instead of running the load test. The test_start and test_stop tasks also run, as the
+    // task sets may rely on them.
+    async fn debug_single_user(&self) -> Result<(), SwanlingError> {
+        self.run_test_start().await?;
+
+        // A single user can't wait for other users to reach a rendezvous.
+        self.coordination.release_rendezvous();
+
+        for task_set in &self.task_sets {
+            let base_url = self.get_task_set_host(task_set.task_sets_index, 0)?;
+            let mut user = SwanlingUser::new(
+                task_set.task_sets_index,
+                base_url,
+                task_set.min_wait,
+                task_set.max_wait,
+                &self.configuration,
+                self.metrics.hash,
+            )?;
+            user.weighted_tasks = task_set.weighted_tasks.clone();
+            user.weighted_on_start_tasks = task_set.weighted_on_start_tasks.clone();
+            user.weighted_on_stop_tasks = task_set.weighted_on_stop_tasks.clone();
+            user.bandwidth = task_set.bandwidth;
+            user.added_latency = task_set.added_latency;
+            user.task_set_base_url = task_set.base_url.clone();
+            user.redirect_policy = task_set.redirect_policy.clone();
+            user.user_agent = task_set.user_agents.first().cloned();
+            user.bind_address = self.configuration.get_bind_address(0);
+            user.hooks = self.hooks.clone();
+            if !self.configuration.sticky_cookie.is_empty()
+                || self.hooks.client_builder.is_some()
+                || user.bind_address.is_some()
+            {
+                *user.client.lock().await = user.build_client()?;
+            }
+            user.shared_data = self.shared_data.snapshot();
+            user.shared_cache = self.shared_cache.clone();
+            user.coordination = self.coordination.clone();
+            user.feeders = self.feeders.clone();
+            #[cfg(feature = "sql")]
+            {
+                user.sql = self.sql.clone();
+            }
+            #[cfg(feature = "queue")]
+            {
+                user.producer = self.producer.clone();
+            }
+
+            user::debug_user_main(task_set, &mut user).await;
+        }
+
+        self.run_test_stop().await
+    }
+
     // Invoke `test_start` tasks if existing.
     async fn run_test_start(&self) -> Result<(), SwanlingError> {
         // Initialize per-user states.
@@ -2999,11 +7412,29 @@ impl SwanlingAttack {
                     info!("running test_start_task");
                     // Create a one-time-use User to run the test_start_task.
                     let base_url = swanling::get_base_url(
-                        self.get_configuration_host(),
+                        self.get_configuration_host(0),
                         None,
                         self.defaults.host.clone(),
                     )?;
-                    let user = SwanlingUser::single(base_url, &self.configuration)?;
+                    let mut user = SwanlingUser::single(base_url, &self.configuration)?;
+                    user.hooks = self.hooks.clone();
+                    user.bind_address = self.configuration.get_bind_address(0);
+                    if self.hooks.client_builder.is_some() || user.bind_address.is_some() {
+                        *user.client.lock().await = user.build_client()?;
+                    }
+                    // Data stored by the test_start_task is shared with all users.
+                    user.shared_data = self.shared_data.clone();
+                    user.shared_cache = self.shared_cache.clone();
+                    user.coordination = self.coordination.clone();
+                    user.feeders = self.feeders.clone();
+                    #[cfg(feature = "sql")]
+                    {
+                        user.sql = self.sql.clone();
+                    }
+                    #[cfg(feature = "queue")]
+                    {
+                        user.producer = self.producer.clone();
+                    }
                     let function = &t.function;
                     let _ = function(&user).await;
                 }
@@ -3025,11 +7456,28 @@ impl SwanlingAttack {
                     info!("running test_stop_task");
                     // Create a one-time-use User to run the test_stop_task.
                     let base_url = swanling::get_base_url(
-                        self.get_configuration_host(),
+                        self.get_configuration_host(0),
                         None,
                         self.defaults.host.clone(),
                     )?;
-                    let user = SwanlingUser::single(base_url, &self.configuration)?;
+                    let mut user = SwanlingUser::single(base_url, &self.configuration)?;
+                    user.hooks = self.hooks.clone();
+                    user.bind_address = self.configuration.get_bind_address(0);
+                    if self.hooks.client_builder.is_some() || user.bind_address.is_some() {
+                        *user.client.lock().await = user.build_client()?;
+                    }
+                    user.shared_data = self.shared_data.snapshot();
+                    user.shared_cache = self.shared_cache.clone();
+                    user.coordination = self.coordination.clone();
+                    user.feeders = self.feeders.clone();
+                    #[cfg(feature = "sql")]
+                    {
+                        user.sql = self.sql.clone();
+                    }
+                    #[cfg(feature = "queue")]
+                    {
+                        user.producer = self.producer.clone();
+                    }
                     let function = &t.function;
                     let _ = function(&user).await;
                 }
@@ -3056,8 +7504,119 @@ impl SwanlingAttack {
             flume::Receiver<SwanlingMetric>,
         ) = flume::unbounded();
 
+        // Thread-safe flag set when the load test is canceled.
+        let canceled = Arc::new(AtomicBool::new(false));
+
         // Optionally spawn a telnet and/or Websocket Controller thread.
-        let controller_channel_rx = self.setup_controllers().await;
+        let controller_channel = self.setup_controllers().await;
+
+        // Optionally display the dashboard, which controls the load test like a Controller.
+        #[cfg(feature = "tui")]
+        let tui = match controller_channel.as_ref() {
+            Some((controller_request_tx, _)) if self.configuration.tui => Some(
+                tui::SwanlingTui::start(controller_request_tx.clone(), canceled.clone())?,
+            ),
+            _ => None,
+        };
+
+        let controller_channel_rx = controller_channel.map(|(_, rx)| rx);
+
+        // Optionally upload metrics and the report to object storage.
+        #[cfg(feature = "object-storage")]
+        let uploader = if self.configuration.upload_url.is_empty() {
+            None
+        } else {
+            match upload::SwanlingUploader::new(
+                &self.configuration.upload_url,
+                self.configuration.upload_interval.unwrap_or(60),
+            ) {
+                Ok(uploader) => Some(uploader),
+                Err(e) => {
+                    return Err(SwanlingError::InvalidOption {
+                        option: "--upload-url".to_string(),
+                        value: self.configuration.upload_url.clone(),
+                        detail: format!("--upload-url {}.", e),
+                    })
+                }
+            }
+        };
+
+        // Monitor the resources used by the load generator, unless metrics are disabled.
+        // Workers don't display metrics, so aren't monitored, and the Manager doesn't sample
+        // its monitor as it doesn't launch users.
+        let monitor = if self.configuration.no_metrics || self.attack_mode == AttackMode::Worker {
+            None
+        } else {
+            Some(monitor::SwanlingMonitor::new())
+        };
+
+        // Optionally check the hosts being load tested are healthy.
+        let healthcheck = if self.configuration.healthcheck.is_empty() {
+            None
+        } else {
+            Some(healthcheck::SwanlingHealthCheck::new(
+                &self.configuration.healthcheck,
+                self.configuration.healthcheck_interval.unwrap_or(10),
+                self.configuration.healthcheck_pause,
+            )?)
+        };
+
+        let circuit_breaker =
+            self.configuration
+                .get_abort_on_error_rate()
+                .map(|(threshold, window)| {
+                    circuit_breaker::SwanlingCircuitBreaker::new(
+                        &self.configuration.abort_on_error_rate,
+                        threshold,
+                        window,
+                    )
+                });
+
+        let latency_alert = self
+            .configuration
+            .get_latency_alert()
+            .map(|(bound, window)| {
+                latency_alert::SwanlingLatencyAlert::new(
+                    &self.configuration.latency_alert,
+                    bound,
+                    window,
+                )
+            });
+
+        // Optionally notify a webhook of load test events.
+        let notifier = if self.configuration.notify_url.is_empty() {
+            None
+        } else {
+            let events = self.subscribe_events();
+            match notify::SwanlingNotifier::start(&self.configuration.notify_url, events) {
+                Ok(notifier) => Some(notifier),
+                Err(e) => {
+                    return Err(SwanlingError::InvalidOption {
+                        option: "--notify-url".to_string(),
+                        value: self.configuration.notify_url.clone(),
+                        detail: format!("--notify-url {}.", e),
+                    })
+                }
+            }
+        };
+
+        // Optionally retire and replace running users.
+        let user_churn = self
+            .configuration
+            .get_user_churn()
+            .map(churn::SwanlingUserChurn::new);
+
+        // Display a progress bar when running for a fixed time, unless only the final metrics
+        // are displayed.
+        let progress_bar = if self.run_time == 0
+            || self.attack_mode == AttackMode::Worker
+            || !self.metrics.display_metrics
+            || self.metrics.summary_only
+        {
+            None
+        } else {
+            progress_bar::SwanlingProgressBar::new()
+        };
 
         // Grab now() once from the standard library, used by multiple timers in
         // the run state.
@@ -3067,32 +7626,56 @@ impl SwanlingAttack {
             spawn_user_timer: std_now,
             spawn_user_in_ms: 0,
             spawn_user_counter: 0,
+            spawn_batch_remaining: 0,
+            spawn_drift: 0,
             drift_timer: tokio::time::Instant::now(),
             all_threads_metrics_tx,
             metrics_rx,
             logger_handle: None,
             all_threads_logger_tx: None,
             throttle_threads_tx: None,
+            throttle_priority_tx: None,
             parent_to_throttle_tx: None,
             controller_channel_rx,
             report_file: None,
             metrics_header_displayed: false,
             idle_status_displayed: false,
+            start_scheduled: false,
             users: Vec::new(),
+            user_tasks: Vec::new(),
             user_channels: Vec::new(),
             running_metrics_timer: std_now,
+            report_snapshot_timer: std_now,
             display_running_metrics: false,
             all_users_spawned: false,
             shutdown_after_stop: !self.configuration.no_autostart,
-            canceled: Arc::new(AtomicBool::new(false)),
+            canceled,
+            forced: coordination::SwanlingFlag::new(),
             socket,
+            #[cfg(feature = "tui")]
+            tui,
+            #[cfg(feature = "object-storage")]
+            uploader,
+            notifier,
+            monitor,
+            healthcheck,
+            circuit_breaker,
+            latency_alert,
+            user_churn,
+            progress_bar,
+            metrics_shards: metrics::SwanlingMetricsShards::new(),
         };
 
         // Access socket to avoid errors.
         trace!("socket: {:?}", &swanling_attack_run_state.socket);
 
         // Catch ctrl-c to allow clean shutdown to display metrics.
-        util::setup_ctrlc_handler(&swanling_attack_run_state.canceled);
+        if self.catch_ctrlc {
+            util::setup_ctrlc_handler(
+                &swanling_attack_run_state.canceled,
+                &swanling_attack_run_state.forced,
+            );
+        }
 
         Ok(swanling_attack_run_state)
     }
@@ -3110,30 +7693,72 @@ impl SwanlingAttack {
             return Ok(());
         }
 
+        // When replaying a request log, launch all replay users at once.
+        if !self.replay_requests.is_empty() {
+            return self.spawn_replay(swanling_attack_run_state).await;
+        }
+
         // Hatch rate is used to schedule the next user, and to ensure we don't
-        // sleep too long.
-        let hatch_rate = util::get_hatch_rate(self.configuration.hatch_rate.clone());
+        // sleep too long. If the hatch rate is a ramp, it changes with the time
+        // since the load test started.
+        let hatch_rate = util::get_hatch_rate_at(
+            self.configuration.hatch_rate.clone(),
+            self.started.unwrap().elapsed().as_secs_f32(),
+        );
 
-        // Determine if it's time to spawn a SwanlingUser.
-        if swanling_attack_run_state.spawn_user_in_ms == 0
+        // Determine if it's time to spawn a SwanlingUser, either because the current
+        // batch isn't finished or because it's time to start the next batch.
+        if swanling_attack_run_state.spawn_batch_remaining > 0
+            || swanling_attack_run_state.spawn_user_in_ms == 0
             || util::ms_timer_expired(
                 swanling_attack_run_state.spawn_user_timer,
                 swanling_attack_run_state.spawn_user_in_ms,
             )
         {
-            // Reset the spawn timer.
-            swanling_attack_run_state.spawn_user_timer = std::time::Instant::now();
+            if swanling_attack_run_state.spawn_batch_remaining == 0 {
+                let hatch_batch = self.configuration.hatch_batch.unwrap_or(1);
+                swanling_attack_run_state.spawn_batch_remaining = hatch_batch;
+
+                // Track how late this batch is, which grows if the load generator can't
+                // keep up with the hatch rate.
+                if swanling_attack_run_state.spawn_user_in_ms > 0 {
+                    let elapsed = swanling_attack_run_state
+                        .spawn_user_timer
+                        .elapsed()
+                        .as_millis() as u64;
+                    swanling_attack_run_state.spawn_drift +=
+                        elapsed.saturating_sub(swanling_attack_run_state.spawn_user_in_ms as u64);
+                }
 
-            // To determine how long before we spawn the next SwanlingUser, start with 1,000.0
-            // milliseconds and divide by the hatch_rate.
-            swanling_attack_run_state.spawn_user_in_ms = (1_000.0 / hatch_rate) as usize;
+                // Reset the spawn timer.
+                swanling_attack_run_state.spawn_user_timer = std::time::Instant::now();
 
-            // If running on a Worker, multiple by the number of workers as each is spawning
-            // SwanlingUsers at this rate.
-            if self.attack_mode == AttackMode::Worker {
-                swanling_attack_run_state.spawn_user_in_ms *=
-                    self.configuration.expect_workers.unwrap() as usize;
+                // To determine how long before we spawn the next batch of SwanlingUsers,
+                // start with 1,000.0 milliseconds per SwanlingUser and divide by the
+                // hatch_rate.
+                swanling_attack_run_state.spawn_user_in_ms =
+                    (hatch_batch as f32 * 1_000.0 / hatch_rate) as usize;
+
+                // If running on a Worker, multiple by the number of workers as each is
+                // spawning SwanlingUsers at this rate.
+                if self.attack_mode == AttackMode::Worker {
+                    swanling_attack_run_state.spawn_user_in_ms *=
+                        self.configuration.expect_workers.unwrap() as usize;
+                }
+                // Likewise each process in a fleet spawns its share of the SwanlingUsers.
+                else if let Some(fleet_size) = self.configuration.fleet_size {
+                    swanling_attack_run_state.spawn_user_in_ms *= fleet_size;
+                }
+
+                // Randomly launch the next batch earlier or later, if enabled.
+                if let Some(jitter) = util::parse_milliseconds(&self.configuration.hatch_jitter) {
+                    let jitter = jitter as i64;
+                    let spawn_user_in_ms = swanling_attack_run_state.spawn_user_in_ms as i64
+                        + thread_rng().gen_range(-jitter..=jitter);
+                    swanling_attack_run_state.spawn_user_in_ms = spawn_user_in_ms.max(0) as usize;
+                }
             }
+            swanling_attack_run_state.spawn_batch_remaining -= 1;
 
             // Spawn next scheduled SwanlingUser.
             let mut thread_user =
@@ -3151,7 +7776,47 @@ impl SwanlingAttack {
                 .weighted_on_stop_tasks
                 .clone();
             // Remember which task group this user is using.
-            thread_user.weighted_users_index = self.metrics.users;
+            thread_user.weighted_users_index =
+                self.configuration.get_fleet_user_index(self.metrics.users);
+            // Send requests from a local address if enabled, assigning addresses round robin.
+            thread_user.bind_address = self
+                .configuration
+                .get_bind_address(thread_user.weighted_users_index);
+            // Pin the user to a backend if enabled, assigning backends round robin.
+            let sticky_backends = self.configuration.get_sticky_backends();
+            if !sticky_backends.is_empty() {
+                thread_user.sticky_backend = Some(
+                    sticky_backends[thread_user.weighted_users_index % sticky_backends.len()]
+                        .to_string(),
+                );
+            }
+            // Users of a task set with a shared client all use the client of the first user
+            // of the task set.
+            let shared_client = if self.task_sets[thread_user.task_sets_index].client_mode
+                == SwanlingClientMode::Shared
+            {
+                self.weighted_users
+                    .iter()
+                    .find(|u| u.task_sets_index == thread_user.task_sets_index)
+                    .filter(|u| !Arc::ptr_eq(&u.client, &thread_user.client))
+            } else {
+                None
+            };
+            // Copy the request and response hooks into the user thread.
+            thread_user.hooks = self.hooks.clone();
+
+            if let Some(first_user) = shared_client {
+                thread_user.client = first_user.client.clone();
+                thread_user.jar = first_user.jar.clone();
+                thread_user.resolver = first_user.resolver.clone();
+            } else if !self.configuration.sticky_cookie.is_empty()
+                || self.hooks.client_builder.is_some()
+                || thread_user.bind_address.is_some()
+            {
+                // Rebuild the client so it sends the sticky cookie with every request from
+                // the bind address, and is customized by the client builder hook.
+                *thread_user.client.lock().await = thread_user.build_client()?;
+            }
 
             // Create a per-thread channel allowing parent thread to control child threads.
             let (parent_sender, thread_receiver): (
@@ -3163,17 +7828,23 @@ impl SwanlingAttack {
             // Clone the logger_tx if enabled, otherwise is None.
             thread_user.logger = swanling_attack_run_state.all_threads_logger_tx.clone();
 
+            // Copy the data shared by the test_start task into the user thread.
+            thread_user.shared_data = self.shared_data.snapshot();
+            thread_user.shared_cache = self.shared_cache.clone();
+            thread_user.coordination = self.coordination.clone();
+            thread_user.feeders = self.feeders.clone();
+            #[cfg(feature = "sql")]
+            {
+                thread_user.sql = self.sql.clone();
+            }
+            #[cfg(feature = "queue")]
+            {
+                thread_user.producer = self.producer.clone();
+            }
+
             // Copy the SwanlingUser-throttle receiver channel, used by all threads.
-            thread_user.throttle = if self.configuration.throttle_requests > 0 {
-                Some(
-                    swanling_attack_run_state
-                        .throttle_threads_tx
-                        .clone()
-                        .unwrap(),
-                )
-            } else {
-                None
-            };
+            thread_user.throttle = swanling_attack_run_state.throttle_threads_tx.clone();
+            thread_user.throttle_priority = swanling_attack_run_state.throttle_priority_tx.clone();
 
             // Copy the SwanlingUser-to-parent sender channel, used by all threads.
             thread_user.channel_to_parent =
@@ -3193,15 +7864,43 @@ impl SwanlingAttack {
                 thread_user.config = self.configuration.clone();
             }
 
-            // Launch a new user.
-            let user = tokio::spawn(user::user_main(
+            // Track which tasks the user is running, to report it if it doesn't exit.
+            thread_user.running_tasks = SwanlingRunningTasks::default();
+            swanling_attack_run_state.user_tasks.push((
                 thread_number,
-                thread_task_set,
-                thread_user,
-                thread_receiver,
-                is_worker,
+                thread_task_set.name.clone(),
+                thread_user.running_tasks.clone(),
+                thread_user.metrics_batch.clone(),
             ));
 
+            // Launch a new user, telling subscribers when it starts and stops.
+            let task_set = thread_task_set.name.clone();
+            send_event(
+                &self.events,
+                SwanlingEvent::UserStarted {
+                    user: thread_number,
+                    task_set: task_set.clone(),
+                },
+            );
+            let events = self.events.clone();
+            let user = tokio::spawn(async move {
+                user::user_main(
+                    thread_number,
+                    thread_task_set,
+                    thread_user,
+                    thread_receiver,
+                    is_worker,
+                )
+                .await;
+                send_event(
+                    &events,
+                    SwanlingEvent::UserStopped {
+                        user: thread_number,
+                        task_set,
+                    },
+                );
+            });
+
             swanling_attack_run_state.users.push(user);
             self.metrics.users += 1;
 
@@ -3213,6 +7912,9 @@ impl SwanlingAttack {
                     )
                 {
                     swanling_attack_run_state.running_metrics_timer = time::Instant::now();
+                    if let Some(progress_bar) = swanling_attack_run_state.progress_bar.as_mut() {
+                        progress_bar.clear();
+                    }
                     self.metrics.print_running();
                 }
             }
@@ -3261,14 +7963,120 @@ impl SwanlingAttack {
         Ok(())
     }
 
+    // Launch one thread for each [`SwanlingUser`](./swanling/struct.SwanlingUser.html) found
+    // in the replayed request log, each replaying the requests originally made by that user.
+    async fn spawn_replay(
+        &mut self,
+        swanling_attack_run_state: &mut SwanlingAttackRunState,
+    ) -> Result<(), SwanlingError> {
+        let replay_speed = self.replay_speed;
+        // Requests are sorted, so the first request determines when the replay starts.
+        let first_elapsed = self.replay_requests[0].elapsed;
+        let started = time::Instant::now();
+
+        for (_, requests) in replay::requests_per_user(&self.replay_requests) {
+            let base_url = self.get_task_set_host(0, self.metrics.users)?;
+            let mut thread_user =
+                SwanlingUser::new(0, base_url, 0, 0, &self.configuration, self.metrics.hash)?;
+            thread_user.weighted_users_index = self.metrics.users;
+
+            // Create a per-thread channel allowing parent thread to control child threads.
+            let (parent_sender, thread_receiver): (
+                flume::Sender<SwanlingUserCommand>,
+                flume::Receiver<SwanlingUserCommand>,
+            ) = flume::unbounded();
+            swanling_attack_run_state.user_channels.push(parent_sender);
+
+            // Clone the logger_tx if enabled, otherwise is None.
+            thread_user.logger = swanling_attack_run_state.all_threads_logger_tx.clone();
+
+            // Copy the request and response hooks into the user thread.
+            thread_user.hooks = self.hooks.clone();
+            thread_user.bind_address = self
+                .configuration
+                .get_bind_address(thread_user.weighted_users_index);
+            if self.hooks.client_builder.is_some() || thread_user.bind_address.is_some() {
+                *thread_user.client.lock().await = thread_user.build_client()?;
+            }
+
+            // Copy the SwanlingUser-throttle receiver channel, used by all threads.
+            thread_user.throttle = swanling_attack_run_state.throttle_threads_tx.clone();
+            thread_user.throttle_priority = swanling_attack_run_state.throttle_priority_tx.clone();
+
+            // Copy the SwanlingUser-to-parent sender channel, used by all threads.
+            thread_user.channel_to_parent =
+                Some(swanling_attack_run_state.all_threads_metrics_tx.clone());
+
+            // We number threads from 1 as they're human-visible (in the logs),
+            // whereas metrics.users starts at 0.
+            let thread_number = self.metrics.users + 1;
+
+            // Replay users don't run tasks.
+            swanling_attack_run_state.user_tasks.push((
+                thread_number,
+                "replay".to_string(),
+                thread_user.running_tasks.clone(),
+                thread_user.metrics_batch.clone(),
+            ));
+
+            send_event(
+                &self.events,
+                SwanlingEvent::UserStarted {
+                    user: thread_number,
+                    task_set: "replay".to_string(),
+                },
+            );
+            let events = self.events.clone();
+            let user = tokio::spawn(async move {
+                replay::replay_main(
+                    thread_number,
+                    thread_user,
+                    requests,
+                    first_elapsed,
+                    replay_speed,
+                    started,
+                    thread_receiver,
+                )
+                .await;
+                send_event(
+                    &events,
+                    SwanlingEvent::UserStopped {
+                        user: thread_number,
+                        task_set: "replay".to_string(),
+                    },
+                );
+            });
+
+            swanling_attack_run_state.users.push(user);
+            self.metrics.users += 1;
+        }
+
+        info!("launched {} replay users...", self.metrics.users);
+
+        // All replay users are launched at once, so there are no metrics to reset.
+        swanling_attack_run_state.all_users_spawned = true;
+        self.set_attack_phase(swanling_attack_run_state, AttackPhase::Running);
+
+        Ok(())
+    }
+
     // Let the [`SwanlingAttack`](./struct.SwanlingAttack.html) run until the timer expires
     // (or the test is canceled), and then trigger a shut down.
     async fn monitor_attack(
         &mut self,
         swanling_attack_run_state: &mut SwanlingAttackRunState,
     ) -> Result<(), SwanlingError> {
-        // Exit if run_time timer expires.
-        if util::timer_expired(self.started.unwrap(), self.run_time) {
+        // Exit if run_time timer expires, time spent paused doesn't count.
+        if self.paused.is_none() && util::timer_expired(self.started.unwrap(), self.run_time) {
+            self.set_attack_phase(swanling_attack_run_state, AttackPhase::Stopping);
+        // Exit when all requests in the replayed request log have been made.
+        } else if !self.replay_requests.is_empty()
+            && swanling_attack_run_state
+                .users
+                .iter()
+                .all(|user| user.is_finished())
+        {
+            info!("finished replaying request log...");
             self.set_attack_phase(swanling_attack_run_state, AttackPhase::Stopping);
         } else {
             // Subtract the time spent doing other things, running the main parent loop twice
@@ -3283,6 +8091,103 @@ impl SwanlingAttack {
         Ok(())
     }
 
+    // Tell all users to stop starting new task iterations until the load test is resumed.
+    fn pause_attack(&mut self, swanling_attack_run_state: &mut SwanlingAttackRunState) {
+        self.update_duration();
+        info!("pausing after {} seconds...", self.metrics.duration);
+        self.paused = Some(time::Instant::now());
+        self.send_to_users(swanling_attack_run_state, SwanlingUserCommand::Wait);
+        self.metrics
+            .annotations
+            .push(SwanlingAnnotation::new(self.metrics.started, "paused"));
+        send_event(&self.events, SwanlingEvent::Paused);
+    }
+
+    // Tell all paused users to resume, excluding the time paused from the duration of the
+    // load test.
+    fn resume_attack(&mut self, swanling_attack_run_state: &mut SwanlingAttackRunState) {
+        if let (Some(started), Some(paused)) = (self.started, self.paused.take()) {
+            info!(
+                "resuming after {} seconds paused...",
+                paused.elapsed().as_secs()
+            );
+            self.started = Some(started + paused.elapsed());
+        }
+        self.send_to_users(swanling_attack_run_state, SwanlingUserCommand::Run);
+        self.metrics
+            .annotations
+            .push(SwanlingAnnotation::new(self.metrics.started, "resumed"));
+        send_event(&self.events, SwanlingEvent::Resumed);
+    }
+
+    // Confirm every host being load tested passes the health check, if enabled.
+    async fn check_health(
+        &self,
+        swanling_attack_run_state: &mut SwanlingAttackRunState,
+    ) -> Result<(), SwanlingError> {
+        if let Some(healthcheck) = swanling_attack_run_state.healthcheck.as_mut() {
+            healthcheck.preflight(self.get_hosts()?).await?;
+        }
+        Ok(())
+    }
+
+    // Pause the load test while every host fails the health check, and resume it when a
+    // host passes again, if enabled.
+    fn pause_while_unhealthy(
+        &mut self,
+        swanling_attack_run_state: &mut SwanlingAttackRunState,
+        unhealthy: bool,
+    ) {
+        let paused = match swanling_attack_run_state.healthcheck.as_ref() {
+            Some(healthcheck) if healthcheck.pause => healthcheck.paused,
+            _ => return,
+        };
+        if self.attack_phase != AttackPhase::Running {
+            return;
+        }
+
+        if unhealthy && self.paused.is_none() {
+            warn!("all hosts failed health check");
+            self.pause_attack(swanling_attack_run_state);
+        } else if !unhealthy && paused && self.paused.is_some() {
+            info!("a host passed health check");
+            self.resume_attack(swanling_attack_run_state);
+        } else {
+            return;
+        }
+        if let Some(healthcheck) = swanling_attack_run_state.healthcheck.as_mut() {
+            healthcheck.paused = unhealthy;
+        }
+    }
+
+    // Retire randomly selected running users, which restart as fresh users.
+    fn retire_users(&self, swanling_attack_run_state: &SwanlingAttackRunState, count: usize) {
+        let user_channels = &swanling_attack_run_state.user_channels;
+        for index in rand::seq::index::sample(
+            &mut thread_rng(),
+            user_channels.len(),
+            count.min(user_channels.len()),
+        ) {
+            match user_channels[index].send(SwanlingUserCommand::Retire) {
+                Ok(_) => debug!("retiring user {}", index),
+                Err(e) => info!("failed to retire user {}: {}", index, e),
+            }
+        }
+    }
+
+    // Send a command to all running users.
+    fn send_to_users(
+        &self,
+        swanling_attack_run_state: &SwanlingAttackRunState,
+        command: SwanlingUserCommand,
+    ) {
+        for (index, send_to_user) in swanling_attack_run_state.user_channels.iter().enumerate() {
+            if let Err(e) = send_to_user.send(command.clone()) {
+                info!("failed to send {:?} to user {}: {}", command, index, e);
+            }
+        }
+    }
+
     async fn stop_running_users(
         &mut self,
         swanling_attack_run_state: &mut SwanlingAttackRunState,
@@ -3328,8 +8233,80 @@ impl SwanlingAttack {
         // Take the users vector out of the SwanlingAttackRunState object so it can be
         // consumed by futures::future::join_all().
         let users = std::mem::take(&mut swanling_attack_run_state.users);
-        futures::future::join_all(users).await;
-        debug!("all users exited");
+        let user_tasks = std::mem::take(&mut swanling_attack_run_state.user_tasks);
+        let abort_handles: Vec<tokio::task::AbortHandle> =
+            users.iter().map(|user| user.abort_handle()).collect();
+        let shutdown_timeout = util::parse_timespan(&self.configuration.shutdown_timeout);
+        let timeout = async {
+            if shutdown_timeout > 0 {
+                tokio::time::sleep(tokio::time::Duration::from_secs(shutdown_timeout as u64)).await
+            } else {
+                futures::future::pending().await
+            }
+        };
+        // Stop waiting if the shutdown timeout expires or ctrl-c is caught again, finalizing
+        // the metrics collected so far.
+        let stuck = tokio::select! {
+            _ = futures::future::join_all(users) => None,
+            _ = timeout => Some(format!(
+                "users didn't exit within --shutdown-timeout {}",
+                self.configuration.shutdown_timeout
+            )),
+            _ = swanling_attack_run_state.forced.wait() => {
+                Some("ctrl-c was caught again before all users exited".to_string())
+            }
+        };
+        match stuck {
+            None => debug!("all users exited"),
+            Some(detail) => {
+                // Abort the users that didn't exit, logging which tasks they're stuck in.
+                for (abort_handle, (user, task_set, running_tasks, metrics_batch)) in
+                    abort_handles.iter().zip(user_tasks.iter())
+                {
+                    if abort_handle.is_finished() {
+                        continue;
+                    }
+                    // A user that panicked poisons the lock, so its tasks can't be listed.
+                    let running_tasks = match running_tasks.lock() {
+                        Ok(running_tasks) => running_tasks.join(", "),
+                        Err(_) => String::new(),
+                    };
+                    if running_tasks.is_empty() {
+                        warn!("aborting user {} from {}", user, task_set);
+                    } else {
+                        warn!(
+                            "aborting user {} from {}, stuck running {}",
+                            user, task_set, running_tasks
+                        );
+                    }
+                    abort_handle.abort();
+
+                    // Collect the metrics the user aggregated but didn't send before it was
+                    // aborted, they're received with the final metrics.
+                    let batch = match metrics_batch.lock() {
+                        Ok(mut metrics_batch) => metrics_batch.take(true),
+                        Err(_) => None,
+                    };
+                    if let Some(batch) = batch {
+                        let _ = swanling_attack_run_state.all_threads_metrics_tx.send(batch);
+                    }
+                }
+                let detail = format!("{}, metrics are partial", detail);
+                warn!("{}", detail);
+                if !self.configuration.no_metrics {
+                    self.metrics.aborted = Some(detail);
+                }
+            }
+        }
+
+        // If enabled, save the cookies of all users so they can be restored when the load
+        // test starts again.
+        if !self.configuration.save_sessions.is_empty() {
+            if let Err(e) = sessions::save(&self.configuration.save_sessions, &self.weighted_users)
+            {
+                warn!("failed to save sessions: {}", e);
+            }
+        }
 
         // If the logger thread is enabled, tell it to flush and exit.
         if swanling_attack_run_state.logger_handle.is_some() {
@@ -3362,11 +8339,7 @@ impl SwanlingAttack {
             if self.attack_mode == AttackMode::Worker {
                 worker::push_metrics_to_manager(
                     &swanling_attack_run_state.socket.clone().unwrap(),
-                    vec![
-                        GaggleMetrics::Requests(self.metrics.requests.clone()),
-                        GaggleMetrics::Errors(self.metrics.errors.clone()),
-                        GaggleMetrics::Tasks(self.metrics.tasks.clone()),
-                    ],
+                    worker::changed_gaggle_metrics(&self.metrics, true),
                     true,
                 );
                 // No need to reset local metrics, the worker is exiting.
@@ -3381,6 +8354,12 @@ impl SwanlingAttack {
         // Run any configured test_stop() functions.
         self.run_test_stop().await?;
 
+        // Include queries run by the test_start and test_stop tasks and all users.
+        #[cfg(feature = "sql")]
+        if let Some(pool) = &self.sql {
+            self.metrics.queries = pool.metrics();
+        }
+
         // Percentile and errors are only displayed when the load test is finished.
         self.metrics.final_metrics = true;
 
@@ -3393,6 +8372,12 @@ impl SwanlingAttack {
         &mut self,
         swanling_attack_run_state: &mut SwanlingAttackRunState,
     ) -> Result<(), SwanlingError> {
+        // Forget queries run by a previous load test.
+        #[cfg(feature = "sql")]
+        if let Some(pool) = &self.sql {
+            pool.reset_metrics();
+        }
+
         // Run any configured test_start() functions.
         self.run_test_start().await.unwrap();
 
@@ -3404,22 +8389,60 @@ impl SwanlingAttack {
             self.metrics.display_metrics = true;
             // Only display status codes if enabled.
             self.metrics.display_status_codes = self.configuration.status_codes;
+            self.metrics.display_compression =
+                self.configuration.no_gzip || !self.configuration.accept_encoding.is_empty();
+            self.metrics.running_metrics_format = self
+                .configuration
+                .running_metrics_format
+                .clone()
+                .unwrap_or_default();
+            self.metrics.summary_only = self.configuration.summary_only || self.configuration.tui;
+            self.metrics.summary_format = self
+                .configuration
+                .summary_format
+                .clone()
+                .unwrap_or_default();
+            self.metrics.percentiles = self.configuration.get_percentiles().unwrap_or_default();
+            self.metrics.labels = self.configuration.get_labels();
         }
+        self.metrics.run_id = self.configuration.run_id.clone();
 
         // Reset the run state.
         let std_now = std::time::Instant::now();
         swanling_attack_run_state.spawn_user_timer = std_now;
         swanling_attack_run_state.spawn_user_in_ms = 0;
         swanling_attack_run_state.spawn_user_counter = 0;
+        swanling_attack_run_state.spawn_batch_remaining = 0;
+        swanling_attack_run_state.spawn_drift = 0;
         swanling_attack_run_state.drift_timer = tokio::time::Instant::now();
         swanling_attack_run_state.metrics_header_displayed = false;
         swanling_attack_run_state.idle_status_displayed = false;
         swanling_attack_run_state.users = Vec::new();
+        swanling_attack_run_state.user_tasks = Vec::new();
         swanling_attack_run_state.user_channels = Vec::new();
         swanling_attack_run_state.running_metrics_timer = std_now;
+        swanling_attack_run_state.report_snapshot_timer = std_now;
         swanling_attack_run_state.display_running_metrics = false;
         swanling_attack_run_state.shutdown_after_stop = !self.configuration.no_autostart;
         swanling_attack_run_state.all_users_spawned = false;
+        #[cfg(feature = "object-storage")]
+        if let Some(uploader) = swanling_attack_run_state.uploader.as_mut() {
+            uploader.reset_timer();
+        }
+        if let Some(monitor) = swanling_attack_run_state.monitor.as_mut() {
+            monitor.reset_timer();
+        }
+        if let Some(circuit_breaker) = swanling_attack_run_state.circuit_breaker.as_mut() {
+            circuit_breaker.reset();
+        }
+        if let Some(latency_alert) = swanling_attack_run_state.latency_alert.as_mut() {
+            latency_alert.reset();
+        }
+
+        // If enabled, restore the cookies users saved when the load test last stopped.
+        if !self.configuration.save_sessions.is_empty() {
+            sessions::load(&self.configuration.save_sessions, &self.weighted_users)?;
+        }
 
         // If enabled, spawn a logger thread.
         let (logger_handle, all_threads_logger_tx) =
@@ -3428,8 +8451,10 @@ impl SwanlingAttack {
         swanling_attack_run_state.all_threads_logger_tx = all_threads_logger_tx;
 
         // If enabled, spawn a throttle thread.
-        let (throttle_threads_tx, parent_to_throttle_tx) = self.setup_throttle().await;
+        let (throttle_threads_tx, throttle_priority_tx, parent_to_throttle_tx) =
+            self.setup_throttle().await;
         swanling_attack_run_state.throttle_threads_tx = throttle_threads_tx;
+        swanling_attack_run_state.throttle_priority_tx = throttle_priority_tx;
         swanling_attack_run_state.parent_to_throttle_tx = parent_to_throttle_tx;
 
         // If enabled, create an report file and confirm access.
@@ -3446,6 +8471,7 @@ impl SwanlingAttack {
 
         // Record when the SwanlingAttack officially started.
         self.started = Some(time::Instant::now());
+        self.paused = None;
 
         // Also record a formattable timestamp, for human readable reports.
         self.metrics.started = Some(Local::now());
@@ -3474,7 +8500,46 @@ impl SwanlingAttack {
                 // In the Idle phase the Swanling configuration can be changed by a Controller,
                 // and otherwise nothing happens but sleeping an checking for messages.
                 AttackPhase::Idle => {
-                    if self.configuration.no_autostart {
+                    // Wait until the time the load test was scheduled to start, if any.
+                    let start_at = self
+                        .configuration
+                        .get_start_at()
+                        .filter(|_| {
+                            !self.configuration.no_autostart
+                                || swanling_attack_run_state.start_scheduled
+                        })
+                        .filter(|start_at| *start_at > Utc::now());
+                    if let Some(start_at) = start_at {
+                        // Only display informational message about waiting one time.
+                        if !swanling_attack_run_state.idle_status_displayed {
+                            info!("waiting until {} to start load test", start_at);
+                            swanling_attack_run_state.idle_status_displayed = true;
+                        }
+                        // Sleep then check again, and for further instructions.
+                        let sleep_duration = (start_at - Utc::now())
+                            .to_std()
+                            .unwrap_or_default()
+                            .min(tokio::time::Duration::from_millis(250));
+                        debug!("sleeping {:?}...", sleep_duration);
+                        swanling_attack_run_state.drift_timer = util::sleep_minus_drift(
+                            sleep_duration,
+                            swanling_attack_run_state.drift_timer,
+                        )
+                        .await;
+                    } else if swanling_attack_run_state.start_scheduled {
+                        // A Controller scheduled the load test to start, remain idle if any
+                        // host is unhealthy.
+                        swanling_attack_run_state.start_scheduled = false;
+                        if let Err(e) = self.check_health(&mut swanling_attack_run_state).await {
+                            warn!("failed to start scheduled load test: {:?}", e);
+                        } else {
+                            self.reset_run_state(&mut swanling_attack_run_state).await?;
+                            self.set_attack_phase(
+                                &mut swanling_attack_run_state,
+                                AttackPhase::Starting,
+                            );
+                        }
+                    } else if self.configuration.no_autostart {
                         // Sleep then check for further instructions.
                         if swanling_attack_run_state.idle_status_displayed {
                             let sleep_duration = tokio::time::Duration::from_millis(250);
@@ -3490,6 +8555,8 @@ impl SwanlingAttack {
                             swanling_attack_run_state.idle_status_displayed = true;
                         }
                     } else {
+                        // Don't start the load test if any host is unhealthy.
+                        self.check_health(&mut swanling_attack_run_state).await?;
                         // Prepare to start the load test, resetting timers and counters.
                         self.reset_run_state(&mut swanling_attack_run_state).await?;
                         self.set_attack_phase(
@@ -3508,11 +8575,24 @@ impl SwanlingAttack {
                 // In the Running phase, Swanling maintains the configured SwanlingAttack.
                 AttackPhase::Running => {
                     self.update_duration();
-                    self.monitor_attack(&mut swanling_attack_run_state).await?;
+                    // A Worker launches any users the Manager assigned it while running.
+                    if self.metrics.users < self.weighted_users.len()
+                        && self.replay_requests.is_empty()
+                    {
+                        self.spawn_attack(&mut swanling_attack_run_state)
+                            .await
+                            .expect("failed to start SwanlingAttack");
+                    } else {
+                        self.monitor_attack(&mut swanling_attack_run_state).await?;
+                    }
                 }
                 // In the Stopping phase, Swanling stops all SwanlingUser threads and optionally reports
                 // any collected metrics.
                 AttackPhase::Stopping => {
+                    // Clear the progress bar before anything else is displayed.
+                    if let Some(progress_bar) = swanling_attack_run_state.progress_bar.as_mut() {
+                        progress_bar.clear();
+                    }
                     // If displaying metrics, update internal state reflecting how long load test
                     // has been running.
                     self.update_duration();
@@ -3525,8 +8605,27 @@ impl SwanlingAttack {
                     self.sync_metrics(&mut swanling_attack_run_state, true)
                         .await?;
                     // Write an html report, if enabled.
-                    self.write_html_report(&mut swanling_attack_run_state)
+                    self.write_html_report(swanling_attack_run_state.report_file.as_mut())
                         .await?;
+                    // Upload the final metrics and html report, if enabled and started.
+                    #[cfg(feature = "object-storage")]
+                    if let Some(uploader) = swanling_attack_run_state
+                        .uploader
+                        .as_mut()
+                        .filter(|_| self.metrics.started.is_some())
+                    {
+                        uploader
+                            .upload_final(&self.metrics, self.build_html_report())
+                            .await;
+                    }
+                    // Notify the webhook the load test completed, if enabled and started.
+                    if let Some(notifier) = swanling_attack_run_state
+                        .notifier
+                        .as_ref()
+                        .filter(|_| self.metrics.started.is_some())
+                    {
+                        notifier.completed(&self.metrics).await;
+                    }
                     // Shutdown Swanling or go into an idle waiting state.
                     if swanling_attack_run_state.shutdown_after_stop {
                         self.set_attack_phase(
@@ -3535,7 +8634,7 @@ impl SwanlingAttack {
                         );
                     } else {
                         // Print metrics, if enabled.
-                        if !self.configuration.no_metrics {
+                        if !self.configuration.no_metrics && !self.metrics.summary_only {
                             println!("{}", self.metrics);
                         }
                         self.set_attack_phase(&mut swanling_attack_run_state, AttackPhase::Idle);
@@ -3548,6 +8647,106 @@ impl SwanlingAttack {
             self.sync_metrics(&mut swanling_attack_run_state, false)
                 .await?;
 
+            // Regularly sample the resources used by the load generator and check whether
+            // it's saturated, if enabled.
+            if [AttackPhase::Starting, AttackPhase::Running].contains(&self.attack_phase) {
+                let throttle_full = matches!(
+                    &swanling_attack_run_state.throttle_threads_tx,
+                    Some(throttle) if throttle.is_full()
+                );
+                if let Some(monitor) = swanling_attack_run_state.monitor.as_mut() {
+                    monitor.sample(
+                        &mut self.metrics,
+                        throttle_full,
+                        swanling_attack_run_state.spawn_drift,
+                    );
+                }
+            }
+
+            // Regularly check the hosts are healthy, if enabled.
+            if [AttackPhase::Starting, AttackPhase::Running].contains(&self.attack_phase) {
+                let unhealthy = swanling_attack_run_state
+                    .healthcheck
+                    .as_mut()
+                    .and_then(|healthcheck| healthcheck.poll(&mut self.metrics));
+                if let Some(unhealthy) = unhealthy {
+                    self.pause_while_unhealthy(&mut swanling_attack_run_state, unhealthy);
+                }
+            }
+
+            // Stop the load test if too many requests are failing, if enabled.
+            if [AttackPhase::Starting, AttackPhase::Running].contains(&self.attack_phase) {
+                if let Some(circuit_breaker) = swanling_attack_run_state.circuit_breaker.as_mut() {
+                    if let Some(detail) = circuit_breaker.check(&self.metrics) {
+                        let threshold = circuit_breaker.threshold();
+                        warn!("aborting load test: {}", detail);
+                        self.metrics.aborted = Some(detail.clone());
+                        send_event(
+                            &self.events,
+                            SwanlingEvent::ThresholdBreached { threshold, detail },
+                        );
+                        self.set_attack_phase(
+                            &mut swanling_attack_run_state,
+                            AttackPhase::Stopping,
+                        );
+                    }
+                }
+            }
+
+            // Warn when the latency of a request stays too high, if enabled.
+            if [AttackPhase::Starting, AttackPhase::Running].contains(&self.attack_phase) {
+                if let Some(latency_alert) = swanling_attack_run_state.latency_alert.as_mut() {
+                    for (request, detail) in latency_alert.check(&mut self.metrics) {
+                        send_event(
+                            &self.events,
+                            SwanlingEvent::LatencyAlert {
+                                request,
+                                threshold: latency_alert.threshold(),
+                                detail,
+                            },
+                        );
+                    }
+                }
+            }
+
+            // Regularly redraw the progress bar, if enabled.
+            if [AttackPhase::Starting, AttackPhase::Running].contains(&self.attack_phase) {
+                let progress = self.progress();
+                if let Some(progress_bar) = swanling_attack_run_state.progress_bar.as_mut() {
+                    progress_bar.update(&progress, &self.metrics);
+                }
+            }
+
+            // Regularly retire and replace running users, if enabled.
+            if self.attack_phase == AttackPhase::Running {
+                let running = self.paused.is_none();
+                if let Some(user_churn) = swanling_attack_run_state.user_churn.as_mut() {
+                    let retiring = user_churn.check(running);
+                    if retiring > 0 {
+                        self.retire_users(&swanling_attack_run_state, retiring);
+                    }
+                }
+            }
+
+            // Regularly write report snapshots to disk, if enabled.
+            if [AttackPhase::Starting, AttackPhase::Running].contains(&self.attack_phase)
+                && util::timer_expired(
+                    swanling_attack_run_state.report_snapshot_timer,
+                    util::parse_timespan(&self.configuration.report_interval),
+                )
+            {
+                swanling_attack_run_state.report_snapshot_timer = time::Instant::now();
+                self.write_report_snapshot().await;
+            }
+
+            // Regularly upload metrics snapshots, if enabled.
+            #[cfg(feature = "object-storage")]
+            if [AttackPhase::Starting, AttackPhase::Running].contains(&self.attack_phase) {
+                if let Some(uploader) = swanling_attack_run_state.uploader.as_mut() {
+                    uploader.upload_snapshot(&self.metrics);
+                }
+            }
+
             // Check if a Controller has made a request.
             self.handle_controller_requests(&mut swanling_attack_run_state)
                 .await?;
@@ -3569,6 +8768,10 @@ impl SwanlingAttack {
             }
         }
 
+        // Restore the terminal before the final metrics are displayed.
+        #[cfg(feature = "tui")]
+        drop(swanling_attack_run_state.tui.take());
+
         Ok(self)
     }
 }
@@ -3609,6 +8812,35 @@ impl SwanlingAttack {
 ///  - [SwanlingDefault::WebSocketHost](../swanling/enum.SwanlingDefault.html#variant.WebSocketHost)
 ///  - [SwanlingDefault::ManagerBindHost](../swanling/enum.SwanlingDefault.html#variant.ManagerBindHost)
 ///  - [SwanlingDefault::ManagerHost](../swanling/enum.SwanlingDefault.html#variant.ManagerHost)
+///  - [SwanlingDefault::ReplayLog](../swanling/enum.SwanlingDefault.html#variant.ReplayLog)
+///  - [SwanlingDefault::ReplaySpeed](../swanling/enum.SwanlingDefault.html#variant.ReplaySpeed)
+///  - [SwanlingDefault::RecordRawLatencies](../swanling/enum.SwanlingDefault.html#variant.RecordRawLatencies)
+///  - [SwanlingDefault::StickyCookie](../swanling/enum.SwanlingDefault.html#variant.StickyCookie)
+///  - [SwanlingDefault::StickyHeader](../swanling/enum.SwanlingDefault.html#variant.StickyHeader)
+///  - [SwanlingDefault::StickyBackends](../swanling/enum.SwanlingDefault.html#variant.StickyBackends)
+///  - [SwanlingDefault::BackendHeader](../swanling/enum.SwanlingDefault.html#variant.BackendHeader)
+///  - [SwanlingDefault::AcceptEncoding](../swanling/enum.SwanlingDefault.html#variant.AcceptEncoding)
+///  - [SwanlingDefault::UserAgentFile](../swanling/enum.SwanlingDefault.html#variant.UserAgentFile)
+///  - [SwanlingDefault::InjectLatency](../swanling/enum.SwanlingDefault.html#variant.InjectLatency)
+///  - [SwanlingDefault::InjectAbort](../swanling/enum.SwanlingDefault.html#variant.InjectAbort)
+///  - [SwanlingDefault::DnsServer](../swanling/enum.SwanlingDefault.html#variant.DnsServer)
+///  - [SwanlingDefault::Healthcheck](../swanling/enum.SwanlingDefault.html#variant.Healthcheck)
+///  - [SwanlingDefault::AbortOnErrorRate](../swanling/enum.SwanlingDefault.html#variant.AbortOnErrorRate)
+///  - [SwanlingDefault::LatencyAlert](../swanling/enum.SwanlingDefault.html#variant.LatencyAlert)
+///  - [SwanlingDefault::ShutdownTimeout](../swanling/enum.SwanlingDefault.html#variant.ShutdownTimeout)
+///  - [SwanlingDefault::UserChurn](../swanling/enum.SwanlingDefault.html#variant.UserChurn)
+///  - [SwanlingDefault::ThrottlePlan](../swanling/enum.SwanlingDefault.html#variant.ThrottlePlan)
+///  - [SwanlingDefault::SaveSessions](../swanling/enum.SwanlingDefault.html#variant.SaveSessions)
+///  - [SwanlingDefault::DebugRedact](../swanling/enum.SwanlingDefault.html#variant.DebugRedact)
+///  - [SwanlingDefault::ErrorLogWindow](../swanling/enum.SwanlingDefault.html#variant.ErrorLogWindow)
+///  - [SwanlingDefault::StartAt](../swanling/enum.SwanlingDefault.html#variant.StartAt)
+///  - [SwanlingDefault::ReportInterval](../swanling/enum.SwanlingDefault.html#variant.ReportInterval)
+///  - [SwanlingDefault::Percentiles](../swanling/enum.SwanlingDefault.html#variant.Percentiles)
+///  - [SwanlingDefault::HistogramBuckets](../swanling/enum.SwanlingDefault.html#variant.HistogramBuckets)
+///  - [SwanlingDefault::UploadUrl](../swanling/enum.SwanlingDefault.html#variant.UploadUrl)
+///  - [SwanlingDefault::NotifyUrl](../swanling/enum.SwanlingDefault.html#variant.NotifyUrl)
+///  - [SwanlingDefault::HatchJitter](../swanling/enum.SwanlingDefault.html#variant.HatchJitter)
+///  - [SwanlingDefault::MetricsFlush](../swanling/enum.SwanlingDefault.html#variant.MetricsFlush)
 ///
 /// The following run-time options can be configured with a custom default using a
 /// `usize` integer:
@@ -3624,6 +8856,16 @@ impl SwanlingAttack {
 ///  - [SwanlingDefault::WebSocketPort](../swanling/enum.SwanlingDefault.html#variant.WebSocketPort)
 ///  - [SwanlingDefault::ManagerBindPort](../swanling/enum.SwanlingDefault.html#variant.ManagerBindPort)
 ///  - [SwanlingDefault::ManagerPort](../swanling/enum.SwanlingDefault.html#variant.ManagerPort)
+///  - [SwanlingDefault::DnsRefresh](../swanling/enum.SwanlingDefault.html#variant.DnsRefresh)
+///  - [SwanlingDefault::HealthcheckInterval](../swanling/enum.SwanlingDefault.html#variant.HealthcheckInterval)
+///  - [SwanlingDefault::MaxRequestNames](../swanling/enum.SwanlingDefault.html#variant.MaxRequestNames)
+///  - [SwanlingDefault::DebugBodyMaxBytes](../swanling/enum.SwanlingDefault.html#variant.DebugBodyMaxBytes)
+///  - [SwanlingDefault::FleetIndex](../swanling/enum.SwanlingDefault.html#variant.FleetIndex)
+///  - [SwanlingDefault::FleetSize](../swanling/enum.SwanlingDefault.html#variant.FleetSize)
+///  - [SwanlingDefault::UploadInterval](../swanling/enum.SwanlingDefault.html#variant.UploadInterval)
+///  - [SwanlingDefault::HatchBatch](../swanling/enum.SwanlingDefault.html#variant.HatchBatch)
+///  - [SwanlingDefault::RuntimeThreads](../swanling/enum.SwanlingDefault.html#variant.RuntimeThreads)
+///  - [SwanlingDefault::Seed](../swanling/enum.SwanlingDefault.html#variant.Seed)
 ///
 /// The following run-time flags can be configured with a custom default using a
 /// `bool` (and otherwise default to `false`).
@@ -3639,13 +8881,27 @@ impl SwanlingAttack {
 ///  - [SwanlingDefault::StickyFollow](../swanling/enum.SwanlingDefault.html#variant.StickyFollow)
 ///  - [SwanlingDefault::Manager](../swanling/enum.SwanlingDefault.html#variant.Manager)
 ///  - [SwanlingDefault::NoHashCheck](../swanling/enum.SwanlingDefault.html#variant.NoHashCheck)
+///  - [SwanlingDefault::HotAddWorkers](../swanling/enum.SwanlingDefault.html#variant.HotAddWorkers)
+///  - [SwanlingDefault::ManagerAlsoWorker](../swanling/enum.SwanlingDefault.html#variant.ManagerAlsoWorker)
 ///  - [SwanlingDefault::Worker](../swanling/enum.SwanlingDefault.html#variant.Worker)
+///  - [SwanlingDefault::NoGzip](../swanling/enum.SwanlingDefault.html#variant.NoGzip)
+///  - [SwanlingDefault::SummaryOnly](../swanling/enum.SwanlingDefault.html#variant.SummaryOnly)
+///  - [SwanlingDefault::NoDnsCache](../swanling/enum.SwanlingDefault.html#variant.NoDnsCache)
+///  - [SwanlingDefault::HealthcheckPause](../swanling/enum.SwanlingDefault.html#variant.HealthcheckPause)
+///  - [SwanlingDefault::Tui](../swanling/enum.SwanlingDefault.html#variant.Tui)
+///  - [SwanlingDefault::RunHeader](../swanling/enum.SwanlingDefault.html#variant.RunHeader)
+///  - [SwanlingDefault::TaskHeaders](../swanling/enum.SwanlingDefault.html#variant.TaskHeaders)
 ///
 /// The following run-time flags can be configured with a custom default using a
 /// `SwanlingLogFormat`.
 ///  - [SwanlingDefault::RequestLog](../swanling/enum.SwanlingDefault.html#variant.RequestLog)
 ///  - [SwanlingDefault::TaskLog](../swanling/enum.SwanlingDefault.html#variant.TaskLog)
 ///  - [SwanlingDefault::DebugFormat](../swanling/enum.SwanlingDefault.html#variant.DebugFormat)
+///
+/// The following run-time options can be configured with a custom default using a
+/// `SwanlingMetricsFormat`.
+///  - [SwanlingDefault::RunningMetricsFormat](../swanling/enum.SwanlingDefault.html#variant.RunningMetricsFormat)
+///  - [SwanlingDefault::SummaryFormat](../swanling/enum.SwanlingDefault.html#variant.SummaryFormat)
 /// # Another Example
 /// ```rust
 /// use swanling::prelude::*;
@@ -3689,6 +8945,57 @@ impl SwanlingDefaultType<&str> for SwanlingAttack {
                 self.defaults.manager_bind_host = Some(value.to_string())
             }
             SwanlingDefault::ManagerHost => self.defaults.manager_host = Some(value.to_string()),
+            SwanlingDefault::ReplayLog => self.defaults.replay_log = Some(value.to_string()),
+            SwanlingDefault::ReplaySpeed => self.defaults.replay_speed = Some(value.to_string()),
+            SwanlingDefault::RecordRawLatencies => {
+                self.defaults.record_raw_latencies = Some(value.to_string())
+            }
+            SwanlingDefault::StickyCookie => self.defaults.sticky_cookie = Some(value.to_string()),
+            SwanlingDefault::StickyHeader => self.defaults.sticky_header = Some(value.to_string()),
+            SwanlingDefault::StickyBackends => {
+                self.defaults.sticky_backends = Some(value.to_string())
+            }
+            SwanlingDefault::BackendHeader => {
+                self.defaults.backend_header = Some(value.to_string())
+            }
+            SwanlingDefault::AcceptEncoding => {
+                self.defaults.accept_encoding = Some(value.to_string())
+            }
+            SwanlingDefault::UserAgentFile => {
+                self.defaults.user_agent_file = Some(value.to_string())
+            }
+            SwanlingDefault::InjectLatency => {
+                self.defaults.inject_latency = Some(value.to_string())
+            }
+            SwanlingDefault::InjectAbort => self.defaults.inject_abort = Some(value.to_string()),
+            SwanlingDefault::DnsServer => self.defaults.dns_server = Some(value.to_string()),
+            SwanlingDefault::Healthcheck => self.defaults.healthcheck = Some(value.to_string()),
+            SwanlingDefault::AbortOnErrorRate => {
+                self.defaults.abort_on_error_rate = Some(value.to_string())
+            }
+            SwanlingDefault::LatencyAlert => self.defaults.latency_alert = Some(value.to_string()),
+            SwanlingDefault::ShutdownTimeout => {
+                self.defaults.shutdown_timeout = Some(value.to_string())
+            }
+            SwanlingDefault::UserChurn => self.defaults.user_churn = Some(value.to_string()),
+            SwanlingDefault::ThrottlePlan => self.defaults.throttle_plan = Some(value.to_string()),
+            SwanlingDefault::SaveSessions => self.defaults.save_sessions = Some(value.to_string()),
+            SwanlingDefault::DebugRedact => self.defaults.debug_redact.push(value.to_string()),
+            SwanlingDefault::ErrorLogWindow => {
+                self.defaults.error_log_window = Some(value.to_string())
+            }
+            SwanlingDefault::StartAt => self.defaults.start_at = Some(value.to_string()),
+            SwanlingDefault::ReportInterval => {
+                self.defaults.report_interval = Some(value.to_string())
+            }
+            SwanlingDefault::Percentiles => self.defaults.percentiles = Some(value.to_string()),
+            SwanlingDefault::HistogramBuckets => {
+                self.defaults.histogram_buckets = Some(value.to_string())
+            }
+            SwanlingDefault::UploadUrl => self.defaults.upload_url = Some(value.to_string()),
+            SwanlingDefault::NotifyUrl => self.defaults.notify_url = Some(value.to_string()),
+            SwanlingDefault::HatchJitter => self.defaults.hatch_jitter = Some(value.to_string()),
+            SwanlingDefault::MetricsFlush => self.defaults.metrics_flush = Some(value.to_string()),
             // Otherwise display a helpful and explicit error.
             SwanlingDefault::Users
             | SwanlingDefault::RunTime
@@ -3699,6 +9006,16 @@ impl SwanlingDefaultType<&str> for SwanlingAttack {
             | SwanlingDefault::TelnetPort
             | SwanlingDefault::WebSocketPort
             | SwanlingDefault::ManagerBindPort
+            | SwanlingDefault::DnsRefresh
+            | SwanlingDefault::HealthcheckInterval
+            | SwanlingDefault::MaxRequestNames
+            | SwanlingDefault::DebugBodyMaxBytes
+            | SwanlingDefault::FleetIndex
+            | SwanlingDefault::FleetSize
+            | SwanlingDefault::UploadInterval
+            | SwanlingDefault::HatchBatch
+            | SwanlingDefault::RuntimeThreads
+            | SwanlingDefault::Seed
             | SwanlingDefault::ManagerPort => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
@@ -3722,6 +9039,15 @@ impl SwanlingDefaultType<&str> for SwanlingAttack {
             | SwanlingDefault::StickyFollow
             | SwanlingDefault::Manager
             | SwanlingDefault::NoHashCheck
+            | SwanlingDefault::HotAddWorkers
+            | SwanlingDefault::ManagerAlsoWorker
+            | SwanlingDefault::NoGzip
+            | SwanlingDefault::SummaryOnly
+            | SwanlingDefault::NoDnsCache
+            | SwanlingDefault::HealthcheckPause
+            | SwanlingDefault::Tui
+            | SwanlingDefault::RunHeader
+            | SwanlingDefault::TaskHeaders
             | SwanlingDefault::Worker => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
@@ -3755,6 +9081,16 @@ impl SwanlingDefaultType<&str> for SwanlingAttack {
                     ),
                 });
             }
+            SwanlingDefault::RunningMetricsFormat | SwanlingDefault::SummaryFormat => {
+                return Err(SwanlingError::InvalidOption {
+                    option: format!("SwanlingDefault::{:?}", key),
+                    value: value.to_string(),
+                    detail: format!(
+                        "set_default(SwanlingDefault::{:?}, {}) expected SwanlingMetricsFormat value, received &str",
+                        key, value
+                    ),
+                });
+            }
         }
         Ok(Box::new(self))
     }
@@ -3779,6 +9115,18 @@ impl SwanlingDefaultType<usize> for SwanlingAttack {
                 self.defaults.manager_bind_port = Some(value as u16)
             }
             SwanlingDefault::ManagerPort => self.defaults.manager_port = Some(value as u16),
+            SwanlingDefault::DnsRefresh => self.defaults.dns_refresh = Some(value),
+            SwanlingDefault::HealthcheckInterval => {
+                self.defaults.healthcheck_interval = Some(value)
+            }
+            SwanlingDefault::MaxRequestNames => self.defaults.max_request_names = Some(value),
+            SwanlingDefault::DebugBodyMaxBytes => self.defaults.debug_body_max_bytes = Some(value),
+            SwanlingDefault::FleetIndex => self.defaults.fleet_index = Some(value),
+            SwanlingDefault::FleetSize => self.defaults.fleet_size = Some(value),
+            SwanlingDefault::UploadInterval => self.defaults.upload_interval = Some(value),
+            SwanlingDefault::HatchBatch => self.defaults.hatch_batch = Some(value),
+            SwanlingDefault::RuntimeThreads => self.defaults.runtime_threads = Some(value),
+            SwanlingDefault::Seed => self.defaults.seed = Some(value as u64),
             // Otherwise display a helpful and explicit error.
             SwanlingDefault::Host
             | SwanlingDefault::HatchRate
@@ -3791,6 +9139,35 @@ impl SwanlingDefaultType<usize> for SwanlingAttack {
             | SwanlingDefault::TelnetHost
             | SwanlingDefault::WebSocketHost
             | SwanlingDefault::ManagerBindHost
+            | SwanlingDefault::ReplayLog
+            | SwanlingDefault::ReplaySpeed
+            | SwanlingDefault::RecordRawLatencies
+            | SwanlingDefault::StickyCookie
+            | SwanlingDefault::StickyHeader
+            | SwanlingDefault::StickyBackends
+            | SwanlingDefault::BackendHeader
+            | SwanlingDefault::AcceptEncoding
+            | SwanlingDefault::UserAgentFile
+            | SwanlingDefault::InjectLatency
+            | SwanlingDefault::InjectAbort
+            | SwanlingDefault::DnsServer
+            | SwanlingDefault::Healthcheck
+            | SwanlingDefault::AbortOnErrorRate
+            | SwanlingDefault::LatencyAlert
+            | SwanlingDefault::ShutdownTimeout
+            | SwanlingDefault::UserChurn
+            | SwanlingDefault::ThrottlePlan
+            | SwanlingDefault::SaveSessions
+            | SwanlingDefault::DebugRedact
+            | SwanlingDefault::ErrorLogWindow
+            | SwanlingDefault::StartAt
+            | SwanlingDefault::ReportInterval
+            | SwanlingDefault::Percentiles
+            | SwanlingDefault::HistogramBuckets
+            | SwanlingDefault::UploadUrl
+            | SwanlingDefault::NotifyUrl
+            | SwanlingDefault::HatchJitter
+            | SwanlingDefault::MetricsFlush
             | SwanlingDefault::ManagerHost => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
@@ -3813,6 +9190,15 @@ impl SwanlingDefaultType<usize> for SwanlingAttack {
             | SwanlingDefault::StickyFollow
             | SwanlingDefault::Manager
             | SwanlingDefault::NoHashCheck
+            | SwanlingDefault::HotAddWorkers
+            | SwanlingDefault::ManagerAlsoWorker
+            | SwanlingDefault::NoGzip
+            | SwanlingDefault::SummaryOnly
+            | SwanlingDefault::NoDnsCache
+            | SwanlingDefault::HealthcheckPause
+            | SwanlingDefault::Tui
+            | SwanlingDefault::RunHeader
+            | SwanlingDefault::TaskHeaders
             | SwanlingDefault::Worker => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
@@ -3846,6 +9232,16 @@ impl SwanlingDefaultType<usize> for SwanlingAttack {
                     ),
                 });
             }
+            SwanlingDefault::RunningMetricsFormat | SwanlingDefault::SummaryFormat => {
+                return Err(SwanlingError::InvalidOption {
+                    option: format!("SwanlingDefault::{:?}", key),
+                    value: value.to_string(),
+                    detail: format!(
+                        "set_default(SwanlingDefault::{:?}, {}) expected SwanlingMetricsFormat value, received usize",
+                        key, value
+                    ),
+                });
+            }
         }
         Ok(Box::new(self))
     }
@@ -3869,7 +9265,16 @@ impl SwanlingDefaultType<bool> for SwanlingAttack {
             SwanlingDefault::StickyFollow => self.defaults.sticky_follow = Some(value),
             SwanlingDefault::Manager => self.defaults.manager = Some(value),
             SwanlingDefault::NoHashCheck => self.defaults.no_hash_check = Some(value),
+            SwanlingDefault::HotAddWorkers => self.defaults.hot_add_workers = Some(value),
+            SwanlingDefault::ManagerAlsoWorker => self.defaults.manager_also_worker = Some(value),
             SwanlingDefault::Worker => self.defaults.worker = Some(value),
+            SwanlingDefault::NoGzip => self.defaults.no_gzip = Some(value),
+            SwanlingDefault::SummaryOnly => self.defaults.summary_only = Some(value),
+            SwanlingDefault::NoDnsCache => self.defaults.no_dns_cache = Some(value),
+            SwanlingDefault::HealthcheckPause => self.defaults.healthcheck_pause = Some(value),
+            SwanlingDefault::Tui => self.defaults.tui = Some(value),
+            SwanlingDefault::RunHeader => self.defaults.run_header = Some(value),
+            SwanlingDefault::TaskHeaders => self.defaults.task_headers = Some(value),
             // Otherwise display a helpful and explicit error.
             SwanlingDefault::Host
             | SwanlingDefault::SwanlingLog
@@ -3882,6 +9287,35 @@ impl SwanlingDefaultType<bool> for SwanlingAttack {
             | SwanlingDefault::TelnetHost
             | SwanlingDefault::WebSocketHost
             | SwanlingDefault::ManagerBindHost
+            | SwanlingDefault::ReplayLog
+            | SwanlingDefault::ReplaySpeed
+            | SwanlingDefault::RecordRawLatencies
+            | SwanlingDefault::StickyCookie
+            | SwanlingDefault::StickyHeader
+            | SwanlingDefault::StickyBackends
+            | SwanlingDefault::BackendHeader
+            | SwanlingDefault::AcceptEncoding
+            | SwanlingDefault::UserAgentFile
+            | SwanlingDefault::InjectLatency
+            | SwanlingDefault::InjectAbort
+            | SwanlingDefault::DnsServer
+            | SwanlingDefault::Healthcheck
+            | SwanlingDefault::AbortOnErrorRate
+            | SwanlingDefault::LatencyAlert
+            | SwanlingDefault::ShutdownTimeout
+            | SwanlingDefault::UserChurn
+            | SwanlingDefault::ThrottlePlan
+            | SwanlingDefault::SaveSessions
+            | SwanlingDefault::DebugRedact
+            | SwanlingDefault::ErrorLogWindow
+            | SwanlingDefault::StartAt
+            | SwanlingDefault::ReportInterval
+            | SwanlingDefault::Percentiles
+            | SwanlingDefault::HistogramBuckets
+            | SwanlingDefault::UploadUrl
+            | SwanlingDefault::NotifyUrl
+            | SwanlingDefault::HatchJitter
+            | SwanlingDefault::MetricsFlush
             | SwanlingDefault::ManagerHost => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
@@ -3902,6 +9336,16 @@ impl SwanlingDefaultType<bool> for SwanlingAttack {
             | SwanlingDefault::TelnetPort
             | SwanlingDefault::WebSocketPort
             | SwanlingDefault::ManagerBindPort
+            | SwanlingDefault::DnsRefresh
+            | SwanlingDefault::HealthcheckInterval
+            | SwanlingDefault::MaxRequestNames
+            | SwanlingDefault::DebugBodyMaxBytes
+            | SwanlingDefault::FleetIndex
+            | SwanlingDefault::FleetSize
+            | SwanlingDefault::UploadInterval
+            | SwanlingDefault::HatchBatch
+            | SwanlingDefault::RuntimeThreads
+            | SwanlingDefault::Seed
             | SwanlingDefault::ManagerPort => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
@@ -3918,35 +9362,195 @@ impl SwanlingDefaultType<bool> for SwanlingAttack {
             | SwanlingDefault::TaskFormat => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
-                    value: value.to_string(),
+                    value: value.to_string(),
+                    detail: format!(
+                        "set_default(SwanlingDefault::{:?}, {}) expected SwanlingLogFormat value, received bool",
+                        key, value
+                    ),
+                });
+            }
+            SwanlingDefault::CoordinatedOmissionMitigation => {
+                return Err(SwanlingError::InvalidOption {
+                    option: format!("SwanlingDefault::{:?}", key),
+                    value: value.to_string(),
+                    detail: format!(
+                        "set_default(SwanlingDefault::{:?}, {}) expected SwanlingCoordinatedOmissionMitigation value, received bool",
+                        key, value
+                    ),
+                });
+            }
+            SwanlingDefault::RunningMetricsFormat | SwanlingDefault::SummaryFormat => {
+                return Err(SwanlingError::InvalidOption {
+                    option: format!("SwanlingDefault::{:?}", key),
+                    value: value.to_string(),
+                    detail: format!(
+                        "set_default(SwanlingDefault::{:?}, {}) expected SwanlingMetricsFormat value, received bool",
+                        key, value
+                    ),
+                });
+            }
+        }
+        Ok(Box::new(self))
+    }
+}
+impl SwanlingDefaultType<SwanlingCoordinatedOmissionMitigation> for SwanlingAttack {
+    fn set_default(
+        mut self,
+        key: SwanlingDefault,
+        value: SwanlingCoordinatedOmissionMitigation,
+    ) -> Result<Box<Self>, SwanlingError> {
+        match key {
+            SwanlingDefault::CoordinatedOmissionMitigation => self.defaults.co_mitigation = Some(value),
+            // Otherwise display a helpful and explicit error.
+            SwanlingDefault::NoResetMetrics
+            | SwanlingDefault::NoMetrics
+            | SwanlingDefault::NoTaskMetrics
+            | SwanlingDefault::NoErrorSummary
+            | SwanlingDefault::NoDebugBody
+            | SwanlingDefault::NoTelnet
+            | SwanlingDefault::NoWebSocket
+            | SwanlingDefault::NoAutoStart
+            | SwanlingDefault::StatusCodes
+            | SwanlingDefault::StickyFollow
+            | SwanlingDefault::Manager
+            | SwanlingDefault::NoHashCheck
+            | SwanlingDefault::HotAddWorkers
+            | SwanlingDefault::ManagerAlsoWorker
+            | SwanlingDefault::NoGzip
+            | SwanlingDefault::SummaryOnly
+            | SwanlingDefault::NoDnsCache
+            | SwanlingDefault::HealthcheckPause
+            | SwanlingDefault::Tui
+            | SwanlingDefault::RunHeader
+            | SwanlingDefault::TaskHeaders
+            | SwanlingDefault::Worker => {
+                return Err(SwanlingError::InvalidOption {
+                    option: format!("SwanlingDefault::{:?}", key),
+                    value: format!("{:?}", value),
+                    detail: format!(
+                        "set_default(SwanlingDefault::{:?}, {:?}) expected bool value, received SwanlingCoordinatedOmissionMitigation",
+                        key, value
+                    ),
+                })
+            }
+            SwanlingDefault::Host
+            | SwanlingDefault::SwanlingLog
+            | SwanlingDefault::ReportFile
+            | SwanlingDefault::RequestLog
+            | SwanlingDefault::TaskLog
+            | SwanlingDefault::RunningMetrics
+            | SwanlingDefault::ErrorLog
+            | SwanlingDefault::DebugLog
+            | SwanlingDefault::TelnetHost
+            | SwanlingDefault::WebSocketHost
+            | SwanlingDefault::ManagerBindHost
+            | SwanlingDefault::ReplayLog
+            | SwanlingDefault::ReplaySpeed
+            | SwanlingDefault::RecordRawLatencies
+            | SwanlingDefault::StickyCookie
+            | SwanlingDefault::StickyHeader
+            | SwanlingDefault::StickyBackends
+            | SwanlingDefault::BackendHeader
+            | SwanlingDefault::AcceptEncoding
+            | SwanlingDefault::UserAgentFile
+            | SwanlingDefault::InjectLatency
+            | SwanlingDefault::InjectAbort
+            | SwanlingDefault::DnsServer
+            | SwanlingDefault::Healthcheck
+            | SwanlingDefault::AbortOnErrorRate
+            | SwanlingDefault::LatencyAlert
+            | SwanlingDefault::ShutdownTimeout
+            | SwanlingDefault::UserChurn
+            | SwanlingDefault::ThrottlePlan
+            | SwanlingDefault::SaveSessions
+            | SwanlingDefault::DebugRedact
+            | SwanlingDefault::ErrorLogWindow
+            | SwanlingDefault::StartAt
+            | SwanlingDefault::ReportInterval
+            | SwanlingDefault::Percentiles
+            | SwanlingDefault::HistogramBuckets
+            | SwanlingDefault::UploadUrl
+            | SwanlingDefault::NotifyUrl
+            | SwanlingDefault::HatchJitter
+            | SwanlingDefault::MetricsFlush
+            | SwanlingDefault::ManagerHost => {
+                return Err(SwanlingError::InvalidOption {
+                    option: format!("SwanlingDefault::{:?}", key),
+                    value: format!("{:?}", value),
+                    detail: format!(
+                        "set_default(SwanlingDefault::{:?}, {:?}) expected &str value, received SwanlingCoordinatedOmissionMitigation",
+                        key, value
+                    ),
+                })
+            }
+            SwanlingDefault::Users
+            | SwanlingDefault::HatchRate
+            | SwanlingDefault::RunTime
+            | SwanlingDefault::LogLevel
+            | SwanlingDefault::Verbose
+            | SwanlingDefault::ThrottleRequests
+            | SwanlingDefault::ExpectWorkers
+            | SwanlingDefault::TelnetPort
+            | SwanlingDefault::WebSocketPort
+            | SwanlingDefault::ManagerBindPort
+            | SwanlingDefault::DnsRefresh
+            | SwanlingDefault::HealthcheckInterval
+            | SwanlingDefault::MaxRequestNames
+            | SwanlingDefault::DebugBodyMaxBytes
+            | SwanlingDefault::FleetIndex
+            | SwanlingDefault::FleetSize
+            | SwanlingDefault::UploadInterval
+            | SwanlingDefault::HatchBatch
+            | SwanlingDefault::RuntimeThreads
+            | SwanlingDefault::Seed
+            | SwanlingDefault::ManagerPort => {
+                return Err(SwanlingError::InvalidOption {
+                    option: format!("SwanlingDefault::{:?}", key),
+                    value: format!("{:?}", value),
+                    detail: format!(
+                        "set_default(SwanlingDefault::{:?}, {:?}) expected usize value, received SwanlingCoordinatedOmissionMitigation",
+                        key, value
+                    ),
+                })
+            }
+            SwanlingDefault::RequestFormat
+            | SwanlingDefault::DebugFormat
+            | SwanlingDefault::ErrorFormat
+            | SwanlingDefault::TaskFormat => {
+                return Err(SwanlingError::InvalidOption {
+                    option: format!("SwanlingDefault::{:?}", key),
+                    value: format!("{:?}", value),
                     detail: format!(
-                        "set_default(SwanlingDefault::{:?}, {}) expected SwanlingLogFormat value, received bool",
+                        "set_default(SwanlingDefault::{:?}, {:?}) expected SwanlingLogFormat value, received SwanlingCoordinatedOmissionMitigation",
                         key, value
                     ),
-                });
+                })
             }
-            SwanlingDefault::CoordinatedOmissionMitigation => {
+            SwanlingDefault::RunningMetricsFormat | SwanlingDefault::SummaryFormat => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
-                    value: value.to_string(),
+                    value: format!("{:?}", value),
                     detail: format!(
-                        "set_default(SwanlingDefault::{:?}, {}) expected SwanlingCoordinatedOmissionMitigation value, received bool",
+                        "set_default(SwanlingDefault::{:?}, {:?}) expected SwanlingMetricsFormat value, received SwanlingCoordinatedOmissionMitigation",
                         key, value
                     ),
-                });
+                })
             }
         }
         Ok(Box::new(self))
     }
 }
-impl SwanlingDefaultType<SwanlingCoordinatedOmissionMitigation> for SwanlingAttack {
+impl SwanlingDefaultType<SwanlingLogFormat> for SwanlingAttack {
     fn set_default(
         mut self,
         key: SwanlingDefault,
-        value: SwanlingCoordinatedOmissionMitigation,
+        value: SwanlingLogFormat,
     ) -> Result<Box<Self>, SwanlingError> {
         match key {
-            SwanlingDefault::CoordinatedOmissionMitigation => self.defaults.co_mitigation = Some(value),
+            SwanlingDefault::RequestFormat => self.defaults.request_format = Some(value),
+            SwanlingDefault::DebugFormat => self.defaults.debug_format = Some(value),
+            SwanlingDefault::ErrorFormat => self.defaults.error_format = Some(value),
+            SwanlingDefault::TaskFormat => self.defaults.task_format = Some(value),
             // Otherwise display a helpful and explicit error.
             SwanlingDefault::NoResetMetrics
             | SwanlingDefault::NoMetrics
@@ -3960,6 +9564,15 @@ impl SwanlingDefaultType<SwanlingCoordinatedOmissionMitigation> for SwanlingAtta
             | SwanlingDefault::StickyFollow
             | SwanlingDefault::Manager
             | SwanlingDefault::NoHashCheck
+            | SwanlingDefault::HotAddWorkers
+            | SwanlingDefault::ManagerAlsoWorker
+            | SwanlingDefault::NoGzip
+            | SwanlingDefault::SummaryOnly
+            | SwanlingDefault::NoDnsCache
+            | SwanlingDefault::HealthcheckPause
+            | SwanlingDefault::Tui
+            | SwanlingDefault::RunHeader
+            | SwanlingDefault::TaskHeaders
             | SwanlingDefault::Worker => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
@@ -3981,6 +9594,35 @@ impl SwanlingDefaultType<SwanlingCoordinatedOmissionMitigation> for SwanlingAtta
             | SwanlingDefault::TelnetHost
             | SwanlingDefault::WebSocketHost
             | SwanlingDefault::ManagerBindHost
+            | SwanlingDefault::ReplayLog
+            | SwanlingDefault::ReplaySpeed
+            | SwanlingDefault::RecordRawLatencies
+            | SwanlingDefault::StickyCookie
+            | SwanlingDefault::StickyHeader
+            | SwanlingDefault::StickyBackends
+            | SwanlingDefault::BackendHeader
+            | SwanlingDefault::AcceptEncoding
+            | SwanlingDefault::UserAgentFile
+            | SwanlingDefault::InjectLatency
+            | SwanlingDefault::InjectAbort
+            | SwanlingDefault::DnsServer
+            | SwanlingDefault::Healthcheck
+            | SwanlingDefault::AbortOnErrorRate
+            | SwanlingDefault::LatencyAlert
+            | SwanlingDefault::ShutdownTimeout
+            | SwanlingDefault::UserChurn
+            | SwanlingDefault::ThrottlePlan
+            | SwanlingDefault::SaveSessions
+            | SwanlingDefault::DebugRedact
+            | SwanlingDefault::ErrorLogWindow
+            | SwanlingDefault::StartAt
+            | SwanlingDefault::ReportInterval
+            | SwanlingDefault::Percentiles
+            | SwanlingDefault::HistogramBuckets
+            | SwanlingDefault::UploadUrl
+            | SwanlingDefault::NotifyUrl
+            | SwanlingDefault::HatchJitter
+            | SwanlingDefault::MetricsFlush
             | SwanlingDefault::ManagerHost => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
@@ -4001,6 +9643,16 @@ impl SwanlingDefaultType<SwanlingCoordinatedOmissionMitigation> for SwanlingAtta
             | SwanlingDefault::TelnetPort
             | SwanlingDefault::WebSocketPort
             | SwanlingDefault::ManagerBindPort
+            | SwanlingDefault::DnsRefresh
+            | SwanlingDefault::HealthcheckInterval
+            | SwanlingDefault::MaxRequestNames
+            | SwanlingDefault::DebugBodyMaxBytes
+            | SwanlingDefault::FleetIndex
+            | SwanlingDefault::FleetSize
+            | SwanlingDefault::UploadInterval
+            | SwanlingDefault::HatchBatch
+            | SwanlingDefault::RuntimeThreads
+            | SwanlingDefault::Seed
             | SwanlingDefault::ManagerPort => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
@@ -4011,15 +9663,23 @@ impl SwanlingDefaultType<SwanlingCoordinatedOmissionMitigation> for SwanlingAtta
                     ),
                 })
             }
-            SwanlingDefault::RequestFormat
-            | SwanlingDefault::DebugFormat
-            | SwanlingDefault::ErrorFormat
-            | SwanlingDefault::TaskFormat => {
+            SwanlingDefault::CoordinatedOmissionMitigation => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{:?}", value),
                     detail: format!(
-                        "set_default(SwanlingDefault::{:?}, {:?}) expected SwanlingLogFormat value, received SwanlingCoordinatedOmissionMitigation",
+                        "set_default(SwanlingDefault::{:?}, {:?}) expected SwanlingCoordinatedOmissionMitigation value, received SwanlingLogFormat",
+                        key, value
+                    ),
+                })
+
+            }
+            SwanlingDefault::RunningMetricsFormat | SwanlingDefault::SummaryFormat => {
+                return Err(SwanlingError::InvalidOption {
+                    option: format!("SwanlingDefault::{:?}", key),
+                    value: format!("{:?}", value),
+                    detail: format!(
+                        "set_default(SwanlingDefault::{:?}, {:?}) expected SwanlingMetricsFormat value, received SwanlingLogFormat",
                         key, value
                     ),
                 })
@@ -4028,17 +9688,18 @@ impl SwanlingDefaultType<SwanlingCoordinatedOmissionMitigation> for SwanlingAtta
         Ok(Box::new(self))
     }
 }
-impl SwanlingDefaultType<SwanlingLogFormat> for SwanlingAttack {
+
+impl SwanlingDefaultType<SwanlingMetricsFormat> for SwanlingAttack {
     fn set_default(
         mut self,
         key: SwanlingDefault,
-        value: SwanlingLogFormat,
+        value: SwanlingMetricsFormat,
     ) -> Result<Box<Self>, SwanlingError> {
         match key {
-            SwanlingDefault::RequestFormat => self.defaults.request_format = Some(value),
-            SwanlingDefault::DebugFormat => self.defaults.debug_format = Some(value),
-            SwanlingDefault::ErrorFormat => self.defaults.error_format = Some(value),
-            SwanlingDefault::TaskFormat => self.defaults.task_format = Some(value),
+            SwanlingDefault::RunningMetricsFormat => {
+                self.defaults.running_metrics_format = Some(value)
+            }
+            SwanlingDefault::SummaryFormat => self.defaults.summary_format = Some(value),
             // Otherwise display a helpful and explicit error.
             SwanlingDefault::NoResetMetrics
             | SwanlingDefault::NoMetrics
@@ -4052,12 +9713,21 @@ impl SwanlingDefaultType<SwanlingLogFormat> for SwanlingAttack {
             | SwanlingDefault::StickyFollow
             | SwanlingDefault::Manager
             | SwanlingDefault::NoHashCheck
+            | SwanlingDefault::HotAddWorkers
+            | SwanlingDefault::ManagerAlsoWorker
+            | SwanlingDefault::NoGzip
+            | SwanlingDefault::SummaryOnly
+            | SwanlingDefault::NoDnsCache
+            | SwanlingDefault::HealthcheckPause
+            | SwanlingDefault::Tui
+            | SwanlingDefault::RunHeader
+            | SwanlingDefault::TaskHeaders
             | SwanlingDefault::Worker => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{:?}", value),
                     detail: format!(
-                        "set_default(SwanlingDefault::{:?}, {:?}) expected bool value, received SwanlingCoordinatedOmissionMitigation",
+                        "set_default(SwanlingDefault::{:?}, {:?}) expected bool value, received SwanlingMetricsFormat",
                         key, value
                     ),
                 })
@@ -4073,12 +9743,41 @@ impl SwanlingDefaultType<SwanlingLogFormat> for SwanlingAttack {
             | SwanlingDefault::TelnetHost
             | SwanlingDefault::WebSocketHost
             | SwanlingDefault::ManagerBindHost
+            | SwanlingDefault::ReplayLog
+            | SwanlingDefault::ReplaySpeed
+            | SwanlingDefault::RecordRawLatencies
+            | SwanlingDefault::StickyCookie
+            | SwanlingDefault::StickyHeader
+            | SwanlingDefault::StickyBackends
+            | SwanlingDefault::BackendHeader
+            | SwanlingDefault::AcceptEncoding
+            | SwanlingDefault::UserAgentFile
+            | SwanlingDefault::InjectLatency
+            | SwanlingDefault::InjectAbort
+            | SwanlingDefault::DnsServer
+            | SwanlingDefault::Healthcheck
+            | SwanlingDefault::AbortOnErrorRate
+            | SwanlingDefault::LatencyAlert
+            | SwanlingDefault::ShutdownTimeout
+            | SwanlingDefault::UserChurn
+            | SwanlingDefault::ThrottlePlan
+            | SwanlingDefault::SaveSessions
+            | SwanlingDefault::DebugRedact
+            | SwanlingDefault::ErrorLogWindow
+            | SwanlingDefault::StartAt
+            | SwanlingDefault::ReportInterval
+            | SwanlingDefault::Percentiles
+            | SwanlingDefault::HistogramBuckets
+            | SwanlingDefault::UploadUrl
+            | SwanlingDefault::NotifyUrl
+            | SwanlingDefault::HatchJitter
+            | SwanlingDefault::MetricsFlush
             | SwanlingDefault::ManagerHost => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{:?}", value),
                     detail: format!(
-                        "set_default(SwanlingDefault::{:?}, {:?}) expected &str value, received SwanlingCoordinatedOmissionMitigation",
+                        "set_default(SwanlingDefault::{:?}, {:?}) expected &str value, received SwanlingMetricsFormat",
                         key, value
                     ),
                 })
@@ -4093,12 +9792,35 @@ impl SwanlingDefaultType<SwanlingLogFormat> for SwanlingAttack {
             | SwanlingDefault::TelnetPort
             | SwanlingDefault::WebSocketPort
             | SwanlingDefault::ManagerBindPort
+            | SwanlingDefault::DnsRefresh
+            | SwanlingDefault::HealthcheckInterval
+            | SwanlingDefault::MaxRequestNames
+            | SwanlingDefault::DebugBodyMaxBytes
+            | SwanlingDefault::FleetIndex
+            | SwanlingDefault::FleetSize
+            | SwanlingDefault::UploadInterval
+            | SwanlingDefault::HatchBatch
+            | SwanlingDefault::RuntimeThreads
+            | SwanlingDefault::Seed
             | SwanlingDefault::ManagerPort => {
                 return Err(SwanlingError::InvalidOption {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{:?}", value),
                     detail: format!(
-                        "set_default(SwanlingDefault::{:?}, {:?}) expected usize value, received SwanlingCoordinatedOmissionMitigation",
+                        "set_default(SwanlingDefault::{:?}, {:?}) expected usize value, received SwanlingMetricsFormat",
+                        key, value
+                    ),
+                })
+            }
+            SwanlingDefault::RequestFormat
+            | SwanlingDefault::DebugFormat
+            | SwanlingDefault::ErrorFormat
+            | SwanlingDefault::TaskFormat => {
+                return Err(SwanlingError::InvalidOption {
+                    option: format!("SwanlingDefault::{:?}", key),
+                    value: format!("{:?}", value),
+                    detail: format!(
+                        "set_default(SwanlingDefault::{:?}, {:?}) expected SwanlingLogFormat value, received SwanlingMetricsFormat",
                         key, value
                     ),
                 })
@@ -4108,7 +9830,7 @@ impl SwanlingDefaultType<SwanlingLogFormat> for SwanlingAttack {
                     option: format!("SwanlingDefault::{:?}", key),
                     value: format!("{:?}", value),
                     detail: format!(
-                        "set_default(SwanlingDefault::{:?}, {:?}) expected SwanlingCoordinatedOmissionMitigation value, received SwanlingLogFormat",
+                        "set_default(SwanlingDefault::{:?}, {:?}) expected SwanlingCoordinatedOmissionMitigation value, received SwanlingMetricsFormat",
                         key, value
                     ),
                 })
@@ -4120,7 +9842,7 @@ impl SwanlingDefaultType<SwanlingLogFormat> for SwanlingAttack {
 }
 
 /// Options available when launching a Swanling load test.
-#[derive(Options, Debug, Clone, Serialize, Deserialize)]
+#[derive(Options, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SwanlingConfiguration {
     /// Displays this help
     #[options(short = "h")]
@@ -4128,22 +9850,40 @@ pub struct SwanlingConfiguration {
     /// Prints version information
     #[options(short = "V")]
     pub version: bool,
-    // Add a blank line after this option
-    #[options(short = "l", help = "Lists all tasks and exits\n")]
+    /// Lists all tasks and exits
+    #[options(short = "l")]
     pub list: bool,
+    /// Sets --list format (text, json)
+    #[options(no_short, meta = "FORMAT")]
+    pub format: String,
+    // Add a blank line after this option
+    #[options(
+        no_short,
+        help = "Validates the load test, prints its plan and exits\n"
+    )]
+    pub dry_run: bool,
 
-    /// Defines host to load test (ie http://10.21.32.33)
+    /// Defines host to load test (ie http://10.21.32.33), or comma-separated hosts to spread users over (ie http://a,http://b=2)
     #[options(short = "H")]
     pub host: String,
     /// Sets concurrent users (default: number of CPUs)
     #[options(short = "u")]
     pub users: Option<usize>,
-    /// Sets per-second user hatch rate (default: 1)
+    /// Sets per-second user hatch rate, or a ramp like 1..20/5m (default: 1)
     #[options(short = "r", meta = "RATE")]
     pub hatch_rate: Option<String>,
+    /// Launches users in batches of this size (default: 1)
+    #[options(no_short, meta = "USERS")]
+    pub hatch_batch: Option<usize>,
+    /// Randomly shifts when each batch launches (ie 200ms)
+    #[options(no_short, meta = "TIME")]
+    pub hatch_jitter: String,
     /// Stops after (30s, 20m, 3h, 1h30m, etc)
     #[options(short = "t", meta = "TIME")]
     pub run_time: String,
+    /// Aborts users still running this long after stopping (ie 60s)
+    #[options(no_short, meta = "TIME")]
+    pub shutdown_timeout: String,
     /// Enables Swanling log file and sets name
     #[options(short = "G", meta = "NAME")]
     pub swanling_log: String,
@@ -4161,6 +9901,21 @@ pub struct SwanlingConfiguration {
     /// How often to optionally print running metrics
     #[options(no_short, meta = "TIME")]
     pub running_metrics: Option<usize>,
+    /// Sets running metrics format (table, compact, json)
+    #[options(no_short, meta = "FORMAT")]
+    pub running_metrics_format: Option<SwanlingMetricsFormat>,
+    /// Only displays the final metrics summary
+    #[options(no_short)]
+    pub summary_only: bool,
+    /// Sets final metrics summary format (table, compact, json)
+    #[options(no_short, meta = "FORMAT")]
+    pub summary_format: Option<SwanlingMetricsFormat>,
+    /// Sets comma-separated response time percentiles to report
+    #[options(no_short, meta = "LIST")]
+    pub percentiles: String,
+    /// Displays a live dashboard instead of running metrics
+    #[options(no_short)]
+    pub tui: bool,
     /// Doesn't reset metrics after all users have started
     #[options(no_short)]
     pub no_reset_metrics: bool,
@@ -4173,36 +9928,93 @@ pub struct SwanlingConfiguration {
     /// Doesn't display an error summary
     #[options(no_short)]
     pub no_error_summary: bool,
+    /// Summarizes errors per value of a request tag (repeatable)
+    #[options(no_short, meta = "KEY")]
+    pub error_tag: Vec<String>,
+    /// Sets maximum unique request names tracked
+    #[options(no_short, meta = "VALUE")]
+    pub max_request_names: usize,
+    /// How often users send metrics to the parent (default: 100ms)
+    #[options(no_short, meta = "TIME")]
+    pub metrics_flush: String,
     /// Create an html-formatted report
     #[options(no_short, meta = "NAME")]
     pub report_file: String,
+    /// How often to write report snapshots while running (ie 5m)
+    #[options(no_short, meta = "TIME")]
+    pub report_interval: String,
+    /// Sets comma-separated latency histogram buckets in the report (in ms)
+    #[options(no_short, meta = "LIST")]
+    pub histogram_buckets: String,
+    /// Adds metadata to reports, ie git_sha=1a2b3c4
+    #[options(no_short, meta = "KEY=VALUE")]
+    pub label: Vec<String>,
+    /// Identifies this run in logs, metrics and reports (default: random UUID)
+    #[options(no_short, meta = "ID")]
+    pub run_id: String,
+    /// Sends the run ID in an X-Load-Test-Run header with each request
+    #[options(no_short)]
+    pub run_header: bool,
+    /// Sends X-Swanling-Task and X-Swanling-User headers with each request
+    #[options(no_short)]
+    pub task_headers: bool,
+    /// Uploads metrics and report to object storage, ie s3://bucket/prefix
+    #[options(no_short, meta = "URL")]
+    pub upload_url: String,
+    /// How often to upload metrics snapshots (default: 60)
+    #[options(no_short, meta = "TIME")]
+    pub upload_interval: Option<usize>,
+    /// Posts load test events and results to a webhook
+    #[options(no_short, meta = "URL")]
+    pub notify_url: String,
+    /// Merges JSON metrics files into one report and exits
+    #[options(no_short)]
+    pub merge_reports: bool,
+    /// JSON metrics files to merge with --merge-reports
+    #[options(free)]
+    pub reports: Vec<String>,
     /// Sets request log file name
     #[options(short = "R", meta = "NAME")]
     pub request_log: String,
-    /// Sets request log format (csv, json, raw)
+    /// Sets request log format (csv, json, ndjson, pretty-json, raw)
     #[options(no_short, meta = "FORMAT")]
     pub request_format: Option<SwanlingLogFormat>,
+    /// Records raw latency of each request to a csv or parquet file
+    #[options(no_short, meta = "NAME")]
+    pub record_raw_latencies: String,
     /// Sets task log file name
     #[options(short = "T", meta = "NAME")]
     pub task_log: String,
-    /// Sets task log format (csv, json, raw)
+    /// Sets task log format (csv, json, ndjson, pretty-json, raw)
     #[options(no_short, meta = "FORMAT")]
     pub task_format: Option<SwanlingLogFormat>,
     /// Sets error log file name
     #[options(short = "E", meta = "NAME")]
     pub error_log: String,
-    /// Sets error log format (csv, json, raw)
+    /// Sets error log format (csv, json, ndjson, pretty-json, raw)
     #[options(no_short, meta = "FORMAT")]
     pub error_format: Option<SwanlingLogFormat>,
+    /// Aggregates identical errors in the error log for this long (ie 1s)
+    #[options(no_short, meta = "TIME")]
+    pub error_log_window: String,
     /// Sets debug log file name
     #[options(short = "D", meta = "NAME")]
     pub debug_log: String,
-    /// Sets debug log format (csv, json, raw)
+    /// Sets debug log format (csv, json, ndjson, pretty-json, raw)
     #[options(no_short, meta = "FORMAT")]
     pub debug_format: Option<SwanlingLogFormat>,
     /// Do not include the response body in the debug log
     #[options(no_short)]
     pub no_debug_body: bool,
+    /// Truncates response bodies in the debug log to this many bytes
+    #[options(no_short, meta = "BYTES")]
+    pub debug_body_max_bytes: usize,
+    /// Redacts a regex from headers and bodies in the debug log (repeatable)
+    #[options(no_short, meta = "REGEX")]
+    pub debug_redact: Vec<String>,
+    /// Runs one user through one iteration of each task set, printing each response
+    #[options(no_short)]
+    pub debug_single_user: bool,
     // Add a blank line and then an Advanced: header after this option
     #[options(no_short, help = "Tracks additional status code metrics\n\nAdvanced:")]
     pub status_codes: bool,
@@ -4228,17 +10040,99 @@ pub struct SwanlingConfiguration {
     /// Doesn't automatically start load test
     #[options(no_short)]
     pub no_autostart: bool,
+    /// Waits to start load test until (ie 2024-06-01T02:00:00Z)
+    #[options(no_short, meta = "TIME")]
+    pub start_at: String,
     /// Sets coordinated omission mitigation strategy
     #[options(no_short, meta = "STRATEGY")]
     pub co_mitigation: Option<SwanlingCoordinatedOmissionMitigation>,
     /// Sets maximum requests per second
     #[options(no_short, meta = "VALUE")]
     pub throttle_requests: usize,
+    /// Changes maximum requests per second over time (ie 100:5m,500:10m,1000)
+    #[options(no_short, meta = "PLAN")]
+    pub throttle_plan: String,
+    /// Sets number of tokio worker threads (default: number of CPUs)
+    #[options(no_short, meta = "VALUE")]
+    pub runtime_threads: Option<usize>,
+    /// Seeds each user's random number generator
+    #[options(no_short, meta = "VALUE")]
+    pub seed: Option<u64>,
+    /// Requests uncompressed responses
+    #[options(no_short)]
+    pub no_gzip: bool,
+    /// Sets Accept-Encoding header sent with requests
+    #[options(no_short, meta = "VALUE")]
+    pub accept_encoding: String,
+    /// Picks each user's user agent from a file
+    #[options(no_short, meta = "NAME")]
+    pub user_agent_file: String,
+    /// Delays a percentage of requests (ie 50ms@1%)
+    #[options(no_short, meta = "VALUE")]
+    pub inject_latency: String,
+    /// Aborts a percentage of requests (ie 1%)
+    #[options(no_short, meta = "PERCENT")]
+    pub inject_abort: String,
+    /// Replays requests from a request log
+    #[options(no_short, meta = "NAME")]
+    pub replay_log: String,
+    /// Sets replay speed factor (default: 1.0)
+    #[options(no_short, meta = "FACTOR")]
+    pub replay_speed: Option<String>,
+    /// Resolves hostnames with a DNS server (ie 10.0.0.53:53)
+    #[options(no_short, meta = "ADDR")]
+    pub dns_server: String,
+    /// Resolves hostnames for every request
+    #[options(no_short)]
+    pub no_dns_cache: bool,
+    /// Resolves hostnames again every VALUE requests per user
+    #[options(no_short, meta = "VALUE")]
+    pub dns_refresh: usize,
+    /// Requests a path from each host before starting, ie /healthz
+    #[options(no_short, meta = "PATH")]
+    pub healthcheck: String,
+    /// How often to check hosts while running (default: 10)
+    #[options(no_short, meta = "TIME")]
+    pub healthcheck_interval: Option<usize>,
+    /// Pauses the load test while all hosts fail the health check
+    #[options(no_short)]
+    pub healthcheck_pause: bool,
+    /// Stops when too many requests fail in a window (ie 25%@30s)
+    #[options(no_short, meta = "RATE")]
+    pub abort_on_error_rate: String,
+    /// Warns when p99 response time stays too high (ie 500ms@30s)
+    #[options(no_short, meta = "BOUND")]
+    pub latency_alert: String,
+    /// Retires and replaces running users (ie 5/min)
+    #[options(no_short, meta = "RATE")]
+    pub user_churn: String,
+    /// Sets local IP address to send requests from (repeatable)
+    #[options(no_short, meta = "IP")]
+    pub bind_address: Vec<String>,
+    /// Sets a value tasks can read with user.config_value (ie tenant=42)
+    #[options(no_short, meta = "KEY=VALUE")]
+    pub set: Vec<String>,
+    /// Saves users' cookies when stopping and restores them when starting
+    #[options(no_short, meta = "NAME")]
+    pub save_sessions: String,
+    /// Follows base_url redirect with subsequent requests
+    #[options(no_short)]
+    pub sticky_follow: bool,
+    /// Pins each user to a backend with a cookie
+    #[options(no_short, meta = "NAME")]
+    pub sticky_cookie: String,
+    /// Pins each user to a backend with a header
+    #[options(no_short, meta = "NAME")]
+    pub sticky_header: String,
+    /// Sets comma-separated backends to pin users to
+    #[options(no_short, meta = "LIST")]
+    pub sticky_backends: String,
     #[options(
         no_short,
-        help = "Follows base_url redirect with subsequent requests\n\nGaggle:"
+        meta = "NAME",
+        help = "Counts backends serving requests from a response header\n\nGaggle:"
     )]
-    pub sticky_follow: bool,
+    pub backend_header: String,
 
     /// Enables distributed load test Manager mode
     #[options(no_short)]
@@ -4249,6 +10143,12 @@ pub struct SwanlingConfiguration {
     /// Tells Manager to ignore load test checksum
     #[options(no_short)]
     pub no_hash_check: bool,
+    /// Assigns users to Workers connecting after the load test started
+    #[options(no_short)]
+    pub hot_add_workers: bool,
+    /// Runs a Worker in the Manager process
+    #[options(no_short)]
+    pub manager_also_worker: bool,
     /// Sets host Manager listens on (default: 0.0.0.0)
     #[options(no_short, meta = "HOST")]
     pub manager_bind_host: String,
@@ -4264,6 +10164,340 @@ pub struct SwanlingConfiguration {
     /// Sets port Worker connects to (default: 5115)
     #[options(no_short, meta = "PORT")]
     pub manager_port: u16,
+    /// Sets index of this process in a fleet without a Manager
+    #[options(no_short, meta = "INDEX")]
+    pub fleet_index: Option<usize>,
+    /// Sets number of processes in a fleet without a Manager
+    #[options(no_short, meta = "VALUE")]
+    pub fleet_size: Option<usize>,
+}
+impl SwanlingConfiguration {
+    /// Build a configuration in code with typed setters, instead of parsing command line
+    /// arguments. Options that aren't set keep their default value.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    /// use swanling::SwanlingConfiguration;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let configuration = SwanlingConfiguration::builder()
+    ///         .host("http://local.dev/")
+    ///         .users(10)
+    ///         .run_time("5m")
+    ///         .no_telnet(true)
+    ///         .build();
+    ///     assert_eq!(configuration.users, Some(10));
+    ///
+    ///     let _swanling_attack = SwanlingAttack::initialize_with_config(configuration)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn builder() -> configuration_builder::SwanlingConfigurationBuilder {
+        configuration_builder::SwanlingConfigurationBuilder::default()
+    }
+
+    // Parse the command line arguments, returning an error instead of exiting the process if
+    // they're invalid.
+    fn from_args() -> Result<Self, SwanlingError> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        SwanlingConfiguration::parse_args_default(&args).map_err(|e| SwanlingError::InvalidOption {
+            option: "command line".to_string(),
+            value: args.join(" "),
+            detail: e.to_string(),
+        })
+    }
+
+    /// Whether the user with the given index across the whole fleet runs in this process.
+    /// Without `--fleet-size`, all users run in this process.
+    pub(crate) fn is_fleet_user(&self, index: usize) -> bool {
+        match (self.fleet_index, self.fleet_size) {
+            (Some(fleet_index), Some(fleet_size)) => index % fleet_size == fleet_index,
+            _ => true,
+        }
+    }
+
+    /// Convert the index of a user launched by this process into its index across the whole
+    /// fleet, so each user of a fleet has a unique index.
+    pub(crate) fn get_fleet_user_index(&self, index: usize) -> usize {
+        match (self.fleet_index, self.fleet_size) {
+            (Some(fleet_index), Some(fleet_size)) => index * fleet_size + fleet_index,
+            _ => index,
+        }
+    }
+
+    /// Select the `--bind-address` the user with the given index sends requests from,
+    /// round-robining users across all configured addresses.
+    pub(crate) fn get_bind_address(&self, index: usize) -> Option<std::net::IpAddr> {
+        if self.bind_address.is_empty() {
+            return None;
+        }
+        self.bind_address[index % self.bind_address.len()]
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// Parse the comma-separated `--host` into a list of hosts, each with the weight it
+    /// was optionally given with `=weight`. Returns `None` if any host or weight is invalid.
+    pub(crate) fn get_hosts(&self) -> Option<Vec<(&str, usize)>> {
+        self.host
+            .split(',')
+            .map(|h| h.trim())
+            .filter(|h| !h.is_empty())
+            .map(|h| {
+                let (host, weight) = match h.rsplit_once('=') {
+                    Some((host, weight)) => (host.trim(), weight.trim().parse().ok()?),
+                    None => (h, 1),
+                };
+                if weight == 0 || util::is_valid_host(host).is_err() {
+                    return None;
+                }
+                Some((host, weight))
+            })
+            .collect()
+    }
+
+    /// Select the `--host` the user with the given index load tests, distributing users
+    /// across all configured hosts in proportion to their weights.
+    pub(crate) fn get_host(&self, index: usize) -> Option<String> {
+        let hosts = self.get_hosts()?;
+        let total: usize = hosts.iter().map(|(_, weight)| weight).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut slot = index % total;
+        for (host, weight) in hosts {
+            if slot < weight {
+                return Some(host.to_string());
+            }
+            slot -= weight;
+        }
+        None
+    }
+
+    /// Split the comma-separated `--sticky-backends` into a list of backends.
+    pub(crate) fn get_sticky_backends(&self) -> Vec<&str> {
+        self.sticky_backends
+            .split(',')
+            .map(|b| b.trim())
+            .filter(|b| !b.is_empty())
+            .collect()
+    }
+
+    /// Parse the comma-separated `--percentiles` into a list of percentiles, or the
+    /// default percentiles if not set. Returns `None` if any percentile is invalid.
+    pub(crate) fn get_percentiles(&self) -> Option<Vec<f64>> {
+        if self.percentiles.trim().is_empty() {
+            return Some(metrics::DEFAULT_PERCENTILES.to_vec());
+        }
+        self.percentiles
+            .split(',')
+            .map(|p| util::parse_percentage(p).filter(|p| *p > 0.0))
+            .collect()
+    }
+
+    /// Parse `--abort-on-error-rate`, such as `25%@30s`, into the percentage of requests
+    /// that can fail and the window in seconds it's measured over. Returns `None` if not set
+    /// or invalid.
+    pub(crate) fn get_abort_on_error_rate(&self) -> Option<(f64, usize)> {
+        let (rate, window) = self.abort_on_error_rate.split_once('@')?;
+        let rate = util::parse_percentage(rate)?;
+        let window = util::parse_timespan(window.trim());
+        if window == 0 {
+            return None;
+        }
+        Some((rate, window))
+    }
+
+    /// Parse `--latency-alert`, such as `500ms@30s`, into the 99th percentile response time
+    /// requests can take in milliseconds and how many seconds it can be exceeded. Returns
+    /// `None` if not set or invalid.
+    pub(crate) fn get_latency_alert(&self) -> Option<(usize, usize)> {
+        let (bound, window) = self.latency_alert.split_once('@')?;
+        let bound = bound.trim();
+        let bound = bound
+            .strip_suffix("ms")
+            .unwrap_or(bound)
+            .parse::<usize>()
+            .ok()?;
+        let window = util::parse_timespan(window.trim());
+        if window == 0 {
+            return None;
+        }
+        Some((bound, window))
+    }
+
+    /// Parse `--user-churn` into how many users to retire per second, from a number of
+    /// users and a time such as `5/min`, `1/30s` or `100/h`.
+    pub(crate) fn get_user_churn(&self) -> Option<f64> {
+        let (users, time) = self.user_churn.split_once('/')?;
+        let users = users
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|users| users.is_finite() && *users > 0.0)?;
+        let seconds = match time.trim() {
+            "s" | "sec" => 1,
+            "m" | "min" => 60,
+            "h" | "hour" => 3_600,
+            time => util::parse_timespan(time),
+        };
+        if seconds == 0 {
+            return None;
+        }
+        Some(users / seconds as f64)
+    }
+
+    /// Parse the comma-separated `--throttle-plan` into steps of a maximum number of requests
+    /// per second and how many seconds it lasts. Only the last step can omit its time, to
+    /// last until the load test stops. Returns `None` if any step is invalid.
+    pub(crate) fn get_throttle_plan(&self) -> Option<Vec<(usize, usize)>> {
+        let steps: Vec<&str> = self.throttle_plan.split(',').map(str::trim).collect();
+        let last = steps.len() - 1;
+        steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| {
+                let (requests, time) = match step.split_once(':') {
+                    Some((requests, time)) => (requests, util::parse_timespan(time.trim())),
+                    None if index == last => (*step, 0),
+                    None => return None,
+                };
+                let requests = requests
+                    .trim()
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|requests| (1..=1_000_000).contains(requests))?;
+                if time == 0 && index != last {
+                    return None;
+                }
+                Some((requests, time))
+            })
+            .collect()
+    }
+
+    /// Parse the comma-separated `--histogram-buckets` into a list of bucket boundaries in
+    /// milliseconds, or the default boundaries if not set. Returns `None` if any boundary
+    /// is invalid or the boundaries don't increase.
+    pub(crate) fn get_histogram_buckets(&self) -> Option<Vec<usize>> {
+        if self.histogram_buckets.trim().is_empty() {
+            return Some(report::DEFAULT_HISTOGRAM_BUCKETS.to_vec());
+        }
+        let buckets = self
+            .histogram_buckets
+            .split(',')
+            .map(|b| b.trim().parse().ok().filter(|b| *b > 0))
+            .collect::<Option<Vec<usize>>>()?;
+        if buckets.windows(2).all(|w| w[0] < w[1]) {
+            Some(buckets)
+        } else {
+            None
+        }
+    }
+
+    /// Parse `--inject-latency` into a delay in milliseconds and a percentage of requests.
+    pub(crate) fn get_inject_latency(&self) -> Option<(u64, f64)> {
+        let mut split = self.inject_latency.splitn(2, '@');
+        let delay = util::parse_milliseconds(split.next()?.trim())?;
+        let percentage = util::parse_percentage(split.next()?)?;
+        Some((delay, percentage))
+    }
+
+    /// Parse `--inject-abort` into a percentage of requests.
+    pub(crate) fn get_inject_abort(&self) -> Option<f64> {
+        util::parse_percentage(&self.inject_abort)
+    }
+
+    /// Parse `--dns-server` into the address of a DNS server, which listens on port 53
+    /// unless another port is specified.
+    pub(crate) fn get_dns_server(&self) -> Option<std::net::SocketAddr> {
+        let server = self.dns_server.trim();
+        server.parse().ok().or_else(|| {
+            server
+                .parse::<std::net::IpAddr>()
+                .ok()
+                .map(|ip| std::net::SocketAddr::new(ip, 53))
+        })
+    }
+
+    /// Parse `--start-at` into the time to start the load test.
+    pub(crate) fn get_start_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(self.start_at.trim())
+            .ok()
+            .map(|start_at| start_at.with_timezone(&Utc))
+    }
+
+    /// Find the value of `key` set with `--set`. If the same key is set more than once,
+    /// the last value wins.
+    pub(crate) fn get_config_value(&self, key: &str) -> Option<&str> {
+        self.set
+            .iter()
+            .rev()
+            .filter_map(|s| s.split_once('='))
+            .find(|(k, _)| k.trim() == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Collect the metadata added to reports with `--label`. If the same key is set more
+    /// than once, the last value wins.
+    pub(crate) fn get_labels(&self) -> BTreeMap<String, String> {
+        self.label
+            .iter()
+            .filter_map(|l| l.split_once('='))
+            .map(|(k, v)| (k.trim().to_string(), v.to_string()))
+            .collect()
+    }
+
+    /// Change a single option, parsing the value as if it was passed on the command line.
+    /// Options are named like their field, or like their command line flag. Flags are set
+    /// with `true` or `false`. Options that only take effect when Swanling launches can't
+    /// be changed.
+    pub(crate) fn set_option(&mut self, option: &str, value: &str) -> Result<(), SwanlingError> {
+        let field = option.trim_start_matches('-').replace('-', "_");
+        let flag = format!("--{}", field.replace('_', "-"));
+        let invalid_option = |detail: String| SwanlingError::InvalidOption {
+            option: flag.clone(),
+            value: value.to_string(),
+            detail,
+        };
+
+        if STARTUP_OPTIONS.contains(&field.as_str()) {
+            return Err(invalid_option(format!(
+                "{} can only be set when launching the load test.",
+                flag
+            )));
+        }
+
+        let mut configuration = serde_json::to_value(&*self)
+            .map_err(|e| invalid_option(format!("Failed to serialize configuration: {}", e)))?;
+        let arguments = match configuration.get(&field) {
+            Some(serde_json::Value::Bool(_)) => match value {
+                "true" => vec![flag.as_str()],
+                "false" => Vec::new(),
+                _ => {
+                    return Err(invalid_option(format!(
+                        "{} must be set to true or false.",
+                        flag
+                    )))
+                }
+            },
+            Some(_) => vec![flag.as_str(), value],
+            None => return Err(invalid_option(format!("{} is not an option.", flag))),
+        };
+
+        // Parse the value, and copy it into the current configuration.
+        let parsed = SwanlingConfiguration::parse_args_default(&arguments)
+            .map_err(|e| invalid_option(e.to_string()))?;
+        let parsed = serde_json::to_value(&parsed)
+            .map_err(|e| invalid_option(format!("Failed to serialize configuration: {}", e)))?;
+        configuration[&field] = parsed[&field].clone();
+        *self = serde_json::from_value(configuration)
+            .map_err(|e| invalid_option(format!("Failed to deserialize configuration: {}", e)))?;
+
+        Ok(())
+    }
 }
 
 /// Use the configured SwanlingScheduler to allocate all [`SwanlingTask`](./swanling/struct.SwanlingTask.html)s
@@ -4318,7 +10552,8 @@ fn allocate_tasks(
                     sequenced_on_stop_tasks.insert(task.sequence, vec![task.clone()]);
                 }
             }
-            if !task.on_start && !task.on_stop {
+            // Scheduled tasks run on their own timer, not with the weighted tasks.
+            if !task.on_start && !task.on_stop && task.schedule.is_none() {
                 if let Some(sequence) = sequenced_tasks.get_mut(&task.sequence) {
                     // This is another task with this order value.
                     sequence.push(task.clone());
@@ -4334,7 +10569,8 @@ fn allocate_tasks(
             if task.on_stop {
                 unsequenced_on_stop_tasks.push(task.clone());
             }
-            if !task.on_start && !task.on_stop {
+            // Scheduled tasks run on their own timer, not with the weighted tasks.
+            if !task.on_start && !task.on_stop && task.schedule.is_none() {
                 unsequenced_tasks.push(task.clone());
             }
         }
@@ -4469,7 +10705,9 @@ fn schedule_sequenced_tasks(
     let mut weighted_tasks: Vec<usize> = Vec::new();
 
     for (_sequence, tasks) in available_sequenced_tasks.iter() {
-        let scheduled_tasks = schedule_unsequenced_tasks(tasks, tasks[0].len(), scheduler);
+        // Include all weighted instances of all tasks in this sequence.
+        let total_tasks = tasks.iter().map(|t| t.len()).sum();
+        let scheduled_tasks = schedule_unsequenced_tasks(tasks, total_tasks, scheduler);
         weighted_tasks.extend(scheduled_tasks);
     }
 
@@ -4537,6 +10775,8 @@ mod test {
         let users: usize = 10;
         let run_time: usize = 10;
         let hatch_rate = "2".to_string();
+        let hatch_batch: usize = 5;
+        let hatch_jitter = "200ms".to_string();
         let log_level: usize = 1;
         let swanling_log = "custom-swanling.log".to_string();
         let verbose: usize = 0;
@@ -4546,11 +10786,48 @@ mod test {
         let debug_log = "custom-swanling-debug.log".to_string();
         let error_log = "custom-swanling-error.log".to_string();
         let throttle_requests: usize = 25;
+        let runtime_threads: usize = 2;
+        let seed: usize = 42;
         let expect_workers: usize = 5;
         let manager_bind_host = "127.0.0.1".to_string();
         let manager_bind_port: usize = 1221;
         let manager_host = "127.0.0.1".to_string();
         let manager_port: usize = 1221;
+        let fleet_index: usize = 1;
+        let fleet_size: usize = 3;
+        let replay_log = "custom-swanling-replay.log".to_string();
+        let replay_speed = "2".to_string();
+        let percentiles = "50,90,99.9".to_string();
+        let histogram_buckets = "10,100,1000".to_string();
+        let upload_url = "s3://bucket/prefix".to_string();
+        let notify_url = "https://hooks.example.com/swanling".to_string();
+        let upload_interval: usize = 30;
+        let sticky_cookie = "SERVERID".to_string();
+        let sticky_header = "x-backend".to_string();
+        let sticky_backends = "web1,web2".to_string();
+        let backend_header = "x-served-by".to_string();
+        let accept_encoding = "gzip, br".to_string();
+        let user_agent_file = "user-agents.txt".to_string();
+        let inject_latency = "50ms@1%".to_string();
+        let inject_abort = "1%".to_string();
+        let dns_server = "10.0.0.53:53".to_string();
+        let dns_refresh: usize = 100;
+        let healthcheck = "/healthz".to_string();
+        let healthcheck_interval: usize = 30;
+        let abort_on_error_rate = "25%@30s".to_string();
+        let latency_alert = "500ms@30s".to_string();
+        let shutdown_timeout = "60s".to_string();
+        let user_churn = "5/min".to_string();
+        let throttle_plan = "100:5m,500".to_string();
+        let save_sessions = "custom-swanling-sessions.json".to_string();
+        let max_request_names: usize = 1000;
+        let debug_body_max_bytes: usize = 1024;
+        let debug_redact = "Bearer [A-Za-z0-9]+".to_string();
+        let error_log_window = "1s".to_string();
+        let start_at = "2024-06-01T02:00:00Z".to_string();
+        let report_interval = "5m".to_string();
+        let metrics_flush = "250ms".to_string();
+        let raw_latencies = "custom-swanling-raw-latencies.csv".to_string();
 
         let swanling_attack = SwanlingAttack::initialize()
             .unwrap()
@@ -4562,6 +10839,10 @@ mod test {
             .unwrap()
             .set_default(SwanlingDefault::HatchRate, hatch_rate.as_str())
             .unwrap()
+            .set_default(SwanlingDefault::HatchBatch, hatch_batch)
+            .unwrap()
+            .set_default(SwanlingDefault::HatchJitter, hatch_jitter.as_str())
+            .unwrap()
             .set_default(SwanlingDefault::LogLevel, log_level)
             .unwrap()
             .set_default(SwanlingDefault::SwanlingLog, swanling_log.as_str())
@@ -4590,6 +10871,31 @@ mod test {
             .unwrap()
             .set_default(SwanlingDefault::RequestFormat, SwanlingLogFormat::Raw)
             .unwrap()
+            .set_default(
+                SwanlingDefault::RunningMetricsFormat,
+                SwanlingMetricsFormat::Json,
+            )
+            .unwrap()
+            .set_default(SwanlingDefault::SummaryOnly, true)
+            .unwrap()
+            .set_default(
+                SwanlingDefault::SummaryFormat,
+                SwanlingMetricsFormat::Compact,
+            )
+            .unwrap()
+            .set_default(SwanlingDefault::Percentiles, percentiles.as_str())
+            .unwrap()
+            .set_default(
+                SwanlingDefault::HistogramBuckets,
+                histogram_buckets.as_str(),
+            )
+            .unwrap()
+            .set_default(SwanlingDefault::UploadUrl, upload_url.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::NotifyUrl, notify_url.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::UploadInterval, upload_interval)
+            .unwrap()
             .set_default(SwanlingDefault::TaskLog, task_log.as_str())
             .unwrap()
             .set_default(SwanlingDefault::TaskFormat, SwanlingLogFormat::Raw)
@@ -4598,12 +10904,22 @@ mod test {
             .unwrap()
             .set_default(SwanlingDefault::ErrorFormat, SwanlingLogFormat::Csv)
             .unwrap()
+            .set_default(SwanlingDefault::ErrorLogWindow, error_log_window.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::StartAt, start_at.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::ReportInterval, report_interval.as_str())
+            .unwrap()
             .set_default(SwanlingDefault::DebugLog, debug_log.as_str())
             .unwrap()
             .set_default(SwanlingDefault::DebugFormat, SwanlingLogFormat::Csv)
             .unwrap()
             .set_default(SwanlingDefault::NoDebugBody, true)
             .unwrap()
+            .set_default(SwanlingDefault::DebugBodyMaxBytes, debug_body_max_bytes)
+            .unwrap()
+            .set_default(SwanlingDefault::DebugRedact, debug_redact.as_str())
+            .unwrap()
             .set_default(SwanlingDefault::StatusCodes, true)
             .unwrap()
             .set_default(
@@ -4613,14 +10929,75 @@ mod test {
             .unwrap()
             .set_default(SwanlingDefault::ThrottleRequests, throttle_requests)
             .unwrap()
+            .set_default(SwanlingDefault::RuntimeThreads, runtime_threads)
+            .unwrap()
+            .set_default(SwanlingDefault::Seed, seed)
+            .unwrap()
+            .set_default(SwanlingDefault::NoGzip, true)
+            .unwrap()
+            .set_default(SwanlingDefault::AcceptEncoding, accept_encoding.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::UserAgentFile, user_agent_file.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::InjectLatency, inject_latency.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::InjectAbort, inject_abort.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::DnsServer, dns_server.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::NoDnsCache, true)
+            .unwrap()
+            .set_default(SwanlingDefault::DnsRefresh, dns_refresh)
+            .unwrap()
+            .set_default(SwanlingDefault::Healthcheck, healthcheck.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::HealthcheckInterval, healthcheck_interval)
+            .unwrap()
+            .set_default(SwanlingDefault::HealthcheckPause, true)
+            .unwrap()
+            .set_default(SwanlingDefault::RunHeader, true)
+            .unwrap()
+            .set_default(SwanlingDefault::TaskHeaders, true)
+            .unwrap()
+            .set_default(
+                SwanlingDefault::AbortOnErrorRate,
+                abort_on_error_rate.as_str(),
+            )
+            .unwrap()
+            .set_default(SwanlingDefault::LatencyAlert, latency_alert.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::ShutdownTimeout, shutdown_timeout.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::UserChurn, user_churn.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::ThrottlePlan, throttle_plan.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::SaveSessions, save_sessions.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::MaxRequestNames, max_request_names)
+            .unwrap()
+            .set_default(SwanlingDefault::MetricsFlush, metrics_flush.as_str())
+            .unwrap()
             .set_default(SwanlingDefault::StickyFollow, true)
             .unwrap()
+            .set_default(SwanlingDefault::StickyCookie, sticky_cookie.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::StickyHeader, sticky_header.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::StickyBackends, sticky_backends.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::BackendHeader, backend_header.as_str())
+            .unwrap()
             .set_default(SwanlingDefault::Manager, true)
             .unwrap()
             .set_default(SwanlingDefault::ExpectWorkers, expect_workers)
             .unwrap()
             .set_default(SwanlingDefault::NoHashCheck, true)
             .unwrap()
+            .set_default(SwanlingDefault::HotAddWorkers, true)
+            .unwrap()
+            .set_default(SwanlingDefault::ManagerAlsoWorker, true)
+            .unwrap()
             .set_default(SwanlingDefault::ManagerBindHost, manager_bind_host.as_str())
             .unwrap()
             .set_default(SwanlingDefault::ManagerBindPort, manager_bind_port)
@@ -4630,15 +11007,29 @@ mod test {
             .set_default(SwanlingDefault::ManagerHost, manager_host.as_str())
             .unwrap()
             .set_default(SwanlingDefault::ManagerPort, manager_port)
+            .unwrap()
+            .set_default(SwanlingDefault::FleetIndex, fleet_index)
+            .unwrap()
+            .set_default(SwanlingDefault::FleetSize, fleet_size)
+            .unwrap()
+            .set_default(SwanlingDefault::ReplayLog, replay_log.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::ReplaySpeed, replay_speed.as_str())
+            .unwrap()
+            .set_default(SwanlingDefault::RecordRawLatencies, raw_latencies.as_str())
             .unwrap();
 
         assert!(swanling_attack.defaults.host == Some(host));
         assert!(swanling_attack.defaults.users == Some(users));
         assert!(swanling_attack.defaults.run_time == Some(run_time));
         assert!(swanling_attack.defaults.hatch_rate == Some(hatch_rate));
+        assert!(swanling_attack.defaults.hatch_batch == Some(hatch_batch));
+        assert!(swanling_attack.defaults.hatch_jitter == Some(hatch_jitter));
         assert!(swanling_attack.defaults.log_level == Some(log_level as u8));
         assert!(swanling_attack.defaults.swanling_log == Some(swanling_log));
         assert!(swanling_attack.defaults.no_debug_body == Some(true));
+        assert!(swanling_attack.defaults.debug_body_max_bytes == Some(debug_body_max_bytes));
+        assert!(swanling_attack.defaults.debug_redact == vec![debug_redact]);
         assert!(swanling_attack.defaults.verbose == Some(verbose as u8));
         assert!(swanling_attack.defaults.running_metrics == Some(15));
         assert!(swanling_attack.defaults.no_reset_metrics == Some(true));
@@ -4651,8 +11042,21 @@ mod test {
         assert!(swanling_attack.defaults.report_file == Some(report_file));
         assert!(swanling_attack.defaults.request_log == Some(request_log));
         assert!(swanling_attack.defaults.request_format == Some(SwanlingLogFormat::Raw));
+        assert!(
+            swanling_attack.defaults.running_metrics_format == Some(SwanlingMetricsFormat::Json)
+        );
+        assert!(swanling_attack.defaults.summary_only == Some(true));
+        assert!(swanling_attack.defaults.summary_format == Some(SwanlingMetricsFormat::Compact));
+        assert!(swanling_attack.defaults.percentiles == Some(percentiles));
+        assert!(swanling_attack.defaults.histogram_buckets == Some(histogram_buckets));
+        assert!(swanling_attack.defaults.upload_url == Some(upload_url));
+        assert!(swanling_attack.defaults.notify_url == Some(notify_url));
+        assert!(swanling_attack.defaults.upload_interval == Some(upload_interval));
         assert!(swanling_attack.defaults.error_log == Some(error_log));
         assert!(swanling_attack.defaults.error_format == Some(SwanlingLogFormat::Csv));
+        assert!(swanling_attack.defaults.error_log_window == Some(error_log_window));
+        assert!(swanling_attack.defaults.start_at == Some(start_at));
+        assert!(swanling_attack.defaults.report_interval == Some(report_interval));
         assert!(swanling_attack.defaults.debug_log == Some(debug_log));
         assert!(swanling_attack.defaults.debug_format == Some(SwanlingLogFormat::Csv));
         assert!(swanling_attack.defaults.status_codes == Some(true));
@@ -4661,14 +11065,48 @@ mod test {
                 == Some(SwanlingCoordinatedOmissionMitigation::Disabled)
         );
         assert!(swanling_attack.defaults.throttle_requests == Some(throttle_requests));
+        assert!(swanling_attack.defaults.runtime_threads == Some(runtime_threads));
+        assert!(swanling_attack.defaults.seed == Some(seed as u64));
+        assert!(swanling_attack.defaults.no_gzip == Some(true));
+        assert!(swanling_attack.defaults.accept_encoding == Some(accept_encoding));
+        assert!(swanling_attack.defaults.user_agent_file == Some(user_agent_file));
+        assert!(swanling_attack.defaults.inject_latency == Some(inject_latency));
+        assert!(swanling_attack.defaults.inject_abort == Some(inject_abort));
+        assert!(swanling_attack.defaults.dns_server == Some(dns_server));
+        assert!(swanling_attack.defaults.no_dns_cache == Some(true));
+        assert!(swanling_attack.defaults.dns_refresh == Some(dns_refresh));
+        assert!(swanling_attack.defaults.healthcheck == Some(healthcheck));
+        assert!(swanling_attack.defaults.healthcheck_interval == Some(healthcheck_interval));
+        assert!(swanling_attack.defaults.healthcheck_pause == Some(true));
+        assert!(swanling_attack.defaults.run_header == Some(true));
+        assert!(swanling_attack.defaults.task_headers == Some(true));
+        assert!(swanling_attack.defaults.abort_on_error_rate == Some(abort_on_error_rate));
+        assert!(swanling_attack.defaults.latency_alert == Some(latency_alert));
+        assert!(swanling_attack.defaults.shutdown_timeout == Some(shutdown_timeout));
+        assert!(swanling_attack.defaults.user_churn == Some(user_churn));
+        assert!(swanling_attack.defaults.throttle_plan == Some(throttle_plan));
+        assert!(swanling_attack.defaults.save_sessions == Some(save_sessions));
+        assert!(swanling_attack.defaults.max_request_names == Some(max_request_names));
+        assert!(swanling_attack.defaults.metrics_flush == Some(metrics_flush));
         assert!(swanling_attack.defaults.sticky_follow == Some(true));
+        assert!(swanling_attack.defaults.sticky_cookie == Some(sticky_cookie));
+        assert!(swanling_attack.defaults.sticky_header == Some(sticky_header));
+        assert!(swanling_attack.defaults.sticky_backends == Some(sticky_backends));
+        assert!(swanling_attack.defaults.backend_header == Some(backend_header));
         assert!(swanling_attack.defaults.manager == Some(true));
         assert!(swanling_attack.defaults.expect_workers == Some(expect_workers as u16));
         assert!(swanling_attack.defaults.no_hash_check == Some(true));
+        assert!(swanling_attack.defaults.hot_add_workers == Some(true));
+        assert!(swanling_attack.defaults.manager_also_worker == Some(true));
         assert!(swanling_attack.defaults.manager_bind_host == Some(manager_bind_host));
         assert!(swanling_attack.defaults.manager_bind_port == Some(manager_bind_port as u16));
         assert!(swanling_attack.defaults.worker == Some(true));
         assert!(swanling_attack.defaults.manager_host == Some(manager_host));
         assert!(swanling_attack.defaults.manager_port == Some(manager_port as u16));
+        assert!(swanling_attack.defaults.fleet_index == Some(fleet_index));
+        assert!(swanling_attack.defaults.fleet_size == Some(fleet_size));
+        assert!(swanling_attack.defaults.replay_log == Some(replay_log));
+        assert!(swanling_attack.defaults.replay_speed == Some(replay_speed));
+        assert!(swanling_attack.defaults.record_raw_latencies == Some(raw_latencies));
     }
 }
@@ -0,0 +1,112 @@
+//! Check the operating system allows enough connections before launching users.
+//!
+//! Each [`SwanlingUser`](../swanling/struct.SwanlingUser.html) holds at least one open
+//! connection to the target, and each connection requires a file descriptor and an ephemeral
+//! port. When a load test needs more than the operating system allows, requests fail with
+//! cryptic connection errors partway through the load test. Instead, before launching users
+//! Swanling compares the number of users against the open file limit (`ulimit -n`), failing
+//! with guidance if the limit is too low, and against the range of ephemeral ports, warning
+//! if the ports may run out. The limits are only checked on Linux.
+
+use crate::SwanlingError;
+
+/// How many file descriptors to reserve for Swanling itself, for example for log files and
+/// Controller connections.
+const RESERVED_FILES: usize = 64;
+
+/// Confirm the operating system allows enough connections for the users being launched.
+pub(crate) fn check_limits(users: usize) -> Result<(), SwanlingError> {
+    if let Some(limit) = open_file_limit() {
+        let required = users + RESERVED_FILES;
+        if limit < required {
+            return Err(SwanlingError::InvalidOption {
+                option: "--users".to_string(),
+                value: users.to_string(),
+                detail: format!(
+                    "--users {} requires at least {} open files, but the open file limit is {}: raise it with `ulimit -n {}`, or launch fewer users.",
+                    users, required, limit, required
+                ),
+            });
+        }
+    }
+
+    if let Some((first, last)) = ephemeral_port_range() {
+        let ports = (last + 1).saturating_sub(first);
+        if users > ports {
+            warn!(
+                "{} users may run out of the {} ephemeral ports ({}-{}) when connecting to one host, causing connection errors: widen the range with `sysctl net.ipv4.ip_local_port_range`, or launch fewer users",
+                users, ports, first, last
+            );
+        }
+    }
+
+    Ok(())
+}
+
+// The soft limit on open files, or None if it can't be determined or is unlimited.
+#[cfg(target_os = "linux")]
+fn open_file_limit() -> Option<usize> {
+    parse_open_file_limit(&std::fs::read_to_string("/proc/self/limits").ok()?)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn open_file_limit() -> Option<usize> {
+    None
+}
+
+// The first and last ephemeral port, or None if they can't be determined.
+#[cfg(target_os = "linux")]
+fn ephemeral_port_range() -> Option<(usize, usize)> {
+    parse_port_range(&std::fs::read_to_string("/proc/sys/net/ipv4/ip_local_port_range").ok()?)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn ephemeral_port_range() -> Option<(usize, usize)> {
+    None
+}
+
+// Parse the soft limit from the "Max open files" line of /proc/self/limits.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_open_file_limit(limits: &str) -> Option<usize> {
+    let line = limits
+        .lines()
+        .find(|line| line.starts_with("Max open files"))?;
+    line.trim_start_matches("Max open files")
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+// Parse the first and last port from /proc/sys/net/ipv4/ip_local_port_range.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_port_range(range: &str) -> Option<(usize, usize)> {
+    let mut ports = range.split_whitespace().map(|port| port.parse().ok());
+    Some((ports.next()??, ports.next()??))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_file_limit() {
+        let limits = "Limit                     Soft Limit           Hard Limit           Units     \n\
+                      Max processes             63455                63455                processes \n\
+                      Max open files            1024                 524288               files     \n";
+        assert_eq!(parse_open_file_limit(limits), Some(1024));
+
+        let unlimited =
+            "Max open files            unlimited            unlimited            files     \n";
+        assert_eq!(parse_open_file_limit(unlimited), None);
+        assert_eq!(parse_open_file_limit(""), None);
+    }
+
+    #[test]
+    fn port_range() {
+        assert_eq!(parse_port_range("32768\t60999\n"), Some((32768, 60999)));
+        assert_eq!(parse_port_range("1024 65535"), Some((1024, 65535)));
+        assert_eq!(parse_port_range("32768"), None);
+        assert_eq!(parse_port_range("foo bar"), None);
+    }
+}
@@ -4,10 +4,17 @@
 //! the following run time options:
 //!  - `--debug-log`, `--request-log`, `--task-log`
 //!
-//! It's also possible to configure the format of any of thse logs to be `json`, `csv`, or `raw`
-//! (the standard debug output of a Rust structure), using the following run time optios:
+//! It's also possible to configure the format of any of thse logs to be `json`, `ndjson`,
+//! `pretty-json`, `csv`, or `raw` (the standard debug output of a Rust structure), using the
+//! following run time optios:
 //!  - `--debug-format`, `--request-format`, `--task-format`
 //!
+//! Any log with a name ending in `.gz` is gzip compressed as it's written.
+//!
+//! Raw latencies recorded with `--record-raw-latencies` are written in the `csv` format, or
+//! in the columnar `parquet` format if the file name ends in `.parquet` and the load test is
+//! compiled with the `parquet` feature.
+//!
 //! All of these loggers use a single shared logger thread, with
 //! [`SwanlingUser`](../swanling/struct.SwanlingUser.html)s sending log messages through the same shared
 //! channel. The logger determines which log file to write the message to based on the message
@@ -19,6 +26,17 @@
 //! generated by Swanling while running a load test. This functionality is not implemented in this
 //! file.
 //!
+//! ## Log Schema
+//! The request, task, error and debug logs have a versioned schema, see
+//! [`LOG_SCHEMA_VERSION`]. Logs written in the `csv` format start with a header row naming
+//! each column, and records written in any json format include a `schema_version` field.
+//! Every record also includes the `run_id` of the load test, so records written by different
+//! runs can be told apart.
+//!
+//! New fields can be added to the logs without changing the version, so parsers should look
+//! up `csv` columns by their name in the header row and ignore unknown `json` fields. When a
+//! field is removed, renamed or changes meaning, the version is increased.
+//!
 //! ## Request File logger
 //! The Swanling requests logger is enabled with the `--request-log` command-line option, or the
 //! [`SwanlingDefault::RequestLog`](../enum.SwanlingDefault.html#variant.RequestLog) default
@@ -41,6 +59,23 @@
 //! Each [`SwanlingTaskMetric`] object generated by all [`SwanlingUser`](../swanling/struct.SwanlingUser.html)
 //! threads during a load test is written to this log file.
 //!
+//! ## Error File logger
+//! The Swanling errors logger is enabled with the `--error-log` command-line option, or the
+//! [`SwanlingDefault::ErrorLog`](../enum.SwanlingDefault.html#variant.ErrorLog) default
+//! configuration option. The format of the log is configured with the `--error-format`
+//! command-line option, or the
+//! [`SwanlingDefault::ErrorFormat`](../enum.SwanlingDefault.html#variant.ErrorFormat) default
+//! configuration option.
+//!
+//! Each [`SwanlingErrorMetric`] object generated during a load test is written to this log
+//! file. A failing endpoint can generate thousands of identical errors per second, so with
+//! the `--error-log-window` command-line option, or the
+//! [`SwanlingDefault::ErrorLogWindow`](../enum.SwanlingDefault.html#variant.ErrorLogWindow)
+//! default configuration option, identical errors are aggregated for the length of the window
+//! and written as one record: the first error seen, with a `count` of how many times it was
+//! seen. Errors are identical when they have the same method, request name, status code,
+//! error text and request tags.
+//!
 //! ## Debug File logger
 //! The Swanling debug logger is enabled with the `--debug-log` command-line option, or the
 //! [`SwanlingDefault::DebugLog`](../enum.SwanlingDefault.html#variant.DebugLog) default
@@ -104,14 +139,14 @@
 //! [`log_debug`](../swanling/struct.SwanlingUser.html#method.log_debug)
 //! results in a debug log message similar to:
 //! ```json
-//! {"body":null,"header":null,"request":null,"tag":"POSTing [(\"field_1\", \"foo\"), (\"field_2\", \"bar\"), (\"op\", \"Save\")] on /path/to/form"}
+//! {"body":null,"header":null,"request":null,"run_id":"4e2b1c8a-7d3f-4b6e-9a51-0c8d2f6e3b17","schema_version":1,"tag":"POSTing [(\"field_1\", \"foo\"), (\"field_2\", \"bar\"), (\"op\", \"Save\")] on /path/to/form"}
 //! ```
 //!
 //! The second call to
 //! [`log_debug`](../swanling/struct.SwanlingUser.html#method.log_debug)
 //! results in a debug log message similar to:
 //! ```json
-//! {"body":null,"header":null,"request":{"elapsed":1,"final_url":"http://local.dev/path/to/form","method":"POST","name":"(Anon) post to form","redirected":false,"response_time":22,"status_code":404,"success":false,"update":false,"url":"http://local.dev/path/to/form","user":0},"tag":"POSTing [(\"field_1\", \"foo\"), (\"field_2\", \"bar\"), (\"op\", \"Save\")] on /path/to/form"}
+//! {"body":null,"header":null,"request":{"elapsed":1,"final_url":"http://local.dev/path/to/form","method":"POST","name":"(Anon) post to form","redirected":false,"response_time":22,"status_code":404,"success":false,"update":false,"url":"http://local.dev/path/to/form","user":0},"run_id":"4e2b1c8a-7d3f-4b6e-9a51-0c8d2f6e3b17","schema_version":1,"tag":"POSTing [(\"field_1\", \"foo\"), (\"field_2\", \"bar\"), (\"op\", \"Save\")] on /path/to/form"}
 //! ```
 //!
 //! For a more complex debug logging example, refer to the
@@ -131,16 +166,45 @@
 //! [`SwanlingDefault::NoDebugBody`](../enum.SwanlingDefault.html#variant.NoDebugBody) default
 //! configuration option. The debug logger will still record any custom messages, details
 //! about the request (when available), and all server response headers (when available).
+//!
+//! Alternatively, limit how much of each response body is logged with the
+//! `--debug-body-max-bytes` command-line option, or the
+//! [`SwanlingDefault::DebugBodyMaxBytes`](../enum.SwanlingDefault.html#variant.DebugBodyMaxBytes)
+//! default configuration option. Longer bodies are truncated, and end with a note of how many
+//! bytes were left out.
+//!
+//! ### Redacting Secrets
+//!
+//! Response headers and bodies can contain secrets, such as bearer tokens or session
+//! cookies. To share a debug log without leaking them, set the `--debug-redact` command-line
+//! option to a regular expression: all matches in headers and bodies are replaced with
+//! `[REDACTED]` before they are written to the debug log. The option can be set more than
+//! once, or with the
+//! [`SwanlingDefault::DebugRedact`](../enum.SwanlingDefault.html#variant.DebugRedact) default
+//! configuration option, for example:
+//!
+//! ```bash
+//! cargo run --example simple -- -H http://local.dev/ --debug-log debug.log --debug-redact "Bearer [A-Za-z0-9._~+/-]+=*"
+//! ```
 
-use regex::RegexSet;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use regex::{Regex, RegexSet};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
+use std::io::Write as _;
 use std::str::FromStr;
 use tokio::fs::File;
 use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::time::{Duration, Instant};
 
-use crate::metrics::{SwanlingErrorMetric, SwanlingRequestMetric, SwanlingTaskMetric};
+use crate::metrics::{
+    format_tags, SwanlingErrorMetric, SwanlingRawLatency, SwanlingRequestMetric,
+    SwanlingTaskMetric,
+};
 use crate::swanling::SwanlingDebug;
+use crate::util;
 use crate::{SwanlingConfiguration, SwanlingDefaults, SwanlingError};
 
 /// Optional unbounded receiver for logger thread, if debug logger is enabled.
@@ -155,15 +219,214 @@ pub(crate) type SwanlingLoggerTx = Option<flume::Sender<Option<SwanlingLog>>>;
 pub enum SwanlingLog {
     Debug(SwanlingDebug),
     Error(SwanlingErrorMetric),
+    RawLatency(SwanlingRawLatency),
     Request(SwanlingRequestMetric),
     Task(SwanlingTaskMetric),
 }
 
+/// A log file being written by the logger thread, gzip compressed if its name ends with `.gz`.
+pub(crate) struct SwanlingLogFile {
+    /// Buffered writes to the file.
+    writer: BufWriter<File>,
+    /// If compressing the log, the compressed data not yet written to the file.
+    encoder: Option<GzEncoder<Vec<u8>>>,
+}
+impl SwanlingLogFile {
+    fn new(path: &str, file: File, buffer_capacity: usize) -> Self {
+        SwanlingLogFile {
+            writer: BufWriter::with_capacity(buffer_capacity, file),
+            encoder: if path.ends_with(".gz") {
+                Some(GzEncoder::new(Vec::new(), Compression::default()))
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Write bytes to the log, compressing them if enabled.
+    async fn write(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        match self.encoder.as_mut() {
+            Some(encoder) => {
+                encoder.write_all(bytes)?;
+                let compressed = std::mem::take(encoder.get_mut());
+                self.writer.write_all(&compressed).await
+            }
+            None => self.writer.write_all(bytes).await,
+        }
+    }
+
+    /// Flush the log to disk, completing the compressed stream if enabled.
+    async fn flush(&mut self) -> std::io::Result<()> {
+        if let Some(encoder) = self.encoder.as_mut() {
+            encoder.try_finish()?;
+            let compressed = std::mem::take(encoder.get_mut());
+            self.writer.write_all(&compressed).await?;
+        }
+        self.writer.flush().await
+    }
+}
+
+/// Returns true if raw latencies are recorded in the parquet format.
+pub(crate) fn is_parquet(path: &str) -> bool {
+    path.to_lowercase().ends_with(".parquet")
+}
+
+/// How many raw latencies are buffered before they're written to the parquet file as a
+/// row group.
+#[cfg(feature = "parquet")]
+const PARQUET_ROW_GROUP_SIZE: usize = 64 * 1024;
+
+/// The columns of the parquet file raw latencies are recorded to, matching the csv header.
+#[cfg(feature = "parquet")]
+const RAW_LATENCIES_PARQUET_SCHEMA: &str = "
+    message raw_latency {
+        REQUIRED INT64 timestamp;
+        REQUIRED INT64 elapsed;
+        REQUIRED BYTE_ARRAY method (UTF8);
+        REQUIRED BYTE_ARRAY name (UTF8);
+        REQUIRED INT32 status_code;
+        REQUIRED INT64 response_time;
+        REQUIRED BOOLEAN success;
+        REQUIRED INT64 user;
+        REQUIRED BYTE_ARRAY run_id (UTF8);
+    }
+";
+
+/// Raw latencies recorded to a parquet file, buffered and written one row group at a time.
+///
+/// The parquet writer doesn't support async writes, but row groups are written rarely, so
+/// they're written to a blocking file.
+#[cfg(feature = "parquet")]
+pub(crate) struct SwanlingParquetLog {
+    writer: parquet::file::writer::SerializedFileWriter<std::io::BufWriter<std::fs::File>>,
+    /// Raw latencies not yet written to the file.
+    latencies: Vec<SwanlingRawLatency>,
+    /// Recorded with every raw latency, like the csv format.
+    run_id: String,
+}
+#[cfg(feature = "parquet")]
+impl SwanlingParquetLog {
+    fn create(path: &str, run_id: &str) -> parquet::errors::Result<Self> {
+        let schema = parquet::schema::parser::parse_message_type(RAW_LATENCIES_PARQUET_SCHEMA)?;
+        let properties = parquet::file::properties::WriterProperties::builder().build();
+        let file = std::fs::File::create(path)?;
+        Ok(SwanlingParquetLog {
+            writer: parquet::file::writer::SerializedFileWriter::new(
+                std::io::BufWriter::new(file),
+                std::sync::Arc::new(schema),
+                std::sync::Arc::new(properties),
+            )?,
+            latencies: Vec::new(),
+            run_id: run_id.to_string(),
+        })
+    }
+
+    /// Buffer a raw latency, writing a row group when enough are buffered.
+    fn write(&mut self, latency: SwanlingRawLatency) -> parquet::errors::Result<()> {
+        self.latencies.push(latency);
+        if self.latencies.len() >= PARQUET_ROW_GROUP_SIZE {
+            self.write_row_group()?;
+        }
+        Ok(())
+    }
+
+    /// Write the buffered raw latencies as a row group, one column at a time.
+    fn write_row_group(&mut self) -> parquet::errors::Result<()> {
+        use parquet::data_type::{BoolType, ByteArray, ByteArrayType, Int32Type, Int64Type};
+
+        if self.latencies.is_empty() {
+            return Ok(());
+        }
+        let latencies = std::mem::take(&mut self.latencies);
+        let int64 = |value: fn(&SwanlingRawLatency) -> i64| -> Vec<i64> {
+            latencies.iter().map(value).collect()
+        };
+        let strings = |value: fn(&SwanlingRawLatency) -> String| -> Vec<ByteArray> {
+            latencies
+                .iter()
+                .map(|l| ByteArray::from(value(l).as_str()))
+                .collect()
+        };
+
+        let mut row_group = self.writer.next_row_group()?;
+        let mut index = 0;
+        while let Some(mut column) = row_group.next_column()? {
+            // Columns are returned in the order of the schema.
+            match index {
+                0 => column.typed::<Int64Type>().write_batch(
+                    &int64(|l| l.timestamp as i64),
+                    None,
+                    None,
+                )?,
+                1 => column.typed::<Int64Type>().write_batch(
+                    &int64(|l| l.elapsed as i64),
+                    None,
+                    None,
+                )?,
+                2 => column.typed::<ByteArrayType>().write_batch(
+                    &strings(|l| l.method.to_string()),
+                    None,
+                    None,
+                )?,
+                3 => column.typed::<ByteArrayType>().write_batch(
+                    &strings(|l| l.name.clone()),
+                    None,
+                    None,
+                )?,
+                4 => column.typed::<Int32Type>().write_batch(
+                    &latencies
+                        .iter()
+                        .map(|l| l.status_code as i32)
+                        .collect::<Vec<_>>(),
+                    None,
+                    None,
+                )?,
+                5 => column.typed::<Int64Type>().write_batch(
+                    &int64(|l| l.response_time as i64),
+                    None,
+                    None,
+                )?,
+                6 => column.typed::<BoolType>().write_batch(
+                    &latencies.iter().map(|l| l.success).collect::<Vec<_>>(),
+                    None,
+                    None,
+                )?,
+                7 => column.typed::<Int64Type>().write_batch(
+                    &int64(|l| l.user as i64),
+                    None,
+                    None,
+                )?,
+                _ => column.typed::<ByteArrayType>().write_batch(
+                    &vec![ByteArray::from(self.run_id.as_str()); latencies.len()],
+                    None,
+                    None,
+                )?,
+            };
+            column.close()?;
+            index += 1;
+        }
+        row_group.close()?;
+
+        Ok(())
+    }
+
+    /// Write the remaining raw latencies and the parquet footer, completing the file.
+    fn close(mut self) -> parquet::errors::Result<()> {
+        self.write_row_group()?;
+        self.writer.close()?;
+        Ok(())
+    }
+}
+
 /// Defines the formats logs can be written to file.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SwanlingLogFormat {
     Csv,
     Json,
+    /// Newline delimited JSON, one compact JSON record per line, as also written by `Json`.
+    Ndjson,
+    /// Indented JSON records, for humans to read while debugging.
+    PrettyJson,
     Raw,
 }
 /// Allow setting log formats from the command line by impleenting [`FromStr`].
@@ -173,8 +436,14 @@ impl FromStr for SwanlingLogFormat {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // Use a [`RegexSet`] to match string representations of `SwanlingCoordinatedOmissionMitigation`,
         // returning the appropriate enum value. Also match a wide range of abbreviations and synonyms.
-        let log_format = RegexSet::new(&[r"(?i)^csv$", r"(?i)^(json|jsn)$", r"(?i)^raw$"])
-            .expect("failed to compile log_format RegexSet");
+        let log_format = RegexSet::new(&[
+            r"(?i)^csv$",
+            r"(?i)^(json|jsn)$",
+            r"(?i)^raw$",
+            r"(?i)^(ndjson|jsonl)$",
+            r"(?i)^(pretty-?json|pretty)$",
+        ])
+        .expect("failed to compile log_format RegexSet");
         let matches = log_format.matches(&s);
         if matches.matched(0) {
             Ok(SwanlingLogFormat::Csv)
@@ -182,27 +451,142 @@ impl FromStr for SwanlingLogFormat {
             Ok(SwanlingLogFormat::Json)
         } else if matches.matched(2) {
             Ok(SwanlingLogFormat::Raw)
+        } else if matches.matched(3) {
+            Ok(SwanlingLogFormat::Ndjson)
+        } else if matches.matched(4) {
+            Ok(SwanlingLogFormat::PrettyJson)
         } else {
             Err(SwanlingError::InvalidOption {
                 option: format!("SwanlingLogFormat::{:?}", s),
                 value: s.to_string(),
-                detail: "Invalid log_format, expected: csv, json, or raw".to_string(),
+                detail: "Invalid log_format, expected: csv, json, ndjson, pretty-json, or raw"
+                    .to_string(),
             })
         }
     }
 }
 
+/// The version of the schema of the request, task, error and debug logs.
+///
+/// Records written in any json format include it as their `schema_version` field. Adding
+/// fields doesn't change the version: new fields are added to `json` records, and new columns
+/// are appended to `csv` rows. The version is increased if a field is removed, renamed or
+/// changes meaning.
+pub const LOG_SCHEMA_VERSION: u32 = 1;
+
+// Serialize a log message as a JSON record, including the version of the log schema and
+// the ID of the run, optionally indented.
+fn json_record<T: Serialize>(message: &T, pretty: bool, run_id: &str) -> String {
+    let mut record = json!(message);
+    if let Some(fields) = record.as_object_mut() {
+        fields.insert("schema_version".to_string(), json!(LOG_SCHEMA_VERSION));
+        fields.insert("run_id".to_string(), json!(run_id));
+    }
+    if pretty {
+        format!("{:#}", record)
+    } else {
+        record.to_string()
+    }
+}
+
+// Identical errors aggregated by `--error-log-window` until the window closes.
+struct SwanlingErrorWindow {
+    // How long to aggregate identical errors for.
+    length: Duration,
+    // When the first error in the current window was seen.
+    started: Instant,
+    // The first of each identical error, in the order they were seen.
+    errors: Vec<SwanlingErrorMetric>,
+    // Indexes into `errors`, keyed by what makes errors identical.
+    index: HashMap<String, usize>,
+}
+impl SwanlingErrorWindow {
+    fn new(length: Duration) -> Self {
+        SwanlingErrorWindow {
+            length,
+            started: Instant::now(),
+            errors: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    // Count an error, starting a new window if none is open.
+    fn add(&mut self, error: SwanlingErrorMetric) {
+        if self.errors.is_empty() {
+            self.started = Instant::now();
+        }
+        let key = format!(
+            "{} {} {} {} {}",
+            error.method,
+            error.name,
+            error.status_code,
+            error.error,
+            format_tags(&error.tags)
+        );
+        match self.index.get(&key) {
+            Some(index) => self.errors[*index].count += error.count,
+            None => {
+                self.index.insert(key, self.errors.len());
+                self.errors.push(error);
+            }
+        }
+    }
+
+    // When the open window closes, or None if no window is open.
+    fn closes(&self) -> Option<Instant> {
+        if self.errors.is_empty() {
+            None
+        } else {
+            Some(self.started + self.length)
+        }
+    }
+
+    // Close the window, returning the aggregated errors.
+    fn close(&mut self) -> Vec<SwanlingErrorMetric> {
+        self.index.clear();
+        std::mem::take(&mut self.errors)
+    }
+}
+
+// Redact secrets from the headers and body of a debug message, and truncate the body to
+// `max_bytes` bytes if not 0.
+fn sanitize_debug(debug: &mut SwanlingDebug, redact: &[Regex], max_bytes: usize) {
+    for pattern in redact {
+        if let Some(header) = debug.header.as_mut() {
+            *header = pattern.replace_all(header, "[REDACTED]").to_string();
+        }
+        if let Some(body) = debug.body.as_mut() {
+            *body = pattern.replace_all(body, "[REDACTED]").to_string();
+        }
+    }
+    if let Some(body) = debug.body.as_mut() {
+        if max_bytes > 0 && body.len() > max_bytes {
+            // Don't split a multi-byte character.
+            let mut end = max_bytes;
+            while !body.is_char_boundary(end) {
+                end -= 1;
+            }
+            let truncated = body.len() - end;
+            body.truncate(end);
+            body.push_str(&format!("... [truncated {} bytes]", truncated));
+        }
+    }
+}
+
 // @TODO this should be automatically derived from the structure.
 fn debug_csv_header() -> String {
     // No quotes needed in header.
-    format!("{},{},{},{}", "tag", "request", "header", "body")
+    format!(
+        "{},{},{},{},{}",
+        "tag", "request", "header", "body", "run_id"
+    )
 }
 
 // @TODO this should be automatically derived from the structure.
 fn error_csv_header() -> String {
     // No quotes needed in header.
     format!(
-        "{},{},{},{},{},{},{},{},{},{}",
+        "{},{},{},{},{},{},{},{},{},{},{},{},{}",
         "elapsed",
         "method",
         "name",
@@ -213,6 +597,9 @@ fn error_csv_header() -> String {
         "status_code",
         "user",
         "error",
+        "tags",
+        "count",
+        "run_id",
     )
 }
 
@@ -220,7 +607,7 @@ fn error_csv_header() -> String {
 fn requests_csv_header() -> String {
     // No quotes needed in header.
     format!(
-        "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+        "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
         "elapsed",
         "method",
         "name",
@@ -235,6 +622,33 @@ fn requests_csv_header() -> String {
         "error",
         "coordinated_omission_elapsed",
         "user_cadence",
+        "backend",
+        "compressed",
+        "response_size",
+        "decoded_size",
+        "conditional",
+        "redirects",
+        "redirect_time",
+        "tags",
+        "user_agent",
+        "run_id",
+    )
+}
+
+// @TODO this should be automatically derived from the structure.
+fn raw_latencies_csv_header() -> String {
+    // No quotes needed in header.
+    format!(
+        "{},{},{},{},{},{},{},{},{}",
+        "timestamp",
+        "elapsed",
+        "method",
+        "name",
+        "status_code",
+        "response_time",
+        "success",
+        "user",
+        "run_id",
     )
 }
 
@@ -242,7 +656,7 @@ fn requests_csv_header() -> String {
 fn tasks_csv_header() -> String {
     format!(
         // No quotes needed in header.
-        "{},{},{},{},{},{},{}",
+        "{},{},{},{},{},{},{},{}",
         "elapsed",
         "taskset_index",
         "task_index",
@@ -250,6 +664,7 @@ fn tasks_csv_header() -> String {
         "run_time",
         "success",
         "user",
+        "run_id",
     )
 }
 
@@ -268,7 +683,10 @@ impl SwanlingLogger<SwanlingDebug> for SwanlingConfiguration {
         if let Some(debug_format) = self.debug_format.as_ref() {
             match debug_format {
                 // Use serde_json to create JSON.
-                SwanlingLogFormat::Json => json!(message).to_string(),
+                SwanlingLogFormat::Json | SwanlingLogFormat::Ndjson => {
+                    json_record(&message, false, &self.run_id)
+                }
+                SwanlingLogFormat::PrettyJson => json_record(&message, true, &self.run_id),
                 // Raw format is Debug output for SwanlingRawRequest structure.
                 SwanlingLogFormat::Raw => format!("{:?}", message),
                 // Not yet implemented.
@@ -285,8 +703,8 @@ impl SwanlingLogger<SwanlingDebug> for SwanlingConfiguration {
         // Put quotes around all fields, as they are all strings.
         // @TODO: properly handle Option<>; also, escape inner quotes etc.
         format!(
-            "\"{}\",\"{:?}\",\"{:?}\",\"{:?}\"",
-            debug.tag, debug.request, debug.header, debug.body
+            "\"{}\",\"{:?}\",\"{:?}\",\"{:?}\",\"{}\"",
+            debug.tag, debug.request, debug.header, debug.body, self.run_id
         )
     }
 }
@@ -297,7 +715,10 @@ impl SwanlingLogger<SwanlingErrorMetric> for SwanlingConfiguration {
         if let Some(error_format) = self.error_format.as_ref() {
             match error_format {
                 // Use serde_json to create JSON.
-                SwanlingLogFormat::Json => json!(message).to_string(),
+                SwanlingLogFormat::Json | SwanlingLogFormat::Ndjson => {
+                    json_record(&message, false, &self.run_id)
+                }
+                SwanlingLogFormat::PrettyJson => json_record(&message, true, &self.run_id),
                 // Raw format is Debug output for SwanlingErrorMetric structure.
                 SwanlingLogFormat::Raw => format!("{:?}", message),
                 // Not yet implemented.
@@ -312,8 +733,8 @@ impl SwanlingLogger<SwanlingErrorMetric> for SwanlingConfiguration {
     /// Converts a SwanlingErrorMetric structure to a CSV row.
     fn prepare_csv(&self, request: &SwanlingErrorMetric) -> String {
         format!(
-            // Put quotes around name, url, final_url and error as they are strings.
-            "{},{},\"{}\",\"{}\",\"{}\",{},{},{},{},\"{}\"",
+            // Put quotes around name, url, final_url, error, tags and run_id as they are strings.
+            "{},{},\"{}\",\"{}\",\"{}\",{},{},{},{},\"{}\",\"{}\",{},\"{}\"",
             request.elapsed,
             request.method,
             request.name,
@@ -324,6 +745,34 @@ impl SwanlingLogger<SwanlingErrorMetric> for SwanlingConfiguration {
             request.status_code,
             request.user,
             request.error,
+            format_tags(&request.tags),
+            request.count,
+            self.run_id,
+        )
+    }
+}
+/// Traits for SwanlingRawLatency logs.
+impl SwanlingLogger<SwanlingRawLatency> for SwanlingConfiguration {
+    /// Converts a SwanlingRawLatency structure to a formatted string.
+    fn format_message(&self, message: SwanlingRawLatency) -> String {
+        // Raw latencies are always recorded in csv format.
+        self.prepare_csv(&message)
+    }
+
+    /// Converts a SwanlingRawLatency structure to a CSV row.
+    fn prepare_csv(&self, latency: &SwanlingRawLatency) -> String {
+        format!(
+            // Put quotes around name and run_id as they are strings.
+            "{},{},{},\"{}\",{},{},{},{},\"{}\"",
+            latency.timestamp,
+            latency.elapsed,
+            latency.method,
+            latency.name,
+            latency.status_code,
+            latency.response_time,
+            latency.success,
+            latency.user,
+            self.run_id,
         )
     }
 }
@@ -334,7 +783,10 @@ impl SwanlingLogger<SwanlingRequestMetric> for SwanlingConfiguration {
         if let Some(request_format) = self.request_format.as_ref() {
             match request_format {
                 // Use serde_json to create JSON.
-                SwanlingLogFormat::Json => json!(message).to_string(),
+                SwanlingLogFormat::Json | SwanlingLogFormat::Ndjson => {
+                    json_record(&message, false, &self.run_id)
+                }
+                SwanlingLogFormat::PrettyJson => json_record(&message, true, &self.run_id),
                 // Raw format is Debug output for SwanlingRequestMetric structure.
                 SwanlingLogFormat::Raw => format!("{:?}", message),
                 // Not yet implemented.
@@ -349,8 +801,9 @@ impl SwanlingLogger<SwanlingRequestMetric> for SwanlingConfiguration {
     /// Converts a SwanlingRequestMetric structure to a CSV row.
     fn prepare_csv(&self, request: &SwanlingRequestMetric) -> String {
         format!(
-            // Put quotes around name, url and final_url as they are strings.
-            "{},{},\"{}\",\"{}\",\"{}\",{},{},{},{},{},{},{},{},{}",
+            // Put quotes around name, url, final_url, backend, tags, user_agent and run_id as
+            // they are strings.
+            "{},{},\"{}\",\"{}\",\"{}\",{},{},{},{},{},{},{},{},{},\"{}\",{},{},{},{},{},{},\"{}\",\"{}\",\"{}\"",
             request.elapsed,
             request.method,
             request.name,
@@ -365,6 +818,16 @@ impl SwanlingLogger<SwanlingRequestMetric> for SwanlingConfiguration {
             request.error,
             request.coordinated_omission_elapsed,
             request.user_cadence,
+            request.backend,
+            request.compressed,
+            request.response_size,
+            request.decoded_size,
+            request.conditional,
+            request.redirects,
+            request.redirect_time,
+            format_tags(&request.tags),
+            request.user_agent,
+            self.run_id,
         )
     }
 }
@@ -375,7 +838,10 @@ impl SwanlingLogger<SwanlingTaskMetric> for SwanlingConfiguration {
         if let Some(task_format) = self.task_format.as_ref() {
             match task_format {
                 // Use serde_json to create JSON.
-                SwanlingLogFormat::Json => json!(message).to_string(),
+                SwanlingLogFormat::Json | SwanlingLogFormat::Ndjson => {
+                    json_record(&message, false, &self.run_id)
+                }
+                SwanlingLogFormat::PrettyJson => json_record(&message, true, &self.run_id),
                 // Raw format is Debug output for SwanlingTaskMetric structure.
                 SwanlingLogFormat::Raw => format!("{:?}", message),
                 // Not yet implemented.
@@ -390,8 +856,8 @@ impl SwanlingLogger<SwanlingTaskMetric> for SwanlingConfiguration {
     /// Converts a SwanlingTaskMetric structure to a CSV row.
     fn prepare_csv(&self, request: &SwanlingTaskMetric) -> String {
         format!(
-            // Put quotes around name as it is a string.
-            "{},{},{},\"{}\",{},{},{}",
+            // Put quotes around name and run_id as they are strings.
+            "{},{},{},\"{}\",{},{},{},\"{}\"",
             request.elapsed,
             request.taskset_index,
             request.task_index,
@@ -399,6 +865,7 @@ impl SwanlingLogger<SwanlingTaskMetric> for SwanlingConfiguration {
             request.run_time,
             request.success,
             request.user,
+            self.run_id,
         )
     }
 }
@@ -441,6 +908,13 @@ impl SwanlingConfiguration {
                 self.task_log = default_task_log;
             }
         }
+        // Configure record_raw_latencies path if enabled.
+        if self.record_raw_latencies.is_empty() {
+            // Set default, if configured.
+            if let Some(default_record_raw_latencies) = defaults.record_raw_latencies.clone() {
+                self.record_raw_latencies = default_record_raw_latencies;
+            }
+        }
     }
 
     /// Spawns the logger thread if one or more loggers are enabled.
@@ -461,6 +935,7 @@ impl SwanlingConfiguration {
             && self.request_log.is_empty()
             && self.task_log.is_empty()
             && self.error_log.is_empty()
+            && self.record_raw_latencies.is_empty()
         {
             return Ok((None, None));
         }
@@ -477,19 +952,20 @@ impl SwanlingConfiguration {
     }
 
     /// A helper used to open any/all log files, deleting any file that already exists.
+    /// Logs with a name ending in `.gz` are gzip compressed.
     async fn open_log_file(
         &self,
         log_file_path: &str,
         log_file_type: &str,
         buffer_capacity: usize,
-    ) -> std::option::Option<tokio::io::BufWriter<tokio::fs::File>> {
+    ) -> std::option::Option<SwanlingLogFile> {
         if log_file_path.is_empty() {
             None
         } else {
             match File::create(log_file_path).await {
                 Ok(f) => {
                     info!("writing {} to: {}", log_file_type, log_file_path);
-                    Some(BufWriter::with_capacity(buffer_capacity, f))
+                    Some(SwanlingLogFile::new(log_file_path, f, buffer_capacity))
                 }
                 Err(e) => {
                     error!(
@@ -505,7 +981,7 @@ impl SwanlingConfiguration {
     /// Helper to write a line to the log file.
     async fn write_to_log_file(
         &self,
-        log_file: &mut tokio::io::BufWriter<tokio::fs::File>,
+        log_file: &mut SwanlingLogFile,
         formatted_message: String,
     ) -> Result<(), ()> {
         match log_file
@@ -521,6 +997,19 @@ impl SwanlingConfiguration {
         Ok(())
     }
 
+    // Write aggregated errors to the error log.
+    async fn write_errors(
+        &self,
+        log_file: &mut SwanlingLogFile,
+        errors: Vec<SwanlingErrorMetric>,
+    ) {
+        for error in errors {
+            let formatted_message = self.format_message(error).to_string();
+            // @TODO: error handling when writing to log fails.
+            let _ = self.write_to_log_file(log_file, formatted_message).await;
+        }
+    }
+
     /// Logger thread, opens a log file (if configured) and waits for messages from
     /// [`SwanlingUser`](../swanling/struct.SwanlingUser.html) threads.
     pub(crate) async fn logger_main(
@@ -575,6 +1064,42 @@ impl SwanlingConfiguration {
             }
         }
 
+        // If record_raw_latencies is enabled, allocate a buffer and open the file. Parquet
+        // files are written separately, only when compiled with the parquet feature.
+        let raw_latencies_csv = if is_parquet(&self.record_raw_latencies) {
+            ""
+        } else {
+            &self.record_raw_latencies
+        };
+        let mut raw_latencies_log = self
+            .open_log_file(raw_latencies_csv, "raw latencies", 64 * 1024)
+            .await;
+        #[cfg(feature = "parquet")]
+        let mut raw_latencies_parquet = if is_parquet(&self.record_raw_latencies) {
+            match SwanlingParquetLog::create(&self.record_raw_latencies, &self.run_id) {
+                Ok(parquet_log) => {
+                    info!("writing raw latencies to: {}", &self.record_raw_latencies);
+                    Some(parquet_log)
+                }
+                Err(e) => {
+                    error!(
+                        "failed to create raw latencies ({}): {}",
+                        &self.record_raw_latencies, e
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        // Raw latencies are always recorded in csv format, write the header.
+        if let Some(log_file) = raw_latencies_log.as_mut() {
+            // @TODO: error handling when writing to log fails.
+            let _ = self
+                .write_to_log_file(log_file, raw_latencies_csv_header())
+                .await;
+        }
+
         // If the task_log is enabled, allocate a buffer and open the file.
         let mut task_log = self
             .open_log_file(&self.task_log, "task log", 64 * 1024)
@@ -587,19 +1112,78 @@ impl SwanlingConfiguration {
             }
         }
 
+        // Compile the patterns to redact from the debug log, they were validated when the
+        // load test was configured.
+        let debug_redact: Vec<Regex> = self
+            .debug_redact
+            .iter()
+            .filter_map(|pattern| Regex::new(pattern).ok())
+            .collect();
+
+        // Optionally aggregate identical errors, the window was validated when the load test
+        // was configured.
+        let mut error_window = util::parse_milliseconds(&self.error_log_window)
+            .filter(|milliseconds| *milliseconds > 0 && error_log.is_some())
+            .map(|milliseconds| SwanlingErrorWindow::new(Duration::from_millis(milliseconds)));
+
         // Loop waiting for and writing error logs from SwanlingUser threads.
-        while let Ok(received_message) = receiver.recv_async().await {
+        loop {
+            // Write the aggregated errors when their window closes, even if no more messages
+            // are received.
+            let closes = error_window.as_ref().and_then(|window| window.closes());
+            let received_message = match closes {
+                Some(closes) if closes <= Instant::now() => None,
+                Some(closes) => tokio::time::timeout_at(closes, receiver.recv_async())
+                    .await
+                    .ok(),
+                None => Some(receiver.recv_async().await),
+            };
+            let received_message = match received_message {
+                Some(Ok(received_message)) => received_message,
+                // The channel is closed.
+                Some(Err(_)) => break,
+                // The error window closed.
+                None => {
+                    if let (Some(window), Some(log_file)) =
+                        (error_window.as_mut(), error_log.as_mut())
+                    {
+                        self.write_errors(log_file, window.close()).await;
+                    }
+                    continue;
+                }
+            };
             if let Some(message) = received_message {
                 let formatted_message;
                 if let Some(log_file) = match message {
-                    SwanlingLog::Debug(debug_message) => {
+                    SwanlingLog::Debug(mut debug_message) => {
+                        sanitize_debug(
+                            &mut debug_message,
+                            &debug_redact,
+                            self.debug_body_max_bytes,
+                        );
                         formatted_message = self.format_message(debug_message).to_string();
                         debug_log.as_mut()
                     }
                     SwanlingLog::Error(error_message) => {
+                        // Aggregated errors are written when their window closes.
+                        if let Some(window) = error_window.as_mut() {
+                            window.add(error_message);
+                            continue;
+                        }
                         formatted_message = self.format_message(error_message).to_string();
                         error_log.as_mut()
                     }
+                    SwanlingLog::RawLatency(latency_message) => {
+                        #[cfg(feature = "parquet")]
+                        if let Some(parquet_log) = raw_latencies_parquet.as_mut() {
+                            if let Err(e) = parquet_log.write(latency_message) {
+                                warn!("failed to write to {}: {}", &self.record_raw_latencies, e);
+                            }
+                            continue;
+                        }
+                        formatted_message = self.format_message(latency_message).to_string();
+                        raw_latencies_log.as_mut()
+                    }
                     SwanlingLog::Request(request_message) => {
                         formatted_message = self.format_message(request_message).to_string();
                         request_log.as_mut()
@@ -618,6 +1202,11 @@ impl SwanlingConfiguration {
             }
         }
 
+        // Write any errors still being aggregated.
+        if let (Some(window), Some(log_file)) = (error_window.as_mut(), error_log.as_mut()) {
+            self.write_errors(log_file, window.close()).await;
+        }
+
         // Flush debug logs to disk if enabled.
         if let Some(debug_log_file) = debug_log.as_mut() {
             info!("flushing debug_log: {}", &self.debug_log);
@@ -630,6 +1219,25 @@ impl SwanlingConfiguration {
             let _ = requests_log_file.flush().await;
         }
 
+        // Flush raw latencies to disk if enabled.
+        if let Some(raw_latencies_log_file) = raw_latencies_log.as_mut() {
+            info!(
+                "flushing record_raw_latencies: {}",
+                &self.record_raw_latencies
+            );
+            let _ = raw_latencies_log_file.flush().await;
+        }
+        #[cfg(feature = "parquet")]
+        if let Some(parquet_log) = raw_latencies_parquet.take() {
+            info!(
+                "flushing record_raw_latencies: {}",
+                &self.record_raw_latencies
+            );
+            if let Err(e) = parquet_log.close() {
+                warn!("failed to write to {}: {}", &self.record_raw_latencies, e);
+            }
+        }
+
         // Flush tasks log to disk if enabled.
         if let Some(tasks_log_file) = task_log.as_mut() {
             info!("flushing task_log: {}", &self.task_log);
@@ -1,18 +1,25 @@
+use chrono::Utc;
 use lazy_static::lazy_static;
 use nng::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::BufWriter;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::{thread, time};
 
+use crate::controller::{
+    SwanlingControllerCommand, SwanlingControllerRequest, SwanlingControllerResponseMessage,
+    SwanlingWorkerStatus,
+};
+use crate::coordination::SwanlingFlag;
 use crate::metrics::{
-    self, SwanlingErrorMetricAggregate, SwanlingErrorMetrics, SwanlingRequestMetricAggregate,
-    SwanlingRequestMetrics, SwanlingTaskMetricAggregate, SwanlingTaskMetrics,
+    self, SwanlingAssertionMetrics, SwanlingErrorMetricAggregate, SwanlingErrorMetrics,
+    SwanlingPollMetrics, SwanlingRequestMetricAggregate, SwanlingRequestMetrics,
+    SwanlingTaskMetricAggregate, SwanlingTaskMetrics,
 };
 use crate::util;
-use crate::worker::GaggleMetrics;
+use crate::worker::{self, GaggleMetrics};
 use crate::{SwanlingAttack, SwanlingConfiguration, SwanlingUserCommand};
 
 /// How long the manager will wait for all workers to stop after the load test ends.
@@ -105,6 +112,10 @@ fn merge_requests_from_worker(
 ) -> SwanlingRequestMetricAggregate {
     // Make a mutable copy where we can merge things
     let mut merged_request = parent_request.clone();
+    // The parent may not have seen a request to this host yet.
+    if merged_request.host.is_empty() {
+        merged_request.host = user_request.host.clone();
+    }
     // Iterate over user response times, and merge into global response time
     merged_request.raw_data.times = metrics::merge_times(
         merged_request.raw_data.times,
@@ -147,6 +158,28 @@ fn merge_requests_from_worker(
                 .insert(*status_code, new_count);
         }
     }
+    // Increment response size counters.
+    merged_request.compressed_count += user_request.compressed_count;
+    merged_request.compressed_bytes += user_request.compressed_bytes;
+    merged_request.decoded_count += user_request.decoded_count;
+    merged_request.decoded_bytes += user_request.decoded_bytes;
+    merged_request.uncompressed_count += user_request.uncompressed_count;
+    merged_request.uncompressed_bytes += user_request.uncompressed_bytes;
+    // Increment redirect counters.
+    merged_request.redirected_count += user_request.redirected_count;
+    merged_request.redirect_count += user_request.redirect_count;
+    merged_request.max_redirects = merged_request.max_redirects.max(user_request.max_redirects);
+    merged_request.redirect_time += user_request.redirect_time;
+    // Increment conditional request counters.
+    merged_request.not_modified_count += user_request.not_modified_count;
+    merged_request.modified_count += user_request.modified_count;
+    // Merge backend_counts, only populated if `--backend-header` is enabled.
+    for (backend, count) in &user_request.backend_counts {
+        *merged_request
+            .backend_counts
+            .entry(backend.to_string())
+            .or_insert(0) += count;
+    }
     merged_request
 }
 
@@ -195,8 +228,12 @@ fn send_message_to_worker(server: &Socket, message: Message) -> bool {
 fn merge_request_metrics(swanling_attack: &mut SwanlingAttack, requests: SwanlingRequestMetrics) {
     if !requests.is_empty() {
         debug!("requests metrics received: {:?}", requests.len());
-        for (request_key, request) in requests {
+        for (request_key, mut request) in requests {
             trace!("request_key: {}", request_key);
+            // Coalesce new request names once --max-request-names is reached.
+            let (request_key, name) =
+                swanling_attack.get_request_key(&request.method, &request.path);
+            request.path = name;
             let merged_request;
             if let Some(parent_request) = swanling_attack.metrics.requests.get(&request_key) {
                 merged_request = merge_requests_from_worker(
@@ -250,6 +287,191 @@ fn merge_error_metrics(swanling_attack: &mut SwanlingAttack, errors: SwanlingErr
     }
 }
 
+/// Merge users removed by a worker into the manager's count of removed users.
+fn merge_removed_users(
+    swanling_attack: &mut SwanlingAttack,
+    removed_users: BTreeMap<String, usize>,
+) {
+    for (error, users) in removed_users {
+        *swanling_attack
+            .metrics
+            .removed_users
+            .entry(error)
+            .or_insert(0) += users;
+    }
+}
+
+/// Merge assertions checked by a worker's users into the manager's assertions.
+fn merge_assertions(swanling_attack: &mut SwanlingAttack, assertions: SwanlingAssertionMetrics) {
+    for (label, assertion) in assertions {
+        swanling_attack
+            .metrics
+            .assertions
+            .entry(label)
+            .or_default()
+            .merge(&assertion);
+    }
+}
+
+/// Merge polls finished by a worker's users into the manager's polls.
+fn merge_polls(swanling_attack: &mut SwanlingAttack, polls: SwanlingPollMetrics) {
+    for (name, poll) in polls {
+        swanling_attack
+            .metrics
+            .polls
+            .entry(name)
+            .or_default()
+            .merge(&poll);
+    }
+}
+
+/// A Worker connected to the Manager.
+struct ManagedWorker {
+    /// The status displayed by the `workers` Controller command.
+    status: SwanlingWorkerStatus,
+    /// The users assigned to the Worker.
+    users: Vec<SwanlingUserInitializer>,
+    /// Users assigned to the Worker while the load test is running, sent in the next reply.
+    pending_users: Vec<SwanlingUserInitializer>,
+}
+
+/// Drain a Worker, distributing its users among the remaining Workers. Returns false if no
+/// such Worker is running, or if it's the last one.
+fn drain_worker(workers: &mut HashMap<Pipe, ManagedWorker>, worker_id: usize) -> bool {
+    let drained = match workers
+        .iter()
+        .find(|(_, worker)| worker.status.worker_id == worker_id && !worker.status.draining)
+    {
+        Some((pipe, _)) => *pipe,
+        None => return false,
+    };
+    // Distribute the users in the order the remaining Workers connected.
+    let mut remaining: Vec<(usize, Pipe)> = workers
+        .iter()
+        .filter(|(pipe, worker)| **pipe != drained && !worker.status.draining)
+        .map(|(pipe, worker)| (worker.status.worker_id, *pipe))
+        .collect();
+    if remaining.is_empty() {
+        return false;
+    }
+    remaining.sort_unstable_by_key(|(worker_id, _)| *worker_id);
+
+    // Unwrap is safe as the Worker was found above.
+    let worker = workers.get_mut(&drained).unwrap();
+    worker.status.draining = true;
+    let mut users = std::mem::take(&mut worker.users);
+    users.append(&mut worker.pending_users);
+    info!(
+        "draining worker {}, moving {} users to {} workers",
+        worker_id,
+        users.len(),
+        remaining.len()
+    );
+    for (index, mut user) in users.into_iter().enumerate() {
+        let (worker_id, pipe) = remaining[index % remaining.len()];
+        user.worker_id = worker_id;
+        // Unwrap is safe as the remaining Workers were found above.
+        let worker = workers.get_mut(&pipe).unwrap();
+        worker.status.assigned_users += 1;
+        worker.pending_users.push(user);
+    }
+    true
+}
+
+/// Reply to a request from the Manager's Controllers, if one was received. The Manager
+/// doesn't run any users itself, so only commands displaying the load test and its
+/// Workers, draining Workers, and shutting it down, are supported.
+fn handle_controller_requests(
+    swanling_attack: &mut SwanlingAttack,
+    controller_rx: &flume::Receiver<SwanlingControllerRequest>,
+    workers: &mut HashMap<Pipe, ManagedWorker>,
+    running: bool,
+    canceled: &AtomicBool,
+) {
+    let message = match controller_rx.try_recv() {
+        Ok(message) => message,
+        // Errors can be ignored, they happen any time there are no messages.
+        Err(_) => return,
+    };
+    info!(
+        "request from controller client {}: {:?}",
+        message.client_id, message.request
+    );
+    let response = match &message.request.command {
+        // Send back a copy of the running configuration.
+        SwanlingControllerCommand::Config | SwanlingControllerCommand::ConfigJson => {
+            SwanlingControllerResponseMessage::Config(Box::new(
+                swanling_attack.configuration.clone(),
+            ))
+        }
+        // Send back a copy of the metrics merged from all Workers so far.
+        SwanlingControllerCommand::Metrics | SwanlingControllerCommand::MetricsJson => {
+            swanling_attack.update_duration();
+            SwanlingControllerResponseMessage::Metrics(Box::new(swanling_attack.metrics.clone()))
+        }
+        // Send back the status of each Worker, in the order they connected.
+        SwanlingControllerCommand::Workers | SwanlingControllerCommand::WorkersJson => {
+            let now = Utc::now().timestamp();
+            let mut statuses: Vec<SwanlingWorkerStatus> = workers
+                .values()
+                .map(|worker| SwanlingWorkerStatus {
+                    lag: worker
+                        .status
+                        .last_flush
+                        .map(|last_flush| (now - last_flush).max(0) as u64),
+                    ..worker.status.clone()
+                })
+                .collect();
+            statuses.sort_by_key(|worker| worker.worker_id);
+            SwanlingControllerResponseMessage::Workers(statuses)
+        }
+        // Drain a Worker while the load test is running.
+        SwanlingControllerCommand::Drain => {
+            let worker_id = message.request.value.as_ref().map(|id| id.parse());
+            let drained = match worker_id {
+                Some(Ok(worker_id)) if running => drain_worker(workers, worker_id),
+                _ => false,
+            };
+            SwanlingControllerResponseMessage::Bool(drained)
+        }
+        // Stop the load test, telling all Workers to exit.
+        SwanlingControllerCommand::Shutdown => {
+            canceled.store(true, Ordering::SeqCst);
+            SwanlingControllerResponseMessage::Bool(true)
+        }
+        // These messages shouldn't be received here.
+        SwanlingControllerCommand::Help | SwanlingControllerCommand::Exit => {
+            warn!("Unexpected command: {:?}", &message.request);
+            return;
+        }
+        // All other commands control users, which the Manager doesn't run.
+        _ => SwanlingControllerResponseMessage::Bool(false),
+    };
+    swanling_attack.reply_to_controller(message, response);
+}
+
+// Launch a Worker in the Manager process, connecting to the Manager like any other Worker.
+fn launch_local_worker(swanling_attack: &SwanlingAttack) {
+    let mut local_worker = swanling_attack.clone();
+    // Connect through the loopback interface unless the Manager listens on a specific host.
+    local_worker.configuration.manager_host =
+        if ["", "0.0.0.0"].contains(&swanling_attack.configuration.manager_bind_host.as_str()) {
+            "127.0.0.1".to_string()
+        } else {
+            swanling_attack.configuration.manager_bind_host.clone()
+        };
+    local_worker.configuration.manager_port = swanling_attack.configuration.manager_bind_port;
+
+    // The Worker blocks while waiting for the Manager, so give it a thread of its own.
+    thread::spawn(move || {
+        let runtime = local_worker
+            .runtime()
+            .map_err(|error| eprintln!("{:?}", error))
+            .expect("failed to build local worker runtime");
+        runtime.block_on(worker::worker_main(&local_worker));
+    });
+}
+
 /// Main manager loop.
 pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> SwanlingAttack {
     // Creates a TCP address.
@@ -284,6 +506,15 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
         swanling_attack.configuration.expect_workers.unwrap(),
     );
 
+    // The Manager only coordinates the Workers, and launches users only if it also runs
+    // one of them.
+    if swanling_attack.configuration.manager_also_worker {
+        info!("manager also running a worker, which counts as one of the expected workers");
+        launch_local_worker(&swanling_attack);
+    } else {
+        info!("manager launching no users, all users are launched by workers");
+    }
+
     // Calculate how many users each worker will be responsible for.
     let (users_per_worker, mut users_remainder) = distribute_users(&swanling_attack);
 
@@ -293,6 +524,15 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
     // Track how many workers we've seen.
     let mut workers: HashSet<Pipe> = HashSet::new();
 
+    // Track the status and users of each worker.
+    let mut managed_workers: HashMap<Pipe, ManagedWorker> = HashMap::new();
+
+    // Launch the Controllers, if enabled.
+    let controller_rx = swanling_attack
+        .setup_controllers()
+        .await
+        .map(|(_, controller_rx)| controller_rx);
+
     // Track start time, we'll reset this when the test actually starts.
     let mut started = time::Instant::now();
     swanling_attack.started = Some(started);
@@ -303,7 +543,8 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
 
     // Catch ctrl-c to allow clean shutdown to display metrics.
     let canceled = Arc::new(AtomicBool::new(false));
-    util::setup_ctrlc_handler(&canceled);
+    let forced = SwanlingFlag::new();
+    util::setup_ctrlc_handler(&canceled, &forced);
 
     // Initialize the optional task metrics.
     swanling_attack
@@ -311,8 +552,12 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
         .initialize_task_metrics(&swanling_attack.task_sets, &swanling_attack.configuration);
 
     // Update metrics, which doesn't happen automatically on the Master as we don't
-    // invoke start_attack. Hatch rate is required here so unwrap() is safe.
-    let hatch_rate = util::get_hatch_rate(swanling_attack.configuration.hatch_rate.clone());
+    // invoke start_attack. Hatch rate is required here so unwrap() is safe. If the
+    // hatch rate is a ramp, use the rate at the end of the load test.
+    let hatch_rate = util::get_hatch_rate_at(
+        swanling_attack.configuration.hatch_rate.clone(),
+        swanling_attack.run_time as f32,
+    );
     let maximum_hatched = hatch_rate * swanling_attack.run_time as f32;
     if maximum_hatched < swanling_attack.configuration.users.unwrap() as f32 {
         swanling_attack.metrics.users = maximum_hatched as usize;
@@ -324,8 +569,13 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
     loop {
         // While running load test, check if any workers go away.
         if !load_test_finished {
-            // If ACTIVE_WORKERS is less than the total workers seen, a worker went away.
-            if ACTIVE_WORKERS.load(Ordering::SeqCst) < workers.len() {
+            // If ACTIVE_WORKERS is less than the total workers seen, not counting drained
+            // workers, a worker went away.
+            let drained = managed_workers
+                .values()
+                .filter(|worker| worker.status.draining)
+                .count();
+            if ACTIVE_WORKERS.load(Ordering::SeqCst) < workers.len() - drained {
                 // If worked goes away during load test, exit gracefully.
                 if load_test_running {
                     info!(
@@ -362,6 +612,12 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                 break;
             }
 
+            // Aborting graceful shutdown, ctrl-c was pressed again.
+            if load_test_finished && forced.is_set() {
+                warn!("no longer waiting for workers to exit...");
+                break;
+            }
+
             // When displaying running metrics, sync data from user threads first.
             if let Some(running_metrics) = swanling_attack.configuration.running_metrics {
                 if util::timer_expired(running_metrics_timer, running_metrics) {
@@ -374,7 +630,18 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
             }
         } else if canceled.load(Ordering::SeqCst) {
             info!("load test canceled, exiting");
-            std::process::exit(1);
+            break;
+        }
+
+        // Reply to any requests from the Controllers.
+        if let Some(controller_rx) = controller_rx.as_ref() {
+            handle_controller_requests(
+                &mut swanling_attack,
+                controller_rx,
+                &mut managed_workers,
+                load_test_running && !load_test_finished,
+                &canceled,
+            );
         }
 
         // Check for messages from workers.
@@ -385,14 +652,20 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
 
                 // Workers always send a vector of SwanlingMetric objects.
                 let mut gaggle_metrics: Vec<GaggleMetrics> =
-                    serde_cbor::from_reader(msg.as_slice()).unwrap();
+                    worker::decode_gaggle_metrics(msg.as_slice()).unwrap();
 
                 // Check if we're seeing this worker for the first time.
                 if !workers.contains(&pipe) {
+                    // Workers connecting after the load test started are optionally
+                    // hot-added to the running Regatta.
+                    let hot_add = swanling_attack.configuration.hot_add_workers
+                        && load_test_running
+                        && !load_test_finished;
                     // Check if we are expecting another worker. Expect workers is required
                     // so unwrap() is safe.
-                    if workers.len()
-                        >= swanling_attack.configuration.expect_workers.unwrap() as usize
+                    if !hot_add
+                        && workers.len()
+                            >= swanling_attack.configuration.expect_workers.unwrap() as usize
                     {
                         warn!(
                             "telling extra worker ({} of {}) to exit",
@@ -442,12 +715,19 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                         }
 
                         workers.insert(pipe);
-                        // Expect workers is required so unwrap() is safe.
-                        info!(
-                            "worker {} of {} connected",
-                            workers.len(),
-                            swanling_attack.configuration.expect_workers.unwrap(),
-                        );
+                        if hot_add {
+                            info!(
+                                "worker {} connected, adding it to the running load test",
+                                workers.len()
+                            );
+                        } else {
+                            // Expect workers is required so unwrap() is safe.
+                            info!(
+                                "worker {} of {} connected",
+                                workers.len(),
+                                swanling_attack.configuration.expect_workers.unwrap(),
+                            );
+                        }
 
                         // Send new worker a batch of users.
                         let mut user_batch = users_per_worker;
@@ -458,9 +738,24 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                         }
                         let mut users = Vec::new();
 
+                        // A hot-added worker only runs for what's left of the load test.
+                        let run_time = if hot_add && swanling_attack.run_time > 0 {
+                            swanling_attack
+                                .run_time
+                                .saturating_sub(started.elapsed().as_secs() as usize)
+                                .max(1)
+                        } else {
+                            swanling_attack.run_time
+                        };
+
                         // Pop users from available_users vector and build worker initializer.
                         debug!("sending {} users to worker", user_batch);
                         for _ in 1..=user_batch {
+                            // Hot-added workers are given additional users, taken from
+                            // the weighted users in the same order as the first workers.
+                            if hot_add && available_users.is_empty() {
+                                available_users = swanling_attack.weighted_gaggle_users.clone();
+                            }
                             let user = match available_users.pop() {
                                 Some(u) => u,
                                 None => {
@@ -474,7 +769,7 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                                 min_wait: user.min_wait,
                                 max_wait: user.max_wait,
                                 config: user.config.clone(),
-                                run_time: swanling_attack.run_time,
+                                run_time,
                                 worker_id: workers.len(),
                             });
                         }
@@ -498,6 +793,26 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                             // test.
                             break;
                         }
+                        managed_workers.insert(
+                            pipe,
+                            ManagedWorker {
+                                status: SwanlingWorkerStatus {
+                                    worker_id: workers.len(),
+                                    assigned_users: users.len(),
+                                    users: 0,
+                                    last_flush: None,
+                                    lag: None,
+                                    draining: false,
+                                },
+                                users,
+                                pending_users: Vec::new(),
+                            },
+                        );
+
+                        // The users of a hot-added worker are added to the load test.
+                        if hot_add {
+                            swanling_attack.metrics.users += user_batch;
+                        }
 
                         // Expect workers is required so unwrap() is safe.
                         if workers.len()
@@ -534,6 +849,16 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                         continue;
                     }
 
+                    // Workers still waiting for the go-ahead send heartbeats, and can't yet
+                    // be sent more users.
+                    let heartbeat = gaggle_metrics
+                        .iter()
+                        .any(|metric| matches!(metric, GaggleMetrics::WorkerInit(_)));
+
+                    // Track when this worker last sent its metrics.
+                    if let Some(worker) = managed_workers.get_mut(&pipe) {
+                        worker.status.last_flush = Some(Utc::now().timestamp());
+                    }
                     for metric in gaggle_metrics {
                         match metric {
                             // Merge in request metrics from Worker.
@@ -548,17 +873,50 @@ pub(crate) async fn manager_main(mut swanling_attack: SwanlingAttack) -> Swanlin
                             GaggleMetrics::Errors(errors) => {
                                 merge_error_metrics(&mut swanling_attack, errors)
                             }
+                            // Merge in users removed by Worker.
+                            GaggleMetrics::RemovedUsers(removed_users) => {
+                                merge_removed_users(&mut swanling_attack, removed_users)
+                            }
+                            // Merge in assertions checked by Worker.
+                            GaggleMetrics::Assertions(assertions) => {
+                                merge_assertions(&mut swanling_attack, assertions)
+                            }
+                            // Merge in polls finished by Worker.
+                            GaggleMetrics::Polls(polls) => merge_polls(&mut swanling_attack, polls),
+                            // Track how many users the Worker has launched.
+                            GaggleMetrics::Users(users) => {
+                                if let Some(worker) = managed_workers.get_mut(&pipe) {
+                                    worker.status.users = users;
+                                }
+                            }
                             // Ignore Worker heartbeats.
                             GaggleMetrics::WorkerInit(_) => (),
                         }
                     }
 
-                    if load_test_finished {
+                    let worker = managed_workers.get_mut(&pipe);
+                    let draining = matches!(&worker, Some(worker) if worker.status.draining);
+                    if load_test_finished || draining {
                         debug!("telling worker to exit");
                         serde_cbor::to_writer(&mut message, &SwanlingUserCommand::Exit)
                             .map_err(|error| eprintln!("{:?}", error))
                             .expect("failed to serialize user command");
                     }
+                    // Send the worker any users taken over from a drained worker.
+                    else if let Some(worker) =
+                        worker.filter(|worker| !heartbeat && !worker.pending_users.is_empty())
+                    {
+                        let mut users = std::mem::take(&mut worker.pending_users);
+                        info!(
+                            "sending {} more users to worker {}",
+                            users.len(),
+                            worker.status.worker_id
+                        );
+                        serde_cbor::to_writer(&mut message, &users)
+                            .map_err(|error| eprintln!("{:?}", error))
+                            .expect("failed to serialize user initializers");
+                        worker.users.append(&mut users);
+                    }
                     // Notify the worker that the load test is still running.
                     else {
                         serde_cbor::to_writer(&mut message, &SwanlingUserCommand::Run)
@@ -625,4 +983,29 @@ mod tests {
         assert_eq!(users_per_process, 4);
         assert_eq!(users_remainder, 16);
     }
+
+    #[test]
+    fn test_decode_gaggle_metrics() {
+        // Small metrics are pushed uncompressed.
+        let payload = worker::encode_gaggle_metrics(&[GaggleMetrics::Users(10)]);
+        assert_eq!(payload[0], 0);
+        let gaggle_metrics = worker::decode_gaggle_metrics(&payload).unwrap();
+        assert!(matches!(gaggle_metrics[..], [GaggleMetrics::Users(10)]));
+
+        // Large metrics are compressed.
+        let removed_users: BTreeMap<String, usize> = (0..1000)
+            .map(|index| (format!("error {}", index), index))
+            .collect();
+        let payload =
+            worker::encode_gaggle_metrics(&[GaggleMetrics::RemovedUsers(removed_users.clone())]);
+        assert_eq!(payload[0], 1);
+        let gaggle_metrics = worker::decode_gaggle_metrics(&payload).unwrap();
+        assert!(
+            matches!(&gaggle_metrics[..], [GaggleMetrics::RemovedUsers(decoded)] if decoded == &removed_users)
+        );
+
+        // Unknown encodings are rejected.
+        assert!(worker::decode_gaggle_metrics(&[2]).is_err());
+        assert!(worker::decode_gaggle_metrics(&[]).is_err());
+    }
 }
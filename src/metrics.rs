@@ -16,21 +16,42 @@ use regex::RegexSet;
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Serialize, Serializer};
 use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use std::{f32, fmt};
+use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
 
 use crate::logger::SwanlingLog;
 use crate::report;
-use crate::swanling::{SwanlingMethod, SwanlingTaskSet};
+use crate::swanling::{SwanlingMethod, SwanlingTaskError, SwanlingTaskSet};
 use crate::util;
 #[cfg(feature = "gaggle")]
-use crate::worker::{self, GaggleMetrics};
+use crate::worker;
 use crate::{
     AttackMode, SwanlingAttack, SwanlingAttackRunState, SwanlingConfiguration, SwanlingError,
 };
 
+/// The name requests are recorded under once `--max-request-names` unique request names
+/// are tracked.
+pub const OTHER_REQUEST_NAME: &str = "(other)";
+
+/// The response time percentiles reported if `--percentiles` isn't set.
+pub(crate) const DEFAULT_PERCENTILES: &[f64] = &[50.0, 75.0, 98.0, 99.0, 99.9, 99.99];
+
+/// CPU usage of the load generator, as a percentage of all available CPUs, at or above
+/// which the load generator rather than the target may be the bottleneck.
+pub(crate) const GENERATOR_CPU_WARNING: f64 = 90.0;
+
+/// How many milliseconds [`SwanlingUser`](../swanling/struct.SwanlingUser.html) threads
+/// aggregate metrics before sending them to the parent if `--metrics-flush` isn't set.
+pub(crate) const DEFAULT_METRICS_FLUSH: u64 = 100;
+
+/// The most tasks the parent merges batches of request metrics on.
+const MAX_METRICS_SHARDS: usize = 4;
+
 /// Used to send metrics from [`SwanlingUser`](../swanling/struct.SwanlingUser.html) threads
 /// to the parent Swanling process.
 ///
@@ -38,16 +59,32 @@ use crate::{
 /// Swanling parent process using an
 /// [`unbounded Flume channel`](https://docs.rs/flume/*/flume/fn.unbounded.html).
 ///
-/// The parent process will spend up to 80% of its time receiving and aggregating
-/// these metrics. The parent process aggregates [`SwanlingRequestMetric`]s into
-/// [`SwanlingRequestMetricAggregate`], [`SwanlingTaskMetric`]s into [`SwanlingTaskMetricAggregate`],
-/// and [`SwanlingErrorMetric`]s into [`SwanlingErrorMetricAggregate`]. Aggregation happens in the
-/// parent process so the individual [`SwanlingUser`](../swanling/struct.SwanlingUser.html) threads
-/// can spend all their time generating and validating load.
+/// To keep the number of messages independent of how many requests are made, each
+/// [`SwanlingUser`](../swanling/struct.SwanlingUser.html) thread aggregates its request and
+/// task metrics and sends them as one [`SwanlingMetric::Batch`] every `--metrics-flush`
+/// milliseconds. Requests that failed with an error, update an earlier request, or need to
+/// be backfilled by Coordinated Omission Mitigation are sent individually. The parent
+/// process merges the batches and aggregates the remaining [`SwanlingRequestMetric`]s into
+/// [`SwanlingRequestMetricAggregate`], [`SwanlingTaskMetric`]s into
+/// [`SwanlingTaskMetricAggregate`], and [`SwanlingErrorMetric`]s into
+/// [`SwanlingErrorMetricAggregate`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SwanlingMetric {
     Request(SwanlingRequestMetric),
     Task(SwanlingTaskMetric),
+    /// Requests, tasks, assertions and polls aggregated by a
+    /// [`SwanlingUser`](../swanling/struct.SwanlingUser.html) since it last sent metrics to
+    /// the parent.
+    Batch(
+        SwanlingRequestMetrics,
+        Vec<SwanlingTaskMetricAggregate>,
+        SwanlingAssertionMetrics,
+        SwanlingPollMetrics,
+    ),
+    /// A user was removed from the load test because an
+    /// [`on_start` session function](../swanling/struct.SwanlingTaskSet.html#method.register_on_start_session)
+    /// failed, with the error it returned.
+    UserRemoved(String),
 }
 
 /// THIS IS IN EXPERIMENTAL FEATURE, DISABLED BY DEFAULT. Optionally mitigate the loss of data
@@ -117,6 +154,47 @@ impl FromStr for SwanlingCoordinatedOmissionMitigation {
     }
 }
 
+/// Defines the formats running metrics can be displayed in.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SwanlingMetricsFormat {
+    /// Multi-line ASCII tables (default).
+    #[default]
+    Table,
+    /// A single line summarizing all requests.
+    Compact,
+    /// A single line of json containing all metrics.
+    Json,
+}
+/// Allow setting metrics formats from the command line by implementing [`FromStr`].
+impl FromStr for SwanlingMetricsFormat {
+    type Err = SwanlingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Use a [`RegexSet`] to match string representations of `SwanlingMetricsFormat`,
+        // returning the appropriate enum value.
+        let metrics_format = RegexSet::new([
+            r"(?i)^(table|tables)$",
+            r"(?i)^compact$",
+            r"(?i)^(json|jsn)$",
+        ])
+        .expect("failed to compile metrics_format RegexSet");
+        let matches = metrics_format.matches(s);
+        if matches.matched(0) {
+            Ok(SwanlingMetricsFormat::Table)
+        } else if matches.matched(1) {
+            Ok(SwanlingMetricsFormat::Compact)
+        } else if matches.matched(2) {
+            Ok(SwanlingMetricsFormat::Json)
+        } else {
+            Err(SwanlingError::InvalidOption {
+                option: format!("SwanlingMetricsFormat::{:?}", s),
+                value: s.to_string(),
+                detail: "Invalid metrics_format, expected: table, compact, or json".to_string(),
+            })
+        }
+    }
+}
+
 /// All requests made during a load test.
 ///
 /// Swanling optionally tracks metrics about requests made during a load test. The
@@ -244,11 +322,16 @@ pub type SwanlingTaskMetrics = Vec<Vec<SwanlingTaskMetricAggregate>>;
 /// ```text
 ///  === ERRORS ===
 /// ------------------------------------------------------------------------------
-/// Count       | Error
+/// Count       | Category
+/// ------------------------------------------------------------------------------
+/// 1,639         Http5xx
+/// 36            Custom
 /// ------------------------------------------------------------------------------
-/// 924           GET (Auth) front page: 503 Service Unavailable: /
-/// 715           POST (Auth) front page: 503 Service Unavailable: /user
-/// 36            GET (Anon) front page: error sending request for url (http://example.com/): connection closed before message completed
+/// Count       | Category          | Error
+/// ------------------------------------------------------------------------------
+/// 924           Http5xx             GET (Auth) front page: 503 Service Unavailable: /
+/// 715           Http5xx             POST (Auth) front page: 503 Service Unavailable: /user
+/// 36            Custom              GET (Anon) front page: error sending request for url (http://example.com/): connection closed before message completed
 /// ```
 pub type SwanlingErrorMetrics = BTreeMap<String, SwanlingErrorMetricAggregate>;
 
@@ -293,6 +376,40 @@ pub struct SwanlingRequestMetric {
     /// [`SwanlingTask`](../swanling/struct.SwanlingTask.html)s by this
     /// [`SwanlingUser`](../swanling/struct.SwanlingUser.html).
     pub user_cadence: u64,
+    /// The optional backend that served the request, read from the `--backend-header`
+    /// response header.
+    #[serde(default)]
+    pub backend: String,
+    /// The optional user agent the request was sent with, picked for the user from the
+    /// [`set_user_agents`](../swanling/struct.SwanlingTaskSet.html#method.set_user_agents)
+    /// of its task set or `--user-agent-file`.
+    #[serde(default)]
+    pub user_agent: String,
+    /// Whether or not the response was compressed, as indicated by its `Content-Encoding`.
+    #[serde(default)]
+    pub compressed: bool,
+    /// The size in bytes of the response body as sent by the server, if known.
+    #[serde(default)]
+    pub response_size: u64,
+    /// The size in bytes of the response body once decoded, if it was compressed with gzip or
+    /// deflate, otherwise 0.
+    #[serde(default)]
+    pub decoded_size: u64,
+    /// Whether the request was conditional, revalidating a cached response with
+    /// `If-None-Match` or `If-Modified-Since`.
+    #[serde(default)]
+    pub conditional: bool,
+    /// How many redirects were followed to get the response.
+    #[serde(default)]
+    pub redirects: usize,
+    /// How many milliseconds were spent following redirects, before the final URL was
+    /// requested.
+    #[serde(default)]
+    pub redirect_time: u64,
+    /// Key/value metadata the request was tagged with by a
+    /// [`SwanlingRequestBuilder`](../swanling/struct.SwanlingRequestBuilder.html).
+    #[serde(default)]
+    pub tags: BTreeMap<String, String>,
 }
 impl SwanlingRequestMetric {
     pub(crate) fn new(
@@ -317,6 +434,23 @@ impl SwanlingRequestMetric {
             error: "".to_string(),
             coordinated_omission_elapsed: 0,
             user_cadence: 0,
+            backend: "".to_string(),
+            user_agent: "".to_string(),
+            compressed: false,
+            response_size: 0,
+            decoded_size: 0,
+            conditional: false,
+            redirects: 0,
+            redirect_time: 0,
+            tags: BTreeMap::new(),
+        }
+    }
+
+    // The host the request was made to.
+    pub(crate) fn host(&self) -> String {
+        match url::Url::parse(&self.url) {
+            Ok(url) => request_host(&url),
+            Err(_) => "".to_string(),
         }
     }
 
@@ -328,6 +462,28 @@ impl SwanlingRequestMetric {
         }
     }
 
+    // Record whether the response was compressed, how large it was, and how large it was
+    // once decoded.
+    pub(crate) fn set_compression(
+        &mut self,
+        content_encoding: Option<&str>,
+        size: Option<u64>,
+        decoded_size: Option<u64>,
+    ) {
+        self.compressed = match content_encoding {
+            Some(encoding) => !encoding.is_empty() && !encoding.eq_ignore_ascii_case("identity"),
+            None => false,
+        };
+        self.response_size = size.unwrap_or(0);
+        self.decoded_size = decoded_size.unwrap_or(0);
+    }
+
+    // Record how many redirects were followed, and how long it took to follow them.
+    pub(crate) fn set_redirects(&mut self, redirects: usize, redirect_time: u128) {
+        self.redirects = redirects;
+        self.redirect_time = redirect_time as u64;
+    }
+
     // Record how long the `response_time` took.
     pub(crate) fn set_response_time(&mut self, response_time: u128) {
         self.response_time = response_time as u64;
@@ -342,6 +498,49 @@ impl SwanlingRequestMetric {
     }
 }
 
+/// A raw latency sample, recorded for every request when `--record-raw-latencies` is enabled.
+///
+/// Unlike [`SwanlingRequestMetricAggregate`], samples are not aggregated, allowing offline
+/// analysis of the distribution of response times and their correlation over time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwanlingRawLatency {
+    /// Unix timestamp in milliseconds of when the request started.
+    pub timestamp: u64,
+    /// How many milliseconds the load test had been running when the request started.
+    pub elapsed: u64,
+    /// The method being used (ie, Get, Post, etc).
+    pub method: SwanlingMethod,
+    /// The optional name of the request.
+    pub name: String,
+    /// The HTTP response code (optional).
+    pub status_code: u16,
+    /// How many milliseconds the request took.
+    pub response_time: u64,
+    /// Whether or not the request was successful.
+    pub success: bool,
+    /// Which SwanlingUser thread processed the request.
+    pub user: usize,
+}
+impl SwanlingRawLatency {
+    pub(crate) fn new(request: &SwanlingRequestMetric) -> Self {
+        // The request started response_time milliseconds ago.
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        SwanlingRawLatency {
+            timestamp: now.saturating_sub(request.response_time),
+            elapsed: request.elapsed,
+            method: request.method.clone(),
+            name: request.name.clone(),
+            status_code: request.status_code,
+            response_time: request.response_time,
+            success: request.success,
+            user: request.user,
+        }
+    }
+}
+
 /// Metrics collected about a method-path pair, (for example `GET /index`).
 ///
 /// [`SwanlingRequestMetric`]s are sent by [`SwanlingUser`](../swanling/struct.SwanlingUser.html)
@@ -357,12 +556,59 @@ pub struct SwanlingRequestMetricAggregate {
     ///
     /// For example: [`SwanlingMethod::Get`].
     pub method: SwanlingMethod,
+    /// The host the requests were made to, including the port if not the default.
+    ///
+    /// For example: "api.example.com".
+    #[serde(default)]
+    pub host: String,
     /// The raw data seen from actual requests.
     pub raw_data: SwanlingRequestMetricTimingData,
     /// Combines the raw data with statistically generated Coordinated Omission Metrics.
     pub coordinated_omission_data: Option<SwanlingRequestMetricTimingData>,
     /// Per-status-code counters, tracking how often each response code was returned for this request.
     pub status_code_counts: HashMap<u16, usize>,
+    /// Per-backend counters, tracking how often each backend served this request.
+    ///
+    /// Only populated when `--backend-header` is enabled.
+    pub backend_counts: HashMap<String, usize>,
+    /// Per-user-agent counters, tracking how often this request was sent with each user agent.
+    ///
+    /// Only populated when users pick a user agent from a pool.
+    #[serde(default)]
+    pub user_agent_counts: HashMap<String, usize>,
+    /// Total number of compressed responses.
+    pub compressed_count: usize,
+    /// Total size in bytes of compressed responses, as sent by the server.
+    pub compressed_bytes: u64,
+    /// Total number of compressed responses decoded, as they were compressed with gzip or
+    /// deflate.
+    #[serde(default)]
+    pub decoded_count: usize,
+    /// Total size in bytes of decoded responses, once decoded.
+    #[serde(default)]
+    pub decoded_bytes: u64,
+    /// Total number of uncompressed responses.
+    pub uncompressed_count: usize,
+    /// Total size in bytes of uncompressed responses.
+    pub uncompressed_bytes: u64,
+    /// Total number of requests that followed at least one redirect.
+    #[serde(default)]
+    pub redirected_count: usize,
+    /// Total number of redirects followed.
+    #[serde(default)]
+    pub redirect_count: usize,
+    /// The most redirects followed by a single request.
+    #[serde(default)]
+    pub max_redirects: usize,
+    /// Total milliseconds spent following redirects.
+    #[serde(default)]
+    pub redirect_time: u64,
+    /// Total number of conditional requests that found the cached response wasn't modified.
+    #[serde(default)]
+    pub not_modified_count: usize,
+    /// Total number of conditional requests that received a new response.
+    #[serde(default)]
+    pub modified_count: usize,
     /// Total number of times this path-method request resulted in a successful (2xx) status code.
     ///
     /// A count of how many requests resulted in a 2xx status code.
@@ -384,9 +630,24 @@ impl SwanlingRequestMetricAggregate {
         SwanlingRequestMetricAggregate {
             path: path.to_string(),
             method,
+            host: "".to_string(),
             raw_data: SwanlingRequestMetricTimingData::new(None),
             coordinated_omission_data: None,
             status_code_counts: HashMap::new(),
+            backend_counts: HashMap::new(),
+            user_agent_counts: HashMap::new(),
+            compressed_count: 0,
+            compressed_bytes: 0,
+            decoded_count: 0,
+            decoded_bytes: 0,
+            uncompressed_count: 0,
+            uncompressed_bytes: 0,
+            redirected_count: 0,
+            redirect_count: 0,
+            max_redirects: 0,
+            redirect_time: 0,
+            not_modified_count: 0,
+            modified_count: 0,
             success_count: 0,
             fail_count: 0,
             load_test_hash,
@@ -431,6 +692,101 @@ impl SwanlingRequestMetricAggregate {
         self.status_code_counts.insert(status_code, counter);
         debug!("incremented {} counter: {}", status_code, counter);
     }
+
+    /// Track the size of a response, separately for compressed and uncompressed responses,
+    /// and the size of compressed responses once decoded.
+    pub(crate) fn record_response_size(
+        &mut self,
+        compressed: bool,
+        response_size: u64,
+        decoded_size: u64,
+    ) {
+        if compressed {
+            self.compressed_count += 1;
+            self.compressed_bytes += response_size;
+            if decoded_size > 0 {
+                self.decoded_count += 1;
+                self.decoded_bytes += decoded_size;
+            }
+        } else {
+            self.uncompressed_count += 1;
+            self.uncompressed_bytes += response_size;
+        }
+    }
+
+    /// Track the redirects followed by a request, if any.
+    pub(crate) fn record_redirects(&mut self, redirects: usize, redirect_time: u64) {
+        if redirects > 0 {
+            self.redirected_count += 1;
+            self.redirect_count += redirects;
+            self.max_redirects = self.max_redirects.max(redirects);
+            self.redirect_time += redirect_time;
+        }
+    }
+
+    /// Track whether a conditional request found the cached response was modified.
+    pub(crate) fn record_conditional(&mut self, conditional: bool, status_code: u16) {
+        if conditional {
+            match status_code {
+                304 => self.not_modified_count += 1,
+                200..=299 => self.modified_count += 1,
+                _ => (),
+            }
+        }
+    }
+
+    /// Increment counter for backend, creating new counter if first time seeing backend.
+    pub(crate) fn set_backend(&mut self, backend: &str) {
+        *self.backend_counts.entry(backend.to_string()).or_insert(0) += 1;
+    }
+
+    /// Increment counter for user agent, creating new counter if first time seeing user agent.
+    pub(crate) fn set_user_agent(&mut self, user_agent: &str) {
+        *self
+            .user_agent_counts
+            .entry(user_agent.to_string())
+            .or_insert(0) += 1;
+    }
+
+    /// Merges metrics collected for the same request by another load test into these metrics.
+    pub(crate) fn merge(&mut self, other: &SwanlingRequestMetricAggregate) {
+        if self.host.is_empty() {
+            self.host = other.host.clone();
+        }
+        self.raw_data.merge(&other.raw_data);
+        if let Some(other_data) = other.coordinated_omission_data.as_ref() {
+            match self.coordinated_omission_data.as_mut() {
+                Some(coordinated_omission_data) => coordinated_omission_data.merge(other_data),
+                None => self.coordinated_omission_data = Some(other_data.clone()),
+            }
+        }
+        for (status_code, count) in &other.status_code_counts {
+            *self.status_code_counts.entry(*status_code).or_insert(0) += count;
+        }
+        for (backend, count) in &other.backend_counts {
+            *self.backend_counts.entry(backend.to_string()).or_insert(0) += count;
+        }
+        for (user_agent, count) in &other.user_agent_counts {
+            *self
+                .user_agent_counts
+                .entry(user_agent.to_string())
+                .or_insert(0) += count;
+        }
+        self.compressed_count += other.compressed_count;
+        self.compressed_bytes += other.compressed_bytes;
+        self.decoded_count += other.decoded_count;
+        self.decoded_bytes += other.decoded_bytes;
+        self.uncompressed_count += other.uncompressed_count;
+        self.uncompressed_bytes += other.uncompressed_bytes;
+        self.redirected_count += other.redirected_count;
+        self.redirect_count += other.redirect_count;
+        self.max_redirects = self.max_redirects.max(other.max_redirects);
+        self.redirect_time += other.redirect_time;
+        self.not_modified_count += other.not_modified_count;
+        self.modified_count += other.modified_count;
+        self.success_count += other.success_count;
+        self.fail_count += other.fail_count;
+    }
 }
 /// Implement ordering for SwanlingRequestMetricAggregate.
 impl Ord for SwanlingRequestMetricAggregate {
@@ -547,6 +903,35 @@ impl SwanlingRequestMetricTimingData {
         debug!("incremented {} counter: {}", rounded_time, counter);
         self.times.insert(rounded_time, counter);
     }
+
+    /// Merges timing data into this timing data, for example to aggregate all requests.
+    pub(crate) fn merge(&mut self, other: &SwanlingRequestMetricTimingData) {
+        self.times = merge_times(std::mem::take(&mut self.times), other.times.clone());
+        self.minimum_time = update_min_time(self.minimum_time, other.minimum_time);
+        self.maximum_time = update_max_time(self.maximum_time, other.maximum_time);
+        self.total_time += other.total_time;
+        self.counter += other.counter;
+    }
+
+    /// Calculates the response time within each of the specified percentiles, keyed by
+    /// percentile.
+    pub(crate) fn percentiles(&self, percentiles: &[f64]) -> BTreeMap<String, usize> {
+        percentiles
+            .iter()
+            .map(|percentile| {
+                (
+                    percentile.to_string(),
+                    response_time_percentile(
+                        &self.times,
+                        self.counter,
+                        self.minimum_time,
+                        self.maximum_time,
+                        (percentile / 100.0) as f32,
+                    ),
+                )
+            })
+            .collect()
+    }
 }
 
 /// The per-task metrics collected each time a task is invoked.
@@ -698,6 +1083,229 @@ impl SwanlingTaskMetricAggregate {
         self.times.insert(rounded_time, counter);
         debug!("incremented {} counter: {}", rounded_time, counter);
     }
+
+    /// Merges metrics collected for the same task by another load test into these metrics.
+    pub(crate) fn merge(&mut self, other: &SwanlingTaskMetricAggregate) {
+        self.times = merge_times(std::mem::take(&mut self.times), other.times.clone());
+        self.min_time = update_min_time(self.min_time, other.min_time);
+        self.max_time = update_max_time(self.max_time, other.max_time);
+        self.total_time += other.total_time;
+        self.counter += other.counter;
+        self.success_count += other.success_count;
+        self.fail_count += other.fail_count;
+    }
+}
+
+/// Metrics aggregated by a [`SwanlingUser`](../swanling/struct.SwanlingUser.html) thread
+/// until they're sent to the parent in a [`SwanlingMetric::Batch`].
+#[derive(Debug)]
+pub(crate) struct SwanlingMetricsBatch {
+    /// Requests aggregated since the last flush, keyed by method and name.
+    requests: SwanlingRequestMetrics,
+    /// Tasks aggregated since the last flush, keyed by task set and task index.
+    tasks: BTreeMap<(usize, usize), SwanlingTaskMetricAggregate>,
+    /// Assertions counted since the last flush, keyed by label.
+    assertions: SwanlingAssertionMetrics,
+    /// Polls finished since the last flush, keyed by name.
+    polls: SwanlingPollMetrics,
+    /// Whether to count status codes, copied from `--status-codes`.
+    status_codes: bool,
+    /// How often to send aggregated metrics to the parent.
+    interval: std::time::Duration,
+    /// When aggregated metrics were last sent to the parent.
+    flushed: std::time::Instant,
+}
+impl SwanlingMetricsBatch {
+    pub(crate) fn new(configuration: &SwanlingConfiguration) -> Self {
+        let interval =
+            util::parse_milliseconds(&configuration.metrics_flush).unwrap_or(DEFAULT_METRICS_FLUSH);
+        SwanlingMetricsBatch {
+            requests: HashMap::new(),
+            tasks: BTreeMap::new(),
+            assertions: BTreeMap::new(),
+            polls: BTreeMap::new(),
+            status_codes: configuration.status_codes,
+            interval: std::time::Duration::from_millis(interval),
+            flushed: std::time::Instant::now(),
+        }
+    }
+
+    /// Aggregate a request, the same way the parent records individual requests.
+    pub(crate) fn record_request(&mut self, request_metric: &SwanlingRequestMetric) {
+        let request = self
+            .requests
+            .entry(format!("{} {}", request_metric.method, request_metric.name))
+            .or_insert_with(|| {
+                let mut request = SwanlingRequestMetricAggregate::new(
+                    &request_metric.name,
+                    request_metric.method.clone(),
+                    0,
+                );
+                request.host = request_metric.host();
+                request
+            });
+        request.record_time(request_metric.response_time, false);
+        if self.status_codes {
+            request.set_status_code(request_metric.status_code);
+        }
+        if !request_metric.backend.is_empty() {
+            request.set_backend(&request_metric.backend);
+        }
+        if !request_metric.user_agent.is_empty() {
+            request.set_user_agent(&request_metric.user_agent);
+        }
+        // Only requests that received a response have a size.
+        if request_metric.status_code > 0 {
+            request.record_response_size(
+                request_metric.compressed,
+                request_metric.response_size,
+                request_metric.decoded_size,
+            );
+        }
+        request.record_redirects(request_metric.redirects, request_metric.redirect_time);
+        request.record_conditional(request_metric.conditional, request_metric.status_code);
+        if request_metric.success {
+            request.success_count += 1;
+        } else {
+            request.fail_count += 1;
+        }
+    }
+
+    /// Aggregate a task. Task set and task names are tracked by the parent.
+    pub(crate) fn record_task(&mut self, task_metric: &SwanlingTaskMetric) {
+        self.tasks
+            .entry((task_metric.taskset_index, task_metric.task_index))
+            .or_insert_with(|| {
+                SwanlingTaskMetricAggregate::new(
+                    task_metric.taskset_index,
+                    "",
+                    task_metric.task_index,
+                    &task_metric.name,
+                )
+            })
+            .set_time(task_metric.run_time, task_metric.success);
+    }
+
+    /// Count whether an assertion with this label passed.
+    pub(crate) fn record_assertion(&mut self, label: &str, passed: bool) {
+        self.assertions
+            .entry(label.to_string())
+            .or_default()
+            .record(passed);
+    }
+
+    /// Record a poll with this name that finished after `iterations` requests and
+    /// `wait_time` milliseconds.
+    pub(crate) fn record_poll(&mut self, name: &str, iterations: usize, wait_time: u64, met: bool) {
+        self.polls
+            .entry(name.to_string())
+            .or_default()
+            .record(iterations, wait_time, met);
+    }
+
+    /// Take the aggregated metrics to send to the parent, if there are any and either `force`
+    /// is set or it's time to send them.
+    pub(crate) fn take(&mut self, force: bool) -> Option<SwanlingMetric> {
+        if !force && self.flushed.elapsed() < self.interval {
+            return None;
+        }
+        self.flushed = std::time::Instant::now();
+        if self.requests.is_empty()
+            && self.tasks.is_empty()
+            && self.assertions.is_empty()
+            && self.polls.is_empty()
+        {
+            return None;
+        }
+        Some(SwanlingMetric::Batch(
+            std::mem::take(&mut self.requests),
+            std::mem::take(&mut self.tasks).into_values().collect(),
+            std::mem::take(&mut self.assertions),
+            std::mem::take(&mut self.polls),
+        ))
+    }
+}
+
+/// Commands sent from the parent to a task merging request metrics.
+#[derive(Debug)]
+enum SwanlingMetricsShardCommand {
+    /// Merge metrics aggregated by a user for the request with this key.
+    Merge(String, Box<SwanlingRequestMetricAggregate>),
+    /// Return the metrics merged since they were last collected.
+    Collect(flume::Sender<SwanlingRequestMetrics>),
+}
+
+/// A small pool of tasks merging the batches of request metrics sent by
+/// [`SwanlingUser`](../swanling/struct.SwanlingUser.html) threads.
+///
+/// Requests are assigned to a task by the hash of their key, so merging request metrics is
+/// spread across CPUs instead of being limited by the parent's single loop. Each time the
+/// parent receives metrics, it collects what the tasks merged into
+/// [`SwanlingMetrics::requests`], so the metrics are complete whenever they're displayed.
+#[derive(Debug)]
+pub(crate) struct SwanlingMetricsShards {
+    shards: Vec<flume::Sender<SwanlingMetricsShardCommand>>,
+}
+impl SwanlingMetricsShards {
+    /// Spawn one task per CPU, up to `MAX_METRICS_SHARDS`. The tasks exit when this is dropped.
+    pub(crate) fn new() -> Self {
+        let shards = (0..num_cpus::get().clamp(1, MAX_METRICS_SHARDS))
+            .map(|_| {
+                let (shard_tx, shard_rx) = flume::unbounded();
+                tokio::spawn(merge_request_metrics(shard_rx));
+                shard_tx
+            })
+            .collect();
+        SwanlingMetricsShards { shards }
+    }
+
+    /// Merge metrics aggregated by a user on the task assigned to this request.
+    pub(crate) fn merge(&self, key: String, request: SwanlingRequestMetricAggregate) {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let shard = hasher.finish() as usize % self.shards.len();
+        // The tasks run until this is dropped, so sending can't fail.
+        let _ = self.shards[shard].send(SwanlingMetricsShardCommand::Merge(key, Box::new(request)));
+    }
+
+    /// Collect the request metrics merged by all tasks since they were last collected.
+    pub(crate) async fn collect(&self) -> Vec<SwanlingRequestMetrics> {
+        // Ask all tasks first, so they finish merging in parallel.
+        let replies: Vec<_> = self
+            .shards
+            .iter()
+            .map(|shard| {
+                let (reply_tx, reply_rx) = flume::bounded(1);
+                let _ = shard.send(SwanlingMetricsShardCommand::Collect(reply_tx));
+                reply_rx
+            })
+            .collect();
+        let mut collected = Vec::new();
+        for reply in replies {
+            if let Ok(requests) = reply.recv_async().await {
+                collected.push(requests);
+            }
+        }
+        collected
+    }
+}
+
+// Merge request metrics until the parent drops its sender.
+async fn merge_request_metrics(receiver: flume::Receiver<SwanlingMetricsShardCommand>) {
+    let mut requests = SwanlingRequestMetrics::new();
+    while let Ok(command) = receiver.recv_async().await {
+        match command {
+            SwanlingMetricsShardCommand::Merge(key, request) => match requests.get_mut(&key) {
+                Some(merge_request) => merge_request.merge(&request),
+                None => {
+                    requests.insert(key, *request);
+                }
+            },
+            SwanlingMetricsShardCommand::Collect(reply) => {
+                let _ = reply.send(std::mem::take(&mut requests));
+            }
+        }
+    }
 }
 
 /// All metrics optionally collected during a Swanling load test.
@@ -801,11 +1409,15 @@ impl SwanlingTaskMetricAggregate {
 ///     Ok(())
 /// }
 /// ```
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(from = "SerializedSwanlingMetrics")]
 pub struct SwanlingMetrics {
     /// A hash of the load test, primarily used to validate all Workers in a Regatta
     /// are running the same load test.
     pub hash: u64,
+    /// A unique ID identifying this run of the load test in logs, metrics and reports,
+    /// configured with the `--run-id` run-time option or a random UUID.
+    pub run_id: String,
     /// An optional system timestamp indicating when the load test started.
     pub started: Option<DateTime<Local>>,
     /// Total number of seconds the load test ran.
@@ -834,16 +1446,82 @@ pub struct SwanlingMetrics {
     /// [SwanlingDefault::NoErrorSummary](../enum.SwanlingDefault.html#variant.NoErrorSummary) or
     /// [SwanlingDefault::NoMetrics](../enum.SwanlingDefault.html#variant.NoMetrics).
     pub errors: SwanlingErrorMetrics,
+    /// Counts how many users were removed from the load test by each error returned by an
+    /// [`on_start` session function](../swanling/struct.SwanlingTaskSet.html#method.register_on_start_session).
+    pub removed_users: BTreeMap<String, usize>,
+    /// Counts how many times each assertion checked with
+    /// [`SwanlingUser::assert`](../swanling/struct.SwanlingUser.html#method.assert) passed
+    /// and failed, by label.
+    pub assertions: SwanlingAssertionMetrics,
+    /// Counts how many times each poll started with
+    /// [`SwanlingUser::poll_until`](../swanling/struct.SwanlingUser.html#method.poll_until)
+    /// met its condition or timed out, how many requests it made and how long it waited, by
+    /// name.
+    pub polls: SwanlingPollMetrics,
+    /// Counts how many times each query run with a
+    /// [`SwanlingSqlPool`](../sql/struct.SwanlingSqlPool.html) succeeded and failed and how
+    /// long it took, by name. Only collected with the `sql` feature.
+    pub queries: SwanlingQueryMetrics,
+    /// Metadata describing the load test, configured with the `--label` run-time option.
+    pub labels: BTreeMap<String, String>,
+    /// Notes added with the Controller `annotate` command while the load test is running.
+    pub annotations: Vec<SwanlingAnnotation>,
+    /// The resources used by the load generator itself while running the load test.
+    pub generator: SwanlingGeneratorMetrics,
+    /// Why the load test was stopped early by `--abort-on-error-rate`, or why its metrics
+    /// are partial if ctrl-c was caught again before all users exited.
+    pub aborted: Option<String>,
     /// Flag indicating whether or not these are the final metrics, used to determine
     /// which metrics should be displayed. Defaults to false.
     pub(crate) final_metrics: bool,
     /// Flag indicating whether or not to display status_codes. Defaults to false.
     pub(crate) display_status_codes: bool,
+    /// Flag indicating whether or not to display response compression. Defaults to false.
+    pub(crate) display_compression: bool,
     /// Flag indicating whether or not to display metrics. This defaults to false on
     /// Workers, otherwise true.
     pub(crate) display_metrics: bool,
+    /// The format running metrics are displayed in. Defaults to
+    /// [`SwanlingMetricsFormat::Table`](./enum.SwanlingMetricsFormat.html#variant.Table).
+    pub(crate) running_metrics_format: SwanlingMetricsFormat,
+    /// Flag indicating whether or not to only display the final metrics. Defaults to false.
+    pub(crate) summary_only: bool,
+    /// The format the final metrics are displayed in. Defaults to
+    /// [`SwanlingMetricsFormat::Table`](./enum.SwanlingMetricsFormat.html#variant.Table).
+    pub(crate) summary_format: SwanlingMetricsFormat,
+    /// The response time percentiles reported, configured with `--percentiles`.
+    pub(crate) percentiles: Vec<f64>,
 }
 impl SwanlingMetrics {
+    /// The response time percentiles reported, falling back to the defaults if none
+    /// are configured.
+    pub(crate) fn get_percentiles(&self) -> &[f64] {
+        if self.percentiles.is_empty() {
+            DEFAULT_PERCENTILES
+        } else {
+            &self.percentiles
+        }
+    }
+
+    /// Calculates the response times within each reported percentile, for each request
+    /// and for all requests aggregated.
+    fn percentile_metrics(&self) -> BTreeMap<String, BTreeMap<String, usize>> {
+        let mut aggregate = SwanlingRequestMetricTimingData::new(None);
+        let mut percentile_metrics = BTreeMap::new();
+        for (request_key, request) in &self.requests {
+            aggregate.merge(&request.raw_data);
+            percentile_metrics.insert(
+                request_key.to_string(),
+                request.raw_data.percentiles(self.get_percentiles()),
+            );
+        }
+        percentile_metrics.insert(
+            "Aggregated".to_string(),
+            aggregate.percentiles(self.get_percentiles()),
+        );
+        percentile_metrics
+    }
+
     /// Initialize the task_metrics vector.
     pub(crate) fn initialize_task_metrics(
         &mut self,
@@ -867,6 +1545,118 @@ impl SwanlingMetrics {
         }
     }
 
+    /// Merges metrics collected by another load test into these metrics, for example to
+    /// combine the metrics of several Workers into a single report.
+    pub(crate) fn merge(&mut self, other: &SwanlingMetrics) {
+        if self.hash != other.hash {
+            info!("merging metrics from different load tests");
+        }
+        if self.run_id.is_empty() {
+            self.run_id = other.run_id.clone();
+        }
+
+        // The merged load test runs from the earliest start to the latest end.
+        self.started = match (self.started, other.started) {
+            (Some(started), Some(other_started)) => {
+                let end = std::cmp::max(
+                    started + chrono::Duration::seconds(self.duration as i64),
+                    other_started + chrono::Duration::seconds(other.duration as i64),
+                );
+                let started = std::cmp::min(started, other_started);
+                self.duration = (end - started).num_seconds() as usize;
+                Some(started)
+            }
+            (None, Some(other_started)) => {
+                self.duration = other.duration;
+                Some(other_started)
+            }
+            (started, None) => {
+                self.duration = std::cmp::max(self.duration, other.duration);
+                started
+            }
+        };
+        self.users += other.users;
+
+        for (request_key, request) in &other.requests {
+            match self.requests.get_mut(request_key) {
+                Some(merged_request) => merged_request.merge(request),
+                None => {
+                    self.requests
+                        .insert(request_key.to_string(), request.clone());
+                }
+            }
+        }
+
+        // Tasks are identified by their index, so must be merged from the same load test.
+        for (task_set_index, task_set) in other.tasks.iter().enumerate() {
+            if task_set_index >= self.tasks.len() {
+                self.tasks.push(task_set.clone());
+                continue;
+            }
+            for (task_index, task) in task_set.iter().enumerate() {
+                match self.tasks[task_set_index].get_mut(task_index) {
+                    Some(merged_task) => merged_task.merge(task),
+                    None => self.tasks[task_set_index].push(task.clone()),
+                }
+            }
+        }
+
+        for (error_key, error) in &other.errors {
+            match self.errors.get_mut(error_key) {
+                Some(merged_error) => merged_error.occurrences += error.occurrences,
+                None => {
+                    self.errors.insert(error_key.to_string(), error.clone());
+                }
+            }
+        }
+
+        for (error, count) in &other.removed_users {
+            *self.removed_users.entry(error.to_string()).or_insert(0) += count;
+        }
+
+        for (label, assertion) in &other.assertions {
+            self.assertions
+                .entry(label.to_string())
+                .or_default()
+                .merge(assertion);
+        }
+
+        for (name, poll) in &other.polls {
+            self.polls.entry(name.to_string()).or_default().merge(poll);
+        }
+
+        for (name, query) in &other.queries {
+            self.queries
+                .entry(name.to_string())
+                .or_default()
+                .merge(query);
+        }
+
+        // Labels configured for the first load test take precedence.
+        for (key, value) in &other.labels {
+            self.labels
+                .entry(key.to_string())
+                .or_insert_with(|| value.to_string());
+        }
+
+        // Annotations are sorted, and their elapsed time is relative to the merged start.
+        self.annotations.extend(other.annotations.iter().cloned());
+        self.annotations
+            .sort_by_key(|annotation| annotation.timestamp);
+        if let Some(started) = self.started {
+            for annotation in &mut self.annotations {
+                annotation.elapsed = (annotation.timestamp - started.timestamp()).max(0) as u64;
+            }
+        }
+
+        self.generator.merge(&other.generator);
+
+        // The first load test that was aborted explains why.
+        if self.aborted.is_none() {
+            self.aborted = other.aborted.clone();
+        }
+    }
+
     /// Consumes and display all enabled metrics from a completed load test.
     ///
     /// # Example
@@ -897,7 +1687,14 @@ impl SwanlingMetrics {
     pub fn print(&self) {
         if self.display_metrics {
             info!("printing final metrics after {} seconds...", self.duration);
-            print!("{}", self);
+            match self.summary_format {
+                SwanlingMetricsFormat::Table => print!("{}", self),
+                SwanlingMetricsFormat::Compact => println!("{}", self.fmt_compact()),
+                SwanlingMetricsFormat::Json => println!(
+                    "{}",
+                    serde_json::to_string(self).expect("unexpected serde failure")
+                ),
+            }
         }
     }
 
@@ -906,17 +1703,69 @@ impl SwanlingMetrics {
     /// This function is invoked one time immediately after all SwanlingUsers are
     /// started, unless the `--no-reset-metrics` run-time option is enabled. It
     /// is invoked at regular intervals if the `--running-metrics` run-time
-    /// option is enabled.
+    /// option is enabled. Running metrics are not displayed with `--summary-only`.
     pub(crate) fn print_running(&self) {
-        if self.display_metrics {
+        if self.display_metrics && !self.summary_only {
             info!(
                 "printing running metrics after {} seconds...",
                 self.duration
             );
 
-            // Include a blank line after printing running metrics.
-            println!("{}", self);
+            match self.running_metrics_format {
+                // Include a blank line after printing running metrics.
+                SwanlingMetricsFormat::Table => println!("{}", self),
+                SwanlingMetricsFormat::Compact => println!("{}", self.fmt_compact()),
+                SwanlingMetricsFormat::Json => println!(
+                    "{}",
+                    serde_json::to_string(self).expect("unexpected serde failure")
+                ),
+            }
+        }
+    }
+
+    /// Summarizes all requests on a single line, used when displaying metrics in the
+    /// compact format.
+    pub(crate) fn fmt_compact(&self) -> String {
+        let mut total_count = 0;
+        let mut fail_count = 0;
+        let mut total_time = 0;
+        let mut counter = 0;
+        for request in self.requests.values() {
+            total_count += request.success_count + request.fail_count;
+            fail_count += request.fail_count;
+            total_time += request.raw_data.total_time;
+            counter += request.raw_data.counter;
+        }
+        let fail_percent = if total_count > 0 {
+            fail_count as f32 / total_count as f32 * 100.0
+        } else {
+            0.0
+        };
+        let average = if counter > 0 {
+            total_time as f32 / counter as f32
+        } else {
+            0.0
+        };
+        let (reqs, fails) = per_second_calculations(self.duration, total_count, fail_count);
+        let mut compact = format!(
+            "[{}s] users: {} requests: {} fails: {} ({:.1}%) req/s: {:.2} fail/s: {:.2} avg: {:.2}ms",
+            self.duration,
+            self.users,
+            total_count.to_formatted_string(&Locale::en),
+            fail_count.to_formatted_string(&Locale::en),
+            fail_percent,
+            reqs,
+            fails,
+            average,
+        );
+        // Include the resources used by the load generator, if sampled.
+        if self.generator.samples > 0 {
+            compact.push_str(&format!(
+                " cpu: {:.1}% mem: {:.1}MB",
+                self.generator.cpu.current, self.generator.memory.current
+            ));
         }
+        compact
     }
 
     /// Optionally prepares a table of requests and fails.
@@ -1046,6 +1895,71 @@ impl SwanlingMetrics {
         Ok(())
     }
 
+    /// Summarizes requests by the host they were made to. Requests recorded without a host,
+    /// for example when merging metrics written by an earlier version, aren't included.
+    pub(crate) fn host_metrics(&self) -> BTreeMap<&str, SwanlingHostMetric> {
+        let mut hosts: BTreeMap<&str, SwanlingHostMetric> = BTreeMap::new();
+        for request in self.requests.values() {
+            if request.host.is_empty() {
+                continue;
+            }
+            let host = hosts.entry(&request.host).or_default();
+            host.success_count += request.success_count;
+            host.fail_count += request.fail_count;
+            host.total_time += request.raw_data.total_time;
+            host.counter += request.raw_data.counter;
+        }
+        hosts
+    }
+
+    /// Optionally prepares a table of requests made to each host, if requests were made to
+    /// more than one host.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_hosts(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hosts = self.host_metrics();
+
+        // If there's nothing to display, exit immediately.
+        if hosts.len() < 2 {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === PER HOST METRICS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>13} | {:>14} | {:>8} | {:>7}",
+            "Host", "# reqs", "# fails", "req/s", "avg ms"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for (host, metric) in hosts {
+            let total_count = metric.success_count + metric.fail_count;
+            let (reqs, _) = per_second_calculations(self.duration, total_count, metric.fail_count);
+            writeln!(
+                fmt,
+                " {:<24} | {:>13} | {:>14} | {:>8.reqs_p$} | {:>7}",
+                util::truncate_string(host, 24),
+                total_count.to_formatted_string(&Locale::en),
+                format!(
+                    "{} ({}%)",
+                    metric.fail_count.to_formatted_string(&Locale::en),
+                    metric.fail_percent()
+                ),
+                reqs,
+                metric.average_time(),
+                reqs_p = determine_precision(reqs),
+            )?;
+        }
+
+        Ok(())
+    }
+
     /// Optionally prepares a table of tasks.
     ///
     /// This function is invoked by `SwanlingMetrics::print()` and
@@ -1560,11 +2474,15 @@ impl SwanlingMetrics {
             return Ok(());
         }
 
-        let mut raw_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
-        let mut raw_aggregate_total_response_time: usize = 0;
-        let mut raw_aggregate_response_time_counter: usize = 0;
-        let mut raw_aggregate_min_response_time: usize = 0;
-        let mut raw_aggregate_max_response_time: usize = 0;
+        let percentiles = self.get_percentiles();
+        let headers: Vec<String> = percentiles.iter().map(|p| format!("{}%", p)).collect();
+        // Add a column for each percentile, the last column is one character narrower so
+        // the default table fits in 80 columns.
+        let mut separator = format!(" {}", "-".repeat(25));
+        separator.push_str(&"+--------".repeat(percentiles.len()));
+        separator.pop();
+
+        let mut raw_aggregate = SwanlingRequestMetricTimingData::new(None);
         writeln!(
             fmt,
             " ------------------------------------------------------------------------------"
@@ -1577,11 +2495,7 @@ impl SwanlingMetrics {
             fmt,
             " ------------------------------------------------------------------------------"
         )?;
-        writeln!(
-            fmt,
-            " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-            "Name", "50%", "75%", "98%", "99%", "99.9%", "99.99%"
-        )?;
+        writeln!(fmt, "{}", percentiles_row("Name", &headers))?;
         writeln!(
             fmt,
             " ------------------------------------------------------------------------------"
@@ -1593,127 +2507,27 @@ impl SwanlingMetrics {
                 co_data = true;
             }
 
-            // Iterate over user response times, and merge into global response times.
-            raw_aggregate_response_times =
-                merge_times(raw_aggregate_response_times, request.raw_data.times.clone());
-
-            // Increment total response time counter.
-            raw_aggregate_total_response_time += &request.raw_data.total_time;
-
-            // Increment counter tracking individual response times seen.
-            raw_aggregate_response_time_counter += &request.raw_data.counter;
-
-            // If user had new fastest response time, update global fastest response time.
-            raw_aggregate_min_response_time = update_min_time(
-                raw_aggregate_min_response_time,
-                request.raw_data.minimum_time,
-            );
+            // Merge response times into the aggregated response times.
+            raw_aggregate.merge(&request.raw_data);
 
-            // If user had new slowest response time, update global slowest response time.
-            raw_aggregate_max_response_time = update_max_time(
-                raw_aggregate_max_response_time,
-                request.raw_data.maximum_time,
-            );
-            // Sort response times so we can calculate a mean.
             writeln!(
                 fmt,
-                " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-                util::truncate_string(&request_key, 24),
-                calculate_response_time_percentile(
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                    0.5
-                ),
-                calculate_response_time_percentile(
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                    0.75
-                ),
-                calculate_response_time_percentile(
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                    0.98
-                ),
-                calculate_response_time_percentile(
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                    0.99
-                ),
-                calculate_response_time_percentile(
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                    0.999
-                ),
-                calculate_response_time_percentile(
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                    0.9999
-                ),
+                "{}",
+                percentiles_row(
+                    request_key,
+                    &percentile_values(&request.raw_data, percentiles)
+                )
             )?;
         }
         if self.requests.len() > 1 {
+            writeln!(fmt, "{}", separator)?;
             writeln!(
                 fmt,
-                " -------------------------+--------+--------+--------+--------+--------+-------"
-            )?;
-            writeln!(
-                fmt,
-                " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-                "Aggregated",
-                calculate_response_time_percentile(
-                    &raw_aggregate_response_times,
-                    raw_aggregate_response_time_counter,
-                    raw_aggregate_min_response_time,
-                    raw_aggregate_max_response_time,
-                    0.5
-                ),
-                calculate_response_time_percentile(
-                    &raw_aggregate_response_times,
-                    raw_aggregate_response_time_counter,
-                    raw_aggregate_min_response_time,
-                    raw_aggregate_max_response_time,
-                    0.75
-                ),
-                calculate_response_time_percentile(
-                    &raw_aggregate_response_times,
-                    raw_aggregate_response_time_counter,
-                    raw_aggregate_min_response_time,
-                    raw_aggregate_max_response_time,
-                    0.98
-                ),
-                calculate_response_time_percentile(
-                    &raw_aggregate_response_times,
-                    raw_aggregate_response_time_counter,
-                    raw_aggregate_min_response_time,
-                    raw_aggregate_max_response_time,
-                    0.99
-                ),
-                calculate_response_time_percentile(
-                    &raw_aggregate_response_times,
-                    raw_aggregate_response_time_counter,
-                    raw_aggregate_min_response_time,
-                    raw_aggregate_max_response_time,
-                    0.999
-                ),
-                calculate_response_time_percentile(
-                    &raw_aggregate_response_times,
-                    raw_aggregate_response_time_counter,
-                    raw_aggregate_min_response_time,
-                    raw_aggregate_max_response_time,
-                    0.9999
-                ),
+                "{}",
+                percentiles_row(
+                    "Aggregated",
+                    &percentile_values(&raw_aggregate, percentiles)
+                )
             )?;
         }
 
@@ -1722,12 +2536,7 @@ impl SwanlingMetrics {
             return Ok(());
         }
 
-        let mut co_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
-        let mut co_aggregate_total_response_time: usize = 0;
-        let mut co_aggregate_response_time_counter: usize = 0;
-        let mut co_aggregate_min_response_time: usize = 0;
-        let mut co_aggregate_max_response_time: usize = 0;
-
+        let mut co_aggregate = SwanlingRequestMetricTimingData::new(None);
         writeln!(
             fmt,
             " ------------------------------------------------------------------------------"
@@ -1737,154 +2546,38 @@ impl SwanlingMetrics {
             fmt,
             " ------------------------------------------------------------------------------"
         )?;
-        writeln!(
-            fmt,
-            " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-            "Name", "50%", "75%", "98%", "99%", "99.9%", "99.99%"
-        )?;
+        writeln!(fmt, "{}", percentiles_row("Name", &headers))?;
         writeln!(
             fmt,
             " ------------------------------------------------------------------------------"
         )?;
         for (request_key, request) in self.requests.iter().sorted() {
             if let Some(coordinated_omission_data) = request.coordinated_omission_data.as_ref() {
-                // Iterate over user response times, and merge into global response times.
-                co_aggregate_response_times = merge_times(
-                    co_aggregate_response_times,
-                    coordinated_omission_data.times.clone(),
-                );
-
-                // Increment total response time counter.
-                co_aggregate_total_response_time += &coordinated_omission_data.total_time;
+                // Merge response times into the aggregated response times.
+                co_aggregate.merge(coordinated_omission_data);
 
-                // Increment counter tracking individual response times seen.
-                co_aggregate_response_time_counter += &coordinated_omission_data.counter;
-
-                // If user had new fastest response time, update global fastest response time.
-                co_aggregate_min_response_time = update_min_time(
-                    co_aggregate_min_response_time,
-                    coordinated_omission_data.minimum_time,
-                );
-
-                // If user had new slowest response time, update global slowest response time.
-                co_aggregate_max_response_time = update_max_time(
-                    co_aggregate_max_response_time,
-                    coordinated_omission_data.maximum_time,
-                );
-
-                // Sort response times so we can calculate a mean.
                 writeln!(
                     fmt,
-                    " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-                    util::truncate_string(&request_key, 24),
-                    calculate_response_time_percentile(
-                        &coordinated_omission_data.times,
-                        coordinated_omission_data.counter,
-                        coordinated_omission_data.minimum_time,
-                        coordinated_omission_data.maximum_time,
-                        0.5
-                    ),
-                    calculate_response_time_percentile(
-                        &coordinated_omission_data.times,
-                        coordinated_omission_data.counter,
-                        coordinated_omission_data.minimum_time,
-                        coordinated_omission_data.maximum_time,
-                        0.75
-                    ),
-                    calculate_response_time_percentile(
-                        &coordinated_omission_data.times,
-                        coordinated_omission_data.counter,
-                        coordinated_omission_data.minimum_time,
-                        coordinated_omission_data.maximum_time,
-                        0.98
-                    ),
-                    calculate_response_time_percentile(
-                        &coordinated_omission_data.times,
-                        coordinated_omission_data.counter,
-                        coordinated_omission_data.minimum_time,
-                        coordinated_omission_data.maximum_time,
-                        0.99
-                    ),
-                    calculate_response_time_percentile(
-                        &coordinated_omission_data.times,
-                        coordinated_omission_data.counter,
-                        coordinated_omission_data.minimum_time,
-                        coordinated_omission_data.maximum_time,
-                        0.999
-                    ),
-                    calculate_response_time_percentile(
-                        &coordinated_omission_data.times,
-                        coordinated_omission_data.counter,
-                        coordinated_omission_data.minimum_time,
-                        coordinated_omission_data.maximum_time,
-                        0.9999
-                    ),
+                    "{}",
+                    percentiles_row(
+                        request_key,
+                        &percentile_values(coordinated_omission_data, percentiles)
+                    )
                 )?;
             } else {
                 writeln!(
                     fmt,
-                    " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-                    util::truncate_string(&request_key, 24),
-                    "-",
-                    "-",
-                    "-",
-                    "-",
-                    "-",
-                    "-"
+                    "{}",
+                    percentiles_row(request_key, &vec!["-".to_string(); percentiles.len()])
                 )?;
             }
         }
         if self.requests.len() > 1 {
+            writeln!(fmt, "{}", separator)?;
             writeln!(
                 fmt,
-                " -------------------------+--------+--------+--------+--------+--------+-------"
-            )?;
-            writeln!(
-                fmt,
-                " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-                "Aggregated",
-                calculate_response_time_percentile(
-                    &co_aggregate_response_times,
-                    co_aggregate_response_time_counter,
-                    co_aggregate_min_response_time,
-                    co_aggregate_max_response_time,
-                    0.5
-                ),
-                calculate_response_time_percentile(
-                    &co_aggregate_response_times,
-                    co_aggregate_response_time_counter,
-                    co_aggregate_min_response_time,
-                    co_aggregate_max_response_time,
-                    0.75
-                ),
-                calculate_response_time_percentile(
-                    &co_aggregate_response_times,
-                    co_aggregate_response_time_counter,
-                    co_aggregate_min_response_time,
-                    co_aggregate_max_response_time,
-                    0.98
-                ),
-                calculate_response_time_percentile(
-                    &co_aggregate_response_times,
-                    co_aggregate_response_time_counter,
-                    co_aggregate_min_response_time,
-                    co_aggregate_max_response_time,
-                    0.99
-                ),
-                calculate_response_time_percentile(
-                    &co_aggregate_response_times,
-                    co_aggregate_response_time_counter,
-                    co_aggregate_min_response_time,
-                    co_aggregate_max_response_time,
-                    0.999
-                ),
-                calculate_response_time_percentile(
-                    &co_aggregate_response_times,
-                    co_aggregate_response_time_counter,
-                    co_aggregate_min_response_time,
-                    co_aggregate_max_response_time,
-                    0.9999
-                ),
+                "{}",
+                percentiles_row("Aggregated", &percentile_values(&co_aggregate, percentiles))
             )?;
         }
 
@@ -1934,6 +2627,295 @@ impl SwanlingMetrics {
         Ok(())
     }
 
+    /// Optionally prepares a table showing how requests were distributed between backends.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_backends(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut backends: HashMap<&str, usize> = HashMap::new();
+        for request in self.requests.values() {
+            for (backend, count) in &request.backend_counts {
+                *backends.entry(backend).or_insert(0) += count;
+            }
+        }
+
+        // If there's nothing to display, exit immediately.
+        if backends.is_empty() {
+            return Ok(());
+        }
+
+        let total: usize = backends.values().sum();
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        writeln!(fmt, " {:<24} | {:>25} | {:>23} ", "Backend", "# reqs", "%")?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for (backend, count) in backends.iter().sorted() {
+            writeln!(
+                fmt,
+                " {:<24} | {:>25} | {:>22.2}%",
+                util::truncate_string(backend, 24),
+                count.to_formatted_string(&Locale::en),
+                *count as f32 / total as f32 * 100.0,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table showing how requests were distributed between user agents.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_user_agents(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut user_agents: HashMap<&str, usize> = HashMap::new();
+        for request in self.requests.values() {
+            for (user_agent, count) in &request.user_agent_counts {
+                *user_agents.entry(user_agent).or_insert(0) += count;
+            }
+        }
+
+        // If there's nothing to display, exit immediately.
+        if user_agents.is_empty() {
+            return Ok(());
+        }
+
+        let total: usize = user_agents.values().sum();
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        // User agents often only differ at the end, so they're displayed in full.
+        writeln!(
+            fmt,
+            " {:>11} | {:>8} | {:<51} ",
+            "# reqs", "%", "User agent"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for (user_agent, count) in user_agents.iter().sorted() {
+            writeln!(
+                fmt,
+                " {:>11} | {:>7.2}% | {}",
+                count.to_formatted_string(&Locale::en),
+                *count as f32 / total as f32 * 100.0,
+                user_agent,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table comparing compressed and uncompressed responses.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_compression(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        if !self.display_compression {
+            return Ok(());
+        }
+
+        // Average size of responses, or 0 if there were none.
+        fn average(bytes: u64, count: usize) -> String {
+            match count {
+                0 => "0".to_string(),
+                _ => (bytes / count as u64).to_formatted_string(&Locale::en),
+            }
+        }
+
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>12} | {:>10} | {:>10} | {:>9} ",
+            "Name", "% compressed", "Compressed", "Decoded", "Plain"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let mut aggregate =
+            SwanlingRequestMetricAggregate::new("Aggregated", SwanlingMethod::Get, 0);
+        for (request_key, request) in self.requests.iter().sorted() {
+            aggregate.compressed_count += request.compressed_count;
+            aggregate.compressed_bytes += request.compressed_bytes;
+            aggregate.decoded_count += request.decoded_count;
+            aggregate.decoded_bytes += request.decoded_bytes;
+            aggregate.uncompressed_count += request.uncompressed_count;
+            aggregate.uncompressed_bytes += request.uncompressed_bytes;
+            writeln!(
+                fmt,
+                " {:<24} | {:>11.2}% | {:>10} | {:>10} | {:>9}",
+                util::truncate_string(request_key, 24),
+                request.compressed_count as f32
+                    / (request.compressed_count + request.uncompressed_count).max(1) as f32
+                    * 100.0,
+                average(request.compressed_bytes, request.compressed_count),
+                average(request.decoded_bytes, request.decoded_count),
+                average(request.uncompressed_bytes, request.uncompressed_count),
+            )?;
+        }
+        writeln!(
+            fmt,
+            " -------------------------+--------------+------------+------------+-----------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>11.2}% | {:>10} | {:>10} | {:>9}",
+            "Aggregated",
+            aggregate.compressed_count as f32
+                / (aggregate.compressed_count + aggregate.uncompressed_count).max(1) as f32
+                * 100.0,
+            average(aggregate.compressed_bytes, aggregate.compressed_count),
+            average(aggregate.decoded_bytes, aggregate.decoded_count),
+            average(aggregate.uncompressed_bytes, aggregate.uncompressed_count),
+        )?;
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of how often conditional requests found the cached
+    /// response wasn't modified, if any conditional requests were made.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_cache(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        if !self.display_metrics
+            || self
+                .requests
+                .values()
+                .all(|request| request.not_modified_count + request.modified_count == 0)
+        {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === CACHE REVALIDATION ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>15} | {:>15} | {:>15} ",
+            "Name", "# not modified", "# modified", "% not modified"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let mut aggregate =
+            SwanlingRequestMetricAggregate::new("Aggregated", SwanlingMethod::Get, 0);
+        for (request_key, request) in self.requests.iter().sorted() {
+            if request.not_modified_count + request.modified_count == 0 {
+                continue;
+            }
+            aggregate.not_modified_count += request.not_modified_count;
+            aggregate.modified_count += request.modified_count;
+            writeln!(
+                fmt,
+                " {:<24} | {:>15} | {:>15} | {:>14.2}%",
+                util::truncate_string(request_key, 24),
+                request.not_modified_count.to_formatted_string(&Locale::en),
+                request.modified_count.to_formatted_string(&Locale::en),
+                request.not_modified_count as f32
+                    / (request.not_modified_count + request.modified_count) as f32
+                    * 100.0,
+            )?;
+        }
+        writeln!(
+            fmt,
+            " -------------------------+-----------------+-----------------+-----------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>15} | {:>15} | {:>14.2}%",
+            "Aggregated",
+            aggregate
+                .not_modified_count
+                .to_formatted_string(&Locale::en),
+            aggregate.modified_count.to_formatted_string(&Locale::en),
+            aggregate.not_modified_count as f32
+                / (aggregate.not_modified_count + aggregate.modified_count) as f32
+                * 100.0,
+        )?;
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of the redirects followed, if any requests were
+    /// redirected.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_redirects(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        if !self.display_metrics
+            || self
+                .requests
+                .values()
+                .all(|request| request.redirected_count == 0)
+        {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === REDIRECTS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>12} | {:>9} | {:>9} | {:>11}",
+            "Name", "# redirected", "avg chain", "max chain", "avg ms"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let mut aggregate =
+            SwanlingRequestMetricAggregate::new("Aggregated", SwanlingMethod::Get, 0);
+        for (request_key, request) in self.requests.iter().sorted() {
+            if request.redirected_count == 0 {
+                continue;
+            }
+            aggregate.merge(request);
+            writeln!(
+                fmt,
+                " {:<24} | {:>12} | {:>9.2} | {:>9} | {:>11}",
+                util::truncate_string(request_key, 24),
+                request.redirected_count.to_formatted_string(&Locale::en),
+                request.redirect_count as f32 / request.redirected_count as f32,
+                request.max_redirects,
+                (request.redirect_time / request.redirected_count as u64)
+                    .to_formatted_string(&Locale::en),
+            )?;
+        }
+        writeln!(
+            fmt,
+            " -------------------------+--------------+-----------+-----------+-------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>12} | {:>9.2} | {:>9} | {:>11}",
+            "Aggregated",
+            aggregate.redirected_count.to_formatted_string(&Locale::en),
+            aggregate.redirect_count as f32 / aggregate.redirected_count as f32,
+            aggregate.max_redirects,
+            (aggregate.redirect_time / aggregate.redirected_count as u64)
+                .to_formatted_string(&Locale::en),
+        )?;
+
+        Ok(())
+    }
+
     /// Optionally prepares a table of errors.
     ///
     /// This function is invoked by `SwanlingMetrics::print()` and
@@ -1945,34 +2927,350 @@ impl SwanlingMetrics {
             return Ok(());
         }
 
-        // Write the errors into a vector which can then be sorted by occurrences.
-        let mut errors: Vec<(usize, String)> = Vec::new();
+        // Write the errors into a vector which can then be sorted by occurrences, while
+        // also totaling how many errors occurred in each category.
+        let mut errors: Vec<(usize, &str, String)> = Vec::new();
+        let mut categories: HashMap<&str, usize> = HashMap::new();
         for error in self.errors.values() {
             errors.push((
                 error.occurrences,
-                format!("{} {}: {}", error.method, error.name, error.error),
+                &error.category,
+                if error.tags.is_empty() {
+                    format!("{} {}: {}", error.method, error.name, error.error)
+                } else {
+                    format!(
+                        "{} {} [{}]: {}",
+                        error.method,
+                        error.name,
+                        format_tags(&error.tags),
+                        error.error
+                    )
+                },
             ));
+            *categories.entry(&error.category).or_insert(0) += error.occurrences;
+        }
+
+        writeln!(
+            fmt,
+            "\n === ERRORS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(fmt, " {:<11} | Category", "Count")?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+
+        // Reverse sort categories to display the category occuring the most first.
+        for (occurrences, category) in categories
+            .iter()
+            .map(|(category, occurrences)| (occurrences, category))
+            .sorted()
+            .rev()
+        {
+            writeln!(fmt, " {:<12}  {}", format_number(*occurrences), category)?;
+        }
+
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        writeln!(fmt, " {:<11} | {:<17} | Error", "Count", "Category")?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+
+        // Reverse sort errors to display the error occuring the most first.
+        for (occurrences, category, error) in errors.iter().sorted().rev() {
+            writeln!(
+                fmt,
+                " {:<12}  {:<17}   {}",
+                format_number(*occurrences),
+                category,
+                error
+            )?;
+        }
+
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of how many times each assertion passed and failed.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_assertions(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        if !self.display_metrics || self.assertions.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === ASSERTIONS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>15} | {:>15} | {:>15} ",
+            "Label", "# passed", "# failed", "% passed"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let mut aggregate = SwanlingAssertionMetric::default();
+        for (label, assertion) in &self.assertions {
+            aggregate.merge(assertion);
+            writeln!(
+                fmt,
+                " {:<24} | {:>15} | {:>15} | {:>14.2}%",
+                util::truncate_string(label, 24),
+                assertion.passed.to_formatted_string(&Locale::en),
+                assertion.failed.to_formatted_string(&Locale::en),
+                assertion.passed_percent(),
+            )?;
+        }
+        writeln!(
+            fmt,
+            " -------------------------+-----------------+-----------------+-----------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>15} | {:>15} | {:>14.2}%",
+            "Aggregated",
+            aggregate.passed.to_formatted_string(&Locale::en),
+            aggregate.failed.to_formatted_string(&Locale::en),
+            aggregate.passed_percent(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of how many times each poll met its condition or timed
+    /// out, and how long it took.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_polls(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        if !self.display_metrics || self.polls.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === POLLS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>9} | {:>11} | {:>9} | {:>11}",
+            "Name", "# met", "# timed out", "avg polls", "avg wait ms"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let mut aggregate = SwanlingPollMetric::default();
+        for (name, poll) in &self.polls {
+            aggregate.merge(poll);
+            writeln!(
+                fmt,
+                " {:<24} | {:>9} | {:>11} | {:>9.2} | {:>11}",
+                util::truncate_string(name, 24),
+                poll.met.to_formatted_string(&Locale::en),
+                poll.timed_out.to_formatted_string(&Locale::en),
+                poll.average_iterations(),
+                poll.average_wait_time().to_formatted_string(&Locale::en),
+            )?;
+        }
+        writeln!(
+            fmt,
+            " -------------------------+-----------+-------------+-----------+-------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>9} | {:>11} | {:>9.2} | {:>11}",
+            "Aggregated",
+            aggregate.met.to_formatted_string(&Locale::en),
+            aggregate.timed_out.to_formatted_string(&Locale::en),
+            aggregate.average_iterations(),
+            aggregate
+                .average_wait_time()
+                .to_formatted_string(&Locale::en),
+        )?;
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of how many times each query succeeded and failed, and
+    /// how long it took.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_queries(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        if !self.display_metrics || self.queries.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === QUERIES ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>9} | {:>8} | {:>9} | {:>9} | {:>9}",
+            "Name", "# queries", "# fails", "Avg (ms)", "Min (ms)", "Max (ms)"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let mut aggregate = SwanlingQueryMetric::default();
+        for (name, query) in &self.queries {
+            aggregate.merge(query);
+            writeln!(
+                fmt,
+                " {:<24} | {:>9} | {:>8} | {:>9} | {:>9} | {:>9}",
+                util::truncate_string(name, 24),
+                query.count().to_formatted_string(&Locale::en),
+                query.fail_count.to_formatted_string(&Locale::en),
+                query.average_time().to_formatted_string(&Locale::en),
+                query.min_time.to_formatted_string(&Locale::en),
+                query.max_time.to_formatted_string(&Locale::en),
+            )?;
+        }
+        writeln!(
+            fmt,
+            " -------------------------+-----------+----------+-----------+-----------+-----------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>9} | {:>8} | {:>9} | {:>9} | {:>9}",
+            "Aggregated",
+            aggregate.count().to_formatted_string(&Locale::en),
+            aggregate.fail_count.to_formatted_string(&Locale::en),
+            aggregate.average_time().to_formatted_string(&Locale::en),
+            aggregate.min_time.to_formatted_string(&Locale::en),
+            aggregate.max_time.to_formatted_string(&Locale::en),
+        )?;
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of users removed from the load test by a failed
+    /// `on_start` session function.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()`.
+    pub(crate) fn fmt_removed_users(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include removed users when displaying the final metrics report, and if
+        // users were removed.
+        if !self.final_metrics || self.removed_users.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === REMOVED USERS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(fmt, " {:<11} | Error", "Count")?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+
+        // Reverse sort errors to display the error removing the most users first.
+        for (users, error) in self
+            .removed_users
+            .iter()
+            .map(|(error, users)| (users, error))
+            .sorted()
+            .rev()
+        {
+            writeln!(fmt, " {:<12}  {}", format_number(*users), error)?;
+        }
+
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of the resources used by the load generator.
+    ///
+    /// This function is invoked by `SwanlingMetrics::print()` and
+    /// `SwanlingMetrics::print_running()`.
+    pub(crate) fn fmt_generator(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If resource usage wasn't sampled there's nothing to display.
+        if self.generator.samples == 0 {
+            return Ok(());
         }
 
         writeln!(
             fmt,
-            "\n === ERRORS ===\n ------------------------------------------------------------------------------"
+            "\n === LOAD GENERATOR ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>15} | {:>15} | {:>15}",
+            "Resource", "Current", "Average", "Maximum"
         )?;
-        writeln!(fmt, " {:<11} | Error", "Count")?;
         writeln!(
             fmt,
             " ------------------------------------------------------------------------------"
         )?;
-
-        // Reverse sort errors to display the error occuring the most first.
-        for (occurrences, error) in errors.iter().sorted().rev() {
-            writeln!(fmt, " {:<12}  {}", format_number(*occurrences), error)?;
+        let samples = self.generator.samples;
+        for (name, resource, unit, precision) in [
+            ("CPU", &self.generator.cpu, "%", 1),
+            ("Memory", &self.generator.memory, " MB", 1),
+            ("Sockets", &self.generator.sockets, "", 0),
+            ("Tasks", &self.generator.tasks, "", 0),
+        ] {
+            writeln!(
+                fmt,
+                " {:<24} | {:>15} | {:>15} | {:>15}",
+                name,
+                format!("{:.p$}{}", resource.current, unit, p = precision),
+                format!("{:.p$}{}", resource.average(samples), unit, p = precision),
+                format!("{:.p$}{}", resource.maximum, unit, p = precision),
+            )?;
         }
-
         writeln!(
             fmt,
             " ------------------------------------------------------------------------------"
         )?;
+        if self.generator.overloaded() {
+            writeln!(
+                fmt,
+                " WARNING: load generator CPU usage reached {:.1}%, the load generator rather than",
+                self.generator.cpu.maximum
+            )?;
+            writeln!(fmt, " the target may have limited the load test.")?;
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares a warning that the load test was stopped early.
+    ///
+    /// This is displayed last, so it's seen when the load test is stopped.
+    pub(crate) fn fmt_aborted(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(aborted) = self.aborted.as_ref() {
+            writeln!(
+                fmt,
+                "\n === LOAD TEST ABORTED ===\n ------------------------------------------------------------------------------"
+            )?;
+            writeln!(fmt, " {}", aborted)?;
+            writeln!(
+                fmt,
+                " ------------------------------------------------------------------------------"
+            )?;
+        }
 
         Ok(())
     }
@@ -1983,8 +3281,9 @@ impl Serialize for SwanlingMetrics {
     where
         S: Serializer,
     {
-        let mut s = serializer.serialize_struct("SwanlingMetrics", 10)?;
+        let mut s = serializer.serialize_struct("SwanlingMetrics", 21)?;
         s.serialize_field("hash", &self.hash)?;
+        s.serialize_field("run_id", &self.run_id)?;
         // Convert started field to a unix timestamp.
         let timestamp;
         if let Some(started) = self.started {
@@ -1998,13 +3297,84 @@ impl Serialize for SwanlingMetrics {
         s.serialize_field("requests", &self.requests)?;
         s.serialize_field("tasks", &self.tasks)?;
         s.serialize_field("errors", &self.errors)?;
+        s.serialize_field("removed_users", &self.removed_users)?;
+        s.serialize_field("assertions", &self.assertions)?;
+        s.serialize_field("polls", &self.polls)?;
+        s.serialize_field("queries", &self.queries)?;
+        s.serialize_field("labels", &self.labels)?;
+        s.serialize_field("annotations", &self.annotations)?;
+        s.serialize_field("generator", &self.generator)?;
+        s.serialize_field("aborted", &self.aborted)?;
+        s.serialize_field("percentiles", &self.percentile_metrics())?;
         s.serialize_field("final_metrics", &self.final_metrics)?;
         s.serialize_field("display_status_codes", &self.display_status_codes)?;
+        s.serialize_field("display_compression", &self.display_compression)?;
         s.serialize_field("display_metrics", &self.display_metrics)?;
         s.end()
     }
 }
 
+/// The fields of SwanlingMetrics that are serialized, used to deserialize metrics written by
+/// an earlier load test.
+#[derive(Deserialize)]
+struct SerializedSwanlingMetrics {
+    hash: u64,
+    #[serde(default)]
+    run_id: String,
+    started: i64,
+    duration: usize,
+    users: usize,
+    requests: SwanlingRequestMetrics,
+    tasks: SwanlingTaskMetrics,
+    errors: SwanlingErrorMetrics,
+    #[serde(default)]
+    removed_users: BTreeMap<String, usize>,
+    #[serde(default)]
+    assertions: SwanlingAssertionMetrics,
+    #[serde(default)]
+    polls: SwanlingPollMetrics,
+    #[serde(default)]
+    queries: SwanlingQueryMetrics,
+    #[serde(default)]
+    labels: BTreeMap<String, String>,
+    #[serde(default)]
+    annotations: Vec<SwanlingAnnotation>,
+    #[serde(default)]
+    generator: SwanlingGeneratorMetrics,
+    #[serde(default)]
+    aborted: Option<String>,
+}
+impl From<SerializedSwanlingMetrics> for SwanlingMetrics {
+    // SwanlingMetrics deserialization can't be derived because of the started field.
+    fn from(metrics: SerializedSwanlingMetrics) -> Self {
+        // Convert started field from a unix timestamp, 0 if the load test never started.
+        let started = match metrics.started {
+            0 => None,
+            timestamp => Local.timestamp_opt(timestamp, 0).single(),
+        };
+        SwanlingMetrics {
+            hash: metrics.hash,
+            run_id: metrics.run_id,
+            started,
+            duration: metrics.duration,
+            users: metrics.users,
+            requests: metrics.requests,
+            tasks: metrics.tasks,
+            errors: metrics.errors,
+            removed_users: metrics.removed_users,
+            assertions: metrics.assertions,
+            polls: metrics.polls,
+            queries: metrics.queries,
+            labels: metrics.labels,
+            annotations: metrics.annotations,
+            generator: metrics.generator,
+            aborted: metrics.aborted,
+            final_metrics: true,
+            ..Default::default()
+        }
+    }
+}
+
 /// Implement format trait to allow displaying metrics.
 impl fmt::Display for SwanlingMetrics {
     // Implement display of metrics with `{}` marker.
@@ -2014,10 +3384,315 @@ impl fmt::Display for SwanlingMetrics {
         self.fmt_tasks(fmt)?;
         self.fmt_task_times(fmt)?;
         self.fmt_requests(fmt)?;
+        self.fmt_hosts(fmt)?;
         self.fmt_response_times(fmt)?;
         self.fmt_percentiles(fmt)?;
         self.fmt_status_codes(fmt)?;
-        self.fmt_errors(fmt)
+        self.fmt_backends(fmt)?;
+        self.fmt_user_agents(fmt)?;
+        self.fmt_compression(fmt)?;
+        self.fmt_redirects(fmt)?;
+        self.fmt_cache(fmt)?;
+        self.fmt_assertions(fmt)?;
+        self.fmt_polls(fmt)?;
+        self.fmt_queries(fmt)?;
+        self.fmt_removed_users(fmt)?;
+        self.fmt_errors(fmt)?;
+        self.fmt_generator(fmt)?;
+        self.fmt_aborted(fmt)
+    }
+}
+
+/// Requests made to one host, summarized by
+/// [`SwanlingMetrics::host_metrics`](./struct.SwanlingMetrics.html).
+#[derive(Debug, Default)]
+pub(crate) struct SwanlingHostMetric {
+    /// Total number of requests to this host that succeeded.
+    pub success_count: usize,
+    /// Total number of requests to this host that failed.
+    pub fail_count: usize,
+    /// Total response time of all requests to this host, in milliseconds.
+    pub total_time: usize,
+    /// How many response times are included in `total_time`.
+    pub counter: usize,
+}
+impl SwanlingHostMetric {
+    /// The average response time, in milliseconds.
+    pub(crate) fn average_time(&self) -> usize {
+        self.total_time / self.counter.max(1)
+    }
+
+    /// The percentage of requests to this host that failed.
+    pub(crate) fn fail_percent(&self) -> usize {
+        self.fail_count * 100 / (self.success_count + self.fail_count).max(1)
+    }
+}
+
+/// The resources used by the Swanling process while running a load test, sampled every
+/// second to detect when the load generator rather than the target is the bottleneck.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SwanlingGeneratorMetrics {
+    /// How many times resource usage was sampled.
+    pub samples: usize,
+    /// CPU usage, as a percentage of all available CPUs.
+    pub cpu: SwanlingResourceMetric,
+    /// Resident memory, in megabytes.
+    pub memory: SwanlingResourceMetric,
+    /// Open sockets, only sampled on Linux.
+    pub sockets: SwanlingResourceMetric,
+    /// Tokio tasks, including one for each running
+    /// [`SwanlingUser`](../swanling/struct.SwanlingUser.html).
+    pub tasks: SwanlingResourceMetric,
+}
+impl SwanlingGeneratorMetrics {
+    /// Record one sample of the resources used by the load generator.
+    #[cfg(feature = "monitor")]
+    pub(crate) fn record(&mut self, cpu: f64, memory: f64, sockets: usize, tasks: usize) {
+        self.samples += 1;
+        self.cpu.record(cpu);
+        self.memory.record(memory);
+        self.sockets.record(sockets as f64);
+        self.tasks.record(tasks as f64);
+    }
+
+    /// Merge the resources used by another load generator.
+    fn merge(&mut self, other: &Self) {
+        self.samples += other.samples;
+        self.cpu.merge(&other.cpu);
+        self.memory.merge(&other.memory);
+        self.sockets.merge(&other.sockets);
+        self.tasks.merge(&other.tasks);
+    }
+
+    /// Returns true if CPU usage was high enough that the load generator may have been
+    /// the bottleneck.
+    pub fn overloaded(&self) -> bool {
+        self.cpu.maximum >= GENERATOR_CPU_WARNING
+    }
+}
+
+/// Tracks a resource sampled by [`SwanlingGeneratorMetrics`](./struct.SwanlingGeneratorMetrics.html).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SwanlingResourceMetric {
+    /// The most recently sampled value.
+    pub current: f64,
+    /// The sum of all sampled values, used to calculate the average.
+    pub total: f64,
+    /// The largest sampled value.
+    pub maximum: f64,
+}
+impl SwanlingResourceMetric {
+    /// Record a sampled value.
+    #[cfg(feature = "monitor")]
+    fn record(&mut self, value: f64) {
+        self.current = value;
+        self.total += value;
+        if value > self.maximum {
+            self.maximum = value;
+        }
+    }
+
+    /// Merge the values sampled by another load generator.
+    fn merge(&mut self, other: &Self) {
+        self.current = self.current.max(other.current);
+        self.total += other.total;
+        self.maximum = self.maximum.max(other.maximum);
+    }
+
+    /// The average of all sampled values.
+    pub fn average(&self, samples: usize) -> f64 {
+        if samples > 0 {
+            self.total / samples as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// How many times the assertions checked with
+/// [`SwanlingUser::assert`](../swanling/struct.SwanlingUser.html#method.assert), keyed by
+/// label.
+pub type SwanlingAssertionMetrics = BTreeMap<String, SwanlingAssertionMetric>;
+
+/// How many times an assertion with the same label passed and failed.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SwanlingAssertionMetric {
+    /// How many times the assertion passed.
+    pub passed: usize,
+    /// How many times the assertion failed.
+    pub failed: usize,
+}
+impl SwanlingAssertionMetric {
+    /// Count whether the assertion passed.
+    pub(crate) fn record(&mut self, passed: bool) {
+        if passed {
+            self.passed += 1;
+        } else {
+            self.failed += 1;
+        }
+    }
+
+    /// Add the counts of another assertion with the same label.
+    pub(crate) fn merge(&mut self, other: &SwanlingAssertionMetric) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+    }
+
+    /// The percentage of times the assertion passed.
+    pub fn passed_percent(&self) -> f32 {
+        if self.passed + self.failed == 0 {
+            0.0
+        } else {
+            self.passed as f32 / (self.passed + self.failed) as f32 * 100.0
+        }
+    }
+}
+
+/// How many times polls started with
+/// [`SwanlingUser::poll_until`](../swanling/struct.SwanlingUser.html#method.poll_until) met
+/// their condition or timed out, keyed by name.
+pub type SwanlingPollMetrics = BTreeMap<String, SwanlingPollMetric>;
+
+/// How many times a poll with the same name met its condition or timed out, how many
+/// requests it made and how long it waited.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SwanlingPollMetric {
+    /// How many times the condition was met.
+    pub met: usize,
+    /// How many times the poll timed out before the condition was met.
+    pub timed_out: usize,
+    /// The total number of requests made while polling.
+    pub iterations: usize,
+    /// The total time spent polling, in milliseconds.
+    pub wait_time: u64,
+    /// The longest time spent polling, in milliseconds.
+    pub max_wait_time: u64,
+}
+impl SwanlingPollMetric {
+    /// Record a poll that finished after `iterations` requests and `wait_time` milliseconds.
+    pub(crate) fn record(&mut self, iterations: usize, wait_time: u64, met: bool) {
+        if met {
+            self.met += 1;
+        } else {
+            self.timed_out += 1;
+        }
+        self.iterations += iterations;
+        self.wait_time += wait_time;
+        self.max_wait_time = self.max_wait_time.max(wait_time);
+    }
+
+    /// Add the counts of another poll with the same name.
+    pub(crate) fn merge(&mut self, other: &SwanlingPollMetric) {
+        self.met += other.met;
+        self.timed_out += other.timed_out;
+        self.iterations += other.iterations;
+        self.wait_time += other.wait_time;
+        self.max_wait_time = self.max_wait_time.max(other.max_wait_time);
+    }
+
+    /// The average number of requests made by a poll.
+    pub fn average_iterations(&self) -> f32 {
+        if self.met + self.timed_out == 0 {
+            0.0
+        } else {
+            self.iterations as f32 / (self.met + self.timed_out) as f32
+        }
+    }
+
+    /// The average time spent polling, in milliseconds.
+    pub fn average_wait_time(&self) -> u64 {
+        if self.met + self.timed_out == 0 {
+            0
+        } else {
+            self.wait_time / (self.met + self.timed_out) as u64
+        }
+    }
+}
+
+/// How many times queries run with a
+/// [`SwanlingSqlPool`](../sql/struct.SwanlingSqlPool.html) succeeded and failed, and how long
+/// they took, keyed by name.
+pub type SwanlingQueryMetrics = BTreeMap<String, SwanlingQueryMetric>;
+
+/// How many times a query with the same name succeeded and failed, and how long it took.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SwanlingQueryMetric {
+    /// How many times the query succeeded.
+    pub success_count: usize,
+    /// How many times the query failed.
+    pub fail_count: usize,
+    /// The total time spent running the query, in milliseconds.
+    pub total_time: u64,
+    /// The shortest time spent running the query, in milliseconds.
+    pub min_time: u64,
+    /// The longest time spent running the query, in milliseconds.
+    pub max_time: u64,
+}
+impl SwanlingQueryMetric {
+    /// Record a query that took `time` milliseconds.
+    #[cfg_attr(not(feature = "sql"), allow(dead_code))]
+    pub(crate) fn record(&mut self, time: u64, success: bool) {
+        if self.count() == 0 || time < self.min_time {
+            self.min_time = time;
+        }
+        if success {
+            self.success_count += 1;
+        } else {
+            self.fail_count += 1;
+        }
+        self.total_time += time;
+        self.max_time = self.max_time.max(time);
+    }
+
+    /// Add the counts of another query with the same name.
+    pub(crate) fn merge(&mut self, other: &SwanlingQueryMetric) {
+        if other.count() == 0 {
+            return;
+        }
+        if self.count() == 0 || other.min_time < self.min_time {
+            self.min_time = other.min_time;
+        }
+        self.success_count += other.success_count;
+        self.fail_count += other.fail_count;
+        self.total_time += other.total_time;
+        self.max_time = self.max_time.max(other.max_time);
+    }
+
+    /// How many times the query ran.
+    pub fn count(&self) -> usize {
+        self.success_count + self.fail_count
+    }
+
+    /// The average time spent running the query, in milliseconds.
+    pub fn average_time(&self) -> u64 {
+        if self.count() == 0 {
+            0
+        } else {
+            self.total_time / self.count() as u64
+        }
+    }
+}
+
+/// A note added to a running load test with the Controller `annotate` command, for example
+/// to mark when a deployment or a cache flush happened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwanlingAnnotation {
+    /// When the note was added, as a unix timestamp.
+    pub timestamp: i64,
+    /// How many seconds the load test had been running when the note was added.
+    pub elapsed: u64,
+    /// The note.
+    pub text: String,
+}
+impl SwanlingAnnotation {
+    /// Create a new SwanlingAnnotation, timestamped now.
+    pub(crate) fn new(started: Option<DateTime<Local>>, text: &str) -> Self {
+        let now = Local::now();
+        SwanlingAnnotation {
+            timestamp: now.timestamp(),
+            elapsed: started.map_or(0, |started| (now - started).num_seconds().max(0) as u64),
+            text: text.to_string(),
+        }
     }
 }
 
@@ -2051,6 +3726,18 @@ pub struct SwanlingErrorMetric {
     pub user: usize,
     /// The error caused by this request.
     pub error: String,
+    /// Key/value metadata the request was tagged with.
+    #[serde(default)]
+    pub tags: BTreeMap<String, String>,
+    /// How many identical errors this record stands for, more than 1 when `--error-log-window`
+    /// aggregates errors.
+    #[serde(default = "one")]
+    pub count: usize,
+}
+
+// Error log records written before errors were aggregated stand for a single error.
+fn one() -> usize {
+    1
 }
 
 /// For tracking and counting errors detected during a load test.
@@ -2061,9 +3748,12 @@ pub struct SwanlingErrorMetric {
 /// SwanlingErrorMetric object, with `occurrences` indicating how many times this error was
 /// seen.
 ///
+/// Errors are also summarized separately for each value of the request tags listed with
+/// `--error-tag`, for example to find out which tenants the errors affected.
+///
 /// Individual `SwanlingErrorMetric`s are stored within a
 /// [`SwanlingErrorMetrics`](./type.SwanlingErrorMetrics.html) `BTreeMap` with a string key of
-/// `error.method.name`. The `BTreeMap` is found in the `errors` field of what is returned
+/// `error.method.name`, followed by any indexed tags. The `BTreeMap` is found in the `errors` field of what is returned
 /// by [`SwanlingAttack::execute()`](../struct.SwanlingAttack.html#method.execute) when a load
 /// test finishes.
 ///
@@ -2077,7 +3767,9 @@ pub struct SwanlingErrorMetric {
 ///     method: Get,
 ///     name: "(Anon) front page",
 ///     error: "503 Service Unavailable: /",
+///     category: "Http5xx",
 ///     occurrences: 4588,
+///     tags: {},
 /// }
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
@@ -2088,16 +3780,30 @@ pub struct SwanlingErrorMetricAggregate {
     pub name: String,
     /// The error string.
     pub error: String,
+    /// The category of the error, as returned by
+    /// [`SwanlingTaskError::category`](../swanling/enum.SwanlingTaskError.html#method.category).
+    pub category: String,
     /// A counter reflecting how many times this error occurred.
     pub occurrences: usize,
+    /// The values of the request tags errors are indexed by with `--error-tag`.
+    #[serde(default)]
+    pub tags: BTreeMap<String, String>,
 }
 impl SwanlingErrorMetricAggregate {
-    pub(crate) fn new(method: SwanlingMethod, name: String, error: String) -> Self {
+    pub(crate) fn new(
+        method: SwanlingMethod,
+        name: String,
+        error: String,
+        category: String,
+        tags: BTreeMap<String, String>,
+    ) -> Self {
         SwanlingErrorMetricAggregate {
             method,
             name,
             error,
+            category,
             occurrences: 0,
+            tags,
         }
     }
 }
@@ -2136,21 +3842,29 @@ impl SwanlingAttack {
                 #[cfg(feature = "gaggle")]
                 {
                     // Push metrics to manager process.
-                    if !worker::push_metrics_to_manager(
+                    match worker::push_metrics_to_manager(
                         &swanling_attack_run_state.socket.clone().unwrap(),
-                        vec![
-                            GaggleMetrics::Requests(self.metrics.requests.clone()),
-                            GaggleMetrics::Tasks(self.metrics.tasks.clone()),
-                        ],
+                        worker::changed_gaggle_metrics(&self.metrics, false),
                         true,
                     ) {
+                        // Allocate any users taken over from a drained worker, they're
+                        // launched while the load test runs.
+                        Some(initializers) => {
+                            for initializer in initializers {
+                                let user = worker::initialize_user(self, &initializer);
+                                self.weighted_users.push(user);
+                            }
+                        }
                         // SwanlingUserCommand::Exit received, cancel.
-                        swanling_attack_run_state
+                        None => swanling_attack_run_state
                             .canceled
-                            .store(true, std::sync::atomic::Ordering::SeqCst);
+                            .store(true, std::sync::atomic::Ordering::SeqCst),
                     }
                     // The manager has all our metrics, reset locally.
                     self.metrics.requests = HashMap::new();
+                    self.metrics.removed_users = BTreeMap::new();
+                    self.metrics.assertions = BTreeMap::new();
+                    self.metrics.polls = BTreeMap::new();
                     self.metrics
                         .initialize_task_metrics(&self.task_sets, &self.configuration);
                 }
@@ -2161,6 +3875,9 @@ impl SwanlingAttack {
         if swanling_attack_run_state.display_running_metrics {
             swanling_attack_run_state.display_running_metrics = false;
             self.update_duration();
+            if let Some(progress_bar) = swanling_attack_run_state.progress_bar.as_mut() {
+                progress_bar.clear();
+            }
             self.metrics.print_running();
         }
 
@@ -2187,7 +3904,7 @@ impl SwanlingAttack {
                 // Reset running_metrics_timer.
                 swanling_attack_run_state.running_metrics_timer = std::time::Instant::now();
 
-                if self.metrics.display_metrics {
+                if self.metrics.display_metrics && !self.metrics.summary_only {
                     // Users is required here so unwrap() is safe.
                     if self.metrics.users < users {
                         println!(
@@ -2203,34 +3920,107 @@ impl SwanlingAttack {
                 self.metrics.requests = HashMap::new();
                 self.metrics
                     .initialize_task_metrics(&self.task_sets, &self.configuration);
+                self.metrics.assertions = BTreeMap::new();
+                self.metrics.polls = BTreeMap::new();
+                self.metrics.generator = SwanlingGeneratorMetrics::default();
                 // Restart the timer now that all threads are launched.
                 self.started = Some(std::time::Instant::now());
-            } else if self.metrics.users < users {
-                println!(
-                    "{} of {} users hatched, timer expired.\n",
-                    self.metrics.users, users
-                );
-            } else {
-                println!("All {} users hatched.\n", self.metrics.users);
+            } else if !self.metrics.summary_only {
+                if self.metrics.users < users {
+                    println!(
+                        "{} of {} users hatched, timer expired.\n",
+                        self.metrics.users, users
+                    );
+                } else {
+                    println!("All {} users hatched.\n", self.metrics.users);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Zero all metrics collected so far when requested by the Controller, starting a new
+    // measurement window without stopping the load test.
+    pub(crate) async fn reset_metrics_from_controller(
+        &mut self,
+        swanling_attack_run_state: &mut SwanlingAttackRunState,
+    ) -> Result<(), SwanlingError> {
+        // Receive metrics before resetting them.
+        self.sync_metrics(swanling_attack_run_state, true).await?;
+
+        // Display the running metrics collected so far, before resetting them.
+        self.update_duration();
+        self.metrics.print_running();
+        // Reset running_metrics_timer.
+        swanling_attack_run_state.running_metrics_timer = std::time::Instant::now();
+
+        if self.metrics.display_metrics && !self.metrics.summary_only {
+            println!("Controller requested reset, resetting metrics.\n");
+        }
+
+        // The timer is also used to enforce the run time, so reduce the run time by how
+        // long the load test has already been running.
+        if self.run_time > 0 {
+            if let Some(started) = self.started {
+                let now = self.paused.unwrap_or_else(std::time::Instant::now);
+                let elapsed = (now - started).as_secs() as usize;
+                self.run_time = std::cmp::max(1, self.run_time.saturating_sub(elapsed));
             }
         }
 
+        self.metrics.requests = HashMap::new();
+        self.metrics.errors = BTreeMap::new();
+        self.metrics
+            .initialize_task_metrics(&self.task_sets, &self.configuration);
+        self.metrics.assertions = BTreeMap::new();
+        self.metrics.polls = BTreeMap::new();
+        self.metrics.generator = SwanlingGeneratorMetrics::default();
+        // Restart the timer so per-second metrics only cover the new measurement window.
+        self.started = Some(std::time::Instant::now());
+        // If paused, the new measurement window starts when the load test resumes.
+        if self.paused.is_some() {
+            self.paused = self.started;
+        }
+
         Ok(())
     }
 
+    // Determine the key and name a request is recorded under in `SwanlingMetrics.requests`.
+    // Once `--max-request-names` unique request names are tracked, new request names are
+    // coalesced into `OTHER_REQUEST_NAME`, limiting how much memory metrics can use.
+    pub(crate) fn get_request_key(&self, method: &SwanlingMethod, name: &str) -> (String, String) {
+        let key = format!("{} {}", method, name);
+        let max_request_names = self.configuration.max_request_names;
+        if max_request_names == 0
+            || self.metrics.requests.len() < max_request_names
+            || self.metrics.requests.contains_key(&key)
+        {
+            return (key, name.to_string());
+        }
+
+        let other_key = format!("{} {}", method, OTHER_REQUEST_NAME);
+        if !self.metrics.requests.contains_key(&other_key) {
+            warn!(
+                "more than {} unique request names, recording new {} requests as {}",
+                max_request_names, method, OTHER_REQUEST_NAME
+            );
+        }
+        (other_key, OTHER_REQUEST_NAME.to_string())
+    }
+
     // Store `SwanlingRequestMetric` in a `SwanlingRequestMetricAggregate` within the
     // `SwanlingMetrics.requests` `HashMap`, merging if already existing, or creating new.
     // Also writes it to the request_file if enabled.
     async fn record_request_metric(&mut self, request_metric: &SwanlingRequestMetric) {
-        let key = format!("{} {}", request_metric.method, request_metric.name);
+        let (key, name) = self.get_request_key(&request_metric.method, &request_metric.name);
         let mut merge_request = match self.metrics.requests.get(&key) {
             Some(m) => m.clone(),
-            None => SwanlingRequestMetricAggregate::new(
-                &request_metric.name,
-                request_metric.method.clone(),
-                0,
-            ),
+            None => SwanlingRequestMetricAggregate::new(&name, request_metric.method.clone(), 0),
         };
+        if merge_request.host.is_empty() {
+            merge_request.host = request_metric.host();
+        }
 
         // Handle a metrics update.
         if request_metric.update {
@@ -2251,6 +4041,23 @@ impl SwanlingAttack {
             if self.configuration.status_codes {
                 merge_request.set_status_code(request_metric.status_code);
             }
+            if !request_metric.backend.is_empty() {
+                merge_request.set_backend(&request_metric.backend);
+            }
+            if !request_metric.user_agent.is_empty() {
+                merge_request.set_user_agent(&request_metric.user_agent);
+            }
+            // Only requests that received a response have a size.
+            if request_metric.status_code > 0 {
+                merge_request.record_response_size(
+                    request_metric.compressed,
+                    request_metric.response_size,
+                    request_metric.decoded_size,
+                );
+            }
+            merge_request.record_redirects(request_metric.redirects, request_metric.redirect_time);
+            merge_request
+                .record_conditional(request_metric.conditional, request_metric.status_code);
             if request_metric.success {
                 merge_request.success_count += 1;
             } else {
@@ -2281,6 +4088,13 @@ impl SwanlingAttack {
             received_message = true;
             match message.unwrap() {
                 SwanlingMetric::Request(request_metric) => {
+                    // Updates can change requests still being merged on the shards, so
+                    // collect them first.
+                    if request_metric.update {
+                        self.collect_request_metrics(swanling_attack_run_state)
+                            .await;
+                    }
+
                     // If there was an error, store it.
                     if !request_metric.error.is_empty() {
                         self.record_error(&request_metric, swanling_attack_run_state);
@@ -2323,6 +4137,47 @@ impl SwanlingAttack {
                     self.metrics.tasks[raw_task.taskset_index][raw_task.task_index]
                         .set_time(raw_task.run_time, raw_task.success);
                 }
+                SwanlingMetric::Batch(requests, tasks, assertions, polls) => {
+                    // Merge the requests aggregated by a SwanlingUser on the metrics shards.
+                    for (_, mut request) in requests {
+                        let (key, name) = self.get_request_key(&request.method, &request.path);
+                        // Track new requests right away, so they're counted by
+                        // --max-request-names before the shards are collected.
+                        if !self.metrics.requests.contains_key(&key) {
+                            self.metrics.requests.insert(
+                                key.to_string(),
+                                SwanlingRequestMetricAggregate::new(
+                                    &name,
+                                    request.method.clone(),
+                                    0,
+                                ),
+                            );
+                        }
+                        request.path = name;
+                        swanling_attack_run_state.metrics_shards.merge(key, request);
+                    }
+                    // Tasks are merged here, they're indexed rather than hashed.
+                    for task in &tasks {
+                        self.metrics.tasks[task.taskset_index][task.task_index].merge(task);
+                    }
+                    for (label, assertion) in &assertions {
+                        self.metrics
+                            .assertions
+                            .entry(label.to_string())
+                            .or_default()
+                            .merge(assertion);
+                    }
+                    for (name, poll) in &polls {
+                        self.metrics
+                            .polls
+                            .entry(name.to_string())
+                            .or_default()
+                            .merge(poll);
+                    }
+                }
+                SwanlingMetric::UserRemoved(error) => {
+                    *self.metrics.removed_users.entry(error).or_insert(0) += 1;
+                }
             }
             // Unless flushing all metrics, break out of receive loop after timeout.
             if !flush && util::ms_timer_expired(receive_started, receive_timeout) {
@@ -2331,9 +4186,32 @@ impl SwanlingAttack {
             message = swanling_attack_run_state.metrics_rx.try_recv();
         }
 
+        // Complete the metrics with the requests merged on the shards.
+        if received_message {
+            self.collect_request_metrics(swanling_attack_run_state)
+                .await;
+        }
+
         Ok(received_message)
     }
 
+    // Collect the requests merged on the metrics shards into `SwanlingMetrics.requests`.
+    async fn collect_request_metrics(
+        &mut self,
+        swanling_attack_run_state: &SwanlingAttackRunState,
+    ) {
+        for requests in swanling_attack_run_state.metrics_shards.collect().await {
+            for (key, request) in requests {
+                match self.metrics.requests.get_mut(&key) {
+                    Some(merge_request) => merge_request.merge(&request),
+                    None => {
+                        self.metrics.requests.insert(key, request);
+                    }
+                }
+            }
+        }
+    }
+
     /// Update error metrics.
     pub(crate) fn record_error(
         &mut self,
@@ -2357,466 +4235,582 @@ impl SwanlingAttack {
                     status_code: raw_request.status_code,
                     user: raw_request.user,
                     error: raw_request.error.clone(),
+                    tags: raw_request.tags.clone(),
+                    count: 1,
                 })));
             }
         }
 
-        // If the error summary is disabled, return without collecting errors.
-        if self.configuration.no_error_summary {
-            return;
+        // If the error summary is disabled, return without collecting errors.
+        if self.configuration.no_error_summary {
+            return;
+        }
+
+        // Optionally index errors by some of the request's tags.
+        let tags: BTreeMap<String, String> = raw_request
+            .tags
+            .iter()
+            .filter(|(key, _)| self.configuration.error_tag.contains(key))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        // Create a string to uniquely identify errors for tracking metrics.
+        let mut error_string = format!(
+            "{}.{}.{}",
+            raw_request.error, raw_request.method, raw_request.name
+        );
+        if !tags.is_empty() {
+            error_string.push_str(&format!(".{}", format_tags(&tags)));
+        }
+
+        let mut error_metrics = match self.metrics.errors.get(&error_string) {
+            // We've seen this error before.
+            Some(m) => m.clone(),
+            // First time we've seen this error.
+            None => SwanlingErrorMetricAggregate::new(
+                raw_request.method.clone(),
+                raw_request.name.to_string(),
+                raw_request.error.to_string(),
+                SwanlingTaskError::from_failed_request(raw_request.clone())
+                    .category()
+                    .to_string(),
+                tags,
+            ),
+        };
+        error_metrics.occurrences += 1;
+        self.metrics.errors.insert(error_string, error_metrics);
+    }
+
+    // Update metrics showing how long the load test has been running.
+    pub(crate) fn update_duration(&mut self) {
+        if let Some(started) = self.started {
+            // While paused, the duration doesn't increase.
+            let now = self.paused.unwrap_or_else(std::time::Instant::now);
+            self.metrics.duration = (now - started).as_secs() as usize;
+        } else {
+            self.metrics.duration = 0;
+        }
+    }
+
+    // Build an HTML-formatted report from the current metrics.
+    pub(crate) fn build_html_report(&self) -> String {
+        // Prepare report summary variables.
+        let started = self.metrics.started.unwrap();
+        let start_time = started.format("%Y-%m-%d %H:%M:%S").to_string();
+        let end_time = (started + chrono::Duration::seconds(self.metrics.duration as i64))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        let host = self.configuration.host.clone();
+
+        // Prepare requests and responses variables.
+        let percentiles = self.metrics.get_percentiles().to_vec();
+        let histogram_buckets = self
+            .configuration
+            .get_histogram_buckets()
+            .unwrap_or_default();
+        let mut histogram_metrics = Vec::new();
+        let mut raw_request_metrics = Vec::new();
+        let mut co_request_metrics = Vec::new();
+        let mut raw_response_metrics = Vec::new();
+        let mut co_response_metrics = Vec::new();
+        let mut raw_aggregate_total_count = 0;
+        let mut co_aggregate_total_count = 0;
+        let mut raw_aggregate_fail_count = 0;
+        let mut raw_aggregate_response_time_counter: usize = 0;
+        let mut raw_aggregate_response_time_minimum: usize = 0;
+        let mut raw_aggregate_response_time_maximum: usize = 0;
+        let mut raw_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut co_aggregate_response_time_counter: usize = 0;
+        let mut co_aggregate_response_time_maximum: usize = 0;
+        let mut co_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut co_data = false;
+        for (request_key, request) in self.metrics.requests.iter().sorted() {
+            // Determine whether or not to include Coordinated Omission data.
+            if !co_data && request.coordinated_omission_data.is_some() {
+                co_data = true;
+            }
+            let method = format!("{}", request.method);
+            // The request_key is "{method} {name}", so by stripping the "{method} "
+            // prefix we get the name.
+            let name = request_key
+                .strip_prefix(&format!("{} ", request.method))
+                .unwrap()
+                .to_string();
+            let total_request_count = request.success_count + request.fail_count;
+            let (requests_per_second, failures_per_second) = per_second_calculations(
+                self.metrics.duration,
+                total_request_count,
+                request.fail_count,
+            );
+            // Prepare per-request metrics.
+            raw_request_metrics.push(report::RequestMetric {
+                method: method.to_string(),
+                name: name.to_string(),
+                number_of_requests: total_request_count,
+                number_of_failures: request.fail_count,
+                response_time_average: format!(
+                    "{:.2}",
+                    request.raw_data.total_time as f32 / request.raw_data.counter as f32
+                ),
+                response_time_minimum: request.raw_data.minimum_time,
+                response_time_maximum: request.raw_data.maximum_time,
+                requests_per_second: format!("{:.2}", requests_per_second),
+                failures_per_second: format!("{:.2}", failures_per_second),
+            });
+
+            // Prepare per-response metrics.
+            raw_response_metrics.push(report::get_response_metric(
+                &method,
+                &name,
+                &request.raw_data.times,
+                request.raw_data.counter,
+                request.raw_data.minimum_time,
+                request.raw_data.maximum_time,
+                &percentiles,
+            ));
+
+            // Prepare per-response latency histograms.
+            histogram_metrics.push(report::get_histogram_metric(
+                &method,
+                &name,
+                &request.raw_data.times,
+                &histogram_buckets,
+            ));
+
+            // Collect aggregated request and response metrics.
+            raw_aggregate_total_count += total_request_count;
+            raw_aggregate_fail_count += request.fail_count;
+            raw_aggregate_response_time_counter += request.raw_data.total_time;
+            raw_aggregate_response_time_minimum = update_min_time(
+                raw_aggregate_response_time_minimum,
+                request.raw_data.minimum_time,
+            );
+            raw_aggregate_response_time_maximum = update_max_time(
+                raw_aggregate_response_time_maximum,
+                request.raw_data.maximum_time,
+            );
+            raw_aggregate_response_times =
+                merge_times(raw_aggregate_response_times, request.raw_data.times.clone());
+        }
+
+        // Prepare aggregate per-request metrics.
+        let (raw_aggregate_requests_per_second, raw_aggregate_failures_per_second) =
+            per_second_calculations(
+                self.metrics.duration,
+                raw_aggregate_total_count,
+                raw_aggregate_fail_count,
+            );
+        raw_request_metrics.push(report::RequestMetric {
+            method: "".to_string(),
+            name: "Aggregated".to_string(),
+            number_of_requests: raw_aggregate_total_count,
+            number_of_failures: raw_aggregate_fail_count,
+            response_time_average: format!(
+                "{:.2}",
+                raw_aggregate_response_time_counter as f32 / raw_aggregate_total_count as f32
+            ),
+            response_time_minimum: raw_aggregate_response_time_minimum,
+            response_time_maximum: raw_aggregate_response_time_maximum,
+            requests_per_second: format!("{:.2}", raw_aggregate_requests_per_second),
+            failures_per_second: format!("{:.2}", raw_aggregate_failures_per_second),
+        });
+
+        // Prepare aggregate per-response metrics.
+        raw_response_metrics.push(report::get_response_metric(
+            "",
+            "Aggregated",
+            &raw_aggregate_response_times,
+            raw_aggregate_total_count,
+            raw_aggregate_response_time_minimum,
+            raw_aggregate_response_time_maximum,
+            &percentiles,
+        ));
+
+        // Prepare aggregate latency histogram.
+        histogram_metrics.push(report::get_histogram_metric(
+            "",
+            "Aggregated",
+            &raw_aggregate_response_times,
+            &histogram_buckets,
+        ));
+
+        // Compile the request metrics template.
+        let mut raw_requests_rows = Vec::new();
+        for metric in raw_request_metrics {
+            raw_requests_rows.push(report::raw_request_metrics_row(metric));
+        }
+
+        // Compile the response metrics template.
+        let mut raw_responses_rows = Vec::new();
+        for metric in raw_response_metrics {
+            raw_responses_rows.push(report::response_metrics_row(metric));
         }
 
-        // Create a string to uniquely identify errors for tracking metrics.
-        let error_string = format!(
-            "{}.{}.{}",
-            raw_request.error, raw_request.method, raw_request.name
-        );
+        // Compile the latency histograms template.
+        let mut histogram_rows = Vec::new();
+        for metric in histogram_metrics {
+            histogram_rows.push(report::histogram_metrics_row(metric, &histogram_buckets));
+        }
+        let histograms_template = report::histogram_metrics_template(&histogram_rows.join("\n"));
 
-        let mut error_metrics = match self.metrics.errors.get(&error_string) {
-            // We've seen this error before.
-            Some(m) => m.clone(),
-            // First time we've seen this error.
-            None => SwanlingErrorMetricAggregate::new(
-                raw_request.method.clone(),
-                raw_request.name.to_string(),
-                raw_request.error.to_string(),
-            ),
+        // Only build the annotations template if the Controller added annotations.
+        let annotations_template = if !self.metrics.annotations.is_empty() {
+            let mut annotation_rows = Vec::new();
+            for annotation in &self.metrics.annotations {
+                annotation_rows.push(report::annotation_row(annotation));
+            }
+            report::annotations_template(&annotation_rows.join("\n"))
+        } else {
+            "".to_string()
         };
-        error_metrics.occurrences += 1;
-        self.metrics.errors.insert(error_string, error_metrics);
-    }
 
-    // Update metrics showing how long the load test has been running.
-    pub(crate) fn update_duration(&mut self) {
-        if let Some(started) = self.started {
-            self.metrics.duration = started.elapsed().as_secs() as usize;
-        } else {
-            self.metrics.duration = 0;
-        }
-    }
+        let co_requests_template: String;
+        let co_responses_template: String;
+        if co_data {
+            for (request_key, request) in self.metrics.requests.iter().sorted() {
+                if let Some(coordinated_omission_data) = request.coordinated_omission_data.as_ref()
+                {
+                    let method = format!("{}", request.method);
+                    // The request_key is "{method} {name}", so by stripping the "{method} "
+                    // prefix we get the name.
+                    let name = request_key
+                        .strip_prefix(&format!("{} ", request.method))
+                        .unwrap()
+                        .to_string();
+                    let raw_average =
+                        request.raw_data.total_time as f32 / request.raw_data.counter as f32;
+                    let co_average = coordinated_omission_data.total_time as f32
+                        / coordinated_omission_data.counter as f32;
+                    // Prepare per-request metrics.
+                    co_request_metrics.push(report::CORequestMetric {
+                        method: method.to_string(),
+                        name: name.to_string(),
+                        response_time_average: format!("{:.2}", co_average),
+                        response_time_standard_deviation: format!(
+                            "{:.2}",
+                            util::standard_deviation(raw_average, co_average)
+                        ),
+                        response_time_maximum: coordinated_omission_data.maximum_time,
+                    });
 
-    // Write an HTML-formatted report, if enabled.
-    pub(crate) async fn write_html_report(
-        &mut self,
-        swanling_attack_run_state: &mut SwanlingAttackRunState,
-    ) -> Result<(), SwanlingError> {
-        // Only write the report if enabled.
-        if let Some(report_file) = swanling_attack_run_state.report_file.as_mut() {
-            // Prepare report summary variables.
-            let started = self.metrics.started.unwrap();
-            let start_time = started.format("%Y-%m-%d %H:%M:%S").to_string();
-            let end_time = (started + chrono::Duration::seconds(self.metrics.duration as i64))
-                .format("%Y-%m-%d %H:%M:%S")
-                .to_string();
-            let host = match self.get_configuration_host() {
-                Some(h) => h.to_string(),
-                None => "".to_string(),
-            };
+                    // Prepare per-response metrics.
+                    co_response_metrics.push(report::get_response_metric(
+                        &method,
+                        &name,
+                        &coordinated_omission_data.times,
+                        coordinated_omission_data.counter,
+                        coordinated_omission_data.minimum_time,
+                        coordinated_omission_data.maximum_time,
+                        &percentiles,
+                    ));
 
-            // Prepare requests and responses variables.
-            let mut raw_request_metrics = Vec::new();
-            let mut co_request_metrics = Vec::new();
-            let mut raw_response_metrics = Vec::new();
-            let mut co_response_metrics = Vec::new();
-            let mut raw_aggregate_total_count = 0;
-            let mut co_aggregate_total_count = 0;
-            let mut raw_aggregate_fail_count = 0;
-            let mut raw_aggregate_response_time_counter: usize = 0;
-            let mut raw_aggregate_response_time_minimum: usize = 0;
-            let mut raw_aggregate_response_time_maximum: usize = 0;
-            let mut raw_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
-            let mut co_aggregate_response_time_counter: usize = 0;
-            let mut co_aggregate_response_time_maximum: usize = 0;
-            let mut co_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
-            let mut co_data = false;
-            for (request_key, request) in self.metrics.requests.iter().sorted() {
-                // Determine whether or not to include Coordinated Omission data.
-                if !co_data && request.coordinated_omission_data.is_some() {
-                    co_data = true;
+                    // Collect aggregated request and response metrics.
+                    co_aggregate_response_time_counter += coordinated_omission_data.total_time;
+                    co_aggregate_response_time_maximum = update_max_time(
+                        co_aggregate_response_time_maximum,
+                        coordinated_omission_data.maximum_time,
+                    );
+                    co_aggregate_response_times = merge_times(
+                        co_aggregate_response_times,
+                        coordinated_omission_data.times.clone(),
+                    );
                 }
-                let method = format!("{}", request.method);
-                // The request_key is "{method} {name}", so by stripping the "{method} "
-                // prefix we get the name.
-                let name = request_key
-                    .strip_prefix(&format!("{} ", request.method))
-                    .unwrap()
-                    .to_string();
                 let total_request_count = request.success_count + request.fail_count;
-                let (requests_per_second, failures_per_second) = per_second_calculations(
-                    self.metrics.duration,
-                    total_request_count,
-                    request.fail_count,
-                );
-                // Prepare per-request metrics.
-                raw_request_metrics.push(report::RequestMetric {
-                    method: method.to_string(),
-                    name: name.to_string(),
-                    number_of_requests: total_request_count,
-                    number_of_failures: request.fail_count,
-                    response_time_average: format!(
-                        "{:.2}",
-                        request.raw_data.total_time as f32 / request.raw_data.counter as f32
-                    ),
-                    response_time_minimum: request.raw_data.minimum_time,
-                    response_time_maximum: request.raw_data.maximum_time,
-                    requests_per_second: format!("{:.2}", requests_per_second),
-                    failures_per_second: format!("{:.2}", failures_per_second),
-                });
-
-                // Prepare per-response metrics.
-                raw_response_metrics.push(report::get_response_metric(
-                    &method,
-                    &name,
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                ));
-
-                // Collect aggregated request and response metrics.
-                raw_aggregate_total_count += total_request_count;
-                raw_aggregate_fail_count += request.fail_count;
-                raw_aggregate_response_time_counter += request.raw_data.total_time;
-                raw_aggregate_response_time_minimum = update_min_time(
-                    raw_aggregate_response_time_minimum,
-                    request.raw_data.minimum_time,
-                );
-                raw_aggregate_response_time_maximum = update_max_time(
-                    raw_aggregate_response_time_maximum,
-                    request.raw_data.maximum_time,
-                );
-                raw_aggregate_response_times =
-                    merge_times(raw_aggregate_response_times, request.raw_data.times.clone());
+                co_aggregate_total_count += total_request_count;
             }
-
-            // Prepare aggregate per-request metrics.
-            let (raw_aggregate_requests_per_second, raw_aggregate_failures_per_second) =
-                per_second_calculations(
-                    self.metrics.duration,
-                    raw_aggregate_total_count,
-                    raw_aggregate_fail_count,
-                );
-            raw_request_metrics.push(report::RequestMetric {
+            let co_average =
+                co_aggregate_response_time_counter as f32 / co_aggregate_total_count as f32;
+            let raw_average =
+                raw_aggregate_response_time_counter as f32 / raw_aggregate_total_count as f32;
+            co_request_metrics.push(report::CORequestMetric {
                 method: "".to_string(),
                 name: "Aggregated".to_string(),
-                number_of_requests: raw_aggregate_total_count,
-                number_of_failures: raw_aggregate_fail_count,
                 response_time_average: format!(
                     "{:.2}",
-                    raw_aggregate_response_time_counter as f32 / raw_aggregate_total_count as f32
+                    co_aggregate_response_time_counter as f32 / co_aggregate_total_count as f32
+                ),
+                response_time_standard_deviation: format!(
+                    "{:.2}",
+                    util::standard_deviation(raw_average, co_average),
                 ),
-                response_time_minimum: raw_aggregate_response_time_minimum,
-                response_time_maximum: raw_aggregate_response_time_maximum,
-                requests_per_second: format!("{:.2}", raw_aggregate_requests_per_second),
-                failures_per_second: format!("{:.2}", raw_aggregate_failures_per_second),
+                response_time_maximum: co_aggregate_response_time_maximum,
             });
 
             // Prepare aggregate per-response metrics.
-            raw_response_metrics.push(report::get_response_metric(
+            co_response_metrics.push(report::get_response_metric(
                 "",
                 "Aggregated",
-                &raw_aggregate_response_times,
-                raw_aggregate_total_count,
+                &co_aggregate_response_times,
+                co_aggregate_total_count,
                 raw_aggregate_response_time_minimum,
-                raw_aggregate_response_time_maximum,
+                co_aggregate_response_time_maximum,
+                &percentiles,
             ));
 
-            // Compile the request metrics template.
-            let mut raw_requests_rows = Vec::new();
-            for metric in raw_request_metrics {
-                raw_requests_rows.push(report::raw_request_metrics_row(metric));
+            // Compile the co_request metrics rows.
+            let mut co_request_rows = Vec::new();
+            for metric in co_request_metrics {
+                co_request_rows.push(report::coordinated_omission_request_metrics_row(metric));
             }
 
-            // Compile the response metrics template.
-            let mut raw_responses_rows = Vec::new();
-            for metric in raw_response_metrics {
-                raw_responses_rows.push(report::response_metrics_row(metric));
+            // Compile the status_code metrics template.
+            co_requests_template =
+                report::coordinated_omission_request_metrics_template(&co_request_rows.join("\n"));
+
+            // Compile the co_request metrics rows.
+            let mut co_response_rows = Vec::new();
+            for metric in co_response_metrics {
+                co_response_rows.push(report::coordinated_omission_response_metrics_row(metric));
             }
 
-            let co_requests_template: String;
-            let co_responses_template: String;
-            if co_data {
-                for (request_key, request) in self.metrics.requests.iter().sorted() {
-                    if let Some(coordinated_omission_data) =
-                        request.coordinated_omission_data.as_ref()
-                    {
-                        let method = format!("{}", request.method);
-                        // The request_key is "{method} {name}", so by stripping the "{method} "
-                        // prefix we get the name.
-                        let name = request_key
-                            .strip_prefix(&format!("{} ", request.method))
-                            .unwrap()
-                            .to_string();
-                        let raw_average =
-                            request.raw_data.total_time as f32 / request.raw_data.counter as f32;
-                        let co_average = coordinated_omission_data.total_time as f32
-                            / coordinated_omission_data.counter as f32;
-                        // Prepare per-request metrics.
-                        co_request_metrics.push(report::CORequestMetric {
-                            method: method.to_string(),
-                            name: name.to_string(),
-                            response_time_average: format!("{:.2}", co_average),
-                            response_time_standard_deviation: format!(
-                                "{:.2}",
-                                util::standard_deviation(raw_average, co_average)
-                            ),
-                            response_time_maximum: coordinated_omission_data.maximum_time,
-                        });
+            // Compile the status_code metrics template.
+            co_responses_template = report::coordinated_omission_response_metrics_template(
+                &co_response_rows.join("\n"),
+                &report::percentile_headers(&percentiles),
+            );
+        } else {
+            // If --status-codes is not enabled, return an empty template.
+            co_requests_template = "".to_string();
+            co_responses_template = "".to_string();
+        }
 
-                        // Prepare per-response metrics.
-                        co_response_metrics.push(report::get_response_metric(
-                            &method,
-                            &name,
-                            &coordinated_omission_data.times,
-                            coordinated_omission_data.counter,
-                            coordinated_omission_data.minimum_time,
-                            coordinated_omission_data.maximum_time,
-                        ));
-
-                        // Collect aggregated request and response metrics.
-                        co_aggregate_response_time_counter += coordinated_omission_data.total_time;
-                        co_aggregate_response_time_maximum = update_max_time(
-                            co_aggregate_response_time_maximum,
-                            coordinated_omission_data.maximum_time,
-                        );
-                        co_aggregate_response_times = merge_times(
-                            co_aggregate_response_times,
-                            coordinated_omission_data.times.clone(),
-                        );
+        // Only build the tasks template if --no-task-metrics isn't enabled.
+        let tasks_template: String;
+        if !self.configuration.no_task_metrics {
+            let mut task_metrics = Vec::new();
+            let mut aggregate_total_count = 0;
+            let mut aggregate_fail_count = 0;
+            let mut aggregate_task_time_counter: usize = 0;
+            let mut aggregate_task_time_minimum: usize = 0;
+            let mut aggregate_task_time_maximum: usize = 0;
+            let mut aggregate_task_times: BTreeMap<usize, usize> = BTreeMap::new();
+            for (task_set_counter, task_set) in self.metrics.tasks.iter().enumerate() {
+                for (task_counter, task) in task_set.iter().enumerate() {
+                    if task_counter == 0 {
+                        // Only the taskset_name is used for task sets.
+                        task_metrics.push(report::TaskMetric {
+                            is_task_set: true,
+                            task: "".to_string(),
+                            name: task.taskset_name.to_string(),
+                            number_of_requests: 0,
+                            number_of_failures: 0,
+                            response_time_average: "".to_string(),
+                            response_time_minimum: 0,
+                            response_time_maximum: 0,
+                            requests_per_second: "".to_string(),
+                            failures_per_second: "".to_string(),
+                        });
                     }
-                    let total_request_count = request.success_count + request.fail_count;
-                    co_aggregate_total_count += total_request_count;
-                }
-                let co_average =
-                    co_aggregate_response_time_counter as f32 / co_aggregate_total_count as f32;
-                let raw_average =
-                    raw_aggregate_response_time_counter as f32 / raw_aggregate_total_count as f32;
-                co_request_metrics.push(report::CORequestMetric {
-                    method: "".to_string(),
-                    name: "Aggregated".to_string(),
-                    response_time_average: format!(
-                        "{:.2}",
-                        co_aggregate_response_time_counter as f32 / co_aggregate_total_count as f32
-                    ),
-                    response_time_standard_deviation: format!(
-                        "{:.2}",
-                        util::standard_deviation(raw_average, co_average),
-                    ),
-                    response_time_maximum: co_aggregate_response_time_maximum,
-                });
+                    let total_run_count = task.success_count + task.fail_count;
+                    let (requests_per_second, failures_per_second) = per_second_calculations(
+                        self.metrics.duration,
+                        total_run_count,
+                        task.fail_count,
+                    );
+                    let average = match task.counter {
+                        0 => 0.00,
+                        _ => task.total_time as f32 / task.counter as f32,
+                    };
+                    task_metrics.push(report::TaskMetric {
+                        is_task_set: false,
+                        task: format!("{}.{}", task_set_counter, task_counter),
+                        name: task.task_name.to_string(),
+                        number_of_requests: total_run_count,
+                        number_of_failures: task.fail_count,
+                        response_time_average: format!("{:.2}", average),
+                        response_time_minimum: task.min_time,
+                        response_time_maximum: task.max_time,
+                        requests_per_second: format!("{:.2}", requests_per_second),
+                        failures_per_second: format!("{:.2}", failures_per_second),
+                    });
 
-                // Prepare aggregate per-response metrics.
-                co_response_metrics.push(report::get_response_metric(
-                    "",
-                    "Aggregated",
-                    &co_aggregate_response_times,
-                    co_aggregate_total_count,
-                    raw_aggregate_response_time_minimum,
-                    co_aggregate_response_time_maximum,
-                ));
-
-                // Compile the co_request metrics rows.
-                let mut co_request_rows = Vec::new();
-                for metric in co_request_metrics {
-                    co_request_rows.push(report::coordinated_omission_request_metrics_row(metric));
+                    aggregate_total_count += total_run_count;
+                    aggregate_fail_count += task.fail_count;
+                    aggregate_task_times = merge_times(aggregate_task_times, task.times.clone());
+                    aggregate_task_time_counter += &task.counter;
+                    aggregate_task_time_minimum =
+                        update_min_time(aggregate_task_time_minimum, task.min_time);
+                    aggregate_task_time_maximum =
+                        update_max_time(aggregate_task_time_maximum, task.max_time);
                 }
+            }
 
-                // Compile the status_code metrics template.
-                co_requests_template = report::coordinated_omission_request_metrics_template(
-                    &co_request_rows.join("\n"),
+            let (aggregate_requests_per_second, aggregate_failures_per_second) =
+                per_second_calculations(
+                    self.metrics.duration,
+                    aggregate_total_count,
+                    aggregate_fail_count,
                 );
+            task_metrics.push(report::TaskMetric {
+                is_task_set: false,
+                task: "".to_string(),
+                name: "Aggregated".to_string(),
+                number_of_requests: aggregate_total_count,
+                number_of_failures: aggregate_fail_count,
+                response_time_average: format!(
+                    "{:.2}",
+                    raw_aggregate_response_time_counter as f32 / aggregate_total_count as f32
+                ),
+                response_time_minimum: aggregate_task_time_minimum,
+                response_time_maximum: aggregate_task_time_maximum,
+                requests_per_second: format!("{:.2}", aggregate_requests_per_second),
+                failures_per_second: format!("{:.2}", aggregate_failures_per_second),
+            });
+            let mut tasks_rows = Vec::new();
+            // Compile the task metrics template.
+            for metric in task_metrics {
+                tasks_rows.push(report::task_metrics_row(metric));
+            }
 
-                // Compile the co_request metrics rows.
-                let mut co_response_rows = Vec::new();
-                for metric in co_response_metrics {
-                    co_response_rows
-                        .push(report::coordinated_omission_response_metrics_row(metric));
-                }
+            tasks_template = report::task_metrics_template(&tasks_rows.join("\n"));
+        } else {
+            tasks_template = "".to_string();
+        }
 
-                // Compile the status_code metrics template.
-                co_responses_template = report::coordinated_omission_response_metrics_template(
-                    &co_response_rows.join("\n"),
-                );
-            } else {
-                // If --status-codes is not enabled, return an empty template.
-                co_requests_template = "".to_string();
-                co_responses_template = "".to_string();
-            }
-
-            // Only build the tasks template if --no-task-metrics isn't enabled.
-            let tasks_template: String;
-            if !self.configuration.no_task_metrics {
-                let mut task_metrics = Vec::new();
-                let mut aggregate_total_count = 0;
-                let mut aggregate_fail_count = 0;
-                let mut aggregate_task_time_counter: usize = 0;
-                let mut aggregate_task_time_minimum: usize = 0;
-                let mut aggregate_task_time_maximum: usize = 0;
-                let mut aggregate_task_times: BTreeMap<usize, usize> = BTreeMap::new();
-                for (task_set_counter, task_set) in self.metrics.tasks.iter().enumerate() {
-                    for (task_counter, task) in task_set.iter().enumerate() {
-                        if task_counter == 0 {
-                            // Only the taskset_name is used for task sets.
-                            task_metrics.push(report::TaskMetric {
-                                is_task_set: true,
-                                task: "".to_string(),
-                                name: task.taskset_name.to_string(),
-                                number_of_requests: 0,
-                                number_of_failures: 0,
-                                response_time_average: "".to_string(),
-                                response_time_minimum: 0,
-                                response_time_maximum: 0,
-                                requests_per_second: "".to_string(),
-                                failures_per_second: "".to_string(),
-                            });
-                        }
-                        let total_run_count = task.success_count + task.fail_count;
-                        let (requests_per_second, failures_per_second) = per_second_calculations(
-                            self.metrics.duration,
-                            total_run_count,
-                            task.fail_count,
-                        );
-                        let average = match task.counter {
-                            0 => 0.00,
-                            _ => task.total_time as f32 / task.counter as f32,
-                        };
-                        task_metrics.push(report::TaskMetric {
-                            is_task_set: false,
-                            task: format!("{}.{}", task_set_counter, task_counter),
-                            name: task.task_name.to_string(),
-                            number_of_requests: total_run_count,
-                            number_of_failures: task.fail_count,
-                            response_time_average: format!("{:.2}", average),
-                            response_time_minimum: task.min_time,
-                            response_time_maximum: task.max_time,
-                            requests_per_second: format!("{:.2}", requests_per_second),
-                            failures_per_second: format!("{:.2}", failures_per_second),
-                        });
+        // Only build the tasks template if --no-task-metrics isn't enabled.
+        let errors_template = if !self.metrics.errors.is_empty() {
+            let mut error_rows = Vec::new();
+            for error in self.metrics.errors.values() {
+                error_rows.push(report::error_row(error));
+            }
+            report::errors_template(&error_rows.join("\n"))
+        } else {
+            "".to_string()
+        };
 
-                        aggregate_total_count += total_run_count;
-                        aggregate_fail_count += task.fail_count;
-                        aggregate_task_times =
-                            merge_times(aggregate_task_times, task.times.clone());
-                        aggregate_task_time_counter += &task.counter;
-                        aggregate_task_time_minimum =
-                            update_min_time(aggregate_task_time_minimum, task.min_time);
-                        aggregate_task_time_maximum =
-                            update_max_time(aggregate_task_time_maximum, task.max_time);
-                    }
-                }
+        // Only build the status_code template if --status-codes is enabled.
+        let status_code_template = if self.configuration.status_codes {
+            let mut status_code_metrics = Vec::new();
+            let mut aggregated_status_code_counts: HashMap<u16, usize> = HashMap::new();
+            for (request_key, request) in self.metrics.requests.iter().sorted() {
+                let method = format!("{}", request.method);
+                // The request_key is "{method} {name}", so by stripping the "{method} "
+                // prefix we get the name.
+                let name = request_key
+                    .strip_prefix(&format!("{} ", request.method))
+                    .unwrap()
+                    .to_string();
 
-                let (aggregate_requests_per_second, aggregate_failures_per_second) =
-                    per_second_calculations(
-                        self.metrics.duration,
-                        aggregate_total_count,
-                        aggregate_fail_count,
-                    );
-                task_metrics.push(report::TaskMetric {
-                    is_task_set: false,
-                    task: "".to_string(),
-                    name: "Aggregated".to_string(),
-                    number_of_requests: aggregate_total_count,
-                    number_of_failures: aggregate_fail_count,
-                    response_time_average: format!(
-                        "{:.2}",
-                        raw_aggregate_response_time_counter as f32 / aggregate_total_count as f32
-                    ),
-                    response_time_minimum: aggregate_task_time_minimum,
-                    response_time_maximum: aggregate_task_time_maximum,
-                    requests_per_second: format!("{:.2}", aggregate_requests_per_second),
-                    failures_per_second: format!("{:.2}", aggregate_failures_per_second),
-                });
-                let mut tasks_rows = Vec::new();
-                // Compile the task metrics template.
-                for metric in task_metrics {
-                    tasks_rows.push(report::task_metrics_row(metric));
-                }
+                // Build a list of status codes, and update the aggregate record.
+                let codes = prepare_status_codes(
+                    &request.status_code_counts,
+                    &mut Some(&mut aggregated_status_code_counts),
+                );
 
-                tasks_template = report::task_metrics_template(&tasks_rows.join("\n"));
-            } else {
-                tasks_template = "".to_string();
+                // Add a row of data for the status code table.
+                status_code_metrics.push(report::StatusCodeMetric {
+                    method,
+                    name,
+                    status_codes: codes,
+                });
             }
 
-            // Only build the tasks template if --no-task-metrics isn't enabled.
-            let errors_template: String;
-            if !self.metrics.errors.is_empty() {
-                let mut error_rows = Vec::new();
-                for error in self.metrics.errors.values() {
-                    error_rows.push(report::error_row(error));
-                }
-                errors_template = report::errors_template(&error_rows.join("\n"));
-            } else {
-                errors_template = "".to_string();
-            }
+            // Build a list of aggregate status codes.
+            let aggregated_codes = prepare_status_codes(&aggregated_status_code_counts, &mut None);
 
-            // Only build the status_code template if --status-codes is enabled.
-            let status_code_template: String;
-            if self.configuration.status_codes {
-                let mut status_code_metrics = Vec::new();
-                let mut aggregated_status_code_counts: HashMap<u16, usize> = HashMap::new();
-                for (request_key, request) in self.metrics.requests.iter().sorted() {
-                    let method = format!("{}", request.method);
-                    // The request_key is "{method} {name}", so by stripping the "{method} "
-                    // prefix we get the name.
-                    let name = request_key
-                        .strip_prefix(&format!("{} ", request.method))
-                        .unwrap()
-                        .to_string();
+            // Add a final row of aggregate data for the status code table.
+            status_code_metrics.push(report::StatusCodeMetric {
+                method: "".to_string(),
+                name: "Aggregated".to_string(),
+                status_codes: aggregated_codes,
+            });
 
-                    // Build a list of status codes, and update the aggregate record.
-                    let codes = prepare_status_codes(
-                        &request.status_code_counts,
-                        &mut Some(&mut aggregated_status_code_counts),
-                    );
+            // Compile the status_code metrics rows.
+            let mut status_code_rows = Vec::new();
+            for metric in status_code_metrics {
+                status_code_rows.push(report::status_code_metrics_row(metric));
+            }
 
-                    // Add a row of data for the status code table.
-                    status_code_metrics.push(report::StatusCodeMetric {
-                        method,
-                        name,
-                        status_codes: codes,
-                    });
-                }
+            // Compile the status_code metrics template.
+            report::status_code_metrics_template(&status_code_rows.join("\n"))
+        } else {
+            // If --status-codes is not enabled, return an empty template.
+            "".to_string()
+        };
 
-                // Build a list of aggregate status codes.
-                let aggregated_codes =
-                    prepare_status_codes(&aggregated_status_code_counts, &mut None);
+        // Only build the hosts template if requests were made to more than one host.
+        let host_metrics = self.metrics.host_metrics();
+        let hosts_template = if host_metrics.len() > 1 {
+            let mut host_rows = Vec::new();
+            for (host, metric) in host_metrics {
+                let total_count = metric.success_count + metric.fail_count;
+                let (requests_per_second, failures_per_second) =
+                    per_second_calculations(self.metrics.duration, total_count, metric.fail_count);
+                host_rows.push(report::host_metrics_row(report::HostMetric {
+                    host: host.to_string(),
+                    number_of_requests: total_count,
+                    number_of_failures: metric.fail_count,
+                    response_time_average: metric.average_time(),
+                    requests_per_second: format!("{:.2}", requests_per_second),
+                    failures_per_second: format!("{:.2}", failures_per_second),
+                }));
+            }
+            report::host_metrics_template(&host_rows.join("\n"))
+        } else {
+            "".to_string()
+        };
 
-                // Add a final row of aggregate data for the status code table.
-                status_code_metrics.push(report::StatusCodeMetric {
-                    method: "".to_string(),
-                    name: "Aggregated".to_string(),
-                    status_codes: aggregated_codes,
-                });
+        // Only build the redirects template if any requests were redirected.
+        let mut redirect_rows = Vec::new();
+        for (request_key, request) in self.metrics.requests.iter().sorted() {
+            if request.redirected_count > 0 {
+                redirect_rows.push(report::redirect_metrics_row(report::RedirectMetric {
+                    method: request.method.to_string(),
+                    name: request_key
+                        .strip_prefix(&format!("{} ", request.method))
+                        .unwrap()
+                        .to_string(),
+                    redirected_count: request.redirected_count,
+                    average_chain: format!(
+                        "{:.2}",
+                        request.redirect_count as f32 / request.redirected_count as f32
+                    ),
+                    max_chain: request.max_redirects,
+                    redirect_time_average: (request.redirect_time / request.redirected_count as u64)
+                        as usize,
+                }));
+            }
+        }
+        let redirects_template = if redirect_rows.is_empty() {
+            "".to_string()
+        } else {
+            report::redirect_metrics_template(&redirect_rows.join("\n"))
+        };
 
-                // Compile the status_code metrics rows.
-                let mut status_code_rows = Vec::new();
-                for metric in status_code_metrics {
-                    status_code_rows.push(report::status_code_metrics_row(metric));
-                }
+        // Compile the report template.
+        report::build_report(
+            &start_time,
+            &end_time,
+            &host,
+            report::SwanlingReportTemplates {
+                raw_requests_template: &raw_requests_rows.join("\n"),
+                hosts_template: &hosts_template,
+                redirects_template: &redirects_template,
+                raw_responses_template: &raw_responses_rows.join("\n"),
+                co_requests_template: &co_requests_template,
+                co_responses_template: &co_responses_template,
+                percentile_headers: &report::percentile_headers(&percentiles),
+                histograms_template: &histograms_template,
+                run_id_template: &report::run_id_template(&self.metrics.run_id),
+                labels_template: &report::labels_template(&self.metrics.labels),
+                aborted_template: &report::aborted_template(self.metrics.aborted.as_ref()),
+                annotations_template: &annotations_template,
+                generator_template: &report::generator_template(&self.metrics.generator),
+                tasks_template: &tasks_template,
+                status_codes_template: &status_code_template,
+                errors_template: &errors_template,
+            },
+        )
+    }
 
-                // Compile the status_code metrics template.
-                status_code_template =
-                    report::status_code_metrics_template(&status_code_rows.join("\n"));
-            } else {
-                // If --status-codes is not enabled, return an empty template.
-                status_code_template = "".to_string();
-            }
-
-            // Compile the report template.
-            let report = report::build_report(
-                &start_time,
-                &end_time,
-                &host,
-                report::SwanlingReportTemplates {
-                    raw_requests_template: &raw_requests_rows.join("\n"),
-                    raw_responses_template: &raw_responses_rows.join("\n"),
-                    co_requests_template: &co_requests_template,
-                    co_responses_template: &co_responses_template,
-                    tasks_template: &tasks_template,
-                    status_codes_template: &status_code_template,
-                    errors_template: &errors_template,
-                },
-            );
+    // Write an HTML-formatted report, if enabled.
+    pub(crate) async fn write_html_report(
+        &mut self,
+        report_file: Option<&mut File>,
+    ) -> Result<(), SwanlingError> {
+        // Only write the report if enabled.
+        if let Some(report_file) = report_file {
+            let report = self.build_html_report();
 
             // Write the report to file.
             if let Err(e) = report_file.write(report.as_ref()).await {
@@ -2837,6 +4831,70 @@ impl SwanlingAttack {
 
         Ok(())
     }
+
+    // Write timestamped snapshots of the html report and json metrics next to the report
+    // file, so partial results survive even if the load test doesn't finish.
+    pub(crate) async fn write_report_snapshot(&mut self) {
+        let report_file_path = match self.get_report_file_path() {
+            Some(report_file_path) => report_file_path,
+            None => return,
+        };
+
+        // Name the snapshots after the report file, ie report-20240601T020500.html.
+        let path = std::path::Path::new(&report_file_path);
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let timestamp = Local::now().format("%Y%m%dT%H%M%S");
+        let html_path = path.with_file_name(format!("{}-{}.html", stem, timestamp));
+        let json_path = path.with_file_name(format!("{}-{}.json", stem, timestamp));
+
+        if let Err(e) = tokio::fs::write(&html_path, self.build_html_report()).await {
+            warn!(
+                "failed to write report snapshot {}: {}",
+                html_path.display(),
+                e
+            );
+            return;
+        }
+        match serde_json::to_string(&self.metrics) {
+            Ok(json) => {
+                if let Err(e) = tokio::fs::write(&json_path, json).await {
+                    warn!(
+                        "failed to write metrics snapshot {}: {}",
+                        json_path.display(),
+                        e
+                    );
+                    return;
+                }
+            }
+            Err(e) => {
+                warn!("failed to serialize metrics snapshot: {}", e);
+                return;
+            }
+        }
+
+        info!(
+            "wrote report snapshot to: {} and {}",
+            html_path.display(),
+            json_path.display()
+        );
+    }
+}
+
+/// The host of a requested URL, including the port if it's not the default port.
+pub(crate) fn request_host(url: &url::Url) -> String {
+    let host = url.host_str().unwrap_or_default();
+    match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    }
+}
+
+/// Request tags formatted as `key=value` pairs, separated by semicolons.
+pub(crate) fn format_tags(tags: &BTreeMap<String, String>) -> String {
+    tags.iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<String>>()
+        .join(";")
 }
 
 /// Helper to calculate requests and fails per seconds.
@@ -2903,7 +4961,33 @@ pub(crate) fn update_max_time(mut global_max: usize, max: usize) -> usize {
     global_max
 }
 
-/// Get the response time that a certain number of percent of the requests finished within.
+/// Format a row of a percentiles table, with a column for each value.
+fn percentiles_row(name: &str, values: &[String]) -> String {
+    let mut row = format!(" {:<24}", util::truncate_string(name, 24));
+    for value in values {
+        row.push_str(&format!(" | {:>6}", value));
+    }
+    row
+}
+
+/// Calculate the response time within each percentile, formatted for display.
+fn percentile_values(data: &SwanlingRequestMetricTimingData, percentiles: &[f64]) -> Vec<String> {
+    percentiles
+        .iter()
+        .map(|percentile| {
+            calculate_response_time_percentile(
+                &data.times,
+                data.counter,
+                data.minimum_time,
+                data.maximum_time,
+                (percentile / 100.0) as f32,
+            )
+        })
+        .collect()
+}
+
+/// Get the response time that a certain number of percent of the requests finished within,
+/// formatted for display.
 pub(crate) fn calculate_response_time_percentile(
     response_times: &BTreeMap<usize, usize>,
     total_requests: usize,
@@ -2911,6 +4995,23 @@ pub(crate) fn calculate_response_time_percentile(
     max: usize,
     percent: f32,
 ) -> String {
+    format_number(response_time_percentile(
+        response_times,
+        total_requests,
+        min,
+        max,
+        percent,
+    ))
+}
+
+/// Get the response time that a certain number of percent of the requests finished within.
+pub(crate) fn response_time_percentile(
+    response_times: &BTreeMap<usize, usize>,
+    total_requests: usize,
+    min: usize,
+    max: usize,
+    percent: f32,
+) -> usize {
     let percentile_request = (total_requests as f32 * percent).round() as usize;
     debug!(
         "percentile: {}, request {} of total {}",
@@ -2923,15 +5024,15 @@ pub(crate) fn calculate_response_time_percentile(
         total_count += counter;
         if total_count >= percentile_request {
             if *value < min {
-                return format_number(min);
+                return min;
             } else if *value > max {
-                return format_number(max);
+                return max;
             } else {
-                return format_number(*value);
+                return *value;
             }
         }
     }
-    format_number(0)
+    0
 }
 
 /// Helper to count and aggregate seen status codes.
@@ -3066,6 +5167,48 @@ mod test {
         assert!((fails_per_second - 1.0).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn metrics_format() {
+        assert_eq!(
+            SwanlingMetricsFormat::from_str("table").unwrap(),
+            SwanlingMetricsFormat::Table
+        );
+        assert_eq!(
+            SwanlingMetricsFormat::from_str("Compact").unwrap(),
+            SwanlingMetricsFormat::Compact
+        );
+        assert_eq!(
+            SwanlingMetricsFormat::from_str("JSON").unwrap(),
+            SwanlingMetricsFormat::Json
+        );
+        assert!(SwanlingMetricsFormat::from_str("csv").is_err());
+    }
+
+    #[test]
+    fn compact_metrics() {
+        let mut metrics = SwanlingMetrics {
+            duration: 10,
+            users: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            metrics.fmt_compact(),
+            "[10s] users: 2 requests: 0 fails: 0 (0.0%) req/s: 0.00 fail/s: 0.00 avg: 0.00ms"
+        );
+
+        // Summarize requests on a single line.
+        let mut request = SwanlingRequestMetricAggregate::new("/", SwanlingMethod::Get, 0);
+        request.success_count = 1_500;
+        request.fail_count = 500;
+        request.raw_data.total_time = 30_000;
+        request.raw_data.counter = 2_000;
+        metrics.requests.insert("GET /".to_string(), request);
+        assert_eq!(
+            metrics.fmt_compact(),
+            "[10s] users: 2 requests: 2,000 fails: 500 (25.0%) req/s: 200.00 fail/s: 50.00 avg: 15.00ms"
+        );
+    }
+
     #[test]
     fn swanling_raw_request() {
         const PATH: &str = "http://127.0.0.1/";
@@ -3304,4 +5447,38 @@ mod test {
         assert_eq!(request.raw_data.total_time, 987657045);
         assert_eq!(request.raw_data.counter, 8);
     }
+
+    #[tokio::test]
+    async fn metrics_shards() {
+        let shards = SwanlingMetricsShards::new();
+
+        // Merge the same requests several times, as sent by several users.
+        for response_time in 1..=10 {
+            for path in &["/", "/about", "/contact"] {
+                let mut request = SwanlingRequestMetricAggregate::new(path, SwanlingMethod::Get, 0);
+                request.record_time(response_time, false);
+                request.success_count += 1;
+                shards.merge(format!("GET {}", path), request);
+            }
+        }
+
+        // Each request is merged on only one shard.
+        let collected = shards.collect().await;
+        let mut requests = SwanlingRequestMetrics::new();
+        for shard in collected {
+            for (key, request) in shard {
+                assert!(requests.insert(key, request).is_none());
+            }
+        }
+        assert_eq!(requests.len(), 3);
+        for request in requests.values() {
+            assert_eq!(request.success_count, 10);
+            assert_eq!(request.raw_data.counter, 10);
+            assert_eq!(request.raw_data.minimum_time, 1);
+            assert_eq!(request.raw_data.maximum_time, 10);
+        }
+
+        // Collecting returns only what was merged since the last collection.
+        assert!(shards.collect().await.iter().all(|shard| shard.is_empty()));
+    }
 }
@@ -0,0 +1,290 @@
+//! Monitor the resources used by the load generator itself.
+//!
+//! While a load test runs, Swanling samples its own CPU usage, resident memory, open sockets
+//! and number of tokio tasks every second. The samples are summarized in the running metrics
+//! and the html report, making it possible to tell when the load generator rather than the
+//! target is the bottleneck: when the load generator is saturated, response times grow
+//! because requests are waiting to be sent or processed, not because the target is slow.
+//!
+//! Each sample also checks for signs that the load generator is saturated:
+//!  - CPU usage is at or above 90% of all available CPUs;
+//!  - the `--throttle-requests` queue stays full, so requests wait for the throttle;
+//!  - launching users keeps falling further behind the configured hatch rate;
+//!  - coordinated omission mitigation keeps backfilling requests.
+//!
+//! When a sign first appears, Swanling logs a warning and annotates the metrics, so the
+//! annotation is included in the html report.
+//!
+//! Resources are only sampled with the `monitor` feature, enabled by default. Only standalone
+//! load tests are monitored: in a Regatta, Workers don't display metrics and the Manager
+//! doesn't launch users, so neither samples its resources or checks for saturation.
+
+use std::collections::BTreeSet;
+use std::fmt;
+#[cfg(feature = "monitor")]
+use sysinfo::{Pid, System};
+
+use crate::metrics::{SwanlingAnnotation, SwanlingMetrics, GENERATOR_CPU_WARNING};
+use crate::util;
+
+/// How often to sample resource usage, in seconds.
+const SAMPLE_INTERVAL: usize = 1;
+
+/// How many consecutive samples the throttle, spawn drift and coordinated omission signs
+/// have to persist before the load generator is considered saturated.
+const SATURATION_SAMPLES: usize = 5;
+
+/// How far launching users can fall behind the hatch rate each second, in milliseconds,
+/// before it's considered falling behind.
+const SPAWN_DRIFT_WARNING: u64 = 100;
+
+/// The percentage of requests backfilled by coordinated omission mitigation at or above
+/// which it's considered constantly triggering.
+const COORDINATED_OMISSION_WARNING: f64 = 10.0;
+
+/// A sign that the load generator rather than the target is the bottleneck.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Saturation {
+    Cpu,
+    Throttle,
+    SpawnDrift,
+    CoordinatedOmission,
+}
+impl fmt::Display for Saturation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Saturation::Cpu => write!(f, "CPU usage"),
+            Saturation::Throttle => write!(f, "throttle"),
+            Saturation::SpawnDrift => write!(f, "launching users"),
+            Saturation::CoordinatedOmission => write!(f, "coordinated omission mitigation"),
+        }
+    }
+}
+
+/// Samples the resources used by the Swanling process.
+#[derive(Debug)]
+pub(crate) struct SwanlingMonitor {
+    #[cfg(feature = "monitor")]
+    system: System,
+    /// The Swanling process, if it can be determined on this platform.
+    #[cfg(feature = "monitor")]
+    pid: Option<Pid>,
+    /// Timer tracking when to take the next sample.
+    timer: std::time::Instant,
+    /// The signs of saturation found in the previous sample, so each is only reported
+    /// when it first appears.
+    saturated: BTreeSet<Saturation>,
+    /// How many consecutive samples found the throttle queue full.
+    throttle_full: usize,
+    /// How far launching users had fallen behind the hatch rate at the previous sample.
+    spawn_drift: u64,
+    /// How many consecutive samples found launching users falling further behind.
+    spawn_behind: usize,
+    /// How many requests had been made and backfilled at the previous sample.
+    requests: (usize, usize),
+    /// How many consecutive samples found coordinated omission mitigation triggering.
+    co_triggered: usize,
+}
+impl SwanlingMonitor {
+    pub(crate) fn new() -> Self {
+        #[cfg(feature = "monitor")]
+        let mut system = System::new();
+        #[cfg(feature = "monitor")]
+        let pid = sysinfo::get_current_pid().ok();
+        // CPU usage is calculated from the difference between two samples, so take an
+        // initial sample now.
+        #[cfg(feature = "monitor")]
+        if let Some(pid) = pid {
+            system.refresh_process(pid);
+        }
+
+        SwanlingMonitor {
+            #[cfg(feature = "monitor")]
+            system,
+            #[cfg(feature = "monitor")]
+            pid,
+            timer: std::time::Instant::now(),
+            saturated: BTreeSet::new(),
+            throttle_full: 0,
+            spawn_drift: 0,
+            spawn_behind: 0,
+            requests: (0, 0),
+            co_triggered: 0,
+        }
+    }
+
+    /// Restart the timer and forget earlier signs of saturation when a new load test starts.
+    pub(crate) fn reset_timer(&mut self) {
+        self.saturated.clear();
+        self.throttle_full = 0;
+        self.spawn_drift = 0;
+        self.spawn_behind = 0;
+        self.requests = (0, 0);
+        self.co_triggered = 0;
+        self.timer = std::time::Instant::now();
+    }
+
+    /// Sample the resources used by the load generator and check for signs it's saturated,
+    /// if it's time to do so. `throttle_full` is whether the throttle queue is currently
+    /// full, and `spawn_drift` is the total milliseconds launching users has fallen behind
+    /// the hatch rate.
+    pub(crate) fn sample(
+        &mut self,
+        metrics: &mut SwanlingMetrics,
+        throttle_full: bool,
+        spawn_drift: u64,
+    ) {
+        if !util::timer_expired(self.timer, SAMPLE_INTERVAL) {
+            return;
+        }
+        self.timer = std::time::Instant::now();
+
+        let mut saturated = Vec::new();
+
+        if let Some(cpu) = self.sample_resources(metrics) {
+            if cpu >= GENERATOR_CPU_WARNING {
+                saturated.push((Saturation::Cpu, format!("CPU usage is {:.1}%", cpu)));
+            }
+        }
+
+        self.throttle_full = if throttle_full {
+            self.throttle_full + 1
+        } else {
+            0
+        };
+        if self.throttle_full >= SATURATION_SAMPLES {
+            saturated.push((
+                Saturation::Throttle,
+                format!(
+                    "requests have waited for --throttle-requests for {} seconds",
+                    self.throttle_full
+                ),
+            ));
+        }
+
+        // Spawn drift is reset with the run state, in which case start over.
+        let behind = spawn_drift.saturating_sub(self.spawn_drift);
+        self.spawn_behind = if behind > SPAWN_DRIFT_WARNING {
+            self.spawn_behind + 1
+        } else {
+            0
+        };
+        self.spawn_drift = spawn_drift;
+        if self.spawn_behind >= SATURATION_SAMPLES {
+            saturated.push((
+                Saturation::SpawnDrift,
+                format!(
+                    "launching users fell {}ms further behind the hatch rate in the last second",
+                    behind
+                ),
+            ));
+        }
+
+        // Requests are reset with the metrics, in which case start over.
+        let requests = backfilled_requests(metrics);
+        let made = requests.0.saturating_sub(self.requests.0);
+        let backfilled = requests.1.saturating_sub(self.requests.1);
+        self.requests = requests;
+        let backfilled_percent = if made > 0 {
+            backfilled as f64 / made as f64 * 100.0
+        } else {
+            0.0
+        };
+        self.co_triggered = if backfilled_percent >= COORDINATED_OMISSION_WARNING {
+            self.co_triggered + 1
+        } else {
+            0
+        };
+        if self.co_triggered >= SATURATION_SAMPLES {
+            saturated.push((
+                Saturation::CoordinatedOmission,
+                format!(
+                    "coordinated omission mitigation backfilled {:.1}% of requests in the last second",
+                    backfilled_percent
+                ),
+            ));
+        }
+
+        // Report each sign of saturation when it first appears.
+        for (saturation, detail) in &saturated {
+            if !self.saturated.contains(saturation) {
+                warn!(
+                    "load generator may be saturated: {}, the load generator rather than the target may be limiting the load test",
+                    detail
+                );
+                metrics.annotations.push(SwanlingAnnotation::new(
+                    metrics.started,
+                    &format!(
+                        "load generator may be saturated ({}): {}",
+                        saturation, detail
+                    ),
+                ));
+            }
+        }
+        self.saturated = saturated
+            .into_iter()
+            .map(|(saturation, _)| saturation)
+            .collect();
+    }
+
+    // Sample the resources used by the Swanling process, returning the CPU usage.
+    #[cfg(feature = "monitor")]
+    fn sample_resources(&mut self, metrics: &mut SwanlingMetrics) -> Option<f64> {
+        let pid = self.pid?;
+        if !self.system.refresh_process(pid) {
+            return None;
+        }
+        let process = self.system.process(pid)?;
+
+        // CPU usage is reported as a percentage of one CPU.
+        let cpu = process.cpu_usage() as f64 / num_cpus::get() as f64;
+        let memory = process.memory() as f64 / 1_048_576.0;
+        let tasks = tokio::runtime::Handle::try_current()
+            .map_or(0, |runtime| runtime.metrics().num_alive_tasks());
+        metrics.generator.record(cpu, memory, open_sockets(), tasks);
+
+        Some(cpu)
+    }
+
+    // Resources are only sampled with the `monitor` feature.
+    #[cfg(not(feature = "monitor"))]
+    fn sample_resources(&mut self, _metrics: &mut SwanlingMetrics) -> Option<f64> {
+        None
+    }
+}
+
+// Count how many requests have been made, and how many additional requests were backfilled
+// by coordinated omission mitigation.
+fn backfilled_requests(metrics: &SwanlingMetrics) -> (usize, usize) {
+    metrics
+        .requests
+        .values()
+        .fold((0, 0), |(made, backfilled), request| {
+            let co_counter = request
+                .coordinated_omission_data
+                .as_ref()
+                .map_or(request.raw_data.counter, |data| data.counter);
+            (
+                made + request.raw_data.counter,
+                backfilled + co_counter.saturating_sub(request.raw_data.counter),
+            )
+        })
+}
+
+// Count the sockets opened by the Swanling process.
+#[cfg(all(feature = "monitor", target_os = "linux"))]
+fn open_sockets() -> usize {
+    match std::fs::read_dir("/proc/self/fd") {
+        Ok(entries) => entries
+            .filter_map(|entry| std::fs::read_link(entry.ok()?.path()).ok())
+            .filter(|target| target.to_string_lossy().starts_with("socket:"))
+            .count(),
+        Err(_) => 0,
+    }
+}
+
+// Open sockets are only counted on Linux.
+#[cfg(all(feature = "monitor", not(target_os = "linux")))]
+fn open_sockets() -> usize {
+    0
+}
@@ -0,0 +1,212 @@
+//! Optionally notify a webhook of load test events.
+//!
+//! When the `--notify-url` run-time option is set (or the matching
+//! [`SwanlingDefault`](../enum.SwanlingDefault.html) default configuration option), Swanling
+//! POSTs a JSON payload to the URL each time the load test changes phase, is paused or
+//! resumed, exceeds a threshold such as `--abort-on-error-rate` or `--latency-alert`, and
+//! when it completes, including summary metrics. Teams therefore learn how a load test went
+//! without watching a terminal.
+//!
+//! Each payload has a `text` field describing the event, which Slack and Microsoft Teams
+//! incoming webhooks display as the message, and an `event` field with its type. Other
+//! fields depend on the event. Webhooks are notified in order, and failed notifications
+//! are logged as warnings without affecting the load test.
+
+use serde_json::{json, Value};
+use std::time::Duration;
+use url::Url;
+
+use crate::metrics::{SwanlingMetrics, SwanlingRequestMetricTimingData};
+use crate::{AttackPhase, SwanlingEvent};
+
+/// How long to wait for the webhook to respond.
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The payload notifying that the load test completed, and a channel to acknowledge once
+/// it was posted.
+type SwanlingCompleted = (Value, flume::Sender<()>);
+
+/// Posts load test events to a webhook.
+#[derive(Debug)]
+pub(crate) struct SwanlingNotifier {
+    /// Used to notify the webhook that the load test completed.
+    completed_tx: flume::Sender<SwanlingCompleted>,
+}
+impl SwanlingNotifier {
+    /// Validate the webhook URL.
+    pub(crate) fn validate(notify_url: &str) -> Result<Url, String> {
+        let url = Url::parse(notify_url).map_err(|e| format!("is not a valid URL: {}", e))?;
+        if !["http", "https"].contains(&url.scheme()) {
+            return Err("must be an http or https URL".to_string());
+        }
+        Ok(url)
+    }
+
+    /// Start posting the events received from `events` to the webhook.
+    pub(crate) fn start(
+        notify_url: &str,
+        events: flume::Receiver<SwanlingEvent>,
+    ) -> Result<Self, String> {
+        let url = SwanlingNotifier::validate(notify_url)?;
+        let client = reqwest::Client::builder()
+            .timeout(NOTIFY_TIMEOUT)
+            .build()
+            .map_err(|e| e.to_string())?;
+        let (completed_tx, completed_rx) = flume::unbounded();
+        tokio::spawn(notify_main(client, url, events, completed_rx));
+        Ok(SwanlingNotifier { completed_tx })
+    }
+
+    /// Notify the webhook that the load test completed, with summary metrics, waiting until
+    /// it and all earlier events were posted.
+    pub(crate) async fn completed(&self, metrics: &SwanlingMetrics) {
+        let (ack_tx, ack_rx) = flume::bounded(1);
+        if self
+            .completed_tx
+            .send((completed_payload(metrics), ack_tx))
+            .is_ok()
+        {
+            let _ = ack_rx.recv_async().await;
+        }
+    }
+}
+
+// Post events to the webhook in the order they happened, until the load test is dropped.
+async fn notify_main(
+    client: reqwest::Client,
+    url: Url,
+    events: flume::Receiver<SwanlingEvent>,
+    completed_rx: flume::Receiver<SwanlingCompleted>,
+) {
+    loop {
+        tokio::select! {
+            biased;
+            event = events.recv_async() => match event {
+                Ok(event) => post(&client, &url, event_payload(&event)).await,
+                Err(_) => break,
+            },
+            completed = completed_rx.recv_async() => match completed {
+                Ok((payload, ack_tx)) => {
+                    // Events sent before the load test completed are already queued.
+                    for event in events.try_iter() {
+                        post(&client, &url, event_payload(&event)).await;
+                    }
+                    post(&client, &url, Some(payload)).await;
+                    let _ = ack_tx.send(());
+                }
+                Err(_) => break,
+            },
+        }
+    }
+}
+
+// Post a payload to the webhook, logging a warning if it fails.
+async fn post(client: &reqwest::Client, url: &Url, payload: Option<Value>) {
+    let payload = match payload {
+        Some(payload) => payload,
+        None => return,
+    };
+    match client.post(url.clone()).json(&payload).send().await {
+        Ok(response) if !response.status().is_success() => {
+            warn!("failed to notify {}: {}", url, response.status())
+        }
+        Ok(_) => (),
+        Err(e) => warn!("failed to notify {}: {}", url, e),
+    }
+}
+
+// Build the payload describing an event, or `None` for events that aren't notified.
+fn event_payload(event: &SwanlingEvent) -> Option<Value> {
+    let payload = match event {
+        // Users starting and stopping are too frequent to notify.
+        SwanlingEvent::UserStarted { .. } | SwanlingEvent::UserStopped { .. } => return None,
+        SwanlingEvent::PhaseChanged(phase) => json!({
+            "text": format!("Swanling load test phase: {}", phase_name(phase)),
+            "event": "phase_changed",
+            "phase": phase_name(phase),
+        }),
+        SwanlingEvent::Paused => json!({
+            "text": "Swanling load test paused",
+            "event": "paused",
+        }),
+        SwanlingEvent::Resumed => json!({
+            "text": "Swanling load test resumed",
+            "event": "resumed",
+        }),
+        SwanlingEvent::ThresholdBreached { threshold, detail } => json!({
+            "text": format!("Swanling load test aborted: {}", detail),
+            "event": "threshold_breached",
+            "threshold": threshold,
+            "detail": detail,
+        }),
+        SwanlingEvent::LatencyAlert {
+            request,
+            threshold,
+            detail,
+        } => json!({
+            "text": format!("Swanling latency alert: {}", detail),
+            "event": "latency_alert",
+            "request": request,
+            "threshold": threshold,
+            "detail": detail,
+        }),
+    };
+    Some(payload)
+}
+
+// The name of a phase, as included in payloads.
+fn phase_name(phase: &AttackPhase) -> String {
+    format!("{:?}", phase).to_lowercase()
+}
+
+// Build the payload notifying that the load test completed, summarizing its metrics.
+fn completed_payload(metrics: &SwanlingMetrics) -> Value {
+    let mut times = SwanlingRequestMetricTimingData::new(None);
+    let mut fails = 0;
+    for request in metrics.requests.values() {
+        times.merge(&request.raw_data);
+        fails += request.fail_count;
+    }
+    let requests = times.counter;
+    let fail_percent = if requests > 0 {
+        fails as f64 / requests as f64 * 100.0
+    } else {
+        0.0
+    };
+    let requests_per_second = requests as f64 / metrics.duration.max(1) as f64;
+    let average_time = times.total_time / requests.max(1);
+    let percentiles = times.percentiles(&[95.0, 99.0]);
+    let p95 = percentiles.get("95").copied().unwrap_or(0);
+    let p99 = percentiles.get("99").copied().unwrap_or(0);
+
+    let mut text = format!(
+        "Swanling load test completed in {}s with {} users: {} requests, {:.2}% failed, {:.2} requests/s, average {}ms, p95 {}ms, p99 {}ms",
+        metrics.duration,
+        metrics.users,
+        requests,
+        fail_percent,
+        requests_per_second,
+        average_time,
+        p95,
+        p99
+    );
+    if let Some(aborted) = metrics.aborted.as_ref() {
+        text = format!("{} (aborted: {})", text, aborted);
+    }
+
+    json!({
+        "text": text,
+        "event": "completed",
+        "duration": metrics.duration,
+        "users": metrics.users,
+        "requests": requests,
+        "fails": fails,
+        "fail_percent": fail_percent,
+        "requests_per_second": requests_per_second,
+        "average_response_time": average_time,
+        "p95_response_time": p95,
+        "p99_response_time": p99,
+        "aborted": metrics.aborted,
+        "labels": metrics.labels,
+    })
+}
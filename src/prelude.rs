@@ -7,11 +7,17 @@
 //! use swanling::prelude::*;
 //! ```
 
-pub use crate::metrics::{SwanlingCoordinatedOmissionMitigation, SwanlingMetrics};
+pub use crate::metrics::{
+    SwanlingCoordinatedOmissionMitigation, SwanlingMetrics, SwanlingMetricsFormat,
+};
+#[cfg(feature = "sql")]
+pub use crate::sql::SwanlingSqlPool;
 pub use crate::swanling::{
-    SwanlingTask, SwanlingTaskError, SwanlingTaskFunction, SwanlingTaskResult, SwanlingTaskSet,
-    SwanlingUser,
+    SwanlingCleanupFunction, SwanlingClientMode, SwanlingRedirectPolicy, SwanlingRequestBuilder,
+    SwanlingSessionFunction, SwanlingTask, SwanlingTaskError, SwanlingTaskFunction,
+    SwanlingTaskPredicate, SwanlingTaskResult, SwanlingTaskSet, SwanlingUser,
 };
+pub use crate::template::SwanlingTemplateContext;
 pub use crate::{
     task, taskset, SwanlingAttack, SwanlingDefault, SwanlingDefaultType, SwanlingError,
     SwanlingScheduler,
@@ -0,0 +1,135 @@
+//! Display a progress bar while the load test runs.
+//!
+//! When stdout is a terminal and `--run-time` is set, Swanling redraws a single line once a
+//! second showing how much of the run time has elapsed, an estimate of when the load test
+//! will finish, the current requests per second and the percentage of requests that failed.
+//! The line is cleared before running metrics or the final metrics are displayed.
+
+use std::io::{self, IsTerminal, Write};
+use std::time::Instant;
+
+use crate::metrics::SwanlingMetrics;
+use crate::{AttackPhase, SwanlingProgress};
+
+/// How many characters wide the bar itself is.
+const BAR_WIDTH: usize = 30;
+
+/// Redraws a progress bar on a single line of stdout.
+#[derive(Debug)]
+pub(crate) struct SwanlingProgressBar {
+    /// Timer tracking when the progress bar was last redrawn.
+    timer: Instant,
+    /// How many requests had been made when the progress bar was last redrawn.
+    requests: usize,
+    /// Whether the progress bar is currently displayed.
+    displayed: bool,
+}
+impl SwanlingProgressBar {
+    /// Returns a progress bar if stdout is a terminal, otherwise it would fill logs that
+    /// are redirected to a file with partial lines.
+    pub(crate) fn new() -> Option<Self> {
+        if !io::stdout().is_terminal() {
+            return None;
+        }
+        Some(SwanlingProgressBar {
+            timer: Instant::now(),
+            requests: 0,
+            displayed: false,
+        })
+    }
+
+    /// Redraw the progress bar, at most once per second.
+    pub(crate) fn update(&mut self, progress: &SwanlingProgress, metrics: &SwanlingMetrics) {
+        let elapsed = self.timer.elapsed().as_secs_f64();
+        if self.displayed && elapsed < 1.0 {
+            return;
+        }
+        self.timer = Instant::now();
+
+        let (requests, fails) =
+            metrics
+                .requests
+                .values()
+                .fold((0, 0), |(requests, fails), request| {
+                    (
+                        requests + request.success_count + request.fail_count,
+                        fails + request.fail_count,
+                    )
+                });
+        // Metrics are reset once all users have started, so count from zero again.
+        let requests_per_second = if elapsed > 0.0 && requests >= self.requests {
+            (requests - self.requests) as f64 / elapsed
+        } else {
+            0.0
+        };
+        self.requests = requests;
+        let fail_percent = if requests > 0 {
+            fails as f64 / requests as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        let remaining = progress.remaining.unwrap_or_default();
+        let run_time = progress.elapsed + remaining;
+        let filled = (progress.elapsed * BAR_WIDTH)
+            .checked_div(run_time)
+            .unwrap_or_default()
+            .min(BAR_WIDTH);
+        let status = if progress.paused {
+            " (paused)"
+        } else if progress.phase == AttackPhase::Starting {
+            " (starting)"
+        } else {
+            ""
+        };
+
+        // Clear the line before redrawing, as it may have been longer.
+        print!(
+            "\r\x1b[2K[{}{}] {}/{} ETA {} | {:.1} req/s | {:.1}% failed{}",
+            "=".repeat(filled),
+            " ".repeat(BAR_WIDTH - filled),
+            format_seconds(progress.elapsed),
+            format_seconds(run_time),
+            format_seconds(remaining),
+            requests_per_second,
+            fail_percent,
+            status
+        );
+        let _ = io::stdout().flush();
+        self.displayed = true;
+    }
+
+    /// Clear the progress bar, so other output starts at the beginning of an empty line.
+    pub(crate) fn clear(&mut self) {
+        if self.displayed {
+            print!("\r\x1b[2K");
+            let _ = io::stdout().flush();
+            self.displayed = false;
+        }
+    }
+}
+
+// Format seconds as hours, minutes and seconds, for example 1h2m3s.
+fn format_seconds(seconds: usize) -> String {
+    let (hours, minutes, seconds) = (seconds / 3_600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}h{}m{}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m{}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seconds() {
+        assert_eq!(format_seconds(0), "0s");
+        assert_eq!(format_seconds(45), "45s");
+        assert_eq!(format_seconds(125), "2m5s");
+        assert_eq!(format_seconds(3_723), "1h2m3s");
+    }
+}
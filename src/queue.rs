@@ -0,0 +1,83 @@
+//! Helpers to publish messages to Kafka, AMQP or other message queues from tasks.
+//!
+//! Many systems accept work both over HTTP and from a message queue, for example an order
+//! service that's called by a web shop and also consumes events published by a warehouse. To
+//! load test both paths at once, a producer registered with
+//! [`SwanlingAttack::set_producer`](../struct.SwanlingAttack.html#method.set_producer) is
+//! shared by all users, which publish messages with
+//! [`SwanlingUser::publish`](../swanling/struct.SwanlingUser.html#method.publish).
+//!
+//! Swanling doesn't bundle a Kafka or AMQP client. Instead the load test implements
+//! [`SwanlingProducer`](./trait.SwanlingProducer.html) with the client of its choice. For
+//! Kafka, the destination is a topic and the key selects the partition. For AMQP, the
+//! destination is an exchange and the key is the routing key.
+//!
+//! Each message published is recorded like a request, with the `PUBLISH` method and named
+//! after its destination, so how long it took to publish and how many failed is displayed
+//! with the other requests and included in the request log.
+//!
+//! This module is only available with the `queue` feature.
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//! use swanling::queue::{SwanlingProducer, SwanlingQueueError};
+//!
+//! /// Publishes messages with the client of your choice.
+//! struct Producer;
+//! #[async_trait::async_trait]
+//! impl SwanlingProducer for Producer {
+//!     async fn publish(
+//!         &self,
+//!         _destination: &str,
+//!         _key: Option<&str>,
+//!         _payload: &[u8],
+//!     ) -> Result<(), SwanlingQueueError> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! /// Place an order over HTTP, then publish a shipment event for it.
+//! async fn place_order(user: &SwanlingUser) -> SwanlingTaskResult {
+//!     let _swanling = user.post("/order", "product=42").await?;
+//!     user.publish("shipments", Some("42"), br#"{"product":42}"#).await?;
+//!
+//!     Ok(())
+//! }
+//!
+//! fn main() -> Result<(), SwanlingError> {
+//!     SwanlingAttack::initialize()?
+//!         .register_taskset(taskset!("LoadTest").register_task(task!(place_order)))
+//!         .set_producer(Producer);
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use async_trait::async_trait;
+use std::fmt;
+use std::sync::Arc;
+
+/// An error returned by the message queue client.
+pub type SwanlingQueueError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Publishes messages to a message queue, implemented with the client of your choice.
+#[async_trait]
+pub trait SwanlingProducer: Send + Sync {
+    /// Publish `payload` to `destination`, a Kafka topic or an AMQP exchange, with an optional
+    /// partition or routing `key`. Returns once the message was acknowledged.
+    async fn publish(
+        &self,
+        destination: &str,
+        key: Option<&str>,
+        payload: &[u8],
+    ) -> Result<(), SwanlingQueueError>;
+}
+
+/// The producer shared by all users, see the [module documentation](./index.html).
+#[derive(Clone)]
+pub(crate) struct SwanlingQueue(pub(crate) Arc<dyn SwanlingProducer>);
+impl fmt::Debug for SwanlingQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SwanlingQueue").finish()
+    }
+}
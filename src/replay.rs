@@ -0,0 +1,317 @@
+//! Optionally replay a previously recorded request log.
+//!
+//! When the `--replay-log` run-time option is set (or the
+//! [`SwanlingDefault::ReplayLog`](../enum.SwanlingDefault.html#variant.ReplayLog) default
+//! configuration option), Swanling reads a request log previously written with the
+//! `--request-log` option and replays every request it contains against the configured
+//! host, instead of running the [`SwanlingTask`](../swanling/struct.SwanlingTask.html)s
+//! defined by the load test.
+//!
+//! Requests are replayed with the same method, path and request name as recorded, and
+//! each [`SwanlingUser`](../swanling/struct.SwanlingUser.html) from the original load test
+//! is simulated by its own replay thread so requests made by the same user are replayed
+//! in order. By default the original inter-arrival times are preserved. The
+//! `--replay-speed` run-time option compresses (values larger than `1.0`) or expands
+//! (values smaller than `1.0`) the recorded timing by the given factor.
+//!
+//! Request logs written in the `json`, `ndjson`, `pretty-json` and `csv` formats can be
+//! replayed, including logs that were gzip compressed because their name ends with `.gz`.
+//! The `raw` format is not supported.
+
+use flate2::read::GzDecoder;
+use http::Method;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::str::FromStr;
+use std::time;
+use url::Url;
+
+use crate::swanling::{
+    swanling_method_from_method, SwanlingMethod, SwanlingUser, SwanlingUserCommand,
+};
+use crate::SwanlingError;
+
+/// A single request loaded from a request log, to be replayed.
+#[derive(Debug, Clone)]
+pub(crate) struct SwanlingReplayRequest {
+    /// How many milliseconds the original load test had been running when the request
+    /// was made.
+    pub elapsed: u64,
+    /// The method used to make the request.
+    pub method: SwanlingMethod,
+    /// The name of the request.
+    pub name: String,
+    /// The path (and query string) that was requested.
+    pub path: String,
+    /// Which SwanlingUser thread originally made the request.
+    pub user: usize,
+}
+
+/// The subset of a json-formatted request log line needed to replay it.
+#[derive(Debug, Deserialize)]
+struct SwanlingReplayJsonRequest {
+    elapsed: u64,
+    method: SwanlingMethod,
+    name: String,
+    url: String,
+    update: bool,
+    user: usize,
+}
+
+/// Load all requests from a request log, sorted by when they were originally made.
+pub(crate) fn load_replay_log(
+    replay_log: &str,
+) -> Result<Vec<SwanlingReplayRequest>, SwanlingError> {
+    let contents = if replay_log.ends_with(".gz") {
+        let mut contents = String::new();
+        GzDecoder::new(std::fs::File::open(replay_log)?).read_to_string(&mut contents)?;
+        contents
+    } else {
+        std::fs::read_to_string(replay_log)?
+    };
+    let mut lines = contents.lines().filter(|l| !l.trim().is_empty()).peekable();
+
+    let mut requests = Vec::new();
+    match lines.peek() {
+        // JSON format, one SwanlingRequestMetric per line, or indented over several lines.
+        Some(first) if first.trim_start().starts_with('{') => {
+            let records = serde_json::Deserializer::from_str(&contents)
+                .into_iter::<SwanlingReplayJsonRequest>();
+            for (index, record) in records.enumerate() {
+                let request = record.map_err(|e| SwanlingError::InvalidOption {
+                    option: "--replay-log".to_string(),
+                    value: replay_log.to_string(),
+                    detail: format!("Failed to parse record {}: {}", index + 1, e),
+                })?;
+                // Updates to earlier requests were not actual requests, and messages published
+                // to a message queue can't be replayed over HTTP.
+                if request.update || request.method == SwanlingMethod::Publish {
+                    continue;
+                }
+                requests.push(SwanlingReplayRequest {
+                    elapsed: request.elapsed,
+                    method: request.method,
+                    name: request.name,
+                    path: path_from_url(&request.url),
+                    user: request.user,
+                });
+            }
+        }
+        // CSV format, the first line is a header.
+        Some(first) if first.starts_with("elapsed,") => {
+            let header = split_csv_row(lines.next().unwrap());
+            let column = |name: &str| -> Result<usize, SwanlingError> {
+                header
+                    .iter()
+                    .position(|c| c == name)
+                    .ok_or_else(|| SwanlingError::InvalidOption {
+                        option: "--replay-log".to_string(),
+                        value: replay_log.to_string(),
+                        detail: format!("CSV header is missing the {} column.", name),
+                    })
+            };
+            let elapsed_column = column("elapsed")?;
+            let method_column = column("method")?;
+            let name_column = column("name")?;
+            let url_column = column("url")?;
+            let update_column = column("update")?;
+            let user_column = column("user")?;
+
+            for (index, line) in lines.enumerate() {
+                let row = split_csv_row(line);
+                let invalid_row = |detail: &str| SwanlingError::InvalidOption {
+                    option: "--replay-log".to_string(),
+                    value: replay_log.to_string(),
+                    // Add 2 to the index as the header is line 1.
+                    detail: format!("Failed to parse line {}: {}", index + 2, detail),
+                };
+                if row.len() < header.len() {
+                    return Err(invalid_row("too few columns"));
+                }
+                if row[update_column] == "true" || row[method_column] == "PUBLISH" {
+                    continue;
+                }
+                let method = Method::from_str(&row[method_column])
+                    .ok()
+                    .and_then(|m| swanling_method_from_method(m).ok())
+                    .ok_or_else(|| invalid_row("invalid method"))?;
+                requests.push(SwanlingReplayRequest {
+                    elapsed: u64::from_str(&row[elapsed_column])
+                        .map_err(|_| invalid_row("invalid elapsed"))?,
+                    method,
+                    name: row[name_column].to_string(),
+                    path: path_from_url(&row[url_column]),
+                    user: usize::from_str(&row[user_column])
+                        .map_err(|_| invalid_row("invalid user"))?,
+                });
+            }
+        }
+        Some(_) => {
+            return Err(SwanlingError::InvalidOption {
+                option: "--replay-log".to_string(),
+                value: replay_log.to_string(),
+                detail: "Unsupported request log format, expected: csv or json.".to_string(),
+            });
+        }
+        None => (),
+    }
+
+    // Requests are not necessarily logged in the order they were made.
+    requests.sort_by_key(|r| r.elapsed);
+
+    Ok(requests)
+}
+
+/// Group replayed requests by the SwanlingUser that originally made them.
+pub(crate) fn requests_per_user(
+    requests: &[SwanlingReplayRequest],
+) -> BTreeMap<usize, Vec<SwanlingReplayRequest>> {
+    let mut users: BTreeMap<usize, Vec<SwanlingReplayRequest>> = BTreeMap::new();
+    for request in requests {
+        users.entry(request.user).or_default().push(request.clone());
+    }
+    users
+}
+
+// Strip the scheme and host from a logged URL, so it can be replayed against a new host.
+fn path_from_url(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(u) => u[url::Position::BeforePath..].to_string(),
+        // Not a full URL, assume it's already a path.
+        Err(_) => url.to_string(),
+    }
+}
+
+// Split a CSV row written by the request logger, removing quotes around fields.
+fn split_csv_row(row: &str) -> Vec<String> {
+    let mut columns = Vec::new();
+    let mut column = String::new();
+    let mut quoted = false;
+    for c in row.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => columns.push(std::mem::take(&mut column)),
+            _ => column.push(c),
+        }
+    }
+    columns.push(column);
+    columns
+}
+
+/// Replay thread, making each request at the same offset from the start of the load test
+/// as it was originally made, adjusted by `replay_speed`.
+pub(crate) async fn replay_main(
+    thread_number: usize,
+    thread_user: SwanlingUser,
+    requests: Vec<SwanlingReplayRequest>,
+    first_elapsed: u64,
+    replay_speed: f32,
+    started: time::Instant,
+    thread_receiver: flume::Receiver<SwanlingUserCommand>,
+) {
+    info!(
+        "launching replay user {} with {} requests...",
+        thread_number,
+        requests.len()
+    );
+
+    let mut started = started;
+    'replay: for request in requests {
+        // Determine when this request should be made.
+        let offset = ((request.elapsed - first_elapsed) as f64 / replay_speed as f64) as u64;
+
+        loop {
+            let delay = time::Duration::from_millis(offset).checked_sub(started.elapsed());
+
+            // Sleep until it's time to make the request, waking if told to exit or pause.
+            let command = match delay {
+                Some(d) => match tokio::time::timeout(d, thread_receiver.recv_async()).await {
+                    Ok(Ok(command)) => Some(command),
+                    // The parent has gone away.
+                    Ok(Err(_)) => Some(SwanlingUserCommand::Exit),
+                    // Timeout expired, time to make the request.
+                    Err(_) => None,
+                },
+                None => thread_receiver.try_recv().ok(),
+            };
+            match command {
+                Some(SwanlingUserCommand::Exit) => break 'replay,
+                // Wait until resumed, then delay the remaining requests by the time paused.
+                Some(SwanlingUserCommand::Wait) => {
+                    let paused = time::Instant::now();
+                    loop {
+                        match thread_receiver.recv_async().await {
+                            Ok(SwanlingUserCommand::Run) => break,
+                            Ok(SwanlingUserCommand::Wait) | Ok(SwanlingUserCommand::Retire) => (),
+                            Ok(SwanlingUserCommand::Exit) | Err(_) => break 'replay,
+                        }
+                    }
+                    started += paused.elapsed();
+                }
+                // Replayed users aren't retired, they replay their requests until done.
+                Some(SwanlingUserCommand::Run) | Some(SwanlingUserCommand::Retire) => (),
+                None => break,
+            }
+        }
+
+        let request_builder = match request.method {
+            SwanlingMethod::Delete => thread_user.swanling_delete(&request.path).await,
+            SwanlingMethod::Get => thread_user.swanling_get(&request.path).await,
+            SwanlingMethod::Head => thread_user.swanling_head(&request.path).await,
+            SwanlingMethod::Options => thread_user.swanling_options(&request.path).await,
+            SwanlingMethod::Patch => thread_user.swanling_patch(&request.path).await,
+            SwanlingMethod::Post => thread_user.swanling_post(&request.path).await,
+            SwanlingMethod::Put => thread_user.swanling_put(&request.path).await,
+            // Messages published to a message queue aren't loaded from the request log.
+            SwanlingMethod::Publish => continue,
+        };
+        match request_builder {
+            Ok(request_builder) => {
+                // Errors are recorded in the request metrics.
+                let _ = thread_user
+                    .swanling_send(request_builder, Some(&request.name))
+                    .await;
+            }
+            Err(e) => warn!(
+                "failed to replay {} {}: {}",
+                request.method, request.path, e
+            ),
+        }
+    }
+
+    info!("exiting replay user {}...", thread_number);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_row() {
+        assert_eq!(
+            split_csv_row(r#"22143,GET,"(Anon) user page","http://apache/user/4",false"#),
+            vec![
+                "22143",
+                "GET",
+                "(Anon) user page",
+                "http://apache/user/4",
+                "false"
+            ]
+        );
+        assert_eq!(
+            split_csv_row(r#"1,"name, with comma",2"#),
+            vec!["1", "name, with comma", "2"]
+        );
+    }
+
+    #[test]
+    fn url_to_path() {
+        assert_eq!(path_from_url("http://apache/user/4"), "/user/4");
+        assert_eq!(
+            path_from_url("https://example.com/misc/drupal.js?q9apdy"),
+            "/misc/drupal.js?q9apdy"
+        );
+        assert_eq!(path_from_url("/already/a/path"), "/already/a/path");
+    }
+}
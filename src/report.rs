@@ -1,19 +1,93 @@
 //! Optionally writes an html-formatted summary report after running a load test.
+//!
+//! The metrics of several load tests can also be merged into a single report, for
+//! example when running the same load test from several servers without a Manager. Each
+//! load test writes its metrics as JSON with `--summary-format json`, and the files are
+//! then merged with the `--merge-reports` run-time option, or with [`merge`].
 
-use crate::metrics;
+use crate::metrics::{self, SwanlingMetrics};
+use crate::SwanlingError;
+
+use chrono::{Local, LocalResult, TimeZone};
 
 use std::collections::BTreeMap;
-use std::mem;
+use std::fs;
 
 use serde::Serialize;
 
+/// The latency histogram bucket boundaries in milliseconds if `--histogram-buckets` isn't set.
+pub(crate) const DEFAULT_HISTOGRAM_BUCKETS: &[usize] =
+    &[10, 25, 50, 100, 250, 500, 1000, 2500, 5000];
+
+/// Merges the metrics of several load tests into one, for example the JSON metrics written
+/// by each of several load tests that ran at the same time without a Manager.
+///
+/// Requests with the same method and name are combined, as are tasks with the same
+/// index, and errors. The merged load test starts when the earliest load test started,
+/// and ends when the latest load test ended.
+///
+/// # Example
+/// ```rust
+/// use swanling::metrics::SwanlingMetrics;
+///
+/// let first: SwanlingMetrics = serde_json::from_str(r#"{"hash": 0, "started": 1625000000,
+///     "duration": 10, "users": 2, "requests": {}, "tasks": [], "errors": {}}"#).unwrap();
+/// let second: SwanlingMetrics = serde_json::from_str(r#"{"hash": 0, "started": 1625000005,
+///     "duration": 10, "users": 3, "requests": {}, "tasks": [], "errors": {}}"#).unwrap();
+///
+/// let merged = swanling::report::merge(vec![first, second]);
+/// assert_eq!(merged.users, 5);
+/// assert_eq!(merged.duration, 15);
+/// ```
+pub fn merge(metrics: Vec<SwanlingMetrics>) -> SwanlingMetrics {
+    let mut metrics = metrics.into_iter();
+    let mut merged = metrics.next().unwrap_or_default();
+    for other in metrics {
+        merged.merge(&other);
+    }
+    merged
+}
+
+/// Reads the JSON metrics written by an earlier load test with `--summary-format json`.
+pub(crate) fn read_metrics(path: &str) -> Result<SwanlingMetrics, SwanlingError> {
+    let invalid_metrics = |detail: String| SwanlingError::InvalidOption {
+        option: "--merge-reports".to_string(),
+        value: path.to_string(),
+        detail,
+    };
+    let json = fs::read_to_string(path)
+        .map_err(|e| invalid_metrics(format!("Failed to read metrics file: {}", e)))?;
+    // The JSON metrics may be surrounded by other output displayed during the load test.
+    let start = json.find("{\"hash\"").unwrap_or(0);
+    match serde_json::Deserializer::from_str(&json[start..])
+        .into_iter()
+        .next()
+    {
+        Some(Ok(metrics)) => Ok(metrics),
+        Some(Err(e)) => Err(invalid_metrics(format!(
+            "Failed to parse JSON metrics: {}",
+            e
+        ))),
+        None => Err(invalid_metrics("No JSON metrics found.".to_string())),
+    }
+}
+
 /// The following templates are necessary to build an html-formatted summary report.
 #[derive(Debug)]
-pub struct SwanlingReportTemplates<'a> {
+pub(crate) struct SwanlingReportTemplates<'a> {
     pub raw_requests_template: &'a str,
+    pub hosts_template: &'a str,
+    pub redirects_template: &'a str,
     pub raw_responses_template: &'a str,
     pub co_requests_template: &'a str,
     pub co_responses_template: &'a str,
+    pub percentile_headers: &'a str,
+    pub histograms_template: &'a str,
+    pub run_id_template: &'a str,
+    pub labels_template: &'a str,
+    pub aborted_template: &'a str,
+    pub annotations_template: &'a str,
+    pub generator_template: &'a str,
     pub tasks_template: &'a str,
     pub status_codes_template: &'a str,
     pub errors_template: &'a str,
@@ -21,7 +95,7 @@ pub struct SwanlingReportTemplates<'a> {
 
 /// Defines the metrics reported about requests.
 #[derive(Debug, Clone, Serialize)]
-pub struct RequestMetric {
+pub(crate) struct RequestMetric {
     pub method: String,
     pub name: String,
     pub number_of_requests: usize,
@@ -33,9 +107,31 @@ pub struct RequestMetric {
     pub failures_per_second: String,
 }
 
+/// Defines the metrics reported about the requests made to each host.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct HostMetric {
+    pub host: String,
+    pub number_of_requests: usize,
+    pub number_of_failures: usize,
+    pub response_time_average: usize,
+    pub requests_per_second: String,
+    pub failures_per_second: String,
+}
+
+/// Defines the metrics reported about the redirects followed by requests.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct RedirectMetric {
+    pub method: String,
+    pub name: String,
+    pub redirected_count: usize,
+    pub average_chain: String,
+    pub max_chain: usize,
+    pub redirect_time_average: usize,
+}
+
 /// Defines the metrics reported about Coordinated Omission requests.
 #[derive(Debug, Clone, Serialize)]
-pub struct CORequestMetric {
+pub(crate) struct CORequestMetric {
     pub method: String,
     pub name: String,
     pub response_time_average: String,
@@ -45,22 +141,23 @@ pub struct CORequestMetric {
 
 /// Defines the metrics reported about responses.
 #[derive(Debug, Clone, Serialize)]
-pub struct ResponseMetric {
+pub(crate) struct ResponseMetric {
+    pub method: String,
+    pub name: String,
+    pub percentiles: Vec<String>,
+}
+
+/// Defines the latency histogram reported about responses.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct HistogramMetric {
     pub method: String,
     pub name: String,
-    pub percentile_50: String,
-    pub percentile_60: String,
-    pub percentile_70: String,
-    pub percentile_80: String,
-    pub percentile_90: String,
-    pub percentile_95: String,
-    pub percentile_99: String,
-    pub percentile_100: String,
+    pub counts: Vec<usize>,
 }
 
 /// Defines the metrics reported about tasks.
 #[derive(Debug, Clone, Serialize)]
-pub struct TaskMetric {
+pub(crate) struct TaskMetric {
     pub is_task_set: bool,
     pub task: String,
     pub name: String,
@@ -74,50 +171,85 @@ pub struct TaskMetric {
 }
 
 /// Defines the metrics reported about status codes.
-pub struct StatusCodeMetric {
+pub(crate) struct StatusCodeMetric {
     pub method: String,
     pub name: String,
     pub status_codes: String,
 }
 
 /// Helper to generate a single response metric.
-pub fn get_response_metric(
+pub(crate) fn get_response_metric(
     method: &str,
     name: &str,
     response_times: &BTreeMap<usize, usize>,
     total_request_count: usize,
     response_time_minimum: usize,
     response_time_maximum: usize,
+    percentiles: &[f64],
 ) -> ResponseMetric {
-    // Calculate percentiles in a loop.
-    let mut percentiles = Vec::new();
-    for percent in &[0.5, 0.6, 0.7, 0.8, 0.9, 0.95, 0.99, 1.0] {
-        percentiles.push(metrics::calculate_response_time_percentile(
-            response_times,
-            total_request_count,
-            response_time_minimum,
-            response_time_maximum,
-            *percent,
-        ));
+    ResponseMetric {
+        method: method.to_string(),
+        name: name.to_string(),
+        percentiles: percentiles
+            .iter()
+            .map(|percentile| {
+                metrics::calculate_response_time_percentile(
+                    response_times,
+                    total_request_count,
+                    response_time_minimum,
+                    response_time_maximum,
+                    (percentile / 100.0) as f32,
+                )
+            })
+            .collect(),
     }
+}
 
-    // Now take the Strings out of the Vector and build a ResponseMetric object.
-    ResponseMetric {
+/// Helper to generate a single latency histogram, counting the responses in each bucket.
+pub(crate) fn get_histogram_metric(
+    method: &str,
+    name: &str,
+    response_times: &BTreeMap<usize, usize>,
+    buckets: &[usize],
+) -> HistogramMetric {
+    // Responses slower than the last boundary are counted in an additional bucket.
+    let mut counts = vec![0; buckets.len() + 1];
+    for (response_time, count) in response_times {
+        let bucket = buckets
+            .iter()
+            .position(|boundary| response_time <= boundary)
+            .unwrap_or(buckets.len());
+        counts[bucket] += count;
+    }
+
+    HistogramMetric {
         method: method.to_string(),
         name: name.to_string(),
-        percentile_50: mem::take(&mut percentiles[0]),
-        percentile_60: mem::take(&mut percentiles[1]),
-        percentile_70: mem::take(&mut percentiles[2]),
-        percentile_80: mem::take(&mut percentiles[3]),
-        percentile_90: mem::take(&mut percentiles[4]),
-        percentile_95: mem::take(&mut percentiles[5]),
-        percentile_99: mem::take(&mut percentiles[6]),
-        percentile_100: mem::take(&mut percentiles[7]),
+        counts,
     }
 }
 
+/// Build the headers of the response metrics tables in the html report, one per percentile.
+pub(crate) fn percentile_headers(percentiles: &[f64]) -> String {
+    percentiles
+        .iter()
+        .map(|percentile| format!("<th>{}%ile (ms)</th>", percentile))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Build the cells of an individual row of response metrics in the html report.
+fn percentile_cells(metric: &ResponseMetric) -> String {
+    metric
+        .percentiles
+        .iter()
+        .map(|percentile| format!("<td>{}</td>", percentile))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
 /// Build an individual row of raw request metrics in the html report.
-pub fn raw_request_metrics_row(metric: RequestMetric) -> String {
+pub(crate) fn raw_request_metrics_row(metric: RequestMetric) -> String {
     format!(
         r#"<tr>
         <td>{method}</td>
@@ -143,36 +275,114 @@ pub fn raw_request_metrics_row(metric: RequestMetric) -> String {
 }
 
 /// Build an individual row of response metrics in the html report.
-pub fn response_metrics_row(metric: ResponseMetric) -> String {
+pub(crate) fn response_metrics_row(metric: ResponseMetric) -> String {
     format!(
         r#"<tr>
             <td>{method}</td>
             <td>{name}</td>
-            <td>{percentile_50}</td>
-            <td>{percentile_60}</td>
-            <td>{percentile_70}</td>
-            <td>{percentile_80}</td>
-            <td>{percentile_90}</td>
-            <td>{percentile_95}</td>
-            <td>{percentile_99}</td>
-            <td>{percentile_100}</td>
+            {percentiles}
         </tr>"#,
         method = metric.method,
         name = metric.name,
-        percentile_50 = metric.percentile_50,
-        percentile_60 = metric.percentile_60,
-        percentile_70 = metric.percentile_70,
-        percentile_80 = metric.percentile_80,
-        percentile_90 = metric.percentile_90,
-        percentile_95 = metric.percentile_95,
-        percentile_99 = metric.percentile_99,
-        percentile_100 = metric.percentile_100,
+        percentiles = percentile_cells(&metric),
+    )
+}
+
+/// If requests were made to more than one host, add a table of requests per host to the
+/// html report.
+pub(crate) fn host_metrics_template(host_rows: &str) -> String {
+    format!(
+        r#"<div class="hosts">
+        <h2>Host Metrics</h2>
+        <table>
+            <thead>
+                <tr>
+                    <th>Host</th>
+                    <th># Requests</th>
+                    <th># Fails</th>
+                    <th>Average (ms)</th>
+                    <th>RPS</th>
+                    <th>Failures/s</th>
+                </tr>
+            </thead>
+            <tbody>
+                {host_rows}
+            </tbody>
+        </table>
+    </div>"#,
+        host_rows = host_rows,
+    )
+}
+
+/// Build an individual row of host metrics in the html report.
+pub(crate) fn host_metrics_row(metric: HostMetric) -> String {
+    format!(
+        r#"<tr>
+        <td>{host}</td>
+        <td>{number_of_requests}</td>
+        <td>{number_of_failures}</td>
+        <td>{response_time_average}</td>
+        <td>{requests_per_second}</td>
+        <td>{failures_per_second}</td>
+    </tr>"#,
+        host = metric.host,
+        number_of_requests = metric.number_of_requests,
+        number_of_failures = metric.number_of_failures,
+        response_time_average = metric.response_time_average,
+        requests_per_second = metric.requests_per_second,
+        failures_per_second = metric.failures_per_second,
+    )
+}
+
+/// If any requests were redirected, add a table of the redirects followed to the html
+/// report.
+pub(crate) fn redirect_metrics_template(redirect_rows: &str) -> String {
+    format!(
+        r#"<div class="redirects">
+        <h2>Redirect Metrics</h2>
+        <table>
+            <thead>
+                <tr>
+                    <th>Method</th>
+                    <th>Name</th>
+                    <th># Redirected</th>
+                    <th>Average chain</th>
+                    <th>Max chain</th>
+                    <th>Average (ms)</th>
+                </tr>
+            </thead>
+            <tbody>
+                {redirect_rows}
+            </tbody>
+        </table>
+    </div>"#,
+        redirect_rows = redirect_rows,
+    )
+}
+
+/// Build an individual row of redirect metrics in the html report.
+pub(crate) fn redirect_metrics_row(metric: RedirectMetric) -> String {
+    format!(
+        r#"<tr>
+        <td>{method}</td>
+        <td>{name}</td>
+        <td>{redirected_count}</td>
+        <td>{average_chain}</td>
+        <td>{max_chain}</td>
+        <td>{redirect_time_average}</td>
+    </tr>"#,
+        method = metric.method,
+        name = metric.name,
+        redirected_count = metric.redirected_count,
+        average_chain = metric.average_chain,
+        max_chain = metric.max_chain,
+        redirect_time_average = metric.redirect_time_average,
     )
 }
 
 /// If Coordinated Omission Mitigation is triggered, add a relevant request table to the
 /// html report.
-pub fn coordinated_omission_request_metrics_template(co_requests_rows: &str) -> String {
+pub(crate) fn coordinated_omission_request_metrics_template(co_requests_rows: &str) -> String {
     format!(
         r#"<div class="CO requests">
         <h2>Request Metrics With Coordinated Omission Mitigation</h2>
@@ -197,7 +407,7 @@ pub fn coordinated_omission_request_metrics_template(co_requests_rows: &str) ->
 
 /// Build an individual row of Coordinated Omission Mitigation request metrics in
 /// the html report.
-pub fn coordinated_omission_request_metrics_row(metric: CORequestMetric) -> String {
+pub(crate) fn coordinated_omission_request_metrics_row(metric: CORequestMetric) -> String {
     format!(
         r#"<tr>
             <td>{method}</td>
@@ -216,7 +426,10 @@ pub fn coordinated_omission_request_metrics_row(metric: CORequestMetric) -> Stri
 
 /// If Coordinated Omission Mitigation is triggered, add a relevant response table to the
 /// html report.
-pub fn coordinated_omission_response_metrics_template(co_responses_rows: &str) -> String {
+pub(crate) fn coordinated_omission_response_metrics_template(
+    co_responses_rows: &str,
+    percentile_headers: &str,
+) -> String {
     format!(
         r#"<div class="responses">
         <h2>Response Time Metrics With Coordinated Omission Mitigation</h2>
@@ -225,14 +438,7 @@ pub fn coordinated_omission_response_metrics_template(co_responses_rows: &str) -
                 <tr>
                     <th>Method</th>
                     <th>Name</th>
-                    <th>50%ile (ms)</th>
-                    <th>60%ile (ms)</th>
-                    <th>70%ile (ms)</th>
-                    <th>80%ile (ms)</th>
-                    <th>90%ile (ms)</th>
-                    <th>95%ile (ms)</th>
-                    <th>99%ile (ms)</th>
-                    <th>100%ile (ms)</th>
+                    {percentile_headers}
                 </tr>
             </thead>
             <tbody>
@@ -241,41 +447,232 @@ pub fn coordinated_omission_response_metrics_template(co_responses_rows: &str) -
         </table>
     </div>"#,
         co_responses_rows = co_responses_rows,
+        percentile_headers = percentile_headers,
     )
 }
 
 /// Build an individual row of Coordinated Omission Mitigation request metrics in
 /// the html report.
-pub fn coordinated_omission_response_metrics_row(metric: ResponseMetric) -> String {
+pub(crate) fn coordinated_omission_response_metrics_row(metric: ResponseMetric) -> String {
     format!(
         r#"<tr>
             <td>{method}</td>
             <td>{name}</td>
-            <td>{percentile_50}</td>
-            <td>{percentile_60}</td>
-            <td>{percentile_70}</td>
-            <td>{percentile_80}</td>
-            <td>{percentile_90}</td>
-            <td>{percentile_95}</td>
-            <td>{percentile_99}</td>
-            <td>{percentile_100}</td>
+            {percentiles}
         </tr>"#,
         method = metric.method,
         name = metric.name,
-        percentile_50 = metric.percentile_50,
-        percentile_60 = metric.percentile_60,
-        percentile_70 = metric.percentile_70,
-        percentile_80 = metric.percentile_80,
-        percentile_90 = metric.percentile_90,
-        percentile_95 = metric.percentile_95,
-        percentile_99 = metric.percentile_99,
-        percentile_100 = metric.percentile_100,
+        percentiles = percentile_cells(&metric),
+    )
+}
+
+/// Add a latency histogram for each request to the html report.
+pub(crate) fn histogram_metrics_template(histogram_rows: &str) -> String {
+    format!(
+        r#"<div class="histograms">
+        <h2>Response Time Histograms</h2>
+        {histogram_rows}
+    </div>"#,
+        histogram_rows = histogram_rows,
+    )
+}
+
+/// Build an individual latency histogram in the html report, with a bar for each bucket
+/// sized relative to the largest bucket.
+pub(crate) fn histogram_metrics_row(metric: HistogramMetric, buckets: &[usize]) -> String {
+    let largest = metric.counts.iter().copied().max().unwrap_or(0).max(1);
+    let bars: Vec<String> = metric
+        .counts
+        .iter()
+        .enumerate()
+        .map(|(bucket, count)| {
+            format!(
+                r#"<tr>
+                <td>{label}</td>
+                <td class="bar"><div style="width: {width:.1}%"></div></td>
+                <td>{count}</td>
+            </tr>"#,
+                label = histogram_bucket_label(buckets, bucket),
+                width = *count as f32 * 100.0 / largest as f32,
+                count = metrics::format_number(*count),
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<div class="histogram">
+        <h3>{title}</h3>
+        <table>
+            {bars}
+        </table>
+    </div>"#,
+        title = format!("{} {}", metric.method, metric.name).trim(),
+        bars = bars.join("\n"),
+    )
+}
+
+/// Describe the range of response times counted in a latency histogram bucket.
+fn histogram_bucket_label(buckets: &[usize], bucket: usize) -> String {
+    if bucket == 0 {
+        format!("0-{} ms", buckets[0])
+    } else if bucket == buckets.len() {
+        format!("&gt; {} ms", buckets[bucket - 1])
+    } else {
+        format!("{}-{} ms", buckets[bucket - 1] + 1, buckets[bucket])
+    }
+}
+
+/// Build the ID identifying the run in the header of the html report, if known.
+pub(crate) fn run_id_template(run_id: &str) -> String {
+    if run_id.is_empty() {
+        "".to_string()
+    } else {
+        format!(
+            "<p>Run ID: <span>{run_id}</span></p>",
+            run_id = escape_html(run_id),
+        )
+    }
+}
+
+/// Build the metadata configured with `--label` in the header of the html report.
+pub(crate) fn labels_template(labels: &BTreeMap<String, String>) -> String {
+    labels
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "<p>{key}: <span>{value}</span></p>",
+                key = escape_html(key),
+                value = escape_html(value),
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// If the load test was stopped by `--abort-on-error-rate`, or its metrics are partial, flag
+/// it in the header of the html report.
+pub(crate) fn aborted_template(aborted: Option<&String>) -> String {
+    match aborted {
+        Some(aborted) => format!(
+            r#"<p class="aborted">Aborted: <span>{aborted}</span></p>"#,
+            aborted = escape_html(aborted),
+        ),
+        None => "".to_string(),
+    }
+}
+
+/// If the Controller added annotations, add an annotations table to the html report.
+pub(crate) fn annotations_template(annotation_rows: &str) -> String {
+    format!(
+        r#"<div class="annotations">
+        <h2>Annotations</h2>
+        <table>
+            <thead>
+                <tr>
+                    <th>Time</th>
+                    <th>Elapsed (s)</th>
+                    <th colspan="3">Annotation</th>
+                </tr>
+            </thead>
+            <tbody>
+                {annotation_rows}
+            </tbody>
+        </table>
+    </div>"#,
+        annotation_rows = annotation_rows,
+    )
+}
+
+/// Build an individual annotation row in the html report.
+pub(crate) fn annotation_row(annotation: &metrics::SwanlingAnnotation) -> String {
+    let time = match Local.timestamp_opt(annotation.timestamp, 0) {
+        LocalResult::Single(time) => time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        _ => annotation.timestamp.to_string(),
+    };
+    format!(
+        r#"<tr>
+        <td>{time}</td>
+        <td>{elapsed}</td>
+        <td colspan="3">{text}</td>
+    </tr>"#,
+        time = time,
+        elapsed = annotation.elapsed,
+        text = escape_html(&annotation.text),
+    )
+}
+
+/// If the resources used by the load generator were sampled, add a load generator table to
+/// the html report.
+pub(crate) fn generator_template(generator: &metrics::SwanlingGeneratorMetrics) -> String {
+    if generator.samples == 0 {
+        return "".to_string();
+    }
+
+    let rows = [
+        ("CPU (%)", &generator.cpu, 1),
+        ("Memory (MB)", &generator.memory, 1),
+        ("Sockets", &generator.sockets, 0),
+        ("Tasks", &generator.tasks, 0),
+    ]
+    .iter()
+    .map(|(name, resource, precision)| {
+        format!(
+            r#"<tr>
+        <td>{name}</td>
+        <td>{average:.precision$}</td>
+        <td>{maximum:.precision$}</td>
+    </tr>"#,
+            name = name,
+            average = resource.average(generator.samples),
+            maximum = resource.maximum,
+            precision = precision,
+        )
+    })
+    .collect::<Vec<String>>()
+    .join("\n");
+
+    let warning = if generator.overloaded() {
+        format!(
+            "<p>Load generator CPU usage reached {:.1}%, the load generator rather than the target may have limited the load test.</p>",
+            generator.cpu.maximum
+        )
+    } else {
+        "".to_string()
+    };
+
+    format!(
+        r#"<div class="generator">
+        <h2>Load Generator</h2>
+        {warning}
+        <table>
+            <thead>
+                <tr>
+                    <th>Resource</th>
+                    <th>Average</th>
+                    <th>Maximum</th>
+                </tr>
+            </thead>
+            <tbody>
+                {rows}
+            </tbody>
+        </table>
+    </div>"#,
+        warning = warning,
+        rows = rows,
     )
 }
 
+/// Escape text provided by the user so it is displayed as is in the html report.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 /// If status code metrics are enabled, add a status code metrics table to the
 /// html report.
-pub fn status_code_metrics_template(status_code_rows: &str) -> String {
+pub(crate) fn status_code_metrics_template(status_code_rows: &str) -> String {
     format!(
         r#"<div class="status_codes">
         <h2>Status Code Metrics</h2>
@@ -297,7 +694,7 @@ pub fn status_code_metrics_template(status_code_rows: &str) -> String {
 }
 
 /// Build an individual row of status code metrics in the html report.
-pub fn status_code_metrics_row(metric: StatusCodeMetric) -> String {
+pub(crate) fn status_code_metrics_row(metric: StatusCodeMetric) -> String {
     format!(
         r#"<tr>
         <td>{method}</td>
@@ -311,7 +708,7 @@ pub fn status_code_metrics_row(metric: StatusCodeMetric) -> String {
 }
 
 /// If task metrics are enabled, add a task metrics table to the html report.
-pub fn task_metrics_template(task_rows: &str) -> String {
+pub(crate) fn task_metrics_template(task_rows: &str) -> String {
     format!(
         r#"<div class="tasks">
         <h2>Task Metrics</h2>
@@ -338,7 +735,7 @@ pub fn task_metrics_template(task_rows: &str) -> String {
 }
 
 /// Build an individual row of task metrics in the html report.
-pub fn task_metrics_row(metric: TaskMetric) -> String {
+pub(crate) fn task_metrics_row(metric: TaskMetric) -> String {
     if metric.is_task_set {
         format!(
             r#"<tr>
@@ -372,7 +769,7 @@ pub fn task_metrics_row(metric: TaskMetric) -> String {
 }
 
 /// If there are errors, add an errors table to the html report.
-pub fn errors_template(error_rows: &str) -> String {
+pub(crate) fn errors_template(error_rows: &str) -> String {
     format!(
         r#"<div class="errors">
         <h2>Errors</h2>
@@ -380,6 +777,7 @@ pub fn errors_template(error_rows: &str) -> String {
             <thead>
                 <tr>
                     <th>#</th>
+                    <th>Category</th>
                     <th colspan="3">Error</th>
                 </tr>
             </thead>
@@ -393,19 +791,21 @@ pub fn errors_template(error_rows: &str) -> String {
 }
 
 /// Build an individual error row in the html report.
-pub fn error_row(error: &metrics::SwanlingErrorMetricAggregate) -> String {
+pub(crate) fn error_row(error: &metrics::SwanlingErrorMetricAggregate) -> String {
     format!(
         r#"<tr>
         <td>{occurrences}</td>
+        <td>{category}</td>
         <td colspan="4">{error}</strong></td>
     </tr>"#,
         occurrences = error.occurrences,
+        category = error.category,
         error = error.error,
     )
 }
 
 /// Build the html report.
-pub fn build_report(
+pub(crate) fn build_report(
     start_time: &str,
     end_time: &str,
     host: &str,
@@ -431,6 +831,11 @@ pub fn build_report(
             color: #b3c3bc;
         }}
 
+        .info .aborted {{
+            color: #ff4c4c;
+            font-weight: bold;
+        }}
+
         table {{
             border-collapse: collapse;
             text-align: center;
@@ -466,6 +871,16 @@ pub fn build_report(
             float: right;
         }}
 
+        .histogram td.bar {{
+            text-align: left;
+            width: 70%;
+        }}
+
+        .histogram td.bar div {{
+            background: #00ca5a;
+            height: 20px;
+        }}
+
         .download a {{
             color: #00ca5a;
         }}
@@ -478,6 +893,9 @@ pub fn build_report(
         <div class="info">
             <p>During: <span>{start_time} - {end_time}</span></p>
             <p>Target Host: <span>{host}</span></p>
+            {run_id_template}
+            {labels_template}
+            {aborted_template}
         </div>
 
         <div class="requests">
@@ -502,6 +920,8 @@ pub fn build_report(
             </table>
         </div>
 
+        {hosts_template}
+
         {co_requests_template}
 
         <div class="responses">
@@ -511,14 +931,7 @@ pub fn build_report(
                     <tr>
                         <th>Method</th>
                         <th>Name</th>
-                        <th>50%ile (ms)</th>
-                        <th>60%ile (ms)</th>
-                        <th>70%ile (ms)</th>
-                        <th>80%ile (ms)</th>
-                        <th>90%ile (ms)</th>
-                        <th>95%ile (ms)</th>
-                        <th>99%ile (ms)</th>
-                        <th>100%ile (ms)</th>
+                        {percentile_headers}
                     </tr>
                 </thead>
                 <tbody>
@@ -529,12 +942,20 @@ pub fn build_report(
 
         {co_responses_template}
 
+        {redirects_template}
+
+        {histograms_template}
+
+        {annotations_template}
+
         {status_codes_template}
 
         {tasks_template}
 
         {errors_template}
 
+        {generator_template}
+
     </div>
 </body>
 </html>"#,
@@ -542,9 +963,18 @@ pub fn build_report(
         end_time = end_time,
         host = host,
         raw_requests_template = templates.raw_requests_template,
+        hosts_template = templates.hosts_template,
+        redirects_template = templates.redirects_template,
         raw_responses_template = templates.raw_responses_template,
         co_requests_template = templates.co_requests_template,
         co_responses_template = templates.co_responses_template,
+        percentile_headers = templates.percentile_headers,
+        histograms_template = templates.histograms_template,
+        run_id_template = templates.run_id_template,
+        labels_template = templates.labels_template,
+        aborted_template = templates.aborted_template,
+        annotations_template = templates.annotations_template,
+        generator_template = templates.generator_template,
         tasks_template = templates.tasks_template,
         status_codes_template = templates.status_codes_template,
         errors_template = templates.errors_template,
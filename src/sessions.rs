@@ -0,0 +1,86 @@
+//! Optionally save the cookies of each user when the load test stops, and restore them
+//! when it starts.
+//!
+//! With `--save-sessions sessions.json`, the cookies set by the server for each
+//! [`SwanlingUser`](../swanling/struct.SwanlingUser.html) are written to the file once all
+//! users have stopped, and restored into the same users the next time the load test
+//! starts, whether it was stopped and started again by a Controller, or run again later.
+//! Long-lived sessions, for example users that logged in, therefore don't need to be
+//! established again every time the load test starts.
+//!
+//! Users are matched by the order they're launched in. Expired cookies are not saved, and
+//! the file is ignored if it doesn't exist yet. Only the cookies of the default client are
+//! saved, a client built with
+//! [`set_client_builder`](../swanling/struct.SwanlingUser.html#method.set_client_builder)
+//! stores its own cookies.
+
+use std::fs;
+use std::io::ErrorKind;
+
+use crate::swanling::SwanlingUser;
+use crate::SwanlingError;
+
+/// The cookies of each user, in the order users are launched.
+type SwanlingSessions = Vec<Vec<cookie_store::Cookie<'static>>>;
+
+// The error returned when the sessions file can't be used.
+fn invalid_sessions(path: &str, detail: String) -> SwanlingError {
+    SwanlingError::InvalidOption {
+        option: "--save-sessions".to_string(),
+        value: path.to_string(),
+        detail,
+    }
+}
+
+/// Read the cookies saved in `path`, or no cookies if the file doesn't exist yet.
+pub(crate) fn read(path: &str) -> Result<SwanlingSessions, SwanlingError> {
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(invalid_sessions(
+                path,
+                format!("Failed to read sessions file: {}", e),
+            ))
+        }
+    };
+    serde_json::from_str(&json)
+        .map_err(|e| invalid_sessions(path, format!("Failed to parse sessions file: {}", e)))
+}
+
+/// Restore the cookies saved in `path` into the cookie jar of each user, replacing the
+/// cookies it already has.
+pub(crate) fn load(path: &str, users: &[SwanlingUser]) -> Result<(), SwanlingError> {
+    let mut sessions = read(path)?.into_iter();
+    let mut restored = 0;
+    for user in users {
+        let cookies = sessions.next().unwrap_or_default();
+        if !cookies.is_empty() {
+            restored += 1;
+        }
+        let jar = cookie_store::CookieStore::from_cookies(
+            cookies.into_iter().map(Ok::<_, SwanlingError>),
+            false,
+        )?;
+        *user.jar.write().unwrap() = jar;
+    }
+    if restored > 0 {
+        info!("restored the sessions of {} users from {}", restored, path);
+    }
+
+    Ok(())
+}
+
+/// Save the unexpired cookies in the cookie jar of each user to `path`.
+pub(crate) fn save(path: &str, users: &[SwanlingUser]) -> Result<(), SwanlingError> {
+    let sessions: SwanlingSessions = users
+        .iter()
+        .map(|user| user.jar.read().unwrap().iter_unexpired().cloned().collect())
+        .collect();
+    let json = serde_json::to_string(&sessions)
+        .map_err(|e| invalid_sessions(path, format!("Failed to serialize sessions: {}", e)))?;
+    fs::write(path, json)?;
+    info!("saved the sessions of {} users to {}", users.len(), path);
+
+    Ok(())
+}
@@ -0,0 +1,278 @@
+//! A size-bounded in-memory cache shared by all users.
+//!
+//! Load tests often need data that's expensive to fetch but doesn't change while the load
+//! test runs, for example the list of product IDs returned by a setup endpoint. Instead of
+//! every [`SwanlingUser`](../swanling/struct.SwanlingUser.html) requesting it, users store
+//! it in the cache returned by
+//! [`SwanlingUser::shared_cache`](../swanling/struct.SwanlingUser.html#method.shared_cache),
+//! so it's fetched once and reused by all users.
+//!
+//! The cache holds values of any type, keyed by string and type. When it's full, the least
+//! recently used value is evicted to make room for a new one. It holds 1,000 values by
+//! default, which can be changed with
+//! [`SwanlingAttack::set_shared_cache_capacity`](../struct.SwanlingAttack.html#method.set_shared_cache_capacity).
+//! When running in a distributed Regatta, each Worker has its own cache.
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//!
+//! let mut task = task!(view_product);
+//!
+//! /// Fetch the product IDs once, then view the first product.
+//! async fn view_product(user: &SwanlingUser) -> SwanlingTaskResult {
+//!     let product_ids = user
+//!         .shared_cache()
+//!         .get_or_try_insert_with("product_ids", || async {
+//!             let swanling = user.get("/products").await?;
+//!             let product_ids: Vec<usize> = swanling.json_as(user).await?;
+//!             Ok::<_, SwanlingTaskError>(product_ids)
+//!         })
+//!         .await?;
+//!     if let Some(product_id) = product_ids.first() {
+//!         let _swanling = user.get(&format!("/product/{}", product_id)).await?;
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// How many values the shared cache holds by default.
+pub(crate) const DEFAULT_SHARED_CACHE_CAPACITY: usize = 1_000;
+
+type SwanlingCacheValue = Arc<dyn Any + Send + Sync>;
+
+/// Values are cached by key and type, so values of different types can't collide.
+type SwanlingCacheKey = (String, TypeId);
+
+/// A value in the cache, initialized at most once.
+struct SwanlingCacheEntry {
+    /// The value, set once it has been fetched.
+    value: Arc<OnceCell<SwanlingCacheValue>>,
+    /// When the value was last used, to evict the least recently used value.
+    used: u64,
+}
+
+/// The entries of the cache, protected by a mutex.
+struct SwanlingCacheEntries {
+    /// Cached values, keyed by name and type.
+    entries: HashMap<SwanlingCacheKey, SwanlingCacheEntry>,
+    /// Counts each time a value is used.
+    clock: u64,
+}
+impl SwanlingCacheEntries {
+    /// Returns the cell holding the value for `key`, adding an empty cell if needed and
+    /// evicting the least recently used value if the cache is full.
+    fn cell(
+        &mut self,
+        key: SwanlingCacheKey,
+        capacity: usize,
+    ) -> Arc<OnceCell<SwanlingCacheValue>> {
+        self.clock += 1;
+        let used = self.clock;
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.used = used;
+            return entry.value.clone();
+        }
+
+        if self.entries.len() >= capacity {
+            let least_recently_used = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.used)
+                .map(|(key, _)| key.clone());
+            if let Some(evicted) = least_recently_used {
+                self.entries.remove(&evicted);
+            }
+        }
+
+        let value = Arc::new(OnceCell::new());
+        self.entries.insert(
+            key,
+            SwanlingCacheEntry {
+                value: value.clone(),
+                used,
+            },
+        );
+        value
+    }
+}
+
+/// A size-bounded cache shared by all users, see the [module documentation](./index.html).
+///
+/// Cloning the cache returns another handle to the same cache.
+#[derive(Clone)]
+pub struct SwanlingSharedCache {
+    /// The maximum number of values held by the cache.
+    capacity: usize,
+    entries: Arc<Mutex<SwanlingCacheEntries>>,
+}
+impl SwanlingSharedCache {
+    /// Create an empty cache holding up to `capacity` values.
+    pub(crate) fn new(capacity: usize) -> Self {
+        SwanlingSharedCache {
+            capacity,
+            entries: Arc::new(Mutex::new(SwanlingCacheEntries {
+                entries: HashMap::new(),
+                clock: 0,
+            })),
+        }
+    }
+
+    /// Returns the value of type `T` cached for `key`, or `None` if no such value is cached.
+    pub fn get<T: Any + Send + Sync>(&self, key: &str) -> Option<Arc<T>> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.clock += 1;
+        let used = entries.clock;
+        let entry = entries
+            .entries
+            .get_mut(&(key.to_string(), TypeId::of::<T>()))?;
+        entry.used = used;
+        entry.value.get()?.clone().downcast::<T>().ok()
+    }
+
+    /// Caches `value` for `key`, replacing any value of the same type already cached, and
+    /// returns it.
+    pub fn insert<T: Any + Send + Sync>(&self, key: &str, value: T) -> Arc<T> {
+        let value = Arc::new(value);
+        let key = (key.to_string(), TypeId::of::<T>());
+        let mut entries = self.entries.lock().unwrap();
+        entries.entries.remove(&key);
+        let cell = entries.cell(key, self.capacity);
+        // The cell was just added, so it can't already be set.
+        let _ = cell.set(value.clone());
+        value
+    }
+
+    /// Returns the value cached for `key`, or fetches it with `fetch` and caches it.
+    ///
+    /// If several users request the same key at once, only one of them fetches the value
+    /// while the others wait for it. If `fetch` fails its error is returned and nothing is
+    /// cached, so the next user tries again.
+    pub async fn get_or_try_insert_with<T, E, F, Fut>(
+        &self,
+        key: &str,
+        fetch: F,
+    ) -> Result<Arc<T>, E>
+    where
+        T: Any + Send + Sync,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let cell = self
+            .entries
+            .lock()
+            .unwrap()
+            .cell((key.to_string(), TypeId::of::<T>()), self.capacity);
+        let value = cell
+            .get_or_try_init(|| async {
+                let value: SwanlingCacheValue = Arc::new(fetch().await?);
+                Ok(value)
+            })
+            .await?;
+        // Values are cached by type, so the value is always a `T`.
+        Ok(value
+            .clone()
+            .downcast::<T>()
+            .expect("cached value has the wrong type"))
+    }
+
+    /// Removes the values cached for `key`, returning whether any value was cached.
+    pub fn remove(&self, key: &str) -> bool {
+        let mut entries = self.entries.lock().unwrap();
+        let cached = entries.entries.len();
+        entries.entries.retain(|(name, _), _| name != key);
+        entries.entries.len() < cached
+    }
+
+    /// Removes all cached values.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().entries.clear();
+    }
+
+    /// Returns how many values are cached.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().entries.len()
+    }
+
+    /// Returns true if no values are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+impl Default for SwanlingSharedCache {
+    fn default() -> Self {
+        SwanlingSharedCache::new(DEFAULT_SHARED_CACHE_CAPACITY)
+    }
+}
+/// Cached values can be of any type, so only display how many are cached.
+impl fmt::Debug for SwanlingSharedCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SwanlingSharedCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = SwanlingSharedCache::new(2);
+        cache.insert("a", 1usize);
+        cache.insert("b", 2usize);
+        // Use "a", so "b" is the least recently used value.
+        assert_eq!(*cache.get::<usize>("a").unwrap(), 1);
+        cache.insert("c", 3usize);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get::<usize>("b").is_none());
+        assert_eq!(*cache.get::<usize>("a").unwrap(), 1);
+        assert_eq!(*cache.get::<usize>("c").unwrap(), 3);
+    }
+
+    #[test]
+    fn keyed_by_type() {
+        let cache = SwanlingSharedCache::default();
+        cache.insert("ids", vec![1usize, 2, 3]);
+        cache.insert("ids", "1,2,3".to_string());
+        assert_eq!(*cache.get::<Vec<usize>>("ids").unwrap(), vec![1, 2, 3]);
+        assert_eq!(*cache.get::<String>("ids").unwrap(), "1,2,3");
+        assert!(cache.get::<usize>("ids").is_none());
+        assert!(cache.remove("ids"));
+        assert!(cache.is_empty());
+        assert!(!cache.remove("ids"));
+    }
+
+    #[tokio::test]
+    async fn fetches_once() {
+        let cache = SwanlingSharedCache::default();
+        let fetched = Arc::new(Mutex::new(0));
+
+        // A failed fetch isn't cached.
+        let failed: Result<Arc<usize>, &str> = cache
+            .get_or_try_insert_with("answer", || async { Err("unavailable") })
+            .await;
+        assert!(failed.is_err());
+
+        for _ in 0..3 {
+            let fetched = fetched.clone();
+            let answer: Result<Arc<usize>, &str> = cache
+                .get_or_try_insert_with("answer", || async move {
+                    *fetched.lock().unwrap() += 1;
+                    Ok(42)
+                })
+                .await;
+            assert_eq!(*answer.unwrap(), 42);
+        }
+        assert_eq!(*fetched.lock().unwrap(), 1);
+    }
+}
@@ -0,0 +1,258 @@
+//! A pooled SQL client shared by all users, to seed data and check the state of the target.
+//!
+//! Load tests often need to prepare the database behind the target before starting, and to
+//! confirm afterward that it ended up in the expected state, for example that every order
+//! placed was stored, or that a queue table drained. A
+//! [`SwanlingSqlPool`](./struct.SwanlingSqlPool.html) registered with
+//! [`SwanlingAttack::set_sql_pool`](../struct.SwanlingAttack.html#method.set_sql_pool) is
+//! available to the `test_start` and `test_stop` tasks and to all users through
+//! [`SwanlingUser::sql`](../swanling/struct.SwanlingUser.html#method.sql).
+//!
+//! Swanling doesn't bundle a database driver. Instead the load test implements
+//! [`SwanlingSqlConnector`](./trait.SwanlingSqlConnector.html) and
+//! [`SwanlingSqlConnection`](./trait.SwanlingSqlConnection.html) with the Postgres or MySQL
+//! driver of its choice, and the pool limits how many connections are open at once and reuses
+//! them.
+//!
+//! Each query is named, and how many times it succeeded and failed and how long it took is
+//! recorded in [`SwanlingMetrics::queries`](../metrics/struct.SwanlingMetrics.html#structfield.queries)
+//! and displayed with the other metrics. When running in a distributed Regatta, each Worker
+//! has its own pool and its queries aren't included in the Manager's metrics.
+//!
+//! This module is only available with the `sql` feature.
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//! use swanling::sql::{SwanlingSqlConnection, SwanlingSqlConnector, SwanlingSqlError, SwanlingSqlRow};
+//!
+//! /// Connects to the database with the driver of your choice.
+//! struct Connector;
+//! #[async_trait::async_trait]
+//! impl SwanlingSqlConnector for Connector {
+//!     async fn connect(&self) -> Result<Box<dyn SwanlingSqlConnection>, SwanlingSqlError> {
+//!         Ok(Box::new(Connection))
+//!     }
+//! }
+//!
+//! /// A connection opened by the driver.
+//! struct Connection;
+//! #[async_trait::async_trait]
+//! impl SwanlingSqlConnection for Connection {
+//!     async fn execute(&mut self, _statement: &str) -> Result<u64, SwanlingSqlError> {
+//!         Ok(1)
+//!     }
+//!     async fn query(&mut self, _query: &str) -> Result<Vec<SwanlingSqlRow>, SwanlingSqlError> {
+//!         Ok(vec![vec![Some("0".to_string())]])
+//!     }
+//! }
+//!
+//! /// Confirm the queue drained once the load test stopped.
+//! async fn check_queue(user: &SwanlingUser) -> SwanlingTaskResult {
+//!     let depth: Option<u64> = user
+//!         .sql()?
+//!         .query_scalar("queue depth", "SELECT COUNT(*) FROM jobs")
+//!         .await?;
+//!     if depth != Some(0) {
+//!         return Err(SwanlingTaskError::SqlFailed {
+//!             name: "queue depth".to_string(),
+//!             detail: format!("{:?} jobs left in the queue", depth),
+//!         });
+//!     }
+//!
+//!     Ok(())
+//! }
+//!
+//! fn main() -> Result<(), SwanlingError> {
+//!     SwanlingAttack::initialize()?
+//!         .set_sql_pool(SwanlingSqlPool::new(Connector, 4))
+//!         .test_stop(task!(check_queue));
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use async_trait::async_trait;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::metrics::SwanlingQueryMetrics;
+use crate::swanling::SwanlingTaskError;
+
+/// An error returned by the database driver.
+pub type SwanlingSqlError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A row returned by a query, with each column converted to text, or `None` if it's `NULL`.
+pub type SwanlingSqlRow = Vec<Option<String>>;
+
+/// A connection to the database, implemented with the driver of your choice.
+#[async_trait]
+pub trait SwanlingSqlConnection: Send {
+    /// Run a statement that doesn't return rows, returning how many rows it changed.
+    async fn execute(&mut self, statement: &str) -> Result<u64, SwanlingSqlError>;
+
+    /// Run a query, returning the rows it selected.
+    async fn query(&mut self, query: &str) -> Result<Vec<SwanlingSqlRow>, SwanlingSqlError>;
+}
+
+/// Opens connections to the database when the pool needs them.
+#[async_trait]
+pub trait SwanlingSqlConnector: Send + Sync {
+    /// Open a new connection to the database.
+    async fn connect(&self) -> Result<Box<dyn SwanlingSqlConnection>, SwanlingSqlError>;
+}
+
+/// A pool of connections to the database, see the [module documentation](./index.html).
+///
+/// Cloning the pool returns another handle to the same pool.
+#[derive(Clone)]
+pub struct SwanlingSqlPool {
+    /// Opens new connections.
+    connector: Arc<dyn SwanlingSqlConnector>,
+    /// Connections that aren't currently in use.
+    idle: Arc<Mutex<Vec<Box<dyn SwanlingSqlConnection>>>>,
+    /// Limits how many connections are in use at once.
+    permits: Arc<Semaphore>,
+    /// Counts and timings of each query, by name.
+    metrics: Arc<Mutex<SwanlingQueryMetrics>>,
+}
+impl SwanlingSqlPool {
+    /// Create a pool opening connections with `connector`, with up to `max_connections`
+    /// connections in use at once. Users running a query while all connections are in use
+    /// wait for one to be returned. The pool always allows at least one connection.
+    pub fn new<C: SwanlingSqlConnector + 'static>(connector: C, max_connections: usize) -> Self {
+        SwanlingSqlPool {
+            connector: Arc::new(connector),
+            idle: Arc::new(Mutex::new(Vec::new())),
+            permits: Arc::new(Semaphore::new(max_connections.max(1))),
+            metrics: Arc::new(Mutex::new(SwanlingQueryMetrics::new())),
+        }
+    }
+
+    /// Run a statement that doesn't return rows, such as an `INSERT`, returning how many
+    /// rows it changed. The statement is recorded in the metrics as `name`.
+    pub async fn execute(&self, name: &str, statement: &str) -> Result<u64, SwanlingTaskError> {
+        let started = Instant::now();
+        let result = match self.checkout().await {
+            Ok((_permit, mut connection)) => {
+                let result = connection.execute(statement).await;
+                self.checkin(connection, result.is_ok());
+                result
+            }
+            Err(error) => Err(error),
+        };
+        self.record(name, started, result)
+    }
+
+    /// Run a query, returning the rows it selected. The query is recorded in the metrics as
+    /// `name`.
+    pub async fn query(
+        &self,
+        name: &str,
+        query: &str,
+    ) -> Result<Vec<SwanlingSqlRow>, SwanlingTaskError> {
+        let started = Instant::now();
+        let result = match self.checkout().await {
+            Ok((_permit, mut connection)) => {
+                let result = connection.query(query).await;
+                self.checkin(connection, result.is_ok());
+                result
+            }
+            Err(error) => Err(error),
+        };
+        self.record(name, started, result)
+    }
+
+    /// Run a query selecting a single value, such as a row count, and parse it as `T`.
+    /// Returns `None` if the query selected no rows or the value is `NULL`. The query is
+    /// recorded in the metrics as `name`.
+    pub async fn query_scalar<T>(
+        &self,
+        name: &str,
+        query: &str,
+    ) -> Result<Option<T>, SwanlingTaskError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let rows = self.query(name, query).await?;
+        match rows
+            .into_iter()
+            .next()
+            .and_then(|row| row.into_iter().next())
+        {
+            Some(Some(value)) => {
+                value
+                    .parse()
+                    .map(Some)
+                    .map_err(|error: T::Err| SwanlingTaskError::SqlFailed {
+                        name: name.to_string(),
+                        detail: format!("failed to parse {:?}: {}", value, error),
+                    })
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the counts and timings of each query run so far, by name.
+    pub fn metrics(&self) -> SwanlingQueryMetrics {
+        self.metrics.lock().unwrap().clone()
+    }
+
+    /// Forget the queries run so far, before starting a new load test.
+    pub(crate) fn reset_metrics(&self) {
+        self.metrics.lock().unwrap().clear();
+    }
+
+    /// Wait until fewer than the maximum number of connections are in use, then return an
+    /// idle connection, or open a new one if none are idle.
+    async fn checkout(
+        &self,
+    ) -> Result<(OwnedSemaphorePermit, Box<dyn SwanlingSqlConnection>), SwanlingSqlError> {
+        // The semaphore is never closed.
+        let permit = self.permits.clone().acquire_owned().await?;
+        let idle = self.idle.lock().unwrap().pop();
+        let connection = match idle {
+            Some(connection) => connection,
+            None => self.connector.connect().await?,
+        };
+        Ok((permit, connection))
+    }
+
+    /// Return a connection to the pool to be reused. A connection that returned an error is
+    /// dropped instead, in case it's broken, and a new one is opened when needed.
+    fn checkin(&self, connection: Box<dyn SwanlingSqlConnection>, healthy: bool) {
+        if healthy {
+            self.idle.lock().unwrap().push(connection);
+        }
+    }
+
+    /// Record how long a query took and whether it succeeded.
+    fn record<T>(
+        &self,
+        name: &str,
+        started: Instant,
+        result: Result<T, SwanlingSqlError>,
+    ) -> Result<T, SwanlingTaskError> {
+        let time = started.elapsed().as_millis() as u64;
+        self.metrics
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_default()
+            .record(time, result.is_ok());
+        result.map_err(|error| SwanlingTaskError::SqlFailed {
+            name: name.to_string(),
+            detail: error.to_string(),
+        })
+    }
+}
+impl fmt::Debug for SwanlingSqlPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SwanlingSqlPool")
+            .field("available", &self.permits.available_permits())
+            .finish()
+    }
+}
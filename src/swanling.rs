@@ -66,6 +66,21 @@
 //! let mut foo_tasks = taskset!("FooTasks").set_wait_time(0, 3).unwrap();
 //! let mut bar_tasks = taskset!("BarTasks").set_wait_time(5, 10).unwrap();
 //! ```
+//!
+//! ### Task Set Client Mode
+//!
+//! By default each [`SwanlingUser`](./struct.SwanlingUser.html) builds its own client
+//! when it starts, and reuses it (along with its connections and cookies) for as long as
+//! it runs. The client mode can be changed to rebuild the client every time the user
+//! loops through all tasks in the task set, simulating first-visit users, or to share a
+//! single client between all users running the task set. In the following example, users
+//! loading `foo` tasks always start with a new client and no cookies:
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//!
+//! let mut foo_tasks = taskset!("FooTasks").set_client_mode(SwanlingClientMode::PerIteration);
+//! ```
 //! ## Creating Tasks
 //!
 //! A [`SwanlingTask`](./struct.SwanlingTask.html) must include a pointer to a function which
@@ -225,7 +240,9 @@
 //!
 //! The most common request types are [`GET`](./struct.SwanlingUser.html#method.get) and
 //! [`POST`](./struct.SwanlingUser.html#method.post), but [`HEAD`](./struct.SwanlingUser.html#method.head),
-//! PUT, PATCH and [`DELETE`](./struct.SwanlingUser.html#method.delete) are also supported.
+//! [`PUT`](./struct.SwanlingUser.html#method.put), [`PATCH`](./struct.SwanlingUser.html#method.patch),
+//! [`DELETE`](./struct.SwanlingUser.html#method.delete) and
+//! [`OPTIONS`](./struct.SwanlingUser.html#method.options) are also supported.
 //!
 //! ### GET
 //!
@@ -268,6 +285,50 @@
 //! }
 //! ```
 //!
+//! ### JSON
+//!
+//! Helpers to [`POST`](./struct.SwanlingUser.html#method.post_json) or
+//! [`PUT`](./struct.SwanlingUser.html#method.put_json) any value that implements
+//! [`serde::Serialize`](https://docs.rs/serde/*/serde/trait.Serialize.html) as JSON, setting the
+//! `Content-Type: application/json` header.
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//! use serde_json::json;
+//!
+//! let mut task = task!(post_json_function);
+//!
+//! /// A very simple task that makes a POST request of a JSON value.
+//! async fn post_json_function(user: &SwanlingUser) -> SwanlingTaskResult {
+//!     let _swanling = user.post_json("/path/to/foo/", &json!({"name": "foo"})).await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ### User Identity
+//!
+//! Each [`SwanlingUser`](./struct.SwanlingUser.html) has a stable
+//! [`user_id`](./struct.SwanlingUser.html#method.user_id) and a random
+//! [`uuid`](./struct.SwanlingUser.html#method.uuid), so each user can work with its own data
+//! on the server being load tested. The `{user_id}` and `{uuid}` placeholders are replaced
+//! with them in all paths, and in any other text with
+//! [`render_template`](./struct.SwanlingUser.html#method.render_template).
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//!
+//! let mut task = task!(cart_function);
+//!
+//! /// Add an item to the cart of this user.
+//! async fn cart_function(user: &SwanlingUser) -> SwanlingTaskResult {
+//!     let body = user.render_template(r#"{"owner": "{uuid}", "item": 42}"#);
+//!     let _swanling = user.post_named("/api/users/{uuid}/cart", "cart", &body).await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
 //! ## License
 //!
 //! Copyright 2020 Jeremy Andrews
@@ -284,26 +345,421 @@
 //! See the License for the specific language governing permissions and
 //! limitations under the License.
 
+use flate2::read::{DeflateDecoder, MultiGzDecoder, ZlibDecoder};
 use http::method::Method;
-use reqwest::{header, Client, ClientBuilder, RequestBuilder, Response};
-use serde::{Deserialize, Serialize};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use reqwest::cookie::CookieStore;
+use reqwest::header::HeaderValue;
+use reqwest::redirect::Policy;
+use reqwest::{
+    header, Client, ClientBuilder, Request, RequestBuilder, Response, ResponseBuilderExt,
+    StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::{future::Future, pin::Pin, time::Instant};
 use tokio::sync::{Mutex, RwLock};
 use url::Url;
 
+use crate::coordination::SwanlingCoordination;
+use crate::dns::SwanlingResolver;
+use crate::feeder::{SwanlingFeeder, SwanlingFeeders};
 use crate::logger::SwanlingLog;
 use crate::metrics::{
-    SwanlingCoordinatedOmissionMitigation, SwanlingMetric, SwanlingRequestMetric,
+    request_host, SwanlingCoordinatedOmissionMitigation, SwanlingMetric, SwanlingMetricsBatch,
+    SwanlingRawLatency, SwanlingRequestMetric,
 };
+#[cfg(feature = "queue")]
+use crate::queue::SwanlingQueue;
+use crate::shared_cache::SwanlingSharedCache;
+#[cfg(feature = "sql")]
+use crate::sql::SwanlingSqlPool;
+use crate::template::SwanlingTemplateContext;
 use crate::{SwanlingConfiguration, SwanlingError, WeightedSwanlingTasks};
 
 /// By default Swanling sets the following User-Agent header when making requests.
 static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
+/// The maximum number of redirects followed by the default client, the same limit as the
+/// default [`reqwest`](https://docs.rs/reqwest/) redirect policy.
+const MAX_REDIRECTS: usize = 10;
+
+/// The error recorded for requests aborted with `--inject-abort`.
+const INJECT_ABORT_ERROR: &str = "request aborted by --inject-abort";
+
+/// The validators of a cached response, sent to revalidate it when the same URL is
+/// requested again.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SwanlingCacheValidators {
+    /// The `ETag` of the cached response, sent as `If-None-Match`.
+    etag: Option<HeaderValue>,
+    /// The `Last-Modified` date of the cached response, sent as `If-Modified-Since`.
+    last_modified: Option<HeaderValue>,
+}
+impl SwanlingCacheValidators {
+    // Read the validators of a response, if it has any.
+    fn from_headers(headers: &header::HeaderMap) -> Option<Self> {
+        let validators = SwanlingCacheValidators {
+            etag: headers.get(header::ETAG).cloned(),
+            last_modified: headers.get(header::LAST_MODIFIED).cloned(),
+        };
+        if validators.etag.is_none() && validators.last_modified.is_none() {
+            None
+        } else {
+            Some(validators)
+        }
+    }
+
+    // Make the request conditional, so the server can respond that it wasn't modified.
+    fn apply(self, mut request_builder: RequestBuilder) -> RequestBuilder {
+        if let Some(etag) = self.etag {
+            request_builder = request_builder.header(header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = self.last_modified {
+            request_builder = request_builder.header(header::IF_MODIFIED_SINCE, last_modified);
+        }
+        request_builder
+    }
+}
+
+/// The cookies set by the server for a [`SwanlingUser`](./struct.SwanlingUser.html), shared
+/// with the cookie store of its default client.
+pub(crate) type SwanlingCookieJar = Arc<std::sync::RwLock<cookie_store::CookieStore>>;
+
+/// The redirect policy of the request a client is making, and the redirects it followed.
+///
+/// Each [`SwanlingUser`](./struct.SwanlingUser.html) has its own, which the redirect policy
+/// of the default client finds in [`REDIRECTS`] while the user makes a request, so users
+/// sharing a client each follow their own redirects.
+#[derive(Debug)]
+pub(crate) struct SwanlingRedirects {
+    /// Whether the request follows redirects at all.
+    follow: bool,
+    /// How redirects are followed.
+    policy: SwanlingRedirectPolicy,
+    /// How many redirects the request followed.
+    count: usize,
+    /// When the request followed its last redirect.
+    last_followed: Option<Instant>,
+}
+impl Default for SwanlingRedirects {
+    fn default() -> Self {
+        SwanlingRedirects {
+            follow: true,
+            policy: SwanlingRedirectPolicy::default(),
+            count: 0,
+            last_followed: None,
+        }
+    }
+}
+impl SwanlingRedirects {
+    // Decide whether to follow a redirect to `url`, counting each redirect followed.
+    fn follow(&mut self, url: &Url, previous: &[Url]) -> Result<bool, &'static str> {
+        if !self.follow {
+            return Ok(false);
+        }
+        let follow = match &self.policy {
+            SwanlingRedirectPolicy::Limited(max) if previous.len() > *max => {
+                return Err("too many redirects")
+            }
+            SwanlingRedirectPolicy::Limited(_) => true,
+            SwanlingRedirectPolicy::None => false,
+            SwanlingRedirectPolicy::Custom(function) => function(url, previous),
+        };
+        if follow {
+            self.count += 1;
+            self.last_followed = Some(Instant::now());
+        }
+        Ok(follow)
+    }
+}
+
+tokio::task_local! {
+    /// The redirects of the request the running user is making with a default client.
+    static REDIRECTS: Arc<std::sync::Mutex<SwanlingRedirects>>;
+}
+
+/// Build the client used by a [`SwanlingUser`](./struct.SwanlingUser.html) to make requests.
+///
+/// Cookies set by the server are stored in `jar`. If `sticky_cookie` is set, the
+/// `(name, value)` cookie is sent with every request, pinning the client to a backend.
+/// Redirects are followed as configured in the [`REDIRECTS`] of the user making the request,
+/// which counts the redirects followed. If `local_address` is set, requests are sent from
+/// that address. If `resolver` is set, it resolves hostnames instead of the system resolver.
+/// If `client_builder` is set, it can further customize the client before it is built.
+fn build_default_client(
+    jar: SwanlingCookieJar,
+    sticky_cookie: Option<(String, String)>,
+    local_address: Option<IpAddr>,
+    resolver: Option<&Arc<SwanlingResolver>>,
+    client_builder: Option<&SwanlingClientBuilderHook>,
+) -> Result<Client, reqwest::Error> {
+    let cookie_store = SwanlingCookieStore { jar, sticky_cookie };
+    let redirect_policy = Policy::custom(|attempt| {
+        // Requests made with the client directly, instead of by the user, follow the default
+        // redirect policy.
+        let follow = REDIRECTS
+            .try_with(|redirects| {
+                redirects
+                    .lock()
+                    .unwrap()
+                    .follow(attempt.url(), attempt.previous())
+            })
+            .unwrap_or_else(|_| {
+                SwanlingRedirects::default().follow(attempt.url(), attempt.previous())
+            });
+        match follow {
+            Ok(true) => attempt.follow(),
+            Ok(false) => attempt.stop(),
+            Err(error) => attempt.error(error),
+        }
+    });
+    let mut builder = Client::builder()
+        .user_agent(APP_USER_AGENT)
+        .cookie_provider(Arc::new(cookie_store))
+        .redirect(redirect_policy)
+        .local_address(local_address);
+    if let Some(resolver) = resolver {
+        builder = builder.dns_resolver(resolver.clone());
+        // Without idle connections, every request opens a new connection and so resolves
+        // the hostname with the resolver.
+        if !resolver.keep_alive {
+            builder = builder.pool_max_idle_per_host(0);
+        }
+    }
+    match client_builder {
+        Some(client_builder) => client_builder(builder).build(),
+        None => builder.build(),
+    }
+}
+
+/// The time it takes to transfer `length` bytes at `bandwidth` bytes per second.
+fn transfer_time(length: usize, bandwidth: usize) -> std::time::Duration {
+    std::time::Duration::from_secs_f64(length as f64 / bandwidth as f64)
+}
+
+/// Read the body of a response at `bandwidth` bytes per second, as a slow client would,
+/// returning a response with the buffered body.
+async fn read_response_at_bandwidth(
+    mut response: Response,
+    bandwidth: usize,
+) -> Result<Response, reqwest::Error> {
+    let mut builder = http::Response::builder()
+        .status(response.status())
+        .version(response.version())
+        .url(response.url().clone());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = response.headers().clone();
+    }
+
+    // Pause after each chunk until the body has taken as long as it would at this rate.
+    let started = Instant::now();
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        body.extend_from_slice(&chunk);
+        let elapsed = started.elapsed();
+        let expected = transfer_time(body.len(), bandwidth);
+        if expected > elapsed {
+            tokio::time::sleep(expected - elapsed).await;
+        }
+    }
+
+    // The parts are copied from a valid response, so can't fail to build.
+    Ok(Response::from(
+        builder.body(body).expect("failed to rebuild response"),
+    ))
+}
+
+/// How many bytes of each response body are printed with `--debug-single-user`, unless
+/// `--debug-body-max-bytes` is set.
+const DEBUG_SINGLE_USER_BODY_BYTES: usize = 1_024;
+
+// Print a response received while debugging a single user: its status, headers and the
+// start of its body. The body has to be read to print it, so the response is rebuilt.
+async fn print_debug_response(
+    request: &SwanlingRequestMetric,
+    response: Response,
+    max_bytes: usize,
+) -> Result<Response, reqwest::Error> {
+    println!(
+        "  {} {} -> {} ({} ms){}",
+        request.method,
+        request.url,
+        response.status(),
+        request.response_time,
+        if request.success { "" } else { " failed" }
+    );
+    for (name, value) in response.headers() {
+        println!(
+            "    {}: {}",
+            name,
+            String::from_utf8_lossy(value.as_bytes())
+        );
+    }
+
+    let mut builder = http::Response::builder()
+        .status(response.status())
+        .version(response.version())
+        .url(response.url().clone());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = response.headers().clone();
+    }
+    let body = response.bytes().await?;
+    let max_bytes = if max_bytes > 0 {
+        max_bytes
+    } else {
+        DEBUG_SINGLE_USER_BODY_BYTES
+    };
+    if body.len() > max_bytes {
+        println!(
+            "{}\n    ... truncated {} of {} bytes",
+            String::from_utf8_lossy(&body[..max_bytes]),
+            body.len() - max_bytes,
+            body.len()
+        );
+    } else if !body.is_empty() {
+        println!("{}", String::from_utf8_lossy(&body));
+    }
+
+    // The parts are copied from a valid response, so can't fail to build.
+    Ok(Response::from(
+        builder.body(body).expect("failed to rebuild response"),
+    ))
+}
+
+// Read the whole body of a response, returning the response rebuilt with the buffered body,
+// and the size of the body once decoded if it was compressed with gzip or deflate.
+async fn read_response_body(response: Response) -> Result<(Response, Option<u64>), reqwest::Error> {
+    let mut builder = http::Response::builder()
+        .status(response.status())
+        .version(response.version())
+        .url(response.url().clone());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = response.headers().clone();
+    }
+    let content_encoding = content_encoding(&response).map(|e| e.to_string());
+    let body = response.bytes().await?;
+    let decoded_size = content_encoding.and_then(|e| decoded_body_size(&e, &body));
+
+    // The parts are copied from a valid response, so can't fail to build.
+    Ok((
+        Response::from(builder.body(body).expect("failed to rebuild response")),
+        decoded_size,
+    ))
+}
+
+// The Content-Encoding of a response, if any.
+fn content_encoding(response: &Response) -> Option<&str> {
+    response
+        .headers()
+        .get(header::CONTENT_ENCODING)
+        .and_then(|e| e.to_str().ok())
+}
+
+// Whether a body with this Content-Encoding can be decoded to measure its size.
+fn is_decodable(content_encoding: &str) -> bool {
+    ["gzip", "x-gzip", "deflate"]
+        .iter()
+        .any(|e| content_encoding.trim().eq_ignore_ascii_case(e))
+}
+
+// Count the bytes of a body compressed with gzip or deflate once decoded, without keeping the
+// decoded body. Returns None for other encodings, or if the body can't be decoded.
+fn decoded_body_size(content_encoding: &str, body: &[u8]) -> Option<u64> {
+    if !is_decodable(content_encoding) {
+        return None;
+    }
+    let mut sink = std::io::sink();
+    if content_encoding.trim().eq_ignore_ascii_case("deflate") {
+        // Deflate is meant to be wrapped in zlib, but some servers send it raw.
+        std::io::copy(&mut ZlibDecoder::new(body), &mut sink)
+            .or_else(|_| std::io::copy(&mut DeflateDecoder::new(body), &mut sink))
+            .ok()
+    } else {
+        std::io::copy(&mut MultiGzDecoder::new(body), &mut sink).ok()
+    }
+}
+
+// Read the body of a response so it can be inspected, returning it as text along with the
+// response rebuilt so the body can still be read.
+async fn read_response_text(response: Response) -> Result<(Response, String), reqwest::Error> {
+    let mut builder = http::Response::builder()
+        .status(response.status())
+        .version(response.version())
+        .url(response.url().clone());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = response.headers().clone();
+    }
+    let body = response.bytes().await?;
+    let text = String::from_utf8_lossy(&body).to_string();
+
+    // The parts are copied from a valid response, so can't fail to build.
+    Ok((
+        Response::from(builder.body(body).expect("failed to rebuild response")),
+        text,
+    ))
+}
+
+/// Stores cookies for the default client, optionally always including a cookie that pins
+/// the client to a load-balanced backend.
+struct SwanlingCookieStore {
+    /// Cookies set by the server.
+    jar: SwanlingCookieJar,
+    /// An optional `(name, value)` cookie sent with every request.
+    sticky_cookie: Option<(String, String)>,
+}
+impl CookieStore for SwanlingCookieStore {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let cookies = cookie_headers
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| cookie_store::RawCookie::parse(value.to_string()).ok());
+        self.jar
+            .write()
+            .unwrap()
+            .store_response_cookies(cookies, url);
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let cookies = self
+            .jar
+            .read()
+            .unwrap()
+            .get_request_values(url)
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        let cookies = if cookies.is_empty() {
+            None
+        } else {
+            HeaderValue::from_str(&cookies).ok()
+        };
+        let (name, value) = match self.sticky_cookie.as_ref() {
+            Some(c) => c,
+            None => return cookies,
+        };
+        // The pinned backend always wins over a cookie of the same name set by the server.
+        let prefix = format!("{}=", name);
+        let mut pairs: Vec<String> = Vec::new();
+        if let Some(Ok(cookies)) = cookies.as_ref().map(|c| c.to_str()) {
+            pairs.extend(
+                cookies
+                    .split("; ")
+                    .filter(|c| !c.starts_with(&prefix))
+                    .map(|c| c.to_string()),
+            );
+        }
+        pairs.push(format!("{}{}", prefix, value));
+        HeaderValue::from_str(&pairs.join("; ")).ok()
+    }
+}
+
 /// `task!(foo)` expands to `SwanlingTask::new(foo)`, but also does some boxing to work around a limitation in the compiler.
 #[macro_export]
 macro_rules! task {
@@ -362,9 +818,137 @@ pub enum SwanlingTaskError {
         /// The unrecognized HTTP request method.
         method: Method,
     },
+    /// The request timed out.
+    Timeout {
+        /// The [`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html) that timed out.
+        raw_request: SwanlingRequestMetric,
+    },
+    /// The host name of the request could not be resolved.
+    Dns {
+        /// The [`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html) that failed.
+        raw_request: SwanlingRequestMetric,
+    },
+    /// The server refused the connection.
+    ConnectionRefused {
+        /// The [`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html) that failed.
+        raw_request: SwanlingRequestMetric,
+    },
+    /// The TLS handshake failed, for example because of an invalid certificate.
+    Tls {
+        /// The [`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html) that failed.
+        raw_request: SwanlingRequestMetric,
+    },
+    /// The server returned a 5xx status code.
+    Http5xx {
+        /// The [`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html) that failed.
+        raw_request: SwanlingRequestMetric,
+    },
+    /// The server returned a 4xx status code.
+    Http4xx {
+        /// The [`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html) that failed.
+        raw_request: SwanlingRequestMetric,
+    },
+    /// The response was marked as a failure with
+    /// [`set_failure`](./struct.SwanlingUser.html#method.set_failure).
+    ValidationFailed {
+        /// The [`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html) that failed.
+        raw_request: SwanlingRequestMetric,
+    },
+    /// The request was aborted before it was sent, as enabled with `--inject-abort`.
+    Aborted {
+        /// The [`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html) that failed.
+        raw_request: SwanlingRequestMetric,
+    },
+    /// The request failed for a reason not covered by any other category.
+    Custom {
+        /// The [`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html) that failed.
+        raw_request: SwanlingRequestMetric,
+    },
+    /// A template passed to [`render`](./struct.SwanlingUser.html#method.render) could not be
+    /// rendered.
+    InvalidTemplate {
+        /// The template that could not be rendered.
+        template: String,
+        /// An explanation of why the template could not be rendered.
+        detail: String,
+    },
+    /// A poll started with [`poll_until`](./struct.SwanlingUser.html#method.poll_until)
+    /// didn't meet its condition before it timed out.
+    PollTimeout {
+        /// The name of the poll, or its path if it isn't named.
+        name: String,
+        /// How many requests the poll made.
+        polls: usize,
+    },
+    /// A row couldn't be checked out of a
+    /// [`SwanlingFeeder`](../feeder/struct.SwanlingFeeder.html).
+    FeederFailed {
+        /// The name of the feeder.
+        name: String,
+        /// Why the row couldn't be checked out.
+        detail: String,
+    },
+    /// A query run with a [`SwanlingSqlPool`](../sql/struct.SwanlingSqlPool.html) failed,
+    /// or its result could not be parsed.
+    SqlFailed {
+        /// The name of the query.
+        name: String,
+        /// The error returned by the database driver.
+        detail: String,
+    },
 }
 /// Implement a helper to provide a text description of all possible types of errors.
 impl SwanlingTaskError {
+    /// Categorize a failed request, based on its status code and error.
+    ///
+    /// Requests that were updated with
+    /// [`set_failure`](./struct.SwanlingUser.html#method.set_failure) are categorized as
+    /// [`ValidationFailed`](./enum.SwanlingTaskError.html#variant.ValidationFailed). Requests
+    /// that got a response are categorized by their status code, and requests that didn't get
+    /// a response are categorized by the error that prevented it.
+    pub fn from_failed_request(raw_request: SwanlingRequestMetric) -> Self {
+        if raw_request.update {
+            return SwanlingTaskError::ValidationFailed { raw_request };
+        }
+
+        match raw_request.status_code {
+            500..=599 => return SwanlingTaskError::Http5xx { raw_request },
+            400..=499 => return SwanlingTaskError::Http4xx { raw_request },
+            _ => (),
+        }
+
+        // Without a response, all that is left to inspect is the error text.
+        let error = raw_request.error.to_lowercase();
+        if raw_request.error == INJECT_ABORT_ERROR {
+            SwanlingTaskError::Aborted { raw_request }
+        } else if error.contains("timed out") || error.contains("timeout") {
+            SwanlingTaskError::Timeout { raw_request }
+        } else if error.contains("dns error") || error.contains("failed to lookup address") {
+            SwanlingTaskError::Dns { raw_request }
+        } else if error.contains("connection refused") {
+            SwanlingTaskError::ConnectionRefused { raw_request }
+        } else if error.contains("tls") || error.contains("ssl") || error.contains("certificate") {
+            SwanlingTaskError::Tls { raw_request }
+        } else {
+            SwanlingTaskError::Custom { raw_request }
+        }
+    }
+
+    /// The category of the error, used to aggregate the error summary.
+    pub fn category(&self) -> &str {
+        match *self {
+            SwanlingTaskError::Timeout { .. } => "Timeout",
+            SwanlingTaskError::Dns { .. } => "Dns",
+            SwanlingTaskError::ConnectionRefused { .. } => "ConnectionRefused",
+            SwanlingTaskError::Tls { .. } => "Tls",
+            SwanlingTaskError::Http5xx { .. } => "Http5xx",
+            SwanlingTaskError::Http4xx { .. } => "Http4xx",
+            SwanlingTaskError::ValidationFailed { .. } => "ValidationFailed",
+            SwanlingTaskError::Aborted { .. } => "Aborted",
+            _ => "Custom",
+        }
+    }
+
     fn describe(&self) -> &str {
         match *self {
             SwanlingTaskError::Reqwest(_) => "reqwest::Error",
@@ -376,6 +960,19 @@ impl SwanlingTaskError {
             SwanlingTaskError::MetricsFailed { .. } => "failed to send metrics to parent thread",
             SwanlingTaskError::LoggerFailed { .. } => "failed to send log message to logger thread",
             SwanlingTaskError::InvalidMethod { .. } => "unrecognized HTTP request method",
+            SwanlingTaskError::Timeout { .. } => "request timed out",
+            SwanlingTaskError::Dns { .. } => "failed to resolve host name",
+            SwanlingTaskError::ConnectionRefused { .. } => "connection refused",
+            SwanlingTaskError::Tls { .. } => "tls handshake failed",
+            SwanlingTaskError::Http5xx { .. } => "server error",
+            SwanlingTaskError::Http4xx { .. } => "client error",
+            SwanlingTaskError::ValidationFailed { .. } => "response failed validation",
+            SwanlingTaskError::Aborted { .. } => "request aborted",
+            SwanlingTaskError::Custom { .. } => "request failed",
+            SwanlingTaskError::InvalidTemplate { .. } => "invalid template",
+            SwanlingTaskError::PollTimeout { .. } => "poll timed out",
+            SwanlingTaskError::FeederFailed { .. } => "feeder failed",
+            SwanlingTaskError::SqlFailed { .. } => "sql query failed",
         }
     }
 }
@@ -400,6 +997,30 @@ impl fmt::Display for SwanlingTaskError {
             SwanlingTaskError::LoggerFailed { ref source } => {
                 write!(f, "SwanlingTaskError: {} ({})", self.describe(), source)
             }
+            SwanlingTaskError::InvalidTemplate { ref detail, .. } => {
+                write!(f, "SwanlingTaskError: {} ({})", self.describe(), detail)
+            }
+            SwanlingTaskError::PollTimeout { ref name, polls } => write!(
+                f,
+                "SwanlingTaskError: {} ({} after {} polls)",
+                self.describe(),
+                name,
+                polls
+            ),
+            SwanlingTaskError::FeederFailed {
+                ref name,
+                ref detail,
+            }
+            | SwanlingTaskError::SqlFailed {
+                ref name,
+                ref detail,
+            } => write!(
+                f,
+                "SwanlingTaskError: {} ({}: {})",
+                self.describe(),
+                name,
+                detail
+            ),
             _ => write!(f, "SwanlingTaskError: {}", self.describe()),
         }
     }
@@ -458,8 +1079,68 @@ impl From<flume::SendError<Option<SwanlingLog>>> for SwanlingTaskError {
     }
 }
 
+/// Controls how long a [`SwanlingUser`](./struct.SwanlingUser.html) keeps using the same
+/// client, and therefore the same connections and cookies.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SwanlingClientMode {
+    /// Each user builds a client when it starts and uses it until it exits, simulating
+    /// long-lived sessions. This is the default.
+    PerUser,
+    /// Each user builds a new client every time it loops through all tasks in the task set,
+    /// simulating first-visit users.
+    PerIteration,
+    /// All users running the task set share a single client.
+    Shared,
+}
+
+/// A function deciding whether to follow a redirect, registered with
+/// [`SwanlingRedirectPolicy::Custom`]. It is invoked with the URL being redirected to and
+/// the URLs already requested, starting with the original URL, and returns `true` to
+/// follow the redirect or `false` to return the redirect response.
+pub type SwanlingRedirectFunction = Arc<dyn Fn(&Url, &[Url]) -> bool + Send + Sync>;
+
+/// How users running a [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html) follow redirects,
+/// set with [`SwanlingTaskSet::set_redirect_policy`](./struct.SwanlingTaskSet.html#method.set_redirect_policy).
+///
+/// Requests made with
+/// [`swanling_send_no_redirect`](./struct.SwanlingUser.html#method.swanling_send_without_redirects)
+/// never follow redirects, whatever the policy.
+#[derive(Clone)]
+pub enum SwanlingRedirectPolicy {
+    /// Follow up to this many redirects, failing the request if there are more. The default
+    /// follows up to 10 redirects.
+    Limited(usize),
+    /// Don't follow redirects, returning the redirect response instead.
+    None,
+    /// Follow redirects while the function returns `true`.
+    Custom(SwanlingRedirectFunction),
+}
+impl Default for SwanlingRedirectPolicy {
+    fn default() -> Self {
+        SwanlingRedirectPolicy::Limited(MAX_REDIRECTS)
+    }
+}
+/// Custom policies are closures, so they're only displayed by name.
+impl fmt::Debug for SwanlingRedirectPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SwanlingRedirectPolicy::Limited(max) => f.debug_tuple("Limited").field(max).finish(),
+            SwanlingRedirectPolicy::None => f.write_str("None"),
+            SwanlingRedirectPolicy::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+impl Hash for SwanlingRedirectPolicy {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        if let SwanlingRedirectPolicy::Limited(max) = self {
+            max.hash(state);
+        }
+    }
+}
+
 /// An individual task set.
-#[derive(Clone, Hash)]
+#[derive(Clone)]
 pub struct SwanlingTaskSet {
     /// The name of the task set.
     pub name: String,
@@ -468,6 +1149,8 @@ pub struct SwanlingTaskSet {
     pub task_sets_index: usize,
     /// An integer value that controls the frequency that this task set will be assigned to a user.
     pub weight: usize,
+    /// An optional absolute number of users that will be assigned this task set, ignoring `weight`.
+    pub fixed_users: Option<usize>,
     /// An integer value indicating the minimum number of seconds a user will sleep after running a task.
     pub min_wait: usize,
     /// An integer value indicating the maximum number of seconds a user will sleep after running a task.
@@ -488,6 +1171,31 @@ pub struct SwanlingTaskSet {
     pub weighted_on_stop_tasks: WeightedSwanlingTasks,
     /// An optional default host to run this `SwanlingTaskSet` against.
     pub host: Option<String>,
+    /// An optional base URL this `SwanlingTaskSet` always runs against, even if `--host` is set.
+    pub base_url: Option<Url>,
+    /// Controls whether users running this `SwanlingTaskSet` reuse, rebuild or share a client.
+    pub client_mode: SwanlingClientMode,
+    /// An optional number of bytes per second users running this `SwanlingTaskSet` can send
+    /// and receive.
+    pub bandwidth: Option<usize>,
+    /// An optional delay added to every request made by users running this `SwanlingTaskSet`.
+    pub added_latency: Option<std::time::Duration>,
+    /// Controls how users running this `SwanlingTaskSet` follow redirects.
+    pub redirect_policy: SwanlingRedirectPolicy,
+    /// User agents each user running this `SwanlingTaskSet` picks one from when it starts.
+    pub user_agents: Vec<String>,
+    /// An optional number of consecutive task failures after which a user running this
+    /// `SwanlingTaskSet` is stopped.
+    pub max_failures_per_user: Option<usize>,
+    /// Whether users stopped after too many consecutive task failures are replaced with a
+    /// fresh user.
+    pub replace_failed_users: bool,
+    /// Functions run in order when each user starts, before any
+    /// [`on_start`](./struct.SwanlingTask.html#method.set_on_start) tasks.
+    pub on_start_sessions: Vec<SwanlingSessionFunction>,
+    /// Functions run in order when each user stops, after any
+    /// [`on_stop`](./struct.SwanlingTask.html#method.set_on_stop) tasks.
+    pub on_stop_sessions: Vec<SwanlingSessionFunction>,
 }
 impl SwanlingTaskSet {
     /// Creates a new [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html). Once created, a
@@ -507,6 +1215,7 @@ impl SwanlingTaskSet {
             name: name.to_string(),
             task_sets_index: usize::max_value(),
             weight: 1,
+            fixed_users: None,
             min_wait: 0,
             max_wait: 0,
             tasks: Vec::new(),
@@ -514,6 +1223,16 @@ impl SwanlingTaskSet {
             weighted_on_start_tasks: Vec::new(),
             weighted_on_stop_tasks: Vec::new(),
             host: None,
+            base_url: None,
+            client_mode: SwanlingClientMode::PerUser,
+            bandwidth: None,
+            added_latency: None,
+            redirect_policy: SwanlingRedirectPolicy::default(),
+            user_agents: Vec::new(),
+            max_failures_per_user: None,
+            replace_failed_users: false,
+            on_start_sessions: Vec::new(),
+            on_stop_sessions: Vec::new(),
         }
     }
 
@@ -571,6 +1290,41 @@ impl SwanlingTaskSet {
         Ok(self)
     }
 
+    /// Assigns an absolute number of users to a task set, instead of allocating users to it
+    /// by weight. Users are first assigned to all task sets with fixed users, then any
+    /// remaining users are allocated by weight to the task sets without fixed users. For
+    /// example, if task set admin has 5 fixed users and task set anonymous and task set
+    /// editor have weights of 3 and 1, and you spin up a load test with 25 users, 5 of them
+    /// will be running the admin task set, 15 the anonymous task set and 5 the editor task set.
+    ///
+    /// Users with a fixed task set are launched before users allocated by weight. The load
+    /// test fails to start if `--users` is less than the total number of fixed users, or
+    /// more than it when all task sets have fixed users.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let mut admin_tasks = taskset!("AdminTasks").set_fixed_users(5)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_fixed_users(mut self, users: usize) -> Result<Self, SwanlingError> {
+        trace!("{} set_fixed_users: {}", self.name, users);
+        if users == 0 {
+            return Err(SwanlingError::InvalidOption {
+                option: "SwanlingTaskSet.set_fixed_users".to_string(),
+                value: users.to_string(),
+                detail: "Fixed users must be set to at least 1.".to_string(),
+            });
+        }
+        self.fixed_users = Some(users);
+
+        Ok(self)
+    }
+
     /// Set a default host for the task set. If no `--host` flag is set when running the load test, this
     /// host will be pre-pended on all requests. For example, this can configure your load test to run
     /// against your local development environment by default, and the `--host` option could be used to
@@ -589,6 +1343,31 @@ impl SwanlingTaskSet {
         self
     }
 
+    /// Set a base URL the task set always runs against. Unlike [`set_host`](#method.set_host),
+    /// the base URL isn't overridden by the `--host` option, so a single load test can
+    /// exercise several hosts, for example a frontend and its API. Relative paths requested
+    /// by users running this task set are prepended with this base URL.
+    ///
+    /// Metrics of requests made to a different host than `--host` are tracked separately,
+    /// their names prefixed with the host unless the request or task is named.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let mut api_tasks = taskset!("ApiTasks").set_base_url("https://api.example.com")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_base_url(mut self, base_url: &str) -> Result<Self, SwanlingError> {
+        trace!("{} set_base_url: {}", self.name, base_url);
+        self.base_url = Some(parse_base_url(base_url, "SwanlingTaskSet.set_base_url()")?);
+
+        Ok(self)
+    }
+
     /// Configure a task_set to to pause after running each task. The length of the pause will be randomly
     /// selected from `min_weight` to `max_wait` inclusively.  For example, if `min_wait` is `0` and
     /// `max_weight` is `2`, the user will randomly sleep for 0, 1 or 2 seconds after each task completes.
@@ -628,44 +1407,339 @@ impl SwanlingTaskSet {
 
         Ok(self)
     }
-}
 
-/// Commands sent from the parent thread to the user threads, and from the manager to the
-/// worker processes.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub enum SwanlingUserCommand {
-    /// Tell worker process to pause load test.
-    Wait,
-    /// Tell worker process to start load test.
-    Run,
-    /// Tell user thread or worker process to exit.
-    Exit,
-}
+    /// Configure whether users running this task set build a client once when they start
+    /// (the default), build a new client each time they loop through all tasks, or share a
+    /// single client. Rebuilding a client discards its connections and cookies, and any
+    /// client configured with
+    /// [`set_client_builder`](./struct.SwanlingUser.html#method.set_client_builder) is
+    /// replaced with the default client.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut example_tasks = taskset!("ExampleTasks").set_client_mode(SwanlingClientMode::Shared);
+    /// ```
+    pub fn set_client_mode(mut self, client_mode: SwanlingClientMode) -> Self {
+        trace!("{} set_client_mode: {:?}", self.name, client_mode);
+        self.client_mode = client_mode;
+        self
+    }
 
-/// Supported HTTP methods.
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Ord, PartialOrd)]
-pub enum SwanlingMethod {
-    Delete,
-    Get,
-    Head,
-    Patch,
-    Post,
-    Put,
-}
-/// Display method in upper case.
-impl fmt::Display for SwanlingMethod {
-    // Implement display of `SwanlingMethod` with `{}` marker.
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            SwanlingMethod::Delete => write!(f, "DELETE"),
-            SwanlingMethod::Get => write!(f, "GET"),
-            SwanlingMethod::Head => write!(f, "HEAD"),
-            SwanlingMethod::Patch => write!(f, "PATCH"),
-            SwanlingMethod::Post => write!(f, "POST"),
-            SwanlingMethod::Put => write!(f, "PUT"),
+    /// Limits how many bytes per second users running this task set can send and receive,
+    /// simulating a slow client such as a mobile device. Request bodies are delayed as if
+    /// uploaded at this rate, and response bodies are read at this rate before being
+    /// returned, so the time spent transferring them is included in the response time.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     // Simulate a 1 MB/s connection.
+    ///     let mut mobile_tasks = taskset!("MobileTasks").set_bandwidth(1_000_000)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_bandwidth(mut self, bytes_per_second: usize) -> Result<Self, SwanlingError> {
+        trace!("{} set_bandwidth: {}", self.name, bytes_per_second);
+        if bytes_per_second == 0 {
+            return Err(SwanlingError::InvalidOption {
+                option: "SwanlingTaskSet.set_bandwidth".to_string(),
+                value: bytes_per_second.to_string(),
+                detail: "Bandwidth must be set to at least 1 byte per second.".to_string(),
+            });
         }
-    }
-}
+        self.bandwidth = Some(bytes_per_second);
+
+        Ok(self)
+    }
+
+    /// Adds a delay before every request made by users running this task set, simulating
+    /// the network latency of a slow client such as a mobile device. The delay is included
+    /// in the response time.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// let mut mobile_tasks = taskset!("MobileTasks").set_added_latency(Duration::from_millis(80));
+    /// ```
+    pub fn set_added_latency(mut self, latency: std::time::Duration) -> Self {
+        trace!("{} set_added_latency: {:?}", self.name, latency);
+        self.added_latency = Some(latency);
+        self
+    }
+
+    /// Sets how users running this task set follow redirects. By default up to 10 redirects
+    /// are followed. Each redirect that is followed is counted in the request's metrics,
+    /// along with how long was spent being redirected. Like
+    /// [`swanling_send_without_redirects`](./struct.SwanlingUser.html#method.swanling_send_without_redirects),
+    /// the policy only applies to the default client.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    /// use std::sync::Arc;
+    ///
+    /// // Return redirect responses instead of following them.
+    /// let mut api_tasks = taskset!("ApiTasks").set_redirect_policy(SwanlingRedirectPolicy::None);
+    ///
+    /// // Only follow redirects to the same host.
+    /// let mut web_tasks = taskset!("WebTasks").set_redirect_policy(SwanlingRedirectPolicy::Custom(
+    ///     Arc::new(|url, previous| url.host() == previous[0].host()),
+    /// ));
+    /// ```
+    pub fn set_redirect_policy(mut self, redirect_policy: SwanlingRedirectPolicy) -> Self {
+        trace!("{} set_redirect_policy: {:?}", self.name, redirect_policy);
+        self.redirect_policy = redirect_policy;
+        self
+    }
+
+    /// Sets a pool of user agents for users running this task set. Each user picks one of
+    /// them at random when it starts, and sends it with every request that doesn't set its
+    /// own `User-Agent` header. Web application firewalls and CDNs often treat browsers, bots
+    /// and mobile devices differently, so how many requests were sent with each user agent is
+    /// summarized in a user agent table. Users pick a user agent with their
+    /// [`rng`](./struct.SwanlingUser.html#method.rng), so with `--seed` they pick the same one
+    /// every time. Task sets without their own user agents use `--user-agent-file`, if set.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let mut browser_tasks = taskset!("BrowserTasks").set_user_agents(vec![
+    ///         "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:89.0) Gecko/20100101 Firefox/89.0",
+    ///         "Mozilla/5.0 (iPhone; CPU iPhone OS 14_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/14.1.1 Mobile/15E148 Safari/604.1",
+    ///     ])?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_user_agents(mut self, user_agents: Vec<&str>) -> Result<Self, SwanlingError> {
+        trace!("{} set_user_agents: {:?}", self.name, user_agents);
+        for user_agent in &user_agents {
+            if user_agent.is_empty() || header::HeaderValue::from_str(user_agent).is_err() {
+                return Err(SwanlingError::InvalidOption {
+                    option: "SwanlingTaskSet.set_user_agents".to_string(),
+                    value: user_agent.to_string(),
+                    detail: "User agents must be valid, non-empty header values.".to_string(),
+                });
+            }
+        }
+        self.user_agents = user_agents.iter().map(|u| u.to_string()).collect();
+
+        Ok(self)
+    }
+
+    /// Stops a user running this task set once its tasks fail this many consecutive times,
+    /// so one wedged session doesn't skew the entire load test. A task fails when it returns
+    /// an error, and any successful task resets the count. Stopped users run their
+    /// [`on_stop`](./struct.SwanlingTask.html#method.set_on_stop) tasks, are logged, and are
+    /// counted in [`SwanlingMetrics`](../metrics/struct.SwanlingMetrics.html)`.removed_users`.
+    /// To replace them with a fresh user instead, also call
+    /// [`set_replace_failed_users`](#method.set_replace_failed_users).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     // Stop users after 10 consecutive task failures.
+    ///     let mut example_tasks = taskset!("ExampleTasks").set_max_failures_per_user(10)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_max_failures_per_user(mut self, max_failures: usize) -> Result<Self, SwanlingError> {
+        trace!("{} set_max_failures_per_user: {}", self.name, max_failures);
+        if max_failures == 0 {
+            return Err(SwanlingError::InvalidOption {
+                option: "SwanlingTaskSet.set_max_failures_per_user".to_string(),
+                value: max_failures.to_string(),
+                detail: "Max failures per user must be set to at least 1.".to_string(),
+            });
+        }
+        self.max_failures_per_user = Some(max_failures);
+
+        Ok(self)
+    }
+
+    /// Replaces users stopped by
+    /// [`set_max_failures_per_user`](#method.set_max_failures_per_user) with a fresh user,
+    /// keeping the number of users running this task set constant. The fresh user gets a
+    /// new [`uuid`](./struct.SwanlingUser.html#method.uuid) and client, and starts over
+    /// with the [`on_start`](./struct.SwanlingTask.html#method.set_on_start) tasks.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let mut example_tasks = taskset!("ExampleTasks")
+    ///         .set_max_failures_per_user(10)?
+    ///         .set_replace_failed_users(true);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_replace_failed_users(mut self, replace: bool) -> Self {
+        trace!("{} set_replace_failed_users: {}", self.name, replace);
+        self.replace_failed_users = replace;
+        self
+    }
+
+    /// Registers a function that runs when each user running this task set starts, before
+    /// any [`on_start`](./struct.SwanlingTask.html#method.set_on_start) tasks. Unlike tasks,
+    /// session functions receive a mutable reference to the
+    /// [`SwanlingUser`](./struct.SwanlingUser.html), and if one returns an error the user
+    /// is removed from the load test, for example because it failed to log in. Removed
+    /// users don't run any tasks, are logged, and are counted in
+    /// [`SwanlingMetrics`](../metrics/struct.SwanlingMetrics.html)`.removed_users`.
+    ///
+    /// Multiple session functions run in the order they were registered.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut example_tasks = taskset!("ExampleTasks")
+    ///     .register_on_start_session(|user| Box::pin(login(user)));
+    ///
+    /// /// Log in, removing the user from the load test if it fails.
+    /// async fn login(user: &mut SwanlingUser) -> SwanlingTaskResult {
+    ///     let mut swanling = user.post("/login", "username=foo&password=bar").await?;
+    ///     if let Ok(response) = &swanling.response {
+    ///         if !response.status().is_success() {
+    ///             return user.set_failure("login failed", &mut swanling.request, None, None);
+    ///         }
+    ///     }
+    ///     // Wait longer between tasks once logged in.
+    ///     user.max_wait = 10;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn register_on_start_session<F>(mut self, function: F) -> Self
+    where
+        F: for<'r> Fn(
+                &'r mut SwanlingUser,
+            ) -> Pin<Box<dyn Future<Output = SwanlingTaskResult> + Send + 'r>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        trace!("{} register_on_start_session", self.name);
+        self.on_start_sessions.push(Arc::new(function));
+        self
+    }
+
+    /// Registers a function that runs when each user running this task set stops, after
+    /// any [`on_stop`](./struct.SwanlingTask.html#method.set_on_stop) tasks, for example
+    /// to log out. Errors are logged, as the user is already leaving the load test.
+    ///
+    /// Users removed from the load test by an
+    /// [`on_start` session function](./struct.SwanlingTaskSet.html#method.register_on_start_session)
+    /// don't run these functions.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut example_tasks = taskset!("ExampleTasks")
+    ///     .register_on_stop_session(|user| Box::pin(logout(user)));
+    ///
+    /// async fn logout(user: &mut SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/logout").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn register_on_stop_session<F>(mut self, function: F) -> Self
+    where
+        F: for<'r> Fn(
+                &'r mut SwanlingUser,
+            ) -> Pin<Box<dyn Future<Output = SwanlingTaskResult> + Send + 'r>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        trace!("{} register_on_stop_session", self.name);
+        self.on_stop_sessions.push(Arc::new(function));
+        self
+    }
+}
+impl Hash for SwanlingTaskSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.task_sets_index.hash(state);
+        self.weight.hash(state);
+        self.fixed_users.hash(state);
+        self.min_wait.hash(state);
+        self.max_wait.hash(state);
+        self.tasks.hash(state);
+        self.weighted_tasks.hash(state);
+        self.weighted_on_start_tasks.hash(state);
+        self.weighted_on_stop_tasks.hash(state);
+        self.host.hash(state);
+        self.base_url.hash(state);
+        self.client_mode.hash(state);
+        self.bandwidth.hash(state);
+        self.added_latency.hash(state);
+        self.redirect_policy.hash(state);
+        self.on_start_sessions.len().hash(state);
+        self.on_stop_sessions.len().hash(state);
+    }
+}
+
+/// Commands sent from the parent thread to the user threads, and from the manager to the
+/// worker processes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SwanlingUserCommand {
+    /// Tell user thread or worker process to pause load test.
+    Wait,
+    /// Tell user thread to resume, or worker process to start load test.
+    Run,
+    /// Tell user thread or worker process to exit.
+    Exit,
+    /// Tell user thread to run its on_stop tasks, and restart as a fresh user.
+    Retire,
+}
+
+/// Supported HTTP methods.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Ord, PartialOrd)]
+pub enum SwanlingMethod {
+    Delete,
+    Get,
+    Head,
+    Options,
+    Patch,
+    Post,
+    Put,
+    /// A message published to a message queue with
+    /// [`publish`](./struct.SwanlingUser.html#method.publish), not an HTTP request.
+    Publish,
+}
+/// Display method in upper case.
+impl fmt::Display for SwanlingMethod {
+    // Implement display of `SwanlingMethod` with `{}` marker.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SwanlingMethod::Delete => write!(f, "DELETE"),
+            SwanlingMethod::Get => write!(f, "GET"),
+            SwanlingMethod::Head => write!(f, "HEAD"),
+            SwanlingMethod::Options => write!(f, "OPTIONS"),
+            SwanlingMethod::Patch => write!(f, "PATCH"),
+            SwanlingMethod::Post => write!(f, "POST"),
+            SwanlingMethod::Put => write!(f, "PUT"),
+            SwanlingMethod::Publish => write!(f, "PUBLISH"),
+        }
+    }
+}
 
 /// Convert [`http::method::Method`](https://docs.rs/http/0.2.4/http/method/struct.Method.html)
 /// to [`SwanlingMethod`](./enum.SwanlingMethod.html).
@@ -674,6 +1748,7 @@ pub fn swanling_method_from_method(method: Method) -> Result<SwanlingMethod, Swa
         Method::DELETE => SwanlingMethod::Delete,
         Method::GET => SwanlingMethod::Get,
         Method::HEAD => SwanlingMethod::Head,
+        Method::OPTIONS => SwanlingMethod::Options,
         Method::PATCH => SwanlingMethod::Patch,
         Method::POST => SwanlingMethod::Post,
         Method::PUT => SwanlingMethod::Put,
@@ -683,6 +1758,62 @@ pub fn swanling_method_from_method(method: Method) -> Result<SwanlingMethod, Swa
     })
 }
 
+/// A request to send with
+/// [`swanling_send`](./struct.SwanlingUser.html#method.swanling_send), tagged with key/value
+/// metadata.
+///
+/// Tags are copied into the [`SwanlingRequestMetric`] of the request, so they're included in
+/// the request log and the error log, and errors can be summarized per tag with
+/// `--error-tag`, for example to analyze errors per tenant or per variant.
+///
+/// Any [`reqwest::RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html)
+/// can be sent without tags, it's converted into a `SwanlingRequestBuilder` automatically.
+///
+/// # Example
+/// ```rust
+/// use swanling::prelude::*;
+///
+/// let mut task = task!(tenant_function);
+///
+/// /// A simple task that requests the front page of a tenant.
+/// async fn tenant_function(user: &SwanlingUser) -> SwanlingTaskResult {
+///     let tenant = user.config_value("tenant").unwrap_or("1");
+///     let request_builder = user.swanling_get("/").await?;
+///     let request_builder = SwanlingRequestBuilder::new(request_builder).tag("tenant", tenant);
+///     let _swanling = user.swanling_send(request_builder, None).await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SwanlingRequestBuilder {
+    /// The request to send.
+    request_builder: RequestBuilder,
+    /// The key/value metadata recorded with the request.
+    tags: BTreeMap<String, String>,
+}
+impl SwanlingRequestBuilder {
+    /// Wraps a request builder, to tag the request before sending it.
+    pub fn new(request_builder: RequestBuilder) -> Self {
+        SwanlingRequestBuilder {
+            request_builder,
+            tags: BTreeMap::new(),
+        }
+    }
+
+    /// Tags the request with a key and a value, replacing any value already set for the
+    /// key.
+    pub fn tag<T: ToString>(mut self, key: &str, value: T) -> Self {
+        self.tags.insert(key.to_string(), value.to_string());
+        self
+    }
+}
+impl From<RequestBuilder> for SwanlingRequestBuilder {
+    fn from(request_builder: RequestBuilder) -> Self {
+        SwanlingRequestBuilder::new(request_builder)
+    }
+}
+
 /// The response to a SwanlingRequest
 #[derive(Debug)]
 pub struct SwanlingResponse {
@@ -695,6 +1826,296 @@ impl SwanlingResponse {
     pub fn new(request: SwanlingRequestMetric, response: Result<Response, reqwest::Error>) -> Self {
         SwanlingResponse { request, response }
     }
+
+    /// Deserialize the response body from JSON into any type that implements
+    /// [`serde::Deserialize`](https://docs.rs/serde/*/serde/trait.Deserialize.html).
+    ///
+    /// If the request failed its error is returned. If the body isn't valid JSON for the
+    /// type, the request is marked as a failure with
+    /// [`set_failure`](./struct.SwanlingUser.html#method.set_failure), so the reason is
+    /// recorded in the metrics and the error log, and a
+    /// [`ValidationFailed`](./enum.SwanlingTaskError.html#variant.ValidationFailed) error is
+    /// returned.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Item {
+    ///     id: usize,
+    /// }
+    ///
+    /// let mut task = task!(items_function);
+    ///
+    /// /// A simple task that requests a list of items, and then each item.
+    /// async fn items_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let swanling = user.get("/items").await?;
+    ///     let items: Vec<Item> = swanling.json_as(user).await?;
+    ///     for item in items {
+    ///         let _swanling = user.get_named(&format!("/items/{}", item.id), "item").await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn json_as<T: DeserializeOwned>(
+        mut self,
+        user: &SwanlingUser,
+    ) -> Result<T, SwanlingTaskError> {
+        // A failed request was already recorded, return the reason it failed.
+        if !self.request.success {
+            return Err(SwanlingTaskError::from_failed_request(self.request));
+        }
+        let response = match self.response {
+            Ok(response) => response,
+            Err(_) => return Err(SwanlingTaskError::from_failed_request(self.request)),
+        };
+
+        let headers = response.headers().clone();
+        let body = match response.bytes().await {
+            Ok(body) => body,
+            Err(e) => {
+                let tag = format!("failed to read JSON response: {}", e);
+                user.set_failure(&tag, &mut self.request, Some(&headers), None)?;
+                return Err(SwanlingTaskError::ValidationFailed {
+                    raw_request: self.request,
+                });
+            }
+        };
+        match serde_json::from_slice(&body) {
+            Ok(value) => Ok(value),
+            Err(e) => {
+                let tag = format!("failed to deserialize JSON response: {}", e);
+                let body = String::from_utf8_lossy(&body);
+                user.set_failure(&tag, &mut self.request, Some(&headers), Some(&body))?;
+                Err(SwanlingTaskError::ValidationFailed {
+                    raw_request: self.request,
+                })
+            }
+        }
+    }
+}
+
+/// A long-lived response returned by
+/// [`swanling_send_streaming`](./struct.SwanlingUser.html#method.swanling_send_streaming) and
+/// [`get_streaming`](./struct.SwanlingUser.html#method.get_streaming), such as a chunked
+/// response or a stream of server-sent events.
+///
+/// The body can be consumed one chunk at a time with
+/// [`chunk`](./struct.SwanlingStream.html#method.chunk), or one server-sent event at a time
+/// with [`next_event`](./struct.SwanlingStream.html#method.next_event). Once done, call
+/// [`finish`](./struct.SwanlingStream.html#method.finish) to read the rest of the stream,
+/// or [`close`](./struct.SwanlingStream.html#method.close) to stop reading early, recording
+/// the total duration of the stream.
+#[derive(Debug)]
+pub struct SwanlingStream {
+    /// The request that opened the stream, with the time to first byte as its response time.
+    pub request: SwanlingRequestMetric,
+    /// The response.
+    pub response: Result<Response, reqwest::Error>,
+    /// When the request was started.
+    started: Instant,
+    /// How many chunks have been received.
+    chunks: usize,
+    /// How many bytes have been received.
+    bytes: usize,
+    /// Bytes received but not yet parsed into server-sent events.
+    buffer: Vec<u8>,
+}
+impl SwanlingStream {
+    fn new(swanling: SwanlingResponse, started: Instant) -> Self {
+        SwanlingStream {
+            request: swanling.request,
+            response: swanling.response,
+            started,
+            chunks: 0,
+            bytes: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// How many milliseconds it took to receive the response headers.
+    pub fn time_to_first_byte(&self) -> u64 {
+        self.request.response_time
+    }
+
+    /// How many chunks of the body have been received so far.
+    pub fn chunks_received(&self) -> usize {
+        self.chunks
+    }
+
+    /// How many bytes of the body have been received so far.
+    pub fn bytes_received(&self) -> usize {
+        self.bytes
+    }
+
+    /// Whether the response body is sent in chunks, with `Transfer-Encoding: chunked`.
+    pub fn is_chunked(&self) -> bool {
+        self.header_contains(header::TRANSFER_ENCODING, "chunked")
+    }
+
+    /// Whether the response is a stream of server-sent events, with
+    /// `Content-Type: text/event-stream`.
+    pub fn is_event_stream(&self) -> bool {
+        self.header_contains(header::CONTENT_TYPE, "text/event-stream")
+    }
+
+    // Whether the response header contains the value, ignoring case.
+    fn header_contains(&self, name: header::HeaderName, value: &str) -> bool {
+        match &self.response {
+            Ok(response) => response
+                .headers()
+                .get_all(name)
+                .iter()
+                .filter_map(|v| v.to_str().ok())
+                .any(|v| v.to_ascii_lowercase().contains(value)),
+            Err(_) => false,
+        }
+    }
+
+    /// Read the next chunk of the body as it is received, returning `None` once the stream
+    /// ends, or if no response was received.
+    pub async fn chunk(&mut self) -> Result<Option<Vec<u8>>, SwanlingTaskError> {
+        let response = match self.response.as_mut() {
+            Ok(response) => response,
+            Err(_) => return Ok(None),
+        };
+        match response.chunk().await? {
+            Some(chunk) => {
+                self.chunks += 1;
+                self.bytes += chunk.len();
+                Ok(Some(chunk.to_vec()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Read the next [server-sent event](https://html.spec.whatwg.org/multipage/server-sent-events.html),
+    /// returning `None` once the stream ends. Comments, and events without data, are skipped.
+    pub async fn next_event(
+        &mut self,
+    ) -> Result<Option<SwanlingServerSentEvent>, SwanlingTaskError> {
+        loop {
+            while let Some(block) = take_event_block(&mut self.buffer) {
+                if let Some(event) = SwanlingServerSentEvent::parse(&block) {
+                    return Ok(Some(event));
+                }
+            }
+            match self.chunk().await? {
+                Some(chunk) => self.buffer.extend_from_slice(&chunk),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Read the rest of the stream, then record its total duration. Returns the total
+    /// duration in milliseconds.
+    pub async fn finish(mut self, user: &SwanlingUser) -> Result<u64, SwanlingTaskError> {
+        let result = loop {
+            match self.chunk().await {
+                Ok(Some(_)) => (),
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+        let duration = self.record_duration(user, result.as_ref().err())?;
+        result.map(|_| duration)
+    }
+
+    /// Stop reading the stream, and record its total duration so far. Returns the total
+    /// duration in milliseconds.
+    pub fn close(self, user: &SwanlingUser) -> Result<u64, SwanlingTaskError> {
+        self.record_duration(user, None)
+    }
+
+    // Record the total duration of the stream as a separate request, failing if the
+    // request that opened it failed or the stream ended with an error.
+    fn record_duration(
+        &self,
+        user: &SwanlingUser,
+        error: Option<&SwanlingTaskError>,
+    ) -> Result<u64, SwanlingTaskError> {
+        let duration = self.started.elapsed().as_millis();
+        let mut request_metric = self.request.clone();
+        request_metric.name = format!("{} (stream)", self.request.name);
+        request_metric.set_response_time(duration);
+        request_metric.response_size = self.bytes as u64;
+        request_metric.coordinated_omission_elapsed = 0;
+        if let Some(error) = error {
+            request_metric.success = false;
+            request_metric.error = error.to_string();
+        }
+
+        if !user.config.no_metrics {
+            user.send_request_metric_to_parent(request_metric)?;
+        }
+
+        Ok(duration as u64)
+    }
+}
+
+/// Remove the first complete server-sent event from the buffer, returning its lines.
+/// Events end with a blank line.
+fn take_event_block(buffer: &mut Vec<u8>) -> Option<String> {
+    let mut start = 0;
+    while let Some(length) = buffer[start..].iter().position(|b| *b == b'\n') {
+        let line = &buffer[start..start + length];
+        if line.is_empty() || line == b"\r" {
+            let block = String::from_utf8_lossy(&buffer[..start]).to_string();
+            buffer.drain(..start + length + 1);
+            return Some(block);
+        }
+        start += length + 1;
+    }
+    None
+}
+
+/// An event received from a stream of
+/// [server-sent events](https://html.spec.whatwg.org/multipage/server-sent-events.html),
+/// returned by [`next_event`](./struct.SwanlingStream.html#method.next_event).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SwanlingServerSentEvent {
+    /// The type of the event, `message` unless set by the server.
+    pub event: String,
+    /// The data of the event, multiple lines of data are joined with a newline.
+    pub data: String,
+    /// The optional id of the event.
+    pub id: Option<String>,
+}
+impl SwanlingServerSentEvent {
+    // Parse the lines of an event, returning `None` if the event has no data.
+    fn parse(block: &str) -> Option<Self> {
+        let mut event = SwanlingServerSentEvent {
+            event: "message".to_string(),
+            ..Default::default()
+        };
+        let mut data: Vec<&str> = Vec::new();
+        for line in block.lines() {
+            // Lines starting with a colon are comments.
+            if line.starts_with(':') {
+                continue;
+            }
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, ""),
+            };
+            match field {
+                "event" => event.event = value.to_string(),
+                "data" => data.push(value),
+                "id" => event.id = Some(value.to_string()),
+                // Other fields, such as retry, are ignored.
+                _ => (),
+            }
+        }
+
+        if data.is_empty() {
+            return None;
+        }
+        event.data = data.join("\n");
+        Some(event)
+    }
 }
 
 /// Object created by [`log_debug()`](struct.SwanlingUser.html#method.log_debug) and written
@@ -815,6 +2236,87 @@ impl SwanlingRequestCadence {
     }
 }
 
+/// A hook invoked with every request before it is sent, registered with
+/// [`SwanlingAttack::register_request_hook`](../struct.SwanlingAttack.html#method.register_request_hook).
+pub type SwanlingRequestHook = Arc<dyn Fn(&mut Request, &SwanlingUser) + Send + Sync>;
+
+/// A hook invoked with every response before its metrics are recorded, registered with
+/// [`SwanlingAttack::register_response_hook`](../struct.SwanlingAttack.html#method.register_response_hook).
+pub type SwanlingResponseHook = Arc<dyn Fn(&Response, &mut SwanlingRequestMetric) + Send + Sync>;
+
+/// A hook invoked with the `ClientBuilder` of every default client before it is built,
+/// registered with
+/// [`SwanlingAttack::set_client_builder`](../struct.SwanlingAttack.html#method.set_client_builder).
+pub type SwanlingClientBuilderHook = Arc<dyn Fn(ClientBuilder) -> ClientBuilder + Send + Sync>;
+
+/// All hooks invoked by [`swanling_send`](./struct.SwanlingUser.html#method.swanling_send).
+#[derive(Clone, Default)]
+pub(crate) struct SwanlingHooks {
+    /// Hooks invoked with every request, in the order they were registered.
+    pub(crate) request: Vec<SwanlingRequestHook>,
+    /// Hooks invoked with every response, in the order they were registered.
+    pub(crate) response: Vec<SwanlingResponseHook>,
+    /// An optional hook customizing each default client before it is built.
+    pub(crate) client_builder: Option<SwanlingClientBuilderHook>,
+}
+/// Hooks are closures, so only display how many of each are registered.
+impl fmt::Debug for SwanlingHooks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SwanlingHooks")
+            .field("request", &self.request.len())
+            .field("response", &self.response.len())
+            .field("client_builder", &self.client_builder.is_some())
+            .finish()
+    }
+}
+
+/// Data set by the [`test_start`](../struct.SwanlingAttack.html#method.test_start) task with
+/// [`set_shared_data`](./struct.SwanlingUser.html#method.set_shared_data), and shared
+/// read-only with all users.
+#[derive(Clone, Default)]
+pub(crate) struct SwanlingSharedData {
+    data: Arc<std::sync::RwLock<Option<Arc<dyn Any + Send + Sync>>>>,
+}
+impl SwanlingSharedData {
+    /// Returns a copy of the shared data, so it can't be replaced by the users it's copied to.
+    pub(crate) fn snapshot(&self) -> Self {
+        SwanlingSharedData {
+            data: Arc::new(std::sync::RwLock::new(self.data.read().unwrap().clone())),
+        }
+    }
+}
+/// Shared data can be of any type, so only display whether or not it is set.
+impl fmt::Debug for SwanlingSharedData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SwanlingSharedData")
+            .field("set", &self.data.read().unwrap().is_some())
+            .finish()
+    }
+}
+
+/// The labels of the tasks a [`SwanlingUser`](./struct.SwanlingUser.html) is running.
+pub(crate) type SwanlingRunningTasks = Arc<std::sync::Mutex<Vec<String>>>;
+
+/// Functions registered with
+/// [`register_cleanup`](./struct.SwanlingUser.html#method.register_cleanup), shared with
+/// the scheduled tasks of the same user.
+#[derive(Clone, Default)]
+pub(crate) struct SwanlingCleanups(Arc<std::sync::Mutex<Vec<SwanlingCleanupFunction>>>);
+impl SwanlingCleanups {
+    /// Removes the most recently registered function, if any.
+    pub(crate) fn pop(&self) -> Option<SwanlingCleanupFunction> {
+        self.0.lock().unwrap().pop()
+    }
+}
+/// Cleanup functions are closures, so only display how many are registered.
+impl fmt::Debug for SwanlingCleanups {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SwanlingCleanups")
+            .field(&self.0.lock().unwrap().len())
+            .finish()
+    }
+}
+
 /// An individual user state, repeatedly running all [`SwanlingTask`](./struct.SwanlingTask.html)s
 /// in a specific [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html).
 #[derive(Debug, Clone)]
@@ -830,6 +2332,16 @@ pub struct SwanlingUser {
     pub position: Arc<AtomicUsize>,
     /// The base URL to prepend to all relative paths.
     pub base_url: Arc<RwLock<Url>>,
+    /// The optional base URL set with `set_base_url()` on the task set, copied from its task set.
+    pub(crate) task_set_base_url: Option<Url>,
+    /// The optional base URL set with `set_base_url()` on the task that's running.
+    pub(crate) task_base_url: Arc<RwLock<Option<Url>>>,
+    /// The name of the task that's running, or `task N` if it's not named, sent in the
+    /// `X-Swanling-Task` header when `--task-headers` is enabled.
+    pub(crate) task_label: Arc<RwLock<String>>,
+    /// Whether users are spread over several hosts with `--host`, in which case all requests
+    /// are named with their host so the metrics of each host are tracked separately.
+    pub(crate) name_with_host: bool,
     /// Minimum amount of time to sleep after running a task.
     pub min_wait: usize,
     /// Maximum amount of time to sleep after running a task.
@@ -840,15 +2352,31 @@ pub struct SwanlingUser {
     pub logger: Option<flume::Sender<Option<SwanlingLog>>>,
     /// Channel to throttle.
     pub throttle: Option<flume::Sender<bool>>,
+    /// Unbuffered channel to throttle, used by high priority tasks when the throttle is full.
+    pub(crate) throttle_priority: Option<flume::Sender<bool>>,
+    /// Whether the running task jumps the throttle queue, set with
+    /// [`SwanlingTask::set_throttle_priority`](./struct.SwanlingTask.html#method.set_throttle_priority).
+    pub(crate) task_throttle_priority: Arc<AtomicBool>,
     /// Normal tasks are optionally throttled,
     /// [`test_start`](../struct.SwanlingAttack.html#method.test_start) and
     /// [`test_stop`](../struct.SwanlingAttack.html#method.test_stop) tasks are not.
     pub is_throttled: bool,
     /// Channel to parent.
     pub channel_to_parent: Option<flume::Sender<SwanlingMetric>>,
+    /// Metrics aggregated until they're sent to the parent, shared with scheduled tasks.
+    pub(crate) metrics_batch: Arc<std::sync::Mutex<SwanlingMetricsBatch>>,
     /// An index into the internal [`SwanlingAttack`](../struct.SwanlingAttack.html)`.weighted_users`
     /// vector, indicating which weighted `SwanlingUser` is running.
+    ///
+    /// When running in a fleet with `--fleet-index` and `--fleet-size`, the index is unique
+    /// across all processes of the fleet, so it can be used to partition test data.
     pub weighted_users_index: usize,
+    /// A random version 4 UUID identifying this user, returned by `uuid()`.
+    pub(crate) uuid: String,
+    /// The next sequence number rendered as `{{seq}}` by `render()`.
+    pub(crate) template_seq: Arc<AtomicUsize>,
+    /// The random number generator returned by `rng()`, seeded with `--seed` if set.
+    pub(crate) rng: Arc<std::sync::Mutex<StdRng>>,
     /// A weighted list of all tasks that run when the user first starts.
     pub weighted_on_start_tasks: WeightedSwanlingTasks,
     /// A weighted list of all tasks that this user runs once started.
@@ -862,6 +2390,48 @@ pub struct SwanlingUser {
     request_cadence: Arc<RwLock<SwanlingRequestCadence>>,
     /// Tracks how much time is spent sleeping during a loop through all tasks.
     pub(crate) slept: Arc<AtomicU64>,
+    /// Hooks invoked with every request and response.
+    pub(crate) hooks: SwanlingHooks,
+    /// The cookies set by the server, shared with the cookie store of the client.
+    pub(crate) jar: SwanlingCookieJar,
+    /// How the request being made follows redirects, read by the redirect policy of the client.
+    pub(crate) redirects: Arc<std::sync::Mutex<SwanlingRedirects>>,
+    /// How this user follows redirects, copied from its task set.
+    pub(crate) redirect_policy: SwanlingRedirectPolicy,
+    /// The paths requested with placeholders before they were rendered, by rendered URL.
+    pub(crate) path_templates: Arc<std::sync::Mutex<HashMap<String, String>>>,
+    /// The validators of the responses cached by this user, by URL.
+    pub(crate) cache: Arc<std::sync::Mutex<HashMap<String, SwanlingCacheValidators>>>,
+    /// The optional backend this user is pinned to with `--sticky-cookie` or `--sticky-header`.
+    pub(crate) sticky_backend: Option<String>,
+    /// The optional user agent this user picked from the user agents of its task set.
+    pub(crate) user_agent: Option<String>,
+    /// The optional local address this user sends requests from with `--bind-address`.
+    pub(crate) bind_address: Option<IpAddr>,
+    /// The optional bytes per second this user can send and receive, copied from its task set.
+    pub(crate) bandwidth: Option<usize>,
+    /// The optional delay added to every request, copied from its task set.
+    pub(crate) added_latency: Option<std::time::Duration>,
+    /// The optional resolver configured with `--dns-server`, `--no-dns-cache` or `--dns-refresh`.
+    pub(crate) resolver: Option<Arc<SwanlingResolver>>,
+    /// Data set by the `test_start` task, shared with all users.
+    pub(crate) shared_data: SwanlingSharedData,
+    /// A size-bounded cache shared with all users.
+    pub(crate) shared_cache: SwanlingSharedCache,
+    /// Counters, barriers and flags shared with all users.
+    pub(crate) coordination: SwanlingCoordination,
+    /// Rows of test data handed out to one user at a time.
+    pub(crate) feeders: SwanlingFeeders,
+    /// The optional pool of database connections shared with all users.
+    #[cfg(feature = "sql")]
+    pub(crate) sql: Option<SwanlingSqlPool>,
+    /// The optional message queue producer shared with all users.
+    #[cfg(feature = "queue")]
+    pub(crate) producer: Option<SwanlingQueue>,
+    /// The tasks this user is running, reported if it doesn't exit when the load test stops.
+    pub(crate) running_tasks: SwanlingRunningTasks,
+    /// Functions cleaning up the resources this user created, run when it stops.
+    pub(crate) cleanups: SwanlingCleanups,
 }
 impl SwanlingUser {
     /// Create a new user state.
@@ -874,10 +2444,9 @@ impl SwanlingUser {
         load_test_hash: u64,
     ) -> Result<Self, SwanlingError> {
         trace!("new SwanlingUser");
-        let client = Client::builder()
-            .user_agent(APP_USER_AGENT)
-            .cookie_store(true)
-            .build()?;
+        let jar = SwanlingCookieJar::default();
+        let resolver = SwanlingResolver::from_configuration(configuration);
+        let client = build_default_client(jar.clone(), None, None, resolver.as_ref(), None)?;
 
         Ok(SwanlingUser {
             started: Instant::now(),
@@ -885,21 +2454,55 @@ impl SwanlingUser {
             client: Arc::new(Mutex::new(client)),
             position: Arc::new(AtomicUsize::new(0)),
             base_url: Arc::new(RwLock::new(base_url)),
+            task_set_base_url: None,
+            task_base_url: Arc::new(RwLock::new(None)),
+            task_label: Arc::new(RwLock::new("".to_string())),
+            name_with_host: matches!(configuration.get_hosts(), Some(hosts) if hosts.len() > 1),
             min_wait,
             max_wait,
             config: configuration.clone(),
             logger: None,
             throttle: None,
+            throttle_priority: None,
+            task_throttle_priority: Arc::new(AtomicBool::new(false)),
             is_throttled: true,
             channel_to_parent: None,
+            metrics_batch: Arc::new(std::sync::Mutex::new(SwanlingMetricsBatch::new(
+                configuration,
+            ))),
             // A value of max_value() indicates this user isn't fully initialized yet.
             weighted_users_index: usize::max_value(),
+            uuid: new_uuid(),
+            template_seq: Arc::new(AtomicUsize::new(0)),
+            rng: Arc::new(std::sync::Mutex::new(user_rng(configuration.seed, 0))),
             weighted_on_start_tasks: Vec::new(),
             weighted_tasks: Vec::new(),
             weighted_on_stop_tasks: Vec::new(),
             load_test_hash,
             request_cadence: Arc::new(RwLock::new(SwanlingRequestCadence::new())),
             slept: Arc::new(AtomicU64::new(0)),
+            hooks: SwanlingHooks::default(),
+            jar,
+            redirects: Arc::new(std::sync::Mutex::new(SwanlingRedirects::default())),
+            redirect_policy: SwanlingRedirectPolicy::default(),
+            path_templates: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            cache: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            sticky_backend: None,
+            user_agent: None,
+            bind_address: None,
+            bandwidth: None,
+            added_latency: None,
+            resolver,
+            shared_data: SwanlingSharedData::default(),
+            shared_cache: SwanlingSharedCache::default(),
+            coordination: SwanlingCoordination::default(),
+            feeders: SwanlingFeeders::default(),
+            #[cfg(feature = "sql")]
+            sql: None,
+            #[cfg(feature = "queue")]
+            producer: None,
+            running_tasks: SwanlingRunningTasks::default(),
+            cleanups: SwanlingCleanups::default(),
         })
     }
 
@@ -922,21 +2525,58 @@ impl SwanlingUser {
     ///
     /// A `base_url` is determined per user thread, using the following order
     /// of precedence:
-    ///  1. `--host` (host specified on the command line when running load test)
-    ///  2. [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html)`.host` (default host defined for the
-    /// current task set)
-    ///  3. [`SwanlingDefault::Host`](../enum.SwanlingDefault.html#variant.Host) (default host
-    /// defined for the current load test)
+    ///  1. [`SwanlingTask`](./struct.SwanlingTask.html)`.base_url` (base URL set with
+    ///     [`set_base_url`](./struct.SwanlingTask.html#method.set_base_url) on the running task)
+    ///  2. [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html)`.base_url` (base URL set with
+    ///     [`set_base_url`](./struct.SwanlingTaskSet.html#method.set_base_url) on the current
+    ///     task set)
+    ///  3. `--host` (host specified on the command line when running load test)
+    ///  4. [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html)`.host` (default host defined for the
+    ///     current task set)
+    ///  5. [`SwanlingDefault::Host`](../enum.SwanlingDefault.html#variant.Host) (default host
+    ///     defined for the current load test)
+    ///
+    /// The `{user_id}` and `{uuid}` placeholders in the path are first replaced as with
+    /// [`render_template`](./struct.SwanlingUser.html#method.render_template). Unnamed
+    /// requests to the URL are named after the path before it was rendered, so the requests
+    /// of all users are tracked together.
     pub async fn build_url(&self, path: &str) -> Result<String, SwanlingTaskError> {
+        let rendered = self.render_template(path);
+        let url = self.join_url(&rendered).await?.to_string();
+
+        // Remember the path before it was rendered, to name requests to the URL after it.
+        if rendered != path {
+            if let Ok(template_url) = self.join_url(path).await {
+                let template_path = template_url.path().replace("%7B", "{").replace("%7D", "}");
+                self.path_templates
+                    .lock()
+                    .unwrap()
+                    .insert(url.clone(), template_path);
+            }
+        }
+
+        Ok(url)
+    }
+
+    // Prepend the `base_url` to a relative path, see `build_url`.
+    async fn join_url(&self, path: &str) -> Result<Url, SwanlingTaskError> {
         // If URL includes a host, simply use it.
         if let Ok(parsed_path) = Url::parse(path) {
             if let Some(_host) = parsed_path.host() {
-                return Ok(path.to_string());
+                return Ok(parsed_path);
             }
         }
 
+        // Otherwise use the base URL set on the task or task set, if any.
+        if let Some(base_url) = self.task_base_url.read().await.as_ref() {
+            return Ok(base_url.join(path)?);
+        }
+        if let Some(base_url) = self.task_set_base_url.as_ref() {
+            return Ok(base_url.join(path)?);
+        }
+
         // Otherwise use the `base_url`.
-        Ok(self.base_url.read().await.join(path)?.to_string())
+        Ok(self.base_url.read().await.join(path)?)
     }
 
     /// A helper to make a `GET` request of a path and collect relevant metrics.
@@ -950,7 +2590,220 @@ impl SwanlingUser {
     /// then call
     /// [`swanling_send`](./struct.SwanlingUser.html#method.swanling_send) to invoke the request.)
     ///
-    /// Calls to `get()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// Calls to `get()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
+    /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(get_function);
+    ///
+    /// /// A very simple task that makes a GET request.
+    /// async fn get_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/path/to/foo/").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get(&self, path: &str) -> Result<SwanlingResponse, SwanlingTaskError> {
+        let request_builder = self.swanling_get(path).await?;
+
+        Ok(self.swanling_send(request_builder, None).await?)
+    }
+
+    /// A helper to make a named `GET` request of a path and collect relevant metrics.
+    /// Automatically prepends the correct host. Naming a request only affects collected
+    /// metrics.
+    ///
+    /// Calls to `get_named()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
+    /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(get_function);
+    ///
+    /// /// A very simple task that makes a GET request.
+    /// async fn get_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get_named("/path/to/foo/", "foo").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_named(
+        &self,
+        path: &str,
+        request_name: &str,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        let request_builder = self.swanling_get(path).await?;
+
+        Ok(self
+            .swanling_send(request_builder, Some(request_name))
+            .await?)
+    }
+
+    /// A helper to make a `GET` request of a path like a browser with a cache, and collect
+    /// relevant metrics. Automatically prepends the correct host.
+    ///
+    /// The `ETag` and `Last-Modified` headers of each successful response are remembered,
+    /// and sent as `If-None-Match` and `If-Modified-Since` the next time this user requests
+    /// the same URL, so the server can respond with `304 Not Modified` instead of the whole
+    /// response. A `304 Not Modified` response to a conditional request is a success, and how
+    /// many conditional requests weren't modified is summarized in the metrics.
+    ///
+    /// Cached responses are forgotten each time the client is rebuilt, for example with
+    /// [`SwanlingClientMode::PerIteration`](./enum.SwanlingClientMode.html#variant.PerIteration).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(get_function);
+    ///
+    /// /// A simple task that loads a stylesheet, revalidating it on repeat visits.
+    /// async fn get_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get_cached("/style.css").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_cached(&self, path: &str) -> Result<SwanlingResponse, SwanlingTaskError> {
+        self.send_cached(path, None).await
+    }
+
+    /// A helper to make a named `GET` request of a path like a browser with a cache, as
+    /// described in [`get_cached`](./struct.SwanlingUser.html#method.get_cached).
+    /// Automatically prepends the correct host. Naming a request only affects collected
+    /// metrics.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(get_function);
+    ///
+    /// /// A simple task that loads a stylesheet, revalidating it on repeat visits.
+    /// async fn get_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get_cached_named("/style.css?v=2", "style").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_cached_named(
+        &self,
+        path: &str,
+        request_name: &str,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        self.send_cached(path, Some(request_name)).await
+    }
+
+    // Make a `GET` request, revalidating the cached response if any, and cache the
+    // validators of the response.
+    async fn send_cached(
+        &self,
+        path: &str,
+        request_name: Option<&str>,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        let url = self.build_url(path).await?;
+        let mut request_builder = self.client.lock().await.get(&url);
+        let cached = self.cache.lock().unwrap().get(&url).cloned();
+        if let Some(validators) = cached {
+            request_builder = validators.apply(request_builder);
+        }
+
+        let swanling = self.swanling_send(request_builder, request_name).await?;
+        if let Ok(response) = &swanling.response {
+            // A response that wasn't modified is still cached.
+            if response.status().is_success() {
+                let mut cache = self.cache.lock().unwrap();
+                match SwanlingCacheValidators::from_headers(response.headers()) {
+                    Some(validators) => cache.insert(url, validators),
+                    None => cache.remove(&url),
+                };
+            }
+        }
+
+        Ok(swanling)
+    }
+
+    /// A helper to make a `GET` request of a path, and deserialize the JSON response into
+    /// any type that implements
+    /// [`serde::Deserialize`](https://docs.rs/serde/*/serde/trait.Deserialize.html).
+    /// Automatically prepends the correct host.
+    ///
+    /// If the response can't be deserialized, the request is marked as a failure as
+    /// described in [`SwanlingResponse::json_as`](./struct.SwanlingResponse.html#method.json_as).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Status {
+    ///     healthy: bool,
+    /// }
+    ///
+    /// let mut task = task!(status_function);
+    ///
+    /// /// A simple task that requests a JSON status.
+    /// async fn status_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let status: Status = user.get_json("/status").await?;
+    ///     if !status.healthy {
+    ///         // Handle an unhealthy status.
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T, SwanlingTaskError> {
+        self.get(path).await?.json_as(self).await
+    }
+
+    /// A helper to make a named `GET` request of a path, and deserialize the JSON response
+    /// into any type that implements
+    /// [`serde::Deserialize`](https://docs.rs/serde/*/serde/trait.Deserialize.html).
+    /// Automatically prepends the correct host. Naming a request only affects collected
+    /// metrics.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(status_function);
+    ///
+    /// /// A simple task that requests a JSON status.
+    /// async fn status_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _status: serde_json::Value = user.get_json_named("/status", "status").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_json_named<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        request_name: &str,
+    ) -> Result<T, SwanlingTaskError> {
+        self.get_named(path, request_name)
+            .await?
+            .json_as(self)
+            .await
+    }
+
+    /// A helper to make a `POST` request of a path and collect relevant metrics.
+    /// Automatically prepends the correct host.
+    ///
+    /// (If you need to set headers, change timeouts, or otherwise make use of the
+    /// [`reqwest::RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html)
+    /// object, you can instead call `swanling_post` which returns a
+    /// [`RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html),
+    /// then call `swanling_send` to invoke the request.)
+    ///
+    /// Calls to `post()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
     /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
     /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
     ///
@@ -958,26 +2811,30 @@ impl SwanlingUser {
     /// ```rust
     /// use swanling::prelude::*;
     ///
-    /// let mut task = task!(get_function);
+    /// let mut task = task!(post_function);
     ///
-    /// /// A very simple task that makes a GET request.
-    /// async fn get_function(user: &SwanlingUser) -> SwanlingTaskResult {
-    ///     let _swanling = user.get("/path/to/foo/").await?;
+    /// /// A very simple task that makes a POST request.
+    /// async fn post_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.post("/path/to/foo/", "BODY BEING POSTED").await?;
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get(&self, path: &str) -> Result<SwanlingResponse, SwanlingTaskError> {
-        let request_builder = self.swanling_get(path).await?;
+    pub async fn post(
+        &self,
+        path: &str,
+        body: &str,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        let request_builder = self.swanling_post(path).await?.body(body.to_string());
 
         Ok(self.swanling_send(request_builder, None).await?)
     }
 
-    /// A helper to make a named `GET` request of a path and collect relevant metrics.
+    /// A helper to make a named `POST` request of a path and collect relevant metrics.
     /// Automatically prepends the correct host. Naming a request only affects collected
     /// metrics.
     ///
-    /// Calls to `get_named()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// Calls to `post_named()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
     /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
     /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
     ///
@@ -985,29 +2842,31 @@ impl SwanlingUser {
     /// ```rust
     /// use swanling::prelude::*;
     ///
-    /// let mut task = task!(get_function);
+    /// let mut task = task!(post_function);
     ///
-    /// /// A very simple task that makes a GET request.
-    /// async fn get_function(user: &SwanlingUser) -> SwanlingTaskResult {
-    ///     let _swanling = user.get_named("/path/to/foo/", "foo").await?;
+    /// /// A very simple task that makes a POST request.
+    /// async fn post_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.post_named("/path/to/foo/", "foo", "BODY BEING POSTED").await?;
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_named(
+    pub async fn post_named(
         &self,
         path: &str,
         request_name: &str,
+        body: &str,
     ) -> Result<SwanlingResponse, SwanlingTaskError> {
-        let request_builder = self.swanling_get(path).await?;
+        let request_builder = self.swanling_post(path).await?.body(body.to_string());
 
         Ok(self
             .swanling_send(request_builder, Some(request_name))
             .await?)
     }
 
-    /// A helper to make a `POST` request of a path and collect relevant metrics.
-    /// Automatically prepends the correct host.
+    /// A helper to make a `POST` request of a value serialized as JSON to the path and
+    /// collect relevant metrics. Automatically prepends the correct host, and sets the
+    /// `Content-Type: application/json` header.
     ///
     /// (If you need to set headers, change timeouts, or otherwise make use of the
     /// [`reqwest::RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html)
@@ -1015,65 +2874,67 @@ impl SwanlingUser {
     /// [`RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html),
     /// then call `swanling_send` to invoke the request.)
     ///
-    /// Calls to `post()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// Calls to `post_json()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
     /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
     /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
     ///
     /// # Example
     /// ```rust
     /// use swanling::prelude::*;
+    /// use serde_json::json;
     ///
     /// let mut task = task!(post_function);
     ///
     /// /// A very simple task that makes a POST request.
     /// async fn post_function(user: &SwanlingUser) -> SwanlingTaskResult {
-    ///     let _swanling = user.post("/path/to/foo/", "BODY BEING POSTED").await?;
+    ///     let _swanling = user.post_json("/path/to/foo/", &json!({"name": "foo"})).await?;
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub async fn post(
+    pub async fn post_json<T: Serialize + ?Sized>(
         &self,
         path: &str,
-        body: &str,
+        json: &T,
     ) -> Result<SwanlingResponse, SwanlingTaskError> {
-        let request_builder = self.swanling_post(path).await?.body(body.to_string());
+        let request_builder = self.swanling_post(path).await?.json(json);
 
-        Ok(self.swanling_send(request_builder, None).await?)
+        self.swanling_send(request_builder, None).await
     }
 
-    /// A helper to make a named `POST` request of a path and collect relevant metrics.
-    /// Automatically prepends the correct host. Naming a request only affects collected
+    /// A helper to make a named `POST` request of a value serialized as JSON to the path
+    /// and collect relevant metrics. Automatically prepends the correct host, and sets the
+    /// `Content-Type: application/json` header. Naming a request only affects collected
     /// metrics.
     ///
-    /// Calls to `post_named()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// Calls to `post_json_named()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
     /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
     /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
     ///
     /// # Example
     /// ```rust
     /// use swanling::prelude::*;
+    /// use serde_json::json;
     ///
     /// let mut task = task!(post_function);
     ///
     /// /// A very simple task that makes a POST request.
     /// async fn post_function(user: &SwanlingUser) -> SwanlingTaskResult {
-    ///     let _swanling = user.post_named("/path/to/foo/", "foo", "BODY BEING POSTED").await?;
+    ///     let _swanling = user.post_json_named("/path/to/foo/", "foo", &json!({"name": "foo"})).await?;
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub async fn post_named(
+    pub async fn post_json_named<T: Serialize + ?Sized>(
         &self,
         path: &str,
         request_name: &str,
-        body: &str,
+        json: &T,
     ) -> Result<SwanlingResponse, SwanlingTaskError> {
-        let request_builder = self.swanling_post(path).await?.body(body.to_string());
+        let request_builder = self.swanling_post(path).await?.json(json);
 
-        Ok(self
-            .swanling_send(request_builder, Some(request_name))
-            .await?)
+        self.swanling_send(request_builder, Some(request_name))
+            .await
     }
 
     /// A helper to make a `HEAD` request of a path and collect relevant metrics.
@@ -1177,7 +3038,279 @@ impl SwanlingUser {
     /// Automatically prepends the correct host. Naming a request only affects collected
     /// metrics.
     ///
-    /// Calls to `delete_named()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// Calls to `delete_named()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
+    /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(delete_function);
+    ///
+    /// /// A very simple task that makes a DELETE request.
+    /// async fn delete_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.delete_named("/path/to/foo/", "foo").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn delete_named(
+        &self,
+        path: &str,
+        request_name: &str,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        let request_builder = self.swanling_delete(path).await?;
+
+        Ok(self
+            .swanling_send(request_builder, Some(request_name))
+            .await?)
+    }
+
+    /// A helper to make a `PUT` request of a string value to the path and collect
+    /// relevant metrics. Automatically prepends the correct host.
+    ///
+    /// (If you need to set headers, change timeouts, or otherwise make use of the
+    /// [`reqwest::RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html)
+    /// object, you can instead call `swanling_put` which returns a
+    /// [`RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html),
+    /// then call `swanling_send` to invoke the request.)
+    ///
+    /// Calls to `put()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
+    /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(put_function);
+    ///
+    /// /// A very simple task that makes a PUT request.
+    /// async fn put_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.put("/path/to/foo/", "BODY BEING PUT").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn put(&self, path: &str, body: &str) -> Result<SwanlingResponse, SwanlingTaskError> {
+        let request_builder = self.swanling_put(path).await?.body(body.to_string());
+
+        self.swanling_send(request_builder, None).await
+    }
+
+    /// A helper to make a named `PUT` request of a string value to the path and collect
+    /// relevant metrics. Automatically prepends the correct host. Naming a request only
+    /// affects collected metrics.
+    ///
+    /// Calls to `put_named()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
+    /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(put_function);
+    ///
+    /// /// A very simple task that makes a PUT request.
+    /// async fn put_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.put_named("/path/to/foo/", "foo", "BODY BEING PUT").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn put_named(
+        &self,
+        path: &str,
+        request_name: &str,
+        body: &str,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        let request_builder = self.swanling_put(path).await?.body(body.to_string());
+
+        self.swanling_send(request_builder, Some(request_name))
+            .await
+    }
+
+    /// A helper to make a `PUT` request of a value serialized as JSON to the path and
+    /// collect relevant metrics. Automatically prepends the correct host, and sets the
+    /// `Content-Type: application/json` header.
+    ///
+    /// (If you need to set headers, change timeouts, or otherwise make use of the
+    /// [`reqwest::RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html)
+    /// object, you can instead call `swanling_put` which returns a
+    /// [`RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html),
+    /// then call `swanling_send` to invoke the request.)
+    ///
+    /// Calls to `put_json()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
+    /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    /// use serde_json::json;
+    ///
+    /// let mut task = task!(put_function);
+    ///
+    /// /// A very simple task that makes a PUT request.
+    /// async fn put_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.put_json("/path/to/foo/", &json!({"name": "foo"})).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn put_json<T: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        json: &T,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        let request_builder = self.swanling_put(path).await?.json(json);
+
+        self.swanling_send(request_builder, None).await
+    }
+
+    /// A helper to make a named `PUT` request of a value serialized as JSON to the path
+    /// and collect relevant metrics. Automatically prepends the correct host, and sets the
+    /// `Content-Type: application/json` header. Naming a request only affects collected
+    /// metrics.
+    ///
+    /// Calls to `put_json_named()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
+    /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    /// use serde_json::json;
+    ///
+    /// let mut task = task!(put_function);
+    ///
+    /// /// A very simple task that makes a PUT request.
+    /// async fn put_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.put_json_named("/path/to/foo/", "foo", &json!({"name": "foo"})).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn put_json_named<T: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        request_name: &str,
+        json: &T,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        let request_builder = self.swanling_put(path).await?.json(json);
+
+        self.swanling_send(request_builder, Some(request_name))
+            .await
+    }
+
+    /// A helper to make a `PATCH` request of a string value to the path and collect
+    /// relevant metrics. Automatically prepends the correct host.
+    ///
+    /// (If you need to set headers, change timeouts, or otherwise make use of the
+    /// [`reqwest::RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html)
+    /// object, you can instead call `swanling_patch` which returns a
+    /// [`RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html),
+    /// then call `swanling_send` to invoke the request.)
+    ///
+    /// Calls to `patch()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
+    /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(patch_function);
+    ///
+    /// /// A very simple task that makes a PATCH request.
+    /// async fn patch_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.patch("/path/to/foo/", "BODY BEING PATCHED").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn patch(
+        &self,
+        path: &str,
+        body: &str,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        let request_builder = self.swanling_patch(path).await?.body(body.to_string());
+
+        self.swanling_send(request_builder, None).await
+    }
+
+    /// A helper to make a named `PATCH` request of a string value to the path and collect
+    /// relevant metrics. Automatically prepends the correct host. Naming a request only
+    /// affects collected metrics.
+    ///
+    /// Calls to `patch_named()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
+    /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(patch_function);
+    ///
+    /// /// A very simple task that makes a PATCH request.
+    /// async fn patch_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.patch_named("/path/to/foo/", "foo", "BODY BEING PATCHED").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn patch_named(
+        &self,
+        path: &str,
+        request_name: &str,
+        body: &str,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        let request_builder = self.swanling_patch(path).await?.body(body.to_string());
+
+        self.swanling_send(request_builder, Some(request_name))
+            .await
+    }
+
+    /// A helper to make an `OPTIONS` request of a path and collect relevant metrics.
+    /// Automatically prepends the correct host.
+    ///
+    /// (If you need to set headers, change timeouts, or otherwise make use of the
+    /// [`reqwest::RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html)
+    /// object, you can instead call `swanling_options` which returns a
+    /// [`RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html),
+    /// then call `swanling_send` to invoke the request.)
+    ///
+    /// Calls to `options()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
+    /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
+    /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(options_function);
+    ///
+    /// /// A very simple task that makes a OPTIONS request.
+    /// async fn options_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.options("/path/to/foo/").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn options(&self, path: &str) -> Result<SwanlingResponse, SwanlingTaskError> {
+        let request_builder = self.swanling_options(path).await?;
+
+        self.swanling_send(request_builder, None).await
+    }
+
+    /// A helper to make a named `OPTIONS` request of a path and collect relevant metrics.
+    /// Automatically prepends the correct host. Naming a request only affects collected
+    /// metrics.
+    ///
+    /// Calls to `options_named()` return a [`SwanlingResponse`](./struct.SwanlingResponse.html) object which
     /// contains a copy of the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)),
     /// and the response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
     ///
@@ -1185,25 +3318,24 @@ impl SwanlingUser {
     /// ```rust
     /// use swanling::prelude::*;
     ///
-    /// let mut task = task!(delete_function);
+    /// let mut task = task!(options_function);
     ///
-    /// /// A very simple task that makes a DELETE request.
-    /// async fn delete_function(user: &SwanlingUser) -> SwanlingTaskResult {
-    ///     let _swanling = user.delete_named("/path/to/foo/", "foo").await?;
+    /// /// A very simple task that makes a OPTIONS request.
+    /// async fn options_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.options_named("/path/to/foo/", "foo").await?;
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub async fn delete_named(
+    pub async fn options_named(
         &self,
         path: &str,
         request_name: &str,
     ) -> Result<SwanlingResponse, SwanlingTaskError> {
-        let request_builder = self.swanling_delete(path).await?;
+        let request_builder = self.swanling_options(path).await?;
 
-        Ok(self
-            .swanling_send(request_builder, Some(request_name))
-            .await?)
+        self.swanling_send(request_builder, Some(request_name))
+            .await
     }
 
     /// Prepends the correct host on the path, then prepares a
@@ -1374,6 +3506,34 @@ impl SwanlingUser {
         Ok(self.client.lock().await.delete(&url))
     }
 
+    /// Prepends the correct host on the path, then prepares a
+    /// [`reqwest::RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html)
+    /// object for making an `OPTIONS` request.
+    ///
+    /// (You must then call [`swanling_send`](./struct.SwanlingUser.html#method.swanling_send) on this
+    /// object to actually execute the request.)
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(options_function);
+    ///
+    /// /// A simple task that makes an OPTIONS request, exposing the Reqwest
+    /// /// request builder.
+    /// async fn options_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let request_builder = user.swanling_options("/path/to/foo").await?;
+    ///     let _swanling = user.swanling_send(request_builder, None).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn swanling_options(&self, path: &str) -> Result<RequestBuilder, SwanlingTaskError> {
+        let url = self.build_url(path).await?;
+
+        Ok(self.client.lock().await.request(Method::OPTIONS, &url))
+    }
+
     /// Builds the provided
     /// [`reqwest::RequestBuilder`](https://docs.rs/reqwest/*/reqwest/struct.RequestBuilder.html)
     /// object and then executes the response. If metrics are being displayed, it
@@ -1392,6 +3552,15 @@ impl SwanlingUser {
     /// the request you made ([`SwanlingRequestMetric`](./struct.SwanlingRequestMetric.html)), and the
     /// response ([`reqwest::Response`](https://docs.rs/reqwest/*/reqwest/struct.Response.html)).
     ///
+    /// Any hooks registered with
+    /// [`register_request_hook`](../struct.SwanlingAttack.html#method.register_request_hook)
+    /// are invoked with the built request before it is sent, and any hooks registered with
+    /// [`register_response_hook`](../struct.SwanlingAttack.html#method.register_response_hook)
+    /// are invoked with the response before its metrics are recorded.
+    ///
+    /// To record key/value metadata with the request, wrap the request builder in a
+    /// [`SwanlingRequestBuilder`](./struct.SwanlingRequestBuilder.html) and tag it.
+    ///
     /// # Example
     /// ```rust
     /// use swanling::prelude::*;
@@ -1411,19 +3580,232 @@ impl SwanlingUser {
     /// ```
     pub async fn swanling_send(
         &self,
-        request_builder: RequestBuilder,
+        request_builder: impl Into<SwanlingRequestBuilder>,
+        request_name: Option<&str>,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        self.send_request(request_builder.into(), request_name, true, false)
+            .await
+    }
+
+    /// Builds and sends a request like
+    /// [`swanling_send`](./struct.SwanlingUser.html#method.swanling_send), but doesn't follow
+    /// redirects. Instead the redirect response itself is returned, for example to inspect
+    /// the `Location` header or a load-balancer cookie set with the redirect.
+    ///
+    /// Redirects can only be disabled on the default client, a client configured with
+    /// [`set_client_builder`](./struct.SwanlingUser.html#method.set_client_builder) uses its
+    /// own redirect policy.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(login_function);
+    ///
+    /// /// A simple task that confirms logging in redirects the user.
+    /// async fn login_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let request_builder = user.swanling_post("/login").await?;
+    ///     let mut swanling = user.swanling_send_without_redirects(request_builder, None).await?;
+    ///
+    ///     if let Ok(response) = &swanling.response {
+    ///         if !response.status().is_redirection() {
+    ///             return user.set_failure("login didn't redirect", &mut swanling.request, None, None);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn swanling_send_without_redirects(
+        &self,
+        request_builder: impl Into<SwanlingRequestBuilder>,
+        request_name: Option<&str>,
+    ) -> Result<SwanlingResponse, SwanlingTaskError> {
+        self.send_request(request_builder.into(), request_name, false, false)
+            .await
+    }
+
+    /// Builds and sends a request like
+    /// [`swanling_send`](./struct.SwanlingUser.html#method.swanling_send), returning a
+    /// [`SwanlingStream`](./struct.SwanlingStream.html) to consume a long-lived response
+    /// such as a long-polling, chunked or server-sent events endpoint.
+    ///
+    /// The request is recorded with the time to first byte as its response time, as soon
+    /// as the response headers are received. When the stream is finished or closed, the
+    /// total duration of the stream is also recorded, as a request with the same method
+    /// and the request name followed by ` (stream)`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(events_function);
+    ///
+    /// /// A simple task that reads the first server-sent event of a stream.
+    /// async fn events_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let request_builder = user.swanling_get("/events").await?;
+    ///     let mut stream = user.swanling_send_streaming(request_builder, None).await?;
+    ///
+    ///     let _event = stream.next_event().await?;
+    ///     stream.close(user)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn swanling_send_streaming(
+        &self,
+        request_builder: impl Into<SwanlingRequestBuilder>,
+        request_name: Option<&str>,
+    ) -> Result<SwanlingStream, SwanlingTaskError> {
+        let started = Instant::now();
+        let swanling = self
+            .send_request(request_builder.into(), request_name, true, true)
+            .await?;
+
+        Ok(SwanlingStream::new(swanling, started))
+    }
+
+    /// A helper to make a `GET` request of a path, returning a
+    /// [`SwanlingStream`](./struct.SwanlingStream.html) to consume a long-lived response.
+    /// Automatically prepends the correct host.
+    ///
+    /// See [`swanling_send_streaming`](./struct.SwanlingUser.html#method.swanling_send_streaming)
+    /// for how streams are recorded.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(events_function);
+    ///
+    /// /// A simple task that reads all server-sent events until the stream ends.
+    /// async fn events_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let mut stream = user.get_streaming("/events").await?;
+    ///
+    ///     if !stream.is_event_stream() {
+    ///         return user.set_failure("not an event stream", &mut stream.request, None, None);
+    ///     }
+    ///     while let Some(event) = stream.next_event().await? {
+    ///         // Do stuff with event.data here.
+    ///     }
+    ///     stream.finish(user).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_streaming(&self, path: &str) -> Result<SwanlingStream, SwanlingTaskError> {
+        let request_builder = self.swanling_get(path).await?;
+
+        self.swanling_send_streaming(request_builder, None).await
+    }
+
+    // Builds and sends a request, optionally following redirects, and records its metrics.
+    // Unless streaming, the response body is read at the task set's bandwidth, if limited.
+    async fn send_request(
+        &self,
+        request_builder: SwanlingRequestBuilder,
         request_name: Option<&str>,
+        follow_redirects: bool,
+        streaming: bool,
     ) -> Result<SwanlingResponse, SwanlingTaskError> {
         // If throttle-requests is enabled...
         if self.is_throttled && self.throttle.is_some() {
             // ...wait until there's room to add a token to the throttle channel before proceeding.
             debug!("SwanlingUser: waiting on throttle");
             // Will result in SwanlingTaskError::RequestCanceled if this fails.
-            self.throttle.clone().unwrap().send_async(true).await?;
+            let throttle = self.throttle.clone().unwrap();
+            match self.throttle_priority.as_ref() {
+                // High priority requests only wait if the bucket is full, then jump the queue.
+                Some(priority) if self.task_throttle_priority.load(Ordering::SeqCst) => {
+                    match throttle.try_send(true) {
+                        Ok(_) => (),
+                        Err(flume::TrySendError::Full(_)) => priority.send_async(true).await?,
+                        Err(flume::TrySendError::Disconnected(token)) => {
+                            return Err(SwanlingTaskError::RequestCanceled {
+                                source: flume::SendError(token),
+                            })
+                        }
+                    }
+                }
+                _ => throttle.send_async(true).await?,
+            }
         };
 
         let started = Instant::now();
-        let request = request_builder.build()?;
+        let SwanlingRequestBuilder {
+            request_builder,
+            tags,
+        } = request_builder;
+        let mut request = request_builder.build()?;
+
+        // If enabled, pin the request to the user's backend with a header. The header name
+        // and backends are validated at startup.
+        if let Some(backend) = self.sticky_backend.as_ref() {
+            if let (Ok(name), Ok(value)) = (
+                header::HeaderName::from_bytes(self.config.sticky_header.as_bytes()),
+                header::HeaderValue::from_str(backend),
+            ) {
+                request.headers_mut().insert(name, value);
+            }
+        }
+
+        // If enabled, send the user's user agent, unless the request sets its own. User
+        // agents are validated when they're configured.
+        let user_agent = match self.user_agent.as_ref() {
+            Some(user_agent) => match request.headers().get(header::USER_AGENT) {
+                Some(value) => value.to_str().unwrap_or_default().to_string(),
+                None => {
+                    if let Ok(value) = header::HeaderValue::from_str(user_agent) {
+                        request.headers_mut().insert(header::USER_AGENT, value);
+                    }
+                    user_agent.clone()
+                }
+            },
+            None => "".to_string(),
+        };
+
+        // If enabled, control which encodings the server may compress the response with,
+        // unless the request sets its own Accept-Encoding header.
+        if !request.headers().contains_key(header::ACCEPT_ENCODING) {
+            let accept_encoding = if self.config.no_gzip {
+                Some("identity")
+            } else if !self.config.accept_encoding.is_empty() {
+                Some(self.config.accept_encoding.as_str())
+            } else {
+                None
+            };
+            if let Some(Ok(value)) = accept_encoding.map(header::HeaderValue::from_str) {
+                request.headers_mut().insert(header::ACCEPT_ENCODING, value);
+            }
+        }
+
+        // If enabled, identify the run so server-side logs can be filtered to it. The run ID
+        // is validated when it's configured.
+        if self.config.run_header {
+            if let Ok(value) = header::HeaderValue::from_str(&self.config.run_id) {
+                request.headers_mut().insert("x-load-test-run", value);
+            }
+        }
+
+        // If enabled, identify the task and user that made the request. Task names that
+        // aren't valid header values are not sent.
+        if self.config.task_headers {
+            let task_label = self.task_label.read().await;
+            if !task_label.is_empty() {
+                if let Ok(value) = header::HeaderValue::from_str(&task_label) {
+                    request.headers_mut().insert("x-swanling-task", value);
+                }
+            }
+            request.headers_mut().insert(
+                "x-swanling-user",
+                header::HeaderValue::from(self.weighted_users_index),
+            );
+        }
+
+        // Allow registered hooks to modify the request, for example to sign it.
+        for hook in &self.hooks.request {
+            hook(&mut request, self);
+        }
 
         // String version of request path.
         let path = match Url::parse(&request.url().to_string()) {
@@ -1434,7 +3816,26 @@ impl SwanlingUser {
             }
         };
         let method = swanling_method_from_method(request.method().clone())?;
-        let request_name = self.get_request_name(&path, request_name);
+        // Requests to paths with placeholders are named after the path before it was
+        // rendered, so the requests of all users are tracked together.
+        let name_path = self
+            .path_templates
+            .lock()
+            .unwrap()
+            .get(request.url().as_str())
+            .cloned()
+            .unwrap_or_else(|| path.clone());
+        // Requests to other hosts than the user's `base_url`, or to any host when users are
+        // spread over several hosts, are named with their host so their metrics are tracked
+        // separately.
+        let request_name = if !self.name_with_host
+            && request.url().origin() == self.base_url.read().await.origin()
+        {
+            self.get_request_name(&name_path, request_name)
+        } else {
+            let host_path = format!("{}{}", request_host(request.url()), name_path);
+            self.get_request_name(&host_path, request_name)
+        };
 
         // Record information about the request.
         let mut request_metric = SwanlingRequestMetric::new(
@@ -1444,23 +3845,152 @@ impl SwanlingUser {
             self.started.elapsed().as_millis(),
             self.weighted_users_index,
         );
+        request_metric.conditional = request.headers().contains_key(header::IF_NONE_MATCH)
+            || request.headers().contains_key(header::IF_MODIFIED_SINCE);
+        request_metric.tags = tags;
+        request_metric.user_agent = user_agent;
+
+        // If enabled, delay a percentage of requests. The delay is included in the response
+        // time, as if it was caused by a slow client.
+        if let Some((delay, percentage)) = self.config.get_inject_latency() {
+            if rand::thread_rng().gen_bool(percentage / 100.0) {
+                debug!("{:?}: injecting {}ms of latency", &path, delay);
+                tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
+            }
+        }
+
+        // If enabled, abort a percentage of requests without sending them.
+        if let Some(percentage) = self.config.get_inject_abort() {
+            if rand::thread_rng().gen_bool(percentage / 100.0) {
+                debug!("{:?}: injecting aborted request", &path);
+                request_metric.success = false;
+                request_metric.set_status_code(None);
+                request_metric.error = INJECT_ABORT_ERROR.to_string();
+                request_metric.set_response_time(started.elapsed().as_millis());
+
+                if !self.config.no_metrics {
+                    self.send_request_metric_to_parent(request_metric.clone())?;
+                }
+
+                return Err(SwanlingTaskError::Aborted {
+                    raw_request: request_metric,
+                });
+            }
+        }
+
+        // If the task set adds latency, delay the request as if sent over a slow network.
+        if let Some(added_latency) = self.added_latency {
+            tokio::time::sleep(added_latency).await;
+        }
+
+        // If the task set limits bandwidth, delay the request as if its body was uploaded
+        // at that rate.
+        if let Some(bandwidth) = self.bandwidth {
+            let length = request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map_or(0, |body| body.len());
+            tokio::time::sleep(transfer_time(length, bandwidth)).await;
+        }
+
+        // If enabled, count the request so hostnames are resolved again every
+        // `--dns-refresh` requests.
+        if let Some(resolver) = self.resolver.as_ref() {
+            resolver.request_sent();
+        }
+
+        // Make the actual request without holding the lock on the client, so users sharing a
+        // client make requests concurrently, and record how many redirects were followed and
+        // how long it took to follow them.
+        let mut response = {
+            let client = self.client.lock().await.clone();
+            {
+                let mut redirects = self.redirects.lock().unwrap();
+                redirects.follow = follow_redirects;
+                redirects.policy = self.redirect_policy.clone();
+                redirects.count = 0;
+                redirects.last_followed = None;
+            }
+            let executed = Instant::now();
+            let response = REDIRECTS
+                .scope(self.redirects.clone(), client.execute(request))
+                .await;
+            let redirects = self.redirects.lock().unwrap();
+            request_metric.set_redirects(
+                redirects.count,
+                redirects.last_followed.map_or(0, |followed| {
+                    followed.saturating_duration_since(executed).as_millis()
+                }),
+            );
+            response
+        };
 
-        // Make the actual request.
-        let response = self.client.lock().await.execute(request).await;
+        // If the task set limits bandwidth, read the response body at that rate.
+        if let (Some(bandwidth), false) = (self.bandwidth, streaming) {
+            response = match response {
+                Ok(r) => read_response_at_bandwidth(r, bandwidth).await,
+                Err(e) => Err(e),
+            };
+        }
         request_metric.set_response_time(started.elapsed().as_millis());
 
+        // When comparing compressed and uncompressed responses, read the body of responses
+        // without a Content-Length header, such as chunked responses, to know their size,
+        // and of responses compressed with gzip or deflate, to know their decoded size.
+        let mut decoded_size = None;
+        if (self.config.no_gzip || !self.config.accept_encoding.is_empty()) && !streaming {
+            response = match response {
+                Ok(r)
+                    if r.content_length().is_none()
+                        || matches!(content_encoding(&r), Some(e) if is_decodable(e)) =>
+                {
+                    read_response_body(r).await.map(|(r, size)| {
+                        decoded_size = size;
+                        r
+                    })
+                }
+                response => response,
+            };
+        }
+
         match &response {
             Ok(r) => {
                 let status_code = r.status();
                 debug!("{:?}: status_code {}", &path, status_code);
                 // @TODO: match/handle all is_foo() https://docs.rs/http/0.2.1/http/status/struct.StatusCode.html
-                if !status_code.is_success() {
+                // A conditional request is expected to find the cached response wasn't
+                // modified.
+                let not_modified =
+                    request_metric.conditional && status_code == StatusCode::NOT_MODIFIED;
+                if !status_code.is_success() && !not_modified {
                     request_metric.success = false;
                     request_metric.error = format!("{}: {}", status_code, &path);
                 }
                 request_metric.set_status_code(Some(status_code));
                 request_metric.set_final_url(r.url().as_str());
 
+                // Record whether the response was compressed, how many bytes of body were
+                // received as sent by the server, and how many once decoded. The body is
+                // returned to the task as it was received.
+                request_metric.set_compression(
+                    content_encoding(r),
+                    r.content_length(),
+                    decoded_size,
+                );
+
+                // If enabled, record which backend served the request.
+                if !self.config.backend_header.is_empty() {
+                    if let Some(backend) = r.headers().get(self.config.backend_header.as_str()) {
+                        request_metric.backend = backend.to_str().unwrap_or_default().to_string();
+                    }
+                }
+
+                // Allow registered hooks to inspect the response, for example to apply
+                // custom failure classification.
+                for hook in &self.hooks.response {
+                    hook(r, &mut request_metric);
+                }
+
                 // Load test user was redirected.
                 if self.config.sticky_follow && request_metric.url != request_metric.final_url {
                     let base_url = self.base_url.read().await.to_string();
@@ -1502,6 +4032,22 @@ impl SwanlingUser {
             self.send_request_metric_to_parent(request_metric.clone())?;
         }
 
+        // If debugging a single user, print each response.
+        if self.config.debug_single_user && !streaming {
+            response = match response {
+                Ok(r) => {
+                    print_debug_response(&request_metric, r, self.config.debug_body_max_bytes).await
+                }
+                Err(e) => {
+                    println!(
+                        "  {} {} -> {}",
+                        request_metric.method, request_metric.url, e
+                    );
+                    Err(e)
+                }
+            };
+        }
+
         Ok(SwanlingResponse::new(request_metric, response))
     }
 
@@ -1668,16 +4214,65 @@ impl SwanlingUser {
             }
         }
 
+        // If raw latencies are being recorded, send a sample to the logger thread. Updates
+        // are not new requests, so they're not recorded.
+        if !self.config.record_raw_latencies.is_empty() && !request_metric.update {
+            if let Some(logger) = self.logger.as_ref() {
+                logger.send(Some(SwanlingLog::RawLatency(SwanlingRawLatency::new(
+                    &request_metric,
+                ))))?;
+            }
+        }
+
         // Parent is not defined when running
         // [`test_start`](../struct.SwanlingAttack.html#method.test_start),
         // [`test_stop`](../struct.SwanlingAttack.html#method.test_stop), and during testing.
         if let Some(parent) = self.channel_to_parent.clone() {
-            parent.send(SwanlingMetric::Request(request_metric))?;
+            // Requests with an error, updates, and requests to backfill with Coordinated
+            // Omission Mitigation are recorded individually by the parent. Send the metrics
+            // aggregated so far first, so the parent receives them in order.
+            if request_metric.update
+                || !request_metric.error.is_empty()
+                || request_metric.coordinated_omission_elapsed > 0
+            {
+                self.flush_metrics(true)?;
+                parent.send(SwanlingMetric::Request(request_metric))?;
+            } else {
+                self.metrics_batch
+                    .lock()
+                    .unwrap()
+                    .record_request(&request_metric);
+                self.flush_metrics(false)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send the metrics aggregated by this user to the parent, if `force` is set or
+    /// `--metrics-flush` has elapsed since they were last sent.
+    pub(crate) fn flush_metrics(&self, force: bool) -> SwanlingTaskResult {
+        if let Some(parent) = self.channel_to_parent.as_ref() {
+            let batch = self.metrics_batch.lock().unwrap().take(force);
+            if let Some(batch) = batch {
+                parent.send(batch)?;
+            }
         }
 
         Ok(())
     }
 
+    /// Count this user as removed from the load test in the metrics, for `reason`.
+    pub(crate) fn send_user_removed(&self, reason: String) {
+        if !self.config.no_metrics {
+            if let Some(parent) = self.channel_to_parent.clone() {
+                // Best effort metrics.
+                let _ = self.flush_metrics(true);
+                let _ = parent.send(SwanlingMetric::UserRemoved(reason));
+            }
+        }
+    }
+
     /// If `request_name` is set, unwrap and use this. Otherwise, if the SwanlingTask has a name
     /// set use it. Otherwise use the path.
     fn get_request_name(&self, path: &str, request_name: Option<&str>) -> String {
@@ -1698,44 +4293,250 @@ impl SwanlingUser {
         }
     }
 
-    /// Manually mark a request as a success.
+    /// Manually mark a request as a success.
+    ///
+    /// By default, Swanling will consider any response with a 2xx status code as a success.
+    /// It may be valid in your test for a non-2xx HTTP status code to be returned. A copy
+    /// of your original request is returned with the response, and a mutable copy must be
+    /// included when setting a request as a success.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(get_function);
+    ///
+    /// /// A simple task that makes a GET request.
+    /// async fn get_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let mut swanling = user.get("/404").await?;
+    ///
+    ///     if let Ok(response) = &swanling.response {
+    ///         // We expect a 404 here.
+    ///         if response.status() == 404 {
+    ///             return user.set_success(&mut swanling.request);
+    ///         }
+    ///     }
+    ///
+    ///     Err(SwanlingTaskError::RequestFailed {
+    ///         raw_request: swanling.request.clone(),
+    ///     })
+    /// }
+    /// ````
+    pub fn set_success(&self, request: &mut SwanlingRequestMetric) -> SwanlingTaskResult {
+        // Only send update if this was previously not a success.
+        if !request.success {
+            request.success = true;
+            request.update = true;
+            self.send_request_metric_to_parent(request.clone())?;
+        }
+
+        Ok(())
+    }
+
+    /// Repeatedly makes a `GET` request of a path until `predicate` returns `true` for the
+    /// body of a successful response, waiting `interval` between requests. This models
+    /// clients of asynchronous job APIs, which poll until the job they started is done.
+    /// Returns the response that met the condition, its body can still be read.
+    ///
+    /// Each request is recorded like any other request. Polls are also summarized by path in
+    /// a polls table, with how many met their condition or timed out, how many requests they
+    /// made on average and how long they waited. Use
+    /// [`poll_until_named`](./struct.SwanlingUser.html#method.poll_until_named) to summarize
+    /// polls of different paths, such as paths including a job ID, together.
+    ///
+    /// If the condition isn't met before `timeout`, returns
+    /// [`SwanlingTaskError::PollTimeout`](./enum.SwanlingTaskError.html#variant.PollTimeout).
+    /// Time spent waiting between requests isn't mistaken for a slow request by Coordinated
+    /// Omission Mitigation.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(export_report);
+    ///
+    /// /// Start an export, then poll each second until it's done, for up to a minute.
+    /// async fn export_report(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.post("/export", "report=sales").await?;
+    ///     let _swanling = user
+    ///         .poll_until(
+    ///             "/export/status",
+    ///             |body| body.contains("done"),
+    ///             Duration::from_secs(1),
+    ///             Duration::from_secs(60),
+    ///         )
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn poll_until<F>(
+        &self,
+        path: &str,
+        predicate: F,
+        interval: std::time::Duration,
+        timeout: std::time::Duration,
+    ) -> Result<SwanlingResponse, SwanlingTaskError>
+    where
+        F: FnMut(&str) -> bool,
+    {
+        self.poll(path, None, predicate, interval, timeout).await
+    }
+
+    /// Repeatedly makes a named `GET` request of a path until `predicate` returns `true`, as
+    /// described in [`poll_until`](./struct.SwanlingUser.html#method.poll_until). The
+    /// requests and the poll are summarized by name.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(export_report);
+    ///
+    /// /// Poll until the export is done, summarizing all exports together.
+    /// async fn export_report(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let swanling = user.post("/export", "report=sales").await?;
+    ///     let export_id = swanling.response?.text().await?;
+    ///     let _swanling = user
+    ///         .poll_until_named(
+    ///             &format!("/export/{}", export_id),
+    ///             "export status",
+    ///             |body| body.contains("done"),
+    ///             Duration::from_secs(1),
+    ///             Duration::from_secs(60),
+    ///         )
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn poll_until_named<F>(
+        &self,
+        path: &str,
+        request_name: &str,
+        predicate: F,
+        interval: std::time::Duration,
+        timeout: std::time::Duration,
+    ) -> Result<SwanlingResponse, SwanlingTaskError>
+    where
+        F: FnMut(&str) -> bool,
+    {
+        self.poll(path, Some(request_name), predicate, interval, timeout)
+            .await
+    }
+
+    // Make `GET` requests until the body of a successful response meets the condition, or
+    // the poll times out, then record the poll.
+    async fn poll<F>(
+        &self,
+        path: &str,
+        request_name: Option<&str>,
+        mut predicate: F,
+        interval: std::time::Duration,
+        timeout: std::time::Duration,
+    ) -> Result<SwanlingResponse, SwanlingTaskError>
+    where
+        F: FnMut(&str) -> bool,
+    {
+        let name = request_name.unwrap_or(path);
+        let started = Instant::now();
+        let mut polls = 0;
+        loop {
+            polls += 1;
+            let request_builder = self.swanling_get(path).await?;
+            let swanling = self.swanling_send(request_builder, request_name).await?;
+            // Failed requests don't meet the condition, polling continues until timeout.
+            if swanling.request.success {
+                if let Ok(response) = swanling.response {
+                    if let Ok((response, body)) = read_response_text(response).await {
+                        if predicate(&body) {
+                            self.record_poll(name, polls, started, true);
+                            return Ok(SwanlingResponse::new(swanling.request, Ok(response)));
+                        }
+                    }
+                }
+            }
+
+            if started.elapsed() + interval > timeout {
+                self.record_poll(name, polls, started, false);
+                return Err(SwanlingTaskError::PollTimeout {
+                    name: name.to_string(),
+                    polls,
+                });
+            }
+            tokio::time::sleep(interval).await;
+            // Track time waiting between polls as sleeping, for Coordinated Omission Mitigation.
+            self.slept
+                .fetch_add(interval.as_millis() as u64, Ordering::SeqCst);
+        }
+    }
+
+    // Record a finished poll, printing it if debugging a single user.
+    fn record_poll(&self, name: &str, polls: usize, started: Instant, met: bool) {
+        let wait_time = started.elapsed().as_millis() as u64;
+        if self.config.debug_single_user {
+            println!(
+                "  poll {}: {} after {} polls ({} ms)",
+                name,
+                if met { "met" } else { "timed out" },
+                polls,
+                wait_time
+            );
+        }
+
+        if !self.config.no_metrics && self.channel_to_parent.is_some() {
+            self.metrics_batch
+                .lock()
+                .unwrap()
+                .record_poll(name, polls, wait_time, met);
+        }
+    }
+
+    /// Check a soft assertion, counting how many times each `label` passed and failed in
+    /// an assertions table, separately from request failures. Returns `condition`.
     ///
-    /// By default, Swanling will consider any response with a 2xx status code as a success.
-    /// It may be valid in your test for a non-2xx HTTP status code to be returned. A copy
-    /// of your original request is returned with the response, and a mutable copy must be
-    /// included when setting a request as a success.
+    /// Unlike [`set_failure`](./struct.SwanlingUser.html#method.set_failure), a failed
+    /// assertion doesn't fail the request or the task, so assertions can verify the content
+    /// of responses under load without changing the request metrics. To also fail the
+    /// request, call `set_failure` when the assertion fails.
     ///
     /// # Example
     /// ```rust
     /// use swanling::prelude::*;
     ///
-    /// let mut task = task!(get_function);
+    /// let mut task = task!(loadtest_index_page);
     ///
-    /// /// A simple task that makes a GET request.
-    /// async fn get_function(user: &SwanlingUser) -> SwanlingTaskResult {
-    ///     let mut swanling = user.get("/404").await?;
+    /// async fn loadtest_index_page(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let swanling = user.get("/").await?;
     ///
-    ///     if let Ok(response) = &swanling.response {
-    ///         // We expect a 404 here.
-    ///         if response.status() == 404 {
-    ///             return user.set_success(&mut swanling.request);
-    ///         }
+    ///     if let Ok(response) = swanling.response {
+    ///         let text = response.text().await.unwrap_or_default();
+    ///         user.assert(text.contains("Welcome"), "index welcomes the user");
     ///     }
     ///
-    ///     Err(SwanlingTaskError::RequestFailed {
-    ///         raw_request: swanling.request.clone(),
-    ///     })
+    ///     Ok(())
     /// }
-    /// ````
-    pub fn set_success(&self, request: &mut SwanlingRequestMetric) -> SwanlingTaskResult {
-        // Only send update if this was previously not a success.
-        if !request.success {
-            request.success = true;
-            request.update = true;
-            self.send_request_metric_to_parent(request.clone())?;
+    /// ```
+    pub fn assert(&self, condition: bool, label: &str) -> bool {
+        // Print the assertion if debugging a single user.
+        if self.config.debug_single_user {
+            println!(
+                "  assert {}: {}",
+                label,
+                if condition { "passed" } else { "failed" }
+            );
         }
 
-        Ok(())
+        if !self.config.no_metrics && self.channel_to_parent.is_some() {
+            self.metrics_batch
+                .lock()
+                .unwrap()
+                .record_assertion(label, condition);
+        }
+
+        condition
     }
 
     /// Manually mark a request as a failure.
@@ -1758,6 +4559,10 @@ impl SwanlingUser {
     /// or is called on a request that was already an error, only the first error will
     /// be collected.
     ///
+    /// Returns a
+    /// [`ValidationFailed`](./enum.SwanlingTaskError.html#variant.ValidationFailed) error,
+    /// which is also how the failure is categorized in the errors summary table.
+    ///
     /// This also calls
     /// [`log_debug`](https://docs.rs/swanling/*/swanling/swanling/struct.SwanlingUser.html#method.log_debug).
     ///
@@ -1813,7 +4618,7 @@ impl SwanlingUser {
         // Print log to stdout if `-v` is enabled.
         info!("set_failure: {}", tag);
 
-        Err(SwanlingTaskError::RequestFailed {
+        Err(SwanlingTaskError::ValidationFailed {
             raw_request: request.clone(),
         })
     }
@@ -1830,7 +4635,10 @@ impl SwanlingUser {
     /// As the response body can be large, the `--no-debug-body` option (or
     /// [`SwanlingDefault::NoDebugBody`](../enum.SwanlingDefault.html#variant.NoDebugBody) default)
     /// can be set to prevent the debug log from including the response body. When this option
-    /// is enabled, the body will always show up as `null` in the debug log.
+    /// is enabled, the body will always show up as `null` in the debug log. Alternatively,
+    /// `--debug-body-max-bytes` truncates longer bodies, and `--debug-redact` replaces matches
+    /// of a regular expression in headers and bodies with `[REDACTED]`, so a debug log can be
+    /// shared without leaking secrets such as bearer tokens.
     ///
     /// Calls to
     /// [`set_failure`](https://docs.rs/swanling/*/swanling/swanling/struct.SwanlingUser.html#method.set_failure)
@@ -2004,6 +4812,35 @@ impl SwanlingUser {
         Ok(())
     }
 
+    /// Build a new default client for this user, pinned to the user's backend if
+    /// `--sticky-cookie` is enabled, sending requests from the user's `--bind-address` if any,
+    /// and customized by the client builder hook if set.
+    pub(crate) fn build_client(&self) -> Result<Client, reqwest::Error> {
+        let sticky_cookie = match self.sticky_backend.as_ref() {
+            Some(backend) if !self.config.sticky_cookie.is_empty() => {
+                Some((self.config.sticky_cookie.clone(), backend.clone()))
+            }
+            _ => None,
+        };
+        build_default_client(
+            self.jar.clone(),
+            sticky_cookie,
+            self.bind_address,
+            self.resolver.as_ref(),
+            self.hooks.client_builder.as_ref(),
+        )
+    }
+
+    /// Replace the client with a new default client, discarding all connections, cookies
+    /// and cached responses.
+    pub(crate) async fn reset_client(&self) -> Result<(), SwanlingTaskError> {
+        *self.client.lock().await = self.build_client()?;
+        self.jar.write().unwrap().clear();
+        self.cache.lock().unwrap().clear();
+
+        Ok(())
+    }
+
     /// Some websites use multiple domains to serve traffic, redirecting depending on
     /// the user's roll. For this reason, Swanling needs to respect a redirect of the
     /// `base_url` and subsequent paths should be built from the redirect domain.
@@ -2057,22 +4894,536 @@ impl SwanlingUser {
     ///     Ok(())
     /// }
     ///
-    /// async fn task_bar(user: &SwanlingUser) -> SwanlingTaskResult {
-    ///     // Before this task runs, all requests are being made against
-    ///     // http://foo.example.com, after this task runs all subsequent
-    ///     // requests are made against http://bar.example.com/.
-    ///     user.set_base_url("http://bar.example.com/");
-    ///     let _swanling = user.get("/").await?;
+    /// async fn task_bar(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     // Before this task runs, all requests are being made against
+    ///     // http://foo.example.com, after this task runs all subsequent
+    ///     // requests are made against http://bar.example.com/.
+    ///     user.set_base_url("http://bar.example.com/");
+    ///     let _swanling = user.get("/").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn set_base_url(&self, host: &str) -> Result<(), SwanlingTaskError> {
+        let url = Url::parse(host)?;
+        *self.base_url.write().await = url;
+
+        Ok(())
+    }
+
+    /// Returns the value set for `key` with `--set key=value` when starting the load
+    /// test, or `None` if it wasn't set. This makes it possible to pass parameters such as
+    /// search terms, tenant IDs or feature flags into tasks without recompiling the load
+    /// test. If the same key is set more than once, the last value is returned.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(search_task);
+    ///
+    /// /// Search for the term set with `--set search=...`, or "swanling" if not set.
+    /// async fn search_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let term = user.config_value("search").unwrap_or("swanling");
+    ///     let _swanling = user.get(&format!("/search?q={}", term)).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn config_value(&self, key: &str) -> Option<&str> {
+        self.config.get_config_value(key)
+    }
+
+    /// Returns the index of this user, which doesn't change while the load test runs. Users
+    /// are numbered from 0 in the order they're launched, and when running in a fleet with
+    /// `--fleet-index` and `--fleet-size` the index is unique across the fleet.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(login_task);
+    ///
+    /// /// Log in as a different test account for each user.
+    /// async fn login_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let body = format!("username=test{}&password=secret", user.user_id());
+    ///     let _swanling = user.post("/login", &body).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn user_id(&self) -> usize {
+        self.weighted_users_index
+    }
+
+    /// Returns a random version 4 UUID generated for this user, which doesn't change while
+    /// the user runs. Unlike [`user_id`](./struct.SwanlingUser.html#method.user_id), it's
+    /// also unique across separate load tests, so it's a convenient way to keep the data
+    /// each user creates on the server apart.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(register_task);
+    ///
+    /// /// Register an account that no other user shares.
+    /// async fn register_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let body = format!("email={}@example.com", user.uuid());
+    ///     let _swanling = user.post("/register", &body).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+
+    /// Registers a function that deletes a resource this user created, for example with a
+    /// POST request, on the server being load tested.
+    ///
+    /// Cleanup functions run when the user stops, after its
+    /// [`on_stop`](./struct.SwanlingTask.html#method.set_on_stop) tasks and before the
+    /// [`on_stop` session functions](./struct.SwanlingTaskSet.html#method.register_on_stop_session),
+    /// including when the load test is stopped early, for example by a Controller. They run
+    /// one time each, the most recently registered first, so resources are deleted before
+    /// the resources they depend on. Errors are logged, and the remaining functions still run.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(create_item);
+    ///
+    /// /// Create an item, deleting it again when the user stops.
+    /// async fn create_item(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let swanling = user.post("/items", "name=example").await?;
+    ///     let id = swanling.response?.text().await?;
+    ///     user.register_cleanup(move |user| {
+    ///         Box::pin(async move {
+    ///             let _swanling = user.delete(&format!("/items/{}", id)).await?;
+    ///             Ok(())
+    ///         })
+    ///     });
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn register_cleanup<F>(&self, function: F)
+    where
+        F: for<'r> FnOnce(
+                &'r SwanlingUser,
+            )
+                -> Pin<Box<dyn Future<Output = SwanlingTaskResult> + Send + 'r>>
+            + Send
+            + 'static,
+    {
+        self.cleanups.0.lock().unwrap().push(Box::new(function));
+    }
+
+    /// Returns a copy of `template` with `{user_id}` replaced by the
+    /// [`user_id`](./struct.SwanlingUser.html#method.user_id) of this user, and `{uuid}` by
+    /// its [`uuid`](./struct.SwanlingUser.html#method.uuid). Paths are rendered automatically,
+    /// use this to render request bodies or headers.
+    ///
+    /// Unnamed requests are named with their path before it's rendered, such as
+    /// `/api/users/{uuid}/cart`, so the requests of all users are tracked together.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(cart_task);
+    ///
+    /// /// Add an item to the cart of this user.
+    /// async fn cart_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let body = user.render_template(r#"{"owner": "{uuid}", "item": 42}"#);
+    ///     let _swanling = user.post_named("/api/users/{uuid}/cart", "cart", &body).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn render_template(&self, template: &str) -> String {
+        template
+            .replace("{user_id}", &self.weighted_users_index.to_string())
+            .replace("{uuid}", &self.uuid)
+    }
+
+    /// Returns the random number generator of this user, to pass to the generators in
+    /// [`util`](../util/index.html) or to use with the [`rand`](https://docs.rs/rand/) crate.
+    ///
+    /// With `--seed`, each user's generator is seeded from the seed and its
+    /// [`user_id`](./struct.SwanlingUser.html#method.user_id) when the user starts, so every
+    /// load test run with the same seed generates the same values. Otherwise it's seeded
+    /// from the operating system.
+    ///
+    /// The generator is locked until the returned guard is dropped, so don't hold on to it
+    /// across an `.await`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    /// use rand::Rng;
+    ///
+    /// let mut task = task!(product_task);
+    ///
+    /// /// View a random product.
+    /// async fn product_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let product: usize = user.rng().gen_range(1..=100);
+    ///     let _swanling = user.get(&format!("/product/{}", product)).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn rng(&self) -> std::sync::MutexGuard<'_, StdRng> {
+        self.rng.lock().unwrap()
+    }
+
+    /// Seed the random number generator for this user's `user_id`, called when the user
+    /// starts.
+    pub(crate) fn reset_rng(&self) {
+        *self.rng.lock().unwrap() = user_rng(self.config.seed, self.weighted_users_index);
+    }
+
+    /// Returns a copy of `template` with each `{{placeholder}}` replaced by a field of
+    /// `context`, or by a built-in such as `{{uuid}}`, `{{seq}}`, `{{random_string 8}}`,
+    /// `{{random_number 1 100}}` or `{{timestamp}}`. All built-ins are listed in the
+    /// [`template`](../template/index.html) module.
+    ///
+    /// Returns [`SwanlingTaskError::InvalidTemplate`](./enum.SwanlingTaskError.html#variant.InvalidTemplate)
+    /// if the template contains an unknown placeholder, or a built-in with invalid arguments.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(order_task);
+    ///
+    /// /// Place a uniquely identified order.
+    /// async fn order_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let context = SwanlingTemplateContext::new().field("sku", 1234);
+    ///     let body = user.render(
+    ///         r#"{"id": "order-{{uuid}}-{{seq}}", "sku": {{sku}}, "note": "{{random_string 8}}"}"#,
+    ///         &context,
+    ///     )?;
+    ///     let _swanling = user.post("/api/orders", &body).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn render(
+        &self,
+        template: &str,
+        context: &SwanlingTemplateContext,
+    ) -> Result<String, SwanlingTaskError> {
+        crate::template::render(template, context, self).map_err(|detail| {
+            SwanlingTaskError::InvalidTemplate {
+                template: template.to_string(),
+                detail,
+            }
+        })
+    }
+
+    /// Stores data in the [`test_start`](../struct.SwanlingAttack.html#method.test_start)
+    /// task to share it with all users, for example product IDs seeded during setup or an
+    /// admin authentication token. All users then get a read-only copy with
+    /// [`get_shared_data`](./struct.SwanlingUser.html#method.get_shared_data), as does the
+    /// [`test_stop`](../struct.SwanlingAttack.html#method.test_stop) task. This avoids having
+    /// to store the data in globals.
+    ///
+    /// Calling this from any other task only changes the data seen by the user running the
+    /// task. When running in a distributed Regatta, shared data is not sent to Workers.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     SwanlingAttack::initialize()?
+    ///         .test_start(task!(setup));
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn setup(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let product_ids: Vec<usize> = vec![1, 2, 3];
+    ///     user.set_shared_data(product_ids);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_shared_data<T: Any + Send + Sync>(&self, data: T) {
+        *self.shared_data.data.write().unwrap() = Some(Arc::new(data));
+    }
+
+    /// Returns the data stored by the
+    /// [`test_start`](../struct.SwanlingAttack.html#method.test_start) task with
+    /// [`set_shared_data`](./struct.SwanlingUser.html#method.set_shared_data), or `None` if no
+    /// data was stored or it isn't of type `T`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(view_product);
+    ///
+    /// /// Load the first product seeded during test_start.
+    /// async fn view_product(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     if let Some(product_ids) = user.get_shared_data::<Vec<usize>>() {
+    ///         let _swanling = user.get(&format!("/product/{}", product_ids[0])).await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn get_shared_data<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let data = self.shared_data.data.read().unwrap().clone()?;
+        data.downcast::<T>().ok()
+    }
+
+    /// Returns the size-bounded cache shared by all users, including the
+    /// [`test_start`](../struct.SwanlingAttack.html#method.test_start) and
+    /// [`test_stop`](../struct.SwanlingAttack.html#method.test_stop) tasks. Unlike
+    /// [`set_shared_data`](./struct.SwanlingUser.html#method.set_shared_data), any task can
+    /// add to the cache, so expensive responses that don't change, such as a list of product
+    /// IDs, can be fetched once and reused by all users.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(view_product);
+    ///
+    /// /// Fetch the product IDs once, then view the first product.
+    /// async fn view_product(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let product_ids = user
+    ///         .shared_cache()
+    ///         .get_or_try_insert_with("product_ids", || async {
+    ///             let swanling = user.get("/products").await?;
+    ///             let product_ids: Vec<usize> = swanling.json_as(user).await?;
+    ///             Ok::<_, SwanlingTaskError>(product_ids)
+    ///         })
+    ///         .await?;
+    ///     if let Some(product_id) = product_ids.first() {
+    ///         let _swanling = user.get(&format!("/product/{}", product_id)).await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn shared_cache(&self) -> &SwanlingSharedCache {
+        &self.shared_cache
+    }
+
+    /// Returns the counters, barriers and flags shared by all users of the load test, to
+    /// coordinate users without unsafe statics. See the
+    /// [`coordination`](../coordination/index.html) module for details.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(admin_task);
+    ///
+    /// /// Only one user runs the admin task.
+    /// async fn admin_task(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     if user.coordination().once("admin") {
+    ///         let _swanling = user.get("/admin").await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn coordination(&self) -> &SwanlingCoordination {
+        &self.coordination
+    }
+
+    /// Returns the feeder registered with
+    /// [`SwanlingAttack::register_feeder`](../struct.SwanlingAttack.html#method.register_feeder)
+    /// with this name, to check out a row of test data no other user is using. See the
+    /// [`feeder`](../feeder/index.html) module for details.
+    ///
+    /// Returns an error if no feeder with this name was registered.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(log_in);
+    ///
+    /// /// Log in with an account no other user is using.
+    /// async fn log_in(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let account = user.feeder("accounts")?.checkout().await?;
+    ///     let _swanling = user.post("/login", account.row()).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn feeder(&self, name: &str) -> Result<SwanlingFeeder, SwanlingTaskError> {
+        let fleet = self.config.fleet_index.zip(self.config.fleet_size);
+        self.feeders
+            .get(name, fleet)
+            .ok_or_else(|| SwanlingTaskError::FeederFailed {
+                name: name.to_string(),
+                detail: "no feeder registered with SwanlingAttack::register_feeder".to_string(),
+            })
+    }
+
+    /// Returns the pool of database connections registered with
+    /// [`SwanlingAttack::set_sql_pool`](../struct.SwanlingAttack.html#method.set_sql_pool),
+    /// to seed data or check the state of the target. See the [`sql`](../sql/index.html)
+    /// module for details. Only available with the `sql` feature.
+    ///
+    /// Returns an error if no pool was registered.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(place_order);
+    ///
+    /// /// Place an order, then confirm it was stored.
+    /// async fn place_order(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.post("/order", "product=42").await?;
+    ///     let orders: Option<u64> = user
+    ///         .sql()?
+    ///         .query_scalar("count orders", "SELECT COUNT(*) FROM orders")
+    ///         .await?;
+    ///     if orders.unwrap_or(0) == 0 {
+    ///         return Err(SwanlingTaskError::SqlFailed {
+    ///             name: "count orders".to_string(),
+    ///             detail: "the order wasn't stored".to_string(),
+    ///         });
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "sql")]
+    pub fn sql(&self) -> Result<&SwanlingSqlPool, SwanlingTaskError> {
+        self.sql
+            .as_ref()
+            .ok_or_else(|| SwanlingTaskError::SqlFailed {
+                name: String::new(),
+                detail: "no pool registered with SwanlingAttack::set_sql_pool".to_string(),
+            })
+    }
+
+    /// Publish a message to a message queue with the producer registered with
+    /// [`SwanlingAttack::set_producer`](../struct.SwanlingAttack.html#method.set_producer).
+    /// The `destination` is a Kafka topic or an AMQP exchange, and the optional `key` is a
+    /// partition or routing key. See the [`queue`](../queue/index.html) module for details.
+    /// Only available with the `queue` feature.
+    ///
+    /// The message is recorded like a request, with the `PUBLISH` method and named after its
+    /// destination. If it fails, or no producer was registered, the error is recorded and
+    /// returned.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut task = task!(place_order);
+    ///
+    /// /// Place an order over HTTP, then publish a shipment event for it.
+    /// async fn place_order(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.post("/order", "product=42").await?;
+    ///     user.publish("shipments", Some("42"), br#"{"product":42}"#).await?;
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub async fn set_base_url(&self, host: &str) -> Result<(), SwanlingTaskError> {
-        let url = Url::parse(host)?;
-        *self.base_url.write().await = url;
+    #[cfg(feature = "queue")]
+    pub async fn publish(
+        &self,
+        destination: &str,
+        key: Option<&str>,
+        payload: &[u8],
+    ) -> SwanlingTaskResult {
+        let started = Instant::now();
+        let mut request_metric = SwanlingRequestMetric::new(
+            SwanlingMethod::Publish,
+            destination,
+            destination,
+            self.started.elapsed().as_millis(),
+            self.weighted_users_index,
+        );
 
-        Ok(())
+        let result = match self.producer.as_ref() {
+            Some(producer) => producer.0.publish(destination, key, payload).await,
+            None => Err("no producer registered with SwanlingAttack::set_producer".into()),
+        };
+        request_metric.set_response_time(started.elapsed().as_millis());
+        if let Err(e) = &result {
+            warn!("{:?}: {}", destination, e);
+            request_metric.success = false;
+            request_metric.error = e.to_string();
+        }
+
+        if !self.config.no_metrics {
+            self.send_request_metric_to_parent(request_metric.clone())?;
+        }
+
+        // If debugging a single user, print each message published.
+        if self.config.debug_single_user {
+            match &result {
+                Ok(_) => println!("  {} {} -> ok", request_metric.method, destination),
+                Err(e) => println!("  {} {} -> {}", request_metric.method, destination, e),
+            }
+        }
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(_) => Err(SwanlingTaskError::from_failed_request(request_metric)),
+        }
+    }
+}
+
+// Build the random number generator of a user, seeded from `--seed` and the user's index if
+// set, or from the operating system.
+fn user_rng(seed: Option<u64>, weighted_users_index: usize) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(weighted_users_index as u64)),
+        None => StdRng::from_entropy(),
+    }
+}
+
+// Generate a random version 4 UUID, formatted as lowercase hex.
+pub(crate) fn new_uuid() -> String {
+    let mut bytes: [u8; 16] = rand::random();
+    // Set the version to 4, and the variant to RFC 4122.
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+// Parse a base URL set with `set_base_url()`, which must include a host.
+fn parse_base_url(base_url: &str, function: &str) -> Result<Url, SwanlingError> {
+    let parsed = Url::parse(base_url).map_err(|parse_error| SwanlingError::InvalidHost {
+        host: base_url.to_string(),
+        detail: format!(
+            "There was a failure parsing the base URL set with {}.",
+            function
+        ),
+        parse_error,
+    })?;
+    if !parsed.has_host() {
+        return Err(SwanlingError::InvalidHost {
+            host: base_url.to_string(),
+            detail: format!("The base URL set with {} must include a host.", function),
+            parse_error: url::ParseError::EmptyHost,
+        });
     }
+
+    Ok(parsed)
 }
 
 /// A helper to determine which host should be prepended to relative load test
@@ -2136,6 +5487,31 @@ pub type SwanlingTaskFunction = Arc<
         + Sync,
 >;
 
+/// A predicate deciding whether a task is skipped, registered with
+/// [`SwanlingTask::set_skip_if`](./struct.SwanlingTask.html#method.set_skip_if).
+pub type SwanlingTaskPredicate = Arc<dyn Fn(&SwanlingUser) -> bool + Send + Sync>;
+
+/// The function type of a session function, registered with
+/// [`SwanlingTaskSet::register_on_start_session`](./struct.SwanlingTaskSet.html#method.register_on_start_session)
+/// or
+/// [`SwanlingTaskSet::register_on_stop_session`](./struct.SwanlingTaskSet.html#method.register_on_stop_session).
+pub type SwanlingSessionFunction = Arc<
+    dyn for<'r> Fn(
+            &'r mut SwanlingUser,
+        ) -> Pin<Box<dyn Future<Output = SwanlingTaskResult> + Send + 'r>>
+        + Send
+        + Sync,
+>;
+
+/// The function type of a cleanup function, registered with
+/// [`SwanlingUser::register_cleanup`](./struct.SwanlingUser.html#method.register_cleanup).
+pub type SwanlingCleanupFunction = Box<
+    dyn for<'r> FnOnce(
+            &'r SwanlingUser,
+        ) -> Pin<Box<dyn Future<Output = SwanlingTaskResult> + Send + 'r>>
+        + Send,
+>;
+
 /// An individual task within a [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html).
 #[derive(Clone)]
 pub struct SwanlingTask {
@@ -2153,6 +5529,20 @@ pub struct SwanlingTask {
     pub on_start: bool,
     /// A flag indicating that this task runs when the user stops.
     pub on_stop: bool,
+    /// A flag indicating that only one task with the same sequence runs, chosen by weight.
+    pub sequence_choice: bool,
+    /// An optional frequency this task runs at per user, independently of the weighted tasks.
+    pub schedule: Option<std::time::Duration>,
+    /// An optional number of users that must reach this task before any of them runs it.
+    pub rendezvous: Option<usize>,
+    /// A flag indicating that requests made by this task jump the throttle queue.
+    pub throttle_priority: bool,
+    /// An optional name of a task that must succeed earlier in the same iteration for this task to run.
+    pub depends_on: Option<String>,
+    /// An optional predicate, this task is skipped when it returns `true`.
+    pub skip_if: Option<SwanlingTaskPredicate>,
+    /// An optional base URL this task always runs against, even if `--host` is set.
+    pub base_url: Option<Url>,
     /// A required function that is executed each time this task runs.
     pub function: SwanlingTaskFunction,
 }
@@ -2166,6 +5556,13 @@ impl SwanlingTask {
             sequence: 0,
             on_start: false,
             on_stop: false,
+            sequence_choice: false,
+            schedule: None,
+            rendezvous: None,
+            throttle_priority: false,
+            depends_on: None,
+            skip_if: None,
+            base_url: None,
             function,
         }
     }
@@ -2375,6 +5772,283 @@ impl SwanlingTask {
         self.sequence = sequence;
         self
     }
+
+    /// Sets the sequence of a task like [`set_sequence`](./struct.SwanlingTask.html#method.set_sequence),
+    /// but instead of running all tasks with the same sequence value, each time through the
+    /// tasks only one of them is randomly chosen to run. Tasks with a higher
+    /// [`weight`](./struct.SwanlingTask.html#method.set_weight) are more likely to be chosen.
+    /// This makes it possible to build flows such as "after logging in, 70% of the time
+    /// browse and 30% of the time search" within one task set.
+    ///
+    /// Only one task needs to be set with `set_sequence_choice` for all tasks with the
+    /// same sequence value to be chosen between. A sequence value of 0 disables sequencing.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let runs_first = task!(login).set_sequence(1);
+    ///     let browse_70_percent = task!(browse).set_sequence_choice(2).set_weight(7)?;
+    ///     let search_30_percent = task!(search).set_sequence_choice(2).set_weight(3)?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn login(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.post("/login", "username=foo&password=bar").await?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn browse(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/products").await?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn search(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/search?q=swanling").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_sequence_choice(mut self, sequence: usize) -> Self {
+        trace!(
+            "{} [{}] set_sequence_choice: {}",
+            self.name,
+            self.tasks_index,
+            sequence
+        );
+        self = self.set_sequence(sequence);
+        self.sequence_choice = sequence > 0;
+        self
+    }
+
+    /// Runs a task at a fixed frequency for each user, instead of as part of the weighted
+    /// rotation of tasks. For example, a heartbeat can be sent every 30 seconds while the
+    /// user keeps running its other tasks. Each user runs the task for the first time one
+    /// `every` after it finishes its [`on_start`](./struct.SwanlingTask.html#method.set_on_start)
+    /// tasks, and stops running it before its
+    /// [`on_stop`](./struct.SwanlingTask.html#method.set_on_stop) tasks.
+    ///
+    /// The frequency must be greater than zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let heartbeat = task!(heartbeat_function).set_schedule(Duration::from_secs(30))?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn heartbeat_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/heartbeat").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_schedule(mut self, every: std::time::Duration) -> Result<Self, SwanlingError> {
+        trace!(
+            "{} [{}] set_schedule: {:?}",
+            self.name,
+            self.tasks_index,
+            every
+        );
+        if every.as_nanos() == 0 {
+            return Err(SwanlingError::InvalidOption {
+                option: "SwanlingTask.set_schedule".to_string(),
+                value: format!("{:?}", every),
+                detail: "Schedule must be greater than zero.".to_string(),
+            });
+        }
+        self.schedule = Some(every);
+
+        Ok(self)
+    }
+
+    /// Waits until `users` users reach this task, then all of them run it at the same time.
+    /// This produces controlled bursts of requests, for example to test a flash sale or a
+    /// cache stampede. Users reaching the task afterwards wait for the next `users` users.
+    ///
+    /// The task set must run at least `users` users, or the load test fails to start.
+    /// Users that stop, for example after too many consecutive task failures, no longer
+    /// reach the rendezvous, so the remaining users may wait until the load test stops.
+    /// Users stop waiting when the load test stops, and scheduled tasks don't wait.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     // Users buy a ticket in bursts of 100.
+    ///     let buy_ticket = task!(buy_ticket).set_rendezvous(100)?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn buy_ticket(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.post("/ticket", "event=1").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_rendezvous(mut self, users: usize) -> Result<Self, SwanlingError> {
+        trace!(
+            "{} [{}] set_rendezvous: {}",
+            self.name,
+            self.tasks_index,
+            users
+        );
+        if users == 0 {
+            return Err(SwanlingError::InvalidOption {
+                option: "SwanlingTask.set_rendezvous".to_string(),
+                value: users.to_string(),
+                detail: "Rendezvous must be at least 1 user.".to_string(),
+            });
+        }
+        self.rendezvous = Some(users);
+
+        Ok(self)
+    }
+
+    /// Set an optional flag indicating that requests made by this task jump the queue of
+    /// requests waiting for the throttle, enabled with `--throttle-requests`. This keeps
+    /// critical tasks such as heartbeats or refreshing a login token on time, even when the
+    /// throttle is holding back most requests. High priority requests still count towards
+    /// the maximum number of requests per second.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let heartbeat = task!(heartbeat_function)
+    ///         .set_schedule(Duration::from_secs(30))?
+    ///         .set_throttle_priority();
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn heartbeat_function(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/heartbeat").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_throttle_priority(mut self) -> Self {
+        trace!(
+            "{} [{}] set_throttle_priority task",
+            self.name,
+            self.tasks_index
+        );
+        self.throttle_priority = true;
+        self
+    }
+
+    /// Only runs a task if the task named `name` ran earlier in the same iteration through the
+    /// tasks, and succeeded. Otherwise the task is skipped, so a broken flow stops early
+    /// instead of generating cascading failures. The results of
+    /// [`on_start`](./struct.SwanlingTask.html#method.set_on_start) tasks are kept for all
+    /// iterations, so tasks can depend on logging in once when the user starts.
+    ///
+    /// The named task must be registered in the same
+    /// [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html), or the load test fails to start.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// let mut login_task = task!(login).set_name("login").set_on_start();
+    /// let mut account_task = task!(account).set_depends_on("login");
+    ///
+    /// async fn login(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.post("/login", "username=foo&password=bar").await?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn account(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/account").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_depends_on(mut self, name: &str) -> Self {
+        trace!(
+            "{} [{}] set_depends_on: {}",
+            self.name,
+            self.tasks_index,
+            name
+        );
+        self.depends_on = Some(name.to_string());
+        self
+    }
+
+    /// Skips a task each time the predicate returns `true`. The predicate is invoked with the
+    /// [`SwanlingUser`](./struct.SwanlingUser.html) before the task would run, so it can check
+    /// state such as values passed with
+    /// [`config_value`](./struct.SwanlingUser.html#method.config_value) or
+    /// [`get_shared_data`](./struct.SwanlingUser.html#method.get_shared_data).
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// // Only search if enabled with `--set search=on`.
+    /// let mut search_task =
+    ///     task!(search).set_skip_if(|user| user.config_value("search") != Some("on"));
+    ///
+    /// async fn search(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/search?q=swanling").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_skip_if<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&SwanlingUser) -> bool + Send + Sync + 'static,
+    {
+        trace!("{} [{}] set_skip_if", self.name, self.tasks_index);
+        self.skip_if = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Set a base URL the task always runs against, taking precedence over the base URL of
+    /// its [`SwanlingTaskSet`](./struct.SwanlingTaskSet.html) and the `--host` option.
+    /// Relative paths requested while the task runs are prepended with this base URL.
+    ///
+    /// # Example
+    /// ```rust
+    /// use swanling::prelude::*;
+    ///
+    /// fn main() -> Result<(), SwanlingError> {
+    ///     let mut api_task = task!(api_status).set_base_url("https://api.example.com")?;
+    ///
+    ///     Ok(())
+    /// }
+    ///
+    /// async fn api_status(user: &SwanlingUser) -> SwanlingTaskResult {
+    ///     let _swanling = user.get("/status").await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_base_url(mut self, base_url: &str) -> Result<Self, SwanlingError> {
+        trace!(
+            "{} [{}] set_base_url: {}",
+            self.name,
+            self.tasks_index,
+            base_url
+        );
+        self.base_url = Some(parse_base_url(base_url, "SwanlingTask.set_base_url()")?);
+
+        Ok(self)
+    }
 }
 impl Hash for SwanlingTask {
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -2382,8 +6056,12 @@ impl Hash for SwanlingTask {
         self.name.hash(state);
         self.weight.hash(state);
         self.sequence.hash(state);
+        self.sequence_choice.hash(state);
         self.on_start.hash(state);
         self.on_stop.hash(state);
+        self.schedule.hash(state);
+        self.depends_on.hash(state);
+        self.base_url.hash(state);
     }
 }
 
@@ -2393,7 +6071,7 @@ mod tests {
 
     use gumdrop::Options;
     use httpmock::{
-        Method::{GET, POST},
+        Method::{GET, OPTIONS, POST, PUT},
         MockServer,
     };
 
@@ -2428,6 +6106,7 @@ mod tests {
         assert_eq!(task_set.min_wait, 0);
         assert_eq!(task_set.max_wait, 0);
         assert!(task_set.host.is_none());
+        assert_eq!(task_set.client_mode, SwanlingClientMode::PerUser);
         assert_eq!(task_set.tasks.len(), 0);
         assert_eq!(task_set.weighted_tasks.len(), 0);
         assert_eq!(task_set.weighted_on_start_tasks.len(), 0);
@@ -2505,6 +6184,46 @@ mod tests {
         task_set = task_set.set_wait_time(3, 9).unwrap();
         assert_eq!(task_set.min_wait, 3);
         assert_eq!(task_set.max_wait, 9);
+
+        // Client mode only affects client mode field.
+        task_set = task_set.set_client_mode(SwanlingClientMode::PerIteration);
+        assert_eq!(task_set.client_mode, SwanlingClientMode::PerIteration);
+        assert_eq!(task_set.min_wait, 3);
+        assert_eq!(task_set.max_wait, 9);
+        assert_eq!(task_set.host, Some("https://bar.example.com/".to_string()));
+        assert_eq!(task_set.weight, 5);
+        assert_eq!(task_set.tasks.len(), 3);
+
+        // Client mode can be changed.
+        task_set = task_set.set_client_mode(SwanlingClientMode::Shared);
+        assert_eq!(task_set.client_mode, SwanlingClientMode::Shared);
+
+        // Network conditions can be simulated.
+        assert_eq!(task_set.bandwidth, None);
+        assert_eq!(task_set.added_latency, None);
+        task_set = task_set
+            .set_bandwidth(1_000_000)
+            .unwrap()
+            .set_added_latency(std::time::Duration::from_millis(80));
+        assert_eq!(task_set.bandwidth, Some(1_000_000));
+        assert_eq!(
+            task_set.added_latency,
+            Some(std::time::Duration::from_millis(80))
+        );
+
+        // Bandwidth can not be set to 0.
+        assert!(task_set.clone().set_bandwidth(0).is_err());
+
+        // Redirect policy can be changed.
+        assert!(matches!(
+            task_set.redirect_policy,
+            SwanlingRedirectPolicy::Limited(10)
+        ));
+        task_set = task_set.set_redirect_policy(SwanlingRedirectPolicy::None);
+        assert!(matches!(
+            task_set.redirect_policy,
+            SwanlingRedirectPolicy::None
+        ));
     }
 
     #[test]
@@ -2585,6 +6304,87 @@ mod tests {
         // Sequence field can be changed multiple times.
         task = task.set_sequence(8);
         assert_eq!(task.sequence, 8);
+
+        // Setting a sequence choice also sets the sequence.
+        task = task.set_sequence_choice(6);
+        assert!(task.sequence_choice);
+        assert_eq!(task.sequence, 6);
+
+        // Disabling the sequence also disables the sequence choice.
+        task = task.set_sequence_choice(0);
+        assert!(!task.sequence_choice);
+        assert_eq!(task.sequence, 0);
+        task = task.set_sequence(8);
+
+        // Setting schedule doesn't change anything else.
+        task = task
+            .set_schedule(std::time::Duration::from_secs(30))
+            .unwrap();
+        assert_eq!(task.schedule, Some(std::time::Duration::from_secs(30)));
+        assert_eq!(task.sequence, 8);
+        assert_eq!(task.weight, 3);
+        assert!(task.on_stop);
+        assert!(task.on_start);
+        assert_eq!(task.name, "bar".to_string());
+
+        // Schedule must be greater than zero.
+        assert!(task
+            .set_schedule(std::time::Duration::from_secs(0))
+            .is_err());
+    }
+
+    #[test]
+    fn task_error_category() {
+        let category = |status_code: u16, update: bool, error: &str| {
+            let mut raw_request =
+                SwanlingRequestMetric::new(SwanlingMethod::Get, "/", "http://example.com/", 0, 0);
+            raw_request.status_code = status_code;
+            raw_request.update = update;
+            raw_request.error = error.to_string();
+            SwanlingTaskError::from_failed_request(raw_request)
+                .category()
+                .to_string()
+        };
+
+        // Requests updated with set_failure() failed validation.
+        assert_eq!(category(200, true, "string missing"), "ValidationFailed");
+
+        // Requests with a response are categorized by status code.
+        assert_eq!(
+            category(503, false, "503 Service Unavailable: /"),
+            "Http5xx"
+        );
+        assert_eq!(category(404, false, "404 Not Found: /"), "Http4xx");
+
+        // Requests without a response are categorized by their error.
+        assert_eq!(category(0, false, "operation timed out"), "Timeout");
+        assert_eq!(
+            category(
+                0,
+                false,
+                "error trying to connect: dns error: failed to lookup address information"
+            ),
+            "Dns"
+        );
+        assert_eq!(
+            category(
+                0,
+                false,
+                "error trying to connect: tcp connect error: Connection refused (os error 111)"
+            ),
+            "ConnectionRefused"
+        );
+        assert_eq!(
+            category(
+                0,
+                false,
+                "error trying to connect: invalid peer certificate"
+            ),
+            "Tls"
+        );
+        assert_eq!(category(0, false, INJECT_ABORT_ERROR), "Aborted");
+        assert_eq!(category(0, false, "connection closed"), "Custom");
+        assert_eq!(category(302, false, "302 Found: /"), "Custom");
     }
 
     #[tokio::test]
@@ -2682,6 +6482,13 @@ mod tests {
         assert_eq!(built_request.method(), &Method::HEAD);
         assert_eq!(built_request.url().as_str(), server.url("/path/to/head"));
         assert_eq!(built_request.timeout(), None);
+
+        // Create an OPTIONS request.
+        swanling_request = user2.swanling_options("/path/to/options").await.unwrap();
+        built_request = swanling_request.build().unwrap();
+        assert_eq!(built_request.method(), &Method::OPTIONS);
+        assert_eq!(built_request.url().as_str(), server.url("/path/to/options"));
+        assert_eq!(built_request.timeout(), None);
     }
 
     #[tokio::test]
@@ -2758,5 +6565,41 @@ mod tests {
         assert!(!swanling.request.update);
         assert_eq!(swanling.request.status_code, 200);
         comment.assert_hits(1);
+
+        // Set up a mock http server endpoint expecting a JSON body.
+        const API_PATH: &str = "/api";
+        let api = server.mock(|when, then| {
+            when.method(PUT)
+                .path(API_PATH)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!({"name": "foo"}));
+            then.status(200);
+        });
+
+        // Make a PUT request with a JSON body and confirm we get a 200 OK response.
+        let swanling = user
+            .put_json(API_PATH, &serde_json::json!({"name": "foo"}))
+            .await
+            .expect("put_json returned unexpected error");
+        assert_eq!(swanling.response.unwrap().status(), 200);
+        assert_eq!(swanling.request.method, SwanlingMethod::Put);
+        assert_eq!(swanling.request.name, API_PATH);
+        api.assert_hits(1);
+
+        // Set up a mock http server endpoint.
+        let options = server.mock(|when, then| {
+            when.method(OPTIONS).path(API_PATH);
+            then.status(204);
+        });
+
+        // Make a named OPTIONS request and confirm we get a 204 response.
+        let swanling = user
+            .options_named(API_PATH, "api options")
+            .await
+            .expect("options_named returned unexpected error");
+        assert_eq!(swanling.response.unwrap().status(), 204);
+        assert_eq!(swanling.request.method, SwanlingMethod::Options);
+        assert_eq!(swanling.request.name, "api options");
+        options.assert_hits(1);
     }
 }
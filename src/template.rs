@@ -0,0 +1,248 @@
+//! Render request bodies, paths and headers from templates.
+//!
+//! [`SwanlingUser::render`](../swanling/struct.SwanlingUser.html#method.render) replaces
+//! each `{{placeholder}}` in a template, so tasks don't have to format every string by
+//! hand. A placeholder is the name of a field in the
+//! [`SwanlingTemplateContext`](./struct.SwanlingTemplateContext.html) passed in, for example
+//! a row of test data, or one of the following built-ins:
+//!
+//!  - `{{user_id}}`: the index of the user, see
+//!    [`user_id`](../swanling/struct.SwanlingUser.html#method.user_id).
+//!  - `{{uuid}}`: the UUID of the user, see
+//!    [`uuid`](../swanling/struct.SwanlingUser.html#method.uuid).
+//!  - `{{seq}}`: a sequence number counting up from 0 each time the user renders a template.
+//!  - `{{random_uuid}}`: a new random version 4 UUID.
+//!  - `{{random_string LENGTH}}`: LENGTH random alphanumeric characters.
+//!  - `{{random_number MIN MAX}}`: a random integer from MIN to MAX, inclusive.
+//!  - `{{timestamp}}`: the current Unix time in seconds.
+//!  - `{{timestamp_millis}}`: the current Unix time in milliseconds.
+//!  - `{{datetime}}`: the current time in RFC 3339 format, for example
+//!    `2021-03-04T05:06:07Z`.
+//!
+//! Random strings and numbers are generated with the
+//! [`rng`](../swanling/struct.SwanlingUser.html#method.rng) of the user, so they're repeatable
+//! with `--seed`. A field takes precedence over a built-in with the same name. Rendering a template with
+//! an unknown placeholder or invalid arguments fails with
+//! [`SwanlingTaskError::InvalidTemplate`](../swanling/enum.SwanlingTaskError.html#variant.InvalidTemplate).
+//!
+//! ```rust
+//! use swanling::prelude::*;
+//!
+//! let mut task = task!(order_function);
+//!
+//! /// Place an order for a random quantity of a product.
+//! async fn order_function(user: &SwanlingUser) -> SwanlingTaskResult {
+//!     let context = SwanlingTemplateContext::new().field("sku", 1234);
+//!     let body = user.render(
+//!         r#"{"id": "order-{{uuid}}-{{seq}}", "sku": {{sku}}, "quantity": {{random_number 1 5}}}"#,
+//!         &context,
+//!     )?;
+//!     let _swanling = user.post("/api/orders", &body).await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use chrono::{SecondsFormat, Utc};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use std::collections::HashMap;
+use std::iter::FromIterator;
+use std::sync::atomic::Ordering;
+
+use crate::swanling::{new_uuid, SwanlingUser};
+
+/// The fields available to a template rendered with
+/// [`SwanlingUser::render`](../swanling/struct.SwanlingUser.html#method.render).
+///
+/// Fields can be added one at a time, or collected from any iterator of name and value
+/// pairs, for example a row of test data read from a file.
+///
+/// # Example
+/// ```rust
+/// use swanling::prelude::*;
+/// use std::collections::HashMap;
+///
+/// let context = SwanlingTemplateContext::new()
+///     .field("sku", 1234)
+///     .field("coupon", "SPRING");
+///
+/// let mut row = HashMap::new();
+/// row.insert("email", "user@example.com");
+/// let context: SwanlingTemplateContext = row.into_iter().collect();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SwanlingTemplateContext {
+    /// The value of each field, by name.
+    fields: HashMap<String, String>,
+}
+impl SwanlingTemplateContext {
+    /// Create an empty context, only built-in placeholders can be rendered.
+    pub fn new() -> Self {
+        SwanlingTemplateContext::default()
+    }
+
+    /// Add a field, replacing any field with the same name.
+    pub fn field<T: ToString>(mut self, name: &str, value: T) -> Self {
+        self.fields.insert(name.to_string(), value.to_string());
+        self
+    }
+}
+impl<K: ToString, V: ToString> FromIterator<(K, V)> for SwanlingTemplateContext {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        SwanlingTemplateContext {
+            fields: iter
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+}
+
+// Parse the argument of a built-in placeholder.
+fn parse_argument<T: std::str::FromStr>(
+    placeholder: &str,
+    argument: Option<&&str>,
+) -> Result<T, String> {
+    argument
+        .and_then(|a| a.parse().ok())
+        .ok_or_else(|| format!("invalid arguments in {{{{{}}}}}", placeholder))
+}
+
+/// Render `template` for `user`, replacing each placeholder with a field of `context` or a
+/// built-in. Returns why the template is invalid if it can't be rendered.
+pub(crate) fn render(
+    template: &str,
+    context: &SwanlingTemplateContext,
+    user: &SwanlingUser,
+) -> Result<String, String> {
+    let mut rendered = String::with_capacity(template.len());
+    // The sequence number is only incremented once per template, so it can be repeated.
+    let mut seq = None;
+    let mut remaining = template;
+    while let Some(start) = remaining.find("{{") {
+        rendered.push_str(&remaining[..start]);
+        let end = match remaining[start..].find("}}") {
+            Some(end) => start + end,
+            None => return Err("placeholder not closed with }}".to_string()),
+        };
+        let placeholder = remaining[start + 2..end].trim();
+        let words: Vec<&str> = placeholder.split_whitespace().collect();
+        let name = words.first().copied().unwrap_or_default();
+
+        if let Some(value) = context.fields.get(placeholder) {
+            rendered.push_str(value);
+        } else {
+            let value = match (name, words.len()) {
+                ("user_id", 1) => user.weighted_users_index.to_string(),
+                ("uuid", 1) => user.uuid.clone(),
+                ("seq", 1) => seq
+                    .get_or_insert_with(|| user.template_seq.fetch_add(1, Ordering::SeqCst))
+                    .to_string(),
+                ("random_uuid", 1) => new_uuid(),
+                ("random_string", 2) => {
+                    let length: usize = parse_argument(placeholder, words.get(1))?;
+                    let mut rng = user.rng();
+                    (0..length)
+                        .map(|_| char::from(rng.sample(Alphanumeric)))
+                        .collect()
+                }
+                ("random_number", 3) => {
+                    let min: i64 = parse_argument(placeholder, words.get(1))?;
+                    let max: i64 = parse_argument(placeholder, words.get(2))?;
+                    if min > max {
+                        return Err(format!(
+                            "MIN is greater than MAX in {{{{{}}}}}",
+                            placeholder
+                        ));
+                    }
+                    user.rng().gen_range(min..=max).to_string()
+                }
+                ("timestamp", 1) => Utc::now().timestamp().to_string(),
+                ("timestamp_millis", 1) => Utc::now().timestamp_millis().to_string(),
+                ("datetime", 1) => Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+                _ => return Err(format!("unknown placeholder {{{{{}}}}}", placeholder)),
+            };
+            rendered.push_str(&value);
+        }
+        remaining = &remaining[end + 2..];
+    }
+    rendered.push_str(remaining);
+
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use gumdrop::Options;
+    use url::Url;
+
+    use crate::SwanlingConfiguration;
+
+    fn setup_user() -> SwanlingUser {
+        let configuration = SwanlingConfiguration::parse_args_default(&Vec::<&str>::new()).unwrap();
+        SwanlingUser::single(Url::parse("http://localhost/").unwrap(), &configuration).unwrap()
+    }
+
+    #[test]
+    fn render_fields_and_builtins() {
+        let user = setup_user();
+        let context = SwanlingTemplateContext::new()
+            .field("sku", 1234)
+            .field("user_id", "overridden");
+
+        assert_eq!(
+            render("order-{{ uuid }}-{{seq}}-{{seq}}", &context, &user).unwrap(),
+            format!("order-{}-0-0", user.uuid())
+        );
+        assert_eq!(
+            render("{{seq}} {{sku}} {{user_id}}", &context, &user).unwrap(),
+            "1 1234 overridden"
+        );
+        assert_eq!(
+            render("no placeholders", &context, &user).unwrap(),
+            "no placeholders"
+        );
+
+        let random = render("{{random_string 12}}", &context, &user).unwrap();
+        assert_eq!(random.len(), 12);
+        assert!(random.chars().all(|c| c.is_ascii_alphanumeric()));
+
+        let number: i64 = render("{{random_number -2 2}}", &context, &user)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((-2..=2).contains(&number));
+
+        let uuid = render("{{random_uuid}}", &context, &user).unwrap();
+        assert_eq!(uuid.len(), 36);
+        assert_ne!(uuid, user.uuid());
+
+        assert!(render("{{timestamp_millis}}", &context, &user)
+            .unwrap()
+            .parse::<i64>()
+            .is_ok());
+        assert!(render("{{datetime}}", &context, &user)
+            .unwrap()
+            .ends_with('Z'));
+    }
+
+    #[test]
+    fn render_invalid() {
+        let user = setup_user();
+        let context = SwanlingTemplateContext::new();
+
+        for template in &[
+            "{{unknown}}",
+            "{{uuid",
+            "{{random_string}}",
+            "{{random_string many}}",
+            "{{random_number 5 1}}",
+            "{{timestamp 1}}",
+        ] {
+            assert!(render(template, &context, &user).is_err());
+        }
+    }
+}
@@ -9,33 +9,25 @@ use crate::util;
 /// implementation of the leaky bucket algorithm as a queue: instead of leaking the
 /// overflow we asynchronously block. More information on the leaky bucket algorithm
 /// can be found at: https://en.wikipedia.org/wiki/Leaky_bucket
+///
+/// Requests made by high priority tasks that find the bucket full are instead sent on the
+/// unbuffered priority channel. Each time tokens are removed, waiting high priority requests
+/// are allowed first, jumping the queue of requests waiting for room in the bucket.
+///
+/// The throttle follows a plan of steps, each allowing a maximum number of requests per
+/// second for a number of seconds. The last step lasts until the load test ends, as does
+/// the only step of a throttle configured with `--throttle-requests`.
 pub async fn throttle_main(
-    throttle_requests: usize,
+    throttle_plan: Vec<(usize, usize)>,
     throttle_receiver: flume::Receiver<bool>,
+    priority_receiver: flume::Receiver<bool>,
     parent_receiver: flume::Receiver<bool>,
 ) {
-    // Use microseconds to allow configurations up to 1,000,000 requests per second.
-    let mut sleep_duration = time::Duration::from_micros(1_000_000 / throttle_requests as u64);
-    let tokens_per_duration;
-
-    let ten_milliseconds = time::Duration::from_millis(10);
-    debug!(
-        "sleep_duration: {:?} ten_milliseconds: {:?}",
-        sleep_duration, ten_milliseconds
-    );
-
-    // Keep sleep_duration at least ~10ms as `delay_for` has millisecond granularity.
-    if sleep_duration < ten_milliseconds {
-        tokens_per_duration = (ten_milliseconds.as_nanos() / sleep_duration.as_nanos()) as u32;
-        sleep_duration *= tokens_per_duration;
-    } else {
-        tokens_per_duration = 1;
-    }
-
-    info!(
-        "throttle allowing {} request(s) every {:?}",
-        tokens_per_duration, sleep_duration
-    );
+    let mut step = 0;
+    let (mut sleep_duration, mut tokens_per_duration) = throttle_rate(throttle_plan[step].0);
+    // Steps of the plan are timed from when the throttle starts.
+    let started = time::Instant::now();
+    let mut step_ends = throttle_plan[step].1;
 
     // One or more token gets removed from the throttle_receiver bucket at regular
     // intervals. The throttle_drift variable tracks how much time is spent on
@@ -50,16 +42,36 @@ pub async fn throttle_main(
         );
         throttle_drift = util::sleep_minus_drift(sleep_duration, throttle_drift).await;
 
+        // Move on to the next step of the plan when this step's time is up.
+        if step_ends > 0 && step + 1 < throttle_plan.len() {
+            let elapsed = started.elapsed().as_secs() as usize;
+            if elapsed >= step_ends {
+                step += 1;
+                step_ends += throttle_plan[step].1;
+                let (next_sleep_duration, next_tokens_per_duration) =
+                    throttle_rate(throttle_plan[step].0);
+                sleep_duration = next_sleep_duration;
+                tokens_per_duration = next_tokens_per_duration;
+            }
+        }
+
         // A message will be received when the load test is over.
         if parent_receiver.try_recv().is_ok() {
             // Close throttle channel to prevent any further requests.
             info!("load test complete, closing throttle channel");
             drop(throttle_receiver);
+            drop(priority_receiver);
             break;
         }
 
+        // First allow waiting high priority requests, each using one of the tokens.
+        let mut priority_tokens = 0;
+        while priority_tokens < tokens_per_duration && priority_receiver.try_recv().is_ok() {
+            priority_tokens += 1;
+        }
+
         // Remove tokens from the channel, freeing spots for request to be made.
-        for token in 0..tokens_per_duration {
+        for token in priority_tokens..tokens_per_duration {
             // If the channel is empty, we will get an error, so stop trying to remove tokens.
             if throttle_receiver.try_recv().is_err() {
                 debug!("empty channel, exit after removing {} tokens", token);
@@ -68,3 +80,32 @@ pub async fn throttle_main(
         }
     }
 }
+
+/// Returns how often to remove tokens from the bucket, and how many tokens to remove each
+/// time, to allow `throttle_requests` requests per second.
+fn throttle_rate(throttle_requests: usize) -> (time::Duration, u32) {
+    // Use microseconds to allow configurations up to 1,000,000 requests per second.
+    let mut sleep_duration = time::Duration::from_micros(1_000_000 / throttle_requests as u64);
+    let tokens_per_duration;
+
+    let ten_milliseconds = time::Duration::from_millis(10);
+    debug!(
+        "sleep_duration: {:?} ten_milliseconds: {:?}",
+        sleep_duration, ten_milliseconds
+    );
+
+    // Keep sleep_duration at least ~10ms as `delay_for` has millisecond granularity.
+    if sleep_duration < ten_milliseconds {
+        tokens_per_duration = (ten_milliseconds.as_nanos() / sleep_duration.as_nanos()) as u32;
+        sleep_duration *= tokens_per_duration;
+    } else {
+        tokens_per_duration = 1;
+    }
+
+    info!(
+        "throttle allowing {} request(s) every {:?}",
+        tokens_per_duration, sleep_duration
+    );
+
+    (sleep_duration, tokens_per_duration)
+}
@@ -0,0 +1,391 @@
+//! Optional live dashboard, enabled with the `tui` feature and the `--tui` run-time option.
+//!
+//! Instead of printing running metrics tables, the dashboard redraws the terminal every
+//! second with the number of active users, the current requests per second and error rate,
+//! a sparkline of the average response time, and the slowest requests. Keys are mapped to
+//! Controller commands, so an interactive load test can be started, stopped, paused, reset
+//! and shut down without connecting a telnet or WebSocket Controller.
+
+use chrono::prelude::*;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute, queue};
+use num_format::{Locale, ToFormattedString};
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+use crate::controller::{
+    SwanlingControllerCommand, SwanlingControllerRequest, SwanlingControllerRequestMessage,
+    SwanlingControllerResponse, SwanlingControllerResponseMessage,
+};
+use crate::metrics::SwanlingMetrics;
+use crate::SwanlingError;
+
+/// How often the dashboard requests the latest metrics.
+const REFRESH: Duration = Duration::from_secs(1);
+
+/// How long to wait for a key to be pressed before checking for responses.
+const KEY_POLL: Duration = Duration::from_millis(100);
+
+/// How many average response times are displayed in the sparkline.
+const HISTORY: usize = 60;
+
+/// How many of the slowest requests are displayed.
+const SLOWEST: usize = 10;
+
+/// The dashboard identifies itself to the parent process as this Controller client.
+const CLIENT_ID: u32 = 0;
+
+/// Characters used to draw the sparkline, from lowest to highest.
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The keys mapped to Controller commands, displayed at the bottom of the dashboard.
+const HELP: &str = "[s] start  [x] stop  [r] reset metrics  [p] pause  [u] resume  [q] shut down";
+
+/// The dashboard thread, which restores the terminal when dropped.
+#[derive(Debug)]
+pub(crate) struct SwanlingTui {
+    /// Tells the dashboard thread to exit.
+    stop: Arc<AtomicBool>,
+    /// The dashboard thread, joined when dropped.
+    thread: Option<thread::JoinHandle<()>>,
+}
+impl SwanlingTui {
+    /// Take over the terminal and start the dashboard thread, which sends requests to the
+    /// parent process over the Controller channel. Pressing ctrl-c cancels the load test,
+    /// as the terminal no longer generates a signal.
+    pub(crate) fn start(
+        controller_request_tx: flume::Sender<SwanlingControllerRequest>,
+        canceled: Arc<AtomicBool>,
+    ) -> Result<Self, SwanlingError> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let thread = thread::spawn(move || {
+            if let Err(e) = tui_main(&controller_request_tx, &canceled, &thread_stop) {
+                error!("dashboard failed: {}", e);
+            }
+        });
+
+        Ok(SwanlingTui {
+            stop,
+            thread: Some(thread),
+        })
+    }
+}
+impl Drop for SwanlingTui {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        // Restore the terminal so the final metrics are displayed normally.
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+// Redraw the dashboard until told to stop, handling key presses.
+fn tui_main(
+    controller_request_tx: &flume::Sender<SwanlingControllerRequest>,
+    canceled: &AtomicBool,
+    stop: &AtomicBool,
+) -> crossterm::Result<()> {
+    let mut stdout = io::stdout();
+    let mut dashboard = Dashboard::default();
+    let mut metrics_response = None;
+    let mut command_response = None;
+    let mut refreshed: Option<Instant> = None;
+
+    while !stop.load(Ordering::SeqCst) {
+        let mut redraw = false;
+
+        // Request the latest metrics once the previous response is received.
+        let refresh = match refreshed {
+            Some(refreshed) => refreshed.elapsed() >= REFRESH,
+            None => true,
+        };
+        if metrics_response.is_none() && refresh {
+            metrics_response =
+                send_request(controller_request_tx, SwanlingControllerCommand::Metrics);
+            refreshed = Some(Instant::now());
+        }
+        if let Some(SwanlingControllerResponseMessage::Metrics(metrics)) =
+            receive_response(&mut metrics_response)
+        {
+            dashboard.update(&metrics);
+            redraw = true;
+        }
+
+        // Display whether the last command succeeded.
+        if let Some((command, response)) = command_response.as_mut() {
+            if let Some(SwanlingControllerResponseMessage::Bool(success)) =
+                receive_response(response)
+            {
+                dashboard.status = format!(
+                    "{:?}: {}",
+                    command,
+                    if success { "ok" } else { "not possible now" }
+                );
+                redraw = true;
+            }
+            if response.is_none() {
+                command_response = None;
+            }
+        }
+
+        // Map keys to Controller commands.
+        if event::poll(KEY_POLL)? {
+            match event::read()? {
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('c'),
+                    modifiers: KeyModifiers::CONTROL,
+                }) => {
+                    canceled.store(true, Ordering::SeqCst);
+                    dashboard.status = "Canceling...".to_string();
+                }
+                Event::Key(KeyEvent { code, .. }) => {
+                    let command = match code {
+                        KeyCode::Char('s') => Some(SwanlingControllerCommand::Start),
+                        KeyCode::Char('x') => Some(SwanlingControllerCommand::Stop),
+                        KeyCode::Char('r') => Some(SwanlingControllerCommand::Reset),
+                        KeyCode::Char('p') => Some(SwanlingControllerCommand::Pause),
+                        KeyCode::Char('u') => Some(SwanlingControllerCommand::Resume),
+                        KeyCode::Char('q') => Some(SwanlingControllerCommand::Shutdown),
+                        _ => None,
+                    };
+                    if let Some(command) = command {
+                        dashboard.status = format!("{:?}...", command);
+                        let response = send_request(controller_request_tx, command.clone());
+                        command_response = Some((command, response));
+                    }
+                }
+                _ => (),
+            }
+            redraw = true;
+        }
+
+        if redraw {
+            dashboard.draw(&mut stdout)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Send a request to the parent process, returning a channel to receive the response on.
+fn send_request(
+    controller_request_tx: &flume::Sender<SwanlingControllerRequest>,
+    command: SwanlingControllerCommand,
+) -> Option<oneshot::Receiver<SwanlingControllerResponse>> {
+    let (response_tx, response_rx) = oneshot::channel();
+    controller_request_tx
+        .send(SwanlingControllerRequest {
+            response_channel: Some(response_tx),
+            client_id: CLIENT_ID,
+            request: SwanlingControllerRequestMessage {
+                command,
+                value: None,
+            },
+        })
+        .ok()
+        .map(|_| response_rx)
+}
+
+// Return the response if it was received, forgetting the channel once it was received
+// or closed.
+fn receive_response(
+    response: &mut Option<oneshot::Receiver<SwanlingControllerResponse>>,
+) -> Option<SwanlingControllerResponseMessage> {
+    let received = match response.as_mut()?.try_recv() {
+        Ok(received) => Some(received.response),
+        Err(oneshot::error::TryRecvError::Empty) => return None,
+        Err(oneshot::error::TryRecvError::Closed) => None,
+    };
+    *response = None;
+    received
+}
+
+/// What the dashboard displays, updated from each metrics snapshot.
+#[derive(Default)]
+struct Dashboard {
+    /// How many users are running.
+    users: usize,
+    /// How many seconds the load test has been running.
+    duration: i64,
+    /// How many requests were made since metrics were last reset.
+    requests: usize,
+    /// How many requests failed since metrics were last reset.
+    fails: usize,
+    /// Requests per second since the previous snapshot.
+    requests_per_second: f32,
+    /// Average response times since each previous snapshot, oldest first.
+    response_times: VecDeque<usize>,
+    /// The slowest requests: name, average and maximum response time, and count.
+    slowest: Vec<(String, f32, usize, usize)>,
+    /// The requests, total response time and time of the previous snapshot.
+    previous: Option<(usize, usize, Instant)>,
+    /// The result of the last command.
+    status: String,
+}
+impl Dashboard {
+    // Update the dashboard from the latest metrics.
+    fn update(&mut self, metrics: &SwanlingMetrics) {
+        self.users = metrics.users;
+        self.duration = metrics
+            .started
+            .map_or(0, |started| (Local::now() - started).num_seconds());
+
+        let mut requests = 0;
+        let mut fails = 0;
+        let mut total_time = 0;
+        self.slowest.clear();
+        for request in metrics.requests.values() {
+            let count = request.success_count + request.fail_count;
+            requests += count;
+            fails += request.fail_count;
+            total_time += request.raw_data.total_time;
+            let average = if request.raw_data.counter > 0 {
+                request.raw_data.total_time as f32 / request.raw_data.counter as f32
+            } else {
+                0.0
+            };
+            self.slowest.push((
+                format!("{} {}", request.method, request.path),
+                average,
+                request.raw_data.maximum_time,
+                count,
+            ));
+        }
+        self.slowest
+            .sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        self.slowest.truncate(SLOWEST);
+
+        // Compare to the previous snapshot, unless metrics were reset since.
+        let now = Instant::now();
+        if let Some((previous_requests, previous_time, previous)) = self.previous {
+            if requests >= previous_requests {
+                let new_requests = requests - previous_requests;
+                let elapsed = (now - previous).as_secs_f32();
+                self.requests_per_second = if elapsed > 0.0 {
+                    new_requests as f32 / elapsed
+                } else {
+                    0.0
+                };
+                let average = total_time
+                    .saturating_sub(previous_time)
+                    .checked_div(new_requests)
+                    .unwrap_or(0);
+                self.response_times.push_back(average);
+                if self.response_times.len() > HISTORY {
+                    self.response_times.pop_front();
+                }
+            }
+        }
+        self.previous = Some((requests, total_time, now));
+        self.requests = requests;
+        self.fails = fails;
+    }
+
+    // Redraw the whole dashboard.
+    fn draw(&self, stdout: &mut io::Stdout) -> crossterm::Result<()> {
+        let (width, height) = terminal::size()?;
+        let fail_percent = if self.requests > 0 {
+            self.fails as f32 / self.requests as f32 * 100.0
+        } else {
+            0.0
+        };
+        let response_time = self.response_times.back().copied().unwrap_or(0);
+
+        let mut lines = vec![
+            format!(
+                " users: {}  elapsed: {}s",
+                self.users.to_formatted_string(&Locale::en),
+                self.duration
+            ),
+            format!(
+                " requests: {}  req/s: {:.2}  fails: {} ({:.1}%)",
+                self.requests.to_formatted_string(&Locale::en),
+                self.requests_per_second,
+                self.fails.to_formatted_string(&Locale::en),
+                fail_percent
+            ),
+            format!(
+                " avg response time: {}ms  {}",
+                response_time,
+                sparkline(&self.response_times)
+            ),
+            String::new(),
+            format!(
+                " {:<40} | {:>10} | {:>10} | {:>10}",
+                "Slowest requests", "Avg (ms)", "Max (ms)", "Requests"
+            ),
+            format!(" {}", "-".repeat(78)),
+        ];
+        for (name, average, maximum, count) in &self.slowest {
+            lines.push(format!(
+                " {:<40} | {:>10.2} | {:>10} | {:>10}",
+                truncate(name, 40),
+                average,
+                maximum.to_formatted_string(&Locale::en),
+                count.to_formatted_string(&Locale::en)
+            ));
+        }
+
+        queue!(stdout, terminal::Clear(ClearType::All))?;
+        queue!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            SetAttribute(Attribute::Bold),
+            Print(truncate(" Swanling", width as usize)),
+            SetAttribute(Attribute::Reset)
+        )?;
+        // Leave room for the header and the two footer lines.
+        let rows = (height as usize).saturating_sub(3);
+        for (row, line) in lines.iter().take(rows).enumerate() {
+            queue!(
+                stdout,
+                cursor::MoveTo(0, row as u16 + 1),
+                Print(truncate(line, width as usize))
+            )?;
+        }
+        queue!(
+            stdout,
+            cursor::MoveTo(0, height.saturating_sub(2)),
+            Print(truncate(&format!(" {}", self.status), width as usize)),
+            cursor::MoveTo(0, height.saturating_sub(1)),
+            SetAttribute(Attribute::Reverse),
+            Print(truncate(&format!(" {}", HELP), width as usize)),
+            SetAttribute(Attribute::Reset)
+        )?;
+        stdout.flush()?;
+
+        Ok(())
+    }
+}
+
+// Draw values as a sparkline, scaled to the largest value.
+fn sparkline(values: &VecDeque<usize>) -> String {
+    let maximum = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|value| {
+            SPARKS[(value * (SPARKS.len() - 1))
+                .checked_div(maximum)
+                .unwrap_or(0)]
+        })
+        .collect()
+}
+
+// Truncate text to fit the width of the terminal.
+fn truncate(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
@@ -1,16 +1,23 @@
+use rand::seq::SliceRandom;
 use rand::Rng;
-use std::sync::atomic::Ordering;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time;
+use tokio::sync::RwLock;
 
-use crate::get_worker_id;
 use crate::logger::SwanlingLog;
-use crate::metrics::{SwanlingMetric, SwanlingTaskMetric};
-use crate::swanling::{SwanlingTaskFunction, SwanlingTaskSet, SwanlingUser, SwanlingUserCommand};
+use crate::metrics::SwanlingTaskMetric;
+use crate::swanling::{
+    new_uuid, SwanlingClientMode, SwanlingTask, SwanlingTaskError, SwanlingTaskSet, SwanlingUser,
+    SwanlingUserCommand,
+};
+use crate::{get_worker_id, WeightedSwanlingTasks};
 
 pub(crate) async fn user_main(
     thread_number: usize,
     thread_task_set: SwanlingTaskSet,
-    thread_user: SwanlingUser,
+    mut thread_user: SwanlingUser,
     thread_receiver: flume::Receiver<SwanlingUserCommand>,
     worker: bool,
 ) {
@@ -28,51 +35,227 @@ pub(crate) async fn user_main(
         );
     }
 
-    // User is starting, first invoke the weighted on_start tasks.
+    // Seed the user's random number generator, now that its index is known.
+    thread_user.reset_rng();
+
+    // Pick a user agent, if the task set has any.
+    let user_agent = thread_task_set
+        .user_agents
+        .choose(&mut *thread_user.rng())
+        .cloned();
+    thread_user.user_agent = user_agent;
+
+    // Run the user, replacing it with a fresh user each time it's retired by --user-churn,
+    // or stopped after too many consecutive task failures if so configured.
+    while run_user(
+        thread_number,
+        &thread_task_set,
+        &mut thread_user,
+        &thread_receiver,
+    )
+    .await
+    {
+        info!(
+            "[user {}]: replacing with a fresh user from {}",
+            thread_number, thread_task_set.name
+        );
+        thread_user.uuid = new_uuid();
+        thread_user.path_templates.lock().unwrap().clear();
+        if let Err(e) = thread_user.reset_client().await {
+            warn!("[user {}]: failed to rebuild client: {}", thread_number, e);
+        }
+    }
+
+    // Send any remaining aggregated metrics to the parent before exiting.
+    // Best effort metrics.
+    let _ = thread_user.flush_metrics(true);
+
+    // Optional debug output when exiting.
+    if worker {
+        info!(
+            "[{}] exiting user {} from {}...",
+            get_worker_id(),
+            thread_number,
+            thread_task_set.name
+        );
+    } else {
+        info!(
+            "exiting user {} from {}...",
+            thread_number, thread_task_set.name
+        );
+    }
+}
+
+// Run the user's session functions and tasks until the parent tells it to exit. Returns
+// true if the user was retired, or stopped after too many consecutive task failures, and
+// should be replaced with a fresh user.
+async fn run_user(
+    thread_number: usize,
+    thread_task_set: &SwanlingTaskSet,
+    thread_user: &mut SwanlingUser,
+    thread_receiver: &flume::Receiver<SwanlingUserCommand>,
+) -> bool {
+    // Track which tasks succeeded, so tasks depending on them can be skipped if they didn't.
+    let mut task_results: HashMap<usize, bool> = HashMap::new();
+
+    // User is starting, first invoke the on_start session functions. If any fails, the
+    // user is removed from the load test.
+    for function in &thread_task_set.on_start_sessions {
+        if let Err(e) = function(thread_user).await {
+            warn!(
+                "[user {}]: removed from {}: {}",
+                thread_number, thread_task_set.name, e
+            );
+            thread_user.send_user_removed(e.to_string());
+            // Delete any resources created before the user was removed.
+            for e in run_cleanups(thread_user).await {
+                warn!(
+                    "[user {}]: cleanup from {} failed: {}",
+                    thread_number, thread_task_set.name, e
+                );
+            }
+            return false;
+        }
+    }
+
+    // Next invoke the weighted on_start tasks.
     if !thread_user.weighted_on_start_tasks.is_empty() {
         // Tasks are already weighted and scheduled, execute each in order.
-        for (thread_task_index, thread_task_name) in &thread_user.weighted_on_start_tasks {
-            // Determine which task we're going to run next.
-            let function = &thread_task_set.tasks[*thread_task_index].function;
+        for position in select_tasks(&thread_user.weighted_on_start_tasks, thread_task_set) {
+            let (thread_task_index, thread_task_name) =
+                &thread_user.weighted_on_start_tasks[position];
             debug!(
                 "[user {}]: launching on_start {} task from {}",
                 thread_number, thread_task_name, thread_task_set.name
             );
             // Invoke the task function.
-            let _todo =
-                invoke_task_function(function, &thread_user, *thread_task_index, thread_task_name)
-                    .await;
+            run_task(
+                thread_task_set,
+                thread_user,
+                *thread_task_index,
+                thread_task_name,
+                &mut task_results,
+            )
+            .await;
         }
     }
+    // The results of on_start tasks are kept for all iterations.
+    let on_start_task_results = task_results.clone();
+
+    // Set while the load test is paused, scheduled tasks are skipped and no new iteration
+    // through the normal tasks starts until it's resumed.
+    let paused = Arc::new(AtomicBool::new(false));
+
+    // Run scheduled tasks on their own timers, alongside the normal tasks. The scheduled
+    // tasks stop when stop_scheduled_tx is dropped.
+    let (stop_scheduled_tx, stop_scheduled_rx) = flume::bounded::<()>(0);
+    let scheduled_tasks: Vec<_> = thread_task_set
+        .tasks
+        .iter()
+        .filter(|task| task.schedule.is_some())
+        .map(|task| {
+            debug!(
+                "[user {}]: scheduling {} task from {}",
+                thread_number, task.name, thread_task_set.name
+            );
+            tokio::spawn(run_scheduled_task(
+                task.clone(),
+                thread_user.clone(),
+                stop_scheduled_rx.clone(),
+                paused.clone(),
+            ))
+        })
+        .collect();
+
+    // Set if the user is stopped after too many consecutive task failures.
+    let mut failed = false;
+    // Set if the parent retires the user, to be replaced with a fresh user.
+    let mut retired = false;
 
     // If normal tasks are defined, loop launching tasks until parent tells us to stop.
     if !thread_user.weighted_tasks.is_empty() {
-        let mut position;
+        let mut consecutive_failures = 0;
+        let mut first_iteration = true;
         'launch_tasks: loop {
+            // Don't start a new iteration while paused, keeping the client and its connections.
+            if paused.load(Ordering::SeqCst) {
+                let paused_timer = time::Instant::now();
+                // Best effort metrics.
+                let _ = thread_user.flush_metrics(true);
+                if !wait_while_paused(thread_receiver, &paused).await {
+                    break 'launch_tasks;
+                }
+                // Time spent paused is tracked like sleeping, so Coordinated Omission
+                // Mitigation doesn't mistake it for a slow request.
+                thread_user.slept.fetch_add(
+                    (time::Instant::now() - paused_timer).as_millis() as u64,
+                    Ordering::SeqCst,
+                );
+            }
+
             // Start at the first task in thread_user.weighted_tasks.
-            position = 0;
-            thread_user.position.store(position, Ordering::SeqCst);
+            thread_user.position.store(0, Ordering::SeqCst);
+
+            // Start each iteration after the first with a new client if so configured.
+            if thread_task_set.client_mode == SwanlingClientMode::PerIteration && !first_iteration {
+                if let Err(e) = thread_user.reset_client().await {
+                    warn!("[user {}]: failed to rebuild client: {}", thread_number, e);
+                }
+            }
+            first_iteration = false;
+
+            // Each iteration only depends on tasks that succeeded during the iteration.
+            task_results = on_start_task_results.clone();
 
             // Tracks the time it takes to loop through all SwanlingTasks when Coordinated Omission
             // Mitigation is enabled.
             thread_user.update_request_cadence(thread_number).await;
 
-            for (thread_task_index, thread_task_name) in &thread_user.weighted_tasks {
-                // Determine which task we're going to run next.
-                let function = &thread_task_set.tasks[*thread_task_index].function;
+            for position in select_tasks(&thread_user.weighted_tasks, thread_task_set) {
+                // Track which task in thread_user.weighted_tasks is running.
+                thread_user.position.store(position, Ordering::SeqCst);
+                let (thread_task_index, thread_task_name) = &thread_user.weighted_tasks[position];
                 debug!(
                     "launching on_start {} task from {}",
                     thread_task_name, thread_task_set.name
                 );
                 // Invoke the task function.
-                let _todo = invoke_task_function(
-                    function,
-                    &thread_user,
+                let success = run_task(
+                    thread_task_set,
+                    thread_user,
                     *thread_task_index,
                     thread_task_name,
+                    &mut task_results,
                 )
                 .await;
 
+                // If enabled, stop the user after too many consecutive task failures.
+                match success {
+                    Some(true) => consecutive_failures = 0,
+                    Some(false) => consecutive_failures += 1,
+                    None => (),
+                }
+                if let Some(max_failures) = thread_task_set.max_failures_per_user {
+                    if consecutive_failures >= max_failures {
+                        let reason = format!(
+                            "{} after {} consecutive task failures",
+                            if thread_task_set.replace_failed_users {
+                                "replaced"
+                            } else {
+                                "stopped"
+                            },
+                            consecutive_failures
+                        );
+                        warn!(
+                            "[user {}]: {} from {}",
+                            thread_number, reason, thread_task_set.name
+                        );
+                        thread_user.send_user_removed(reason);
+                        failed = true;
+                        break 'launch_tasks;
+                    }
+                }
+
                 // Prepare to sleep for a random value from min_wait to max_wait.
                 let wait_time = if thread_user.max_wait > 0 {
                     rand::thread_rng().gen_range(thread_user.min_wait..thread_user.max_wait)
@@ -80,6 +263,12 @@ pub(crate) async fn user_main(
                     0
                 };
 
+                // Send aggregated metrics to the parent before sleeping.
+                if thread_user.max_wait > 0 {
+                    // Best effort metrics.
+                    let _ = thread_user.flush_metrics(true);
+                }
+
                 // Counter to track how long we've slept, waking regularly to check for messages.
                 let mut slept: usize = 0;
 
@@ -95,9 +284,14 @@ pub(crate) async fn user_main(
                             SwanlingUserCommand::Exit => {
                                 break 'launch_tasks;
                             }
-                            command => {
-                                debug!("ignoring unexpected SwanlingUserCommand: {:?}", command);
+                            // Time to retire, stop launching tasks and restart.
+                            SwanlingUserCommand::Retire => {
+                                retired = true;
+                                break 'launch_tasks;
                             }
+                            // Pause after this iteration.
+                            SwanlingUserCommand::Wait => paused.store(true, Ordering::SeqCst),
+                            SwanlingUserCommand::Run => paused.store(false, Ordering::SeqCst),
                         }
                         message = thread_receiver.try_recv();
                     }
@@ -122,82 +316,394 @@ pub(crate) async fn user_main(
                     (time::Instant::now() - sleep_timer).as_millis() as u64,
                     Ordering::SeqCst,
                 );
+            }
+        }
+    }
 
-                // Move to the next task in thread_user.weighted_tasks.
-                position += 1;
-                thread_user.position.store(position, Ordering::SeqCst);
+    // If only scheduled tasks are defined, wait until parent tells us to stop.
+    if thread_user.weighted_tasks.is_empty() && !scheduled_tasks.is_empty() {
+        while let Ok(command) = thread_receiver.recv_async().await {
+            match command {
+                SwanlingUserCommand::Exit => break,
+                SwanlingUserCommand::Retire => {
+                    retired = true;
+                    break;
+                }
+                SwanlingUserCommand::Wait => paused.store(true, Ordering::SeqCst),
+                SwanlingUserCommand::Run => paused.store(false, Ordering::SeqCst),
             }
         }
     }
 
+    // Stop running scheduled tasks, waiting for any that are running to finish.
+    drop(stop_scheduled_tx);
+    for scheduled_task in scheduled_tasks {
+        let _ = scheduled_task.await;
+    }
+
     // User is exiting, first invoke the weighted on_stop tasks.
     if !thread_user.weighted_on_stop_tasks.is_empty() {
         // Tasks are already weighted and scheduled, execute each in order.
-        for (thread_task_index, thread_task_name) in &thread_user.weighted_on_stop_tasks {
-            // Determine which task we're going to run next.
-            let function = &thread_task_set.tasks[*thread_task_index].function;
+        for position in select_tasks(&thread_user.weighted_on_stop_tasks, thread_task_set) {
+            let (thread_task_index, thread_task_name) =
+                &thread_user.weighted_on_stop_tasks[position];
             debug!(
                 "[user: {}]: launching on_stop {} task from {}",
                 thread_number, thread_task_name, thread_task_set.name
             );
             // Invoke the task function.
-            let _todo =
-                invoke_task_function(function, &thread_user, *thread_task_index, thread_task_name)
-                    .await;
+            run_task(
+                thread_task_set,
+                thread_user,
+                *thread_task_index,
+                thread_task_name,
+                &mut task_results,
+            )
+            .await;
         }
     }
 
-    // Optional debug output when exiting.
-    if worker {
-        info!(
-            "[{}] exiting user {} from {}...",
-            get_worker_id(),
-            thread_number,
-            thread_task_set.name
+    // Then delete the resources the user created.
+    for e in run_cleanups(thread_user).await {
+        warn!(
+            "[user {}]: cleanup from {} failed: {}",
+            thread_number, thread_task_set.name, e
         );
-    } else {
-        info!(
-            "exiting user {} from {}...",
-            thread_number, thread_task_set.name
+    }
+
+    // Finally invoke the on_stop session functions.
+    for function in &thread_task_set.on_stop_sessions {
+        if let Err(e) = function(thread_user).await {
+            warn!(
+                "[user {}]: on_stop session from {} failed: {}",
+                thread_number, thread_task_set.name, e
+            );
+        }
+    }
+
+    retired || (failed && thread_task_set.replace_failed_users)
+}
+
+// Run a user through one iteration of its task set with --debug-single-user, printing
+// which task is running and whether it succeeded. Each response is printed when received.
+pub(crate) async fn debug_user_main(
+    thread_task_set: &SwanlingTaskSet,
+    thread_user: &mut SwanlingUser,
+) {
+    println!("\n{}:", thread_task_set.name);
+
+    // Seed the user's random number generator, now that its index is known.
+    thread_user.reset_rng();
+
+    for function in &thread_task_set.on_start_sessions {
+        if let Err(e) = function(thread_user).await {
+            println!("on_start session failed: {}", e);
+            return;
+        }
+    }
+
+    let mut task_results: HashMap<usize, bool> = HashMap::new();
+    for weighted_tasks in [
+        thread_user.weighted_on_start_tasks.clone(),
+        thread_user.weighted_tasks.clone(),
+    ] {
+        debug_tasks(
+            thread_task_set,
+            thread_user,
+            &weighted_tasks,
+            &mut task_results,
+        )
+        .await;
+    }
+
+    // Scheduled tasks run once, as if they were due.
+    for task in thread_task_set
+        .tasks
+        .iter()
+        .filter(|task| task.schedule.is_some())
+    {
+        println!("{} (scheduled):", task_label(task.tasks_index, &task.name));
+        let success = invoke_task_function(task, thread_user).await;
+        println!("  {}", if success { "succeeded" } else { "failed" });
+    }
+
+    let weighted_on_stop_tasks = thread_user.weighted_on_stop_tasks.clone();
+    debug_tasks(
+        thread_task_set,
+        thread_user,
+        &weighted_on_stop_tasks,
+        &mut task_results,
+    )
+    .await;
+
+    for e in run_cleanups(thread_user).await {
+        println!("cleanup failed: {}", e);
+    }
+
+    for function in &thread_task_set.on_stop_sessions {
+        if let Err(e) = function(thread_user).await {
+            println!("on_stop session failed: {}", e);
+        }
+    }
+}
+
+// Run the cleanup functions registered by the user, the most recently registered first,
+// returning the errors of any that failed.
+async fn run_cleanups(thread_user: &SwanlingUser) -> Vec<SwanlingTaskError> {
+    let mut errors = Vec::new();
+    // Functions are removed one at a time, so a cleanup function can register another.
+    while let Some(function) = thread_user.cleanups.pop() {
+        if let Err(e) = function(thread_user).await {
+            errors.push(e);
+        }
+    }
+    errors
+}
+
+// Run weighted tasks once each, in order, printing whether each succeeded.
+async fn debug_tasks(
+    thread_task_set: &SwanlingTaskSet,
+    thread_user: &SwanlingUser,
+    weighted_tasks: &WeightedSwanlingTasks,
+    task_results: &mut HashMap<usize, bool>,
+) {
+    for position in select_tasks(weighted_tasks, thread_task_set) {
+        // Track which task is running, so requests are named after it.
+        thread_user.position.store(position, Ordering::SeqCst);
+        let (thread_task_index, thread_task_name) = &weighted_tasks[position];
+        println!("{}:", task_label(*thread_task_index, thread_task_name));
+        let success = run_task(
+            thread_task_set,
+            thread_user,
+            *thread_task_index,
+            thread_task_name,
+            task_results,
+        )
+        .await;
+        println!(
+            "  {}",
+            match success {
+                Some(true) => "succeeded",
+                Some(false) => "failed",
+                None => "skipped",
+            }
         );
     }
 }
 
-// Invoke the task function, collecting task metrics.
-async fn invoke_task_function(
-    function: &SwanlingTaskFunction,
+// Unnamed tasks are labeled with their index.
+fn task_label(tasks_index: usize, name: &str) -> String {
+    if name.is_empty() {
+        format!("task {}", tasks_index)
+    } else {
+        name.to_string()
+    }
+}
+
+// Run a scheduled task at its configured frequency, until the user is exiting.
+async fn run_scheduled_task(
+    task: SwanlingTask,
+    mut thread_user: SwanlingUser,
+    stop: flume::Receiver<()>,
+    paused: Arc<AtomicBool>,
+) {
+    // Track the position separately from the normal tasks, so requests are named after
+    // the scheduled task.
+    thread_user.position = Arc::new(AtomicUsize::new(0));
+    thread_user.weighted_tasks = vec![(task.tasks_index, task.name.clone())];
+    // Track the task's base URL separately too, as it runs alongside the normal tasks.
+    thread_user.task_base_url = Arc::new(RwLock::new(None));
+    thread_user.task_label = Arc::new(RwLock::new("".to_string()));
+    thread_user.task_throttle_priority = Arc::new(AtomicBool::new(false));
+
+    // Only scheduled tasks are run here, so unwrap() is safe.
+    let every = task.schedule.unwrap();
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + every, every);
+    // Don't try to catch up if the task takes longer than its schedule.
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if paused.load(Ordering::SeqCst) {
+                    continue;
+                }
+                if let Some(skip_if) = &task.skip_if {
+                    if skip_if(&thread_user) {
+                        continue;
+                    }
+                }
+                invoke_task_function(&task, &thread_user).await;
+                // Scheduled tasks run infrequently, send their metrics right away.
+                let _ = thread_user.flush_metrics(true);
+            }
+            // The sender is dropped when the user is exiting.
+            _ = stop.recv_async() => break,
+        }
+    }
+}
+
+// Wait until a paused user is told to resume, returning false if instead told to exit.
+async fn wait_while_paused(
+    thread_receiver: &flume::Receiver<SwanlingUserCommand>,
+    paused: &AtomicBool,
+) -> bool {
+    while paused.load(Ordering::SeqCst) {
+        match thread_receiver.recv_async().await {
+            Ok(SwanlingUserCommand::Run) => paused.store(false, Ordering::SeqCst),
+            // Paused users aren't retired.
+            Ok(SwanlingUserCommand::Wait) | Ok(SwanlingUserCommand::Retire) => (),
+            Ok(SwanlingUserCommand::Exit) | Err(_) => return false,
+        }
+    }
+    true
+}
+
+// Select the positions of the tasks to run in this iteration through the weighted tasks.
+// Of each sequence containing a task set with set_sequence_choice(), only one randomly
+// chosen task runs, so tasks with a higher weight are more likely to be chosen.
+fn select_tasks(
+    weighted_tasks: &WeightedSwanlingTasks,
+    thread_task_set: &SwanlingTaskSet,
+) -> Vec<usize> {
+    let mut positions = Vec::with_capacity(weighted_tasks.len());
+    let mut position = 0;
+    while position < weighted_tasks.len() {
+        let sequence = thread_task_set.tasks[weighted_tasks[position].0].sequence;
+        let sequence_choice = sequence > 0
+            && thread_task_set
+                .tasks
+                .iter()
+                .any(|t| t.sequence_choice && t.sequence == sequence);
+        if !sequence_choice {
+            positions.push(position);
+            position += 1;
+            continue;
+        }
+        // All weighted instances of the tasks in this sequence are next to each other.
+        let choices = weighted_tasks[position..]
+            .iter()
+            .take_while(|(index, _)| thread_task_set.tasks[*index].sequence == sequence)
+            .count();
+        positions.push(position + rand::thread_rng().gen_range(0..choices));
+        position += choices;
+    }
+    positions
+}
+
+// Invoke the task function unless it should be skipped, recording whether it succeeded.
+// Returns whether the task succeeded, or None if it was skipped.
+async fn run_task(
+    thread_task_set: &SwanlingTaskSet,
     thread_user: &SwanlingUser,
     thread_task_index: usize,
     thread_task_name: &str,
-) -> Result<(), flume::SendError<Option<SwanlingLog>>> {
+    task_results: &mut HashMap<usize, bool>,
+) -> Option<bool> {
+    let task = &thread_task_set.tasks[thread_task_index];
+    if skip_task(task, thread_task_set, thread_user, task_results) {
+        debug!(
+            "skipping {} task from {}",
+            thread_task_name, thread_task_set.name
+        );
+        // Yield, as a user skipping all tasks would otherwise never give up control.
+        tokio::task::yield_now().await;
+        return None;
+    }
+    if let Some(users) = task.rendezvous {
+        debug!(
+            "waiting for {} users to reach {} task from {}",
+            users, thread_task_name, thread_task_set.name
+        );
+        thread_user
+            .coordination
+            .rendezvous(thread_user.task_sets_index, thread_task_index, users)
+            .await;
+    }
+    let success = invoke_task_function(task, thread_user).await;
+    task_results.insert(thread_task_index, success);
+    Some(success)
+}
+
+// Determine if a task should be skipped, because the task it depends on didn't succeed
+// or its skip_if predicate returns true.
+fn skip_task(
+    task: &SwanlingTask,
+    thread_task_set: &SwanlingTaskSet,
+    thread_user: &SwanlingUser,
+    task_results: &HashMap<usize, bool>,
+) -> bool {
+    if let Some(depends_on) = &task.depends_on {
+        let succeeded = thread_task_set
+            .tasks
+            .iter()
+            .filter(|t| &t.name == depends_on)
+            .any(|t| task_results.get(&t.tasks_index) == Some(&true));
+        if !succeeded {
+            return true;
+        }
+    }
+    if let Some(skip_if) = &task.skip_if {
+        return skip_if(thread_user);
+    }
+    false
+}
+
+// Invoke the task function, collecting task metrics. Returns whether the task succeeded.
+async fn invoke_task_function(task: &SwanlingTask, thread_user: &SwanlingUser) -> bool {
+    // Relative paths requested by the task use its base URL, if set.
+    *thread_user.task_base_url.write().await = task.base_url.clone();
+    // Requests made by the task jump the throttle queue, if so configured.
+    thread_user
+        .task_throttle_priority
+        .store(task.throttle_priority, Ordering::SeqCst);
+    // Track the task is running, in case it doesn't exit when the load test stops.
+    let label = task_label(task.tasks_index, &task.name);
+    *thread_user.task_label.write().await = label.clone();
+    thread_user
+        .running_tasks
+        .lock()
+        .unwrap()
+        .push(label.clone());
+
     let started = time::Instant::now();
     let mut raw_task = SwanlingTaskMetric::new(
         thread_user.started.elapsed().as_millis(),
         thread_user.task_sets_index,
-        thread_task_index,
-        thread_task_name.to_string(),
+        task.tasks_index,
+        task.name.clone(),
         thread_user.weighted_users_index,
     );
-    let success = function(&thread_user).await.is_ok();
+    let success = (task.function)(&thread_user).await.is_ok();
     raw_task.set_time(started.elapsed().as_millis(), success);
+    {
+        let mut running_tasks = thread_user.running_tasks.lock().unwrap();
+        if let Some(index) = running_tasks.iter().position(|running| running == &label) {
+            running_tasks.remove(index);
+        }
+    }
 
     // Exit if all metrics or task metrics are disabled.
     if thread_user.config.no_metrics || thread_user.config.no_task_metrics {
-        return Ok(());
+        return success;
     }
 
     // If tasks-file is enabled, send a copy of the raw task metric to the logger thread.
     if !thread_user.config.task_log.is_empty() {
         if let Some(logger) = thread_user.logger.as_ref() {
-            logger.send(Some(SwanlingLog::Task(raw_task.clone())))?;
+            let _ = logger.send(Some(SwanlingLog::Task(raw_task.clone())));
         }
     }
 
-    // Otherwise send metrics to parent.
-    if let Some(parent) = thread_user.channel_to_parent.clone() {
+    // Otherwise aggregate metrics to send to parent.
+    if thread_user.channel_to_parent.is_some() {
+        thread_user
+            .metrics_batch
+            .lock()
+            .unwrap()
+            .record_task(&raw_task);
         // Best effort metrics.
-        let _ = parent.send(SwanlingMetric::Task(raw_task));
+        let _ = thread_user.flush_metrics(false);
     }
 
-    Ok(())
+    success
 }
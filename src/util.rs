@@ -1,5 +1,6 @@
 //! Utility functions used by Swanling, and available when writing load tests.
 
+use rand::Rng;
 use regex::Regex;
 use std::cmp::{max, min};
 use std::collections::BTreeMap;
@@ -9,6 +10,7 @@ use std::sync::Arc;
 use std::time;
 use url::Url;
 
+use crate::coordination::SwanlingFlag;
 use crate::SwanlingError;
 
 /// Parse a string representing a time span and return the number of seconds.
@@ -69,6 +71,69 @@ pub fn parse_timespan(time_str: &str) -> usize {
     }
 }
 
+/// Parse a string representing a short delay and return the number of milliseconds.
+///
+/// Can be specified as an integer, indicating milliseconds. Or can use an integer
+/// followed by "ms" or "s", indicating "milliseconds" or "seconds".
+///
+/// Valid formats include: 50, 50ms, 2s, etc.
+///
+/// # Example
+/// ```rust
+/// use swanling::util;
+///
+/// // 50 milliseconds is 50 milliseconds.
+/// assert_eq!(util::parse_milliseconds("50ms"), Some(50));
+///
+/// // 2 seconds is 2,000 milliseconds.
+/// assert_eq!(util::parse_milliseconds("2s"), Some(2_000));
+///
+/// // Invalid value returns None.
+/// assert_eq!(util::parse_milliseconds("foo"), None);
+/// ```
+pub fn parse_milliseconds(delay_str: &str) -> Option<u64> {
+    if let Some(milliseconds) = delay_str.strip_suffix("ms") {
+        u64::from_str(milliseconds).ok()
+    } else if let Some(seconds) = delay_str.strip_suffix('s') {
+        u64::from_str(seconds).ok()?.checked_mul(1_000)
+    } else {
+        u64::from_str(delay_str).ok()
+    }
+}
+
+/// Parse a string representing a percentage and return it as a number between 0 and 100.
+///
+/// The trailing "%" is optional, and fractions are allowed.
+///
+/// # Example
+/// ```rust
+/// use swanling::util;
+///
+/// // Percentages can include a fraction.
+/// assert_eq!(util::parse_percentage("0.5%"), Some(0.5));
+///
+/// // The trailing "%" is optional.
+/// assert_eq!(util::parse_percentage("10"), Some(10.0));
+///
+/// // Percentages larger than 100 are invalid.
+/// assert_eq!(util::parse_percentage("150%"), None);
+/// ```
+pub fn parse_percentage(percentage_str: &str) -> Option<f64> {
+    let percentage_str = percentage_str.trim();
+    let percentage = f64::from_str(
+        percentage_str
+            .strip_suffix('%')
+            .unwrap_or(percentage_str)
+            .trim(),
+    )
+    .ok()?;
+    if (0.0..=100.0).contains(&percentage) {
+        Some(percentage)
+    } else {
+        None
+    }
+}
+
 /// Sleep for a specified duration, minus the time spent doing other things.
 ///
 /// # Example
@@ -335,6 +400,8 @@ pub fn ms_timer_expired(started: time::Instant, elapsed: usize) -> bool {
 
 /// Convert optional string to f32, otherwise defaulting to 1.0.
 ///
+/// If the hatch rate is a ramp, such as `1..20/5m`, the starting rate is returned.
+///
 /// # Example
 /// ```rust
 /// use swanling::util;
@@ -353,11 +420,51 @@ pub fn ms_timer_expired(started: time::Instant, elapsed: usize) -> bool {
 ///
 /// // No number returns the defaut of 1.0.
 /// assert_eq!(util::get_hatch_rate(None), 1.0);
+///
+/// // A ramp returns the starting rate.
+/// assert_eq!(util::get_hatch_rate(Some("2..20/5m".to_string())), 2.0);
 /// ```
 pub fn get_hatch_rate(hatch_rate: Option<String>) -> f32 {
+    get_hatch_rate_at(hatch_rate, 0.0)
+}
+
+/// Determine the hatch rate after users have been launching for `elapsed` seconds.
+///
+/// The hatch rate can be a number of users launched per second, or a ramp that
+/// changes the rate over time. A ramp is formatted `FROM..TO/TIME`, where TIME is
+/// a time span as accepted by [`parse_timespan`](./fn.parse_timespan.html). By
+/// default the rate increases linearly, append `:exp` to increase it exponentially.
+/// Once TIME has elapsed, users are launched at the TO rate. Invalid hatch rates
+/// default to 1.0.
+///
+/// # Example
+/// ```rust
+/// use swanling::util;
+///
+/// // A number is a constant hatch rate.
+/// assert_eq!(util::get_hatch_rate_at(Some("4".to_string()), 30.0), 4.0);
+///
+/// // Linearly ramp from 1 to 21 users per second over 10 seconds.
+/// assert_eq!(util::get_hatch_rate_at(Some("1..21/10s".to_string()), 5.0), 11.0);
+///
+/// // Exponentially ramp from 1 to 100 users per second over 10 seconds.
+/// assert_eq!(util::get_hatch_rate_at(Some("1..100/10s:exp".to_string()), 5.0), 10.0);
+///
+/// // After the ramp, the final rate is used.
+/// assert_eq!(util::get_hatch_rate_at(Some("1..21/10s".to_string()), 60.0), 21.0);
+/// ```
+pub fn get_hatch_rate_at(hatch_rate: Option<String>, elapsed: f32) -> f32 {
     match hatch_rate {
-        Some(h) => match h.parse::<f32>() {
-            Ok(rate) => rate,
+        Some(h) => match parse_hatch_rate(&h) {
+            Ok((from, to, over, exponential)) => {
+                if over == 0 || elapsed >= over as f32 {
+                    to
+                } else if exponential {
+                    from * (to / from).powf(elapsed / over as f32)
+                } else {
+                    from + (to - from) * elapsed / over as f32
+                }
+            }
             Err(e) => {
                 warn!(
                     "failed to convert hatch rate {} to float: {}, defaulting to 1.0",
@@ -370,6 +477,64 @@ pub fn get_hatch_rate(hatch_rate: Option<String>) -> f32 {
     }
 }
 
+/// Helper function to determine if a hatch rate can be parsed, either as a number
+/// of users launched per second, or as a ramp such as `1..20/5m`. All rates must
+/// be greater than 0.
+///
+/// # Example
+/// ```rust
+/// use swanling::util;
+///
+/// assert_eq!(util::is_valid_hatch_rate("1.5"), true);
+/// assert_eq!(util::is_valid_hatch_rate("1..20/5m"), true);
+/// assert_eq!(util::is_valid_hatch_rate("1..1000/10m:exp"), true);
+///
+/// // A ramp requires a time span.
+/// assert_eq!(util::is_valid_hatch_rate("1..20"), false);
+///
+/// // Rates must be greater than 0.
+/// assert_eq!(util::is_valid_hatch_rate("0..20/5m"), false);
+/// ```
+pub fn is_valid_hatch_rate(hatch_rate: &str) -> bool {
+    match parse_hatch_rate(hatch_rate) {
+        Ok((from, to, _, _)) => from > 0.0 && to > 0.0,
+        Err(_) => false,
+    }
+}
+
+// Split a hatch rate into the starting rate, the final rate, the number of seconds
+// to ramp between them, and whether or not the ramp is exponential. A constant hatch
+// rate starts and ends at the same rate.
+fn parse_hatch_rate(hatch_rate: &str) -> Result<(f32, f32, usize, bool), String> {
+    let (from, rest) = match hatch_rate.split_once("..") {
+        Some(ramp) => ramp,
+        None => {
+            let rate = hatch_rate.parse::<f32>().map_err(|e| e.to_string())?;
+            return Ok((rate, rate, 0, false));
+        }
+    };
+    let (to, over) = rest
+        .split_once('/')
+        .ok_or_else(|| "a ramp requires a time span".to_string())?;
+    let (over, exponential) = match over.strip_suffix(":exp") {
+        Some(over) => (over, true),
+        None => (over, false),
+    };
+    let from = from.parse::<f32>().map_err(|e| e.to_string())?;
+    let to = to.parse::<f32>().map_err(|e| e.to_string())?;
+    if !Regex::new(r"^(\d+|(\d+h)?(\d+m)?(\d+s)?)$")
+        .unwrap()
+        .is_match(over)
+    {
+        return Err(format!("invalid time span {}", over));
+    }
+    let over = parse_timespan(over);
+    if over == 0 {
+        return Err("a ramp requires a time span".to_string());
+    }
+    Ok((from, to, over, exponential))
+}
+
 /// Helper function to determine if a host can be parsed.
 ///
 /// # Example
@@ -398,14 +563,19 @@ pub fn is_valid_host(host: &str) -> Result<bool, SwanlingError> {
 }
 
 // Internal helper to configure the control-c handler. Shutdown cleanly on the first
-// ctrl-c. Exit abruptly on the second ctrl-c.
-pub(crate) fn setup_ctrlc_handler(canceled: &Arc<AtomicBool>) {
+// ctrl-c. Stop waiting for users to exit on the second ctrl-c, and exit abruptly on the
+// third ctrl-c.
+pub(crate) fn setup_ctrlc_handler(canceled: &Arc<AtomicBool>, forced: &SwanlingFlag) {
     let caught_ctrlc = canceled.clone();
+    let forced = forced.clone();
     match ctrlc::set_handler(move || {
         // We've caught a ctrl-c, determine if it's the first time or an additional time.
-        if caught_ctrlc.load(Ordering::SeqCst) {
+        if forced.is_set() {
             warn!("caught another ctrl-c, exiting immediately...");
             std::process::exit(1);
+        } else if caught_ctrlc.load(Ordering::SeqCst) {
+            warn!("caught another ctrl-c, no longer waiting for users to exit...");
+            forced.set();
         } else {
             warn!("caught ctrl-c, stopping...");
             caught_ctrlc.store(true, Ordering::SeqCst);
@@ -418,6 +588,151 @@ pub(crate) fn setup_ctrlc_handler(canceled: &Arc<AtomicBool>) {
     }
 }
 
+/// Realistic browser user agents returned by [`random_user_agent`](./fn.random_user_agent.html).
+pub const USER_AGENTS: &[&str] = &[
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.0.0",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:121.0) Gecko/20100101 Firefox/121.0",
+    "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15",
+    "Mozilla/5.0 (iPhone; CPU iPhone OS 17_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Mobile/15E148 Safari/604.1",
+    "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36",
+    "Mozilla/5.0 (Linux; Android 13; SM-S918B) AppleWebKit/537.36 (KHTML, like Gecko) SamsungBrowser/23.0 Chrome/115.0.0.0 Mobile Safari/537.36",
+];
+
+/// Choose an item from a list of items and their weights, so an item with weight 2 is
+/// chosen twice as often as an item with weight 1. Items with a weight of 0 are never
+/// chosen. Returns `None` if there are no items with a weight.
+///
+/// Pass [`SwanlingUser::rng`](../swanling/struct.SwanlingUser.html#method.rng) to make the
+/// choice repeatable with `--seed`.
+///
+/// # Example
+/// ```rust
+/// use swanling::prelude::*;
+/// use swanling::util;
+///
+/// let mut task = task!(browse_task);
+///
+/// /// Browse the home page most of the time, and search occasionally.
+/// async fn browse_task(user: &SwanlingUser) -> SwanlingTaskResult {
+///     let pages = [("/", 8), ("/about", 1), ("/search?q=swanling", 1)];
+///     let page = util::weighted_choice(&mut *user.rng(), &pages).unwrap();
+///     let _swanling = user.get(page).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub fn weighted_choice<'a, T, R: Rng + ?Sized>(
+    rng: &mut R,
+    items: &'a [(T, usize)],
+) -> Option<&'a T> {
+    let total: usize = items.iter().map(|(_, weight)| weight).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut choice = rng.gen_range(0..total);
+    for (item, weight) in items {
+        if choice < *weight {
+            return Some(item);
+        }
+        choice -= weight;
+    }
+
+    None
+}
+
+/// Selects items following a Zipfian distribution, where the item ranked `k` is chosen in
+/// proportion to `1 / k^exponent`. A few items are therefore chosen very often, modeling
+/// hot keys such as popular products or trending articles, and most items rarely.
+///
+/// Pass [`SwanlingUser::rng`](../swanling/struct.SwanlingUser.html#method.rng) to make the
+/// selection repeatable with `--seed`.
+///
+/// # Example
+/// ```rust
+/// use swanling::prelude::*;
+/// use swanling::util::Zipf;
+///
+/// let mut task = task!(product_task);
+///
+/// /// View one of 1,000 products, the first ones most often.
+/// async fn product_task(user: &SwanlingUser) -> SwanlingTaskResult {
+///     let products = Zipf::new(1_000, 1.0).unwrap();
+///     let product = products.sample(&mut *user.rng());
+///     let _swanling = user.get(&format!("/product/{}", product)).await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Zipf {
+    /// The cumulative probability of choosing each item, or any item ranked before it.
+    cumulative: Vec<f64>,
+}
+impl Zipf {
+    /// Prepare to select from `items` items. An `exponent` of 0 selects all items equally
+    /// often, and the higher it is the more often the first items are selected, 1 is
+    /// typical. Returns `None` if there are no items, or the exponent is negative.
+    pub fn new(items: usize, exponent: f64) -> Option<Self> {
+        if items == 0 || !exponent.is_finite() || exponent < 0.0 {
+            return None;
+        }
+
+        let mut total = 0.0;
+        let mut cumulative: Vec<f64> = (1..=items)
+            .map(|rank| {
+                total += 1.0 / (rank as f64).powf(exponent);
+                total
+            })
+            .collect();
+        for probability in cumulative.iter_mut() {
+            *probability /= total;
+        }
+
+        Some(Zipf { cumulative })
+    }
+
+    /// Select an item, returning its index from 0, the most often selected item, to one less
+    /// than the number of items.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let choice: f64 = rng.gen();
+        let index = self
+            .cumulative
+            .partition_point(|probability| *probability <= choice);
+        min(index, self.cumulative.len() - 1)
+    }
+}
+
+/// Returns a realistic browser user agent, chosen at random from
+/// [`USER_AGENTS`](./constant.USER_AGENTS.html).
+///
+/// Pass [`SwanlingUser::rng`](../swanling/struct.SwanlingUser.html#method.rng) to make the
+/// choice repeatable with `--seed`.
+///
+/// # Example
+/// ```rust
+/// use swanling::prelude::*;
+/// use swanling::util;
+///
+/// let mut task = task!(get_task);
+///
+/// /// Load the home page with a browser user agent.
+/// async fn get_task(user: &SwanlingUser) -> SwanlingTaskResult {
+///     let user_agent = util::random_user_agent(&mut *user.rng());
+///     let request_builder = user.swanling_get("/").await?.header("User-Agent", user_agent);
+///     let _swanling = user.swanling_send(request_builder, None).await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub fn random_user_agent<R: Rng + ?Sized>(rng: &mut R) -> &'static str {
+    USER_AGENTS[rng.gen_range(0..USER_AGENTS.len())]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -449,6 +764,29 @@ mod tests {
         assert_eq!(parse_timespan("100hourblah"), 360000);
     }
 
+    #[test]
+    fn milliseconds() {
+        assert_eq!(parse_milliseconds("0"), Some(0));
+        assert_eq!(parse_milliseconds("50"), Some(50));
+        assert_eq!(parse_milliseconds("50ms"), Some(50));
+        assert_eq!(parse_milliseconds("1s"), Some(1000));
+        assert_eq!(parse_milliseconds("1m"), None);
+        assert_eq!(parse_milliseconds("ms"), None);
+        assert_eq!(parse_milliseconds("foo"), None);
+    }
+
+    #[test]
+    fn percentage() {
+        assert_eq!(parse_percentage("0"), Some(0.0));
+        assert_eq!(parse_percentage("1%"), Some(1.0));
+        assert_eq!(parse_percentage(" 2.5 % "), Some(2.5));
+        assert_eq!(parse_percentage("100%"), Some(100.0));
+        assert_eq!(parse_percentage("101%"), None);
+        assert_eq!(parse_percentage("-1%"), None);
+        assert_eq!(parse_percentage("NaN"), None);
+        assert_eq!(parse_percentage("foo"), None);
+    }
+
     #[test]
     fn greatest_common_divisor() {
         assert_eq!(gcd(2, 4), 2);
@@ -569,6 +907,41 @@ mod tests {
         assert!((get_hatch_rate(Some("g".to_string())) - 1.0).abs() < f32::EPSILON);
         assert!((get_hatch_rate(Some("2.1f".to_string())) - 1.0).abs() < f32::EPSILON);
         assert!((get_hatch_rate(Some("1.1.1".to_string())) - 1.0).abs() < f32::EPSILON);
+        // Ramps start at the first rate.
+        assert!((get_hatch_rate(Some("5..20/1m".to_string())) - 5.0).abs() < f32::EPSILON);
+        assert!((get_hatch_rate(Some(".5..20/1m:exp".to_string())) - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn hatch_rate_ramp() {
+        let linear = Some("2..12/1m".to_string());
+        assert!((get_hatch_rate_at(linear.clone(), 0.0) - 2.0).abs() < f32::EPSILON);
+        assert!((get_hatch_rate_at(linear.clone(), 30.0) - 7.0).abs() < f32::EPSILON);
+        assert!((get_hatch_rate_at(linear.clone(), 60.0) - 12.0).abs() < f32::EPSILON);
+        assert!((get_hatch_rate_at(linear, 600.0) - 12.0).abs() < f32::EPSILON);
+
+        // Ramps can also decrease.
+        let decreasing = Some("12..2/10".to_string());
+        assert!((get_hatch_rate_at(decreasing, 5.0) - 7.0).abs() < f32::EPSILON);
+
+        let exponential = Some("1..10000/4m:exp".to_string());
+        assert!((get_hatch_rate_at(exponential.clone(), 0.0) - 1.0).abs() < 0.001);
+        assert!((get_hatch_rate_at(exponential.clone(), 60.0) - 10.0).abs() < 0.001);
+        assert!((get_hatch_rate_at(exponential.clone(), 120.0) - 100.0).abs() < 0.01);
+        assert!((get_hatch_rate_at(exponential, 240.0) - 10000.0).abs() < f32::EPSILON);
+
+        // Invalid ramps default to 1.0.
+        assert!((get_hatch_rate_at(Some("1..20".to_string()), 5.0) - 1.0).abs() < f32::EPSILON);
+        assert!((get_hatch_rate_at(Some("1..20/foo".to_string()), 5.0) - 1.0).abs() < f32::EPSILON);
+        assert!((get_hatch_rate_at(Some("a..20/1m".to_string()), 5.0) - 1.0).abs() < f32::EPSILON);
+
+        assert!(is_valid_hatch_rate("1"));
+        assert!(is_valid_hatch_rate("1..20/5m"));
+        assert!(is_valid_hatch_rate("1..20/1h30m:exp"));
+        assert!(!is_valid_hatch_rate("0"));
+        assert!(!is_valid_hatch_rate("g"));
+        assert!(!is_valid_hatch_rate("1..0/5m"));
+        assert!(!is_valid_hatch_rate("1..20/5m:log"));
     }
 
     #[test]
@@ -587,4 +960,43 @@ mod tests {
         assert!(is_valid_host("http:///example.com").is_ok());
         assert!(!is_valid_host("http:// example.com").is_ok());
     }
+
+    #[test]
+    fn generators() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let items = [("never", 0), ("a", 1), ("b", 3)];
+        let mut chosen = BTreeMap::new();
+        for _ in 0..1_000 {
+            *chosen
+                .entry(*weighted_choice(&mut rng, &items).unwrap())
+                .or_insert(0) += 1;
+        }
+        assert!(!chosen.contains_key("never"));
+        assert!(chosen["b"] > chosen["a"] * 2);
+        assert!(weighted_choice::<&str, _>(&mut rng, &[]).is_none());
+        assert!(weighted_choice(&mut rng, &[("never", 0)]).is_none());
+
+        assert!(Zipf::new(0, 1.0).is_none());
+        assert!(Zipf::new(10, -1.0).is_none());
+        let zipf = Zipf::new(10, 1.0).unwrap();
+        let mut selected = [0; 10];
+        for _ in 0..1_000 {
+            selected[zipf.sample(&mut rng)] += 1;
+        }
+        assert!(selected[0] > selected[1]);
+        assert!(selected[1] > selected[9]);
+        assert_eq!(Zipf::new(1, 1.0).unwrap().sample(&mut rng), 0);
+
+        assert!(USER_AGENTS.contains(&random_user_agent(&mut rng)));
+
+        // The same seed makes the same choices.
+        let mut first = StdRng::seed_from_u64(7);
+        let mut second = StdRng::seed_from_u64(7);
+        for _ in 0..10 {
+            assert_eq!(zipf.sample(&mut first), zipf.sample(&mut second));
+        }
+    }
 }
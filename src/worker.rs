@@ -1,15 +1,29 @@
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
 use gumdrop::Options;
 use nng::*;
 use serde::{Deserialize, Serialize};
-use std::io::BufWriter;
+use std::collections::BTreeMap;
+use std::io::Write;
 use std::sync::atomic::Ordering;
 use std::{thread, time};
 use url::Url;
 
 const EMPTY_ARGS: Vec<&str> = vec![];
 
+/// Metrics larger than this many bytes are compressed before being pushed to the Manager.
+const COMPRESSION_THRESHOLD: usize = 1024;
+
+/// First byte of the metrics pushed to the Manager, identifying how they're encoded.
+const PLAIN_PAYLOAD: u8 = 0;
+const DEFLATE_PAYLOAD: u8 = 1;
+
 use crate::manager::SwanlingUserInitializer;
-use crate::metrics::{SwanlingErrorMetrics, SwanlingRequestMetrics, SwanlingTaskMetrics};
+use crate::metrics::{
+    SwanlingAssertionMetrics, SwanlingErrorMetrics, SwanlingMetrics, SwanlingPollMetrics,
+    SwanlingRequestMetrics, SwanlingTaskMetrics,
+};
 use crate::swanling::{SwanlingUser, SwanlingUserCommand};
 use crate::{get_worker_id, AttackMode, SwanlingAttack, SwanlingConfiguration, WORKER_ID};
 
@@ -24,6 +38,14 @@ pub enum GaggleMetrics {
     Tasks(SwanlingTaskMetrics),
     /// Swanling error metrics.
     Errors(SwanlingErrorMetrics),
+    /// Users removed from the load test, by error.
+    RemovedUsers(BTreeMap<String, usize>),
+    /// Assertions checked by users, by label.
+    Assertions(SwanlingAssertionMetrics),
+    /// Polls finished by users, by name.
+    Polls(SwanlingPollMetrics),
+    /// How many users the Worker has launched.
+    Users(usize),
 }
 
 // If pipe closes unexpectedly, panic.
@@ -136,16 +158,7 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
         if worker_id == 0 {
             worker_id = initializer.worker_id;
         }
-        let user = SwanlingUser::new(
-            initializer.task_sets_index,
-            Url::parse(&initializer.base_url).unwrap(),
-            initializer.min_wait,
-            initializer.max_wait,
-            &initializer.config,
-            swanling_attack.metrics.hash,
-        )
-        .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
-        .expect("failed to create socket");
+        let user = initialize_user(swanling_attack, &initializer);
 
         // The initializer.config and run_time are the same for all users, only copy it
         // one time.
@@ -184,13 +197,13 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
         match command {
             // Break out of loop and start the load test.
             SwanlingUserCommand::Run => break,
-            // Exit worker process immediately.
+            // Stop the Worker without running the load test.
             SwanlingUserCommand::Exit => {
                 warn!(
                     "[{}] received SwanlingUserCommand::Exit command from manager",
                     get_worker_id()
                 );
-                std::process::exit(0);
+                return swanling_attack.clone();
             }
             // Sleep and then loop again.
             _ => {
@@ -222,6 +235,9 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
     // This is a Worker instance, not a Manager instance.
     worker_swanling_attack.configuration.manager = false;
     worker_swanling_attack.configuration.worker = true;
+    // Workers are controlled by the Manager, don't launch the Manager's Controllers.
+    worker_swanling_attack.configuration.no_telnet = true;
+    worker_swanling_attack.configuration.no_websocket = true;
     // The request_log option is configured on the Worker.
     worker_swanling_attack.configuration.request_log =
         swanling_attack.configuration.request_log.to_string();
@@ -240,15 +256,32 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
     // The error_format option is configured on the Worker.
     worker_swanling_attack.configuration.error_format =
         swanling_attack.configuration.error_format.clone();
+    // The error_log_window option is configured on the Worker.
+    worker_swanling_attack.configuration.error_log_window =
+        swanling_attack.configuration.error_log_window.clone();
     // The debug_log option is configured on the Worker.
     worker_swanling_attack.configuration.debug_log =
         swanling_attack.configuration.debug_log.to_string();
     // The debug_format option is configured on the Worker.
     worker_swanling_attack.configuration.debug_format =
         swanling_attack.configuration.debug_format.clone();
+    // The debug_body_max_bytes option is configured on the Worker.
+    worker_swanling_attack.configuration.debug_body_max_bytes =
+        swanling_attack.configuration.debug_body_max_bytes;
+    // The debug_redact option is configured on the Worker.
+    worker_swanling_attack.configuration.debug_redact =
+        swanling_attack.configuration.debug_redact.clone();
     // The throttle_requests option is set on the Worker.
     worker_swanling_attack.configuration.throttle_requests =
         swanling_attack.configuration.throttle_requests;
+    worker_swanling_attack.configuration.throttle_plan =
+        swanling_attack.configuration.throttle_plan.clone();
+    // The shutdown_timeout option is configured on the Worker.
+    worker_swanling_attack.configuration.shutdown_timeout =
+        swanling_attack.configuration.shutdown_timeout.clone();
+    // The bind_address option is configured on the Worker.
+    worker_swanling_attack.configuration.bind_address =
+        swanling_attack.configuration.bind_address.clone();
     worker_swanling_attack.attack_mode = AttackMode::Worker;
     worker_swanling_attack.defaults = swanling_attack.defaults.clone();
 
@@ -259,25 +292,129 @@ pub(crate) async fn worker_main(swanling_attack: &SwanlingAttack) -> SwanlingAtt
         .expect("failed to launch SwanlingAttack")
 }
 
-// Push metrics to manager.
+/// Allocate the state of a user assigned to this Worker by the Manager.
+pub(crate) fn initialize_user(
+    swanling_attack: &SwanlingAttack,
+    initializer: &SwanlingUserInitializer,
+) -> SwanlingUser {
+    let mut user = SwanlingUser::new(
+        initializer.task_sets_index,
+        Url::parse(&initializer.base_url).unwrap(),
+        initializer.min_wait,
+        initializer.max_wait,
+        &initializer.config,
+        swanling_attack.metrics.hash,
+    )
+    .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
+    .expect("failed to create socket");
+    // Simulate the task set's network conditions, if any.
+    user.bandwidth = swanling_attack.task_sets[initializer.task_sets_index].bandwidth;
+    user.added_latency = swanling_attack.task_sets[initializer.task_sets_index].added_latency;
+    user.task_set_base_url = swanling_attack.task_sets[initializer.task_sets_index]
+        .base_url
+        .clone();
+    user.redirect_policy = swanling_attack.task_sets[initializer.task_sets_index]
+        .redirect_policy
+        .clone();
+    user
+}
+
+/// Collect the metrics a Worker pushes to the Manager. The Worker resets its metrics after
+/// each push, so only tasks that ran and metrics that changed since the previous push are
+/// included. Errors are only pushed when the Worker exits.
+pub(crate) fn changed_gaggle_metrics(
+    metrics: &SwanlingMetrics,
+    include_errors: bool,
+) -> Vec<GaggleMetrics> {
+    let mut gaggle_metrics = Vec::new();
+    if !metrics.requests.is_empty() {
+        gaggle_metrics.push(GaggleMetrics::Requests(metrics.requests.clone()));
+    }
+    if include_errors && !metrics.errors.is_empty() {
+        gaggle_metrics.push(GaggleMetrics::Errors(metrics.errors.clone()));
+    }
+    // The Manager merges each task by its index, so tasks that didn't run are left out.
+    let tasks: SwanlingTaskMetrics = metrics
+        .tasks
+        .iter()
+        .map(|task_set| {
+            task_set
+                .iter()
+                .filter(|task| task.counter > 0)
+                .cloned()
+                .collect()
+        })
+        .collect();
+    if tasks.iter().any(|task_set| !task_set.is_empty()) {
+        gaggle_metrics.push(GaggleMetrics::Tasks(tasks));
+    }
+    if !metrics.removed_users.is_empty() {
+        gaggle_metrics.push(GaggleMetrics::RemovedUsers(metrics.removed_users.clone()));
+    }
+    if !metrics.assertions.is_empty() {
+        gaggle_metrics.push(GaggleMetrics::Assertions(metrics.assertions.clone()));
+    }
+    if !metrics.polls.is_empty() {
+        gaggle_metrics.push(GaggleMetrics::Polls(metrics.polls.clone()));
+    }
+    gaggle_metrics.push(GaggleMetrics::Users(metrics.users));
+    gaggle_metrics
+}
+
+/// Serialize the metrics pushed to the Manager, compressing them if they're large.
+pub(crate) fn encode_gaggle_metrics(metrics: &[GaggleMetrics]) -> Vec<u8> {
+    let serialized = serde_cbor::to_vec(&metrics)
+        .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
+        .expect("failed to serialize GaggleMetrics");
+
+    if serialized.len() <= COMPRESSION_THRESHOLD {
+        let mut payload = Vec::with_capacity(serialized.len() + 1);
+        payload.push(PLAIN_PAYLOAD);
+        payload.extend_from_slice(&serialized);
+        return payload;
+    }
+
+    let mut encoder = DeflateEncoder::new(vec![DEFLATE_PAYLOAD], Compression::fast());
+    let payload = encoder
+        .write_all(&serialized)
+        .and_then(|_| encoder.finish())
+        .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
+        .expect("failed to compress GaggleMetrics");
+    debug!(
+        "[{}] compressed metrics from {} to {} bytes",
+        get_worker_id(),
+        serialized.len(),
+        payload.len()
+    );
+    payload
+}
+
+/// Deserialize the metrics pushed by a Worker, decompressing them if necessary.
+pub(crate) fn decode_gaggle_metrics(
+    payload: &[u8],
+) -> std::result::Result<Vec<GaggleMetrics>, serde_cbor::Error> {
+    match payload.split_first() {
+        Some((&PLAIN_PAYLOAD, serialized)) => serde_cbor::from_slice(serialized),
+        Some((&DEFLATE_PAYLOAD, compressed)) => {
+            serde_cbor::from_reader(DeflateDecoder::new(compressed))
+        }
+        _ => Err(serde::de::Error::custom("unknown metrics encoding")),
+    }
+}
+
+// Push metrics to manager. Returns None if the manager told the worker to exit, otherwise
+// any users the manager assigned to the worker in its reply, taken over from a drained
+// worker.
 pub fn push_metrics_to_manager(
     manager: &Socket,
     metrics: Vec<GaggleMetrics>,
     get_response: bool,
-) -> bool {
+) -> Option<Vec<SwanlingUserInitializer>> {
     debug!("[{}] pushing metrics to manager", get_worker_id(),);
-    let mut message = BufWriter::new(Message::new());
-
-    serde_cbor::to_writer(&mut message, &metrics)
-        .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
-        .expect("failed to serialize GaggleMetrics");
+    let message = Message::from(encode_gaggle_metrics(&metrics).as_slice());
 
     manager
-        .try_send(
-            message
-                .into_inner()
-                .expect("failed to extract nng message from buffer"),
-        )
+        .try_send(message)
         .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
         .expect("communication failure");
 
@@ -288,6 +425,18 @@ pub fn push_metrics_to_manager(
             .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
             .expect("error receiving manager message");
 
+        // The manager replies with a command, or with additional users to launch.
+        if let Ok(initializers) =
+            serde_cbor::from_reader::<Vec<SwanlingUserInitializer>, _>(msg.as_slice())
+        {
+            info!(
+                "[{}] manager assigned {} more users",
+                get_worker_id(),
+                initializers.len()
+            );
+            return Some(initializers);
+        }
+
         let command: SwanlingUserCommand = serde_cbor::from_reader(msg.as_slice())
             .map_err(|error| eprintln!("{:?} worker_id({})", error, get_worker_id()))
             .expect("invalid message");
@@ -299,8 +448,8 @@ pub fn push_metrics_to_manager(
             );
             // Shutting down, register shutdown pipe handler.
             register_shutdown_pipe_handler(manager);
-            return false;
+            return None;
         }
     }
-    true
+    Some(Vec::new())
 }
@@ -0,0 +1,145 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ERROR_PATH: &str = "/error";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 10;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_error(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ERROR_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Stop the load test early when too many requests fail.
+fn test_abort_on_error_rate() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let error = server.mock(|when, then| {
+        when.method(GET).path(ERROR_PATH);
+        then.status(500);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--abort-on-error-rate",
+            "50%@2s",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(get_error))
+                .set_wait_time(0, 1)
+                .unwrap(),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm the load test was stopped before the run time expired, and flagged.
+    assert!(error.hits() > 0);
+    assert!(swanling_metrics.duration < RUN_TIME);
+    let aborted = swanling_metrics.aborted.as_ref().unwrap();
+    assert!(aborted.contains("--abort-on-error-rate 50%@2s"));
+    assert!(swanling_metrics.to_string().contains("LOAD TEST ABORTED"));
+}
+
+#[test]
+// Don't stop the load test while requests are succeeding.
+fn test_abort_on_error_rate_healthy() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            "3",
+            "--abort-on-error-rate",
+            "50%@1s",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm the load test ran to completion.
+    assert!(index.hits() > 0);
+    assert!(swanling_metrics.duration == 3);
+    assert!(swanling_metrics.aborted.is_none());
+}
+
+#[test]
+// Confirm --abort-on-error-rate must be valid.
+fn test_abort_on_error_rate_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in [
+        // A window is required.
+        vec!["--abort-on-error-rate", "25%"],
+        // The percentage can't be more than 100%.
+        vec!["--abort-on-error-rate", "125%@30s"],
+        // The window can't be empty.
+        vec!["--abort-on-error-rate", "25%@0s"],
+        // The error rate can't be measured without metrics.
+        vec!["--abort-on-error-rate", "25%@30s", "--no-metrics"],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let swanling = user.get(INDEX_PATH).await?;
+
+    if let Ok(response) = swanling.response {
+        let text = response.text().await.unwrap_or_default();
+        user.assert(text.contains("Welcome"), "welcome");
+        user.assert(text.contains("Goodbye"), "goodbye");
+    }
+
+    Ok(())
+}
+
+#[test]
+// Assertions are counted separately from request failures.
+fn test_assertions() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200).body("Welcome!");
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--no-reset-metrics",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Every request succeeded, even though one of the assertions always failed.
+    let requests = index.hits();
+    assert!(requests > 0);
+    let request = swanling_metrics.requests.get("GET /").unwrap();
+    assert!(request.fail_count == 0);
+
+    // Each request checked both assertions.
+    let welcome = swanling_metrics.assertions.get("welcome").unwrap();
+    assert!(welcome.passed == requests);
+    assert!(welcome.failed == 0);
+    let goodbye = swanling_metrics.assertions.get("goodbye").unwrap();
+    assert!(goodbye.passed == 0);
+    assert!(goodbye.failed == requests);
+}
@@ -0,0 +1,104 @@
+use httpmock::{Method::GET, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::AttackPhase;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Control a load test from the application embedding it.
+async fn test_attack_handle() {
+    // Start the mock server.
+    let server = MockServer::start_async().await;
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server
+        .mock_async(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        })
+        .await;
+
+    // Build configuration, running much longer than the test so it must be stopped.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            "60",
+            "--no-telnet",
+            "--no-websocket",
+        ],
+    );
+    let (handle, swanling_attack) = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    )
+    .execute_with_handle();
+    let swanling_attack = tokio::spawn(swanling_attack);
+
+    // Wait for the snapshot to include requests.
+    let mut requested = false;
+    for _ in 0..50 {
+        let metrics = handle.metrics_snapshot().await.unwrap();
+        if !metrics.requests.is_empty() {
+            requested = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(requested);
+
+    // Wait for all users to launch.
+    let mut running = false;
+    for _ in 0..50 {
+        let status = handle.status().await.unwrap();
+        if status.phase == AttackPhase::Running {
+            assert_eq!(status.users, USERS);
+            assert_eq!(status.target_users, USERS);
+            assert!(status.remaining.unwrap() <= 60);
+            running = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(running);
+
+    // Stopping leaves Swanling idle, so it can't be stopped twice.
+    assert!(handle.stop().await);
+    assert!(!handle.stop().await);
+    assert_eq!(handle.status().await.unwrap().phase, AttackPhase::Idle);
+
+    // Shutting down ends the load test.
+    assert!(handle.shutdown().await);
+    let swanling_metrics = tokio::time::timeout(Duration::from_secs(10), swanling_attack)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert!(swanling_metrics.duration < 60);
+    assert!(index.hits_async().await > 0);
+
+    // Requests are ignored once the load test has finished.
+    assert!(handle.metrics_snapshot().await.is_none());
+    assert!(handle.status().await.is_none());
+    assert!(!handle.shutdown().await);
+}
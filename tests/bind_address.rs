@@ -0,0 +1,119 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Run a load test sending requests from the specified addresses, and return the index
+// request metrics.
+fn run_bind_address_test(
+    server: &MockServer,
+    addresses: &[&str],
+) -> swanling::metrics::SwanlingRequestMetricAggregate {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration_options = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+    ];
+    for address in addresses {
+        configuration_options.extend(vec!["--bind-address", address]);
+    }
+
+    // Build configuration.
+    let configuration = common::build_configuration(server, configuration_options);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            // Pause after each request, as requests that can't connect fail immediately.
+            &taskset!("LoadTest")
+                .set_wait_time(1, 2)
+                .unwrap()
+                .register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap()
+        .clone()
+}
+
+#[test]
+// Send requests from multiple local addresses.
+fn test_bind_address() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Both addresses are on the loopback interface, so can reach the mock server.
+    let index_metrics = run_bind_address_test(&server, &["127.0.0.1", "127.0.0.2"]);
+
+    assert!(index.hits() > 0);
+    assert!(index_metrics.success_count > 0);
+    assert!(index_metrics.fail_count == 0);
+}
+
+#[test]
+// Requests fail if they can't be sent from the bind address.
+fn test_bind_address_unavailable() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // This documentation address isn't assigned to any local interface.
+    let index_metrics = run_bind_address_test(&server, &["192.0.2.1"]);
+
+    assert!(index.hits() == 0);
+    assert!(index_metrics.success_count == 0);
+    assert!(index_metrics.fail_count > 0);
+}
+
+#[test]
+// Confirm --bind-address must be an IP address.
+fn test_bind_address_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration = common::build_configuration(&server, vec!["--bind-address", "foo"]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
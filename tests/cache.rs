@@ -0,0 +1,160 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::metrics::SwanlingMetrics;
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const ETAG_PATH: &str = "/etag";
+const MODIFIED_PATH: &str = "/modified";
+const UNCACHED_PATH: &str = "/uncached";
+
+// Indexes to the above paths.
+const ETAG_NOT_MODIFIED_KEY: usize = 0;
+const ETAG_KEY: usize = 1;
+const MODIFIED_NOT_MODIFIED_KEY: usize = 2;
+const MODIFIED_KEY: usize = 3;
+const UNCACHED_KEY: usize = 4;
+
+// The validators returned by the mock server.
+const ETAG: &str = "\"v1\"";
+const LAST_MODIFIED: &str = "Wed, 21 Oct 2015 07:28:00 GMT";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_etag(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get_cached(ETAG_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_modified(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get_cached(MODIFIED_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_uncached(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get_cached(UNCACHED_PATH).await?;
+    Ok(())
+}
+
+// Sets up the endpoints used to test cache revalidation. Conditional requests are
+// matched first.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(ETAG_PATH)
+                .header("If-None-Match", ETAG);
+            then.status(304);
+        }),
+        server.mock(|when, then| {
+            when.method(GET).path(ETAG_PATH);
+            then.status(200).header("ETag", ETAG).body("etag");
+        }),
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(MODIFIED_PATH)
+                .header("If-Modified-Since", LAST_MODIFIED);
+            then.status(304);
+        }),
+        server.mock(|when, then| {
+            when.method(GET).path(MODIFIED_PATH);
+            then.status(200)
+                .header("Last-Modified", LAST_MODIFIED)
+                .body("modified");
+        }),
+        server.mock(|when, then| {
+            when.method(GET).path(UNCACHED_PATH);
+            then.status(200).body("uncached");
+        }),
+    ]
+}
+
+// Run a load test requesting all paths with the given client mode.
+fn run_load_test(server: &MockServer, client_mode: SwanlingClientMode) -> SwanlingMetrics {
+    // Build configuration.
+    let configuration = common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(get_etag))
+                .register_task(task!(get_modified))
+                .register_task(task!(get_uncached))
+                .set_client_mode(client_mode),
+            None,
+            None,
+        ),
+        None,
+    )
+}
+
+#[test]
+// Revalidate cached responses on repeat visits.
+fn test_cache_revalidation() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let swanling_metrics = run_load_test(&server, SwanlingClientMode::PerUser);
+
+    // Each user only loaded the cacheable responses one time, then revalidated them.
+    mock_endpoints[ETAG_KEY].assert_hits(USERS);
+    mock_endpoints[MODIFIED_KEY].assert_hits(USERS);
+    assert!(mock_endpoints[ETAG_NOT_MODIFIED_KEY].hits() > 0);
+    assert!(mock_endpoints[MODIFIED_NOT_MODIFIED_KEY].hits() > 0);
+    assert!(mock_endpoints[UNCACHED_KEY].hits() > USERS);
+
+    // Responses that weren't modified are successful, and counted.
+    for path in &[ETAG_PATH, MODIFIED_PATH] {
+        let request = swanling_metrics
+            .requests
+            .get(&format!("GET {}", path))
+            .unwrap();
+        assert!(request.fail_count == 0);
+        assert!(request.not_modified_count > 0);
+        assert!(request.modified_count == 0);
+    }
+    let uncached = swanling_metrics.requests.get("GET /uncached").unwrap();
+    assert!(uncached.not_modified_count == 0);
+    assert!(uncached.modified_count == 0);
+    assert!(swanling_metrics.to_string().contains("CACHE REVALIDATION"));
+}
+
+#[test]
+// Forget cached responses when the client is rebuilt.
+fn test_cache_per_iteration() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let swanling_metrics = run_load_test(&server, SwanlingClientMode::PerIteration);
+
+    // Every iteration starts without any cached responses.
+    assert!(mock_endpoints[ETAG_KEY].hits() > USERS);
+    mock_endpoints[ETAG_NOT_MODIFIED_KEY].assert_hits(0);
+    mock_endpoints[MODIFIED_NOT_MODIFIED_KEY].assert_hits(0);
+    assert!(!swanling_metrics.to_string().contains("CACHE REVALIDATION"));
+}
@@ -0,0 +1,158 @@
+use httpmock::{
+    Method::{DELETE, POST},
+    MockServer,
+};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const ITEMS_PATH: &str = "/items";
+const ITEM_PATH: &str = "/items/42";
+const ITEM_ID: &str = "42";
+const FIRST_PATH: &str = "/first";
+const SECOND_PATH: &str = "/second";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn create_item(user: &SwanlingUser) -> SwanlingTaskResult {
+    let swanling = user.post(ITEMS_PATH, "name=example").await?;
+    let id = swanling.response?.text().await?;
+    user.register_cleanup(move |user| {
+        Box::pin(async move {
+            let _swanling = user.delete(&format!("{}/{}", ITEMS_PATH, id)).await?;
+            Ok(())
+        })
+    });
+    Ok(())
+}
+
+// Test task, registering two cleanup functions.
+pub async fn register_two(user: &SwanlingUser) -> SwanlingTaskResult {
+    user.register_cleanup(|user| {
+        Box::pin(async move {
+            let _swanling = user.delete(FIRST_PATH).await?;
+            Ok(())
+        })
+    });
+    user.register_cleanup(|user| {
+        Box::pin(async move {
+            let _swanling = user.delete(SECOND_PATH).await?;
+            Ok(())
+        })
+    });
+    Ok(())
+}
+
+#[test]
+// Delete each resource created by the load test when users stop.
+fn test_cleanup() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let create = server.mock(|when, then| {
+        when.method(POST).path(ITEMS_PATH);
+        then.status(201).body(ITEM_ID);
+    });
+    let delete = server.mock(|when, then| {
+        when.method(DELETE).path(ITEM_PATH);
+        then.status(204);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(create_item)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Every item created was deleted.
+    assert!(create.hits() > 0);
+    assert!(delete.hits() == create.hits());
+}
+
+#[test]
+// Run cleanup functions the most recently registered first, one time each.
+fn test_cleanup_order() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let first = server.mock(|when, then| {
+        when.method(DELETE).path(FIRST_PATH);
+        then.status(204);
+    });
+    let second = server.mock(|when, then| {
+        when.method(DELETE).path(SECOND_PATH);
+        then.status(204);
+    });
+
+    let request_log = "cleanup-request-log.json";
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            "1",
+            "--hatch-rate",
+            "1",
+            "--run-time",
+            "1",
+            "--request-log",
+            request_log,
+            "--request-format",
+            "json",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(register_two).set_on_start()),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(first.hits() == 1);
+    assert!(second.hits() == 1);
+
+    // The cleanup function registered last ran first.
+    let contents = std::fs::read_to_string(request_log).unwrap();
+    let paths: Vec<String> = contents
+        .lines()
+        .map(|line| {
+            let record: serde_json::Value = serde_json::from_str(line).unwrap();
+            record["url"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert!(paths.len() == 2);
+    assert!(paths[0].ends_with(SECOND_PATH));
+    assert!(paths[1].ends_with(FIRST_PATH));
+
+    common::cleanup_files(vec![request_log]);
+}
@@ -0,0 +1,113 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use reqwest::header::{HeaderMap, HeaderValue};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the endpoints.
+const CUSTOMIZED_KEY: usize = 0;
+const DEFAULT_KEY: usize = 1;
+
+// Header added by the client builder.
+const CUSTOM_HEADER: &str = "x-load-test";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up INDEX_PATH with the custom header, store in vector at CUSTOMIZED_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header(CUSTOM_HEADER, "swanling");
+            then.status(200);
+        }),
+        // Next set up INDEX_PATH without the custom header, store in vector at DEFAULT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Customize each client to send the custom header with every request.
+fn add_custom_header(builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    let mut headers = HeaderMap::new();
+    headers.insert(CUSTOM_HEADER, HeaderValue::from_static("swanling"));
+    builder.default_headers(headers)
+}
+
+// Run a load test with the specified client mode, customizing each client.
+fn run_client_builder_test(server: &MockServer, client_mode: SwanlingClientMode) {
+    // Build configuration.
+    let configuration = common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .set_client_mode(client_mode)
+                .register_task(task!(get_index)),
+            Some(&task!(get_index)),
+            Some(&task!(get_index)),
+        )
+        .set_client_builder(add_custom_header),
+        None,
+    );
+}
+
+#[test]
+// Every user's client, including the test_start and test_stop users, is customized.
+fn test_client_builder() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    run_client_builder_test(&server, SwanlingClientMode::PerUser);
+
+    // Confirm all requests were made by customized clients.
+    assert!(mock_endpoints[CUSTOMIZED_KEY].hits() > 2);
+    assert!(mock_endpoints[DEFAULT_KEY].hits() == 0);
+}
+
+#[test]
+// Clients rebuilt each iteration are also customized.
+fn test_client_builder_per_iteration() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    run_client_builder_test(&server, SwanlingClientMode::PerIteration);
+
+    // Confirm all requests were made by customized clients.
+    assert!(mock_endpoints[CUSTOMIZED_KEY].hits() > 2);
+    assert!(mock_endpoints[DEFAULT_KEY].hits() == 0);
+}
@@ -0,0 +1,202 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const LOGIN_PATH: &str = "/login";
+const PAGE_PATH: &str = "/page";
+const SLOW_PATH: &str = "/slow";
+
+// Indexes to the above paths.
+const LOGIN_KEY: usize = 0;
+const PAGE_WITH_COOKIE_KEY: usize = 1;
+const PAGE_WITHOUT_COOKIE_KEY: usize = 2;
+const SLOW_WITH_COOKIE_KEY: usize = 3;
+const SLOW_WITHOUT_COOKIE_KEY: usize = 4;
+
+// Cookie set when logging in.
+const SESSION_COOKIE: &str = "session";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// How long the server takes to respond to SLOW_PATH.
+const SLOW_TIME: u64 = 500;
+
+// Test task.
+pub async fn login(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(LOGIN_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_page(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(PAGE_PATH).await?;
+    Ok(())
+}
+
+// Test task that logs in once for all users sharing a client, the others waiting until
+// it's done.
+pub async fn login_once(user: &SwanlingUser) -> SwanlingTaskResult {
+    let logged_in = user.coordination().flag("logged in");
+    if user.coordination().once("login") {
+        let _swanling = user.get(LOGIN_PATH).await?;
+        logged_in.set();
+    } else {
+        logged_in.wait().await;
+    }
+    Ok(())
+}
+
+// Test task.
+pub async fn get_slow_page(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(SLOW_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up LOGIN_PATH, store in vector at LOGIN_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(LOGIN_PATH);
+            then.status(200)
+                .header("Set-Cookie", &format!("{}=1; Path=/", SESSION_COOKIE));
+        }),
+        // Next set up PAGE_PATH with a session cookie, store in vector at PAGE_WITH_COOKIE_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(PAGE_PATH)
+                .cookie_exists(SESSION_COOKIE);
+            then.status(200);
+        }),
+        // Finally set up PAGE_PATH without a session cookie, store in vector at
+        // PAGE_WITHOUT_COOKIE_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(PAGE_PATH);
+            then.status(200);
+        }),
+        // Set up SLOW_PATH with a session cookie, store in vector at SLOW_WITH_COOKIE_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(SLOW_PATH)
+                .cookie_exists(SESSION_COOKIE);
+            then.status(200).delay(Duration::from_millis(SLOW_TIME));
+        }),
+        // Set up SLOW_PATH without a session cookie, store in vector at
+        // SLOW_WITHOUT_COOKIE_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(SLOW_PATH);
+            then.status(200).delay(Duration::from_millis(SLOW_TIME));
+        }),
+    ]
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks(client_mode: SwanlingClientMode) -> SwanlingTaskSet {
+    taskset!("LoadTest")
+        .set_client_mode(client_mode)
+        .register_task(task!(login).set_on_start())
+        .register_task(task!(get_page))
+}
+
+// Run a load test logging in once per user, and return the mock endpoints.
+fn run_client_mode_test(server: &MockServer, client_mode: SwanlingClientMode) -> Vec<MockRef<'_>> {
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(server);
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(client_mode), None, None),
+        None,
+    );
+
+    // Confirm each user logged in once.
+    assert!(mock_endpoints[LOGIN_KEY].hits() == USERS);
+
+    mock_endpoints
+}
+
+#[test]
+// By default the client is reused, so the session cookie is sent with every request.
+fn test_client_mode_per_user() {
+    let server = MockServer::start();
+    let mock_endpoints = run_client_mode_test(&server, SwanlingClientMode::PerUser);
+
+    assert!(mock_endpoints[PAGE_WITH_COOKIE_KEY].hits() > 0);
+    assert!(mock_endpoints[PAGE_WITHOUT_COOKIE_KEY].hits() == 0);
+}
+
+#[test]
+// Rebuilding the client each iteration discards the session cookie.
+fn test_client_mode_per_iteration() {
+    let server = MockServer::start();
+    let mock_endpoints = run_client_mode_test(&server, SwanlingClientMode::PerIteration);
+
+    // Each user sends the session cookie only during its first iteration.
+    assert!(mock_endpoints[PAGE_WITH_COOKIE_KEY].hits() == USERS);
+    assert!(mock_endpoints[PAGE_WITHOUT_COOKIE_KEY].hits() > 0);
+}
+
+#[test]
+// Users sharing a client share its cookies, and still make requests concurrently.
+fn test_client_mode_shared() {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration, launching all users at once.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .set_client_mode(SwanlingClientMode::Shared)
+                .register_task(task!(login_once).set_on_start())
+                .register_task(task!(get_slow_page)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Only one user logged in, but all users sent the session cookie.
+    assert!(mock_endpoints[LOGIN_KEY].hits() == 1);
+    assert!(mock_endpoints[SLOW_WITH_COOKIE_KEY].hits() > 0);
+    assert!(mock_endpoints[SLOW_WITHOUT_COOKIE_KEY].hits() == 0);
+
+    // No request waited for another user's request to finish.
+    let slow_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", SLOW_PATH))
+        .unwrap();
+    assert!(slow_metrics.raw_data.maximum_time < 2 * SLOW_TIME as usize);
+}
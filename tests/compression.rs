@@ -0,0 +1,248 @@
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the endpoints.
+const COMPRESSED_KEY: usize = 0;
+const UNCOMPRESSED_KEY: usize = 1;
+
+// Response bodies. The compressed body is not really compressed, as Swanling only decodes
+// responses to measure their size it only needs to be smaller.
+const COMPRESSED_BODY: &str = "compressed";
+const UNCOMPRESSED_BODY: &str = "this response body was not compressed";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Respond to every request on the connection with a compressed chunked response, which has
+// no Content-Length header.
+fn respond_chunked(stream: TcpStream) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+    loop {
+        // Read the request headers, the requests have no body.
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                return;
+            }
+            if line == "\r\n" {
+                break;
+            }
+        }
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-encoding: gzip\r\ntransfer-encoding: chunked\r\n\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+            COMPRESSED_BODY.len(),
+            COMPRESSED_BODY
+        );
+        if writer.write_all(response.as_bytes()).is_err() {
+            return;
+        }
+    }
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up a compressed INDEX_PATH, store in vector at COMPRESSED_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header("accept-encoding", "gzip");
+            then.status(200)
+                .header("content-encoding", "gzip")
+                .body(COMPRESSED_BODY);
+        }),
+        // Next set up an uncompressed INDEX_PATH, store in vector at UNCOMPRESSED_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header("accept-encoding", "identity");
+            then.status(200).body(UNCOMPRESSED_BODY);
+        }),
+    ]
+}
+
+// Run a load test with the specified options, and return the index request metrics.
+fn run_compression_test(
+    server: &MockServer,
+    options: Vec<&str>,
+) -> swanling::metrics::SwanlingRequestMetricAggregate {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration_options = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+    ];
+    configuration_options.extend(options);
+
+    // Build configuration.
+    let configuration = common::build_configuration(server, configuration_options);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap()
+        .clone()
+}
+
+#[test]
+// Request compressed responses with --accept-encoding.
+fn test_accept_encoding() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let index_metrics = run_compression_test(&server, vec!["--accept-encoding", "gzip"]);
+
+    // Confirm only compressed responses were requested.
+    assert!(mock_endpoints[COMPRESSED_KEY].hits() > 0);
+    assert!(mock_endpoints[UNCOMPRESSED_KEY].hits() == 0);
+
+    // Confirm the compressed responses and their sizes were tracked.
+    assert!(index_metrics.fail_count == 0);
+    assert!(index_metrics.compressed_count > 0);
+    assert!(index_metrics.uncompressed_count == 0);
+    assert!(
+        index_metrics.compressed_bytes
+            == (index_metrics.compressed_count * COMPRESSED_BODY.len()) as u64
+    );
+}
+
+#[test]
+// Request uncompressed responses with --no-gzip.
+fn test_no_gzip() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let index_metrics = run_compression_test(&server, vec!["--no-gzip"]);
+
+    // Confirm only uncompressed responses were requested.
+    assert!(mock_endpoints[COMPRESSED_KEY].hits() == 0);
+    assert!(mock_endpoints[UNCOMPRESSED_KEY].hits() > 0);
+
+    // Confirm the uncompressed responses and their sizes were tracked.
+    assert!(index_metrics.fail_count == 0);
+    assert!(index_metrics.compressed_count == 0);
+    assert!(index_metrics.uncompressed_count > 0);
+    assert!(
+        index_metrics.uncompressed_bytes
+            == (index_metrics.uncompressed_count * UNCOMPRESSED_BODY.len()) as u64
+    );
+}
+
+#[test]
+// Confirm --accept-encoding can not be set together with --no-gzip.
+fn test_accept_encoding_no_gzip() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration =
+        common::build_configuration(&server, vec!["--accept-encoding", "gzip", "--no-gzip"]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
+
+#[test]
+// The size of chunked responses is the number of bytes received, as they have no
+// Content-Length header.
+fn test_chunked_response_size() {
+    // Start a server only sending chunked responses.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let host = format!("http://{}/", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            thread::spawn(move || respond_chunked(stream));
+        }
+    });
+
+    // The mock server isn't loaded, it's only needed to build the configuration.
+    let server = MockServer::start();
+    let index_metrics =
+        run_compression_test(&server, vec!["--host", &host, "--accept-encoding", "gzip"]);
+
+    // Confirm the size of the compressed responses was tracked.
+    assert!(index_metrics.fail_count == 0);
+    assert!(index_metrics.compressed_count > 0);
+    assert!(
+        index_metrics.compressed_bytes
+            == (index_metrics.compressed_count * COMPRESSED_BODY.len()) as u64
+    );
+}
+
+#[test]
+// The decoded size of gzip compressed responses is tracked next to their size as sent by the
+// server.
+fn test_decoded_response_size() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Respond with a body that is really gzip compressed.
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(UNCOMPRESSED_BODY.as_bytes()).unwrap();
+    let gzip_body = encoder.finish().unwrap();
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200)
+            .header("content-encoding", "gzip")
+            .body(&gzip_body);
+    });
+
+    let index_metrics = run_compression_test(&server, vec!["--accept-encoding", "gzip"]);
+
+    // Confirm the size as sent by the server and the decoded size were both tracked.
+    assert!(index.hits() > 0);
+    assert!(index_metrics.fail_count == 0);
+    assert!(index_metrics.compressed_count > 0);
+    assert!(
+        index_metrics.compressed_bytes == (index_metrics.compressed_count * gzip_body.len()) as u64
+    );
+    assert!(index_metrics.decoded_count == index_metrics.compressed_count);
+    assert!(
+        index_metrics.decoded_bytes
+            == (index_metrics.decoded_count * UNCOMPRESSED_BODY.len()) as u64
+    );
+}
@@ -0,0 +1,137 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const SEARCH_PATH: &str = "/search";
+
+// Indexes to the endpoints.
+const CONFIGURED_KEY: usize = 0;
+const DEFAULT_KEY: usize = 1;
+
+// Search terms.
+const CONFIGURED_TERM: &str = "configured";
+const DEFAULT_TERM: &str = "default";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn search(user: &SwanlingUser) -> SwanlingTaskResult {
+    let term = user.config_value("search").unwrap_or(DEFAULT_TERM);
+    let _swanling = user.get(&format!("{}?q={}", SEARCH_PATH, term)).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up SEARCH_PATH with the configured term, store in vector at CONFIGURED_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(SEARCH_PATH)
+                .query_param("q", CONFIGURED_TERM);
+            then.status(200);
+        }),
+        // Next set up SEARCH_PATH with the default term, store in vector at DEFAULT_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(SEARCH_PATH)
+                .query_param("q", DEFAULT_TERM);
+            then.status(200);
+        }),
+    ]
+}
+
+// Run a load test with the specified options.
+fn run_config_value_test(server: &MockServer, options: Vec<&str>) {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration_options = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+    ];
+    configuration_options.extend(options);
+
+    // Build configuration.
+    let configuration = common::build_configuration(server, configuration_options);
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(search)),
+            None,
+            None,
+        ),
+        None,
+    );
+}
+
+#[test]
+// Pass a value into task code with --set, the last value set for a key wins.
+fn test_config_value() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let configured = format!("search={}", CONFIGURED_TERM);
+    run_config_value_test(
+        &server,
+        vec![
+            "--set",
+            "search=ignored",
+            "--set",
+            &configured,
+            "--set",
+            "tenant=42",
+        ],
+    );
+
+    // Confirm only the configured term was searched for.
+    assert!(mock_endpoints[CONFIGURED_KEY].hits() > 0);
+    assert!(mock_endpoints[DEFAULT_KEY].hits() == 0);
+}
+
+#[test]
+// Without --set the task falls back to its own default.
+fn test_config_value_not_set() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    run_config_value_test(&server, vec![]);
+
+    // Confirm only the default term was searched for.
+    assert!(mock_endpoints[CONFIGURED_KEY].hits() == 0);
+    assert!(mock_endpoints[DEFAULT_KEY].hits() > 0);
+}
+
+#[test]
+// Confirm --set requires a key and a value.
+fn test_config_value_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for value in &["search", "=configured"] {
+        let configuration = common::build_configuration(&server, vec!["--set", value]);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(search)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::metrics::SwanlingCoordinatedOmissionMitigation;
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Confirm the builder sets options the same way as the command line.
+fn test_configuration_builder() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    let configuration = SwanlingConfiguration::builder()
+        .host(&server.base_url())
+        .users(USERS)
+        .hatch_rate(&USERS.to_string())
+        .run_time(&RUN_TIME.to_string())
+        .co_mitigation(SwanlingCoordinatedOmissionMitigation::Disabled)
+        .label("team=checkout")
+        .label("release=1.2")
+        .build();
+
+    // The same options parsed from the command line result in the same configuration.
+    let parsed = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--label",
+            "team=checkout",
+            "--label",
+            "release=1.2",
+        ],
+    );
+    assert!(
+        serde_json::to_value(&configuration).unwrap() == serde_json::to_value(&parsed).unwrap()
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(index.hits() > 0);
+    assert!(swanling_metrics.users == USERS);
+    assert!(swanling_metrics.duration == RUN_TIME);
+    assert!(swanling_metrics.labels.get("team") == Some(&"checkout".to_string()));
+}
+
+#[test]
+// Confirm options that aren't set keep their default value.
+fn test_configuration_builder_default() {
+    let configuration = SwanlingConfiguration::builder().build();
+    let parsed = SwanlingConfiguration::default();
+    assert!(
+        serde_json::to_value(&configuration).unwrap() == serde_json::to_value(&parsed).unwrap()
+    );
+    assert!(configuration.users.is_none());
+    assert!(configuration.host.is_empty());
+}
@@ -38,7 +38,7 @@ enum TestType {
 // State machine for tracking Controller state during tests.
 struct TestState {
     // A buffer for the telnet Controller.
-    buf: [u8; 2048],
+    buf: Vec<u8>,
     // Track iterations through SwanlingControllerCommands.
     position: usize,
     // Track the steps within a given iteration.
@@ -63,6 +63,8 @@ struct TestState {
     websocket_expect_reply: bool,
     // A flag indicating whether or not the WebSocket controller is being tested.
     websocket_controller: bool,
+    // The duration of the load test when it was paused.
+    paused_duration: Option<usize>,
 }
 
 // Test task.
@@ -189,12 +191,21 @@ fn run_standalone_test(test_type: TestType) {
             let response;
             let websocket_response: SwanlingControllerWebSocketResponse;
             if let Some(stream) = test_state.telnet_stream.as_mut() {
-                let _ = match stream.read(&mut test_state.buf) {
-                    Ok(data) => data,
-                    Err(_) => {
-                        panic!("ERROR: server disconnected!");
+                // Long responses such as metrics-json span several reads, so read until
+                // the prompt or until the Controller disconnects.
+                let mut chunk = [0; 2048];
+                loop {
+                    let read = match stream.read(&mut chunk) {
+                        Ok(data) => data,
+                        Err(_) => {
+                            panic!("ERROR: server disconnected!");
+                        }
+                    };
+                    test_state.buf.extend_from_slice(&chunk[..read]);
+                    if read == 0 || test_state.buf.ends_with(b"swanling> ") {
+                        break;
                     }
-                };
+                }
                 response = str::from_utf8(&test_state.buf).unwrap();
             // Process data received from the client in a loop.
             } else if let Some(stream) = test_state.websocket_stream.as_mut() {
@@ -425,6 +436,28 @@ fn run_standalone_test(test_type: TestType) {
                         }
                     }
                 }
+                SwanlingControllerCommand::Set => {
+                    match test_state.step {
+                        // Configure an option.
+                        0 => {
+                            make_request(&mut test_state, "set status_codes true\r\n");
+                        }
+                        // Confirm the option is configured.
+                        1 => {
+                            assert!(response.starts_with("option configured"));
+
+                            // Try to configure an option that can't be changed.
+                            make_request(&mut test_state, "set telnet_port 5000\r\n");
+                        }
+                        // Confirm the option is not configured.
+                        _ => {
+                            assert!(response.starts_with("failed to configure option"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
                 SwanlingControllerCommand::Config => {
                     match test_state.step {
                         // Request the configuration.
@@ -498,6 +531,214 @@ fn run_standalone_test(test_type: TestType) {
                         }
                     }
                 }
+                SwanlingControllerCommand::Status => {
+                    match test_state.step {
+                        // Request the status of the load test.
+                        0 => {
+                            make_request(&mut test_state, "status\r\n");
+                        }
+                        _ => {
+                            // Confirm the status is returned in json format.
+                            if test_state.websocket_controller {
+                                assert!(response.starts_with(r#"{"phase":"#));
+                            }
+                            // Confirm the status is returned one value per line.
+                            else {
+                                assert!(response.starts_with("phase: "));
+                                assert!(response.contains("users: "));
+                            }
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
+                SwanlingControllerCommand::StatusJson => {
+                    match test_state.step {
+                        // Request the status of the load test in json format.
+                        0 => {
+                            make_request(&mut test_state, "status-json\r\n");
+                        }
+                        // Confirm the status is returned in json format.
+                        _ => {
+                            assert!(response.starts_with(r#"{"phase":"#));
+                            assert!(response.contains(r#""target_users":"#));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
+                SwanlingControllerCommand::Workers => {
+                    match test_state.step {
+                        // Request the Workers connected to the Manager.
+                        0 => {
+                            make_request(&mut test_state, "workers\r\n");
+                        }
+                        // Confirm there are no Workers, as Swanling isn't running as a Manager.
+                        _ => {
+                            assert!(response.starts_with("not running as a manager"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
+                SwanlingControllerCommand::Drain => {
+                    match test_state.step {
+                        // Drain the first Worker.
+                        0 => {
+                            make_request(&mut test_state, "drain 1\r\n");
+                        }
+                        // Confirm there's no Worker to drain, as Swanling isn't running as a
+                        // Manager.
+                        _ => {
+                            assert!(response.starts_with("failed to drain worker"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
+                SwanlingControllerCommand::WorkersJson => {
+                    match test_state.step {
+                        // Request the Workers connected to the Manager in json format.
+                        0 => {
+                            make_request(&mut test_state, "workers-json\r\n");
+                        }
+                        // Confirm there are no Workers, as Swanling isn't running as a Manager.
+                        _ => {
+                            assert!(response.starts_with("not running as a manager"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
+                SwanlingControllerCommand::Annotate => {
+                    match test_state.step {
+                        // Annotate the running load test.
+                        0 => {
+                            make_request(&mut test_state, "annotate cache flushed\r\n");
+                        }
+                        // Confirm the annotation was added.
+                        1 => {
+                            assert!(response.starts_with("annotation added"));
+
+                            // Request the metrics.
+                            make_request(&mut test_state, "metrics-json\r\n");
+                        }
+                        // Confirm the annotation is included in the metrics.
+                        _ => {
+                            assert!(response.contains("cache flushed"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
+                SwanlingControllerCommand::Report => {
+                    // The telnet and websocket tests run at the same time, so each writes
+                    // its own report.
+                    let report_file = match test_type {
+                        TestType::Telnet => "controller-report-telnet.html",
+                        TestType::WebSocket => "controller-report-websocket.html",
+                    };
+                    match test_state.step {
+                        // Request the html report.
+                        0 => {
+                            make_request(&mut test_state, "report\r\n");
+                        }
+                        // Confirm the html report is returned, then write it to a file.
+                        1 => {
+                            assert!(response.contains("<title>Swanling Attack Report</title>"));
+
+                            make_request(&mut test_state, &format!("report {}\r\n", report_file));
+                        }
+                        // Confirm the html report was written.
+                        _ => {
+                            assert!(response.starts_with("report written"));
+                            let report = std::fs::read_to_string(report_file).unwrap();
+                            assert!(report.contains("<title>Swanling Attack Report</title>"));
+                            std::fs::remove_file(report_file).unwrap();
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
+                SwanlingControllerCommand::Reset => {
+                    match test_state.step {
+                        // Reset the running metrics.
+                        0 => {
+                            make_request(&mut test_state, "reset\r\n");
+                        }
+                        // Confirm the metrics were reset.
+                        _ => {
+                            assert!(response.starts_with("metrics reset"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
+                SwanlingControllerCommand::Pause => {
+                    match test_state.step {
+                        // Try to resume a load test that isn't paused.
+                        0 => {
+                            make_request(&mut test_state, "resume\r\n");
+                        }
+                        // Confirm a load test that isn't paused can not be resumed.
+                        1 => {
+                            assert!(response.starts_with("load test not paused"));
+
+                            // Pause the running load test.
+                            make_request(&mut test_state, "pause\r\n");
+                        }
+                        // Confirm the load test was paused.
+                        2 => {
+                            assert!(response.starts_with("load test paused"));
+
+                            // Try to pause the load test again.
+                            make_request(&mut test_state, "pause\r\n");
+                        }
+                        // Confirm a paused load test can not be paused again.
+                        _ => {
+                            assert!(response.starts_with("load test not running or already paused"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
+                SwanlingControllerCommand::Resume => {
+                    match test_state.step {
+                        // Request the metrics of the paused load test.
+                        0 => {
+                            make_request(&mut test_state, "metrics-json\r\n");
+                        }
+                        // Wait, then request the metrics again.
+                        1 => {
+                            test_state.paused_duration = Some(get_duration(response));
+                            thread::sleep(time::Duration::from_millis(1500));
+                            make_request(&mut test_state, "metrics-json\r\n");
+                        }
+                        // Confirm the duration didn't increase while paused.
+                        2 => {
+                            assert!(Some(get_duration(response)) == test_state.paused_duration);
+
+                            // Resume the paused load test.
+                            make_request(&mut test_state, "resume\r\n");
+                        }
+                        // Confirm the load test was resumed.
+                        _ => {
+                            assert!(response.starts_with("load test resumed"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type);
+                        }
+                    }
+                }
                 SwanlingControllerCommand::Start => {
                     match test_state.step {
                         // Try to stop an idle load test.
@@ -508,11 +749,28 @@ fn run_standalone_test(test_type: TestType) {
                         1 => {
                             assert!(response.starts_with("load test not running"));
 
-                            // Send the start request.
-                            make_request(&mut test_state, "start\r\n");
+                            // Try to reset the metrics of an idle load test.
+                            make_request(&mut test_state, "reset\r\n");
                         }
-                        // Confirm an idle load test can be started.
+                        // Confirm the metrics of an idle load test can not be reset.
                         2 => {
+                            assert!(response.starts_with("load test not running"));
+
+                            // Try to start the load test with an invalid option.
+                            make_request(&mut test_state, "start users=foo\r\n");
+                        }
+                        // Confirm the load test can not be started with an invalid option.
+                        3 => {
+                            assert!(response.starts_with("unable to start load test"));
+
+                            // Send the start request, configuring users again.
+                            make_request(
+                                &mut test_state,
+                                &["start users=", &USERS.to_string(), "\r\n"].concat(),
+                            );
+                        }
+                        // Confirm an idle load test can be started.
+                        4 => {
                             assert!(response.starts_with("load test started"));
 
                             // Send the start request again.
@@ -576,7 +834,7 @@ fn run_standalone_test(test_type: TestType) {
                 }
             }
             // Flush the buffer.
-            test_state.buf = [0; 2048];
+            test_state.buf.clear();
 
             // Give the parent process time to catch up.
             thread::sleep(time::Duration::from_millis(100));
@@ -609,11 +867,22 @@ fn update_state(test_state: Option<TestState>, test_type: &TestType) -> TestStat
         SwanlingControllerCommand::Users,
         SwanlingControllerCommand::HatchRate,
         SwanlingControllerCommand::RunTime,
+        SwanlingControllerCommand::Set,
         SwanlingControllerCommand::Start,
+        SwanlingControllerCommand::Annotate,
+        SwanlingControllerCommand::Report,
         SwanlingControllerCommand::Config,
         SwanlingControllerCommand::ConfigJson,
         SwanlingControllerCommand::Metrics,
         SwanlingControllerCommand::MetricsJson,
+        SwanlingControllerCommand::Status,
+        SwanlingControllerCommand::StatusJson,
+        SwanlingControllerCommand::Workers,
+        SwanlingControllerCommand::WorkersJson,
+        SwanlingControllerCommand::Drain,
+        SwanlingControllerCommand::Reset,
+        SwanlingControllerCommand::Pause,
+        SwanlingControllerCommand::Resume,
         SwanlingControllerCommand::Stop,
         SwanlingControllerCommand::Shutdown,
     ];
@@ -657,7 +926,7 @@ fn update_state(test_state: Option<TestState>, test_type: &TestType) -> TestStat
         };
 
         TestState {
-            buf: [0; 2048],
+            buf: Vec::new(),
             position: 0,
             step: 0,
             command: commands_to_test.first().unwrap().clone(),
@@ -665,10 +934,17 @@ fn update_state(test_state: Option<TestState>, test_type: &TestType) -> TestStat
             websocket_stream,
             websocket_expect_reply: false,
             websocket_controller,
+            paused_duration: None,
         }
     }
 }
 
+// Extract the duration from metrics in json format.
+fn get_duration(response: &str) -> usize {
+    let duration = &response[response.find("\"duration\":").unwrap() + 11..];
+    duration[..duration.find(',').unwrap()].parse().unwrap()
+}
+
 fn make_request(test_state: &mut TestState, command: &str) {
     //println!("making request: {}", command);
     if let Some(stream) = test_state.telnet_stream.as_mut() {
@@ -678,6 +954,7 @@ fn make_request(test_state: &mut TestState, command: &str) {
             .write_message(Message::Text(
                 serde_json::to_string(&SwanlingControllerWebSocketRequest {
                     request: command.to_string(),
+                    id: None,
                 })
                 .unwrap(),
             ))
@@ -697,3 +974,149 @@ fn test_telnet_controller() {
 fn test_websocket_controller() {
     run_standalone_test(TestType::WebSocket);
 }
+
+#[test]
+// Test identifying and batching WebSocket controller requests.
+fn test_websocket_controller_batch() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Use a different port than the other WebSocket test, as tests run in parallel.
+    let mut configuration_flags = vec!["--no-telnet", "--websocket-port", "5118"];
+    let configuration = common_build_configuration(&server, &mut configuration_flags);
+
+    // Create a new thread from which to test the Controller.
+    let controller_handle = thread::spawn(move || {
+        // Sleep a half a second allowing the SwanlingAttack to start.
+        thread::sleep(time::Duration::from_millis(500));
+
+        let (mut stream, _) = tungstenite::client::connect("ws://127.0.0.1:5118").unwrap();
+
+        // A batch of requests is answered with an array of identified responses, in order.
+        stream
+            .write_message(Message::Text(
+                r#"[{"request": "users 2", "id": 1}, {"request": "foo", "id": "two"}, {"request": "help"}]"#
+                    .into(),
+            ))
+            .unwrap();
+        let responses: Vec<SwanlingControllerWebSocketResponse> =
+            serde_json::from_str(&stream.read_message().unwrap().into_text().unwrap()).unwrap();
+        assert!(responses.len() == 3);
+        assert!(responses[0].success);
+        assert!(responses[0].id == Some(serde_json::json!(1)));
+        assert!(!responses[1].success);
+        assert!(responses[1].id == Some(serde_json::json!("two")));
+        assert!(responses[2].success);
+        assert!(responses[2].id.is_none());
+
+        // A single request is answered with a single identified response.
+        stream
+            .write_message(Message::Text(
+                serde_json::to_string(&SwanlingControllerWebSocketRequest {
+                    request: "hatchrate 5".to_string(),
+                    id: Some(serde_json::json!("last")),
+                })
+                .unwrap(),
+            ))
+            .unwrap();
+        let response: SwanlingControllerWebSocketResponse =
+            serde_json::from_str(&stream.read_message().unwrap().into_text().unwrap()).unwrap();
+        assert!(response.success);
+        assert!(response.id == Some(serde_json::json!("last")));
+
+        // Shut down the load test.
+        stream
+            .write_message(Message::Text(r#"{"request": "shutdown"}"#.into()))
+            .unwrap();
+    });
+
+    // Run the Swanling Attack until the Controller shuts it down.
+    common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    // Confirm the Controller received the expected responses.
+    controller_handle.join().unwrap();
+}
+
+#[test]
+// Test configuring options with the set command while idle.
+fn test_controller_set() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Use a different port than the other WebSocket tests, as tests run in parallel.
+    let mut configuration_flags = vec!["--no-telnet", "--websocket-port", "5119"];
+    let configuration = common_build_configuration(&server, &mut configuration_flags);
+
+    // Create a new thread from which to test the Controller.
+    let controller_handle = thread::spawn(move || {
+        // Sleep a half a second allowing the SwanlingAttack to start.
+        thread::sleep(time::Duration::from_millis(500));
+
+        let (mut stream, _) = tungstenite::client::connect("ws://127.0.0.1:5119").unwrap();
+        let mut request = |request: &str| -> SwanlingControllerWebSocketResponse {
+            stream
+                .write_message(Message::Text(
+                    serde_json::to_string(&SwanlingControllerWebSocketRequest {
+                        request: request.to_string(),
+                        id: None,
+                    })
+                    .unwrap(),
+                ))
+                .unwrap();
+            serde_json::from_str(&stream.read_message().unwrap().into_text().unwrap()).unwrap()
+        };
+
+        // Options can be named like their field or their command line flag.
+        assert!(request("set throttle_requests 5").success);
+        assert!(request("set --request-log requests.log").success);
+        assert!(request("set no_gzip true").success);
+
+        // Values must be valid.
+        assert!(!request("set throttle_requests foo").success);
+        assert!(!request("set no_gzip yes").success);
+
+        // Options must exist, and not only take effect when launching the load test.
+        assert!(!request("set foo 1").success);
+        assert!(!request("set telnet_port 5000").success);
+        assert!(!request("set runtime_threads 4").success);
+        assert!(!request("set fleet_size 2").success);
+
+        // The new configuration must be valid, --accept-encoding conflicts with --no-gzip.
+        assert!(!request("set accept_encoding gzip").success);
+
+        // Options set when starting are not configured if the load test can't start, as no
+        // host is configured.
+        assert!(!request("start throttle_requests=7").success);
+
+        // Annotations can only be added to a running load test.
+        assert!(!request("annotate too early").success);
+
+        // The html report can only be generated once a load test has started.
+        assert!(!request("report").success);
+
+        // Confirm the valid options were configured, and the invalid ones weren't.
+        let configuration: SwanlingConfiguration =
+            serde_json::from_str(&request("config").response).unwrap();
+        assert!(configuration.throttle_requests == 5);
+        assert!(configuration.request_log == "requests.log");
+        assert!(configuration.no_gzip);
+        assert!(configuration.accept_encoding.is_empty());
+
+        // Shut down the load test.
+        stream
+            .write_message(Message::Text(r#"{"request": "shutdown"}"#.into()))
+            .unwrap();
+    });
+
+    // Run the Swanling Attack until the Controller shuts it down.
+    common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    // Confirm the Controller received the expected responses.
+    controller_handle.join().unwrap();
+}
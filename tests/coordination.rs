@@ -0,0 +1,98 @@
+use httpmock::{Method::GET, MockServer};
+use std::sync::atomic::Ordering;
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ADMIN_PATH: &str = "/admin";
+const READY_PATH: &str = "/ready";
+
+// Load test configuration.
+const USERS: usize = 3;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn wait_for_all_users(user: &SwanlingUser) -> SwanlingTaskResult {
+    // Record how many users arrived, then wait until all users arrived.
+    let arrived = user.coordination().counter("arrived");
+    arrived.fetch_add(1, Ordering::SeqCst);
+    user.coordination().barrier("start", USERS).wait().await;
+    // All users arrived before any user was released.
+    if arrived.load(Ordering::SeqCst) == USERS {
+        let _swanling = user.get(READY_PATH).await?;
+    }
+    Ok(())
+}
+
+// Test task.
+pub async fn get_admin_once(user: &SwanlingUser) -> SwanlingTaskResult {
+    if user.coordination().once("admin") {
+        let _swanling = user.get(ADMIN_PATH).await?;
+        user.coordination().flag("admin done").set();
+    }
+    user.coordination().flag("admin done").wait().await;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Users coordinate with counters, barriers and flags shared by all users.
+fn test_coordination() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let admin = server.mock(|when, then| {
+        when.method(GET).path(ADMIN_PATH);
+        then.status(200);
+    });
+    let ready = server.mock(|when, then| {
+        when.method(GET).path(READY_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(wait_for_all_users).set_on_start())
+                .register_task(task!(get_admin_once))
+                .register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // All users were released from the barrier together.
+    assert!(ready.hits() == USERS);
+    // Only one user loaded the admin page, while all users loaded the index.
+    assert!(admin.hits() == 1);
+    assert!(index.hits() > 0);
+}
@@ -0,0 +1,86 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about";
+
+// Load test configuration.
+const USERS: usize = 5;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_about(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// A single user runs through one iteration of the task set.
+fn test_debug_single_user() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200).body("index");
+    });
+    let about = server.mock(|when, then| {
+        when.method(GET).path(ABOUT_PATH);
+        then.status(200).body("about");
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec!["--users", &USERS.to_string(), "--debug-single-user"],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(get_index).set_weight(2).unwrap())
+                .register_task(task!(get_about)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Only one user ran, once through its weighted tasks.
+    assert!(index.hits() == 2);
+    assert!(about.hits() == 1);
+    assert!(swanling_metrics.started.is_none());
+}
+
+#[test]
+// A single user can't be debugged in Regatta mode, or together with --dry-run.
+fn test_debug_single_user_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in [
+        vec!["--debug-single-user", "--manager"],
+        vec!["--debug-single-user", "--dry-run"],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const LOGIN_PATH: &str = "/login";
+const ACCOUNT_PATH: &str = "/account";
+const SEARCH_PATH: &str = "/search";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const LOGIN_KEY: usize = 1;
+const ACCOUNT_KEY: usize = 2;
+const SEARCH_KEY: usize = 3;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn login(user: &SwanlingUser) -> SwanlingTaskResult {
+    let mut swanling = user.get(LOGIN_PATH).await?;
+    if let Ok(response) = &swanling.response {
+        if !response.status().is_success() {
+            return user.set_failure("login failed", &mut swanling.request, None, None);
+        }
+    }
+    Ok(())
+}
+
+// Test task.
+pub async fn get_account(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ACCOUNT_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn search(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(SEARCH_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer, login_status: u16) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up LOGIN_PATH, store in vector at LOGIN_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(LOGIN_PATH);
+            then.status(login_status);
+        }),
+        // Next set up ACCOUNT_PATH, store in vector at ACCOUNT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ACCOUNT_PATH);
+            then.status(200);
+        }),
+        // Finally set up SEARCH_PATH, store in vector at SEARCH_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(SEARCH_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest")
+        .register_task(task!(login).set_name("login").set_on_start())
+        .register_task(task!(get_index))
+        .register_task(task!(get_account).set_depends_on("login"))
+        .register_task(task!(search).set_skip_if(|user| user.config_value("search") != Some("on")))
+}
+
+// Run a load test with the specified options, and return the mock endpoints.
+fn run_depends_test<'a>(
+    server: &'a MockServer,
+    login_status: u16,
+    options: Vec<&str>,
+) -> Vec<MockRef<'a>> {
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(server, login_status);
+
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration_options = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+    ];
+    configuration_options.extend(options);
+
+    // Build configuration.
+    let configuration = common::build_configuration(server, configuration_options);
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    // Confirm each user logged in once, and tasks without conditions always ran.
+    assert!(mock_endpoints[LOGIN_KEY].hits() == USERS);
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    mock_endpoints
+}
+
+#[test]
+// Run tasks when the task they depend on succeeded and they're not skipped.
+fn test_depends_on_succeeded() {
+    let server = MockServer::start();
+    let mock_endpoints = run_depends_test(&server, 200, vec!["--set", "search=on"]);
+
+    assert!(mock_endpoints[ACCOUNT_KEY].hits() > 0);
+    assert!(mock_endpoints[SEARCH_KEY].hits() > 0);
+}
+
+#[test]
+// Skip tasks when the task they depend on failed or their predicate is true.
+fn test_depends_on_failed() {
+    let server = MockServer::start();
+    let mock_endpoints = run_depends_test(&server, 500, vec![]);
+
+    assert!(mock_endpoints[ACCOUNT_KEY].hits() == 0);
+    assert!(mock_endpoints[SEARCH_KEY].hits() == 0);
+}
+
+#[test]
+// Confirm a task can't depend on a task that isn't registered.
+fn test_depends_on_unknown_task() {
+    let server = MockServer::start();
+
+    let configuration = common::build_configuration(&server, vec![]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_account).set_depends_on("login")),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
@@ -0,0 +1,159 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::net::UdpSocket;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Hostname only resolved by the test DNS server.
+const HOSTNAME: &str = "swanling.test";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+const DNS_REFRESH: usize = 5;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Start a DNS server resolving every hostname to 127.0.0.1, returning its address and a
+// count of the IPv4 address queries it answered.
+fn start_dns_server() -> (String, Arc<AtomicUsize>) {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let address = socket.local_addr().unwrap().to_string();
+    let queries = Arc::new(AtomicUsize::new(0));
+
+    let counter = queries.clone();
+    std::thread::spawn(move || {
+        let mut buffer = [0; 512];
+        while let Ok((length, source)) = socket.recv_from(&mut buffer) {
+            // Reply with the query's header and question, flagged as a response.
+            let mut response = buffer[..length].to_vec();
+            response[2] = 0x81;
+            response[3] = 0x80;
+            // The record type follows the question's name.
+            if response[length - 3] == 1 {
+                counter.fetch_add(1, Ordering::SeqCst);
+                // Answer with a single address, pointing to the name in the question.
+                response[7] = 1;
+                response.extend_from_slice(&[0xc0, 12, 0, 1, 0, 1, 0, 0, 0, 0, 0, 4, 127, 0, 0, 1]);
+            }
+            let _ = socket.send_to(&response, source);
+        }
+    });
+
+    (address, queries)
+}
+
+// Run a load test against the test hostname with the specified DNS options, returning the
+// index endpoint and how many times the hostname was resolved.
+fn run_dns_test<'a>(server: &'a MockServer, options: Vec<&str>) -> (MockRef<'a>, usize) {
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let (dns_server, queries) = start_dns_server();
+
+    let host = format!("http://{}:{}", HOSTNAME, server.port());
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration_options = vec![
+        "--host",
+        &host,
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+        "--dns-server",
+        &dns_server,
+    ];
+    configuration_options.extend(options);
+
+    // Build configuration.
+    let configuration = common::build_configuration(server, configuration_options);
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    let queries = queries.load(Ordering::SeqCst);
+    (index, queries)
+}
+
+#[test]
+// Resolve hostnames with a DNS server, reusing connections.
+fn test_dns_server() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let (index, queries) = run_dns_test(&server, vec![]);
+
+    // Each user resolved the hostname once, for its only connection.
+    assert!(index.hits() > USERS);
+    assert!(queries > 0);
+    assert!(queries <= USERS);
+}
+
+#[test]
+// Resolve hostnames for every request.
+fn test_no_dns_cache() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let (index, queries) = run_dns_test(&server, vec!["--no-dns-cache"]);
+
+    assert!(index.hits() > USERS);
+    assert!(queries >= index.hits());
+}
+
+#[test]
+// Resolve hostnames again every few requests.
+fn test_dns_refresh() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let dns_refresh = DNS_REFRESH.to_string();
+    let (index, queries) = run_dns_test(&server, vec!["--dns-refresh", &dns_refresh]);
+
+    assert!(index.hits() > DNS_REFRESH * USERS);
+    assert!(queries < index.hits());
+    assert!(queries * DNS_REFRESH >= index.hits());
+}
+
+#[test]
+// Confirm invalid DNS options are rejected.
+fn test_dns_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in &[
+        vec!["--dns-server", "foo"],
+        vec!["--dns-refresh", "5", "--no-dns-cache"],
+    ] {
+        let configuration = common::build_configuration(&server, options.clone());
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const SETUP_PATH: &str = "/setup";
+
+// Load test configuration.
+const USERS: usize = 3;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn setup(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(SETUP_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// A dry run validates the load test without making any requests.
+fn test_dry_run() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let setup_endpoint = server.mock(|when, then| {
+        when.method(GET).path(SETUP_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration =
+        common::build_configuration(&server, vec!["--users", &USERS.to_string(), "--dry-run"]);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            Some(&task!(setup)),
+            None,
+        ),
+        None,
+    );
+
+    // No requests were made, not even by test_start.
+    assert!(index.hits() == 0);
+    assert!(setup_endpoint.hits() == 0);
+    assert!(swanling_metrics.started.is_none());
+    assert!(swanling_metrics.requests.is_empty());
+}
+
+#[test]
+// A dry run still fails if the load test is invalid.
+fn test_dry_run_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in [
+        // A dry run requires a host, even without autostarting.
+        vec!["--host", "", "--no-autostart", "--dry-run"],
+        // Workers receive their plan from the Manager.
+        vec!["--worker", "--dry-run"],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
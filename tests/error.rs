@@ -125,6 +125,8 @@ fn validate_error(
                 assert!(error.1.method == SwanlingMethod::Get);
                 // The captured error was for the 404 path.
                 assert!(error.1.name == A_404_PATH);
+                // The captured error was categorized as a 4xx response.
+                assert!(error.1.category == "Http4xx");
                 // The error was captured the number of times we requested the 404 path.
                 assert!(error.1.occurrences == a_404_metrics.fail_count);
             }
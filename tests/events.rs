@@ -0,0 +1,94 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::{AttackPhase, SwanlingEvent};
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 3;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Subscribers receive the lifecycle events of the load test.
+fn test_subscribe_events() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    let mut swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    let events = swanling_attack.subscribe_events();
+
+    // Run the Swanling Attack.
+    common::run_load_test(swanling_attack, None);
+    assert!(index.hits() > 0);
+
+    // The stream ends once the load test has finished.
+    let events: Vec<SwanlingEvent> = events.iter().collect();
+
+    // Confirm every user started and stopped.
+    for user in 1..=USERS {
+        for event in &[
+            SwanlingEvent::UserStarted {
+                user,
+                task_set: "LoadTest".to_string(),
+            },
+            SwanlingEvent::UserStopped {
+                user,
+                task_set: "LoadTest".to_string(),
+            },
+        ] {
+            assert!(events.iter().filter(|e| e == &event).count() == 1);
+        }
+    }
+
+    // Confirm the load test moved through each phase in order.
+    let phases: Vec<&AttackPhase> = events
+        .iter()
+        .filter_map(|event| match event {
+            SwanlingEvent::PhaseChanged(phase) => Some(phase),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        phases,
+        vec![
+            &AttackPhase::Starting,
+            &AttackPhase::Running,
+            &AttackPhase::Stopping,
+            &AttackPhase::Shutdown
+        ]
+    );
+}
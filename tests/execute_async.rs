@@ -0,0 +1,81 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 4;
+const HATCH_RATE: usize = 100;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Run load tests on the runtime the application is already running.
+async fn test_execute_async() {
+    // Start the mock server.
+    let server = MockServer::start_async().await;
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server
+        .mock_async(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        })
+        .await;
+
+    // Run two load tests, without Controllers listening between them.
+    for _ in 0..2 {
+        let configuration = common::build_configuration(
+            &server,
+            vec![
+                "--users",
+                &USERS.to_string(),
+                // Launch all users right away, well within the one second run time.
+                "--hatch-rate",
+                &HATCH_RATE.to_string(),
+                "--no-telnet",
+                "--no-websocket",
+            ],
+        );
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+
+        // Run the load test as a task, as a service controlling load tests would.
+        let swanling_metrics = tokio::spawn(swanling_attack.execute_async())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(swanling_metrics.users == USERS);
+    }
+
+    // Confirm requests were made by both load tests.
+    assert!(index.hits_async().await > 1);
+}
+
+#[tokio::test]
+// Confirm --runtime-threads can't be set when running on the application's runtime.
+async fn test_execute_async_runtime_threads() {
+    // Start the mock server.
+    let server = MockServer::start_async().await;
+
+    let configuration = common::build_configuration(&server, vec!["--runtime-threads", "2"]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute_async().await.is_err());
+}
@@ -0,0 +1,173 @@
+use httpmock::{Method::POST, MockServer};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const LOGIN_PATH: &str = "/login";
+
+// Load test configuration.
+const USERS: usize = 4;
+const RUN_TIME: usize = 2;
+const ACCOUNTS: usize = 3;
+
+lazy_static::lazy_static! {
+    // The rows currently checked out, and every row ever checked out.
+    static ref IN_USE: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+    static ref USED: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+// Set if a row was checked out by two users at once.
+static OVERLAP: AtomicBool = AtomicBool::new(false);
+
+// The rows of the feeder used in these tests.
+fn accounts() -> Vec<String> {
+    (0..ACCOUNTS).map(|a| format!("account={}", a)).collect()
+}
+
+// Test task, logging in with an account no other user is using.
+pub async fn log_in(user: &SwanlingUser) -> SwanlingTaskResult {
+    let account = user.feeder("accounts")?.checkout().await?;
+    let row = account.row().to_string();
+    if !IN_USE.lock().unwrap().insert(row.clone()) {
+        OVERLAP.store(true, Ordering::SeqCst);
+    }
+    USED.lock().unwrap().insert(row.clone());
+    let _swanling = user.post(LOGIN_PATH, &row).await?;
+    IN_USE.lock().unwrap().remove(&row);
+    account.release().await;
+    Ok(())
+}
+
+// Test task, using a feeder that wasn't registered.
+pub async fn unregistered(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _account = user.feeder("missing")?.checkout().await?;
+    Ok(())
+}
+
+#[test]
+// Hand out each row to one user at a time, and only this process's share in a fleet.
+fn test_feeder() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server, responding slowly so users
+    // wait for a row.
+    let login = server.mock(|when, then| {
+        when.method(POST).path(LOGIN_PATH);
+        then.status(200).delay(std::time::Duration::from_millis(10));
+    });
+
+    // Build configuration, running the second of two processes of a fleet.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--fleet-index",
+            "1",
+            "--fleet-size",
+            "2",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(log_in)),
+            None,
+            None,
+        )
+        .register_feeder("accounts", accounts())
+        .unwrap(),
+        None,
+    );
+    assert!(login.hits() > 0);
+    assert!(swanling_metrics.tasks[0][0].success_count > 0);
+
+    // No row was checked out by two users at once.
+    assert!(!OVERLAP.load(Ordering::SeqCst));
+
+    // Only the odd rows are handed out by the second process of the fleet.
+    let used = USED.lock().unwrap();
+    assert!(used.len() == 1);
+    assert!(used.contains("account=1"));
+}
+
+#[test]
+// Confirm feeders must be uniquely named and have rows, and tasks fail when using a feeder
+// that wasn't registered.
+fn test_feeder_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration = common::build_configuration(&server, vec![]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(unregistered)),
+        None,
+        None,
+    );
+    match swanling_attack.register_feeder("empty", vec![]) {
+        Err(SwanlingError::InvalidOption { option, .. }) => {
+            assert!(option == "SwanlingAttack.register_feeder")
+        }
+        _ => panic!("a feeder without rows was registered"),
+    }
+
+    let configuration = common::build_configuration(
+        &server,
+        vec!["--users", "1", "--hatch-rate", "1", "--run-time", "1"],
+    );
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(unregistered)),
+        None,
+        None,
+    )
+    .register_feeder("accounts", accounts())
+    .unwrap();
+    assert!(swanling_attack
+        .clone()
+        .register_feeder("accounts", accounts())
+        .is_err());
+
+    let swanling_metrics = common::run_load_test(swanling_attack, None);
+    assert!(swanling_metrics.tasks[0][0].success_count == 0);
+    assert!(swanling_metrics.tasks[0][0].fail_count > 0);
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+// Confirm a Regatta won't start with feeders but no backend, as each Worker would hand out
+// every row.
+fn test_feeder_gaggle_without_backend() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration =
+        common::build_configuration(&server, vec!["--manager", "--expect-workers", "1"]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(log_in)),
+        None,
+        None,
+    )
+    .register_feeder("accounts", accounts())
+    .unwrap();
+
+    match swanling_attack.execute() {
+        Err(SwanlingError::InvalidOption { option, .. }) => {
+            assert!(option == "SwanlingAttack.register_feeder")
+        }
+        result => panic!("unexpected result: {:?}", result.map(|_| ())),
+    }
+}
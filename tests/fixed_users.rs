@@ -0,0 +1,167 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const ADMIN_PATH: &str = "/admin";
+const ANONYMOUS_PATH: &str = "/anonymous";
+const EDITOR_PATH: &str = "/editor";
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const ADMIN_KEY: usize = 0;
+const ANONYMOUS_KEY: usize = 1;
+const EDITOR_KEY: usize = 2;
+
+// Load test configuration.
+const ADMIN_USERS: usize = 2;
+const USERS: usize = 10;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn start_admin(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ADMIN_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn start_anonymous(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ANONYMOUS_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn start_editor(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(EDITOR_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up ADMIN_PATH, store in vector at ADMIN_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ADMIN_PATH);
+            then.status(200);
+        }),
+        // Next set up ANONYMOUS_PATH, store in vector at ANONYMOUS_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ANONYMOUS_PATH);
+            then.status(200);
+        }),
+        // Next set up EDITOR_PATH, store in vector at EDITOR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(EDITOR_PATH);
+            then.status(200);
+        }),
+        // Finally set up INDEX_PATH.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build a load test where each task set makes one request to its own path when a
+// user starts, so the number of users running each task set can be counted.
+fn build_load_test(configuration: swanling::SwanlingConfiguration) -> SwanlingAttack {
+    SwanlingAttack::initialize_with_config(configuration)
+        .unwrap()
+        .register_taskset(
+            taskset!("Admin")
+                .set_fixed_users(ADMIN_USERS)
+                .unwrap()
+                .register_task(task!(start_admin).set_on_start())
+                .register_task(task!(get_index)),
+        )
+        .register_taskset(
+            taskset!("Anonymous")
+                .set_weight(3)
+                .unwrap()
+                .register_task(task!(start_anonymous).set_on_start())
+                .register_task(task!(get_index)),
+        )
+        .register_taskset(
+            taskset!("Editor")
+                .register_task(task!(start_editor).set_on_start())
+                .register_task(task!(get_index)),
+        )
+}
+
+#[test]
+// Allocate a fixed number of users to one task set, and the rest by weight.
+fn test_fixed_users() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(build_load_test(configuration), None);
+
+    // Confirm the fixed users ran the admin task set, and the remaining users were
+    // allocated to the other task sets by weight.
+    assert!(mock_endpoints[ADMIN_KEY].hits() == ADMIN_USERS);
+    assert!(mock_endpoints[ANONYMOUS_KEY].hits() == (USERS - ADMIN_USERS) / 4 * 3);
+    assert!(mock_endpoints[EDITOR_KEY].hits() == (USERS - ADMIN_USERS) / 4);
+}
+
+#[test]
+// Confirm a load test can't start with fewer users than the fixed users.
+fn test_fixed_users_too_few_users() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration =
+        common::build_configuration(&server, vec!["--users", &(ADMIN_USERS - 1).to_string()]);
+    assert!(build_load_test(configuration).execute().is_err());
+}
+
+#[test]
+// Confirm a load test can't start with more users than the fixed users when all task
+// sets have fixed users.
+fn test_fixed_users_too_many_users() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration =
+        common::build_configuration(&server, vec!["--users", &(ADMIN_USERS + 1).to_string()]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("Admin")
+            .set_fixed_users(ADMIN_USERS)
+            .unwrap()
+            .register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
+
+#[test]
+// Confirm fixed users must be at least 1.
+fn test_fixed_users_zero() {
+    assert!(taskset!("Admin").set_fixed_users(0).is_err());
+}
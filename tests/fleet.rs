@@ -0,0 +1,151 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const USER_PATH: &str = "/user/";
+
+// Load test configuration.
+const USERS: usize = 4;
+const FLEET_SIZE: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task, each user requests a path including its index.
+pub async fn get_user(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user
+        .get(&format!("{}{}", USER_PATH, user.weighted_users_index))
+        .await?;
+    Ok(())
+}
+
+// Set up one endpoint per user.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    (0..USERS)
+        .map(|index| {
+            server.mock(|when, then| {
+                when.method(GET).path(format!("{}{}", USER_PATH, index));
+                then.status(200);
+            })
+        })
+        .collect()
+}
+
+// Run the load test as one process of the fleet, returning its metrics.
+fn run_fleet_process(server: &MockServer, fleet_index: usize) -> SwanlingMetrics {
+    // Build configuration.
+    let configuration = common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--fleet-index",
+            &fleet_index.to_string(),
+            "--fleet-size",
+            &FLEET_SIZE.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_user)),
+            None,
+            None,
+        ),
+        None,
+    )
+}
+
+#[test]
+// Partition users across a fleet of processes, and merge their metrics.
+fn test_fleet() {
+    let metrics_files = ["fleet-0.json", "fleet-1.json"];
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let mut fleet_metrics = Vec::new();
+    for (fleet_index, metrics_file) in metrics_files.iter().enumerate() {
+        let swanling_metrics = run_fleet_process(&server, fleet_index);
+
+        // Confirm this process only launched its share of the users, each with an index
+        // unique across the fleet.
+        assert!(swanling_metrics.users == USERS / FLEET_SIZE);
+        for (index, mock_endpoint) in mock_endpoints.iter().enumerate() {
+            if index % FLEET_SIZE <= fleet_index {
+                assert!(mock_endpoint.hits() > 0);
+            } else {
+                assert!(mock_endpoint.hits() == 0);
+            }
+        }
+
+        std::fs::write(
+            metrics_file,
+            serde_json::to_string(&swanling_metrics).unwrap(),
+        )
+        .unwrap();
+        fleet_metrics.push(swanling_metrics);
+    }
+
+    // Merge the metrics of the fleet.
+    let configuration = common::build_configuration(
+        &server,
+        vec!["--merge-reports", metrics_files[0], metrics_files[1]],
+    );
+    let merged_metrics = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_user)),
+        None,
+        None,
+    )
+    .execute()
+    .unwrap();
+    common::cleanup_files(vec![metrics_files[0], metrics_files[1]]);
+
+    // Confirm the merged metrics include all users and requests of the fleet.
+    assert!(merged_metrics.users == USERS);
+    assert!(merged_metrics.requests.len() == USERS);
+    for swanling_metrics in &fleet_metrics {
+        for (request_key, request) in &swanling_metrics.requests {
+            assert!(merged_metrics.requests[request_key].success_count == request.success_count);
+        }
+    }
+}
+
+#[test]
+// Confirm --fleet-index and --fleet-size must be valid together.
+fn test_fleet_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in [
+        // Both options must be set.
+        vec!["--fleet-index", "0"],
+        vec!["--fleet-size", "2"],
+        // The fleet needs at least one process.
+        vec!["--fleet-index", "0", "--fleet-size", "0"],
+        // The index must be smaller than the size.
+        vec!["--fleet-index", "2", "--fleet-size", "2"],
+        // Each process must launch at least one user.
+        vec!["--users", "1", "--fleet-index", "0", "--fleet-size", "2"],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_user)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
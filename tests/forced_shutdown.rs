@@ -0,0 +1,95 @@
+// Ctrl-c is caught once per process, so this test runs in its own test binary.
+#![cfg(unix)]
+
+use httpmock::{Method::GET, MockServer};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 1;
+
+// How long the stuck task sleeps, much longer than the test waits before pressing ctrl-c.
+const STUCK_TIME: u64 = 30;
+
+// Test task that never finishes in time.
+pub async fn get_index_and_hang(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    tokio::time::sleep(Duration::from_secs(STUCK_TIME)).await;
+    Ok(())
+}
+
+// Send ctrl-c to this process.
+fn send_ctrlc() {
+    Command::new("kill")
+        .args(["-INT", &std::process::id().to_string()])
+        .status()
+        .unwrap();
+}
+
+#[test]
+// Abort users that don't exit when ctrl-c is pressed again, still producing metrics.
+fn test_forced_shutdown() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration, waiting for users to exit without a shutdown timeout.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            // Keep the requests in each user's metrics until it's aborted.
+            "--metrics-flush",
+            "60s",
+            "--no-reset-metrics",
+        ],
+    );
+
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index_and_hang)),
+        None,
+        None,
+    );
+
+    // Press ctrl-c twice while the load test waits for the stuck users to exit.
+    let ctrlc_handle = thread::spawn(|| {
+        thread::sleep(Duration::from_millis(2_500));
+        send_ctrlc();
+        thread::sleep(Duration::from_millis(500));
+        send_ctrlc();
+    });
+
+    // Run the Swanling Attack.
+    let started = Instant::now();
+    let swanling_metrics = common::run_load_test(swanling_attack, None);
+    ctrlc_handle.join().unwrap();
+    assert!(index.hits() > 0);
+
+    // Confirm the users were aborted instead of waited for.
+    assert!(started.elapsed() < Duration::from_secs(STUCK_TIME));
+    let aborted = swanling_metrics.aborted.unwrap();
+    assert!(aborted.contains("ctrl-c was caught again"));
+
+    // Confirm the metrics collected before aborting were kept.
+    assert!(swanling_metrics.requests.contains_key("GET /"));
+}
@@ -0,0 +1,123 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use serial_test::serial;
+use std::thread;
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 4;
+const EXPECT_WORKERS: usize = 1;
+
+// How long to wait before launching a Worker after the load test started.
+const LATE_WORKER_DELAY: u64 = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoint(server: &MockServer) -> MockRef<'_> {
+    server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    })
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest").register_task(task!(get_index))
+}
+
+// Build the configuration of a Manager expecting EXPECT_WORKERS Workers.
+fn build_manager_configuration(server: &MockServer, custom: Vec<&str>) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let expect_workers = EXPECT_WORKERS.to_string();
+    let mut configuration = vec![
+        "--manager",
+        "--expect-workers",
+        &expect_workers,
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+    ];
+    configuration.extend(custom);
+
+    common::build_configuration(server, configuration)
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+#[serial]
+// Assign users to a Worker connecting after the load test started.
+fn test_hot_add_workers_gaggle() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = setup_mock_server_endpoint(&server);
+
+    // Build the load test for the Workers.
+    let worker_configuration = common::build_configuration(&server, vec!["--worker"]);
+    let swanling_attack = common::build_load_test(worker_configuration, &get_tasks(), None, None);
+
+    // Launch the expected Workers right away, and one more once the load test is running.
+    let mut worker_handles = common::launch_gaggle_workers(swanling_attack.clone(), EXPECT_WORKERS);
+    worker_handles.push(thread::spawn(move || {
+        thread::sleep(Duration::from_secs(LATE_WORKER_DELAY));
+        common::run_load_test(swanling_attack, None);
+    }));
+
+    // Build the load test for the Manager.
+    let manager_configuration = build_manager_configuration(&server, vec!["--hot-add-workers"]);
+    let manager_swanling_attack =
+        common::build_load_test(manager_configuration, &get_tasks(), None, None);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(manager_swanling_attack, Some(worker_handles));
+
+    // Confirm the late Worker was assigned as many users as the first one, adding them to
+    // the load test.
+    assert!(index.hits() > 0);
+    assert!(swanling_metrics.users == USERS * 2);
+}
+
+#[test]
+#[cfg_attr(not(feature = "gaggle"), ignore)]
+#[serial]
+// Run the only expected Worker in the Manager process.
+fn test_manager_also_worker_gaggle() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = setup_mock_server_endpoint(&server);
+
+    // Build the load test for the Manager, no other Workers are launched.
+    let manager_configuration = build_manager_configuration(&server, vec!["--manager-also-worker"]);
+    let manager_swanling_attack =
+        common::build_load_test(manager_configuration, &get_tasks(), None, None);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(manager_swanling_attack, None);
+
+    // Confirm the Worker in the Manager process launched all users and loaded the endpoint.
+    assert!(index.hits() > 0);
+    assert!(swanling_metrics.users == USERS);
+    assert!(swanling_metrics
+        .requests
+        .contains_key(&format!("GET {}", INDEX_PATH)));
+}
@@ -0,0 +1,125 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 10;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Run a load test with the specified hatch rate and any additional options, returning the
+// number of users launched.
+fn launched_users(server: &MockServer, hatch_rate: &str, options: Vec<&str>) -> usize {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        hatch_rate,
+        "--run-time",
+        &run_time,
+        "--no-reset-metrics",
+    ];
+    configuration.extend(options);
+    let configuration = common::build_configuration(server, configuration);
+
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+    swanling_metrics.users
+}
+
+#[test]
+// Ramp up the hatch rate, linearly and exponentially.
+fn test_hatch_rate_ramp() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Ramping up quickly launches all users before the load test ends, while starting
+    // at 2 users per second would only launch 5 users.
+    assert!(launched_users(&server, "2..40/1s", vec![]) == USERS);
+    assert!(launched_users(&server, "2..200/1s:exp", vec![]) == USERS);
+
+    // Ramping up slowly doesn't launch all users before the load test ends.
+    assert!(launched_users(&server, "1..2/1m", vec![]) < USERS);
+
+    assert!(index.hits() > 0);
+}
+
+#[test]
+// Launch users in batches, optionally randomly shifting when each batch launches.
+fn test_hatch_batch() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Launching 1 user per second only launches some of the users before the load test
+    // ends, while launching them all in one batch launches them all at once.
+    assert!(launched_users(&server, "1", vec![]) < USERS);
+    assert!(launched_users(&server, "1", vec!["--hatch-batch", &USERS.to_string()]) == USERS);
+
+    // Two batches of 5 users launched at 10 users per second are launched about half a
+    // second apart, no more than 100 milliseconds earlier or later.
+    assert!(
+        launched_users(
+            &server,
+            "10",
+            vec!["--hatch-batch", "5", "--hatch-jitter", "100ms"]
+        ) == USERS
+    );
+
+    assert!(index.hits() > 0);
+}
+
+#[test]
+// Confirm invalid hatch rate ramps, batches and jitter are rejected.
+fn test_hatch_rate_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in [
+        vec!["--hatch-rate", "1..20"],
+        vec!["--hatch-rate", "0..20/5m"],
+        vec!["--hatch-rate", "1..20/5x"],
+        vec!["--hatch-rate", "1..20/5m:log"],
+        vec!["--hatch-batch", "0"],
+        vec!["--hatch-jitter", "foo"],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use httpmock::{Method::GET, MockServer};
+use tokio::time::{sleep, Duration};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const HEALTHCHECK_PATH: &str = "/healthz";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 3;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Check the host is healthy before starting, and again while the load test runs.
+fn test_healthcheck() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let healthcheck = server.mock(|when, then| {
+        when.method(GET).path(HEALTHCHECK_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--healthcheck",
+            HEALTHCHECK_PATH,
+            "--healthcheck-interval",
+            "1",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm the host was checked before starting and while running.
+    assert!(index.hits() > 0);
+    assert!(healthcheck.hits() > 1);
+
+    // Health checks aren't included in the metrics, and the host never failed one.
+    assert!(swanling_metrics.requests.len() == 1);
+    assert!(!swanling_metrics
+        .annotations
+        .iter()
+        .any(|annotation| annotation.text.contains("health check")));
+}
+
+#[test]
+// Don't start the load test if the host is unhealthy.
+fn test_healthcheck_failed() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let healthcheck = server.mock(|when, then| {
+        when.method(GET).path(HEALTHCHECK_PATH);
+        then.status(503);
+    });
+
+    let configuration =
+        common::build_configuration(&server, vec!["--healthcheck", HEALTHCHECK_PATH]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+
+    // Confirm no load was generated.
+    assert!(healthcheck.hits() == 1);
+    assert!(index.hits() == 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Pause the load test while the host is unhealthy, and resume it when it's healthy again.
+async fn test_healthcheck_pause() {
+    // Start the mock server.
+    let server = MockServer::start_async().await;
+
+    // Setup the endpoints needed for this test on the mock server.
+    let index = server
+        .mock_async(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        })
+        .await;
+    let healthcheck = server
+        .mock_async(|when, then| {
+            when.method(GET).path(HEALTHCHECK_PATH);
+            then.status(200);
+        })
+        .await;
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--healthcheck",
+            HEALTHCHECK_PATH,
+            "--healthcheck-interval",
+            "1",
+            "--healthcheck-pause",
+            "--no-telnet",
+            "--no-websocket",
+        ],
+    );
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    let load_test = tokio::spawn(swanling_attack.execute_async());
+
+    // The health check fails once the endpoint is removed.
+    sleep(Duration::from_millis(1500)).await;
+    healthcheck.delete_async().await;
+
+    // Confirm users stop making requests while the load test is paused.
+    sleep(Duration::from_millis(3000)).await;
+    let paused_hits = index.hits_async().await;
+    sleep(Duration::from_millis(1000)).await;
+    assert!(index.hits_async().await == paused_hits);
+
+    // The health check passes again once the endpoint is restored.
+    server
+        .mock_async(|when, then| {
+            when.method(GET).path(HEALTHCHECK_PATH);
+            then.status(200);
+        })
+        .await;
+
+    let swanling_metrics = load_test.await.unwrap().unwrap();
+
+    // Confirm the load test resumed, and the health checks are annotated.
+    assert!(index.hits_async().await > paused_hits);
+    let annotations: Vec<&str> = swanling_metrics
+        .annotations
+        .iter()
+        .map(|annotation| annotation.text.as_str())
+        .collect();
+    assert!(annotations.contains(&"paused"));
+    assert!(annotations.contains(&"resumed"));
+    assert!(annotations
+        .iter()
+        .any(|text| text.ends_with("failed health check")));
+    assert!(annotations
+        .iter()
+        .any(|text| text.ends_with("passed health check")));
+}
+
+#[test]
+// Confirm the health check options must be valid.
+fn test_healthcheck_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in [
+        // The health check must be a path.
+        vec!["--healthcheck", "healthz"],
+        // Checking hosts while running requires a health check.
+        vec!["--healthcheck-interval", "5"],
+        vec!["--healthcheck-pause"],
+        // Pausing requires checking hosts while running.
+        vec![
+            "--healthcheck",
+            HEALTHCHECK_PATH,
+            "--healthcheck-interval",
+            "0",
+            "--healthcheck-pause",
+        ],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use httpmock::{Method::GET, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Build a load test with a weighted and a scheduled task.
+fn build_load_test(server: &MockServer, options: Vec<&str>) -> SwanlingAttack {
+    let configuration = common::build_configuration(server, options);
+    common::build_load_test(
+        configuration,
+        &taskset!("LoadTest")
+            .register_task(task!(get_index).set_name("index"))
+            .register_task(
+                task!(get_index)
+                    .set_name("heartbeat")
+                    .set_schedule(Duration::from_secs(5))
+                    .unwrap(),
+            ),
+        None,
+        None,
+    )
+}
+
+#[test]
+// Confirm information requested with --help and --version is returned.
+fn test_help() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for (option, expected) in [
+        ("--help", "Usage:"),
+        ("--version", env!("CARGO_PKG_VERSION")),
+    ] {
+        match build_load_test(&server, vec![option]).execute() {
+            Err(SwanlingError::Help { message }) => assert!(message.contains(expected)),
+            result => panic!("unexpected result: {:?}", result.map(|_| ())),
+        }
+    }
+}
+
+#[test]
+// Confirm the registered task sets can be listed.
+fn test_list() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let task_sets = build_load_test(&server, vec![]).list();
+    assert!(task_sets.len() == 1);
+    assert!(task_sets[0].name == "LoadTest");
+    assert!(task_sets[0].weight == 1);
+    assert!(task_sets[0].fixed_users.is_none());
+    assert!(task_sets[0].host.is_none());
+    let tasks: Vec<(&str, Option<Duration>)> = task_sets[0]
+        .tasks
+        .iter()
+        .map(|task| (task.name.as_str(), task.schedule))
+        .collect();
+    assert!(tasks == vec![("index", None), ("heartbeat", Some(Duration::from_secs(5)))]);
+}
+
+#[test]
+// Confirm --list displays task sets and tasks without running the load test.
+fn test_list_option() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    for options in [
+        vec!["--list"],
+        vec!["--list", "--format", "text"],
+        vec!["--list", "--format", "json"],
+    ] {
+        let swanling_metrics = build_load_test(&server, options).execute().unwrap();
+        assert!(swanling_metrics.requests.is_empty());
+    }
+    assert!(index.hits() == 0);
+
+    // The format must be valid, and requires --list.
+    for options in [vec!["--list", "--format", "xml"], vec!["--format", "json"]] {
+        assert!(build_load_test(&server, options).execute().is_err());
+    }
+}
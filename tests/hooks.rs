@@ -0,0 +1,104 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const MAINTENANCE_PATH: &str = "/maintenance";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const MAINTENANCE_KEY: usize = 1;
+
+// Header added to all requests by the request hook.
+const SIGNATURE_HEADER: &str = "x-signature";
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_maintenance(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(MAINTENANCE_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY. Only requests signed by
+        // the request hook are matched.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header_exists(SIGNATURE_HEADER);
+            then.status(200);
+        }),
+        // Next set up MAINTENANCE_PATH, store in vector at MAINTENANCE_KEY. The page
+        // returns a 200, but the response hook classifies it as a failure.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(MAINTENANCE_PATH)
+                .header_exists(SIGNATURE_HEADER);
+            then.status(200).header("x-maintenance", "true");
+        }),
+    ]
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest")
+        .register_task(task!(get_index))
+        .register_task(task!(get_maintenance))
+}
+
+#[test]
+// Register request and response hooks, and confirm they are invoked.
+fn test_hooks() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration.
+    let configuration = common::build_configuration(&server, vec![]);
+
+    // Register hooks and run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None)
+            .register_request_hook(|request, _user| {
+                request
+                    .headers_mut()
+                    .insert(SIGNATURE_HEADER, "signed".parse().unwrap());
+            })
+            .register_response_hook(|response, request| {
+                if response.headers().contains_key("x-maintenance") {
+                    request.success = false;
+                    request.error = "maintenance mode".to_string();
+                }
+            }),
+        None,
+    );
+
+    // Confirm the request hook signed all requests.
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[MAINTENANCE_KEY].hits() > 0);
+
+    // Confirm the response hook classified the maintenance page as a failure.
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    let maintenance_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", MAINTENANCE_PATH))
+        .unwrap();
+    assert!(index_metrics.fail_count == 0);
+    assert!(maintenance_metrics.success_count == 0);
+    assert!(maintenance_metrics.fail_count > 0);
+}
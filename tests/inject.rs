@@ -0,0 +1,132 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Latency injected into requests, in milliseconds.
+const INJECTED_LATENCY: usize = 200;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against a common endpoint.
+fn setup_mock_server_endpoint(server: &MockServer) -> MockRef<'_> {
+    server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    })
+}
+
+// Run a load test with the specified options, and return the metrics.
+fn run_inject_test(server: &MockServer, options: Vec<&str>) -> SwanlingMetrics {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration_options = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+    ];
+    configuration_options.extend(options);
+
+    // Build configuration.
+    let configuration = common::build_configuration(server, configuration_options);
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    )
+}
+
+#[test]
+// Delay all requests with --inject-latency.
+fn test_inject_latency() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let mock_endpoint = setup_mock_server_endpoint(&server);
+
+    let inject_latency = format!("{}ms@100%", INJECTED_LATENCY);
+    let swanling_metrics = run_inject_test(&server, vec!["--inject-latency", &inject_latency]);
+
+    // Confirm the requests were sent, and the injected latency was included in their
+    // response times.
+    assert!(mock_endpoint.hits() > 0);
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(index_metrics.fail_count == 0);
+    assert!(index_metrics.raw_data.minimum_time >= INJECTED_LATENCY);
+}
+
+#[test]
+// Abort all requests with --inject-abort.
+fn test_inject_abort() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let mock_endpoint = setup_mock_server_endpoint(&server);
+
+    let swanling_metrics = run_inject_test(&server, vec!["--inject-abort", "100%"]);
+
+    // Confirm no requests were sent, but all were recorded as failures.
+    assert!(mock_endpoint.hits() == 0);
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(index_metrics.success_count == 0);
+    assert!(index_metrics.fail_count > 0);
+
+    // Confirm the aborted requests are categorized in the error summary.
+    assert!(!swanling_metrics.errors.is_empty());
+    assert!(swanling_metrics
+        .errors
+        .values()
+        .all(|error| error.category == "Aborted"));
+}
+
+#[test]
+// Confirm invalid --inject-latency and --inject-abort values are rejected.
+fn test_inject_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in &[
+        vec!["--inject-latency", "50ms"],
+        vec!["--inject-latency", "foo@1%"],
+        vec!["--inject-latency", "50ms@200%"],
+        vec!["--inject-abort", "foo"],
+    ] {
+        let configuration = common::build_configuration(&server, options.clone());
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use httpmock::MockServer;
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Confirm all problems with a load test are reported together.
+fn test_invalid_configuration() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration = common::build_configuration(
+        &server,
+        vec!["--users", "0", "--shutdown-timeout", "forever"],
+    );
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest")
+            .register_task(task!(get_index).set_name("index"))
+            .register_task(task!(get_index).set_name("index")),
+        None,
+        None,
+    );
+
+    match swanling_attack.execute() {
+        Err(SwanlingError::InvalidConfiguration { errors }) => {
+            let options: Vec<String> = errors
+                .iter()
+                .map(|error| match error {
+                    SwanlingError::InvalidOption { option, .. } => option.to_string(),
+                    _ => panic!("unexpected error: {:?}", error),
+                })
+                .collect();
+            assert!(
+                options
+                    == vec![
+                        "SwanlingTask.set_name".to_string(),
+                        "--users".to_string(),
+                        "--shutdown-timeout".to_string(),
+                    ]
+            );
+        }
+        result => panic!("unexpected result: {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+// Confirm a single problem is reported as is.
+fn test_invalid_configuration_single() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration = common::build_configuration(&server, vec!["--users", "0"]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+
+    match swanling_attack.execute() {
+        Err(SwanlingError::InvalidOption { option, .. }) => assert!(option == "--users"),
+        result => panic!("unexpected result: {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+// Confirm every duplicate name and unresolved dependency is reported, not only the first.
+fn test_invalid_configuration_names() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration = common::build_configuration(&server, vec![]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest")
+            .register_task(task!(get_index).set_name("index"))
+            .register_task(task!(get_index).set_name("index"))
+            .register_task(task!(get_index).set_name("about"))
+            .register_task(task!(get_index).set_name("about"))
+            .register_task(task!(get_index).set_depends_on("login"))
+            .register_task(task!(get_index).set_depends_on("logout")),
+        None,
+        None,
+    );
+
+    match swanling_attack.execute() {
+        Err(SwanlingError::InvalidConfiguration { errors }) => {
+            let values: Vec<String> = errors
+                .iter()
+                .map(|error| match error {
+                    SwanlingError::InvalidOption { value, .. } => value.to_string(),
+                    _ => panic!("unexpected error: {:?}", error),
+                })
+                .collect();
+            assert!(values == vec!["index", "about", "login", "logout"]);
+        }
+        result => panic!("unexpected result: {:?}", result.map(|_| ())),
+    }
+}
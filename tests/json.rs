@@ -0,0 +1,108 @@
+use httpmock::{Method::GET, MockServer};
+use serde::Deserialize;
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const ITEM_PATH: &str = "/item";
+const INVALID_PATH: &str = "/invalid";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// The JSON body returned by the mock server.
+#[derive(Deserialize)]
+struct Item {
+    id: usize,
+}
+
+// Test task.
+pub async fn get_item(user: &SwanlingUser) -> SwanlingTaskResult {
+    let item: Item = user.get_json(ITEM_PATH).await?;
+    assert_eq!(item.id, 1);
+    Ok(())
+}
+
+// Test task.
+pub async fn get_invalid(user: &SwanlingUser) -> SwanlingTaskResult {
+    let swanling = user.get(INVALID_PATH).await?;
+    let _item: Item = swanling.json_as(user).await?;
+    Ok(())
+}
+
+#[test]
+// Deserialize JSON responses, failing requests that can't be deserialized.
+fn test_json() {
+    let error_log = "json-error-log.csv";
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let item = server.mock(|when, then| {
+        when.method(GET).path(ITEM_PATH);
+        then.status(200).body(r#"{"id": 1}"#);
+    });
+    let invalid = server.mock(|when, then| {
+        when.method(GET).path(INVALID_PATH);
+        then.status(200).body(r#"{"id": "one"}"#);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--error-log",
+            error_log,
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(get_item))
+                .register_task(task!(get_invalid))
+                .set_wait_time(0, 1)
+                .unwrap(),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm both endpoints were loaded.
+    assert!(item.hits() > 0);
+    assert!(invalid.hits() > 0);
+
+    // Only the response that couldn't be deserialized failed.
+    let item_metrics = swanling_metrics.requests.get("GET /item").unwrap();
+    assert!(item_metrics.fail_count == 0);
+    let invalid_metrics = swanling_metrics.requests.get("GET /invalid").unwrap();
+    assert!(invalid_metrics.fail_count > 0);
+
+    // Confirm why it failed is recorded in the metrics and the error log.
+    let error = swanling_metrics
+        .errors
+        .values()
+        .find(|error| error.name == INVALID_PATH)
+        .unwrap();
+    assert!(error
+        .error
+        .starts_with("failed to deserialize JSON response"));
+    assert!(error.category == "ValidationFailed");
+    let error_log_contents = std::fs::read_to_string(error_log).unwrap();
+    assert!(error_log_contents.contains("invalid type: string"));
+
+    common::cleanup_files(vec![error_log]);
+}
@@ -0,0 +1,126 @@
+use httpmock::{Method::GET, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::SwanlingEvent;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const SLOW_PATH: &str = "/slow";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 4;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_slow(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(SLOW_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Warn when the p99 latency of a request stays too high, without stopping the load test.
+fn test_latency_alert() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let slow = server.mock(|when, then| {
+        when.method(GET).path(SLOW_PATH);
+        then.status(200).delay(Duration::from_millis(300));
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--latency-alert",
+            "200ms@1s",
+        ],
+    );
+
+    let mut swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest")
+            .register_task(task!(get_index))
+            .register_task(task!(get_slow)),
+        None,
+        None,
+    );
+    let events = swanling_attack.subscribe_events();
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(swanling_attack, None);
+    assert!(index.hits() > 0);
+    assert!(slow.hits() > 0);
+
+    // Confirm the load test ran to completion.
+    assert!(swanling_metrics.duration == RUN_TIME);
+    assert!(swanling_metrics.aborted.is_none());
+
+    // Only the slow request was reported, once.
+    let alerts: Vec<String> = events
+        .iter()
+        .filter_map(|event| match event {
+            SwanlingEvent::LatencyAlert {
+                request, threshold, ..
+            } => {
+                assert!(threshold == "--latency-alert 200ms@1s");
+                Some(request)
+            }
+            _ => None,
+        })
+        .collect();
+    assert!(alerts == vec![format!("GET {}", SLOW_PATH)]);
+
+    // Confirm the alert was annotated.
+    assert!(swanling_metrics
+        .annotations
+        .iter()
+        .any(|annotation| annotation.text.starts_with("p99 latency of GET /slow")));
+}
+
+#[test]
+// Confirm --latency-alert must be valid.
+fn test_latency_alert_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in [
+        // A window is required.
+        vec!["--latency-alert", "500ms"],
+        // The bound must be in milliseconds.
+        vec!["--latency-alert", "0.5s@30s"],
+        // The window can't be empty.
+        vec!["--latency-alert", "500ms@0s"],
+        // Latency can't be measured without metrics.
+        vec!["--latency-alert", "500ms@30s", "--no-metrics"],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
@@ -9,6 +9,7 @@ use swanling::prelude::*;
 // Paths used in load tests performed during these tests.
 const INDEX_PATH: &str = "/";
 const ERROR_PATH: &str = "/error";
+const SECRET_PATH: &str = "/secret";
 
 // Indexes to the above paths.
 const INDEX_KEY: usize = 0;
@@ -589,3 +590,400 @@ fn test_all_logs_raw() {
 fn test_all_logs_raw_gaggle() {
     run_gaggle_test(TestType::All, "raw");
 }
+
+#[test]
+// Record raw request latencies, and confirm one sample is written for each request.
+fn test_record_raw_latencies() {
+    let raw_latencies = "raw-latencies.csv";
+
+    let server = MockServer::start();
+
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--record-raw-latencies",
+            raw_latencies,
+            "--users",
+            "4",
+            "--hatch-rate",
+            "4",
+            "--run-time",
+            "2",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    // Confirm the raw latencies file exists.
+    assert!(std::path::Path::new(raw_latencies).exists());
+
+    // Confirm there's a csv header and one line per request.
+    let requests = mock_endpoints[INDEX_KEY].hits() + mock_endpoints[ERROR_KEY].hits();
+    assert!(requests > 0);
+    assert!(common::file_length(raw_latencies) == requests + 1);
+
+    common::cleanup_files(vec![raw_latencies]);
+}
+
+#[test]
+#[cfg(feature = "parquet")]
+// Record raw request latencies to a parquet file, and confirm one row is written for each
+// request.
+fn test_record_raw_latencies_parquet() {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    let raw_latencies = "raw-latencies.parquet";
+
+    let server = MockServer::start();
+
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--record-raw-latencies",
+            raw_latencies,
+            "--users",
+            "4",
+            "--hatch-rate",
+            "4",
+            "--run-time",
+            "2",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    // Confirm the parquet file has all columns, and one row per request.
+    let file = std::fs::File::open(raw_latencies).unwrap();
+    let reader = SerializedFileReader::new(file).unwrap();
+    let metadata = reader.metadata().file_metadata();
+    assert!(metadata.schema_descr().num_columns() == 9);
+    let requests = mock_endpoints[INDEX_KEY].hits() + mock_endpoints[ERROR_KEY].hits();
+    assert!(requests > 0);
+    assert!(metadata.num_rows() as usize == requests);
+
+    common::cleanup_files(vec![raw_latencies]);
+}
+
+#[test]
+#[cfg(not(feature = "parquet"))]
+// Confirm recording raw latencies to a parquet file requires the parquet feature.
+fn test_record_raw_latencies_parquet_not_enabled() {
+    let server = MockServer::start();
+
+    let configuration = common::build_configuration(
+        &server,
+        vec!["--record-raw-latencies", "raw-latencies.parquet"],
+    );
+
+    match common::build_load_test(configuration, &get_tasks(), None, None).execute() {
+        Err(SwanlingError::FeatureNotEnabled { feature, .. }) => assert!(feature == "parquet"),
+        result => panic!("unexpected result: {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+// Confirm all json log records include the version of the log schema, and all csv logs
+// start with a header row.
+fn test_log_schema() {
+    let server = MockServer::start();
+
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    for format in &["json", "csv"] {
+        let request_log = "schema-request-log.".to_string() + format;
+        let task_log = "schema-task-log.".to_string() + format;
+        let error_log = "schema-error-log.".to_string() + format;
+        let debug_log = "schema-debug-log.".to_string() + format;
+
+        let configuration = common::build_configuration(
+            &server,
+            vec![
+                "--request-log",
+                &request_log,
+                "--request-format",
+                format,
+                "--task-log",
+                &task_log,
+                "--task-format",
+                format,
+                "--error-log",
+                &error_log,
+                "--error-format",
+                format,
+                "--debug-log",
+                &debug_log,
+                "--debug-format",
+                format,
+                "--users",
+                "2",
+                "--hatch-rate",
+                "2",
+                "--run-time",
+                "2",
+            ],
+        );
+
+        // Run the Swanling Attack.
+        common::run_load_test(
+            common::build_load_test(configuration, &get_tasks(), None, None),
+            None,
+        );
+
+        for (log, header) in &[
+            (&request_log, "elapsed,"),
+            (&task_log, "elapsed,"),
+            (&error_log, "elapsed,"),
+            (&debug_log, "tag,"),
+        ] {
+            let contents = std::fs::read_to_string(log).unwrap();
+            let mut lines = contents.lines();
+            if *format == "json" {
+                for line in lines {
+                    let record: serde_json::Value = serde_json::from_str(line).unwrap();
+                    assert!(record["schema_version"] == swanling::logger::LOG_SCHEMA_VERSION);
+                }
+            } else {
+                assert!(lines.next().unwrap().starts_with(header));
+                assert!(lines.next().is_some());
+            }
+        }
+
+        common::cleanup_files(vec![&request_log, &task_log, &error_log, &debug_log]);
+    }
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+}
+
+#[test]
+// Confirm logs with a name ending in .gz are gzip compressed.
+fn test_compressed_ndjson_log() {
+    use std::io::Read;
+
+    let request_log = "compressed-request-log.json.gz";
+
+    let server = MockServer::start();
+
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--request-log",
+            request_log,
+            "--request-format",
+            "ndjson",
+            "--users",
+            "2",
+            "--hatch-rate",
+            "2",
+            "--run-time",
+            "2",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    // Confirm the log decompresses to one json record per request.
+    let mut contents = String::new();
+    flate2::read::GzDecoder::new(std::fs::File::open(request_log).unwrap())
+        .read_to_string(&mut contents)
+        .unwrap();
+    let requests = mock_endpoints[INDEX_KEY].hits() + mock_endpoints[ERROR_KEY].hits();
+    assert!(contents.lines().count() >= requests);
+    for line in contents.lines() {
+        let record: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(record["schema_version"] == swanling::logger::LOG_SCHEMA_VERSION);
+    }
+
+    common::cleanup_files(vec![request_log]);
+}
+
+// Test task.
+pub async fn get_secret(user: &SwanlingUser) -> SwanlingTaskResult {
+    let swanling = user.get(SECRET_PATH).await?;
+
+    if let Ok(r) = swanling.response {
+        let headers = r.headers().clone();
+        let body = r.text().await.unwrap_or_default();
+        user.log_debug(
+            "secret",
+            Some(&swanling.request),
+            Some(&headers),
+            Some(&body),
+        )?;
+    }
+    Ok(())
+}
+
+#[test]
+// Confirm secrets are redacted from the debug log, and long bodies are truncated.
+fn test_debug_log_redaction() {
+    let debug_log = "redacted-debug-log.json";
+
+    let server = MockServer::start();
+
+    let secret = server.mock(|when, then| {
+        when.method(GET).path(SECRET_PATH);
+        then.status(200)
+            .header("x-token", "Bearer header123")
+            .body(format!("token: Bearer body123 {}", "x".repeat(100)));
+    });
+
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--debug-log",
+            debug_log,
+            "--debug-format",
+            "json",
+            "--debug-redact",
+            "Bearer [a-z0-9]+",
+            "--debug-body-max-bytes",
+            "40",
+            "--users",
+            "1",
+            "--hatch-rate",
+            "1",
+            "--run-time",
+            "1",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_secret)),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(secret.hits() > 0);
+
+    let contents = std::fs::read_to_string(debug_log).unwrap();
+    assert!(!contents.contains("header123"));
+    assert!(!contents.contains("body123"));
+    for line in contents.lines() {
+        let record: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(record["header"].as_str().unwrap().contains("[REDACTED]"));
+        let body = record["body"].as_str().unwrap();
+        assert!(body.starts_with("token: [REDACTED] xxx"));
+        assert!(body.ends_with("... [truncated 78 bytes]"));
+    }
+
+    common::cleanup_files(vec![debug_log]);
+}
+
+#[test]
+// Confirm invalid redaction patterns are rejected.
+fn test_debug_log_redaction_invalid() {
+    let server = MockServer::start();
+
+    for options in [
+        vec![
+            "--debug-log",
+            "invalid-debug-log.json",
+            "--debug-redact",
+            "Bearer [",
+        ],
+        vec!["--debug-redact", "Bearer .*", "--manager"],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(configuration, &get_tasks(), None, None);
+        assert!(swanling_attack.execute().is_err());
+    }
+}
+
+#[test]
+// Confirm identical errors are aggregated in the error log.
+fn test_error_log_window() {
+    let server = MockServer::start();
+
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    for format in &["json", "csv"] {
+        let error_log = "window-error-log.".to_string() + format;
+
+        let configuration = common::build_configuration(
+            &server,
+            vec![
+                "--error-log",
+                &error_log,
+                "--error-format",
+                format,
+                "--error-log-window",
+                "60s",
+                "--users",
+                "2",
+                "--hatch-rate",
+                "2",
+                "--run-time",
+                "2",
+            ],
+        );
+        let errors = mock_endpoints[ERROR_KEY].hits();
+
+        // Run the Swanling Attack.
+        common::run_load_test(
+            common::build_load_test(configuration, &get_tasks(), None, None),
+            None,
+        );
+        let errors = mock_endpoints[ERROR_KEY].hits() - errors;
+
+        // All errors were seen within the window, and are written as one record.
+        let contents = std::fs::read_to_string(&error_log).unwrap();
+        let mut lines = contents.lines();
+        let count = if *format == "json" {
+            let record: serde_json::Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+            record["count"].as_u64().unwrap() as usize
+        } else {
+            assert!(lines.next().unwrap().ends_with(",count,run_id"));
+            let row = lines.next().unwrap();
+            let row = &row[..row.rfind(',').unwrap()];
+            row[row.rfind(',').unwrap() + 1..].parse().unwrap()
+        };
+        // Requests still running when the load test stops aren't recorded.
+        assert!(count > 1 && count <= errors);
+        assert!(lines.next().is_none());
+
+        common::cleanup_files(vec![&error_log]);
+    }
+}
+
+#[test]
+// Confirm the error log window must be usable.
+fn test_error_log_window_invalid() {
+    let server = MockServer::start();
+
+    for options in [
+        // The window must be a time.
+        vec![
+            "--error-log",
+            "invalid-error-log.json",
+            "--error-log-window",
+            "foo",
+        ],
+        // Errors are only aggregated when they are logged.
+        vec!["--error-log-window", "1s"],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(configuration, &get_tasks(), None, None);
+        assert!(swanling_attack.execute().is_err());
+    }
+}
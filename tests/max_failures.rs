@@ -0,0 +1,128 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const LOGIN_PATH: &str = "/login";
+const ERROR_PATH: &str = "/error";
+
+// Indexes to the above paths.
+const LOGIN_KEY: usize = 0;
+const ERROR_KEY: usize = 1;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+const MAX_FAILURES: usize = 3;
+
+// Test session function.
+pub async fn login(user: &mut SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(LOGIN_PATH).await?;
+    Ok(())
+}
+
+// Test task, always fails.
+pub async fn get_error(user: &SwanlingUser) -> SwanlingTaskResult {
+    let mut swanling = user.get(ERROR_PATH).await?;
+    if let Ok(response) = &swanling.response {
+        if !response.status().is_success() {
+            return user.set_failure("request failed", &mut swanling.request, None, None);
+        }
+    }
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up LOGIN_PATH, store in vector at LOGIN_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(LOGIN_PATH);
+            then.status(200);
+        }),
+        // Next set up ERROR_PATH, store in vector at ERROR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ERROR_PATH);
+            then.status(500);
+        }),
+    ]
+}
+
+// Run a load test with the specified task set, and return the metrics.
+fn run_max_failures_test(server: &MockServer, taskset: SwanlingTaskSet) -> SwanlingMetrics {
+    // Build configuration.
+    let configuration = common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &taskset, None, None),
+        None,
+    )
+}
+
+#[test]
+// Users are stopped after too many consecutive task failures.
+fn test_max_failures_per_user() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let swanling_metrics = run_max_failures_test(
+        &server,
+        taskset!("LoadTest")
+            .register_on_start_session(|user| Box::pin(login(user)))
+            .register_task(task!(get_error))
+            .set_max_failures_per_user(MAX_FAILURES)
+            .unwrap(),
+    );
+
+    // Confirm each user logged in once, and was stopped after MAX_FAILURES failures.
+    assert!(mock_endpoints[LOGIN_KEY].hits() == USERS);
+    assert!(mock_endpoints[ERROR_KEY].hits() == USERS * MAX_FAILURES);
+    assert!(swanling_metrics.removed_users.values().sum::<usize>() == USERS);
+}
+
+#[test]
+// Users stopped after too many consecutive task failures are replaced with fresh users.
+fn test_replace_failed_users() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let swanling_metrics = run_max_failures_test(
+        &server,
+        taskset!("LoadTest")
+            .register_on_start_session(|user| Box::pin(login(user)))
+            .register_task(task!(get_error))
+            .set_max_failures_per_user(MAX_FAILURES)
+            .unwrap()
+            .set_replace_failed_users(true),
+    );
+
+    // Confirm fresh users logged in again after each user was replaced.
+    assert!(mock_endpoints[LOGIN_KEY].hits() > USERS);
+    assert!(mock_endpoints[ERROR_KEY].hits() > USERS * MAX_FAILURES);
+    assert!(swanling_metrics.removed_users.values().sum::<usize>() > USERS);
+}
+
+#[test]
+// Max failures per user must be at least 1.
+fn test_max_failures_per_user_invalid() {
+    assert!(taskset!("LoadTest").set_max_failures_per_user(0).is_err());
+}
@@ -0,0 +1,84 @@
+use httpmock::{Method::GET, MockServer};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod common;
+
+use swanling::metrics::OTHER_REQUEST_NAME;
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const ITEM_PATH: &str = "/item/";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+const MAX_REQUEST_NAMES: usize = 3;
+
+// Each request is made to a unique path.
+static ITEM: AtomicUsize = AtomicUsize::new(0);
+
+// Test task.
+pub async fn get_item(user: &SwanlingUser) -> SwanlingTaskResult {
+    let item = ITEM.fetch_add(1, Ordering::SeqCst);
+    let _swanling = user.get(&format!("{}{}", ITEM_PATH, item)).await?;
+    Ok(())
+}
+
+#[test]
+// Requests with new names are coalesced once the maximum number of names is tracked.
+fn test_max_request_names() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let item = server.mock(|when, then| {
+        when.method(GET).path_contains(ITEM_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--max-request-names",
+            &MAX_REQUEST_NAMES.to_string(),
+            // Count all requests, so metrics can be compared to the mock endpoint.
+            "--no-reset-metrics",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_item)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm only the maximum number of request names plus the other bucket were tracked.
+    assert!(item.hits() > MAX_REQUEST_NAMES);
+    assert!(swanling_metrics.requests.len() == MAX_REQUEST_NAMES + 1);
+    let other_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", OTHER_REQUEST_NAME))
+        .unwrap();
+    assert!(other_metrics.path == OTHER_REQUEST_NAME);
+
+    // Confirm no requests were lost.
+    let success_count: usize = swanling_metrics
+        .requests
+        .values()
+        .map(|request| request.success_count)
+        .sum();
+    assert!(success_count == item.hits());
+    assert!(other_metrics.success_count == item.hits() - MAX_REQUEST_NAMES);
+}
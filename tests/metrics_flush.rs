@@ -0,0 +1,115 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const MISSING_PATH: &str = "/missing";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_missing(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(MISSING_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Metrics aggregated by users are all sent to the parent, even if the load test ends
+// before --metrics-flush elapses.
+fn test_metrics_flush() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let missing = server.mock(|when, then| {
+        when.method(GET).path(MISSING_PATH);
+        then.status(404);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--metrics-flush",
+            "10s",
+            // Count all requests, so metrics can be compared to the mock endpoints.
+            "--no-reset-metrics",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(get_index))
+                .register_task(task!(get_missing)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm no successful requests were lost.
+    assert!(index.hits() > 0);
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(index_metrics.success_count == index.hits());
+    assert!(index_metrics.fail_count == 0);
+
+    // Confirm no failed requests were lost, and their errors were recorded.
+    assert!(missing.hits() > 0);
+    let missing_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", MISSING_PATH))
+        .unwrap();
+    assert!(missing_metrics.success_count == 0);
+    assert!(missing_metrics.fail_count == missing.hits());
+    assert!(!swanling_metrics.errors.is_empty());
+
+    // Confirm no tasks were lost.
+    let task_count: usize = swanling_metrics.tasks[0]
+        .iter()
+        .map(|task| task.counter)
+        .sum();
+    assert!(task_count == index.hits() + missing.hits());
+}
+
+#[test]
+// Confirm --metrics-flush must be a delay.
+fn test_metrics_flush_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration = common::build_configuration(&server, vec!["--metrics-flush", "soon"]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
@@ -0,0 +1,203 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const STATUS_PATH: &str = "/status";
+
+// Load test configuration.
+const USERS: usize = 2;
+const HATCH_RATE: usize = 100;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_status(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(STATUS_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Run task sets and tasks against several hosts, tracking the metrics of each host
+// separately.
+fn test_multiple_hosts() {
+    // Start a mock server for the frontend, and another for its API.
+    let frontend = MockServer::start();
+    let api = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock servers.
+    let frontend_index = frontend.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let api_index = api.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let api_status = api.mock(|when, then| {
+        when.method(GET).path(STATUS_PATH);
+        then.status(200);
+    });
+
+    // Build configuration, with --host set to the frontend.
+    let configuration = common::build_configuration(
+        &frontend,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            // Launch all users right away, well within the one second run time.
+            "--hatch-rate",
+            &HATCH_RATE.to_string(),
+            // Count all requests, so metrics can be compared to the mock endpoints.
+            "--no-reset-metrics",
+        ],
+    );
+
+    // The frontend task set requests the API status with a task-level base URL, and the API
+    // task set always runs against the API.
+    let frontend_tasks = taskset!("Frontend")
+        .register_task(task!(get_index))
+        .register_task(task!(get_status).set_base_url(&api.url("/")).unwrap());
+    let api_tasks = taskset!("Api")
+        .set_base_url(&api.url("/"))
+        .unwrap()
+        .register_task(task!(get_index));
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &frontend_tasks, None, None)
+            .register_taskset(api_tasks),
+        None,
+    );
+
+    // Requests to the frontend are named by path.
+    let frontend_host = format!("127.0.0.1:{}", frontend.port());
+    let frontend_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(frontend_index.hits() > 0);
+    assert!(frontend_metrics.success_count == frontend_index.hits());
+    assert!(frontend_metrics.host == frontend_host);
+
+    // Requests to the API are named with its host, so they're tracked separately.
+    let api_host = format!("127.0.0.1:{}", api.port());
+    let api_index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}{}", api_host, INDEX_PATH))
+        .unwrap();
+    assert!(api_index.hits() > 0);
+    assert!(api_index_metrics.success_count == api_index.hits());
+    assert!(api_index_metrics.host == api_host);
+    let api_status_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}{}", api_host, STATUS_PATH))
+        .unwrap();
+    assert!(api_status.hits() > 0);
+    assert!(api_status_metrics.success_count == api_status.hits());
+
+    // A table summarizes the requests made to each host.
+    let displayed = swanling_metrics.to_string();
+    assert!(displayed.contains("PER HOST METRICS"));
+    assert!(displayed.contains(&api_host));
+}
+
+#[test]
+// Spread users over several hosts with --host, tracking the metrics of each host separately.
+fn test_host_rotation() {
+    // Start a mock server for each deployment being compared.
+    let blue = MockServer::start();
+    let green = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock servers.
+    let blue_index = blue.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let green_index = green.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration, sending one user to blue and three to green.
+    let hosts = format!("{},{}=3", blue.url("/"), green.url("/"));
+    let configuration = common::build_configuration(
+        &blue,
+        vec![
+            "--host",
+            &hosts,
+            "--users",
+            "4",
+            // Launch all users right away, well within the one second run time.
+            "--hatch-rate",
+            &HATCH_RATE.to_string(),
+            // Count all requests, so metrics can be compared to the mock endpoints.
+            "--no-reset-metrics",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Requests to each host are named with the host, so they're tracked separately.
+    for (server, index) in [(&blue, &blue_index), (&green, &green_index)] {
+        let host = format!("127.0.0.1:{}", server.port());
+        let index_metrics = swanling_metrics
+            .requests
+            .get(&format!("GET {}{}", host, INDEX_PATH))
+            .unwrap();
+        assert!(index.hits() > 0);
+        assert!(index_metrics.success_count == index.hits());
+        assert!(index_metrics.host == host);
+    }
+
+    // A table summarizes the requests made to each host.
+    assert!(swanling_metrics.to_string().contains("PER HOST METRICS"));
+}
+
+#[test]
+// Confirm each host and weight set with --host must be valid.
+fn test_host_rotation_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for hosts in [
+        format!("{},not a url", server.url("/")),
+        format!("{},{}=0", server.url("/"), server.url("/")),
+        format!("{}=heavy", server.url("/")),
+    ] {
+        let configuration = common::build_configuration(&server, vec!["--host", &hosts]);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
+
+#[test]
+// Confirm base URLs must include a host.
+fn test_base_url_invalid() {
+    assert!(taskset!("LoadTest").set_base_url("not a url").is_err());
+    assert!(taskset!("LoadTest")
+        .set_base_url("data:text/plain")
+        .is_err());
+    assert!(task!(get_index).set_base_url("/relative").is_err());
+}
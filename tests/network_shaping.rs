@@ -0,0 +1,142 @@
+use httpmock::{Method::GET, Method::POST, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const UPLOAD_PATH: &str = "/upload";
+
+// Bodies are 1,000 bytes, taking 250 milliseconds to transfer at 4,000 bytes per second.
+const BODY_SIZE: usize = 1_000;
+const BANDWIDTH: usize = 4_000;
+const TRANSFER_TIME: usize = 250;
+
+// Added latency, in milliseconds.
+const LATENCY: u64 = 200;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let mut swanling = user.get(INDEX_PATH).await?;
+
+    // Confirm the whole body is still returned when reading it at a limited bandwidth.
+    match swanling.response {
+        Ok(response) => match response.text().await {
+            Ok(text) if text.len() == BODY_SIZE => Ok(()),
+            _ => user.set_failure("incomplete body", &mut swanling.request, None, None),
+        },
+        Err(_) => Ok(()),
+    }
+}
+
+// Test task.
+pub async fn post_upload(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.post(UPLOAD_PATH, &"x".repeat(BODY_SIZE)).await?;
+    Ok(())
+}
+
+// Run a load test with the specified task set, and return the metrics.
+fn run_network_shaping_test(server: &MockServer, taskset: SwanlingTaskSet) -> SwanlingMetrics {
+    // Setup the endpoints needed for this test on the mock server.
+    server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200).body("x".repeat(BODY_SIZE));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path(UPLOAD_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &taskset, None, None),
+        None,
+    )
+}
+
+#[test]
+// Response and request bodies are transferred at the task set's bandwidth.
+fn test_bandwidth() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let swanling_metrics = run_network_shaping_test(
+        &server,
+        taskset!("LoadTest")
+            .set_bandwidth(BANDWIDTH)
+            .unwrap()
+            .register_task(task!(get_index))
+            .register_task(task!(post_upload)),
+    );
+
+    for name in &[
+        format!("GET {}", INDEX_PATH),
+        format!("POST {}", UPLOAD_PATH),
+    ] {
+        let metrics = swanling_metrics.requests.get(name).unwrap();
+        assert!(metrics.success_count > 0);
+        assert!(metrics.fail_count == 0);
+        assert!(metrics.raw_data.minimum_time >= TRANSFER_TIME);
+    }
+}
+
+#[test]
+// Latency is added to every request.
+fn test_added_latency() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let swanling_metrics = run_network_shaping_test(
+        &server,
+        taskset!("LoadTest")
+            .set_added_latency(Duration::from_millis(LATENCY))
+            .register_task(task!(get_index)),
+    );
+
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(index_metrics.success_count > 0);
+    assert!(index_metrics.fail_count == 0);
+    assert!(index_metrics.raw_data.minimum_time >= LATENCY as usize);
+}
+
+#[test]
+// Without network shaping requests aren't delayed.
+fn test_no_network_shaping() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let swanling_metrics = run_network_shaping_test(
+        &server,
+        taskset!("LoadTest").register_task(task!(get_index)),
+    );
+
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(index_metrics.success_count > 0);
+    assert!(index_metrics.fail_count == 0);
+    assert!(index_metrics.raw_data.minimum_time < LATENCY as usize);
+}
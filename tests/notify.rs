@@ -0,0 +1,104 @@
+use httpmock::{
+    Method::{GET, POST},
+    MockServer,
+};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const NOTIFY_PATH: &str = "/notify";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// The webhook is notified when the load test changes phase and when it completes.
+fn test_notify_url() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let running = server.mock(|when, then| {
+        when.method(POST)
+            .path(NOTIFY_PATH)
+            .body_contains(r#""event":"phase_changed""#)
+            .body_contains(r#""phase":"running""#);
+        then.status(200);
+    });
+    let completed = server.mock(|when, then| {
+        when.method(POST)
+            .path(NOTIFY_PATH)
+            .body_contains(r#""event":"completed""#)
+            .body_contains(r#""users":2"#);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let notify_url = server.url(NOTIFY_PATH);
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--notify-url",
+            &notify_url,
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm the webhook was notified once of each event.
+    assert!(index.hits() > 0);
+    assert!(running.hits() == 1);
+    assert!(completed.hits() == 1);
+}
+
+#[test]
+// Confirm --notify-url must be valid.
+fn test_notify_url_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in [
+        // The URL must be valid.
+        vec!["--notify-url", "not a url"],
+        // Only http and https are supported.
+        vec!["--notify-url", "ftp://hooks.example.com/swanling"],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
@@ -0,0 +1,97 @@
+use httpmock::{Method::GET, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const DONE_PATH: &str = "/job/done";
+const PENDING_PATH: &str = "/job/pending";
+
+// Load test configuration.
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn poll_done(user: &SwanlingUser) -> SwanlingTaskResult {
+    let swanling = user
+        .poll_until(
+            DONE_PATH,
+            |body| body.contains("done"),
+            Duration::from_millis(100),
+            Duration::from_secs(1),
+        )
+        .await?;
+    // The body of the response that met the condition can still be read.
+    let body = swanling.response?.text().await?;
+    user.assert(body == "done", "body still available");
+    Ok(())
+}
+
+// Test task.
+pub async fn poll_pending(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user
+        .poll_until_named(
+            PENDING_PATH,
+            "pending job",
+            |body| body.contains("done"),
+            Duration::from_millis(100),
+            Duration::from_millis(250),
+        )
+        .await?;
+    Ok(())
+}
+
+#[test]
+// Polls are counted by name, with how many met their condition or timed out.
+fn test_poll_until() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let done = server.mock(|when, then| {
+        when.method(GET).path(DONE_PATH);
+        then.status(200).body("done");
+    });
+    let pending = server.mock(|when, then| {
+        when.method(GET).path(PENDING_PATH);
+        then.status(200).body("pending");
+    });
+
+    // Build configuration.
+    let configuration =
+        common::build_configuration(&server, vec!["--run-time", &RUN_TIME.to_string()]);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(poll_done))
+                .register_task(task!(poll_pending)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // The done job met its condition with the first request.
+    let done_polls = swanling_metrics.polls.get(DONE_PATH).unwrap();
+    assert!(done_polls.met > 0);
+    assert!(done_polls.timed_out == 0);
+    assert!(done_polls.iterations == done_polls.met);
+    assert!(done.hits() >= done_polls.met);
+    let assertion = swanling_metrics
+        .assertions
+        .get("body still available")
+        .unwrap();
+    assert!(assertion.failed == 0);
+
+    // The pending job timed out after polling 3 times, at 0, 100 and 200 milliseconds.
+    let pending_polls = swanling_metrics.polls.get("pending job").unwrap();
+    assert!(pending_polls.met == 0);
+    assert!(pending_polls.timed_out > 0);
+    assert!(pending_polls.iterations == pending_polls.timed_out * 3);
+    assert!(pending_polls.max_wait_time >= 200);
+    assert!(pending.hits() >= pending_polls.iterations);
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "queue")]
+
+use httpmock::{Method::POST, MockServer};
+use std::sync::{Arc, Mutex};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::queue::{SwanlingProducer, SwanlingQueueError};
+
+// Paths used in load tests performed during these tests.
+const ORDER_PATH: &str = "/order";
+
+// Destinations messages are published to.
+const SHIPMENTS: &str = "shipments";
+const INVALID: &str = "invalid";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Stores the messages published, as a message queue would.
+#[derive(Clone, Default)]
+struct FakeProducer {
+    messages: Arc<Mutex<Vec<(String, Option<String>, Vec<u8>)>>>,
+}
+#[async_trait::async_trait]
+impl SwanlingProducer for FakeProducer {
+    async fn publish(
+        &self,
+        destination: &str,
+        key: Option<&str>,
+        payload: &[u8],
+    ) -> Result<(), SwanlingQueueError> {
+        if destination == INVALID {
+            return Err(format!("unknown topic: {}", destination).into());
+        }
+        self.messages.lock().unwrap().push((
+            destination.to_string(),
+            key.map(|k| k.to_string()),
+            payload.to_vec(),
+        ));
+        Ok(())
+    }
+}
+
+// Test task, placing an order over HTTP and publishing a shipment for it.
+pub async fn place_order(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.post(ORDER_PATH, "product=42").await?;
+    user.publish(SHIPMENTS, Some("42"), b"product=42").await?;
+    Ok(())
+}
+
+// Test task, publishing to a destination that doesn't exist.
+pub async fn publish_invalid(user: &SwanlingUser) -> SwanlingTaskResult {
+    user.publish(INVALID, None, b"").await?;
+    Ok(())
+}
+
+#[test]
+// Publish messages from tasks, recording each like a request.
+fn test_queue() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let order = server.mock(|when, then| {
+        when.method(POST).path(ORDER_PATH);
+        then.status(201);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--no-reset-metrics",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let producer = FakeProducer::default();
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(place_order))
+                .register_task(task!(publish_invalid)),
+            None,
+            None,
+        )
+        .set_producer(producer.clone()),
+        None,
+    );
+    assert!(order.hits() > 0);
+
+    // Each message published was recorded like a request.
+    let messages = producer.messages.lock().unwrap();
+    let shipments = &swanling_metrics.requests["PUBLISH shipments"];
+    assert!(!messages.is_empty());
+    assert!(shipments.success_count == messages.len());
+    assert!(shipments.fail_count == 0);
+    assert!(
+        messages[0]
+            == (
+                SHIPMENTS.to_string(),
+                Some("42".to_string()),
+                b"product=42".to_vec()
+            )
+    );
+
+    // Messages that failed to publish were recorded as failures.
+    let invalid = &swanling_metrics.requests["PUBLISH invalid"];
+    assert!(invalid.success_count == 0);
+    assert!(invalid.fail_count > 0);
+}
+
+#[test]
+// Confirm publishing fails when no producer was registered.
+fn test_queue_unregistered() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            "1",
+            "--hatch-rate",
+            "1",
+            "--run-time",
+            "1",
+            "--no-reset-metrics",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(publish_invalid)),
+            None,
+            None,
+        ),
+        None,
+    );
+    let invalid = &swanling_metrics.requests["PUBLISH invalid"];
+    assert!(invalid.success_count == 0);
+    assert!(invalid.fail_count > 0);
+}
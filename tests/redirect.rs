@@ -1,8 +1,10 @@
 use httpmock::{Method::GET, MockRef, MockServer};
 use serial_test::serial;
+use std::sync::Arc;
 
 mod common;
 
+use swanling::metrics::SwanlingMetrics;
 use swanling::prelude::*;
 use swanling::swanling::SwanlingTaskSet;
 use swanling::SwanlingConfiguration;
@@ -315,7 +317,7 @@ fn get_tasks(test_type: &TestType) -> SwanlingTaskSet {
 }
 
 // Helper to run all standalone tests.
-fn run_standalone_test(test_type: TestType) {
+fn run_standalone_test(test_type: TestType) -> SwanlingMetrics {
     // Start the mock servers.
     let server1 = MockServer::start();
     let server2 = MockServer::start();
@@ -331,13 +333,45 @@ fn run_standalone_test(test_type: TestType) {
     let configuration = common_build_configuration(&server1, sticky, None, None);
 
     // Run the Swanling Attack.
-    common::run_load_test(
+    let swanling_metrics = common::run_load_test(
         common::build_load_test(configuration, &get_tasks(&test_type), None, None),
         None,
     );
 
     // Confirm that the load test was actually redirected.
     validate_redirect(&test_type, &mock_endpoints);
+
+    swanling_metrics
+}
+
+// Helper to run a test of a task set's redirect policy, requesting a page that redirects
+// three times.
+fn run_redirect_policy_test(
+    redirect_policy: SwanlingRedirectPolicy,
+) -> (MockServer, SwanlingMetrics) {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let _mock_endpoints = setup_mock_server_endpoints(&TestType::Chain, &server, None);
+
+    // Build configuration.
+    let configuration = common_build_configuration(&server, false, None, None);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(get_domain_redirect))
+                .set_redirect_policy(redirect_policy),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    (server, swanling_metrics)
 }
 
 // Helper to run all standalone tests.
@@ -378,10 +412,72 @@ fn run_gaggle_test(test_type: TestType) {
     validate_redirect(&test_type, &mock_endpoints);
 }
 
+// Returns the error recorded for requests to REDIRECT_PATH.
+fn redirect_error(swanling_metrics: &SwanlingMetrics) -> &str {
+    &swanling_metrics
+        .errors
+        .values()
+        .find(|error| error.name == REDIRECT_PATH)
+        .unwrap()
+        .error
+}
+
 #[test]
 // Request a page that redirects multiple times with different redirect headers.
 fn test_redirect() {
-    run_standalone_test(TestType::Chain);
+    let swanling_metrics = run_standalone_test(TestType::Chain);
+
+    // Confirm every redirected request followed the whole chain of redirects.
+    let redirect = swanling_metrics.requests.get("GET /redirect").unwrap();
+    assert!(redirect.redirected_count > 0);
+    assert!(redirect.redirected_count == redirect.success_count + redirect.fail_count);
+    assert!(redirect.redirect_count == redirect.redirected_count * 3);
+    assert!(redirect.max_redirects == 3);
+    assert!(swanling_metrics.to_string().contains("REDIRECTS"));
+
+    // Requests that weren't redirected aren't counted.
+    let index = swanling_metrics.requests.get("GET /").unwrap();
+    assert!(index.redirected_count == 0);
+    assert!(index.redirect_time == 0);
+}
+
+#[test]
+// Return redirect responses instead of following them.
+fn test_redirect_policy_none() {
+    let (_server, swanling_metrics) = run_redirect_policy_test(SwanlingRedirectPolicy::None);
+
+    // Redirect responses are returned, so fail.
+    let redirect = swanling_metrics.requests.get("GET /redirect").unwrap();
+    assert!(redirect.fail_count > 0);
+    assert!(redirect.success_count == 0);
+    assert!(redirect_error(&swanling_metrics).starts_with("301"));
+    assert!(redirect.redirected_count == 0);
+    assert!(!swanling_metrics.to_string().contains("REDIRECTS"));
+}
+
+#[test]
+// Fail requests that are redirected more often than allowed.
+fn test_redirect_policy_limited() {
+    let (_server, swanling_metrics) = run_redirect_policy_test(SwanlingRedirectPolicy::Limited(2));
+
+    let redirect = swanling_metrics.requests.get("GET /redirect").unwrap();
+    assert!(redirect.fail_count > 0);
+    assert!(redirect.success_count == 0);
+    assert!(redirect_error(&swanling_metrics).contains("too many redirects"));
+}
+
+#[test]
+// Only follow the redirects a custom policy allows.
+fn test_redirect_policy_custom() {
+    let (_server, swanling_metrics) = run_redirect_policy_test(SwanlingRedirectPolicy::Custom(
+        Arc::new(|url, _previous| url.path() != ABOUT_PATH),
+    ));
+
+    // Two redirects are followed, and the redirect to the about page is returned.
+    let redirect = swanling_metrics.requests.get("GET /redirect").unwrap();
+    assert!(redirect_error(&swanling_metrics).starts_with("303"));
+    assert!(redirect.redirect_count == redirect.redirected_count * 2);
+    assert!(redirect.max_redirects == 2);
 }
 
 #[test]
@@ -0,0 +1,96 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const BURST_PATH: &str = "/burst";
+
+// Load test configuration.
+const USERS: usize = 3;
+const RUN_TIME: usize = 4;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_burst(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(BURST_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Users wait for each other at a rendezvous, then all run the task.
+fn test_rendezvous() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let burst = server.mock(|when, then| {
+        when.method(GET).path(BURST_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            "1",
+            // Run long enough for the last user, launched after two seconds, to run.
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack, which stops even though users are waiting at the rendezvous.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(get_index).set_sequence(1))
+                .register_task(
+                    task!(get_burst)
+                        .set_sequence(2)
+                        .set_rendezvous(USERS)
+                        .unwrap(),
+                ),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // The first user waited at the rendezvous until all users were launched.
+    assert!(index.hits() >= USERS);
+    assert!(burst.hits() >= USERS);
+}
+
+#[test]
+// A rendezvous can't wait for more users than the task set runs.
+fn test_rendezvous_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    assert!(task!(get_burst).set_rendezvous(0).is_err());
+
+    let configuration = common::build_configuration(&server, vec!["--users", &USERS.to_string()]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_burst).set_rendezvous(USERS + 1).unwrap()),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
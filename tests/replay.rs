@@ -0,0 +1,156 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ABOUT_KEY: usize = 1;
+
+// Load test configuration.
+const USERS: usize = 3;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_about(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Returns the appropriate taskset needed to build these tests.
+fn get_tasks() -> SwanlingTaskSet {
+    taskset!("LoadTest")
+        .register_task(task!(get_index))
+        .register_task(task!(get_about))
+}
+
+// Record a request log, then replay it and confirm the same requests are made again.
+fn run_replay_test(request_log: &str, request_format: &str) {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration to record a request log.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--request-log",
+            request_log,
+            "--request-format",
+            request_format,
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    // Confirm that we loaded the mock endpoints.
+    let index_hits = mock_endpoints[INDEX_KEY].hits();
+    let about_hits = mock_endpoints[ABOUT_KEY].hits();
+    assert!(index_hits > 0);
+    assert!(about_hits > 0);
+
+    // Build configuration to replay the request log twice as fast as it was recorded.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--replay-log",
+            request_log,
+            "--replay-speed",
+            "2",
+            // The load test stops as soon as all requests are replayed.
+            "--run-time",
+            "30",
+        ],
+    );
+
+    // Replay the request log.
+    let started = std::time::Instant::now();
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+
+    // Confirm the replay finished before the run-time expired.
+    assert!(started.elapsed().as_secs() < 30);
+
+    // Confirm every recorded request was made a second time.
+    assert!(mock_endpoints[INDEX_KEY].hits() == index_hits * 2);
+    assert!(mock_endpoints[ABOUT_KEY].hits() == about_hits * 2);
+
+    // Confirm a replay user was launched for each recorded user.
+    assert!(swanling_metrics.users == USERS);
+
+    // Cleanup from test.
+    common::cleanup_files(vec![request_log]);
+}
+
+#[test]
+// Replay a json-formatted request log.
+fn test_replay_json() {
+    run_replay_test("replay-json.log", "json");
+}
+
+#[test]
+// Replay a csv-formatted request log.
+fn test_replay_csv() {
+    run_replay_test("replay-csv.log", "csv");
+}
+
+#[test]
+// Replay a gzip compressed, pretty-json-formatted request log.
+fn test_replay_pretty_json_gz() {
+    run_replay_test("replay-pretty-json.log.gz", "pretty-json");
+}
+
+#[test]
+// Confirm --replay-speed can not be set without --replay-log.
+fn test_replay_speed_requires_replay_log() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Build configuration without a request log to replay.
+    let configuration = common::build_configuration(&server, vec!["--replay-speed", "2"]);
+
+    // Confirm the load test fails to start.
+    let swanling_attack = common::build_load_test(configuration, &get_tasks(), None, None);
+    assert!(swanling_attack.execute().is_err());
+}
@@ -0,0 +1,457 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "monitor")]
+// Include the resources used by the load generator in the metrics and the html report.
+fn test_report_generator() {
+    let report_file = "report-generator.html";
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--report-file",
+            report_file,
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(index.hits() > 0);
+
+    // Confirm resource usage was sampled while the load test ran, including a task for
+    // each user.
+    let generator = &swanling_metrics.generator;
+    assert!(generator.samples > 0);
+    assert!(generator.memory.maximum > 0.0);
+    assert!(generator.tasks.maximum >= USERS as f64);
+    assert!(generator.cpu.maximum <= 100.0);
+    #[cfg(target_os = "linux")]
+    assert!(generator.sockets.maximum > 0.0);
+
+    // Confirm the report includes the resources used by the load generator.
+    let report = std::fs::read_to_string(report_file).unwrap();
+    common::cleanup_files(vec![report_file]);
+    assert!(report.contains("<h2>Load Generator</h2>"));
+    assert!(report.contains("<td>Memory (MB)</td>"));
+}
+
+#[test]
+// Include a latency histogram for each request in the html report.
+fn test_report_histograms() {
+    let report_file = "report-histograms.html";
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--report-file",
+            report_file,
+            "--histogram-buckets",
+            "10,100",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(index.hits() > 0);
+
+    // Confirm the report includes a histogram with the configured buckets for the
+    // request and for all requests aggregated.
+    let report = std::fs::read_to_string(report_file).unwrap();
+    common::cleanup_files(vec![report_file]);
+    assert!(report.contains("<h2>Response Time Histograms</h2>"));
+    assert!(report.contains(&format!("<h3>GET {}</h3>", INDEX_PATH)));
+    assert!(report.contains("<h3>Aggregated</h3>"));
+    assert!(report.matches("<td>0-10 ms</td>").count() == 2);
+    assert!(report.matches("<td>11-100 ms</td>").count() == 2);
+    assert!(report.matches("<td>&gt; 100 ms</td>").count() == 2);
+}
+
+#[test]
+// Confirm --histogram-buckets must be a list of increasing milliseconds.
+fn test_report_histograms_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for buckets in &["0", "100,10", "10,10", "10,,100", "foo"] {
+        let configuration =
+            common::build_configuration(&server, vec!["--histogram-buckets", buckets]);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
+
+#[test]
+// Include the metadata configured with --label in the html report and the metrics.
+fn test_report_labels() {
+    let report_file = "report-labels.html";
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--report-file",
+            report_file,
+            "--label",
+            "version=1.2.0",
+            "--label",
+            "git_sha=1a2b3c4",
+            "--label",
+            "note=<cache> & cdn",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            // Pause after each request, so the load generator isn't saturated and annotated.
+            &taskset!("LoadTest")
+                .set_wait_time(1, 2)
+                .unwrap()
+                .register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(index.hits() > 0);
+
+    // Confirm the labels are included in the metrics.
+    assert!(swanling_metrics.labels.len() == 3);
+    assert!(swanling_metrics.labels.get("version") == Some(&"1.2.0".to_string()));
+    let summary = serde_json::to_value(&swanling_metrics).unwrap();
+    assert!(summary["labels"]["git_sha"] == "1a2b3c4");
+
+    // Confirm the labels are escaped and included in the html report header.
+    let report = std::fs::read_to_string(report_file).unwrap();
+    common::cleanup_files(vec![report_file]);
+    assert!(report.contains("<p>version: <span>1.2.0</span></p>"));
+    assert!(report.contains("<p>git_sha: <span>1a2b3c4</span></p>"));
+    assert!(report.contains("<p>note: <span>&lt;cache&gt; &amp; cdn</span></p>"));
+
+    // No annotations were added, so the report doesn't include an annotations table.
+    assert!(swanling_metrics.annotations.is_empty());
+    assert!(!report.contains("<h2>Annotations</h2>"));
+}
+
+#[test]
+// Confirm --label must be a key and a value.
+fn test_report_labels_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for label in &["version", "=1.2.0"] {
+        let configuration = common::build_configuration(&server, vec!["--label", label]);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
+
+#[test]
+// Merge the JSON metrics of several load tests into one report with --merge-reports.
+fn test_report_merge() {
+    let metrics_files = ["report-merge-1.json", "report-merge-2.json"];
+    let report_file = "report-merge.html";
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--label",
+            "region=us-east",
+        ],
+    );
+
+    // Run the Swanling Attack, and write its metrics as if it ran on two servers.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(index.hits() > 0);
+    let json = serde_json::to_string(&swanling_metrics).unwrap();
+    for metrics_file in &metrics_files {
+        std::fs::write(metrics_file, &json).unwrap();
+    }
+
+    let hits = index.hits();
+
+    // Merge the metrics into one report.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--merge-reports",
+            metrics_files[0],
+            metrics_files[1],
+            "--report-file",
+            report_file,
+        ],
+    );
+    let merged_metrics = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    )
+    .execute()
+    .unwrap();
+    let report = std::fs::read_to_string(report_file).unwrap();
+    common::cleanup_files(vec![metrics_files[0], metrics_files[1], report_file]);
+
+    // Confirm no requests were made while merging.
+    assert!(index.hits() == hits);
+
+    // Confirm the merged metrics combine both load tests.
+    let request_key = format!("GET {}", INDEX_PATH);
+    let request = &swanling_metrics.requests[&request_key];
+    let merged_request = &merged_metrics.requests[&request_key];
+    assert!(merged_metrics.users == USERS * 2);
+    assert!(merged_metrics.duration == swanling_metrics.duration);
+    assert!(merged_request.success_count == request.success_count * 2);
+    assert!(merged_request.raw_data.counter == request.raw_data.counter * 2);
+    assert!(merged_request.raw_data.minimum_time == request.raw_data.minimum_time);
+    assert!(merged_request.raw_data.maximum_time == request.raw_data.maximum_time);
+    assert!(merged_metrics.tasks[0][0].counter == swanling_metrics.tasks[0][0].counter * 2);
+    assert!(merged_metrics.labels.get("region") == Some(&"us-east".to_string()));
+
+    // Confirm the merged report was written.
+    assert!(report.contains(&format!("<td>{}</td>", merged_request.success_count)));
+    assert!(report.contains("region: <span>us-east</span>"));
+}
+
+#[test]
+// Confirm --merge-reports requires readable JSON metrics files.
+fn test_report_merge_invalid() {
+    let metrics_file = "report-merge-invalid.json";
+    std::fs::write(metrics_file, "not json").unwrap();
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in [
+        // Metrics files can't be listed without --merge-reports.
+        vec![metrics_file],
+        // At least one metrics file is required.
+        vec!["--merge-reports"],
+        // The metrics files must exist and contain JSON metrics.
+        vec!["--merge-reports", "report-merge-missing.json"],
+        vec!["--merge-reports", metrics_file],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+
+    common::cleanup_files(vec![metrics_file]);
+}
+
+#[test]
+// Write timestamped snapshots of the report while the load test runs.
+fn test_report_interval() {
+    let report_dir = "report-interval";
+    let report_file = "report-interval/report.html";
+    let _ = std::fs::remove_dir_all(report_dir);
+    std::fs::create_dir(report_dir).unwrap();
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            "3",
+            "--report-file",
+            report_file,
+            "--report-interval",
+            "1s",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(index.hits() > 0);
+
+    // Confirm html and json snapshots were written next to the final report.
+    let mut snapshots: Vec<String> = std::fs::read_dir(report_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|name| name != "report.html")
+        .collect();
+    snapshots.sort();
+    assert!(snapshots.len() >= 2);
+    assert!(snapshots.iter().all(|name| name.starts_with("report-")));
+    let json = snapshots
+        .iter()
+        .find(|name| name.ends_with(".json"))
+        .unwrap();
+    let html = json.replace(".json", ".html");
+    assert!(snapshots.contains(&html));
+    let metrics: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(format!("{}/{}", report_dir, json)).unwrap())
+            .unwrap();
+    assert!(metrics["requests"].get("GET /").is_some());
+    let report = std::fs::read_to_string(format!("{}/{}", report_dir, html)).unwrap();
+    assert!(report.contains("<h2>Request Metrics</h2>"));
+
+    std::fs::remove_dir_all(report_dir).unwrap();
+}
+
+#[test]
+// Confirm --report-interval must be a time, and requires a report file.
+fn test_report_interval_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in [
+        vec![
+            "--report-file",
+            "report-invalid.html",
+            "--report-interval",
+            "foo",
+        ],
+        vec!["--report-interval", "5m"],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
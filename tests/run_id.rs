@@ -0,0 +1,146 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// The run ID configured in these tests.
+const RUN_ID: &str = "build-1234";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 1;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Send the configured run ID with each request, and include it in the metrics and logs.
+fn test_run_id() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Only requests identifying the run succeed.
+    let index = server.mock(|when, then| {
+        when.method(GET)
+            .path(INDEX_PATH)
+            .header("x-load-test-run", RUN_ID);
+        then.status(200);
+    });
+
+    let request_log = "run-id-request-log.json";
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--run-id",
+            RUN_ID,
+            "--run-header",
+            "--request-log",
+            request_log,
+            "--request-format",
+            "json",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(index.hits() > 0);
+    assert!(swanling_metrics.run_id == RUN_ID);
+    assert!(swanling_metrics.requests["GET /"].fail_count == 0);
+
+    // Each record in the request log identifies the run.
+    let contents = std::fs::read_to_string(request_log).unwrap();
+    for line in contents.lines() {
+        let record: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(record["run_id"] == RUN_ID);
+    }
+
+    common::cleanup_files(vec![request_log]);
+}
+
+#[test]
+// Generate a unique run ID when none is configured, without sending it by default.
+fn test_run_id_generated() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server, requests identifying
+    // the run match the first one.
+    let identified = server.mock(|when, then| {
+        when.method(GET)
+            .path(INDEX_PATH)
+            .header_exists("x-load-test-run");
+        then.status(500);
+    });
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    let mut run_ids = Vec::new();
+    for _ in 0..2 {
+        let configuration = common::build_configuration(
+            &server,
+            vec![
+                "--users",
+                &USERS.to_string(),
+                "--hatch-rate",
+                &USERS.to_string(),
+                "--run-time",
+                &RUN_TIME.to_string(),
+            ],
+        );
+        let swanling_metrics = common::run_load_test(
+            common::build_load_test(
+                configuration,
+                &taskset!("LoadTest").register_task(task!(get_index)),
+                None,
+                None,
+            ),
+            None,
+        );
+        assert!(swanling_metrics.run_id.len() == 36);
+        run_ids.push(swanling_metrics.run_id);
+    }
+    assert!(index.hits() > 0);
+    assert!(identified.hits() == 0);
+    assert!(run_ids[0] != run_ids[1]);
+}
+
+#[test]
+// Confirm the run ID must be printable, as it's sent in a header.
+fn test_run_id_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration = common::build_configuration(&server, vec!["--run-id", "build 1234"]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
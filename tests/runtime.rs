@@ -0,0 +1,139 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 4;
+const HATCH_RATE: usize = 100;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Run the load test on a runtime with a configured number of worker threads.
+fn test_runtime_threads() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            // Launch all users right away, well within the one second run time.
+            "--hatch-rate",
+            &HATCH_RATE.to_string(),
+            "--runtime-threads",
+            "1",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm all users ran on the single worker thread.
+    assert!(index.hits() > 0);
+    assert!(swanling_metrics.users == USERS);
+}
+
+#[test]
+// Run the load test on a runtime supplied by the application.
+fn test_set_runtime() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build the runtime the application already uses.
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .thread_name("application")
+        .enable_all()
+        .build()
+        .unwrap();
+
+    // Run two load tests on the same runtime, without Controllers listening between them.
+    for _ in 0..2 {
+        let configuration = common::build_configuration(
+            &server,
+            vec![
+                "--users",
+                &USERS.to_string(),
+                // Launch all users right away, well within the one second run time.
+                "--hatch-rate",
+                &HATCH_RATE.to_string(),
+                "--no-telnet",
+                "--no-websocket",
+            ],
+        );
+        let swanling_metrics = common::run_load_test(
+            common::build_load_test(
+                configuration,
+                &taskset!("LoadTest").register_task(task!(get_index)),
+                None,
+                None,
+            )
+            .set_runtime(runtime.handle().clone()),
+            None,
+        );
+        assert!(swanling_metrics.users == USERS);
+    }
+
+    // Confirm requests were made by both load tests.
+    assert!(index.hits() > 1);
+}
+
+#[test]
+// Confirm --runtime-threads must be valid.
+fn test_runtime_threads_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    for (options, set_runtime) in [
+        // At least one worker thread is required.
+        (vec!["--runtime-threads", "0"], false),
+        // A supplied runtime already has its worker threads.
+        (vec!["--runtime-threads", "2"], true),
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let mut swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        if set_runtime {
+            swanling_attack = swanling_attack.set_runtime(runtime.handle().clone());
+        }
+        assert!(swanling_attack.execute().is_err());
+    }
+}
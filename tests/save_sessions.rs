@@ -0,0 +1,169 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::metrics::SwanlingMetrics;
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const LOGIN_PATH: &str = "/login";
+const ACCOUNT_PATH: &str = "/account";
+
+// Indexes to the above paths.
+const LOGIN_KEY: usize = 0;
+const ACCOUNT_KEY: usize = 1;
+const DENIED_KEY: usize = 2;
+
+// The session cookie set when logging in.
+const SESSION_COOKIE: &str = "session=42";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test session function.
+pub async fn login(user: &mut SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(LOGIN_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_account(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ACCOUNT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints. The account page can only be
+// loaded with the session cookie.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        server.mock(|when, then| {
+            when.method(GET).path(LOGIN_PATH);
+            then.status(200).header("Set-Cookie", SESSION_COOKIE);
+        }),
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(ACCOUNT_PATH)
+                .header("Cookie", SESSION_COOKIE);
+            then.status(200);
+        }),
+        server.mock(|when, then| {
+            when.method(GET).path(ACCOUNT_PATH);
+            then.status(401);
+        }),
+    ]
+}
+
+// Run a load test that saves its sessions, optionally logging in when each user starts.
+fn run_load_test(server: &MockServer, sessions: &str, login_first: bool) -> SwanlingMetrics {
+    // Build configuration.
+    let configuration = common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--save-sessions",
+            sessions,
+        ],
+    );
+
+    let mut taskset = taskset!("LoadTest").register_task(task!(get_account));
+    if login_first {
+        taskset = taskset.register_on_start_session(|user| Box::pin(login(user)));
+    }
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &taskset, None, None),
+        None,
+    )
+}
+
+#[test]
+// Restore the sessions saved by an earlier load test.
+fn test_save_sessions() {
+    let sessions = "save-sessions.json";
+    common::cleanup_files(vec![sessions]);
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Log in, and save the session cookie of each user.
+    let swanling_metrics = run_load_test(&server, sessions, true);
+    mock_endpoints[LOGIN_KEY].assert_hits(USERS);
+    assert!(mock_endpoints[ACCOUNT_KEY].hits() > 0);
+    mock_endpoints[DENIED_KEY].assert_hits(0);
+    assert!(
+        swanling_metrics
+            .requests
+            .get("GET /account")
+            .unwrap()
+            .fail_count
+            == 0
+    );
+    let saved = std::fs::read_to_string(sessions).unwrap();
+    assert!(saved.contains("session=42"));
+
+    // Without logging in, the restored session cookies still load the account page.
+    let swanling_metrics = run_load_test(&server, sessions, false);
+    mock_endpoints[LOGIN_KEY].assert_hits(USERS);
+    mock_endpoints[DENIED_KEY].assert_hits(0);
+    assert!(
+        swanling_metrics
+            .requests
+            .get("GET /account")
+            .unwrap()
+            .fail_count
+            == 0
+    );
+
+    // Without saved sessions, the account page can't be loaded.
+    common::cleanup_files(vec![sessions]);
+    let swanling_metrics = run_load_test(&server, sessions, false);
+    assert!(mock_endpoints[DENIED_KEY].hits() > 0);
+    assert!(
+        swanling_metrics
+            .requests
+            .get("GET /account")
+            .unwrap()
+            .success_count
+            == 0
+    );
+
+    common::cleanup_files(vec![sessions]);
+}
+
+#[test]
+// Confirm --save-sessions must be usable.
+fn test_save_sessions_invalid() {
+    let sessions = "save-sessions-invalid.json";
+    std::fs::write(sessions, "not json").unwrap();
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in [
+        // The saved sessions must be valid.
+        vec!["--save-sessions", sessions],
+        // Sessions are saved by the process that launches users.
+        vec!["--save-sessions", "sessions.json", "--manager"],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_account)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+
+    common::cleanup_files(vec![sessions]);
+}
@@ -0,0 +1,123 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+use std::time::Duration;
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const HEARTBEAT_PATH: &str = "/heartbeat";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const HEARTBEAT_KEY: usize = 1;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 3;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn heartbeat(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(HEARTBEAT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up HEARTBEAT_PATH, store in vector at HEARTBEAT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(HEARTBEAT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Run a load test with the specified task set, and return the mock endpoints and metrics.
+fn run_schedule_test(
+    server: &MockServer,
+    taskset: SwanlingTaskSet,
+) -> (Vec<MockRef<'_>>, SwanlingMetrics) {
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(server);
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            // Count all requests, so metrics can be compared to the mock endpoints.
+            "--no-reset-metrics",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &taskset, None, None),
+        None,
+    );
+
+    (mock_endpoints, swanling_metrics)
+}
+
+// Returns a heartbeat task scheduled to run every second.
+fn heartbeat_task() -> SwanlingTask {
+    task!(heartbeat)
+        .set_name("heartbeat")
+        .set_schedule(Duration::from_secs(1))
+        .unwrap()
+}
+
+#[test]
+// Run a scheduled task alongside the weighted tasks.
+fn test_schedule() {
+    let server = MockServer::start();
+    let (mock_endpoints, swanling_metrics) = run_schedule_test(
+        &server,
+        taskset!("LoadTest")
+            .register_task(task!(get_index))
+            .register_task(heartbeat_task()),
+    );
+
+    // Confirm the weighted task ran continuously, while the scheduled task only ran
+    // about once per second per user.
+    assert!(mock_endpoints[INDEX_KEY].hits() > USERS * RUN_TIME);
+    assert!(mock_endpoints[HEARTBEAT_KEY].hits() >= USERS);
+    assert!(mock_endpoints[HEARTBEAT_KEY].hits() <= USERS * RUN_TIME);
+
+    // Confirm requests made by the scheduled task are named after it.
+    let heartbeat_metrics = swanling_metrics.requests.get("GET heartbeat").unwrap();
+    assert!(heartbeat_metrics.success_count == mock_endpoints[HEARTBEAT_KEY].hits());
+}
+
+#[test]
+// Run a task set with only a scheduled task.
+fn test_schedule_only() {
+    let server = MockServer::start();
+    let (mock_endpoints, _swanling_metrics) = run_schedule_test(
+        &server,
+        taskset!("LoadTest").register_task(heartbeat_task()),
+    );
+
+    // Confirm the users kept running the scheduled task until the load test stopped.
+    assert!(mock_endpoints[INDEX_KEY].hits() == 0);
+    assert!(mock_endpoints[HEARTBEAT_KEY].hits() >= USERS);
+    assert!(mock_endpoints[HEARTBEAT_KEY].hits() <= USERS * RUN_TIME);
+}
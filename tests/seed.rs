@@ -0,0 +1,106 @@
+use httpmock::{Method::GET, MockServer};
+use rand::Rng;
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::util;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 3;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_seeded(user: &SwanlingUser) -> SwanlingTaskResult {
+    let path = {
+        let mut rng = user.rng();
+        let item = util::Zipf::new(1_000_000, 0.5).unwrap().sample(&mut *rng);
+        let variant = util::weighted_choice(&mut *rng, &[("a", 1), ("b", 1)]).unwrap();
+        format!("/seed/{}/{}/{}", item, variant, rng.gen::<u32>())
+    };
+    let _swanling = user.get(&path).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Run a load test with the specified seed, returning the seeded paths each user loaded.
+fn run_seeded_load_test(server: &MockServer, seed: &str) -> Vec<String> {
+    let request_log = format!("seed-{}.log", seed);
+    let configuration = common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--seed",
+            seed,
+            "--request-log",
+            &request_log,
+        ],
+    );
+
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(get_seeded).set_on_start())
+                .register_task(task!(get_index))
+                .set_wait_time(0, 1)
+                .unwrap(),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Read the seeded paths from the request log.
+    let log = std::fs::read_to_string(&request_log).unwrap();
+    let mut paths: Vec<String> = log
+        .lines()
+        .filter_map(|line| line.find("/seed/").map(|start| &line[start..]))
+        .map(|path| path.split('"').next().unwrap().to_string())
+        .collect();
+    paths.sort();
+    paths.dedup();
+    common::cleanup_files(vec![&request_log]);
+    paths
+}
+
+#[test]
+// Random values are repeatable with --seed.
+fn test_seed() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let seeded = server.mock(|when, then| {
+        when.method(GET)
+            .path_matches(regex::Regex::new(r"^/seed/").unwrap());
+        then.status(200);
+    });
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Each user loads a different seeded path once.
+    let paths = run_seeded_load_test(&server, "42");
+    seeded.assert_hits(USERS);
+    assert!(index.hits() > 0);
+    assert!(paths.len() == USERS);
+
+    // The same seed generates the same paths, another seed different paths.
+    assert!(run_seeded_load_test(&server, "42") == paths);
+    assert!(run_seeded_load_test(&server, "43") != paths);
+}
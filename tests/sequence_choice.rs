@@ -0,0 +1,106 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const LOGIN_PATH: &str = "/login";
+const BROWSE_PATH: &str = "/browse";
+const SEARCH_PATH: &str = "/search";
+
+// Indexes to the above paths.
+const LOGIN_KEY: usize = 0;
+const BROWSE_KEY: usize = 1;
+const SEARCH_KEY: usize = 2;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn login(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(LOGIN_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn browse(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(BROWSE_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn search(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(SEARCH_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up LOGIN_PATH, store in vector at LOGIN_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(LOGIN_PATH);
+            then.status(200);
+        }),
+        // Next set up BROWSE_PATH, store in vector at BROWSE_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(BROWSE_PATH);
+            then.status(200);
+        }),
+        // Finally set up SEARCH_PATH, store in vector at SEARCH_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(SEARCH_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+#[test]
+// Only run one of the tasks with the same sequence, chosen by weight.
+fn test_sequence_choice() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(login).set_sequence(1))
+                .register_task(task!(browse).set_sequence_choice(2).set_weight(7).unwrap())
+                .register_task(task!(search).set_sequence(2).set_weight(3).unwrap()),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm that each time through the tasks either browse or search ran after logging
+    // in. Users may stop after logging in, before running either.
+    let login_hits = mock_endpoints[LOGIN_KEY].hits();
+    let choice_hits = mock_endpoints[BROWSE_KEY].hits() + mock_endpoints[SEARCH_KEY].hits();
+    assert!(choice_hits <= login_hits);
+    assert!(choice_hits >= login_hits - USERS);
+
+    // Confirm both tasks were chosen, browse more often as it has a higher weight.
+    assert!(mock_endpoints[SEARCH_KEY].hits() > 0);
+    assert!(mock_endpoints[BROWSE_KEY].hits() > mock_endpoints[SEARCH_KEY].hits());
+}
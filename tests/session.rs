@@ -0,0 +1,150 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const LOGIN_PATH: &str = "/login";
+const DENIED_PATH: &str = "/denied";
+const PAGE_PATH: &str = "/page";
+const LOGOUT_PATH: &str = "/logout";
+
+// Indexes to the above paths.
+const LOGIN_KEY: usize = 0;
+const DENIED_KEY: usize = 1;
+const PAGE_KEY: usize = 2;
+const LOGOUT_KEY: usize = 3;
+
+// Load test configuration.
+const USERS: usize = 4;
+const RUN_TIME: usize = 2;
+
+// Log in, failing if the server doesn't accept the login.
+async fn login(user: &mut SwanlingUser, path: &str) -> SwanlingTaskResult {
+    let mut swanling = user.get(path).await?;
+    if let Ok(response) = &swanling.response {
+        if !response.status().is_success() {
+            return user.set_failure("login failed", &mut swanling.request, None, None);
+        }
+    }
+    Ok(())
+}
+
+// Test session function.
+pub async fn login_allowed(user: &mut SwanlingUser) -> SwanlingTaskResult {
+    login(user, LOGIN_PATH).await
+}
+
+// Test session function.
+pub async fn login_denied(user: &mut SwanlingUser) -> SwanlingTaskResult {
+    login(user, DENIED_PATH).await
+}
+
+// Test session function.
+pub async fn logout(user: &mut SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(LOGOUT_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_page(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(PAGE_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up LOGIN_PATH, store in vector at LOGIN_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(LOGIN_PATH);
+            then.status(200);
+        }),
+        // Next set up DENIED_PATH, store in vector at DENIED_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(DENIED_PATH);
+            then.status(403);
+        }),
+        // Next set up PAGE_PATH, store in vector at PAGE_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(PAGE_PATH);
+            then.status(200);
+        }),
+        // Finally set up LOGOUT_PATH, store in vector at LOGOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(LOGOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Run a load test with the specified session function, and return the metrics.
+fn run_session_test(server: &MockServer, taskset: SwanlingTaskSet) -> SwanlingMetrics {
+    // Build configuration.
+    let configuration = common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &taskset, None, None),
+        None,
+    )
+}
+
+#[test]
+// Users run session functions when starting and stopping.
+fn test_session() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let swanling_metrics = run_session_test(
+        &server,
+        taskset!("LoadTest")
+            .register_on_start_session(|user| Box::pin(login_allowed(user)))
+            .register_on_stop_session(|user| Box::pin(logout(user)))
+            .register_task(task!(get_page)),
+    );
+
+    // Confirm each user logged in and out once, and ran its tasks.
+    assert!(mock_endpoints[LOGIN_KEY].hits() == USERS);
+    assert!(mock_endpoints[PAGE_KEY].hits() > 0);
+    assert!(mock_endpoints[LOGOUT_KEY].hits() == USERS);
+    assert!(swanling_metrics.removed_users.is_empty());
+}
+
+#[test]
+// Users are removed from the load test when an on_start session function fails.
+fn test_session_removes_users() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let swanling_metrics = run_session_test(
+        &server,
+        taskset!("LoadTest")
+            .register_on_start_session(|user| Box::pin(login_denied(user)))
+            .register_on_stop_session(|user| Box::pin(logout(user)))
+            .register_task(task!(get_page)),
+    );
+
+    // Confirm each user tried to log in, then was removed without running any tasks.
+    assert!(mock_endpoints[DENIED_KEY].hits() == USERS);
+    assert!(mock_endpoints[PAGE_KEY].hits() == 0);
+    assert!(mock_endpoints[LOGOUT_KEY].hits() == 0);
+    assert!(swanling_metrics.removed_users.values().sum::<usize>() == USERS);
+}
@@ -0,0 +1,83 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const PRODUCTS_PATH: &str = "/products";
+const PRODUCT_PATH: &str = "/product/7";
+
+// Load test configuration.
+const USERS: usize = 5;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn view_product(user: &SwanlingUser) -> SwanlingTaskResult {
+    let product_ids = user
+        .shared_cache()
+        .get_or_try_insert_with("product_ids", || async {
+            let swanling = user.get(PRODUCTS_PATH).await?;
+            let product_ids: Vec<usize> = swanling.json_as(user).await?;
+            Ok::<_, SwanlingTaskError>(product_ids)
+        })
+        .await?;
+    let _swanling = user.get(&format!("/product/{}", product_ids[0])).await?;
+    Ok(())
+}
+
+#[test]
+// All users share the product IDs fetched by the first user.
+fn test_shared_cache() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let products = server.mock(|when, then| {
+        when.method(GET).path(PRODUCTS_PATH);
+        then.status(200).body("[7, 8, 9]");
+    });
+    let product = server.mock(|when, then| {
+        when.method(GET).path(PRODUCT_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(view_product)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // The product IDs were only fetched once.
+    assert!(products.hits() == 1);
+    assert!(product.hits() > USERS);
+}
+
+#[test]
+// The shared cache must hold at least one value.
+fn test_shared_cache_capacity_invalid() {
+    let swanling_attack = SwanlingAttack::initialize_with_config(common::build_configuration(
+        &MockServer::start(),
+        vec![],
+    ))
+    .unwrap();
+    assert!(swanling_attack.set_shared_cache_capacity(0).is_err());
+}
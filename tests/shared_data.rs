@@ -0,0 +1,115 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const SETUP_PATH: &str = "/setup";
+const PRODUCT_PATH: &str = "/product/42";
+const TEARDOWN_PATH: &str = "/teardown/42";
+
+// Indexes to the above paths.
+const SETUP_KEY: usize = 0;
+const PRODUCT_KEY: usize = 1;
+const TEARDOWN_KEY: usize = 2;
+
+// Data shared by the test_start task.
+struct SeededProducts {
+    ids: Vec<usize>,
+}
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn setup(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(SETUP_PATH).await?;
+    user.set_shared_data(SeededProducts { ids: vec![42] });
+    Ok(())
+}
+
+// Test task.
+pub async fn get_product(user: &SwanlingUser) -> SwanlingTaskResult {
+    // Shared data of another type isn't returned.
+    assert!(user.get_shared_data::<String>().is_none());
+
+    let products = user.get_shared_data::<SeededProducts>().unwrap();
+    let _swanling = user.get(&format!("/product/{}", products.ids[0])).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn teardown(user: &SwanlingUser) -> SwanlingTaskResult {
+    let products = user.get_shared_data::<SeededProducts>().unwrap();
+    let _swanling = user.get(&format!("/teardown/{}", products.ids[0])).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up SETUP_PATH, store in vector at SETUP_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(SETUP_PATH);
+            then.status(200);
+        }),
+        // Next set up PRODUCT_PATH, store in vector at PRODUCT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(PRODUCT_PATH);
+            then.status(200);
+        }),
+        // Finally set up TEARDOWN_PATH, store in vector at TEARDOWN_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(TEARDOWN_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+#[test]
+// Share data set by test_start with all users and test_stop.
+fn test_shared_data() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_product)),
+            Some(&task!(setup)),
+            Some(&task!(teardown)),
+        ),
+        None,
+    );
+
+    // Confirm the users and the test_stop task all used the shared data.
+    assert!(mock_endpoints[SETUP_KEY].hits() == 1);
+    assert!(mock_endpoints[PRODUCT_KEY].hits() > 0);
+    assert!(mock_endpoints[TEARDOWN_KEY].hits() == 1);
+
+    // Confirm all product requests succeeded.
+    let product_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", PRODUCT_PATH))
+        .unwrap();
+    assert!(product_metrics.fail_count == 0);
+}
@@ -0,0 +1,97 @@
+use httpmock::{Method::GET, MockServer};
+use std::time::{Duration, Instant};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 1;
+
+// How long the stuck task sleeps, much longer than the shutdown timeout.
+const STUCK_TIME: u64 = 30;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task that never finishes in time.
+pub async fn get_index_and_hang(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    tokio::time::sleep(Duration::from_secs(STUCK_TIME)).await;
+    Ok(())
+}
+
+#[test]
+// Abort users that don't exit within the shutdown timeout, still producing metrics.
+fn test_shutdown_timeout() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--shutdown-timeout",
+            "1s",
+            // Keep the requests in each user's metrics until it's aborted.
+            "--metrics-flush",
+            "60s",
+            "--no-reset-metrics",
+        ],
+    );
+
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index_and_hang)),
+        None,
+        None,
+    );
+
+    // Run the Swanling Attack.
+    let started = Instant::now();
+    let swanling_metrics = common::run_load_test(swanling_attack, None);
+    assert!(index.hits() > 0);
+
+    // Confirm the users were aborted instead of waited for.
+    assert!(started.elapsed() < Duration::from_secs(STUCK_TIME));
+    let aborted = swanling_metrics.aborted.unwrap();
+    assert!(aborted.contains("--shutdown-timeout 1s"));
+
+    // Confirm the metrics collected before aborting were kept.
+    assert!(swanling_metrics.requests.contains_key("GET /"));
+}
+
+#[test]
+// Confirm --shutdown-timeout must be a time.
+fn test_shutdown_timeout_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration = common::build_configuration(&server, vec!["--shutdown-timeout", "forever"]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
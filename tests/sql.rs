@@ -0,0 +1,175 @@
+#![cfg(feature = "sql")]
+
+use httpmock::{Method::POST, MockServer};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::sql::{
+    SwanlingSqlConnection, SwanlingSqlConnector, SwanlingSqlError, SwanlingSqlRow,
+};
+
+// Paths used in load tests performed during these tests.
+const ORDER_PATH: &str = "/order";
+
+// Load test configuration.
+const USERS: usize = 4;
+const RUN_TIME: usize = 2;
+const MAX_CONNECTIONS: usize = 2;
+
+// The rows in the fake orders table.
+static ORDERS: AtomicUsize = AtomicUsize::new(0);
+// How many connections were opened to the fake database.
+static CONNECTIONS: AtomicUsize = AtomicUsize::new(0);
+// How many orders the test_stop task counted.
+static COUNTED: AtomicUsize = AtomicUsize::new(0);
+
+// Opens connections to a fake database with a single orders table.
+struct FakeConnector;
+#[async_trait::async_trait]
+impl SwanlingSqlConnector for FakeConnector {
+    async fn connect(&self) -> Result<Box<dyn SwanlingSqlConnection>, SwanlingSqlError> {
+        CONNECTIONS.fetch_add(1, Ordering::SeqCst);
+        Ok(Box::new(FakeConnection))
+    }
+}
+
+struct FakeConnection;
+#[async_trait::async_trait]
+impl SwanlingSqlConnection for FakeConnection {
+    async fn execute(&mut self, statement: &str) -> Result<u64, SwanlingSqlError> {
+        match statement {
+            "DELETE FROM orders" => Ok(ORDERS.swap(0, Ordering::SeqCst) as u64),
+            "INSERT INTO orders" => {
+                ORDERS.fetch_add(1, Ordering::SeqCst);
+                Ok(1)
+            }
+            _ => Err(format!("syntax error: {}", statement).into()),
+        }
+    }
+
+    async fn query(&mut self, query: &str) -> Result<Vec<SwanlingSqlRow>, SwanlingSqlError> {
+        match query {
+            "SELECT COUNT(*) FROM orders" => {
+                Ok(vec![vec![Some(ORDERS.load(Ordering::SeqCst).to_string())]])
+            }
+            _ => Err(format!("syntax error: {}", query).into()),
+        }
+    }
+}
+
+// Test task, run by test_start to seed the database.
+pub async fn seed(user: &SwanlingUser) -> SwanlingTaskResult {
+    user.sql()?.execute("seed", "DELETE FROM orders").await?;
+    Ok(())
+}
+
+// Test task, placing an order and storing it as the target would.
+pub async fn place_order(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.post(ORDER_PATH, "product=42").await?;
+    user.sql()?
+        .execute("insert order", "INSERT INTO orders")
+        .await?;
+    Ok(())
+}
+
+// Test task, run by test_stop to count the orders stored.
+pub async fn count_orders(user: &SwanlingUser) -> SwanlingTaskResult {
+    let orders: Option<usize> = user
+        .sql()?
+        .query_scalar("count orders", "SELECT COUNT(*) FROM orders")
+        .await?;
+    COUNTED.store(orders.unwrap_or(0), Ordering::SeqCst);
+    // A failed query is recorded in the metrics.
+    let _ = user.sql()?.query("invalid", "SELECT").await;
+    Ok(())
+}
+
+// Test task, using a pool that wasn't registered.
+pub async fn unregistered(user: &SwanlingUser) -> SwanlingTaskResult {
+    user.sql()?;
+    Ok(())
+}
+
+#[test]
+// Seed the database, check its state when the load test stops, and record each query.
+fn test_sql() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let order = server.mock(|when, then| {
+        when.method(POST).path(ORDER_PATH);
+        then.status(201);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(place_order)),
+            Some(&task!(seed)),
+            Some(&task!(count_orders)),
+        )
+        .set_sql_pool(SwanlingSqlPool::new(FakeConnector, MAX_CONNECTIONS)),
+        None,
+    );
+
+    // Every order stored was counted.
+    assert!(order.hits() > 0);
+    let inserted = swanling_metrics.queries["insert order"].success_count;
+    assert!(inserted > 0 && inserted <= order.hits());
+    assert!(COUNTED.load(Ordering::SeqCst) == inserted);
+
+    // Connections were reused.
+    assert!(CONNECTIONS.load(Ordering::SeqCst) <= MAX_CONNECTIONS);
+
+    // Each query was recorded by name.
+    let queries = &swanling_metrics.queries;
+    assert!(queries["seed"].success_count == 1);
+    assert!(queries["insert order"].fail_count == 0);
+    assert!(queries["count orders"].success_count == 1);
+    assert!(queries["invalid"].success_count == 0);
+    assert!(queries["invalid"].fail_count == 1);
+}
+
+#[test]
+// Confirm tasks fail when no pool was registered.
+fn test_sql_unregistered() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec!["--users", "1", "--hatch-rate", "1", "--run-time", "1"],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(unregistered)),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(swanling_metrics.queries.is_empty());
+    assert!(swanling_metrics.tasks[0][0].fail_count > 0);
+    assert!(swanling_metrics.tasks[0][0].success_count == 0);
+}
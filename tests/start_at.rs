@@ -0,0 +1,89 @@
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 1;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Wait until the scheduled time to start the load test.
+fn test_start_at() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Schedule the load test to start in two seconds, to the millisecond as it's passed to
+    // --start-at.
+    let start_at = Utc::now() + Duration::seconds(2);
+    let start_at = start_at.to_rfc3339_opts(SecondsFormat::Millis, true);
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--start-at",
+            &start_at,
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // The load test didn't start until the scheduled time.
+    assert!(index.hits() > 0);
+    let start_at = DateTime::parse_from_rfc3339(&start_at).unwrap();
+    assert!(swanling_metrics.started.unwrap() >= start_at);
+}
+
+#[test]
+// Confirm --start-at must be a timestamp, and can't be set in Regatta mode.
+fn test_start_at_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in [
+        vec!["--start-at", "tomorrow"],
+        vec!["--start-at", "2024-06-01T02:00:00Z", "--manager"],
+    ] {
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
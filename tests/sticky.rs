@@ -0,0 +1,265 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const LOGIN_PATH: &str = "/login";
+const REDIRECT_PATH: &str = "/redirect";
+const FOLLOW_PATH: &str = "/follow";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const LOGIN_KEY: usize = 1;
+const REDIRECT_KEY: usize = 2;
+const FOLLOW_KEY: usize = 3;
+
+// Indexes to the backend specific endpoints.
+const WEB1_KEY: usize = 0;
+const WEB2_KEY: usize = 1;
+
+// Load balancer configuration.
+const STICKY_COOKIE: &str = "SERVERID";
+const STICKY_HEADER: &str = "x-backend";
+const BACKEND_HEADER: &str = "x-served-by";
+const BACKENDS: [&str; 2] = ["web1", "web2"];
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn login(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(LOGIN_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_redirect(user: &SwanlingUser) -> SwanlingTaskResult {
+    let request_builder = user.swanling_get(REDIRECT_PATH).await?;
+    let _swanling = user
+        .swanling_send_without_redirects(request_builder, None)
+        .await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_follow(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(FOLLOW_PATH).await?;
+    Ok(())
+}
+
+// Set up one endpoint per backend, matching requests pinned to that backend.
+fn setup_backend_endpoints<'a>(
+    server: &'a MockServer,
+    pinned_with_cookie: bool,
+) -> Vec<MockRef<'a>> {
+    BACKENDS
+        .iter()
+        .map(|backend| {
+            server.mock(|when, then| {
+                let when = when.method(GET).path(INDEX_PATH);
+                let then = then.status(200).header(BACKEND_HEADER, backend);
+                if pinned_with_cookie {
+                    // The session cookie set by the server must still be sent.
+                    when.cookie(STICKY_COOKIE, backend).cookie_exists("session");
+                    // The load balancer tries to move the user to another backend.
+                    then.header("Set-Cookie", &format!("{}=web3; Path=/", STICKY_COOKIE));
+                } else {
+                    when.header(STICKY_HEADER, backend);
+                }
+            })
+        })
+        .collect()
+}
+
+// Run a load test, returning the metrics.
+fn run_sticky_test(
+    server: &MockServer,
+    options: Vec<&str>,
+    taskset: SwanlingTaskSet,
+) -> SwanlingMetrics {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let backends = BACKENDS.join(",");
+    let mut configuration_options = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+        "--sticky-backends",
+        &backends,
+        "--backend-header",
+        BACKEND_HEADER,
+        // Count all requests, so metrics can be compared to the mock endpoints.
+        "--no-reset-metrics",
+    ];
+    configuration_options.extend(options);
+
+    // Build configuration.
+    let configuration = common::build_configuration(server, configuration_options);
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &taskset, None, None),
+        None,
+    )
+}
+
+// Confirm requests were evenly pinned to both backends, and counted in the metrics.
+fn validate_backends(mock_endpoints: &[MockRef<'_>], swanling_metrics: &SwanlingMetrics) {
+    assert!(mock_endpoints[WEB1_KEY].hits() > 0);
+    assert!(mock_endpoints[WEB2_KEY].hits() > 0);
+
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(index_metrics.fail_count == 0);
+    assert!(
+        index_metrics.backend_counts.get(BACKENDS[0]) == Some(&mock_endpoints[WEB1_KEY].hits())
+    );
+    assert!(
+        index_metrics.backend_counts.get(BACKENDS[1]) == Some(&mock_endpoints[WEB2_KEY].hits())
+    );
+}
+
+#[test]
+// Pin users to a backend with a header.
+fn test_sticky_header() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_backend_endpoints(&server, false);
+
+    let swanling_metrics = run_sticky_test(
+        &server,
+        vec!["--sticky-header", STICKY_HEADER],
+        taskset!("LoadTest").register_task(task!(get_index)),
+    );
+
+    validate_backends(&mock_endpoints, &swanling_metrics);
+}
+
+#[test]
+// Pin users to a backend with a cookie, even if the server tries to change it.
+fn test_sticky_cookie() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_backend_endpoints(&server, true);
+    let login_endpoint = server.mock(|when, then| {
+        when.method(GET).path(LOGIN_PATH);
+        then.status(200).header("Set-Cookie", "session=1; Path=/");
+    });
+
+    let swanling_metrics = run_sticky_test(
+        &server,
+        vec!["--sticky-cookie", STICKY_COOKIE],
+        taskset!("LoadTest")
+            .register_task(task!(login).set_on_start())
+            .register_task(task!(get_index)),
+    );
+
+    assert!(login_endpoint.hits() == USERS);
+    validate_backends(&mock_endpoints, &swanling_metrics);
+}
+
+#[test]
+// Disable following redirects for a single request.
+fn test_send_without_redirects() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = [
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        server.mock(|when, then| {
+            when.method(GET).path(LOGIN_PATH);
+            then.status(200);
+        }),
+        server.mock(|when, then| {
+            when.method(GET).path(REDIRECT_PATH);
+            then.status(302).header("Location", INDEX_PATH);
+        }),
+        server.mock(|when, then| {
+            when.method(GET).path(FOLLOW_PATH);
+            then.status(302).header("Location", LOGIN_PATH);
+        }),
+    ];
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(get_redirect))
+                .register_task(task!(get_follow)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm the redirect wasn't followed, but later requests still follow redirects.
+    assert!(mock_endpoints[REDIRECT_KEY].hits() > 0);
+    assert!(mock_endpoints[INDEX_KEY].hits() == 0);
+    assert!(mock_endpoints[FOLLOW_KEY].hits() > 0);
+    assert!(mock_endpoints[LOGIN_KEY].hits() == mock_endpoints[FOLLOW_KEY].hits());
+}
+
+#[test]
+// Confirm --sticky-cookie and --sticky-header can not be set without --sticky-backends.
+fn test_sticky_requires_backends() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for option in &["--sticky-cookie", "--sticky-header"] {
+        let configuration = common::build_configuration(&server, vec![option, "foo"]);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+
+    // And --sticky-backends can not be set without --sticky-cookie or --sticky-header.
+    let configuration = common::build_configuration(&server, vec!["--sticky-backends", "web1"]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
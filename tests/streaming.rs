@@ -0,0 +1,126 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const EVENTS_PATH: &str = "/events";
+const CLOSE_PATH: &str = "/close";
+
+// Indexes to the above paths.
+const EVENTS_KEY: usize = 0;
+const CLOSE_KEY: usize = 1;
+
+// A stream of server-sent events, including a comment and a multi-line event.
+const EVENTS_BODY: &str =
+    ": connected\n\nid: 1\ndata: first\n\nevent: update\r\ndata: second\r\ndata: line\r\n\r\n";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn read_events(user: &SwanlingUser) -> SwanlingTaskResult {
+    let mut stream = user.get_streaming(EVENTS_PATH).await?;
+    assert!(stream.is_event_stream());
+
+    let first = stream.next_event().await?.unwrap();
+    assert_eq!(first.event, "message");
+    assert_eq!(first.data, "first");
+    assert_eq!(first.id.as_deref(), Some("1"));
+
+    let second = stream.next_event().await?.unwrap();
+    assert_eq!(second.event, "update");
+    assert_eq!(second.data, "second\nline");
+    assert_eq!(second.id, None);
+
+    assert!(stream.next_event().await?.is_none());
+    assert_eq!(stream.bytes_received(), EVENTS_BODY.len());
+
+    stream.finish(user).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn close_stream(user: &SwanlingUser) -> SwanlingTaskResult {
+    let request_builder = user.swanling_get(CLOSE_PATH).await?;
+    let stream = user
+        .swanling_send_streaming(request_builder, Some("close"))
+        .await?;
+    stream.close(user)?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up EVENTS_PATH, store in vector at EVENTS_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(EVENTS_PATH);
+            then.status(200)
+                .header("content-type", "text/event-stream")
+                .body(EVENTS_BODY);
+        }),
+        // Next set up CLOSE_PATH, store in vector at CLOSE_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(CLOSE_PATH);
+            then.status(200).body(EVENTS_BODY);
+        }),
+    ]
+}
+
+#[test]
+// Read streams, recording the time to first byte and the total duration separately.
+fn test_streaming() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            // Count all requests, so metrics can be compared to the mock endpoints.
+            "--no-reset-metrics",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(read_events))
+                .register_task(task!(close_stream)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm both the request and the stream were recorded for every request made.
+    for (key, name) in &[(EVENTS_KEY, EVENTS_PATH), (CLOSE_KEY, "close")] {
+        assert!(mock_endpoints[*key].hits() > 0);
+
+        let request_metrics = swanling_metrics
+            .requests
+            .get(&format!("GET {}", name))
+            .unwrap();
+        let stream_metrics = swanling_metrics
+            .requests
+            .get(&format!("GET {} (stream)", name))
+            .unwrap();
+        assert!(request_metrics.success_count == mock_endpoints[*key].hits());
+        assert!(stream_metrics.success_count == mock_endpoints[*key].hits());
+        assert!(stream_metrics.fail_count == 0);
+    }
+}
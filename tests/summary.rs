@@ -0,0 +1,164 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Only display the final metrics, in json format.
+fn test_summary_only() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--summary-only",
+            "--summary-format",
+            "json",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm the load test ran, and the full final metrics were collected.
+    assert!(index.hits() > 0);
+    let index_metrics = swanling_metrics
+        .requests
+        .get(&format!("GET {}", INDEX_PATH))
+        .unwrap();
+    assert!(index_metrics.success_count > 0);
+
+    // Confirm the final metrics can be serialized as a single json document.
+    let summary = serde_json::to_string(&swanling_metrics).unwrap();
+    assert!(!summary.contains('\n'));
+    assert!(summary.starts_with(r#"{"hash":"#));
+}
+
+#[test]
+// Confirm --summary-only can not be set together with running metrics or verbose output.
+fn test_summary_only_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in &[
+        vec!["--summary-only", "--running-metrics", "5"],
+        vec!["--summary-only", "-v"],
+    ] {
+        let configuration = common::build_configuration(&server, options.clone());
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
+
+#[test]
+// Report the configured response time percentiles.
+fn test_percentiles() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--percentiles",
+            "50,90,99.9",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(index.hits() > 0);
+
+    // Confirm the configured percentiles are displayed in the final metrics.
+    let summary = swanling_metrics.to_string();
+    assert!(summary.contains(" |    50% |    90% |  99.9%\n"));
+    assert!(!summary.contains("99.99%"));
+
+    // Confirm the configured percentiles are included in json-formatted metrics.
+    let summary = serde_json::to_value(&swanling_metrics).unwrap();
+    for request in &[format!("GET {}", INDEX_PATH), "Aggregated".to_string()] {
+        let percentiles = summary["percentiles"][request].as_object().unwrap();
+        assert!(percentiles.keys().collect::<Vec<_>>() == vec!["50", "90", "99.9"]);
+        assert!(percentiles.values().all(|time| time.is_u64()));
+    }
+}
+
+#[test]
+// Confirm --percentiles must be a list of valid percentiles.
+fn test_percentiles_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for percentiles in &["0", "50,101", "50,,90", "foo"] {
+        let configuration =
+            common::build_configuration(&server, vec!["--percentiles", percentiles]);
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
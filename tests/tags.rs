@@ -0,0 +1,139 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ERROR_PATH: &str = "/error";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let request_builder = user.swanling_get(INDEX_PATH).await?;
+    let request_builder = SwanlingRequestBuilder::new(request_builder)
+        .tag("tenant", user.weighted_users_index)
+        .tag("variant", "a");
+    let _swanling = user.swanling_send(request_builder, None).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_error(user: &SwanlingUser) -> SwanlingTaskResult {
+    let request_builder = user.swanling_get(ERROR_PATH).await?;
+    let request_builder = SwanlingRequestBuilder::new(request_builder)
+        .tag("tenant", user.weighted_users_index)
+        .tag("variant", "a");
+    let _swanling = user.swanling_send(request_builder, None).await?;
+    Ok(())
+}
+
+#[test]
+// Record request tags in the logs, and summarize errors per tag.
+fn test_tags() {
+    let request_log = "tags-request-log.csv";
+    let error_log = "tags-error-log.json";
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let error = server.mock(|when, then| {
+        when.method(GET).path(ERROR_PATH);
+        then.status(503);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--request-log",
+            request_log,
+            "--request-format",
+            "csv",
+            "--error-log",
+            error_log,
+            "--error-format",
+            "json",
+            "--error-tag",
+            "tenant",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(get_index))
+                .register_task(task!(get_error))
+                .set_wait_time(0, 1)
+                .unwrap(),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm both endpoints were loaded.
+    assert!(index.hits() > 0);
+    assert!(error.hits() > 0);
+
+    // Errors are summarized separately for each tenant, but not for each variant.
+    assert!(swanling_metrics.errors.len() == USERS);
+    for user in 0..USERS {
+        let tenant = user.to_string();
+        let error = swanling_metrics
+            .errors
+            .values()
+            .find(|error| error.tags.get("tenant") == Some(&tenant))
+            .unwrap();
+        assert!(error.name == ERROR_PATH);
+        assert!(error.tags.len() == 1);
+    }
+    assert!(swanling_metrics.to_string().contains("[tenant=0]"));
+
+    // All tags are included in the logs.
+    let request_log_contents = std::fs::read_to_string(request_log).unwrap();
+    assert!(request_log_contents
+        .lines()
+        .next()
+        .unwrap()
+        .ends_with(",tags,user_agent,run_id"));
+    assert!(request_log_contents.contains("\"tenant=0;variant=a\""));
+    let error_log_contents = std::fs::read_to_string(error_log).unwrap();
+    assert!(error_log_contents.contains("\"variant\":\"a\""));
+
+    common::cleanup_files(vec![request_log, error_log]);
+}
+
+#[test]
+// Errors can't be summarized by tag without an error summary.
+fn test_tags_no_error_summary() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration =
+        common::build_configuration(&server, vec!["--error-tag", "tenant", "--no-error-summary"]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
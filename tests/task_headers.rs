@@ -0,0 +1,126 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 1;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_about(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Identify the task and user that made each request with headers.
+fn test_task_headers() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Only requests identifying the task and user succeed.
+    let index = server.mock(|when, then| {
+        when.method(GET)
+            .path(INDEX_PATH)
+            .header("x-swanling-task", "index")
+            .header_exists("x-swanling-user");
+        then.status(200);
+    });
+    let about = server.mock(|when, then| {
+        when.method(GET)
+            .path(ABOUT_PATH)
+            .header("x-swanling-task", "task 1")
+            .header_exists("x-swanling-user");
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+            "--task-headers",
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(get_index).set_name("index"))
+                .register_task(task!(get_about)),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(index.hits() > 0);
+    assert!(about.hits() > 0);
+    for request in swanling_metrics.requests.values() {
+        assert!(request.fail_count == 0);
+    }
+}
+
+#[test]
+// Confirm the headers aren't sent unless enabled.
+fn test_task_headers_disabled() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server, requests identifying
+    // the task match the first one.
+    let identified = server.mock(|when, then| {
+        when.method(GET)
+            .path(INDEX_PATH)
+            .header_exists("x-swanling-task");
+        then.status(500);
+    });
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index).set_name("index")),
+            None,
+            None,
+        ),
+        None,
+    );
+    assert!(index.hits() > 0);
+    assert!(identified.hits() == 0);
+}
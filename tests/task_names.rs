@@ -0,0 +1,143 @@
+use httpmock::{Method::GET, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// Names of the task sets and tasks in these tests.
+const WEBSITE: &str = "Website";
+const API: &str = "Api";
+const INDEX: &str = "index";
+const ABOUT: &str = "about";
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 1;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_about(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Confirm the registered names can be asserted against, and the load test runs them.
+fn test_task_names() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let index = server.mock(|when, then| {
+        when.method(GET).path(INDEX_PATH);
+        then.status(200);
+    });
+    let about = server.mock(|when, then| {
+        when.method(GET).path(ABOUT_PATH);
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!(WEBSITE)
+            .register_task(task!(get_index).set_name(INDEX))
+            .register_task(task!(get_about).set_name(ABOUT))
+            .register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(
+        swanling_attack.task_names()
+            == vec![(
+                WEBSITE.to_string(),
+                vec![INDEX.to_string(), ABOUT.to_string(), "".to_string()]
+            )]
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(swanling_attack, None);
+    assert!(index.hits() > 0);
+    assert!(about.hits() > 0);
+
+    // Requests made by named tasks are named after them.
+    assert!(swanling_metrics
+        .requests
+        .contains_key(&format!("GET {}", INDEX)));
+    assert!(swanling_metrics
+        .requests
+        .contains_key(&format!("GET {}", ABOUT)));
+}
+
+#[test]
+// Confirm duplicate names are rejected before the load test starts.
+fn test_task_names_duplicate() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Task sets must have unique names.
+    let configuration = common::build_configuration(&server, vec![]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!(WEBSITE).register_task(task!(get_index)),
+        None,
+        None,
+    )
+    .register_taskset(taskset!(WEBSITE).register_task(task!(get_about)));
+    assert!(swanling_attack.execute().is_err());
+
+    // Tasks must have unique names within their task set.
+    let configuration = common::build_configuration(&server, vec![]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!(WEBSITE)
+            .register_task(task!(get_index).set_name(INDEX))
+            .register_task(task!(get_about).set_name(INDEX)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+
+    // Tasks must also have unique names across task sets.
+    let configuration = common::build_configuration(&server, vec![]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!(WEBSITE).register_task(task!(get_index).set_name(INDEX)),
+        None,
+        None,
+    )
+    .register_taskset(taskset!(API).register_task(task!(get_index).set_name(INDEX)));
+    assert!(swanling_attack.execute().is_err());
+
+    // Tasks in different task sets can have different names.
+    let configuration = common::build_configuration(&server, vec!["--dry-run"]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!(WEBSITE).register_task(task!(get_index).set_name(INDEX)),
+        None,
+        None,
+    )
+    .register_taskset(taskset!(API).register_task(task!(get_about).set_name(ABOUT)));
+    assert!(swanling_attack.execute().is_ok());
+}
@@ -220,6 +220,137 @@ fn test_throttle() {
     );
 }
 
+#[test]
+// Confirm a warning is annotated when requests are constantly waiting for the throttle,
+// as the throttle rather than the target is limiting the load test.
+fn test_throttle_saturation() {
+    // Multiple tests run together, so set a unique name.
+    let request_log = "saturation-".to_string() + REQUEST_LOG;
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration, allowing far fewer requests than the users try to make for
+    // long enough to detect it.
+    let configuration = common_build_configuration(&server, &request_log, 1, USERS, 8, None, None);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+    common::cleanup_files(vec![&request_log]);
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    // Confirm the saturated throttle was annotated.
+    assert!(swanling_metrics
+        .annotations
+        .iter()
+        .any(|annotation| annotation
+            .text
+            .starts_with("load generator may be saturated (throttle)")));
+}
+
+#[test]
+// Confirm high priority tasks jump the queue of requests waiting for the throttle.
+fn test_throttle_priority() {
+    // Multiple tests run together, so set a unique name.
+    let request_log = "priority-".to_string() + REQUEST_LOG;
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Build configuration, allowing far fewer requests than the users try to make.
+    let configuration =
+        common_build_configuration(&server, &request_log, 5, USERS * 2, RUN_TIME, None, None);
+
+    // Run the Swanling Attack, with requests for the about page jumping the queue.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(get_index).set_weight(3).unwrap())
+                .register_task(task!(get_about).set_throttle_priority()),
+            None,
+            None,
+        ),
+        None,
+    );
+    common::cleanup_files(vec![&request_log]);
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ABOUT_KEY].hits() > 0);
+
+    // Tasks include the time waiting for the throttle, which is shorter for high priority
+    // requests.
+    let average_time = |task: &swanling::metrics::SwanlingTaskMetricAggregate| {
+        task.total_time as f32 / task.success_count.max(1) as f32
+    };
+    let tasks = &swanling_metrics.tasks[0];
+    assert!(average_time(&tasks[1]) < average_time(&tasks[0]));
+}
+
+#[test]
+// Confirm the throttle follows a plan, allowing more requests per second in its last step.
+fn test_throttle_plan() {
+    // Multiple tests run together, so set a unique name.
+    let request_log = "plan-".to_string() + REQUEST_LOG;
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Allow 5 requests per second for 2 seconds, then 50 requests per second.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &(RUN_TIME + 1).to_string(),
+            "--throttle-plan",
+            "5:2s,50",
+            "--request-log",
+            &request_log,
+        ],
+    );
+
+    // Run the Swanling Attack.
+    common::run_load_test(
+        common::build_load_test(configuration, &get_tasks(), None, None),
+        None,
+    );
+    let requests = common::file_length(&request_log);
+    common::cleanup_files(vec![&request_log]);
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    // More requests were made than the first step allows, but no more than the plan allows
+    // while SwanlingUsers are hatched, and then for RUN_TIME + 1 seconds.
+    assert!(requests > (RUN_TIME + 2) * 5);
+    assert!(requests <= 3 * 5 + (RUN_TIME + 1) * 50);
+
+    // Only the last step can last until the load test stops, and rates must be numbers.
+    for plan in &["5,50", "0:2s,50", "5:2s,50", "fast"] {
+        let mut options = vec!["--throttle-plan", plan];
+        // The throttle can't be configured twice.
+        if *plan == "5:2s,50" {
+            options.extend(vec!["--throttle-requests", "5"]);
+        }
+        let configuration = common::build_configuration(&server, options);
+        let swanling_attack = common::build_load_test(configuration, &get_tasks(), None, None);
+        assert!(swanling_attack.execute().is_err());
+    }
+}
+
 #[test]
 #[cfg_attr(not(feature = "gaggle"), ignore)]
 // Enable throttle to confirm it limits the number of request per second, in
@@ -0,0 +1,52 @@
+use httpmock::MockServer;
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Confirm --tui can not be set together with options that display metrics differently.
+fn test_tui_conflicts() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in &[
+        vec!["--tui", "--running-metrics", "5"],
+        vec!["--tui", "--no-metrics"],
+    ] {
+        let configuration = common::build_configuration(&server, options.clone());
+        let swanling_attack = common::build_load_test(
+            configuration,
+            &taskset!("LoadTest").register_task(task!(get_index)),
+            None,
+            None,
+        );
+        assert!(swanling_attack.execute().is_err());
+    }
+}
+
+#[cfg(not(feature = "tui"))]
+#[test]
+// Confirm --tui can not be set unless Swanling is compiled with the tui feature.
+fn test_tui_feature_not_enabled() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    let configuration = common::build_configuration(&server, vec!["--tui"]);
+    let swanling_attack = common::build_load_test(
+        configuration,
+        &taskset!("LoadTest").register_task(task!(get_index)),
+        None,
+        None,
+    );
+    assert!(swanling_attack.execute().is_err());
+}
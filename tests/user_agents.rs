@@ -0,0 +1,150 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+use swanling::util;
+use swanling::SwanlingConfiguration;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// User agents the users pick from.
+const DESKTOP_AGENT: &str = util::USER_AGENTS[0];
+const MOBILE_AGENT: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 14_6 like Mac OS X) Mobile/15E148";
+
+// Load test configuration.
+const USERS: usize = 6;
+const RUN_TIME: usize = 2;
+
+// Test task.
+pub async fn get_index(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against the following common endpoints, which only match
+// requests sent with one of the user agents.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header("User-Agent", DESKTOP_AGENT);
+            then.status(200);
+        }),
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(INDEX_PATH)
+                .header("User-Agent", MOBILE_AGENT);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build the configuration used by these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> SwanlingConfiguration {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+        "--seed",
+        "42",
+        "--no-reset-metrics",
+    ];
+    configuration.extend(custom);
+    common::build_configuration(server, configuration)
+}
+
+// Confirm every request was sent with one of the user agents, and summarized by user agent.
+fn validate_user_agents(swanling_metrics: &SwanlingMetrics, mock_endpoints: &[MockRef]) {
+    let desktop_hits = mock_endpoints[0].hits();
+    let mobile_hits = mock_endpoints[1].hits();
+    assert!(desktop_hits + mobile_hits > 0);
+
+    let index = swanling_metrics.requests.get("GET /").unwrap();
+    assert!(index.fail_count == 0);
+    assert!(index.user_agent_counts.values().sum::<usize>() == desktop_hits + mobile_hits);
+    if desktop_hits > 0 {
+        assert!(index.user_agent_counts[DESKTOP_AGENT] == desktop_hits);
+    }
+    if mobile_hits > 0 {
+        assert!(index.user_agent_counts[MOBILE_AGENT] == mobile_hits);
+    }
+    assert!(swanling_metrics.to_string().contains("User agent"));
+}
+
+// Run a load test with the task set, returning its metrics.
+fn run_load_test(
+    configuration: SwanlingConfiguration,
+    task_set: &SwanlingTaskSet,
+) -> SwanlingMetrics {
+    common::run_load_test(
+        common::build_load_test(configuration, task_set, None, None),
+        None,
+    )
+}
+
+#[test]
+// Users pick a user agent from the pool of their task set.
+fn test_set_user_agents() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = run_load_test(
+        common_build_configuration(&server, vec![]),
+        &taskset!("LoadTest")
+            .register_task(task!(get_index))
+            .set_wait_time(0, 1)
+            .unwrap()
+            .set_user_agents(vec![DESKTOP_AGENT, MOBILE_AGENT])
+            .unwrap(),
+    );
+
+    validate_user_agents(&swanling_metrics, &mock_endpoints);
+
+    // Invalid user agents are rejected.
+    assert!(taskset!("LoadTest").set_user_agents(vec![""]).is_err());
+    assert!(taskset!("LoadTest")
+        .set_user_agents(vec!["Mozilla\n5.0"])
+        .is_err());
+}
+
+#[test]
+// Users pick a user agent from --user-agent-file.
+fn test_user_agent_file() {
+    let user_agent_file = "user-agents.txt";
+    std::fs::write(
+        user_agent_file,
+        format!("{}\n\n{}\n", DESKTOP_AGENT, MOBILE_AGENT),
+    )
+    .unwrap();
+
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Swanling Attack.
+    let swanling_metrics = run_load_test(
+        common_build_configuration(&server, vec!["--user-agent-file", user_agent_file]),
+        &taskset!("LoadTest")
+            .register_task(task!(get_index))
+            .set_wait_time(0, 1)
+            .unwrap(),
+    );
+
+    validate_user_agents(&swanling_metrics, &mock_endpoints);
+
+    common::cleanup_files(vec![user_agent_file]);
+}
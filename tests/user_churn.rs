@@ -0,0 +1,145 @@
+use httpmock::{Method::GET, MockRef, MockServer};
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const LOGIN_PATH: &str = "/login";
+const PAGE_PATH: &str = "/page";
+const LOGOUT_PATH: &str = "/logout";
+
+// Indexes to the above paths.
+const LOGIN_KEY: usize = 0;
+const PAGE_KEY: usize = 1;
+const LOGOUT_KEY: usize = 2;
+
+// Load test configuration.
+const USERS: usize = 2;
+const RUN_TIME: usize = 4;
+
+// Test session function.
+pub async fn login(user: &mut SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(LOGIN_PATH).await?;
+    Ok(())
+}
+
+// Test session function.
+pub async fn logout(user: &mut SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(LOGOUT_PATH).await?;
+    Ok(())
+}
+
+// Test task.
+pub async fn get_page(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(PAGE_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<MockRef<'_>> {
+    vec![
+        // First set up LOGIN_PATH, store in vector at LOGIN_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(LOGIN_PATH);
+            then.status(200);
+        }),
+        // Next set up PAGE_PATH, store in vector at PAGE_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(PAGE_PATH);
+            then.status(200);
+        }),
+        // Finally set up LOGOUT_PATH, store in vector at LOGOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(LOGOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build a load test with the specified options.
+fn build_user_churn_test(server: &MockServer, options: Vec<&str>) -> SwanlingAttack {
+    let users = USERS.to_string();
+    let run_time = RUN_TIME.to_string();
+    let mut configuration = vec![
+        "--users",
+        &users,
+        "--hatch-rate",
+        &users,
+        "--run-time",
+        &run_time,
+    ];
+    configuration.extend(options);
+
+    common::build_load_test(
+        common::build_configuration(server, configuration),
+        &taskset!("LoadTest")
+            .register_on_start_session(|user| Box::pin(login(user)))
+            .register_on_stop_session(|user| Box::pin(logout(user)))
+            .register_task(task!(get_page))
+            .set_wait_time(0, 1)
+            .unwrap(),
+        None,
+        None,
+    )
+}
+
+#[test]
+// Running users are retired and replaced with fresh users.
+fn test_user_churn() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Retire 2 users per second.
+    let swanling_metrics = common::run_load_test(
+        build_user_churn_test(&server, vec!["--user-churn", "2/s"]),
+        None,
+    );
+
+    // Confirm retired users logged out, and fresh users logged in again.
+    assert!(mock_endpoints[LOGIN_KEY].hits() > USERS);
+    assert!(mock_endpoints[PAGE_KEY].hits() > 0);
+    assert!(mock_endpoints[LOGOUT_KEY].hits() > USERS);
+    assert!(mock_endpoints[LOGIN_KEY].hits() == mock_endpoints[LOGOUT_KEY].hits());
+    assert!(swanling_metrics.duration == RUN_TIME);
+    assert!(swanling_metrics.removed_users.is_empty());
+}
+
+#[test]
+// Without --user-churn, users only log in and out once.
+fn test_no_user_churn() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    common::run_load_test(build_user_churn_test(&server, vec![]), None);
+
+    // Confirm each user logged in and out once.
+    assert!(mock_endpoints[LOGIN_KEY].hits() == USERS);
+    assert!(mock_endpoints[LOGOUT_KEY].hits() == USERS);
+}
+
+#[test]
+// Confirm --user-churn must be valid.
+fn test_user_churn_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    for options in [
+        // A time is required.
+        vec!["--user-churn", "5"],
+        // The number of users must be positive.
+        vec!["--user-churn", "0/min"],
+        // The time can't be empty.
+        vec!["--user-churn", "5/0s"],
+        // The time must be valid.
+        vec!["--user-churn", "5/often"],
+    ] {
+        assert!(build_user_churn_test(&server, options).execute().is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use httpmock::{
+    Method::{GET, POST},
+    MockServer,
+};
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+mod common;
+
+use swanling::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const CART_PATH: &str = "/api/users/{user_id}/{uuid}/cart";
+const VIEW_CART_PATH: &str = "/api/users/{uuid}/cart";
+
+// The cart path once rendered with a user index and a version 4 UUID.
+const CART_REGEX: &str =
+    r"^/api/users/\d+/[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}/cart$";
+const VIEW_CART_REGEX: &str =
+    r"^/api/users/[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}/cart$";
+
+// Load test configuration.
+const USERS: usize = 3;
+const RUN_TIME: usize = 2;
+
+lazy_static::lazy_static! {
+    // The rendered cart paths requested by all users.
+    static ref CART_URLS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+// Test task.
+pub async fn add_to_cart(user: &SwanlingUser) -> SwanlingTaskResult {
+    let body = user.render_template(r#"{"owner":"{uuid}"}"#);
+    let swanling = user.post(CART_PATH, &body).await?;
+    CART_URLS.lock().unwrap().insert(swanling.request.url);
+    Ok(())
+}
+
+// Test task.
+pub async fn get_cart(user: &SwanlingUser) -> SwanlingTaskResult {
+    let _swanling = user.get(VIEW_CART_PATH).await?;
+    Ok(())
+}
+
+#[test]
+// Each user fills in its own index and UUID.
+fn test_user_identity() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoint needed for this test on the mock server, it only matches paths
+    // and bodies with a valid user index and UUID.
+    let cart = server.mock(|when, then| {
+        when.method(POST)
+            .path_matches(regex::Regex::new(CART_REGEX).unwrap())
+            .body_contains(r#"{"owner":""#);
+        then.status(200);
+    });
+    let view_cart = server.mock(|when, then| {
+        when.method(GET)
+            .path_matches(regex::Regex::new(VIEW_CART_REGEX).unwrap());
+        then.status(200);
+    });
+
+    // Build configuration.
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            &RUN_TIME.to_string(),
+        ],
+    );
+
+    // Run the Swanling Attack.
+    let swanling_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            &taskset!("LoadTest")
+                .register_task(task!(add_to_cart))
+                .register_task(task!(get_cart))
+                .set_wait_time(0, 1)
+                .unwrap(),
+            None,
+            None,
+        ),
+        None,
+    );
+
+    // Confirm the endpoints were loaded, and every request matched them.
+    assert!(cart.hits() > 0);
+    assert!(view_cart.hits() > 0);
+    assert!(swanling_metrics
+        .requests
+        .values()
+        .all(|request| request.fail_count == 0));
+
+    // Unnamed requests are named with their path before it was rendered, so all users are
+    // tracked together.
+    assert!(swanling_metrics.requests.len() == 2);
+    assert!(swanling_metrics
+        .requests
+        .contains_key(&format!("POST {}", CART_PATH)));
+    assert!(swanling_metrics
+        .requests
+        .contains_key(&format!("GET {}", VIEW_CART_PATH)));
+
+    // Each user requested the cart with its own index and UUID.
+    let mut uuids = Vec::new();
+    for url in CART_URLS.lock().unwrap().iter() {
+        uuids.push(url.rsplit('/').nth(1).unwrap().to_string());
+    }
+    uuids.sort();
+    uuids.dedup();
+    assert!(uuids.len() == USERS);
+}